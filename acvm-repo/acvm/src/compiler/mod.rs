@@ -10,8 +10,8 @@ use crate::Language;
 mod optimizers;
 mod transformers;
 
-pub use optimizers::optimize;
 use optimizers::optimize_internal;
+pub use optimizers::{optimize, optimize_with_report};
 pub use transformers::transform;
 use transformers::transform_internal;
 
@@ -74,7 +74,8 @@ pub fn compile(
     np_language: Language,
     is_opcode_supported: impl Fn(&Opcode) -> bool,
 ) -> Result<(Circuit, AcirTransformationMap), CompileError> {
-    let (acir, AcirTransformationMap { acir_opcode_positions }) = optimize_internal(acir);
+    let (acir, AcirTransformationMap { acir_opcode_positions }) =
+        optimize_internal(acir, &mut |_, _| ());
 
     let (mut acir, transformation_map) =
         transform_internal(acir, np_language, is_opcode_supported, acir_opcode_positions)?;
@@ -0,0 +1,331 @@
+use acir::{
+    circuit::{Circuit, Opcode},
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// The minimum number of opcodes which must share a quadratic term before it is worth
+/// introducing a new witness for it.
+const MIN_SHARED_USES: usize = 2;
+
+/// `CommonSubtermOptimizer` will replace quadratic terms which are repeated across
+/// [`Opcode::Arithmetic`]s with a single witness.
+///
+/// # Example
+///
+/// Suppose we had the following opcodes:
+///
+/// ```text
+/// x*y + z - a = 0
+/// 2*x*y - b = 0
+/// x*y + 3 - c = 0
+/// ```
+/// The product `x*y` is computed by all three opcodes. This optimization pass will
+/// introduce a new witness `w` for it once and rewrite each use in terms of `w`:
+///
+/// ```text
+/// x*y - w = 0
+/// w + z - a = 0
+/// 2*w - b = 0
+/// w + 3 - c = 0
+/// ```
+/// If an opcode which already defines a witness as the product, such as `x*y - w = 0`,
+/// exists, later uses are rewritten in terms of that witness instead and no new witness is needed.
+///
+/// A use is only rewritten if both witnesses of the product are known by the time its
+/// opcode is solved, so that the opcode does not lose the witness it would solve for.
+pub(crate) struct CommonSubtermOptimizer {
+    circuit: Circuit,
+}
+
+/// An existing opcode of the form `s*x*y + t*w = 0`, which defines `x*y` as `-t/s * w`.
+struct Definition {
+    opcode_index: usize,
+    witness: Witness,
+    scale: FieldElement,
+}
+
+impl CommonSubtermOptimizer {
+    pub(crate) fn new(circuit: Circuit) -> Self {
+        Self { circuit }
+    }
+
+    /// Returns a `Circuit` where quadratic terms shared between opcodes are replaced by
+    /// a single witness, along with the updated `order_list`.
+    pub(crate) fn extract_common_subterms(self, order_list: Vec<usize>) -> (Circuit, Vec<usize>) {
+        let mut circuit = self.circuit;
+        let known_from = Self::collect_known_witnesses(&circuit);
+
+        let mut definitions: BTreeMap<(Witness, Witness), Definition> = BTreeMap::new();
+        let mut uses: BTreeMap<(Witness, Witness), Vec<usize>> = BTreeMap::new();
+
+        for (index, opcode) in circuit.opcodes.iter().enumerate() {
+            let Opcode::Arithmetic(expr) = opcode else { continue };
+
+            if let Some((term, definition)) = as_definition(index, expr) {
+                definitions.entry(term).or_insert(definition);
+                continue;
+            }
+            for (_, w_l, w_r) in &expr.mul_terms {
+                uses.entry(ordered(*w_l, *w_r)).or_default().push(index);
+            }
+        }
+
+        // Opcodes defining new witnesses for shared terms, keyed by the opcode they must precede
+        let mut new_definitions: BTreeMap<usize, Vec<Opcode>> = BTreeMap::new();
+
+        for (term, term_uses) in uses {
+            let (w_l, w_r) = term;
+            let (Some(l_known), Some(r_known)) = (known_from.get(&w_l), known_from.get(&w_r))
+            else {
+                continue;
+            };
+            let known_from = *l_known.max(r_known);
+
+            let (witness, scale, eligible_uses) = match definitions.get(&term) {
+                Some(definition) => {
+                    let eligible_uses: Vec<_> = term_uses
+                        .into_iter()
+                        .filter(|index| *index > definition.opcode_index)
+                        .collect();
+                    (definition.witness, definition.scale, eligible_uses)
+                }
+                None => {
+                    let eligible_uses: Vec<_> =
+                        term_uses.into_iter().filter(|index| *index >= known_from).collect();
+                    if eligible_uses.len() < MIN_SHARED_USES {
+                        continue;
+                    }
+
+                    circuit.current_witness_index += 1;
+                    let witness = Witness(circuit.current_witness_index);
+                    let definition = Expression {
+                        mul_terms: vec![(FieldElement::one(), w_l, w_r)],
+                        linear_combinations: vec![(-FieldElement::one(), witness)],
+                        q_c: FieldElement::zero(),
+                    };
+                    new_definitions
+                        .entry(eligible_uses[0])
+                        .or_default()
+                        .push(Opcode::Arithmetic(definition));
+                    (witness, FieldElement::one(), eligible_uses)
+                }
+            };
+
+            for index in eligible_uses {
+                if let Opcode::Arithmetic(expr) = &mut circuit.opcodes[index] {
+                    replace_mul_term(expr, term, witness, scale);
+                }
+            }
+        }
+
+        let mut new_order_list = Vec::with_capacity(order_list.len() + new_definitions.len());
+        let mut optimized_opcodes = Vec::with_capacity(circuit.opcodes.len());
+        for (index, opcode) in circuit.opcodes.into_iter().enumerate() {
+            for definition in new_definitions.remove(&index).unwrap_or_default() {
+                new_order_list.push(order_list[index]);
+                optimized_opcodes.push(definition);
+            }
+            new_order_list.push(order_list[index]);
+            optimized_opcodes.push(opcode);
+        }
+
+        (Circuit { opcodes: optimized_opcodes, ..circuit }, new_order_list)
+    }
+
+    /// Maps each witness to the index of the first opcode which is solved after it is known.
+    ///
+    /// Parameters are known from the start. Any other witness is conservatively assumed to
+    /// only be known once an [`Opcode::Arithmetic`] containing it has been solved.
+    fn collect_known_witnesses(circuit: &Circuit) -> HashMap<Witness, usize> {
+        let mut known_from: HashMap<Witness, usize> = HashMap::new();
+
+        for witness in circuit.private_parameters.iter().chain(&circuit.public_parameters.0) {
+            known_from.insert(*witness, 0);
+        }
+
+        for (index, opcode) in circuit.opcodes.iter().enumerate() {
+            let Opcode::Arithmetic(expr) = opcode else { continue };
+
+            let mul_witnesses = expr.mul_terms.iter().flat_map(|(_, w_l, w_r)| [*w_l, *w_r]);
+            let linear_witnesses = expr.linear_combinations.iter().map(|(_, witness)| *witness);
+            for witness in mul_witnesses.chain(linear_witnesses) {
+                known_from.entry(witness).or_insert(index + 1);
+            }
+        }
+        known_from
+    }
+}
+
+/// Returns the quadratic term defined by `expr` if it is of the form `s*x*y + t*w = 0`.
+fn as_definition(
+    opcode_index: usize,
+    expr: &Expression,
+) -> Option<((Witness, Witness), Definition)> {
+    match (expr.mul_terms.as_slice(), expr.linear_combinations.as_slice()) {
+        ([(s, w_l, w_r)], [(t, witness)])
+            if expr.q_c.is_zero()
+                && !s.is_zero()
+                && !t.is_zero()
+                && witness != w_l
+                && witness != w_r =>
+        {
+            let scale = -(*t / *s);
+            Some((ordered(*w_l, *w_r), Definition { opcode_index, witness: *witness, scale }))
+        }
+        _ => None,
+    }
+}
+
+/// Replaces the quadratic term `term` in `expr` with `scale * witness`.
+fn replace_mul_term(
+    expr: &mut Expression,
+    term: (Witness, Witness),
+    witness: Witness,
+    scale: FieldElement,
+) {
+    let Some(position) =
+        expr.mul_terms.iter().position(|(_, w_l, w_r)| ordered(*w_l, *w_r) == term)
+    else {
+        return;
+    };
+    let (coefficient, _, _) = expr.mul_terms.remove(position);
+    let coefficient = coefficient * scale;
+
+    match expr.linear_combinations.iter_mut().find(|(_, existing)| *existing == witness) {
+        Some((existing_coefficient, _)) => *existing_coefficient += coefficient,
+        None => expr.linear_combinations.push((coefficient, witness)),
+    }
+    expr.linear_combinations.retain(|(coefficient, _)| !coefficient.is_zero());
+}
+
+fn ordered(w_l: Witness, w_r: Witness) -> (Witness, Witness) {
+    if w_l <= w_r {
+        (w_l, w_r)
+    } else {
+        (w_r, w_l)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::compiler::optimizers::common_subterm::CommonSubtermOptimizer;
+    use acir::{
+        circuit::{Circuit, Opcode, PublicInputs},
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    fn test_circuit(opcodes: Vec<Opcode>, current_witness_index: u32) -> Circuit {
+        Circuit {
+            current_witness_index,
+            opcodes,
+            private_parameters: BTreeSet::from([Witness(1), Witness(2), Witness(3)]),
+            public_parameters: PublicInputs::default(),
+            return_values: PublicInputs::default(),
+            assert_messages: Default::default(),
+        }
+    }
+
+    /// Builds the opcode `scale*x*y + extra - output = 0`
+    fn product_opcode(scale: u128, extra: Witness, output: Witness) -> Opcode {
+        Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::from(scale), Witness(1), Witness(2))],
+            linear_combinations: vec![(FieldElement::one(), extra), (-FieldElement::one(), output)],
+            q_c: FieldElement::zero(),
+        })
+    }
+
+    #[test]
+    fn extracts_shared_product() {
+        // x1*x2 + x3 - x4 = 0
+        // 2*x2*x1 + x3 - x5 = 0
+        // x1*x2 + x3 - x6 = 0
+        let mut opcodes = vec![
+            product_opcode(1, Witness(3), Witness(4)),
+            product_opcode(2, Witness(3), Witness(5)),
+        ];
+        if let Opcode::Arithmetic(expr) = &mut opcodes[1] {
+            expr.mul_terms[0] = (FieldElement::from(2u128), Witness(2), Witness(1));
+        }
+        opcodes.push(product_opcode(1, Witness(3), Witness(6)));
+
+        let circuit = test_circuit(opcodes, 6);
+        let acir_opcode_positions = vec![0, 1, 2];
+        let optimizer = CommonSubtermOptimizer::new(circuit);
+        let (optimized_circuit, new_positions) =
+            optimizer.extract_common_subterms(acir_opcode_positions);
+
+        // x1*x2 - x7 = 0 is inserted before the first use and takes its position
+        assert_eq!(optimized_circuit.opcodes.len(), 4);
+        assert_eq!(optimized_circuit.current_witness_index, 7);
+        assert_eq!(new_positions, vec![0, 0, 1, 2]);
+
+        let Opcode::Arithmetic(definition) = &optimized_circuit.opcodes[0] else {
+            panic!("Expected an arithmetic opcode");
+        };
+        assert_eq!(definition.mul_terms, vec![(FieldElement::one(), Witness(1), Witness(2))]);
+        assert_eq!(definition.linear_combinations, vec![(-FieldElement::one(), Witness(7))]);
+
+        for (opcode, scale) in optimized_circuit.opcodes[1..].iter().zip([1u128, 2, 1]) {
+            let Opcode::Arithmetic(expr) = opcode else {
+                panic!("Expected an arithmetic opcode");
+            };
+            assert!(expr.mul_terms.is_empty());
+            assert!(expr.linear_combinations.contains(&(FieldElement::from(scale), Witness(7))));
+        }
+    }
+
+    #[test]
+    fn reuses_existing_definition() {
+        // x1*x2 - x4 = 0
+        // x1*x2 + x3 - x5 = 0
+        let definition = Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(4))],
+            q_c: FieldElement::zero(),
+        });
+        let circuit = test_circuit(vec![definition, product_opcode(1, Witness(3), Witness(5))], 5);
+        let optimizer = CommonSubtermOptimizer::new(circuit);
+        let (optimized_circuit, new_positions) = optimizer.extract_common_subterms(vec![0, 1]);
+
+        // No witness is added and the use is rewritten as x4 + x3 - x5 = 0
+        assert_eq!(optimized_circuit.opcodes.len(), 2);
+        assert_eq!(optimized_circuit.current_witness_index, 5);
+        assert_eq!(new_positions, vec![0, 1]);
+
+        let Opcode::Arithmetic(expr) = &optimized_circuit.opcodes[1] else {
+            panic!("Expected an arithmetic opcode");
+        };
+        assert!(expr.mul_terms.is_empty());
+        assert!(expr.linear_combinations.contains(&(FieldElement::one(), Witness(4))));
+    }
+
+    #[test]
+    fn does_not_rewrite_opcode_solving_for_operand() {
+        // x1*x8 - x3 = 0, which solves for x8 as it is unknown
+        // x1*x8 + x3 - x5 = 0
+        let solve_operand = Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(8))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+            q_c: FieldElement::zero(),
+        });
+        let use_operand = Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(8))],
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(3)),
+                (-FieldElement::one(), Witness(5)),
+            ],
+            q_c: FieldElement::zero(),
+        });
+        let circuit = test_circuit(vec![solve_operand, use_operand], 8);
+        let optimizer = CommonSubtermOptimizer::new(circuit.clone());
+        let (optimized_circuit, _) = optimizer.extract_common_subterms(vec![0, 1]);
+
+        // Only one use comes after x8 is known, which is not worth a new witness
+        assert_eq!(optimized_circuit, circuit);
+    }
+}
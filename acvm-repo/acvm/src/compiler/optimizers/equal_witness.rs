@@ -0,0 +1,378 @@
+use acir::{
+    circuit::{
+        brillig::{BrilligInputs, BrilligOutputs},
+        directives::{Directive, QuotientDirective},
+        opcodes::MemOp,
+        Circuit, Opcode,
+    },
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// `EqualWitnessOptimizer` will merge witnesses which are constrained to be equal by an
+/// [`Opcode::Arithmetic`] of the form `k*a - k*b = 0`, reducing the number of witnesses
+/// the backend needs to allocate.
+///
+/// # Example
+///
+/// Suppose we had the following opcodes:
+///
+/// ```text
+/// x1*x2 - x4 = 0
+/// x4 - x5 = 0
+/// x5 + x3 - x6 = 0
+/// ```
+/// The second opcode only states that `x4` and `x5` are equal. This optimization pass removes it
+/// and uses `x4` in place of `x5` everywhere:
+///
+/// ```text
+/// x1*x2 - x4 = 0
+/// x4 + x3 - x6 = 0
+/// ```
+/// Chains of equalities are merged into a single witness. Witnesses which are parameters or
+/// return values of the circuit, or which are used by black box functions, are never replaced.
+/// If two such witnesses are constrained to be equal, the opcode constraining them is kept.
+pub(crate) struct EqualWitnessOptimizer {
+    circuit: Circuit,
+}
+
+impl EqualWitnessOptimizer {
+    pub(crate) fn new(circuit: Circuit) -> Self {
+        Self { circuit }
+    }
+
+    /// Returns a `Circuit` where witnesses constrained to be equal are merged, along with the
+    /// updated `order_list`.
+    pub(crate) fn merge_equal_witnesses(self, order_list: Vec<usize>) -> (Circuit, Vec<usize>) {
+        let circuit = self.circuit;
+        let pinned = Self::collect_pinned_witnesses(&circuit);
+        let mut merged = MergedWitnesses { representatives: HashMap::new(), pinned };
+
+        let mut removed_opcodes = BTreeSet::new();
+        for (index, opcode) in circuit.opcodes.iter().enumerate() {
+            let Opcode::Arithmetic(expr) = opcode else { continue };
+            let Some((a, b)) = as_equality(expr) else { continue };
+
+            if merged.merge(a, b) {
+                removed_opcodes.insert(index);
+            }
+        }
+
+        if removed_opcodes.is_empty() {
+            return (circuit, order_list);
+        }
+
+        let mut new_order_list = Vec::with_capacity(order_list.len() - removed_opcodes.len());
+        let mut optimized_opcodes = Vec::with_capacity(new_order_list.capacity());
+        for (index, opcode) in circuit.opcodes.into_iter().enumerate() {
+            if removed_opcodes.contains(&index) {
+                continue;
+            }
+            let opcode = merged.substitute_opcode(opcode);
+
+            // Merging may leave an opcode which only restates an equality which has been removed
+            if matches!(&opcode, Opcode::Arithmetic(expr) if expr.is_zero()) {
+                continue;
+            }
+            new_order_list.push(order_list[index]);
+            optimized_opcodes.push(opcode);
+        }
+
+        (Circuit { opcodes: optimized_opcodes, ..circuit }, new_order_list)
+    }
+
+    /// Returns the witnesses which must not be replaced by another witness.
+    ///
+    /// Parameters and return values are part of the circuit's interface, and black box function
+    /// calls are left untouched so their witnesses cannot be rewritten.
+    fn collect_pinned_witnesses(circuit: &Circuit) -> BTreeSet<Witness> {
+        let mut pinned = circuit.circuit_arguments();
+        pinned.extend(circuit.return_values.0.iter().copied());
+
+        for opcode in &circuit.opcodes {
+            if let Opcode::BlackBoxFuncCall(call) = opcode {
+                pinned.extend(call.get_inputs_vec().into_iter().map(|input| input.witness));
+                pinned.extend(call.get_outputs_vec());
+            }
+        }
+        pinned
+    }
+}
+
+/// Returns the two witnesses `expr` constrains to be equal if it is of the form `k*a - k*b = 0`.
+fn as_equality(expr: &Expression) -> Option<(Witness, Witness)> {
+    match expr.linear_combinations.as_slice() {
+        [(k_a, a), (k_b, b)]
+            if expr.mul_terms.is_empty()
+                && expr.q_c.is_zero()
+                && !k_a.is_zero()
+                && *k_a == -*k_b
+                && a != b =>
+        {
+            Some((*a, *b))
+        }
+        _ => None,
+    }
+}
+
+/// A union-find over witnesses, where each set of equal witnesses is represented by a pinned
+/// witness if it contains one, or by its lowest witness otherwise.
+struct MergedWitnesses {
+    representatives: HashMap<Witness, Witness>,
+    pinned: BTreeSet<Witness>,
+}
+
+impl MergedWitnesses {
+    fn find(&self, mut witness: Witness) -> Witness {
+        while let Some(representative) = self.representatives.get(&witness) {
+            witness = *representative;
+        }
+        witness
+    }
+
+    /// Merges the sets containing `a` and `b`, returning false if they cannot be merged as both
+    /// are represented by a pinned witness.
+    fn merge(&mut self, a: Witness, b: Witness) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return true;
+        }
+
+        let (representative, replaced) = match (self.pinned.contains(&a), self.pinned.contains(&b))
+        {
+            (true, true) => return false,
+            (true, false) => (a, b),
+            (false, true) => (b, a),
+            (false, false) => (a.min(b), a.max(b)),
+        };
+        self.representatives.insert(replaced, representative);
+        true
+    }
+
+    fn substitute_opcode(&self, opcode: Opcode) -> Opcode {
+        match opcode {
+            Opcode::Arithmetic(expr) => Opcode::Arithmetic(self.substitute_expression(expr)),
+            Opcode::Directive(directive) => Opcode::Directive(self.substitute_directive(directive)),
+            Opcode::Brillig(mut brillig) => {
+                brillig.inputs = brillig
+                    .inputs
+                    .into_iter()
+                    .map(|input| match input {
+                        BrilligInputs::Single(expr) => {
+                            BrilligInputs::Single(self.substitute_expression(expr))
+                        }
+                        BrilligInputs::Array(exprs) => BrilligInputs::Array(
+                            exprs
+                                .into_iter()
+                                .map(|expr| self.substitute_expression(expr))
+                                .collect(),
+                        ),
+                    })
+                    .collect();
+                for output in &mut brillig.outputs {
+                    match output {
+                        BrilligOutputs::Simple(witness) => *witness = self.find(*witness),
+                        BrilligOutputs::Array(witnesses) => {
+                            witnesses.iter_mut().for_each(|witness| *witness = self.find(*witness));
+                        }
+                    }
+                }
+                brillig.predicate = brillig.predicate.map(|expr| self.substitute_expression(expr));
+                Opcode::Brillig(brillig)
+            }
+            Opcode::MemoryOp { block_id, op, predicate } => Opcode::MemoryOp {
+                block_id,
+                op: MemOp {
+                    operation: self.substitute_expression(op.operation),
+                    index: self.substitute_expression(op.index),
+                    value: self.substitute_expression(op.value),
+                },
+                predicate: predicate.map(|expr| self.substitute_expression(expr)),
+            },
+            Opcode::MemoryInit { block_id, init } => Opcode::MemoryInit {
+                block_id,
+                init: init.into_iter().map(|witness| self.find(witness)).collect(),
+            },
+            Opcode::BlackBoxFuncCall(_) => opcode,
+        }
+    }
+
+    fn substitute_directive(&self, directive: Directive) -> Directive {
+        match directive {
+            Directive::Quotient(QuotientDirective { a, b, q, r, predicate }) => {
+                Directive::Quotient(QuotientDirective {
+                    a: self.substitute_expression(a),
+                    b: self.substitute_expression(b),
+                    q: self.find(q),
+                    r: self.find(r),
+                    predicate: predicate.map(|expr| self.substitute_expression(expr)),
+                })
+            }
+            Directive::ToLeRadix { a, b, radix } => Directive::ToLeRadix {
+                a: self.substitute_expression(a),
+                b: b.into_iter().map(|witness| self.find(witness)).collect(),
+                radix,
+            },
+            Directive::PermutationSort { inputs, tuple, bits, sort_by } => {
+                Directive::PermutationSort {
+                    inputs: inputs
+                        .into_iter()
+                        .map(|tuple| {
+                            tuple.into_iter().map(|expr| self.substitute_expression(expr)).collect()
+                        })
+                        .collect(),
+                    tuple,
+                    bits: bits.into_iter().map(|witness| self.find(witness)).collect(),
+                    sort_by,
+                }
+            }
+        }
+    }
+
+    /// Replaces each witness in `expr` with its representative, combining any terms which
+    /// become identical.
+    fn substitute_expression(&self, expr: Expression) -> Expression {
+        let mut mul_terms: BTreeMap<(Witness, Witness), FieldElement> = BTreeMap::new();
+        for (coefficient, w_l, w_r) in expr.mul_terms {
+            let (w_l, w_r) = (self.find(w_l), self.find(w_r));
+            let key = if w_l <= w_r { (w_l, w_r) } else { (w_r, w_l) };
+            *mul_terms.entry(key).or_insert_with(FieldElement::zero) += coefficient;
+        }
+
+        let mut linear_combinations: BTreeMap<Witness, FieldElement> = BTreeMap::new();
+        for (coefficient, witness) in expr.linear_combinations {
+            *linear_combinations.entry(self.find(witness)).or_insert_with(FieldElement::zero) +=
+                coefficient;
+        }
+
+        Expression {
+            mul_terms: mul_terms
+                .into_iter()
+                .filter(|(_, coefficient)| !coefficient.is_zero())
+                .map(|((w_l, w_r), coefficient)| (coefficient, w_l, w_r))
+                .collect(),
+            linear_combinations: linear_combinations
+                .into_iter()
+                .filter(|(_, coefficient)| !coefficient.is_zero())
+                .map(|(witness, coefficient)| (coefficient, witness))
+                .collect(),
+            q_c: expr.q_c,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::compiler::optimizers::equal_witness::EqualWitnessOptimizer;
+    use acir::{
+        circuit::{
+            opcodes::{BlockId, MemOp},
+            Circuit, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    fn test_circuit(opcodes: Vec<Opcode>, current_witness_index: u32) -> Circuit {
+        Circuit {
+            current_witness_index,
+            opcodes,
+            private_parameters: BTreeSet::from([Witness(1), Witness(2), Witness(3)]),
+            public_parameters: PublicInputs::default(),
+            return_values: PublicInputs::default(),
+            assert_messages: Default::default(),
+        }
+    }
+
+    /// Builds the opcode `a - b = 0`
+    fn equality(a: Witness, b: Witness) -> Opcode {
+        Opcode::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(FieldElement::one(), a), (-FieldElement::one(), b)],
+            q_c: FieldElement::zero(),
+        })
+    }
+
+    #[test]
+    fn merges_chain_of_equal_witnesses() {
+        // x1*x2 - x4 = 0
+        // x4 - x5 = 0
+        // x6 - x5 = 0
+        // x6 + x3 - x7 = 0
+        let product = Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(4))],
+            q_c: FieldElement::zero(),
+        });
+        let sum = Opcode::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(6)),
+                (FieldElement::one(), Witness(3)),
+                (-FieldElement::one(), Witness(7)),
+            ],
+            q_c: FieldElement::zero(),
+        });
+        let opcodes = vec![
+            product.clone(),
+            equality(Witness(4), Witness(5)),
+            equality(Witness(6), Witness(5)),
+            sum,
+        ];
+
+        let circuit = test_circuit(opcodes, 7);
+        let optimizer = EqualWitnessOptimizer::new(circuit);
+        let (optimized_circuit, new_positions) = optimizer.merge_equal_witnesses(vec![0, 1, 2, 3]);
+
+        // Both equalities are removed and x6 is replaced by x4
+        assert_eq!(optimized_circuit.opcodes.len(), 2);
+        assert_eq!(new_positions, vec![0, 3]);
+        assert_eq!(optimized_circuit.opcodes[0], product);
+
+        let Opcode::Arithmetic(expr) = &optimized_circuit.opcodes[1] else {
+            panic!("Expected an arithmetic opcode");
+        };
+        assert_eq!(
+            expr.linear_combinations,
+            vec![
+                (FieldElement::one(), Witness(3)),
+                (FieldElement::one(), Witness(4)),
+                (-FieldElement::one(), Witness(7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn replaces_witnesses_with_parameters() {
+        // x4 - x1 = 0
+        // MEM (id: 0, write x4 at index x2)
+        let write = Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::write_to_mem_index(Witness(2).into(), Witness(4).into()),
+            predicate: None,
+        };
+        let circuit = test_circuit(vec![equality(Witness(4), Witness(1)), write], 4);
+        let optimizer = EqualWitnessOptimizer::new(circuit);
+        let (optimized_circuit, new_positions) = optimizer.merge_equal_witnesses(vec![0, 1]);
+
+        assert_eq!(new_positions, vec![1]);
+        let Opcode::MemoryOp { op, .. } = &optimized_circuit.opcodes[0] else {
+            panic!("Expected a memory opcode");
+        };
+        assert_eq!(op.value, Witness(1).into());
+    }
+
+    #[test]
+    fn keeps_equality_between_parameters() {
+        // x1 - x2 = 0
+        let circuit = test_circuit(vec![equality(Witness(1), Witness(2))], 3);
+        let optimizer = EqualWitnessOptimizer::new(circuit.clone());
+        let (optimized_circuit, new_positions) = optimizer.merge_equal_witnesses(vec![0]);
+
+        assert_eq!(optimized_circuit, circuit);
+        assert_eq!(new_positions, vec![0]);
+    }
+}
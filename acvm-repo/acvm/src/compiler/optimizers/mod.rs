@@ -1,5 +1,7 @@
 use acir::circuit::{Circuit, Opcode};
 
+mod common_subterm;
+mod equal_witness;
 mod general;
 mod redundant_range;
 mod unused_memory;
@@ -7,13 +9,25 @@ mod unused_memory;
 pub(crate) use general::GeneralOptimizer;
 pub(crate) use redundant_range::RangeOptimizer;
 
-use self::unused_memory::UnusedMemoryOptimizer;
+use self::{
+    common_subterm::CommonSubtermOptimizer, equal_witness::EqualWitnessOptimizer,
+    unused_memory::UnusedMemoryOptimizer,
+};
 
 use super::{transform_assert_messages, AcirTransformationMap};
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] independent optimizations to a [`Circuit`].
 pub fn optimize(acir: Circuit) -> (Circuit, AcirTransformationMap) {
-    let (mut acir, transformation_map) = optimize_internal(acir);
+    optimize_with_report(acir, |_, _| ())
+}
+
+/// The same as [`optimize`] but calls `report` with the name of each optimization pass
+/// and the circuit it produced.
+pub fn optimize_with_report(
+    acir: Circuit,
+    mut report: impl FnMut(&str, &Circuit),
+) -> (Circuit, AcirTransformationMap) {
+    let (mut acir, transformation_map) = optimize_internal(acir, &mut report);
 
     acir.assert_messages = transform_assert_messages(acir.assert_messages, &transformation_map);
 
@@ -21,7 +35,10 @@ pub fn optimize(acir: Circuit) -> (Circuit, AcirTransformationMap) {
 }
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] independent optimizations to a [`Circuit`].
-pub(super) fn optimize_internal(acir: Circuit) -> (Circuit, AcirTransformationMap) {
+pub(super) fn optimize_internal(
+    acir: Circuit,
+    report: &mut impl FnMut(&str, &Circuit),
+) -> (Circuit, AcirTransformationMap) {
     // General optimizer pass
     let opcodes: Vec<Opcode> = acir
         .opcodes
@@ -35,6 +52,7 @@ pub(super) fn optimize_internal(acir: Circuit) -> (Circuit, AcirTransformationMa
         })
         .collect();
     let acir = Circuit { opcodes, ..acir };
+    report("General Optimization", &acir);
 
     // Track original acir opcode positions throughout the transformation passes of the compilation
     // by applying the modifications done to the circuit opcodes and also to the opcode_positions (delete and insert)
@@ -44,11 +62,25 @@ pub(super) fn optimize_internal(acir: Circuit) -> (Circuit, AcirTransformationMa
     let memory_optimizer = UnusedMemoryOptimizer::new(acir);
     let (acir, acir_opcode_positions) =
         memory_optimizer.remove_unused_memory_initializations(acir_opcode_positions);
+    report("Unused Memory Optimization", &acir);
 
     // Range optimization pass
     let range_optimizer = RangeOptimizer::new(acir);
     let (acir, acir_opcode_positions) =
         range_optimizer.replace_redundant_ranges(acir_opcode_positions);
+    report("Range Optimization", &acir);
+
+    // Equal witness merging pass
+    let equal_witness_optimizer = EqualWitnessOptimizer::new(acir);
+    let (acir, acir_opcode_positions) =
+        equal_witness_optimizer.merge_equal_witnesses(acir_opcode_positions);
+    report("Equal Witness Merging", &acir);
+
+    // Common subterm extraction pass
+    let subterm_optimizer = CommonSubtermOptimizer::new(acir);
+    let (acir, acir_opcode_positions) =
+        subterm_optimizer.extract_common_subterms(acir_opcode_positions);
+    report("Common Subterm Extraction", &acir);
 
     let transformation_map = AcirTransformationMap { acir_opcode_positions };
 
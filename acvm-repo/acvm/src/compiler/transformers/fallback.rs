@@ -1,7 +1,12 @@
+use std::collections::{HashMap, HashSet};
+
 use super::super::CompileError;
 use acir::{
-    circuit::{opcodes::BlackBoxFuncCall, Circuit, Opcode},
-    native_types::Expression,
+    circuit::{
+        opcodes::{BlackBoxFuncCall, BlockId, MemOp},
+        Circuit, Opcode,
+    },
+    native_types::{Expression, Witness},
 };
 
 /// The initial transformer to act on a [`Circuit`]. This replaces any unsupported opcodes with
@@ -19,6 +24,39 @@ impl FallbackTransformer {
         let mut new_opcode_positions = Vec::with_capacity(opcode_positions.len());
         let mut witness_idx = acir.current_witness_index + 1;
 
+        // Memory opcodes for a block are only kept if the backend supports initializing the block and
+        // every kind of access made to it, so a backend may support read-only blocks but not blocks
+        // which are written to. Otherwise we track the contents of each block so that accesses can
+        // be replaced by multiplexers.
+        let supports_access = |op: MemOp| {
+            is_supported(&Opcode::MemoryOp { block_id: BlockId::default(), op, predicate: None })
+        };
+        let rom_supported =
+            is_supported(&Opcode::MemoryInit { block_id: BlockId::default(), init: Vec::new() })
+                && supports_access(MemOp::read_at_mem_index(
+                    Expression::zero(),
+                    Witness::default(),
+                ));
+        let ram_supported = rom_supported
+            && supports_access(MemOp::write_to_mem_index(Expression::zero(), Expression::zero()));
+
+        let written_blocks: HashSet<BlockId> = acir
+            .opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::MemoryOp { block_id, op, .. } if !op.operation.is_zero() => Some(*block_id),
+                _ => None,
+            })
+            .collect();
+        let block_supported = |block_id: &BlockId| {
+            if written_blocks.contains(block_id) {
+                ram_supported
+            } else {
+                rom_supported
+            }
+        };
+        let mut memory_blocks: HashMap<BlockId, Vec<Witness>> = HashMap::new();
+
         for (idx, opcode) in acir.opcodes.into_iter().enumerate() {
             match &opcode {
                 Opcode::Arithmetic(_) | Opcode::Directive(_) | Opcode::Brillig(_) => {
@@ -27,15 +65,30 @@ impl FallbackTransformer {
                     acir_supported_opcodes.push(opcode);
                     continue;
                 }
-                Opcode::MemoryInit { .. } | Opcode::MemoryOp { .. } => {
-                    if !is_supported(&opcode) {
-                        return Err(CompileError::UnsupportedMemoryOpcode(
-                            opcode.unsupported_opcode(),
-                        ));
-                    }
+                Opcode::MemoryInit { block_id, .. } | Opcode::MemoryOp { block_id, .. }
+                    if block_supported(block_id) =>
+                {
                     new_opcode_positions.push(opcode_positions[idx]);
                     acir_supported_opcodes.push(opcode);
                 }
+                Opcode::MemoryInit { block_id, init } => {
+                    // The block's initial contents are already witnesses so no opcodes are needed
+                    memory_blocks.insert(*block_id, init.clone());
+                }
+                Opcode::MemoryOp { block_id, op, predicate } => {
+                    let elements = memory_blocks.get_mut(block_id).ok_or_else(|| {
+                        CompileError::UnsupportedMemoryOpcode(opcode.unsupported_opcode())
+                    })?;
+                    let (updated_witness_index, opcodes_fallback) =
+                        Self::memory_op_fallback(elements, op, predicate.clone(), witness_idx)
+                            .ok_or_else(|| {
+                                CompileError::UnsupportedMemoryOpcode(opcode.unsupported_opcode())
+                            })?;
+                    witness_idx = updated_witness_index;
+                    new_opcode_positions
+                        .extend(vec![opcode_positions[idx]; opcodes_fallback.len()]);
+                    acir_supported_opcodes.extend(opcodes_fallback);
+                }
                 Opcode::BlackBoxFuncCall(bb_func_call) => {
                     // We know it is an black box function. Now check if it is
                     // supported by the backend. If it is supported, then we can simply
@@ -69,6 +122,41 @@ impl FallbackTransformer {
         ))
     }
 
+    /// Replaces a memory access with a multiplexer over the block's `elements`, which are
+    /// updated in place if the access is a write.
+    ///
+    /// Returns `None` if the operation is not known to be either a read or a write at compile time.
+    fn memory_op_fallback(
+        elements: &mut Vec<Witness>,
+        op: &MemOp,
+        predicate: Option<Expression>,
+        current_witness_idx: u32,
+    ) -> Option<(u32, Vec<Opcode>)> {
+        let operation = op.operation.to_const()?;
+        if operation.is_zero() {
+            Some(stdlib::memory_fallbacks::memory_read(
+                elements,
+                op.index.clone(),
+                op.value.clone(),
+                predicate,
+                current_witness_idx,
+            ))
+        } else if operation.is_one() {
+            let (new_elements, updated_witness_index, opcodes_fallback) =
+                stdlib::memory_fallbacks::memory_write(
+                    elements,
+                    op.index.clone(),
+                    op.value.clone(),
+                    predicate,
+                    current_witness_idx,
+                );
+            *elements = new_elements;
+            Some((updated_witness_index, opcodes_fallback))
+        } else {
+            None
+        }
+    }
+
     fn opcode_fallback(
         gc: &BlackBoxFuncCall,
         current_witness_idx: u32,
@@ -108,7 +196,6 @@ impl FallbackTransformer {
                     current_witness_idx,
                 )
             }
-            #[cfg(feature = "unstable-fallbacks")]
             BlackBoxFuncCall::SHA256 { inputs, outputs } => {
                 let sha256_inputs =
                     inputs.iter().map(|input| (input.witness.into(), input.num_bits)).collect();
@@ -118,7 +205,6 @@ impl FallbackTransformer {
                     current_witness_idx,
                 )
             }
-            #[cfg(feature = "unstable-fallbacks")]
             BlackBoxFuncCall::Blake2s { inputs, outputs } => {
                 let blake2s_inputs =
                     inputs.iter().map(|input| (input.witness.into(), input.num_bits)).collect();
@@ -128,7 +214,6 @@ impl FallbackTransformer {
                     current_witness_idx,
                 )
             }
-            #[cfg(feature = "unstable-fallbacks")]
             BlackBoxFuncCall::HashToField128Security { inputs, output } => {
                 let hash_to_field_inputs =
                     inputs.iter().map(|input| (input.witness.into(), input.num_bits)).collect();
@@ -138,7 +223,6 @@ impl FallbackTransformer {
                     current_witness_idx,
                 )
             }
-            #[cfg(feature = "unstable-fallbacks")]
             BlackBoxFuncCall::Keccak256 { inputs, outputs } => {
                 let keccak_inputs =
                     inputs.iter().map(|input| (input.witness.into(), input.num_bits)).collect();
@@ -3,7 +3,7 @@ mod solver;
 use crate::solver::StubbedBackend;
 use acir::{
     circuit::{
-        opcodes::{BlackBoxFuncCall, FunctionInput},
+        opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
         Circuit, Opcode,
     },
     native_types::{Expression, Witness},
@@ -352,3 +352,93 @@ proptest! {
         prop_assert_eq!(solver_status, ACVMStatus::Solved, "should be fully solved");
     }
 }
+
+fn does_not_support_memory(opcode: &Opcode) -> bool {
+    !matches!(opcode, Opcode::MemoryInit { .. } | Opcode::MemoryOp { .. })
+}
+
+/// Builds a circuit which writes `value` into a block initialized with `elements` at `write_index`
+/// and then reads `read_index` from it, constraining the result to equal `expected`.
+fn memory_circuit(
+    elements: &[u64],
+    write_index: u64,
+    value: u64,
+    read_index: u64,
+    expected: u64,
+) -> (Circuit, BTreeMap<Witness, FieldElement>) {
+    let len = elements.len() as u32;
+    let mut witness_assignments: BTreeMap<Witness, FieldElement> = elements
+        .iter()
+        .enumerate()
+        .map(|(i, element)| (Witness(i as u32 + 1), FieldElement::from(*element as u128)))
+        .collect();
+    let inputs = [write_index, value, read_index, expected];
+    for (i, input) in inputs.into_iter().enumerate() {
+        witness_assignments.insert(Witness(len + 1 + i as u32), FieldElement::from(input as u128));
+    }
+    let output = Witness(len + 5);
+
+    let block_id = BlockId(0);
+    let mut expected_constraint = Expression::from(Witness(len + 4));
+    expected_constraint.push_addition_term(-FieldElement::one(), output);
+    let opcodes = vec![
+        Opcode::MemoryInit { block_id, init: (1..=len).map(Witness).collect() },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::write_to_mem_index(Witness(len + 1).into(), Witness(len + 2).into()),
+            predicate: None,
+        },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(Witness(len + 3).into(), output),
+            predicate: None,
+        },
+        Opcode::Arithmetic(expected_constraint),
+    ];
+
+    let circuit = Circuit {
+        current_witness_index: len + 5,
+        opcodes,
+        private_parameters: witness_assignments.keys().copied().collect(),
+        ..Circuit::default()
+    };
+    (circuit, witness_assignments)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+    #[test]
+    fn test_memory_fallback(
+        elements in proptest::collection::vec(any::<u64>(), 1..20),
+        write_index in any::<prop::sample::Index>(),
+        read_index in any::<prop::sample::Index>(),
+        value in any::<u64>(),
+    ) {
+        let write_index = write_index.index(elements.len());
+        let read_index = read_index.index(elements.len());
+        let expected = if read_index == write_index { value } else { elements[read_index] };
+
+        let (circuit, witness_assignments) =
+            memory_circuit(&elements, write_index as u64, value, read_index as u64, expected);
+        let circuit = compile(circuit, Language::PLONKCSat{ width: 3 }, does_not_support_memory).unwrap().0;
+        prop_assert!(circuit.opcodes.iter().all(does_not_support_memory));
+
+        // solve witnesses
+        let mut acvm = ACVM::new(&StubbedBackend, &circuit.opcodes, witness_assignments.into());
+        let solver_status = acvm.solve();
+
+        prop_assert_eq!(solver_status, ACVMStatus::Solved, "should be fully solved");
+    }
+}
+
+#[test]
+fn memory_fallback_rejects_out_of_bounds_index() {
+    let (circuit, witness_assignments) = memory_circuit(&[1, 2, 3], 0, 4, 3, 0);
+    let circuit =
+        compile(circuit, Language::PLONKCSat { width: 3 }, does_not_support_memory).unwrap().0;
+
+    let mut acvm = ACVM::new(&StubbedBackend, &circuit.opcodes, witness_assignments.into());
+    let solver_status = acvm.solve();
+
+    assert!(matches!(solver_status, ACVMStatus::Failure(_)), "out of bounds reads must fail");
+}
@@ -32,6 +32,10 @@ pub use memory::Memory;
 use num_bigint::BigUint;
 pub use registers::Registers;
 
+/// The deepest that calls may be nested before the VM fails, so that unbounded recursion in an
+/// unconstrained function traps instead of growing the call stack and memory until they run out.
+pub const MAX_CALL_DEPTH: usize = 1 << 16;
+
 /// The error call stack contains the opcode indexes of the call stack at the time of failure, plus the index of the opcode that failed.
 pub type ErrorCallStack = Vec<usize>;
 
@@ -314,6 +318,11 @@ impl<'a, B: BlackBoxFunctionSolver> VM<'a, B> {
                 self.increment_program_counter()
             }
             Opcode::Call { location } => {
+                if self.call_stack.len() >= MAX_CALL_DEPTH {
+                    return self.fail(format!(
+                        "call stack depth limit of {MAX_CALL_DEPTH} exceeded, which may be caused by unbounded recursion"
+                    ));
+                }
                 // Push a return location
                 self.call_stack.push(Value::from(self.program_counter));
                 self.set_program_counter(*location)
@@ -906,6 +915,20 @@ mod tests {
         assert_eq!(memory, expected);
     }
 
+    #[test]
+    fn call_depth_limit() {
+        // A function which calls itself forever
+        let opcodes = [Opcode::Call { location: 0 }];
+        let mut vm = VM::new(empty_registers(), vec![], &opcodes, vec![], &DummyBlackBoxSolver);
+
+        let status = vm.process_opcodes();
+        let VMStatus::Failure { message, call_stack } = status else {
+            panic!("expected the VM to fail, found {status:?}");
+        };
+        assert!(message.contains("call stack depth limit"));
+        assert_eq!(call_stack.len(), MAX_CALL_DEPTH + 1);
+    }
+
     fn empty_registers() -> Registers {
         Registers::load(vec![Value::from(0u128); 16])
     }
@@ -8,7 +8,7 @@ mod uint;
 mod uint32;
 mod uint64;
 mod uint8;
-mod utils;
+pub(crate) mod utils;
 pub use blake2s::blake2s;
 pub use hash_to_field::hash_to_field;
 pub use keccak256::keccak256;
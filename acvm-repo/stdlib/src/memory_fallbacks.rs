@@ -0,0 +1,159 @@
+//! Fallback implementations of ACIR memory opcodes for backends which do not support them.
+//!
+//! Each memory operation is replaced by a multiplexer over the current contents of the block.
+//! The index is decomposed into bits which are then combined into a tree of selectors, such that
+//! exactly one selector is set for each index within the bounds of the block.
+use crate::{blackbox_fallbacks::utils::bit_decomposition, helpers::VariableStore};
+use acir::{
+    acir_field::FieldElement,
+    circuit::Opcode,
+    native_types::{Expression, Witness},
+};
+
+/// Returns a set of opcodes which constrain `value` to be the element of `elements` at `index`.
+///
+/// If `predicate` is zero then `index` is ignored and `value` is constrained to the first element.
+pub fn memory_read(
+    elements: &[Witness],
+    index: Expression,
+    value: Expression,
+    predicate: Option<Expression>,
+    mut num_witness: u32,
+) -> (u32, Vec<Opcode>) {
+    let mut opcodes = Vec::new();
+    let predicate = predicate.map(|predicate| linearize(predicate, &mut num_witness, &mut opcodes));
+
+    let Some(selectors) =
+        index_selectors(elements.len(), index, predicate.as_ref(), &mut num_witness, &mut opcodes)
+    else {
+        return (num_witness, opcodes);
+    };
+
+    let mut read_expr = -&value;
+    for (selector, element) in selectors.iter().zip(elements) {
+        read_expr = &read_expr
+            + &(selector * &Expression::from(*element))
+                .expect("selectors and elements are both linear");
+    }
+    opcodes.push(Opcode::Arithmetic(read_expr));
+
+    (num_witness, opcodes)
+}
+
+/// Returns the witnesses holding the contents of `elements` after `value` is written at `index`,
+/// along with the set of opcodes which constrain them.
+///
+/// If `predicate` is zero then the write is skipped and the contents are unchanged.
+pub fn memory_write(
+    elements: &[Witness],
+    index: Expression,
+    value: Expression,
+    predicate: Option<Expression>,
+    mut num_witness: u32,
+) -> (Vec<Witness>, u32, Vec<Opcode>) {
+    let mut opcodes = Vec::new();
+    let predicate = predicate.map(|predicate| linearize(predicate, &mut num_witness, &mut opcodes));
+
+    let Some(selectors) =
+        index_selectors(elements.len(), index, predicate.as_ref(), &mut num_witness, &mut opcodes)
+    else {
+        return (elements.to_vec(), num_witness, opcodes);
+    };
+    let value = linearize(value, &mut num_witness, &mut opcodes);
+
+    let mut new_elements = Vec::with_capacity(elements.len());
+    for (selector, element) in selectors.into_iter().zip(elements) {
+        // Skipped writes must not select any element, including the first one
+        let selector = match &predicate {
+            Some(predicate) => {
+                let product = (&selector * predicate).expect("both expressions are linear");
+                linearize(product, &mut num_witness, &mut opcodes)
+            }
+            None => selector,
+        };
+
+        // new_element = element + selector * (value - element)
+        let difference = &value - &Expression::from(*element);
+        let update = (&selector * &difference).expect("both expressions are linear");
+        let new_element = VariableStore::new(&mut num_witness).new_variable();
+        let mut write_expr = &update + *element;
+        write_expr.push_addition_term(-FieldElement::one(), new_element);
+        opcodes.push(Opcode::Arithmetic(write_expr));
+
+        new_elements.push(new_element);
+    }
+
+    (new_elements, num_witness, opcodes)
+}
+
+/// Returns linear expressions `selectors[i]` which are one when `i == index` and zero otherwise.
+///
+/// The opcodes pushed into `opcodes` constrain `index` to be within the `len` elements of the block,
+/// unless `predicate` is zero in which case the index is replaced by zero. If the block is empty
+/// then no selectors are returned and the operation is constrained to be skipped.
+///
+/// `predicate` is expected to be linear.
+fn index_selectors(
+    len: usize,
+    index: Expression,
+    predicate: Option<&Expression>,
+    num_witness: &mut u32,
+    opcodes: &mut Vec<Opcode>,
+) -> Option<Vec<Expression>> {
+    if len == 0 {
+        let predicate = predicate.cloned().unwrap_or_else(Expression::one);
+        opcodes.push(Opcode::Arithmetic(predicate));
+        return None;
+    }
+
+    let index = match predicate {
+        Some(predicate) => {
+            let index = linearize(index, num_witness, opcodes);
+            (&index * predicate).expect("both expressions are linear")
+        }
+        None => index,
+    };
+
+    let num_bits = usize::BITS - (len - 1).leading_zeros();
+    let (bit_opcodes, bits, updated_witness_index) =
+        bit_decomposition(index, num_bits, *num_witness);
+    *num_witness = updated_witness_index;
+    opcodes.extend(bit_opcodes);
+
+    // Each level of the tree splits every selector by the next bit of the index,
+    // so that after processing `j` bits there is a selector for each value of the low `j` bits.
+    let mut selectors = vec![Expression::one()];
+    for bit in bits {
+        let bit = Expression::from(bit);
+        let mut set_selectors = Vec::with_capacity(selectors.len());
+        for selector in &mut selectors {
+            let set_selector = (&*selector * &bit).expect("both expressions are linear");
+            let set_selector = linearize(set_selector, num_witness, opcodes);
+            *selector = &*selector - &set_selector;
+            set_selectors.push(set_selector);
+        }
+        selectors.extend(set_selectors);
+    }
+    selectors.truncate(len);
+
+    // Exactly one of the remaining selectors is set only if the index is within bounds
+    let mut bounds_check = Expression::one();
+    for selector in &selectors {
+        bounds_check = &bounds_check - selector;
+    }
+    opcodes.push(Opcode::Arithmetic(bounds_check));
+
+    Some(selectors)
+}
+
+/// Replaces a non-linear expression with a new witness which is constrained to be equal to it.
+fn linearize(expr: Expression, num_witness: &mut u32, opcodes: &mut Vec<Opcode>) -> Expression {
+    if expr.is_linear() {
+        return expr;
+    }
+    let witness = VariableStore::new(num_witness).new_variable();
+    let mut constraint = expr;
+    constraint.push_addition_term(-FieldElement::one(), witness);
+    opcodes.push(Opcode::Arithmetic(constraint));
+    witness.into()
+}
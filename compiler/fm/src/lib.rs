@@ -52,14 +52,7 @@ impl FileManager {
     }
 
     pub fn add_file(&mut self, file_name: &Path) -> Option<FileId> {
-        // Handle both relative file paths and std/lib virtual paths.
-        let resolved_path: PathBuf = if is_stdlib_asset(file_name) {
-            // Special case for stdlib where we want to read specifically the `std/` relative path
-            // TODO: The stdlib path should probably be an absolute path rooted in something people would never create
-            file_name.to_path_buf()
-        } else {
-            self.root.join(file_name).normalize()
-        };
+        let resolved_path = self.resolve_path(file_name);
 
         // Check that the resolved path already exists in the file map, if it is, we return it.
         if let Some(file_id) = self.path_to_id.get(&resolved_path) {
@@ -73,6 +66,17 @@ impl FileManager {
         Some(file_id)
     }
 
+    fn resolve_path(&self, file_name: &Path) -> PathBuf {
+        // Handle both relative file paths and std/lib virtual paths.
+        if is_stdlib_asset(file_name) {
+            // Special case for stdlib where we want to read specifically the `std/` relative path
+            // TODO: The stdlib path should probably be an absolute path rooted in something people would never create
+            file_name.to_path_buf()
+        } else {
+            self.root.join(file_name).normalize()
+        }
+    }
+
     fn register_path(&mut self, file_id: FileId, path: PathBuf) {
         let old_value = self.id_to_path.insert(file_id, path.clone());
         assert!(
@@ -94,23 +98,72 @@ impl FileManager {
         self.id_to_path.get(&file_id).unwrap().as_path()
     }
 
-    pub fn find_module(&mut self, anchor: FileId, mod_name: &str) -> Result<FileId, String> {
+    /// Returns the id and path of every file added which was read from the file system, leaving out
+    /// the files of the standard library which are built into the compiler.
+    pub fn source_files(&self) -> impl Iterator<Item = (FileId, &Path)> {
+        self.id_to_path
+            .iter()
+            .filter(|(_, path)| !is_stdlib_asset(path))
+            .map(|(file_id, path)| (*file_id, path.as_path()))
+    }
+
+    /// Returns the id of an already added file, if any.
+    pub fn name_to_id(&self, file_name: PathBuf) -> Option<FileId> {
+        self.path_to_id.get(&self.resolve_path(&file_name)).copied()
+    }
+
+    /// Finds the file of the module `mod_name` declared with `mod mod_name;` in `anchor`.
+    /// The module is either at `base/mod_name.nr` or at `base/mod_name/mod.nr`.
+    pub fn find_module(&mut self, anchor: FileId, mod_name: &str) -> Result<FileId, ModuleError> {
         let anchor_path = self.path(anchor).with_extension("");
         let anchor_dir = anchor_path.parent().unwrap();
 
         // if `anchor` is a `main.nr`, `lib.nr`, `mod.nr` or `{mod_name}.nr`, we check siblings of
         // the anchor at `base/mod_name.nr`.
-        let candidate = if should_check_siblings_for_module(&anchor_path, anchor_dir) {
-            anchor_dir.join(format!("{mod_name}.{FILE_EXTENSION}"))
+        let base = if should_check_siblings_for_module(&anchor_path, anchor_dir) {
+            anchor_dir
         } else {
             // Otherwise, we check for children of the anchor at `base/anchor/mod_name.nr`
-            anchor_path.join(format!("{mod_name}.{FILE_EXTENSION}"))
+            anchor_path.as_path()
         };
 
-        self.add_file(&candidate).ok_or_else(|| candidate.as_os_str().to_string_lossy().to_string())
+        let file_candidate = base.join(format!("{mod_name}.{FILE_EXTENSION}"));
+        let dir_candidate = base.join(mod_name).join(format!("mod.{FILE_EXTENSION}"));
+
+        match (self.add_file(&file_candidate), self.file_exists(&dir_candidate)) {
+            (Some(_), true) => Err(ModuleError::Ambiguous {
+                file_path: path_to_string(&file_candidate),
+                dir_path: path_to_string(&dir_candidate),
+            }),
+            (Some(file_id), false) => Ok(file_id),
+            (None, _) => self.add_file(&dir_candidate).ok_or_else(|| ModuleError::NotFound {
+                file_path: path_to_string(&file_candidate),
+                dir_path: path_to_string(&dir_candidate),
+            }),
+        }
+    }
+
+    /// Returns true if the given file is either already known or can be read.
+    fn file_exists(&self, file_name: &Path) -> bool {
+        let resolved_path = self.resolve_path(file_name);
+        self.path_to_id.contains_key(&resolved_path)
+            || file_reader::read_file_to_string(&resolved_path, &self.file_reader).is_ok()
     }
 }
 
+/// The reasons a `mod foo;` declaration can fail to resolve to a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleError {
+    /// Neither `foo.nr` nor `foo/mod.nr` exist.
+    NotFound { file_path: String, dir_path: String },
+    /// Both `foo.nr` and `foo/mod.nr` exist, so it is unclear which one is the module.
+    Ambiguous { file_path: String, dir_path: String },
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.as_os_str().to_string_lossy().to_string()
+}
+
 /// Returns true if a module's child module's are expected to be in the same directory.
 /// Returns false if they are expected to be in a subdirectory matching the name of the module.
 fn should_check_siblings_for_module(module_path: &Path, parent_path: &Path) -> bool {
@@ -285,6 +338,49 @@ mod tests {
         fm.find_module(sub_dir_file_id, "foo").unwrap();
     }
 
+    #[test]
+    fn path_resolve_directory_module() {
+        let dir = tempdir().unwrap();
+        let mut fm = FileManager::new(dir.path(), Box::new(|path| std::fs::read_to_string(path)));
+
+        // we now have:
+        // - dir/lib.nr
+        // - dir/foo/mod.nr
+        // - dir/foo/bar.nr
+        // - dir/foo/baz/mod.nr
+        create_dummy_file(&dir, Path::new("lib.nr"));
+        std::fs::create_dir_all(dir.path().join("foo").join("baz")).unwrap();
+        create_dummy_file(&dir, Path::new("foo/mod.nr"));
+        create_dummy_file(&dir, Path::new("foo/bar.nr"));
+        create_dummy_file(&dir, Path::new("foo/baz/mod.nr"));
+
+        let file_id = fm.add_file(Path::new("lib.nr")).unwrap();
+        let foo_file_id = fm.find_module(file_id, "foo").unwrap();
+        assert!(fm.path(foo_file_id).ends_with("foo/mod.nr"));
+
+        // Children of `foo/mod.nr` are within the `foo` directory
+        let bar_file_id = fm.find_module(foo_file_id, "bar").unwrap();
+        assert!(fm.path(bar_file_id).ends_with("foo/bar.nr"));
+        let baz_file_id = fm.find_module(foo_file_id, "baz").unwrap();
+        assert!(fm.path(baz_file_id).ends_with("foo/baz/mod.nr"));
+
+        assert!(matches!(fm.find_module(file_id, "qux"), Err(ModuleError::NotFound { .. })));
+    }
+
+    #[test]
+    fn path_resolve_ambiguous_module() {
+        let dir = tempdir().unwrap();
+        let mut fm = FileManager::new(dir.path(), Box::new(|path| std::fs::read_to_string(path)));
+
+        create_dummy_file(&dir, Path::new("lib.nr"));
+        std::fs::create_dir_all(dir.path().join("foo")).unwrap();
+        create_dummy_file(&dir, Path::new("foo.nr"));
+        create_dummy_file(&dir, Path::new("foo/mod.nr"));
+
+        let file_id = fm.add_file(Path::new("lib.nr")).unwrap();
+        assert!(matches!(fm.find_module(file_id, "foo"), Err(ModuleError::Ambiguous { .. })));
+    }
+
     /// Tests that two identical files that have different paths are treated as the same file
     /// e.g. if we start in the dir ./src and have a file ../../foo.nr
     /// that should be treated as the same file as ../ starting in ./
@@ -2,11 +2,12 @@ use std::collections::BTreeMap;
 
 use acvm::acir::native_types::Witness;
 use iter_extended::{btree_map, vecmap};
-use noirc_abi::{Abi, AbiParameter, AbiType};
+use noirc_abi::{Abi, AbiOracle, AbiOracleParameter, AbiParameter, AbiType};
 use noirc_frontend::{
     hir::Context,
     hir_def::{function::Param, stmt::HirPattern},
     node_interner::{FuncId, NodeInterner},
+    token::FunctionAttribute,
 };
 use std::ops::Range;
 
@@ -15,12 +16,15 @@ use std::ops::Range;
 pub(super) fn gen_abi(
     context: &Context,
     func_id: &FuncId,
+    oracles: &[FuncId],
     input_witnesses: Vec<Witness>,
     return_witnesses: Vec<Witness>,
 ) -> Abi {
     let (parameters, return_type) = compute_function_abi(context, func_id);
     let param_witnesses = param_witnesses_from_abi_param(&parameters, input_witnesses);
-    Abi { parameters, return_type, param_witnesses, return_witnesses }
+    let return_visibility = context.def_interner.function_meta(func_id).return_visibility.into();
+    let oracles = oracles.iter().filter_map(|oracle| compute_oracle_abi(context, oracle)).collect();
+    Abi { parameters, return_type, return_visibility, param_witnesses, return_witnesses, oracles }
 }
 
 pub(super) fn compute_function_abi(
@@ -35,6 +39,38 @@ pub(super) fn compute_function_abi(
     (parameters, return_type)
 }
 
+/// Computes the signature of an `#[oracle]` function.
+///
+/// Returns None if the oracle is generic or its signature contains a type which cannot be
+/// represented in the ABI, such as a slice. Calls to these oracles are not described by the ABI.
+fn compute_oracle_abi(context: &Context, func_id: &FuncId) -> Option<AbiOracle> {
+    let name = match context.def_interner.function_attributes(func_id).function.as_ref() {
+        Some(FunctionAttribute::Oracle(name)) => name.clone(),
+        _ => return None,
+    };
+
+    let func_meta = context.def_interner.function_meta(func_id);
+    if func_meta.typ.generic_count() > 0 {
+        return None;
+    }
+
+    let (parameters, return_type) = func_meta.into_function_signature();
+    if !parameters.iter().all(|(_, typ, _)| typ.is_valid_for_program_input())
+        || !return_type.as_ref().map_or(true, |typ| typ.is_valid_for_program_input())
+    {
+        return None;
+    }
+
+    let parameters = vecmap(parameters, |(pattern, typ, _)| {
+        let name = get_param_name(&pattern, &context.def_interner)
+            .expect("oracle parameters should have been checked to be named")
+            .to_owned();
+        AbiOracleParameter { name, typ: AbiType::from_type(context, &typ) }
+    });
+    let return_type = return_type.map(|typ| AbiType::from_type(context, &typ));
+    Some(AbiOracle { name, parameters, return_type })
+}
+
 /// Attempts to retrieve the name of this parameter. Returns None
 /// if this parameter is a tuple or struct pattern.
 fn get_param_name<'a>(pattern: &HirPattern, interner: &'a NodeInterner) -> Option<&'a str> {
@@ -49,7 +85,7 @@ fn get_param_name<'a>(pattern: &HirPattern, interner: &'a NodeInterner) -> Optio
 fn into_abi_params(context: &Context, params: Vec<Param>) -> Vec<AbiParameter> {
     vecmap(params, |(pattern, typ, vis)| {
         let param_name = get_param_name(&pattern, &context.def_interner)
-            .expect("Abi for tuple and struct parameters is unimplemented")
+            .expect("entry point parameters should have been checked to be named")
             .to_owned();
         let as_abi = AbiType::from_type(context, &typ);
         AbiParameter { name: param_name, typ: as_abi, visibility: vis.into() }
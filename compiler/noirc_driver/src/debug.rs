@@ -1,8 +1,11 @@
 use fm::{FileId, FileManager};
-use noirc_errors::debug_info::DebugInfo;
+use noirc_errors::debug_info::{DebugFunction, DebugInfo};
+use noirc_errors::{Location, Span};
+use noirc_frontend::node_interner::NodeInterner;
+use noirc_frontend::FunctionKind;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     path::PathBuf,
 };
 
@@ -43,3 +46,36 @@ pub(crate) fn filter_relevant_files(
     }
     file_map
 }
+
+/// Collects the functions whose bodies contain a location of `debug_info`, so that consumers of the
+/// debug artifact can name the functions in the call stack of a runtime error.
+pub(crate) fn debug_functions(
+    interner: &NodeInterner,
+    debug_info: &DebugInfo,
+) -> Vec<DebugFunction> {
+    let mut spans_per_file: HashMap<FileId, Vec<Span>> = HashMap::new();
+    for location in debug_info.locations.values().flatten() {
+        spans_per_file.entry(location.file).or_default().push(location.span);
+    }
+
+    let mut functions: Vec<_> = interner
+        .function_ids()
+        .filter(|func_id| interner.function_meta(func_id).kind == FunctionKind::Normal)
+        .filter_map(|func_id| {
+            let body = interner.function(&func_id);
+            let Location { span, file } = interner.expr_location(body.as_expr());
+            let contains_location = spans_per_file
+                .get(&file)?
+                .iter()
+                .any(|location| span.start() <= location.start() && location.end() <= span.end());
+            contains_location.then(|| DebugFunction {
+                name: interner.function_name(&func_id).to_owned(),
+                location: Location::new(span, file),
+            })
+        })
+        .collect();
+
+    // Function ids are hashed so they are sorted to keep the artifact deterministic.
+    functions.sort_by_key(|function| (function.location.file, function.location.span));
+    functions
+}
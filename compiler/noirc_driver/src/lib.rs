@@ -3,30 +3,33 @@
 #![warn(unreachable_pub)]
 #![warn(clippy::semicolon_if_nothing_returned)]
 
+use acvm::Language;
 use clap::Args;
 use fm::FileId;
 use iter_extended::vecmap;
 use noirc_abi::{AbiParameter, AbiType, ContractEvent};
-use noirc_errors::{CustomDiagnostic, FileDiagnostic};
+use noirc_errors::{error_codes, CustomDiagnostic, FileDiagnostic};
 use noirc_evaluator::create_circuit;
 use noirc_evaluator::errors::RuntimeError;
 use noirc_frontend::graph::{CrateId, CrateName};
 use noirc_frontend::hir::def_map::{Contract, CrateDefMap};
 use noirc_frontend::hir::Context;
+use noirc_frontend::lints::Lint;
 use noirc_frontend::monomorphization::monomorphize;
 use noirc_frontend::node_interner::FuncId;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 mod abi_gen;
 mod contract;
 mod debug;
 mod program;
 
-use debug::filter_relevant_files;
+use debug::{debug_functions, filter_relevant_files};
 
 pub use contract::{CompiledContract, ContractFunction, ContractFunctionType};
 pub use debug::DebugFile;
+pub use noirc_evaluator::{SsaPass, SsaPipeline, SsaPipelineError};
 pub use program::CompiledProgram;
 
 const STD_CRATE_NAME: &str = "std";
@@ -49,17 +52,64 @@ pub struct CompileOptions {
     #[arg(long, hide = true)]
     pub show_brillig: bool,
 
+    /// Report which function calls were inlined during SSA generation
+    #[arg(long, hide = true)]
+    pub show_inlining: bool,
+
+    /// Write the SSA after each optimization pass to numbered files under `target/debug_ssa/`
+    #[arg(long, hide = true)]
+    pub show_ssa_passes: bool,
+
+    /// Print the number of SSA instructions in each function after each SSA pass, and the number
+    /// of ACIR opcodes and witnesses after ACIR generation and each ACIR optimization pass
+    #[arg(long, hide = true)]
+    pub show_opcode_counts: bool,
+
+    /// The directory SSA is written to when `show_ssa_passes` is set.
+    /// Defaults to `target/debug_ssa` within the current directory.
+    #[arg(skip)]
+    #[serde(skip)]
+    pub ssa_dump_dir: Option<PathBuf>,
+
+    /// The language of the backend the program is compiled for. If given, arithmetic
+    /// expressions are split during ACIR generation so that each fits in one of its opcodes.
+    #[arg(skip)]
+    #[serde(skip)]
+    pub np_language: Option<Language>,
+
     /// Display the ACIR for compiled circuit
     #[arg(long)]
     pub print_acir: bool,
 
-    /// Treat all warnings as errors
+    /// Treat all warnings as errors, including lints which are not denied
     #[arg(long, conflicts_with = "silence_warnings")]
     pub deny_warnings: bool,
 
     /// Suppress warnings
     #[arg(long, conflicts_with = "deny_warnings")]
     pub silence_warnings: bool,
+
+    /// Report the given lint as an error, overriding the `[lints]` table of the package's Nargo.toml.
+    /// May be given more than once.
+    #[arg(long = "deny", value_name = "LINT")]
+    #[serde(default)]
+    pub deny_lints: Vec<Lint>,
+
+    /// Don't report the given lint, overriding the `[lints]` table of the package's Nargo.toml.
+    /// May be given more than once.
+    #[arg(long = "allow", value_name = "LINT")]
+    #[serde(default)]
+    pub allow_lints: Vec<Lint>,
+
+    /// Compile every function to Brillig, including `main`
+    #[arg(long)]
+    pub force_brillig: bool,
+
+    /// The comma-separated SSA passes to run after inlining, in order. Passes of the default
+    /// pipeline may instead be disabled by prefixing their names with `-`.
+    /// Overrides the `[optimizer]` table of the package's Nargo.toml.
+    #[arg(long)]
+    pub passes: Option<SsaPipeline>,
 }
 
 /// Helper type used to signify where only warnings are expected in file diagnostics
@@ -123,9 +173,14 @@ pub fn check_crate(
 ) -> CompilationResult<()> {
     let mut errors = vec![];
     let diagnostics = CrateDefMap::collect_defs(crate_id, context);
-    errors.extend(diagnostics.into_iter().map(|(error, file_id)| {
-        let diagnostic: CustomDiagnostic = error.into();
-        diagnostic.in_file(file_id)
+    let lint_levels = context.def_interner.lint_levels();
+    errors.extend(diagnostics.into_iter().filter_map(|(error, file_id)| {
+        let lint = error.lint();
+        let mut diagnostic: CustomDiagnostic = error.into();
+        if let Some(lint) = lint {
+            diagnostic = lint_levels.apply(lint, diagnostic, file_id)?;
+        }
+        Some(diagnostic.in_file(file_id))
     }));
 
     if has_errors(&errors, deny_warnings) {
@@ -162,6 +217,7 @@ pub fn compile_main(
         let err = CustomDiagnostic::from_message(
             "cannot compile crate into a program as it does not contain a `main` function",
         )
+        .with_code(error_codes::E0700)
         .in_file(FileId::default());
         vec![err]
     })?;
@@ -198,12 +254,14 @@ pub fn compile_contract(
 
     if contracts.len() > 1 {
         let err = CustomDiagnostic::from_message("Packages are limited to a single contract")
+            .with_code(error_codes::E0701)
             .in_file(FileId::default());
         return Err(vec![err]);
     } else if contracts.is_empty() {
         let err = CustomDiagnostic::from_message(
             "cannot compile crate into a contract as it does not contain any contracts",
         )
+        .with_code(error_codes::E0702)
         .in_file(FileId::default());
         return Err(vec![err]);
     };
@@ -327,24 +385,54 @@ pub fn compile_no_check(
     cached_program: Option<CompiledProgram>,
     force_compile: bool,
 ) -> Result<CompiledProgram, RuntimeError> {
-    let program = monomorphize(main_function, &context.def_interner);
+    let mut program = monomorphize(main_function, &context.def_interner);
+    if options.force_brillig {
+        program.force_brillig();
+    }
 
-    let hash = fxhash::hash64(&program);
+    // A program compiled with a different pipeline must not be taken from the cache
+    let hash = match &options.passes {
+        Some(pipeline) => fxhash::hash64(&(&program, pipeline)),
+        None => fxhash::hash64(&program),
+    };
     let hashes_match = cached_program.as_ref().map_or(false, |program| program.hash == hash);
 
     // If user has specified that they want to see intermediate steps printed then we should
     // force compilation even if the program hasn't changed.
-    let force_compile =
-        force_compile || options.print_acir || options.show_brillig || options.show_ssa;
+    let force_compile = force_compile
+        || options.print_acir
+        || options.show_brillig
+        || options.show_ssa
+        || options.show_inlining
+        || options.show_ssa_passes
+        || options.show_opcode_counts;
 
     if !force_compile && hashes_match {
         return Ok(cached_program.expect("cache must exist for hashes to match"));
     }
 
-    let (circuit, debug, input_witnesses, return_witnesses, warnings) =
-        create_circuit(program, options.show_ssa, options.show_brillig)?;
-
-    let abi = abi_gen::gen_abi(context, &main_function, input_witnesses, return_witnesses);
+    // Each entry point gets its own directory as contracts compile several of them
+    let ssa_dump_dir = options.show_ssa_passes.then(|| {
+        let dir = options.ssa_dump_dir.clone().unwrap_or_else(|| PathBuf::from("target/debug_ssa"));
+        dir.join(context.def_interner.function_name(&main_function))
+    });
+
+    let oracles = program.oracles.clone();
+    let pipeline = options.passes.clone().unwrap_or_default();
+    let (circuit, mut debug, input_witnesses, return_witnesses, warnings) = create_circuit(
+        program,
+        &pipeline,
+        options.show_ssa,
+        options.show_brillig,
+        options.show_inlining,
+        options.show_opcode_counts,
+        ssa_dump_dir.as_deref(),
+        options.np_language,
+    )?;
+
+    let abi =
+        abi_gen::gen_abi(context, &main_function, &oracles, input_witnesses, return_witnesses);
+    debug.functions = debug_functions(&context.def_interner, &debug);
     let file_map = filter_relevant_files(&[debug.clone()], &context.file_manager);
 
     Ok(CompiledProgram {
@@ -18,6 +18,27 @@ pub struct DebugInfo {
     /// that they should be serialized to/from strings.
     #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
     pub locations: BTreeMap<OpcodeLocation, Vec<Location>>,
+
+    /// The functions whose bodies contain any of the `locations`, used to name the functions
+    /// in the call stack of a runtime error.
+    #[serde(default)]
+    pub functions: Vec<DebugFunction>,
+}
+
+/// A function of the source program along with the location of its body.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DebugFunction {
+    pub name: String,
+    pub location: Location,
+}
+
+impl DebugFunction {
+    /// True if `location` is within the body of this function.
+    pub fn contains(&self, location: &Location) -> bool {
+        self.location.file == location.file
+            && self.location.span.start() <= location.span.start()
+            && location.span.end() <= self.location.span.end()
+    }
 }
 
 /// Holds OpCodes Counts for Acir and Brillig Opcodes
@@ -30,7 +51,7 @@ pub struct OpCodesCount {
 
 impl DebugInfo {
     pub fn new(locations: BTreeMap<OpcodeLocation, Vec<Location>>) -> Self {
-        DebugInfo { locations }
+        DebugInfo { locations, functions: Vec::new() }
     }
 
     /// Updates the locations map when the [`Circuit`][acvm::acir::circuit::Circuit] is modified.
@@ -52,6 +73,36 @@ impl DebugInfo {
         self.locations.get(loc).cloned()
     }
 
+    /// Returns the name of the innermost function whose body contains `location`, if known.
+    pub fn function_name(&self, location: &Location) -> Option<&str> {
+        self.functions
+            .iter()
+            .filter(|function| function.contains(location))
+            .min_by_key(|function| function.location.span.end() - function.location.span.start())
+            .map(|function| function.name.as_str())
+    }
+
+    /// Counts the opcodes generated by each of the `functions`, including those generated by
+    /// the functions it calls.
+    pub fn count_function_opcodes(&self) -> Vec<(&DebugFunction, OpCodesCount)> {
+        self.functions
+            .iter()
+            .map(|function| {
+                let mut count = OpCodesCount::default();
+                for (opcode_location, call_stack) in &self.locations {
+                    if !call_stack.iter().any(|location| function.contains(location)) {
+                        continue;
+                    }
+                    match opcode_location {
+                        OpcodeLocation::Acir(_) => count.acir_size += 1,
+                        OpcodeLocation::Brillig { .. } => count.brillig_size += 1,
+                    }
+                }
+                (function, count)
+            })
+            .collect()
+    }
+
     pub fn count_span_opcodes(&self) -> HashMap<Location, OpCodesCount> {
         let mut accumulator: HashMap<Location, Vec<&OpcodeLocation>> = HashMap::new();
 
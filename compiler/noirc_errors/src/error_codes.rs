@@ -0,0 +1,211 @@
+//! Stable codes identifying each kind of diagnostic reported by the compiler.
+//!
+//! A code is never reused or renumbered once it has been assigned, so that it can be searched for
+//! and referred to across versions of the compiler. Each code has an extended explanation with
+//! examples, printed by `nargo explain`, which is kept in the markdown file of the same name in the
+//! `error_codes` directory.
+//!
+//! Codes are grouped by the stage of compilation which reports them:
+//!
+//! - `E00xx`: lexing
+//! - `E01xx`: parsing
+//! - `E02xx`: collecting definitions and resolving paths
+//! - `E03xx`: name resolution
+//! - `E04xx`: type checking
+//! - `E05xx`: compile-time evaluation
+//! - `E06xx`: code generation
+//! - `E07xx`: compiling packages
+
+macro_rules! error_codes {
+    ($($code:ident,)*) => {
+        $(
+            pub const $code: &str = stringify!($code);
+        )*
+
+        /// Every error code along with its explanation, in ascending order.
+        pub const ERROR_CODES: &[(&str, &str)] = &[
+            $((stringify!($code), include_str!(concat!("error_codes/", stringify!($code), ".md"))),)*
+        ];
+    };
+}
+
+error_codes! {
+    E0001,
+    E0002,
+    E0003,
+    E0004,
+    E0005,
+    E0006,
+    E0007,
+    E0008,
+    E0009,
+    E0010,
+    E0100,
+    E0101,
+    E0102,
+    E0103,
+    E0104,
+    E0105,
+    E0106,
+    E0107,
+    E0108,
+    E0109,
+    E0110,
+    E0111,
+    E0112,
+    E0113,
+    E0114,
+    E0200,
+    E0201,
+    E0202,
+    E0203,
+    E0204,
+    E0205,
+    E0206,
+    E0207,
+    E0208,
+    E0209,
+    E0210,
+    E0211,
+    E0212,
+    E0213,
+    E0214,
+    E0215,
+    E0216,
+    E0217,
+    E0218,
+    E0219,
+    E0250,
+    E0251,
+    E0252,
+    E0253,
+    E0300,
+    E0301,
+    E0302,
+    E0303,
+    E0304,
+    E0305,
+    E0306,
+    E0307,
+    E0308,
+    E0309,
+    E0310,
+    E0311,
+    E0312,
+    E0313,
+    E0314,
+    E0315,
+    E0316,
+    E0317,
+    E0318,
+    E0319,
+    E0320,
+    E0321,
+    E0322,
+    E0323,
+    E0324,
+    E0325,
+    E0326,
+    E0327,
+    E0328,
+    E0329,
+    E0330,
+    E0331,
+    E0332,
+    E0333,
+    E0334,
+    E0335,
+    E0336,
+    E0337,
+    E0338,
+    E0339,
+    E0340,
+    E0341,
+    E0342,
+    E0343,
+    E0344,
+    E0345,
+    E0346,
+    E0347,
+    E0348,
+    E0349,
+    E0350,
+    E0400,
+    E0401,
+    E0402,
+    E0403,
+    E0404,
+    E0405,
+    E0406,
+    E0407,
+    E0408,
+    E0409,
+    E0410,
+    E0411,
+    E0412,
+    E0413,
+    E0414,
+    E0415,
+    E0416,
+    E0417,
+    E0418,
+    E0419,
+    E0420,
+    E0421,
+    E0422,
+    E0423,
+    E0424,
+    E0425,
+    E0426,
+    E0427,
+    E0428,
+    E0429,
+    E0430,
+    E0431,
+    E0432,
+    E0433,
+    E0434,
+    E0435,
+    E0436,
+    E0437,
+    E0438,
+    E0439,
+    E0440,
+    E0500,
+    E0501,
+    E0502,
+    E0503,
+    E0504,
+    E0505,
+    E0506,
+    E0507,
+    E0508,
+    E0600,
+    E0601,
+    E0602,
+    E0603,
+    E0604,
+    E0605,
+    E0606,
+    E0607,
+    E0608,
+    E0609,
+    E0610,
+    E0611,
+    E0612,
+    E0613,
+    E0650,
+    E0651,
+    E0700,
+    E0701,
+    E0702,
+    E0703,
+}
+
+/// Returns the extended explanation of `code`, or `None` if it isn't a known error code.
+pub fn explain(code: &str) -> Option<&'static str> {
+    ERROR_CODES
+        .iter()
+        .find(|(known_code, _)| *known_code == code)
+        .map(|(_, explanation)| *explanation)
+}
@@ -4,10 +4,11 @@
 #![warn(clippy::semicolon_if_nothing_returned)]
 
 pub mod debug_info;
+pub mod error_codes;
 mod position;
 pub mod reporter;
 pub use position::{Location, Position, Span, Spanned};
-pub use reporter::{CustomDiagnostic, DiagnosticKind};
+pub use reporter::{Applicability, CustomDiagnostic, DiagnosticKind, Suggestion};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileDiagnostic {
@@ -16,7 +17,15 @@ pub struct FileDiagnostic {
 
     /// An optional call stack to display the full runtime call stack
     /// leading up to a runtime error. If this is empty it will not be displayed.
-    pub call_stack: Vec<Location>,
+    pub call_stack: Vec<StackFrame>,
+}
+
+/// A single call in the call stack of a [`FileDiagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackFrame {
+    pub location: Location,
+    /// The name of the function which `location` is in, if it is known.
+    pub function: Option<String>,
 }
 
 impl FileDiagnostic {
@@ -25,6 +34,14 @@ impl FileDiagnostic {
     }
 
     pub fn with_call_stack(mut self, call_stack: Vec<Location>) -> Self {
+        self.call_stack = call_stack
+            .into_iter()
+            .map(|location| StackFrame { location, function: None })
+            .collect();
+        self
+    }
+
+    pub fn with_stack_frames(mut self, call_stack: Vec<StackFrame>) -> Self {
         self.call_stack = call_stack;
         self
     }
@@ -1,4 +1,4 @@
-use crate::{FileDiagnostic, Location, Span};
+use crate::{FileDiagnostic, Location, Span, StackFrame};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::files::Files;
 use codespan_reporting::term;
@@ -7,8 +7,12 @@ use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CustomDiagnostic {
     pub message: String,
+    /// The stable [error code][crate::error_codes] identifying this kind of diagnostic.
+    pub code: Option<&'static str>,
     pub secondaries: Vec<CustomLabel>,
     notes: Vec<String>,
+    helps: Vec<String>,
+    suggestions: Vec<Suggestion>,
     pub kind: DiagnosticKind,
 }
 
@@ -28,8 +32,11 @@ impl CustomDiagnostic {
     pub fn from_message(msg: &str) -> CustomDiagnostic {
         Self {
             message: msg.to_owned(),
+            code: None,
             secondaries: Vec::new(),
             notes: Vec::new(),
+            helps: Vec::new(),
+            suggestions: Vec::new(),
             kind: DiagnosticKind::Error,
         }
     }
@@ -41,8 +48,11 @@ impl CustomDiagnostic {
     ) -> CustomDiagnostic {
         CustomDiagnostic {
             message: primary_message,
+            code: None,
             secondaries: vec![CustomLabel::new(secondary_message, secondary_span)],
             notes: Vec::new(),
+            helps: Vec::new(),
+            suggestions: Vec::new(),
             kind: DiagnosticKind::Error,
         }
     }
@@ -54,8 +64,11 @@ impl CustomDiagnostic {
     ) -> CustomDiagnostic {
         CustomDiagnostic {
             message: primary_message,
+            code: None,
             secondaries: vec![CustomLabel::new(secondary_message, secondary_span)],
             notes: Vec::new(),
+            helps: Vec::new(),
+            suggestions: Vec::new(),
             kind: DiagnosticKind::Warning,
         }
     }
@@ -64,14 +77,51 @@ impl CustomDiagnostic {
         FileDiagnostic::new(file_id, self)
     }
 
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
     pub fn add_note(&mut self, message: String) {
         self.notes.push(message);
     }
 
+    /// Adds a message describing how the error might be fixed, shown after any notes.
+    pub fn add_help(&mut self, message: String) {
+        self.helps.push(message);
+    }
+
+    /// Adds a change to the source which might fix this diagnostic.
+    pub fn add_suggestion(&mut self, suggestion: Suggestion) {
+        self.suggestions.push(suggestion);
+    }
+
     pub fn add_secondary(&mut self, message: String, span: Span) {
         self.secondaries.push(CustomLabel::new(message, span));
     }
 
+    /// Adds a label pointing at a location which may be in a different file than the diagnostic,
+    /// such as the definition of an item used where the error occurred.
+    pub fn add_secondary_with_location(&mut self, message: String, location: Location) {
+        self.secondaries.push(CustomLabel {
+            message,
+            span: location.span,
+            file: Some(location.file),
+        });
+    }
+
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    pub fn helps(&self) -> &[String] {
+        &self.helps
+    }
+
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
     pub fn is_error(&self) -> bool {
         matches!(self.kind, DiagnosticKind::Error)
     }
@@ -93,6 +143,14 @@ impl std::fmt::Display for CustomDiagnostic {
             write!(f, "\nnote: {note}")?;
         }
 
+        for help in &self.helps {
+            write!(f, "\nhelp: {help}")?;
+        }
+
+        for suggestion in &self.suggestions {
+            write!(f, "\nhelp: {suggestion}")?;
+        }
+
         Ok(())
     }
 }
@@ -101,14 +159,56 @@ impl std::fmt::Display for CustomDiagnostic {
 pub struct CustomLabel {
     pub message: String,
     pub span: Span,
+    /// The file `span` is in, if it isn't the file of the diagnostic itself.
+    pub file: Option<fm::FileId>,
 }
 
 impl CustomLabel {
     fn new(message: String, span: Span) -> CustomLabel {
-        CustomLabel { message, span }
+        CustomLabel { message, span, file: None }
     }
 }
 
+/// A replacement of a span of source code which might fix a diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub message: String,
+    /// The span to replace, in the file of the diagnostic. An empty span inserts the replacement.
+    pub span: Span,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+impl Suggestion {
+    pub fn new(
+        message: impl Into<String>,
+        span: Span,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Suggestion {
+        Suggestion { message: message.into(), span, replacement: replacement.into(), applicability }
+    }
+}
+
+impl std::fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.replacement.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: `{}`", self.message, self.replacement)
+        }
+    }
+}
+
+/// How confident the compiler is that applying a [`Suggestion`] fixes the diagnostic.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended and may be applied automatically.
+    MachineApplicable,
+    /// The suggestion may be what the user intended but should be reviewed before applying it.
+    MaybeIncorrect,
+}
+
 /// Writes the given diagnostics to stderr and returns the count
 /// of diagnostics that were errors.
 pub fn report_all<'files>(
@@ -145,7 +245,7 @@ pub fn report<'files>(
     files: &'files impl Files<'files, FileId = fm::FileId>,
     custom_diagnostic: &CustomDiagnostic,
     file: Option<fm::FileId>,
-    call_stack: &[Location],
+    call_stack: &[StackFrame],
     deny_warnings: bool,
 ) -> bool {
     let writer = StandardStream::stderr(ColorChoice::Always);
@@ -164,25 +264,28 @@ fn convert_diagnostic(
     stack_trace: String,
     deny_warnings: bool,
 ) -> Diagnostic<fm::FileId> {
-    let diagnostic = match (cd.kind, deny_warnings) {
+    let mut diagnostic = match (cd.kind, deny_warnings) {
         (DiagnosticKind::Warning, false) => Diagnostic::warning(),
         _ => Diagnostic::error(),
     };
+    if let Some(code) = cd.code {
+        diagnostic = diagnostic.with_code(code);
+    }
 
-    let secondary_labels = if let Some(file_id) = file {
-        cd.secondaries
-            .iter()
-            .map(|sl| {
-                let start_span = sl.span.start() as usize;
-                let end_span = sl.span.end() as usize;
-                Label::secondary(file_id, start_span..end_span).with_message(&sl.message)
-            })
-            .collect()
-    } else {
-        vec![]
-    };
+    let secondary_labels = cd
+        .secondaries
+        .iter()
+        .filter_map(|sl| {
+            let file_id = sl.file.or(file)?;
+            let start_span = sl.span.start() as usize;
+            let end_span = sl.span.end() as usize;
+            Some(Label::secondary(file_id, start_span..end_span).with_message(&sl.message))
+        })
+        .collect();
 
     let mut notes = cd.notes.clone();
+    notes.extend(cd.helps.iter().map(|help| format!("help: {help}")));
+    notes.extend(cd.suggestions.iter().map(|suggestion| format!("help: {suggestion}")));
     notes.push(stack_trace);
 
     diagnostic.with_message(&cd.message).with_labels(secondary_labels).with_notes(notes)
@@ -190,7 +293,7 @@ fn convert_diagnostic(
 
 fn stack_trace<'files>(
     files: &'files impl Files<'files, FileId = fm::FileId>,
-    call_stack: &[Location],
+    call_stack: &[StackFrame],
 ) -> String {
     if call_stack.is_empty() {
         return String::new();
@@ -198,12 +301,21 @@ fn stack_trace<'files>(
 
     let mut result = "Call stack:\n".to_string();
 
-    for (i, call_item) in call_stack.iter().enumerate() {
+    for (i, frame) in call_stack.iter().enumerate() {
+        let call_item = frame.location;
         let path = files.name(call_item.file).expect("should get file path");
         let source = files.source(call_item.file).expect("should get file source");
 
         let (line, column) = location(source.as_ref(), call_item.span.start());
-        result += &format!("{}. {}:{}:{}\n", i + 1, path, line, column);
+        result += &format!("{}. {}:{}:{}", i + 1, path, line, column);
+        if let Some(function) = &frame.function {
+            result += &format!(" in {function}");
+        }
+        result.push('\n');
+
+        if let Some(source_line) = source.as_ref().lines().nth(line as usize - 1) {
+            result += &format!("       {}\n", source_line.trim());
+        }
     }
 
     result
@@ -8,10 +8,14 @@ mod variable_liveness;
 
 use self::{brillig_block::BrilligBlock, brillig_fn::FunctionContext};
 use super::brillig_ir::{artifact::BrilligArtifact, BrilligContext};
+use crate::errors::RuntimeError;
 use crate::ssa::ir::function::Function;
 
 /// Converting an SSA function into Brillig bytecode.
-pub(crate) fn convert_ssa_function(func: &Function, enable_debug_trace: bool) -> BrilligArtifact {
+pub(crate) fn convert_ssa_function(
+    func: &Function,
+    enable_debug_trace: bool,
+) -> Result<BrilligArtifact, RuntimeError> {
     let mut brillig_context = BrilligContext::new(enable_debug_trace);
 
     let mut function_context = FunctionContext::new(func, &mut brillig_context);
@@ -19,8 +23,117 @@ pub(crate) fn convert_ssa_function(func: &Function, enable_debug_trace: bool) ->
     brillig_context.enter_context(FunctionContext::function_id_to_function_label(func.id()));
 
     for block in function_context.blocks.clone() {
-        BrilligBlock::compile(&mut function_context, &mut brillig_context, block, &func.dfg);
+        BrilligBlock::compile(&mut function_context, &mut brillig_context, block, &func.dfg)?;
     }
 
-    brillig_context.artifact()
+    let mut artifact = brillig_context.artifact();
+    artifact.optimize();
+    Ok(artifact)
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::brillig::{BinaryIntOp, Opcode as BrilligOpcode};
+    use fxhash::FxHashSet as HashSet;
+
+    use crate::errors::RuntimeError;
+    use crate::ssa::function_builder::FunctionBuilder;
+    use crate::ssa::ir::{
+        function::RuntimeType,
+        instruction::{BinaryOp, Intrinsic},
+        map::Id,
+        types::Type,
+    };
+
+    use super::convert_ssa_function;
+
+    #[test]
+    fn reports_unsupported_intrinsics() {
+        // brillig fn main f0 {
+        //   b0(v0: Field):
+        //     call assert_constant(v0)
+        //     return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::field());
+        let assert_constant = builder.import_intrinsic_id(Intrinsic::AssertConstant);
+        builder.insert_call(assert_constant, vec![v0], vec![]);
+        builder.terminate_with_return(vec![]);
+        let ssa = builder.finish();
+
+        let result = convert_ssa_function(ssa.main(), false);
+        assert!(matches!(result, Err(RuntimeError::UnsupportedInBrillig { .. })));
+    }
+
+    #[test]
+    fn reuses_registers_of_dead_variables() {
+        // brillig fn main f0 {
+        //   b0(v0: Field, v1: Field):
+        //     v2 = add v0, v1
+        //     v3 = add v2, v1
+        //     ...
+        //     v21 = add v20, v1
+        //     return v21
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::field());
+        let mut sum = v0;
+        for _ in 0..20 {
+            sum = builder.insert_binary(sum, BinaryOp::Add, v1);
+        }
+        builder.terminate_with_return(vec![sum]);
+        let ssa = builder.finish();
+
+        let artifact = convert_ssa_function(ssa.main(), false).unwrap();
+        let destinations: HashSet<_> = artifact
+            .byte_code
+            .iter()
+            .filter_map(|opcode| match opcode {
+                BrilligOpcode::BinaryFieldOp { destination, .. } => Some(*destination),
+                _ => None,
+            })
+            .collect();
+        // Each sum dies once the next one is computed, so two registers are enough for all of them
+        assert_eq!(destinations.len(), 2);
+    }
+
+    /// Converts a function returning `v0 <operator> 8` for a u32 `v0` and returns the integer
+    /// operations in its bytecode.
+    fn integer_ops_by_constant_eight(operator: BinaryOp) -> Vec<BinaryIntOp> {
+        // brillig fn main f0 {
+        //   b0(v0: u32):
+        //     v2 = <operator> v0, u32 8
+        //     return v2
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let eight = builder.numeric_constant(8u128, Type::unsigned(32));
+        let v2 = builder.insert_binary(v0, operator, eight);
+        builder.terminate_with_return(vec![v2]);
+        let ssa = builder.finish();
+
+        let artifact = convert_ssa_function(ssa.main(), false).unwrap();
+        artifact
+            .byte_code
+            .iter()
+            .filter_map(|opcode| match opcode {
+                BrilligOpcode::BinaryIntOp { op, bit_size: 32, .. } => Some(*op),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unsigned_division_by_power_of_two_is_a_right_shift() {
+        assert_eq!(integer_ops_by_constant_eight(BinaryOp::Div), vec![BinaryIntOp::Shr]);
+    }
+
+    #[test]
+    fn unsigned_multiplication_by_power_of_two_is_a_left_shift() {
+        assert_eq!(integer_ops_by_constant_eight(BinaryOp::Mul), vec![BinaryIntOp::Shl]);
+    }
 }
@@ -4,6 +4,8 @@ use acvm::acir::{
 };
 
 use crate::brillig::brillig_ir::BrilligContext;
+use crate::errors::RuntimeError;
+use crate::ssa::ir::dfg::CallStack;
 
 /// Transforms SSA's black box function calls into the corresponding brillig instructions
 /// Extracting arguments and results from the SSA function call
@@ -13,7 +15,8 @@ pub(crate) fn convert_black_box_call(
     bb_func: &BlackBoxFunc,
     function_arguments: &[RegisterOrMemory],
     function_results: &[RegisterOrMemory],
-) {
+    call_stack: &CallStack,
+) -> Result<(), RuntimeError> {
     match bb_func {
         BlackBoxFunc::SHA256 => {
             if let ([message], [RegisterOrMemory::HeapArray(result_array)]) =
@@ -92,6 +95,27 @@ pub(crate) fn convert_black_box_call(
                 )
             }
         }
+        BlackBoxFunc::EcdsaSecp256r1 => {
+            if let (
+                [RegisterOrMemory::HeapArray(public_key_x), RegisterOrMemory::HeapArray(public_key_y), RegisterOrMemory::HeapArray(signature), message],
+                [RegisterOrMemory::RegisterIndex(result_register)],
+            ) = (function_arguments, function_results)
+            {
+                let message_hash_vector =
+                    convert_array_or_vector(brillig_context, message, bb_func);
+                brillig_context.black_box_op_instruction(BlackBoxOp::EcdsaSecp256r1 {
+                    hashed_msg: message_hash_vector,
+                    public_key_x: *public_key_x,
+                    public_key_y: *public_key_y,
+                    signature: *signature,
+                    result: *result_register,
+                });
+            } else {
+                unreachable!(
+                    "ICE: EcdsaSecp256r1 expects four array arguments and one register result"
+                )
+            }
+        }
         BlackBoxFunc::PedersenCommitment => {
             if let (
                 [message, RegisterOrMemory::RegisterIndex(domain_separator)],
@@ -160,8 +184,14 @@ pub(crate) fn convert_black_box_call(
                 )
             }
         }
-        _ => unimplemented!("ICE: Black box function {:?} is not implemented", bb_func),
+        _ => {
+            return Err(RuntimeError::UnsupportedInBrillig {
+                item: format!("The `{}` black box function", bb_func.name()),
+                call_stack: call_stack.clone(),
+            })
+        }
     }
+    Ok(())
 }
 
 fn convert_array_or_vector(
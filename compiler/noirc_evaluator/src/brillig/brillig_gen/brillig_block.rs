@@ -2,6 +2,7 @@ use crate::brillig::brillig_ir::{
     extract_heap_array, extract_register, extract_registers, BrilligBinaryOp, BrilligContext,
     BRILLIG_INTEGER_ARITHMETIC_BIT_SIZE,
 };
+use crate::errors::{InternalError, RuntimeError};
 use crate::ssa::ir::dfg::CallStack;
 use crate::ssa::ir::{
     basic_block::{BasicBlock, BasicBlockId},
@@ -44,7 +45,7 @@ impl<'block> BrilligBlock<'block> {
         brillig_context: &'block mut BrilligContext,
         block_id: BasicBlockId,
         dfg: &DataFlowGraph,
-    ) {
+    ) -> Result<(), RuntimeError> {
         let live_in = function_context.liveness.get_live_in(&block_id);
         let variables =
             BlockVariables::new(live_in.clone(), function_context.all_block_parameters());
@@ -61,21 +62,21 @@ impl<'block> BrilligBlock<'block> {
         let mut brillig_block =
             BrilligBlock { function_context, block_id, brillig_context, variables, last_uses };
 
-        brillig_block.convert_block(dfg);
+        brillig_block.convert_block(dfg)
     }
 
-    fn convert_block(&mut self, dfg: &DataFlowGraph) {
+    fn convert_block(&mut self, dfg: &DataFlowGraph) -> Result<(), RuntimeError> {
         // Add a label for this block
         let block_label = self.create_block_label_for_current_function(self.block_id);
         self.brillig_context.enter_context(block_label);
 
         // Convert the block parameters
         let block = &dfg[self.block_id];
-        self.convert_block_params(block, dfg);
+        self.convert_block_params(block, dfg)?;
 
         // Convert all of the instructions into the block
         for instruction_id in block.instructions() {
-            self.convert_ssa_instruction(*instruction_id, dfg);
+            self.convert_ssa_instruction(*instruction_id, dfg)?;
         }
 
         // Process the block's terminator instruction
@@ -83,6 +84,7 @@ impl<'block> BrilligBlock<'block> {
             block.terminator().expect("block is expected to be constructed");
 
         self.convert_ssa_terminator(terminator_instruction, dfg);
+        Ok(())
     }
 
     fn get_bit_size_from_ssa_type(typ: Type) -> u32 {
@@ -202,7 +204,11 @@ impl<'block> BrilligBlock<'block> {
     }
 
     /// Converts SSA Block parameters into Brillig Registers.
-    fn convert_block_params(&mut self, block: &BasicBlock, dfg: &DataFlowGraph) {
+    fn convert_block_params(
+        &mut self,
+        block: &BasicBlock,
+        dfg: &DataFlowGraph,
+    ) -> Result<(), RuntimeError> {
         for param_id in block.parameters() {
             let value = &dfg[*param_id];
             let param_type = match value {
@@ -223,16 +229,26 @@ impl<'block> BrilligBlock<'block> {
                     );
                 }
                 _ => {
-                    todo!("ICE: Param type not supported")
+                    return Err(InternalError::General {
+                        message: format!("Block parameters of type {param_type} are not supported"),
+                        call_stack: CallStack::new(),
+                    }
+                    .into());
                 }
             }
         }
+        Ok(())
     }
 
     /// Converts an SSA instruction into a sequence of Brillig opcodes.
-    fn convert_ssa_instruction(&mut self, instruction_id: InstructionId, dfg: &DataFlowGraph) {
+    fn convert_ssa_instruction(
+        &mut self,
+        instruction_id: InstructionId,
+        dfg: &DataFlowGraph,
+    ) -> Result<(), RuntimeError> {
         let instruction = &dfg[instruction_id];
-        self.brillig_context.set_call_stack(dfg.get_call_stack(instruction_id));
+        let call_stack = dfg.get_call_stack(instruction_id);
+        self.brillig_context.set_call_stack(call_stack.clone());
 
         match instruction {
             Instruction::Binary(binary) => {
@@ -373,7 +389,8 @@ impl<'block> BrilligBlock<'block> {
                         bb_func,
                         &function_arguments,
                         &function_results,
-                    );
+                        &call_stack,
+                    )?;
                 }
                 Value::Intrinsic(Intrinsic::ArrayLen) => {
                     let result_register = self.variables.define_register_variable(
@@ -481,8 +498,18 @@ impl<'block> BrilligBlock<'block> {
 
                     self.brillig_context.deallocate_register(radix);
                 }
+                Value::Intrinsic(intrinsic) => {
+                    return Err(RuntimeError::UnsupportedInBrillig {
+                        item: format!("The `{intrinsic}` builtin"),
+                        call_stack,
+                    });
+                }
                 _ => {
-                    unreachable!("unsupported function call type {:?}", dfg[*func])
+                    return Err(InternalError::General {
+                        message: format!("Unsupported function call type {:?}", dfg[*func]),
+                        call_stack,
+                    }
+                    .into());
                 }
             },
             Instruction::Truncate { value, .. } => {
@@ -510,7 +537,8 @@ impl<'block> BrilligBlock<'block> {
                     source_register,
                     target_type,
                     &dfg.type_of_value(*value),
-                );
+                    &call_stack,
+                )?;
             }
             Instruction::ArrayGet { array, index } => {
                 let result_ids = dfg.instruction_results(instruction_id);
@@ -574,7 +602,13 @@ impl<'block> BrilligBlock<'block> {
                 self.brillig_context.deallocate_register(condition);
                 self.brillig_context.deallocate_register(right);
             }
-            _ => todo!("ICE: Instruction not supported {instruction:?}"),
+            Instruction::EnableSideEffects { .. } => {
+                return Err(InternalError::General {
+                    message: format!("Instruction not supported in Brillig: {instruction:?}"),
+                    call_stack,
+                }
+                .into());
+            }
         };
 
         let dead_variables = self
@@ -583,9 +617,14 @@ impl<'block> BrilligBlock<'block> {
             .expect("Last uses for instruction should have been computed");
 
         for dead_variable in dead_variables {
-            self.variables.remove_variable(dead_variable);
+            self.variables.remove_variable(
+                dead_variable,
+                self.function_context,
+                self.brillig_context,
+            );
         }
         self.brillig_context.set_call_stack(CallStack::new());
+        Ok(())
     }
 
     fn convert_ssa_function_call(
@@ -1011,7 +1050,8 @@ impl<'block> BrilligBlock<'block> {
         source: RegisterIndex,
         target_type: &Type,
         source_type: &Type,
-    ) {
+        call_stack: &CallStack,
+    ) -> Result<(), RuntimeError> {
         fn numeric_to_bit_size(typ: &NumericType) -> u32 {
             match typ {
                 NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => *bit_size,
@@ -1019,12 +1059,19 @@ impl<'block> BrilligBlock<'block> {
             }
         }
         // Casting is only valid for numeric types
-        // This should be checked by the frontend, so we panic if this is the case
+        // This should be checked by the frontend, so this is an internal error if it isn't the case
         let (source_numeric_type, target_numeric_type) = match (source_type, target_type) {
             (Type::Numeric(source_numeric_type), Type::Numeric(target_numeric_type)) => {
                 (source_numeric_type, target_numeric_type)
             }
-            _ => unimplemented!("The cast operation is only valid for integers."),
+            _ => {
+                return Err(InternalError::UnExpected {
+                    expected: "a cast between numeric types".to_owned(),
+                    found: format!("a cast from {source_type} to {target_type}"),
+                    call_stack: call_stack.clone(),
+                }
+                .into())
+            }
         };
         let source_bit_size = numeric_to_bit_size(source_numeric_type);
         let target_bit_size = numeric_to_bit_size(target_numeric_type);
@@ -1042,6 +1089,51 @@ impl<'block> BrilligBlock<'block> {
         } else {
             self.brillig_context.mov_instruction(destination, source);
         }
+        Ok(())
+    }
+
+    /// Converts an unsigned multiplication, division or remainder by a constant power of 2 into
+    /// a cheaper bitwise operation, returning false if `binary` isn't one of these:
+    /// - `a * 2^k` and `2^k * a` become `a << k`
+    /// - `a / 2^k` becomes `a >> k`
+    /// - `a % 2^k` only keeps the low bits of `a`, so it becomes `a & (2^k - 1)`
+    ///
+    /// Brillig wraps integer operations to their bit size, so the shifted values wrap exactly
+    /// like the products they replace.
+    fn convert_power_of_two_binary(
+        &mut self,
+        binary: &Binary,
+        dfg: &DataFlowGraph,
+        bit_size: u32,
+        result_register: RegisterIndex,
+    ) -> bool {
+        let power_of_two =
+            |value| dfg.get_numeric_constant(value).filter(|constant| is_power_of_two(*constant));
+        let exponent = |power: FieldElement| FieldElement::from(power.num_bits() as u128 - 1);
+
+        let (operand, op, constant) = match binary.operator {
+            BinaryOp::Mul => match (power_of_two(binary.lhs), power_of_two(binary.rhs)) {
+                (_, Some(power)) => (binary.lhs, BinaryIntOp::Shl, exponent(power)),
+                (Some(power), None) => (binary.rhs, BinaryIntOp::Shl, exponent(power)),
+                (None, None) => return false,
+            },
+            BinaryOp::Div => match power_of_two(binary.rhs) {
+                Some(power) => (binary.lhs, BinaryIntOp::Shr, exponent(power)),
+                None => return false,
+            },
+            BinaryOp::Mod => match power_of_two(binary.rhs) {
+                Some(power) => (binary.lhs, BinaryIntOp::And, power - FieldElement::one()),
+                None => return false,
+            },
+            _ => return false,
+        };
+
+        let left = self.convert_ssa_register_value(operand, dfg);
+        let right = self.brillig_context.make_constant(constant.into());
+        let operation = BrilligBinaryOp::Integer { op, bit_size };
+        self.brillig_context.binary_instruction(left, right, result_register, operation);
+        self.brillig_context.deallocate_register(right);
+        true
     }
 
     /// Converts the Binary instruction into a sequence of Brillig opcodes.
@@ -1054,9 +1146,43 @@ impl<'block> BrilligBlock<'block> {
         let binary_type =
             type_of_binary_operation(dfg[binary.lhs].get_type(), dfg[binary.rhs].get_type());
 
+        if let Type::Numeric(NumericType::Unsigned { bit_size }) = &binary_type {
+            if self.convert_power_of_two_binary(binary, dfg, *bit_size, result_register) {
+                return;
+            }
+        }
+
         let left = self.convert_ssa_register_value(binary.lhs, dfg);
         let right = self.convert_ssa_register_value(binary.rhs, dfg);
 
+        // `LessThan` compares its operands as unsigned integers. Flipping the sign bit of both
+        // operands maps the signed integers onto the unsigned ones while preserving their order.
+        if let (BinaryOp::Lt, Type::Numeric(NumericType::Signed { bit_size })) =
+            (binary.operator, &binary_type)
+        {
+            let sign_bit = self
+                .brillig_context
+                .make_constant(FieldElement::from(2_u128.pow(*bit_size - 1)).into());
+            let flip = BrilligBinaryOp::Integer { op: BinaryIntOp::Xor, bit_size: *bit_size };
+            let flipped_left = self.brillig_context.allocate_register();
+            let flipped_right = self.brillig_context.allocate_register();
+            self.brillig_context.binary_instruction(left, sign_bit, flipped_left, flip.clone());
+            self.brillig_context.binary_instruction(right, sign_bit, flipped_right, flip);
+
+            let operation =
+                BrilligBinaryOp::Integer { op: BinaryIntOp::LessThan, bit_size: *bit_size };
+            self.brillig_context.binary_instruction(
+                flipped_left,
+                flipped_right,
+                result_register,
+                operation,
+            );
+            self.brillig_context.deallocate_register(sign_bit);
+            self.brillig_context.deallocate_register(flipped_left);
+            self.brillig_context.deallocate_register(flipped_right);
+            return;
+        }
+
         let brillig_binary_op =
             convert_ssa_binary_op_to_brillig_binary_op(binary.operator, &binary_type);
 
@@ -1265,6 +1391,11 @@ pub(crate) fn type_of_binary_operation(lhs_type: &Type, rhs_type: &Type) -> Type
     }
 }
 
+/// Returns true if the constant is a power of 2 which fits in a u128.
+fn is_power_of_two(value: FieldElement) -> bool {
+    value.num_bits() <= 128 && value.to_u128().is_power_of_two()
+}
+
 /// Convert an SSA binary operation into:
 /// - Brillig Binary Integer Op, if it is a integer type
 /// - Brillig Binary Field Op, if it is a field type
@@ -2,7 +2,7 @@ use acvm::brillig_vm::brillig::{HeapArray, HeapVector, RegisterIndex, RegisterOr
 use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
 use crate::{
-    brillig::brillig_ir::{extract_register, BrilligContext},
+    brillig::brillig_ir::{extract_register, extract_registers, BrilligContext},
     ssa::ir::{
         basic_block::BasicBlockId,
         dfg::DataFlowGraph,
@@ -77,9 +77,26 @@ impl BlockVariables {
         extract_register(variable)
     }
 
-    /// Removes a variable so it's not used anymore within this block.
-    pub(crate) fn remove_variable(&mut self, value_id: &ValueId) {
-        self.available_variables.remove(value_id);
+    /// Removes a variable so it's not used anymore within this block, freeing its registers.
+    /// Block parameters keep their registers since these are allocated for the whole function.
+    pub(crate) fn remove_variable(
+        &mut self,
+        value_id: &ValueId,
+        function_context: &FunctionContext,
+        brillig_context: &mut BrilligContext,
+    ) {
+        if !self.available_variables.remove(value_id)
+            || function_context.block_parameters.values().any(|params| params.contains(value_id))
+        {
+            return;
+        }
+        let variable = function_context
+            .ssa_value_allocations
+            .get(value_id)
+            .unwrap_or_else(|| panic!("ICE: Value not found in cache {value_id}"));
+        for register in extract_registers(*variable) {
+            brillig_context.deallocate_register(register);
+        }
     }
 
     /// For a given SSA value id, return the corresponding cached allocation.
@@ -9,6 +9,7 @@ pub(crate) mod debug_show;
 pub(crate) mod registers;
 
 mod entry_point;
+mod peephole;
 
 use crate::ssa::ir::dfg::CallStack;
 
@@ -25,7 +26,7 @@ use acvm::{
 };
 use debug_show::DebugShow;
 
-/// Integer arithmetic in Brillig is limited to 127 bit
+/// Integer arithmetic in Brillig is limited to 128 bit
 /// integers.
 ///
 /// We could lift this in the future and have Brillig
@@ -35,7 +36,7 @@ use debug_show::DebugShow;
 /// Since constrained functions do not have this property, it
 /// would mean that unconstrained functions will differ from
 /// constrained functions in terms of syntax compatibility.
-pub(crate) const BRILLIG_INTEGER_ARITHMETIC_BIT_SIZE: u32 = 127;
+pub(crate) const BRILLIG_INTEGER_ARITHMETIC_BIT_SIZE: u32 = 128;
 /// The Brillig VM does not apply a limit to the memory address space,
 /// As a convention, we take use 64 bits. This means that we assume that
 /// memory has 2^64 memory slots.
@@ -355,8 +356,7 @@ impl BrilligContext {
     }
 
     /// Push a register to the deallocation list, ready for reuse.
-    /// TODO(AD): currently, register deallocation is only done with immediate values.
-    /// TODO(AD): See https://github.com/noir-lang/noir/issues/1720
+    /// Registers are deallocated once the temporary or SSA variable they hold is no longer used.
     pub(crate) fn deallocate_register(&mut self, register_index: RegisterIndex) {
         self.registers.deallocate_register(register_index);
     }
@@ -1,8 +1,10 @@
 use acvm::acir::brillig::Opcode as BrilligOpcode;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use crate::ssa::ir::dfg::CallStack;
 
+use super::peephole::simplify;
+
 /// Represents a parameter or a return value of a function.
 #[derive(Debug, Clone)]
 pub(crate) enum BrilligParameter {
@@ -78,6 +80,53 @@ impl BrilligArtifact {
         }
     }
 
+    /// Simplifies the bytecode of a function with a peephole pass, moving the labels, jumps and
+    /// metadata of the opcodes which are kept to their new positions.
+    ///
+    /// This must be called before the artifact is linked, while all of its jumps go through labels.
+    pub(crate) fn optimize(&mut self) {
+        let block_starts: BTreeSet<_> = self.labels.values().copied().collect();
+        let jump_targets: BTreeMap<_, _> = self
+            .unresolved_jumps
+            .iter()
+            .filter_map(|(position, label)| Some((*position, *self.labels.get(label)?)))
+            .collect();
+        let keep = simplify(&mut self.byte_code, &block_starts, &jump_targets);
+
+        // The new position of each opcode, followed by the new length of the bytecode, so that
+        // labels pointing at removed opcodes or at the end of the bytecode can be moved too.
+        let mut new_positions = Vec::with_capacity(keep.len() + 1);
+        let mut kept_count = 0;
+        for is_kept in &keep {
+            new_positions.push(kept_count);
+            kept_count += usize::from(*is_kept);
+        }
+        new_positions.push(kept_count);
+
+        let mut keep_opcodes = keep.iter();
+        self.byte_code.retain(|_| *keep_opcodes.next().unwrap());
+
+        for position in self.labels.values_mut() {
+            *position = new_positions[*position];
+        }
+        for jumps in [&mut self.unresolved_jumps, &mut self.unresolved_external_call_labels] {
+            jumps.retain(|(position, _)| keep[*position]);
+            for (position, _) in jumps.iter_mut() {
+                *position = new_positions[*position];
+            }
+        }
+        self.assert_messages = std::mem::take(&mut self.assert_messages)
+            .into_iter()
+            .filter(|(position, _)| keep[*position])
+            .map(|(position, message)| (new_positions[position], message))
+            .collect();
+        self.locations = std::mem::take(&mut self.locations)
+            .into_iter()
+            .filter(|(position, _)| keep[*position])
+            .map(|(position, call_stack)| (new_positions[position], call_stack))
+            .collect();
+    }
+
     /// Gets the first unresolved function call of this artifact.
     pub(crate) fn first_unresolved_function_call(&self) -> Option<Label> {
         self.unresolved_external_call_labels.first().map(|(_, label)| label.clone())
@@ -6,7 +6,7 @@ use super::{
     registers::BrilligRegistersContext,
     BrilligContext,
 };
-use acvm::acir::brillig::{Opcode as BrilligOpcode, RegisterIndex};
+use acvm::acir::brillig::{BinaryIntOp, Opcode as BrilligOpcode, RegisterIndex};
 
 impl BrilligContext {
     /// Creates an entry point artifact that will jump to the function label provided.
@@ -18,7 +18,7 @@ impl BrilligContext {
         let mut context = BrilligContext {
             obj: BrilligArtifact::default(),
             registers: BrilligRegistersContext::new(),
-            context_label: String::default(),
+            context_label: "entry_point".to_string(),
             section_label: 0,
             debug_show: DebugShow::new(false),
         };
@@ -92,6 +92,7 @@ impl BrilligContext {
     }
 
     /// Deflatten an array by recursively allocating nested arrays and copying the plain values.
+    /// The items are copied in a loop so that the bytecode does not grow with the array length.
     fn deflatten_array(
         &mut self,
         item_type: &[BrilligParameter],
@@ -106,57 +107,64 @@ impl BrilligContext {
 
         self.allocate_fixed_length_array(deflattened_array_pointer, item_count * target_item_size);
 
-        for item_index in 0..item_count {
-            let source_item_base_index = item_index * source_item_size;
-            let target_item_base_index = item_index * target_item_size;
+        let item_count_register = self.make_constant(item_count.into());
+        self.loop_instruction(item_count_register, |ctx, item_index| {
+            let source_item_base_index = ctx.allocate_register();
+            ctx.usize_op(item_index, source_item_base_index, BinaryIntOp::Mul, source_item_size);
+            let target_item_base_index = ctx.allocate_register();
+            ctx.usize_op(item_index, target_item_base_index, BinaryIntOp::Mul, target_item_size);
 
             let mut source_offset = 0;
 
             for (subitem_index, subitem) in item_type.iter().enumerate() {
-                let source_index =
-                    self.make_constant((source_item_base_index + source_offset).into());
+                let source_index = ctx.allocate_register();
+                ctx.usize_op(source_item_base_index, source_index, BinaryIntOp::Add, source_offset);
 
-                let target_index =
-                    self.make_constant((target_item_base_index + subitem_index).into());
+                let target_index = ctx.allocate_register();
+                ctx.usize_op(target_item_base_index, target_index, BinaryIntOp::Add, subitem_index);
 
                 match subitem {
                     BrilligParameter::Simple => {
-                        self.array_get(flattened_array_pointer, source_index, movement_register);
-                        self.array_set(deflattened_array_pointer, target_index, movement_register);
+                        ctx.array_get(flattened_array_pointer, source_index, movement_register);
+                        ctx.array_set(deflattened_array_pointer, target_index, movement_register);
                         source_offset += 1;
                     }
                     BrilligParameter::Array(nested_array_item_type, nested_array_item_count) => {
-                        let nested_array_pointer = self.allocate_register();
-                        self.mov_instruction(nested_array_pointer, flattened_array_pointer);
-                        self.memory_op(
-                            nested_array_pointer,
+                        let nested_array_pointer = ctx.allocate_register();
+                        ctx.memory_op(
+                            flattened_array_pointer,
                             source_index,
                             nested_array_pointer,
-                            acvm::brillig_vm::brillig::BinaryIntOp::Add,
+                            BinaryIntOp::Add,
                         );
-                        let deflattened_nested_array_pointer = self.deflatten_array(
+                        let deflattened_nested_array_pointer = ctx.deflatten_array(
                             nested_array_item_type,
                             *nested_array_item_count,
                             nested_array_pointer,
                         );
-                        self.array_set(
+                        ctx.array_set(
                             deflattened_array_pointer,
                             target_index,
                             deflattened_nested_array_pointer,
                         );
 
-                        self.deallocate_register(nested_array_pointer);
+                        ctx.deallocate_register(nested_array_pointer);
+                        ctx.deallocate_register(deflattened_nested_array_pointer);
 
                         source_offset += BrilligContext::flattened_size(subitem);
                     }
                     BrilligParameter::Slice(..) => unreachable!("ICE: Cannot deflatten slices"),
                 }
 
-                self.deallocate_register(source_index);
-                self.deallocate_register(target_index);
+                ctx.deallocate_register(source_index);
+                ctx.deallocate_register(target_index);
             }
-        }
 
+            ctx.deallocate_register(source_item_base_index);
+            ctx.deallocate_register(target_item_base_index);
+        });
+
+        self.deallocate_register(item_count_register);
         self.deallocate_register(movement_register);
 
         deflattened_array_pointer
@@ -206,6 +214,7 @@ impl BrilligContext {
     }
 
     // Flattens an array by recursively copying nested arrays and regular items.
+    // The items are copied in a loop so that the bytecode does not grow with the array length.
     fn flatten_array(
         &mut self,
         item_type: &[BrilligParameter],
@@ -218,66 +227,68 @@ impl BrilligContext {
         let source_item_size = item_type.len();
         let target_item_size: usize = item_type.iter().map(BrilligContext::flattened_size).sum();
 
-        for item_index in 0..item_count {
-            let source_item_base_index = item_index * source_item_size;
-            let target_item_base_index = item_index * target_item_size;
+        let item_count_register = self.make_constant(item_count.into());
+        self.loop_instruction(item_count_register, |ctx, item_index| {
+            let source_item_base_index = ctx.allocate_register();
+            ctx.usize_op(item_index, source_item_base_index, BinaryIntOp::Mul, source_item_size);
+            let target_item_base_index = ctx.allocate_register();
+            ctx.usize_op(item_index, target_item_base_index, BinaryIntOp::Mul, target_item_size);
 
             let mut target_offset = 0;
 
             for (subitem_index, subitem) in item_type.iter().enumerate() {
-                let source_index =
-                    self.make_constant((source_item_base_index + subitem_index).into());
-                let target_index =
-                    self.make_constant((target_item_base_index + target_offset).into());
+                let source_index = ctx.allocate_register();
+                ctx.usize_op(source_item_base_index, source_index, BinaryIntOp::Add, subitem_index);
+
+                let target_index = ctx.allocate_register();
+                ctx.usize_op(target_item_base_index, target_index, BinaryIntOp::Add, target_offset);
 
                 match subitem {
                     BrilligParameter::Simple => {
-                        self.array_get(deflattened_array_pointer, source_index, movement_register);
-                        self.array_set(flattened_array_pointer, target_index, movement_register);
+                        ctx.array_get(deflattened_array_pointer, source_index, movement_register);
+                        ctx.array_set(flattened_array_pointer, target_index, movement_register);
                         target_offset += 1;
                     }
                     BrilligParameter::Array(nested_array_item_type, nested_array_item_count) => {
-                        let nested_array_pointer = self.allocate_register();
-                        self.array_get(
+                        let nested_array_pointer = ctx.allocate_register();
+                        ctx.array_get(
                             deflattened_array_pointer,
                             source_index,
                             nested_array_pointer,
                         );
 
-                        let flattened_nested_array_pointer = self.allocate_register();
-
-                        self.mov_instruction(
-                            flattened_nested_array_pointer,
+                        let flattened_nested_array_pointer = ctx.allocate_register();
+                        ctx.memory_op(
                             flattened_array_pointer,
-                        );
-
-                        self.memory_op(
-                            flattened_nested_array_pointer,
                             target_index,
                             flattened_nested_array_pointer,
-                            acvm::brillig_vm::brillig::BinaryIntOp::Add,
+                            BinaryIntOp::Add,
                         );
 
-                        self.flatten_array(
+                        ctx.flatten_array(
                             nested_array_item_type,
                             *nested_array_item_count,
                             flattened_nested_array_pointer,
                             nested_array_pointer,
                         );
 
-                        self.deallocate_register(nested_array_pointer);
-                        self.deallocate_register(flattened_nested_array_pointer);
+                        ctx.deallocate_register(nested_array_pointer);
+                        ctx.deallocate_register(flattened_nested_array_pointer);
 
                         target_offset += BrilligContext::flattened_size(subitem);
                     }
                     BrilligParameter::Slice(..) => unreachable!("ICE: Cannot flatten slices"),
                 }
 
-                self.deallocate_register(source_index);
-                self.deallocate_register(target_index);
+                ctx.deallocate_register(source_index);
+                ctx.deallocate_register(target_index);
             }
-        }
 
+            ctx.deallocate_register(source_item_base_index);
+            ctx.deallocate_register(target_item_base_index);
+        });
+
+        self.deallocate_register(item_count_register);
         self.deallocate_register(movement_register);
     }
 }
@@ -419,4 +430,25 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn entry_point_bytecode_does_not_grow_with_array_length() {
+        let bytecode_length = |item_count| {
+            let array_param = BrilligParameter::Array(
+                vec![
+                    BrilligParameter::Simple,
+                    BrilligParameter::Array(vec![BrilligParameter::Simple], 2),
+                ],
+                item_count,
+            );
+            let mut context = create_context();
+            let array_pointer = context.allocate_register();
+            context.return_instruction(&[array_pointer]);
+            create_entry_point_bytecode(context, vec![array_param.clone()], vec![array_param])
+                .byte_code
+                .len()
+        };
+
+        assert_eq!(bytecode_length(2), bytecode_length(100));
+    }
 }
@@ -0,0 +1,332 @@
+//! A peephole pass over the bytecode of a single function, run before it is linked.
+//!
+//! Brillig generation emits opcodes one SSA instruction at a time, so the bytecode holds constants
+//! which are loaded again into registers already holding them, moves between registers holding the
+//! same value and jumps to the opcode which follows them. This pass folds operations on constants,
+//! removes opcodes which don't change any register and removes code which can't be reached.
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use acvm::{
+    acir::brillig::{BinaryFieldOp, BinaryIntOp, Opcode as BrilligOpcode, RegisterIndex, Value},
+    FieldElement,
+};
+use num_bigint::BigUint;
+
+use super::artifact::OpcodeLocation;
+
+/// What is known about the value of a register at some point of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KnownValue {
+    Constant(Value),
+    /// The register holds the same value as another register, which isn't itself a copy.
+    CopyOf(RegisterIndex),
+}
+
+/// The values known of registers since the start of the current block.
+///
+/// Blocks start at every label, as a jump may reach them with registers holding any values.
+#[derive(Default)]
+struct KnownRegisters(HashMap<RegisterIndex, KnownValue>);
+
+impl KnownRegisters {
+    fn constant(&self, register: RegisterIndex) -> Option<Value> {
+        match self.0.get(&register) {
+            Some(KnownValue::Constant(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the register which `register` is a copy of, or `register` itself.
+    fn root(&self, register: RegisterIndex) -> RegisterIndex {
+        match self.0.get(&register) {
+            Some(KnownValue::CopyOf(root)) => *root,
+            _ => register,
+        }
+    }
+
+    /// Records that `register` was written to, holding `value` if it's known.
+    fn write(&mut self, register: RegisterIndex, value: Option<KnownValue>) {
+        self.0.remove(&register);
+        self.0.retain(|_, known| *known != KnownValue::CopyOf(register));
+        if let Some(value) = value {
+            self.0.insert(register, value);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Simplifies the opcodes of a function in place, returning whether each of them should be kept.
+///
+/// `block_starts` holds the position of every label and `jump_targets` maps the position of every
+/// jump to the position of the label it jumps to. Opcodes which aren't kept must be removed and
+/// labels pointing at them moved to the next opcode which is kept.
+pub(crate) fn simplify(
+    byte_code: &mut [BrilligOpcode],
+    block_starts: &BTreeSet<OpcodeLocation>,
+    jump_targets: &BTreeMap<OpcodeLocation, OpcodeLocation>,
+) -> Vec<bool> {
+    let mut keep = vec![true; byte_code.len()];
+    let mut known = KnownRegisters::default();
+    let mut reachable = true;
+    for (position, opcode) in byte_code.iter_mut().enumerate() {
+        if block_starts.contains(&position) {
+            known.clear();
+            reachable = true;
+        }
+        // The `Stop` of a function is kept as linking turns it into the function's `Return`.
+        if !reachable && *opcode != BrilligOpcode::Stop {
+            keep[position] = false;
+            continue;
+        }
+
+        match simplify_opcode(opcode, &mut known) {
+            Some(simplified) => *opcode = simplified,
+            None => {
+                keep[position] = false;
+                continue;
+            }
+        }
+        if matches!(
+            opcode,
+            BrilligOpcode::Jump { .. }
+                | BrilligOpcode::Return
+                | BrilligOpcode::Trap
+                | BrilligOpcode::Stop
+        ) {
+            reachable = false;
+        }
+    }
+
+    // Jumps to the next opcode which is kept don't change the flow of the program.
+    for (&position, &target) in jump_targets {
+        if keep[position] && target > position && (position + 1..target).all(|next| !keep[next]) {
+            keep[position] = false;
+        }
+    }
+
+    keep
+}
+
+/// Returns a simpler opcode with the same effect on the registers as `opcode`, or `None` if the
+/// opcode has no effect at all, recording the values of the registers it writes to in `known`.
+fn simplify_opcode(opcode: &BrilligOpcode, known: &mut KnownRegisters) -> Option<BrilligOpcode> {
+    match opcode {
+        BrilligOpcode::Const { destination, value } => set_constant(*destination, *value, known),
+        BrilligOpcode::Mov { destination, source } => {
+            let source = known.root(*source);
+            if known.root(*destination) == source {
+                return None;
+            }
+            let value = match known.constant(source) {
+                Some(value) if known.constant(*destination) == Some(value) => return None,
+                Some(value) => KnownValue::Constant(value),
+                None => KnownValue::CopyOf(source),
+            };
+            known.write(*destination, Some(value));
+            Some(BrilligOpcode::Mov { destination: *destination, source })
+        }
+        BrilligOpcode::BinaryFieldOp { destination, op, lhs, rhs } => {
+            let (lhs_value, rhs_value) = (known.constant(*lhs), known.constant(*rhs));
+            if let (Some(lhs), Some(rhs)) = (lhs_value, rhs_value) {
+                if let Some(result) = fold_field_op(*op, lhs.to_field(), rhs.to_field()) {
+                    return set_constant(*destination, result.into(), known);
+                }
+            }
+
+            let is_zero = |value: Option<Value>| value.map_or(false, |value| value.is_zero());
+            let is_one =
+                |value: Option<Value>| value.map_or(false, |value| value.to_field().is_one());
+            let identity_operand = match op {
+                BinaryFieldOp::Add if is_zero(rhs_value) => Some(lhs),
+                BinaryFieldOp::Add if is_zero(lhs_value) => Some(rhs),
+                BinaryFieldOp::Sub if is_zero(rhs_value) => Some(lhs),
+                BinaryFieldOp::Mul | BinaryFieldOp::Div if is_one(rhs_value) => Some(lhs),
+                BinaryFieldOp::Mul if is_one(lhs_value) => Some(rhs),
+                _ => None,
+            };
+            match identity_operand {
+                Some(source) => simplify_opcode(
+                    &BrilligOpcode::Mov { destination: *destination, source: *source },
+                    known,
+                ),
+                None => {
+                    known.write(*destination, None);
+                    Some(opcode.clone())
+                }
+            }
+        }
+        BrilligOpcode::BinaryIntOp { destination, op, bit_size, lhs, rhs } => {
+            // Integer operations truncate their operands to `bit_size`, which is how casts are
+            // emitted, so adding zero isn't a no-op unless the operand is a known constant.
+            if let (Some(lhs), Some(rhs)) = (known.constant(*lhs), known.constant(*rhs)) {
+                if let Some(result) = fold_int_op(*op, lhs, rhs, *bit_size) {
+                    return set_constant(*destination, result, known);
+                }
+            }
+            known.write(*destination, None);
+            Some(opcode.clone())
+        }
+        BrilligOpcode::JumpIf { condition, location } => match known.constant(*condition) {
+            Some(condition) if condition.is_zero() => None,
+            Some(_) => Some(BrilligOpcode::Jump { location: *location }),
+            None => Some(opcode.clone()),
+        },
+        BrilligOpcode::JumpIfNot { condition, location } => match known.constant(*condition) {
+            Some(condition) if condition.is_zero() => {
+                Some(BrilligOpcode::Jump { location: *location })
+            }
+            Some(_) => None,
+            None => Some(opcode.clone()),
+        },
+        BrilligOpcode::Load { destination, .. } => {
+            known.write(*destination, None);
+            Some(opcode.clone())
+        }
+        // Foreign calls and black box functions may write to any of the registers of their outputs,
+        // and called functions to any register at all.
+        BrilligOpcode::Call { .. }
+        | BrilligOpcode::ForeignCall { .. }
+        | BrilligOpcode::BlackBox(_) => {
+            known.clear();
+            Some(opcode.clone())
+        }
+        BrilligOpcode::Jump { .. }
+        | BrilligOpcode::Store { .. }
+        | BrilligOpcode::Return
+        | BrilligOpcode::Trap
+        | BrilligOpcode::Stop => Some(opcode.clone()),
+    }
+}
+
+/// Emits a `Const` opcode, unless `destination` already holds `value`.
+fn set_constant(
+    destination: RegisterIndex,
+    value: Value,
+    known: &mut KnownRegisters,
+) -> Option<BrilligOpcode> {
+    if known.constant(destination) == Some(value) {
+        return None;
+    }
+    known.write(destination, Some(KnownValue::Constant(value)));
+    Some(BrilligOpcode::Const { destination, value })
+}
+
+/// Evaluates a field operation on constants as the VM does.
+///
+/// Divisions are left to the VM, so that a division by zero still happens at runtime.
+fn fold_field_op(op: BinaryFieldOp, lhs: FieldElement, rhs: FieldElement) -> Option<FieldElement> {
+    match op {
+        BinaryFieldOp::Add => Some(lhs + rhs),
+        BinaryFieldOp::Sub => Some(lhs - rhs),
+        BinaryFieldOp::Mul => Some(lhs * rhs),
+        BinaryFieldOp::Equals => Some((lhs == rhs).into()),
+        BinaryFieldOp::Div => None,
+    }
+}
+
+/// Evaluates an integer operation on constants as the VM does, modulo 2^`bit_size`.
+///
+/// Divisions, which may fail, and shifts, which panic on some operands, are left to the VM.
+fn fold_int_op(op: BinaryIntOp, lhs: Value, rhs: Value, bit_size: u32) -> Option<Value> {
+    let modulus = BigUint::from(1u32) << bit_size;
+    let lhs = BigUint::from_bytes_be(&lhs.to_field().to_be_bytes()) % &modulus;
+    let rhs = BigUint::from_bytes_be(&rhs.to_field().to_be_bytes()) % &modulus;
+    let result = match op {
+        BinaryIntOp::Add => (lhs + rhs) % &modulus,
+        BinaryIntOp::Sub => (&modulus + lhs - rhs) % &modulus,
+        BinaryIntOp::Mul => (lhs * rhs) % &modulus,
+        BinaryIntOp::Equals => BigUint::from(u32::from(lhs == rhs)),
+        BinaryIntOp::LessThan => BigUint::from(u32::from(lhs < rhs)),
+        BinaryIntOp::LessThanEquals => BigUint::from(u32::from(lhs <= rhs)),
+        BinaryIntOp::And => lhs & rhs,
+        BinaryIntOp::Or => lhs | rhs,
+        BinaryIntOp::Xor => lhs ^ rhs,
+        BinaryIntOp::SignedDiv | BinaryIntOp::UnsignedDiv | BinaryIntOp::Shl | BinaryIntOp::Shr => {
+            return None
+        }
+    };
+    Some(FieldElement::from_be_bytes_reduce(&result.to_bytes_be()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use acvm::acir::brillig::{BinaryIntOp, Opcode as BrilligOpcode, RegisterIndex, Value};
+
+    use super::simplify;
+
+    fn simplified(
+        mut byte_code: Vec<BrilligOpcode>,
+        block_starts: &[usize],
+        jump_targets: &[(usize, usize)],
+    ) -> Vec<BrilligOpcode> {
+        let block_starts = BTreeSet::from_iter(block_starts.iter().copied());
+        let jump_targets = BTreeMap::from_iter(jump_targets.iter().copied());
+        let keep = simplify(&mut byte_code, &block_starts, &jump_targets);
+        byte_code
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(opcode, keep)| keep.then_some(opcode))
+            .collect()
+    }
+
+    #[test]
+    fn folds_constants_and_removes_redundant_opcodes() {
+        let (r0, r1, r2, r3) =
+            (RegisterIndex(0), RegisterIndex(1), RegisterIndex(2), RegisterIndex(3));
+        let add = |destination, lhs, rhs| BrilligOpcode::BinaryIntOp {
+            destination,
+            op: BinaryIntOp::Add,
+            bit_size: 8,
+            lhs,
+            rhs,
+        };
+        let byte_code = vec![
+            BrilligOpcode::Const { destination: r0, value: Value::from(200_usize) },
+            BrilligOpcode::Const { destination: r1, value: Value::from(100_usize) },
+            add(r2, r0, r1),
+            // Reloading the constant and moving a register into a copy of it have no effect.
+            BrilligOpcode::Const { destination: r0, value: Value::from(200_usize) },
+            BrilligOpcode::Mov { destination: r3, source: r2 },
+            BrilligOpcode::Mov { destination: r2, source: r3 },
+            BrilligOpcode::Stop,
+        ];
+
+        let expected = vec![
+            BrilligOpcode::Const { destination: r0, value: Value::from(200_usize) },
+            BrilligOpcode::Const { destination: r1, value: Value::from(100_usize) },
+            // 200 + 100 wraps around to 44 in 8 bits.
+            BrilligOpcode::Const { destination: r2, value: Value::from(44_usize) },
+            BrilligOpcode::Mov { destination: r3, source: r2 },
+            BrilligOpcode::Stop,
+        ];
+        assert_eq!(simplified(byte_code, &[0], &[]), expected);
+    }
+
+    #[test]
+    fn removes_unreachable_code_and_jumps_to_the_next_opcode() {
+        let (r0, r1) = (RegisterIndex(0), RegisterIndex(1));
+        let byte_code = vec![
+            BrilligOpcode::JumpIf { condition: r0, location: 0 },
+            BrilligOpcode::Const { destination: r0, value: Value::from(1_usize) },
+            BrilligOpcode::JumpIf { condition: r0, location: 0 },
+            // Unreachable once the constant condition turns the `JumpIf` into a `Jump`.
+            BrilligOpcode::Trap,
+            BrilligOpcode::Mov { destination: r1, source: r0 },
+            BrilligOpcode::Jump { location: 0 },
+            BrilligOpcode::Stop,
+        ];
+
+        let expected = vec![
+            BrilligOpcode::JumpIf { condition: r0, location: 0 },
+            BrilligOpcode::Const { destination: r0, value: Value::from(1_usize) },
+            BrilligOpcode::Mov { destination: r1, source: r0 },
+            BrilligOpcode::Stop,
+        ];
+        assert_eq!(simplified(byte_code, &[0, 1, 4, 6], &[(0, 4), (2, 4), (5, 6)]), expected);
+    }
+}
@@ -70,8 +70,7 @@ impl BrilligRegistersContext {
     }
 
     /// Push a register to the deallocation list, ready for reuse.
-    /// TODO(AD): currently, register deallocation is only done with immediate values.
-    /// TODO(AD): See https://github.com/noir-lang/noir/issues/1720
+    /// Registers are deallocated once the temporary or SSA variable they hold is no longer used.
     pub(crate) fn deallocate_register(&mut self, register_index: RegisterIndex) {
         assert!(!self.deallocated_registers.contains(&register_index));
         self.deallocated_registers.push(register_index);
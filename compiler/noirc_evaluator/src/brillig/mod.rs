@@ -5,6 +5,7 @@ use self::{
     brillig_gen::{brillig_fn::FunctionContext, convert_ssa_function},
     brillig_ir::artifact::{BrilligArtifact, Label},
 };
+use crate::errors::RuntimeError;
 use crate::ssa::{
     ir::function::{Function, FunctionId, RuntimeType},
     ssa_gen::Ssa,
@@ -21,9 +22,14 @@ pub struct Brillig {
 
 impl Brillig {
     /// Compiles a function into brillig and store the compilation artifacts
-    pub(crate) fn compile(&mut self, func: &Function, enable_debug_trace: bool) {
-        let obj = convert_ssa_function(func, enable_debug_trace);
+    pub(crate) fn compile(
+        &mut self,
+        func: &Function,
+        enable_debug_trace: bool,
+    ) -> Result<(), RuntimeError> {
+        let obj = convert_ssa_function(func, enable_debug_trace)?;
         self.ssa_function_to_brillig.insert(func.id(), obj);
+        Ok(())
     }
 
     /// Finds a brillig function artifact by its function label
@@ -47,7 +53,7 @@ impl std::ops::Index<FunctionId> for Brillig {
 
 impl Ssa {
     /// Compile to brillig brillig functions and ACIR functions reachable from them
-    pub(crate) fn to_brillig(&self, enable_debug_trace: bool) -> Brillig {
+    pub(crate) fn to_brillig(&self, enable_debug_trace: bool) -> Result<Brillig, RuntimeError> {
         // Collect all the function ids that are reachable from brillig
         // That means all the functions marked as brillig and ACIR functions called by them
         let brillig_reachable_function_ids = self
@@ -59,9 +65,9 @@ impl Ssa {
         let mut brillig = Brillig::default();
         for brillig_function_id in brillig_reachable_function_ids {
             let func = &self.functions[&brillig_function_id];
-            brillig.compile(func, enable_debug_trace);
+            brillig.compile(func, enable_debug_trace)?;
         }
 
-        brillig
+        Ok(brillig)
     }
 }
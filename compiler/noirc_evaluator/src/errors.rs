@@ -9,7 +9,7 @@
 //! An Error of the latter is an error in the implementation of the compiler
 use acvm::{acir::native_types::Expression, FieldElement};
 use iter_extended::vecmap;
-use noirc_errors::{CustomDiagnostic as Diagnostic, FileDiagnostic};
+use noirc_errors::{error_codes as codes, CustomDiagnostic as Diagnostic, FileDiagnostic};
 use thiserror::Error;
 
 use crate::ssa::ir::{dfg::CallStack, types::NumericType};
@@ -42,6 +42,14 @@ pub enum RuntimeError {
     UnknownLoopBound { call_stack: CallStack },
     #[error("Argument is not constant")]
     AssertConstantFailed { call_stack: CallStack },
+    #[error("Static assertion failed: '{message}'")]
+    StaticAssertFailed { message: String, call_stack: CallStack },
+    #[error("The condition of a static assertion must be known at compile-time")]
+    StaticAssertDynamicPredicate { call_stack: CallStack },
+    #[error("Could not determine the maximum recursion depth at compile-time")]
+    UnboundedRecursion { call_stack: CallStack },
+    #[error("{item} is not supported in unconstrained functions")]
+    UnsupportedInBrillig { item: String, call_stack: CallStack },
 }
 
 // We avoid showing the actual lhs and rhs since most of the time they are just 0
@@ -64,6 +72,7 @@ impl From<SsaReport> for FileDiagnostic {
         match error {
             SsaReport::Warning(warning) => {
                 let message = warning.to_string();
+                let code = warning.code();
                 let (secondary_message, call_stack) = match warning {
                     InternalWarning::ReturnConstant { call_stack } => {
                         ("constant value".to_string(), call_stack)
@@ -76,7 +85,8 @@ impl From<SsaReport> for FileDiagnostic {
                 let file_id = call_stack.last().map(|location| location.file).unwrap_or_default();
                 let location = call_stack.last().expect("Expected RuntimeError to have a location");
                 let diagnostic =
-                    Diagnostic::simple_warning(message, secondary_message, location.span);
+                    Diagnostic::simple_warning(message, secondary_message, location.span)
+                        .with_code(code);
                 diagnostic.in_file(file_id).with_call_stack(call_stack)
             }
         }
@@ -91,6 +101,16 @@ pub enum InternalWarning {
     VerifyProof { call_stack: CallStack },
 }
 
+impl InternalWarning {
+    /// Returns the stable error code of this kind of warning.
+    pub fn code(&self) -> &'static str {
+        match self {
+            InternalWarning::ReturnConstant { .. } => codes::E0650,
+            InternalWarning::VerifyProof { .. } => codes::E0651,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Error)]
 pub enum InternalError {
     #[error("ICE: Both expressions should have degree<=1")]
@@ -128,17 +148,42 @@ impl RuntimeError {
             | RuntimeError::UnInitialized { call_stack, .. }
             | RuntimeError::UnknownLoopBound { call_stack }
             | RuntimeError::AssertConstantFailed { call_stack }
+            | RuntimeError::StaticAssertFailed { call_stack, .. }
+            | RuntimeError::StaticAssertDynamicPredicate { call_stack }
+            | RuntimeError::UnboundedRecursion { call_stack }
+            | RuntimeError::UnsupportedInBrillig { call_stack, .. }
             | RuntimeError::IntegerOutOfBounds { call_stack, .. }
             | RuntimeError::UnsupportedIntegerSize { call_stack, .. } => call_stack,
         }
     }
+
+    /// Returns the stable error code of this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RuntimeError::InternalError(_) => codes::E0600,
+            RuntimeError::FailedConstraint { .. } => codes::E0601,
+            RuntimeError::IndexOutOfBounds { .. } => codes::E0602,
+            RuntimeError::InvalidRangeConstraint { .. } => codes::E0603,
+            RuntimeError::IntegerOutOfBounds { .. } => codes::E0604,
+            RuntimeError::TypeConversion { .. } => codes::E0605,
+            RuntimeError::UnInitialized { .. } => codes::E0606,
+            RuntimeError::UnsupportedIntegerSize { .. } => codes::E0607,
+            RuntimeError::UnknownLoopBound { .. } => codes::E0608,
+            RuntimeError::AssertConstantFailed { .. } => codes::E0609,
+            RuntimeError::StaticAssertFailed { .. } => codes::E0610,
+            RuntimeError::StaticAssertDynamicPredicate { .. } => codes::E0611,
+            RuntimeError::UnboundedRecursion { .. } => codes::E0612,
+            RuntimeError::UnsupportedInBrillig { .. } => codes::E0613,
+        }
+    }
 }
 
 impl From<RuntimeError> for FileDiagnostic {
     fn from(error: RuntimeError) -> FileDiagnostic {
         let call_stack = vecmap(error.call_stack(), |location| *location);
         let file_id = call_stack.last().map(|location| location.file).unwrap_or_default();
-        let diagnostic = error.into_diagnostic();
+        let code = error.code();
+        let diagnostic = error.into_diagnostic().with_code(code);
         diagnostic.in_file(file_id).with_call_stack(call_stack)
     }
 }
@@ -146,20 +191,47 @@ impl From<RuntimeError> for FileDiagnostic {
 impl RuntimeError {
     fn into_diagnostic(self) -> Diagnostic {
         match self {
-            RuntimeError::InternalError(cause) => {
-                Diagnostic::simple_error(
-                    "Internal Consistency Evaluators Errors: \n
-                    This is likely a bug. Consider Opening an issue at https://github.com/noir-lang/noir/issues".to_owned(),
-                    cause.to_string(),
-                    noirc_errors::Span::inclusive(0, 0)
-                )
+            RuntimeError::InternalError(ref cause) => {
+                // Internal errors are reported at the code being compiled when it is known, so
+                // that the bug can be reproduced from the report.
+                let mut diagnostic = match self.call_stack().back() {
+                    Some(location) => Diagnostic::simple_error(
+                        "Internal compiler error".to_owned(),
+                        cause.to_string(),
+                        location.span,
+                    ),
+                    None => Diagnostic::from_message(&format!("Internal compiler error: {cause}")),
+                };
+                diagnostic.add_note(
+                    "This is a bug in the compiler. Consider opening an issue at https://github.com/noir-lang/noir/issues".to_owned(),
+                );
+                diagnostic
             }
-            _ => {
+            RuntimeError::UnknownLoopBound { .. } => {
                 let message = self.to_string();
                 let location =
                     self.call_stack().back().expect("Expected RuntimeError to have a location");
 
-                Diagnostic::simple_error(message, String::new(), location.span)
+                let mut diagnostic = Diagnostic::simple_error(
+                    message,
+                    "This bound must be known at compile-time".to_owned(),
+                    location.span,
+                );
+                diagnostic.add_note(
+                    "Loops are unrolled outside of unconstrained functions, so the range of a for loop must be known at compile-time. \
+                    Consider looping up to a constant bound and checking the actual bound with an `if` inside the loop, \
+                    adding a `#[max_iterations(N)]` attribute to the loop, or moving the loop into an unconstrained function".to_owned(),
+                );
+                diagnostic
+            }
+            _ => {
+                let message = self.to_string();
+                match self.call_stack().back() {
+                    Some(location) => {
+                        Diagnostic::simple_error(message, String::new(), location.span)
+                    }
+                    None => Diagnostic::from_message(&message),
+                }
             }
         }
     }
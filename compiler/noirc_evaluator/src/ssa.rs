@@ -7,22 +7,26 @@
 //! This module heavily borrows from Cranelift
 #![allow(dead_code)]
 
-use std::collections::BTreeSet;
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
 
 use crate::{
     brillig::Brillig,
     errors::{RuntimeError, SsaReport},
 };
-use acvm::acir::{
-    circuit::{Circuit, PublicInputs},
-    native_types::Witness,
+use acvm::{
+    acir::{
+        circuit::{Circuit, PublicInputs},
+        native_types::Witness,
+    },
+    Language,
 };
 
 use noirc_errors::debug_info::DebugInfo;
 
-use noirc_frontend::{
-    hir_def::function::FunctionSignature, monomorphization::ast::Program, Visibility,
-};
+use noirc_frontend::{hir_def::function::FunctionSignature, monomorphization::ast::Program};
 
 use self::{acir_gen::GeneratedAcir, ssa_gen::Ssa};
 
@@ -30,38 +34,40 @@ mod acir_gen;
 pub(super) mod function_builder;
 pub mod ir;
 mod opt;
+mod pipeline;
 pub mod ssa_gen;
 
+pub use pipeline::{SsaPass, SsaPipeline, SsaPipelineError};
+
 /// Optimize the given program by converting it into SSA
 /// form and performing optimizations there. When finished,
 /// convert the final SSA into ACIR and return it.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn optimize_into_acir(
     program: Program,
+    pipeline: &SsaPipeline,
     print_ssa_passes: bool,
     print_brillig_trace: bool,
+    print_inlining: bool,
+    print_opcode_counts: bool,
+    ssa_dump_dir: Option<&Path>,
+    np_language: Option<Language>,
 ) -> Result<GeneratedAcir, RuntimeError> {
     let abi_distinctness = program.return_distinctness;
 
-    let ssa_builder = SsaBuilder::new(program, print_ssa_passes)?
-        .run_pass(Ssa::defunctionalize, "After Defunctionalization:")
-        .run_pass(Ssa::inline_functions, "After Inlining:")
-        // Run mem2reg with the CFG separated into blocks
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .try_run_pass(Ssa::evaluate_assert_constant, "After Assert Constant:")?
-        .try_run_pass(Ssa::unroll_loops, "After Unrolling:")?
-        .run_pass(Ssa::simplify_cfg, "After Simplifying:")
-        // Run mem2reg before flattening to handle any promotion
-        // of values that can be accessed after loop unrolling.
-        // If there are slice mergers uncovered by loop unrolling
-        // and this pass is missed, slice merging will fail inside of flattening.
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .run_pass(Ssa::flatten_cfg, "After Flattening:")
-        // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .run_pass(Ssa::fold_constants, "After Constant Folding:")
-        .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:");
-
-    let brillig = ssa_builder.to_brillig(print_brillig_trace);
+    let mut ssa_builder =
+        SsaBuilder::new(program, print_ssa_passes, print_opcode_counts, ssa_dump_dir)?
+            .run_pass(Ssa::defunctionalize, "After Defunctionalization:")
+            .try_run_pass(
+                |ssa| ssa.inline_functions_with_report(print_inlining),
+                "After Inlining:",
+            )?;
+
+    for pass in pipeline.passes() {
+        ssa_builder = ssa_builder.try_run_pass(|ssa| pass.run(ssa), pass.message())?;
+    }
+
+    let brillig = ssa_builder.to_brillig(print_brillig_trace)?;
 
     // Split off any passes the are not necessary for Brillig generation but are necessary for ACIR generation.
     // We only need to fill out nested slices as we need to have a known length when dealing with memory operations
@@ -71,21 +77,43 @@ pub(crate) fn optimize_into_acir(
         .finish();
 
     let last_array_uses = ssa.find_last_array_uses();
-    ssa.into_acir(brillig, abi_distinctness, &last_array_uses)
+    ssa.into_acir(brillig, abi_distinctness, &last_array_uses, np_language)
 }
 
 /// Compiles the [`Program`] into [`ACIR`][acvm::acir::circuit::Circuit].
 ///
 /// The output ACIR is is backend-agnostic and so must go through a transformation pass before usage in proof generation.
-#[allow(clippy::type_complexity)]
+///
+/// The SSA passes run after inlining are given by `pipeline`.
+///
+/// If `ssa_dump_dir` is given, the SSA after each optimization pass is written to a numbered file
+/// within it. If `np_language` is given, each arithmetic opcode is fit to the backend's language.
+///
+/// If `enable_opcode_count_logging` is set, the number of instructions in each function is printed
+/// after each SSA pass, as is the size of the circuit after ACIR generation and each ACIR
+/// optimization pass.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn create_circuit(
     program: Program,
+    pipeline: &SsaPipeline,
     enable_ssa_logging: bool,
     enable_brillig_logging: bool,
+    enable_inlining_logging: bool,
+    enable_opcode_count_logging: bool,
+    ssa_dump_dir: Option<&Path>,
+    np_language: Option<Language>,
 ) -> Result<(Circuit, DebugInfo, Vec<Witness>, Vec<Witness>, Vec<SsaReport>), RuntimeError> {
     let func_sig = program.main_function_signature.clone();
-    let mut generated_acir =
-        optimize_into_acir(program, enable_ssa_logging, enable_brillig_logging)?;
+    let mut generated_acir = optimize_into_acir(
+        program,
+        pipeline,
+        enable_ssa_logging,
+        enable_brillig_logging,
+        enable_inlining_logging,
+        enable_opcode_count_logging,
+        ssa_dump_dir,
+        np_language,
+    )?;
     let opcodes = generated_acir.take_opcodes();
     let GeneratedAcir {
         current_witness_index,
@@ -121,12 +149,23 @@ pub fn create_circuit(
     let mut debug_info = DebugInfo::new(locations);
 
     // Perform any ACIR-level optimizations
-    let (optimized_circuit, transformation_map) = acvm::compiler::optimize(circuit);
+    let (optimized_circuit, transformation_map) = if enable_opcode_count_logging {
+        print_circuit_size("After ACIR Generation:", &circuit);
+        acvm::compiler::optimize_with_report(circuit, |pass, circuit| {
+            print_circuit_size(&format!("After {pass}:"), circuit);
+        })
+    } else {
+        acvm::compiler::optimize(circuit)
+    };
     debug_info.update_acir(transformation_map);
 
     Ok((optimized_circuit, debug_info, input_witnesses, return_witnesses, warnings))
 }
 
+fn print_circuit_size(msg: &str, circuit: &Circuit) {
+    println!("{msg} {} opcodes, {} witnesses", circuit.opcodes.len(), circuit.num_vars());
+}
+
 // Takes each function argument and partitions the circuit's inputs witnesses according to its visibility.
 fn split_public_and_private_inputs(
     func_sig: &FunctionSignature,
@@ -148,7 +187,7 @@ fn split_public_and_private_inputs(
         })
         .fold((BTreeSet::new(), BTreeSet::new()), |mut acc, (vis, witnesses)| {
             // Split witnesses into sets based on their visibility.
-            if *vis == Visibility::Public {
+            if vis.is_public() {
                 for witness in witnesses {
                     acc.0.insert(witness);
                 }
@@ -165,12 +204,29 @@ fn split_public_and_private_inputs(
 struct SsaBuilder {
     ssa: Ssa,
     print_ssa_passes: bool,
+
+    /// Whether to print the number of instructions in each function after each pass
+    print_opcode_counts: bool,
+
+    /// The directory each SSA snapshot is written to, if any
+    ssa_dump_dir: Option<PathBuf>,
+
+    /// The number of snapshots taken so far, used to order the files in `ssa_dump_dir`
+    passes_run: usize,
 }
 
 impl SsaBuilder {
-    fn new(program: Program, print_ssa_passes: bool) -> Result<SsaBuilder, RuntimeError> {
+    fn new(
+        program: Program,
+        print_ssa_passes: bool,
+        print_opcode_counts: bool,
+        ssa_dump_dir: Option<&Path>,
+    ) -> Result<SsaBuilder, RuntimeError> {
         let ssa = ssa_gen::generate_ssa(program)?;
-        Ok(SsaBuilder { print_ssa_passes, ssa }.print("Initial SSA:"))
+        let ssa_dump_dir = ssa_dump_dir.map(Path::to_path_buf);
+        let builder =
+            SsaBuilder { print_ssa_passes, print_opcode_counts, ssa, ssa_dump_dir, passes_run: 0 };
+        Ok(builder.print("Initial SSA:"))
     }
 
     fn finish(self) -> Ssa {
@@ -186,21 +242,58 @@ impl SsaBuilder {
     /// The same as `run_pass` but for passes that may fail
     fn try_run_pass(
         mut self,
-        pass: fn(Ssa) -> Result<Ssa, RuntimeError>,
+        pass: impl FnOnce(Ssa) -> Result<Ssa, RuntimeError>,
         msg: &str,
     ) -> Result<Self, RuntimeError> {
         self.ssa = pass(self.ssa)?;
         Ok(self.print(msg))
     }
 
-    fn to_brillig(&self, print_brillig_trace: bool) -> Brillig {
+    fn to_brillig(&self, print_brillig_trace: bool) -> Result<Brillig, RuntimeError> {
         self.ssa.to_brillig(print_brillig_trace)
     }
 
-    fn print(self, msg: &str) -> Self {
+    fn print(mut self, msg: &str) -> Self {
         if self.print_ssa_passes {
             println!("{msg}\n{}", self.ssa);
         }
+        if self.print_opcode_counts {
+            print_instruction_counts(msg, &self.ssa);
+        }
+        if let Some(dir) = &self.ssa_dump_dir {
+            write_ssa_snapshot(dir, self.passes_run, msg, &self.ssa);
+        }
+        self.passes_run += 1;
         self
     }
 }
+
+/// Prints the number of instructions in the reachable blocks of each function in `ssa`.
+fn print_instruction_counts(msg: &str, ssa: &Ssa) {
+    println!("{msg}");
+    for function in ssa.functions.values() {
+        let instructions: usize = function
+            .reachable_blocks()
+            .into_iter()
+            .map(|block| function.dfg[block].instructions().len())
+            .sum();
+        println!("  {} ({}): {instructions} instructions", function.name(), function.id());
+    }
+}
+
+/// Writes the SSA to a file in `dir` named after the pass which produced it, e.g. the message
+/// "After Mem2Reg:" for the third pass is written to `03_mem2reg.ssa`.
+///
+/// Failing to write a snapshot is reported but does not stop compilation.
+fn write_ssa_snapshot(dir: &Path, pass_number: usize, msg: &str, ssa: &Ssa) {
+    let pass_name = msg.trim_start_matches("After ").trim_end_matches(':').to_lowercase();
+    let pass_name: String =
+        pass_name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    let path = dir.join(format!("{pass_number:02}_{pass_name}.ssa"));
+
+    let result =
+        std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&path, format!("{msg}\n{ssa}")));
+    if let Err(error) = result {
+        eprintln!("Failed to write SSA to {}: {error}", path.display());
+    }
+}
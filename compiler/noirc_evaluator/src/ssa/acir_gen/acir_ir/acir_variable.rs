@@ -17,7 +17,7 @@ use acvm::{
         native_types::{Expression, Witness},
         BlackBoxFunc,
     },
-    FieldElement,
+    FieldElement, Language,
 };
 use acvm::{BlackBoxFunctionSolver, BlackBoxResolutionError};
 use fxhash::FxHashMap as HashMap;
@@ -110,6 +110,13 @@ pub(crate) struct AcirContext {
 }
 
 impl AcirContext {
+    /// Creates a new context whose arithmetic opcodes are fit to the given language, if any.
+    pub(crate) fn new(np_language: Option<Language>) -> Self {
+        let mut context = AcirContext::default();
+        context.acir_ir.np_language = np_language;
+        context
+    }
+
     pub(crate) fn current_witness_index(&self) -> Witness {
         self.acir_ir.current_witness_index()
     }
@@ -404,7 +411,7 @@ impl AcirContext {
             // max - ((max - a) AND (max -b))
             // Subtracting from max flips the bits, so this is effectively:
             // (NOT a) NAND (NOT b)
-            let max = self.add_constant(FieldElement::from((1_u128 << bit_size) - 1));
+            let max = self.add_constant(FieldElement::from(u128::MAX >> (128 - bit_size)));
             let a = self.sub_var(max, lhs)?;
             let b = self.sub_var(max, rhs)?;
             let inputs = vec![AcirValue::Var(a, typ.clone()), AcirValue::Var(b, typ)];
@@ -469,7 +476,7 @@ impl AcirContext {
             }
             NumericType::Signed { bit_size } => {
                 let (quotient_var, _remainder_var) =
-                    self.signed_division_var(lhs, rhs, bit_size)?;
+                    self.signed_division_var(lhs, rhs, bit_size, predicate)?;
                 Ok(quotient_var)
             }
         }
@@ -547,7 +554,7 @@ impl AcirContext {
     pub(crate) fn not_var(&mut self, x: AcirVar, typ: AcirType) -> Result<AcirVar, RuntimeError> {
         let bit_size = typ.bit_size();
         // Subtracting from max flips the bits
-        let max = self.add_constant(FieldElement::from((1_u128 << bit_size) - 1));
+        let max = self.add_constant(FieldElement::from(u128::MAX >> (128 - bit_size)));
         self.sub_var(max, x)
     }
 
@@ -616,26 +623,37 @@ impl AcirContext {
         // maximum bit size for q and for [r and rhs]
         let mut max_q_bits = bit_size;
         let mut max_rhs_bits = bit_size;
+        // maximum bit size for r, which is one bit less than rhs when rhs is a power of 2
+        let mut max_r_bits = bit_size;
         // when rhs is constant, we can better estimate the maximum bit sizes
-        if let Some(rhs_const) = self.var_to_expression(rhs)?.to_const() {
+        let rhs_const = self.var_to_expression(rhs)?.to_const();
+        let rhs_is_power_of_two = rhs_const.map_or(false, |rhs_const| {
+            rhs_const.num_bits() <= 128 && rhs_const.to_u128().is_power_of_two()
+        });
+        if let Some(rhs_const) = rhs_const {
             max_rhs_bits = rhs_const.num_bits();
+            max_r_bits = max_rhs_bits;
             if max_rhs_bits != 0 {
                 if max_rhs_bits > bit_size {
                     return Ok((zero, zero));
                 }
                 max_q_bits = bit_size - max_rhs_bits + 1;
             }
+            if rhs_is_power_of_two {
+                max_r_bits = max_rhs_bits - 1;
+            }
         }
 
         // Avoids overflow: 'q*b+r < 2^max_q_bits*2^max_rhs_bits'
         let mut avoid_overflow = false;
+        let mut bound_product = false;
         if max_q_bits + max_rhs_bits >= FieldElement::max_num_bits() - 1 {
             // q*b+r can overflow; we avoid this when b is constant
             if self.var_to_expression(rhs)?.is_const() {
                 avoid_overflow = true;
             } else {
-                // we do not support unbounded division
-                unreachable!("overflow in unbounded division");
+                // otherwise we constrain q*b to be small enough that it cannot overflow
+                bound_product = true;
             }
         }
 
@@ -661,20 +679,25 @@ impl AcirContext {
             None,
         )?;
 
-        // Constrain `r < 2^{max_rhs_bits}`.
+        // Constrain `r < 2^{max_r_bits}`.
         //
-        // If `rhs` is a power of 2, then is just a looser version of the following bound constraint.
+        // If `rhs` is a power of 2, then this is exactly `r < rhs` and no bound constraint is needed.
         // In the case where `rhs` isn't a power of 2 then this range constraint is required
         // as the bound constraint creates a new witness.
-        // This opcode will be optimized out if it is redundant so we always add it for safety.
         self.range_constrain_var(
             remainder_var,
-            &NumericType::Unsigned { bit_size: max_rhs_bits },
+            &NumericType::Unsigned { bit_size: max_r_bits },
             None,
         )?;
 
         // Constrain `r < rhs`.
-        self.bound_constraint_with_offset(remainder_var, rhs, predicate, max_rhs_bits)?;
+        if !rhs_is_power_of_two {
+            self.bound_constraint_with_offset(remainder_var, rhs, predicate, max_rhs_bits)?;
+        }
+
+        if bound_product {
+            self.assert_product_does_not_wrap(rhs, quotient_var, bit_size, predicate)?;
+        }
 
         // a * predicate == (b * q + r) * predicate
         // => predicate * (a - b * q - r) == 0
@@ -719,6 +742,34 @@ impl AcirContext {
         Ok((quotient_var, remainder_var))
     }
 
+    /// Constrains the product of `lhs` and `rhs`, both integers of `bit_size` bits, to fit
+    /// within `bit_size` bits when the predicate is active.
+    ///
+    /// The product itself is not constrained here. Instead, the operands are split as
+    /// `high * 2^h + low` with `h = ceil(bit_size / 2)`, and one of the high halves must be zero.
+    /// This holds for any product below 2^bit_size. It also bounds the product below
+    /// 2^(bit_size + h + 2), so the product cannot wrap around the field.
+    fn assert_product_does_not_wrap(
+        &mut self,
+        lhs: AcirVar,
+        rhs: AcirVar,
+        bit_size: u32,
+        predicate: AcirVar,
+    ) -> Result<(), RuntimeError> {
+        assert!(
+            bit_size + (bit_size + 1) / 2 + 2 < FieldElement::max_num_bits(),
+            "products of {bit_size}-bit integers may overflow the field"
+        );
+        let half_width = self.add_constant(FieldElement::from(2_u128.pow((bit_size + 1) / 2)));
+        let (lhs_high, _) = self.euclidean_division_var(lhs, half_width, bit_size, predicate)?;
+        let (rhs_high, _) = self.euclidean_division_var(rhs, half_width, bit_size, predicate)?;
+
+        let high_product = self.mul_var(lhs_high, rhs_high)?;
+        let high_product = self.mul_var(high_product, predicate)?;
+        let zero = self.add_constant(FieldElement::zero());
+        self.assert_eq_var(high_product, zero, None)
+    }
+
     /// Generate constraints that are satisfied iff
     /// lhs < rhs , when offset is 1, or
     /// lhs <= rhs, when offset is 0
@@ -766,11 +817,11 @@ impl AcirContext {
 
             let bit_size = bit_size_u128(rhs_offset);
             // r = 2^bit_size - rhs_offset -1, is of bit size  'bit_size' by construction
-            let r = (1_u128 << bit_size) - rhs_offset - 1;
+            let r = 2_u128.checked_pow(bit_size).map_or(u128::MAX, |max| max - 1) - rhs_offset;
             // however, since it is a constant, we can compute it's actual bit size
             let r_bit_size = bit_size_u128(r);
             // witness = lhs_offset + r
-            assert!(bits + r_bit_size < FieldElement::max_num_bits()); //we need to ensure lhs_offset + r does not overflow
+            assert!(bits.max(r_bit_size) + 1 < FieldElement::max_num_bits()); //we need to ensure lhs_offset + r does not overflow
 
             let r_var = self.add_constant(r.into());
             let aor = self.add_var(lhs_offset, r_var)?;
@@ -813,6 +864,7 @@ impl AcirContext {
         lhs: AcirVar,
         rhs: AcirVar,
         bit_size: u32,
+        predicate: AcirVar,
     ) -> Result<(AcirVar, AcirVar), RuntimeError> {
         // We derive the signed division from the unsigned euclidean division.
         // note that this is not euclidean division!
@@ -840,9 +892,10 @@ impl AcirContext {
         let unsigned_lhs = self.two_complement(lhs, lhs_leading, bit_size)?;
         let unsigned_rhs = self.two_complement(rhs, rhs_leading, bit_size)?;
 
-        // Performs the division using the unsigned values of lhs and rhs
+        // Performs the division using the unsigned values of lhs and rhs.
+        // The absolute value of the most negative integer needs all `bit_size` bits.
         let (q1, r1) =
-            self.euclidean_division_var(unsigned_lhs, unsigned_rhs, bit_size - 1, one)?;
+            self.euclidean_division_var(unsigned_lhs, unsigned_rhs, bit_size, predicate)?;
 
         // Unsigned to signed: derive q and r from q1,r1 and the signs of lhs and rhs
         // Quotient sign is lhs sign * rhs sign, whose resulting sign bit is the XOR of the sign bits
@@ -850,21 +903,39 @@ impl AcirContext {
         let sign_prod = self.mul_var(lhs_leading, rhs_leading)?;
         let q_sign = self.add_mul_var(sign_sum, -FieldElement::from(2_i128), sign_prod)?;
 
+        // Zero has no sign, as its two's complement would otherwise be 2^bit_size
+        let q_sign = self.sign_unless_zero(q1, q_sign)?;
+        let r_sign = self.sign_unless_zero(r1, lhs_leading)?;
+
         let quotient = self.two_complement(q1, q_sign, bit_size)?;
-        let remainder = self.two_complement(r1, lhs_leading, bit_size)?;
+        let remainder = self.two_complement(r1, r_sign, bit_size)?;
 
         Ok((quotient, remainder))
     }
 
-    /// Returns a variable which is constrained to be `lhs mod rhs`
+    /// Returns `sign` if `value` is non-zero, and zero otherwise
+    fn sign_unless_zero(&mut self, value: AcirVar, sign: AcirVar) -> Result<AcirVar, RuntimeError> {
+        let zero = self.add_constant(FieldElement::zero());
+        let is_zero = self.eq_var(value, zero)?;
+        let sign_if_zero = self.mul_var(sign, is_zero)?;
+        self.sub_var(sign, sign_if_zero)
+    }
+
+    /// Returns a variable which is constrained to be `lhs mod rhs`.
+    /// For signed integers the remainder has the same sign as `lhs`.
     pub(crate) fn modulo_var(
         &mut self,
         lhs: AcirVar,
         rhs: AcirVar,
-        bit_size: u32,
+        typ: AcirType,
         predicate: AcirVar,
     ) -> Result<AcirVar, RuntimeError> {
-        let (_, remainder) = self.euclidean_division_var(lhs, rhs, bit_size, predicate)?;
+        let (_, remainder) = match typ {
+            AcirType::NumericType(NumericType::Signed { bit_size }) => {
+                self.signed_division_var(lhs, rhs, bit_size, predicate)?
+            }
+            typ => self.euclidean_division_var(lhs, rhs, typ.bit_size(), predicate)?,
+        };
         Ok(remainder)
     }
 
@@ -1017,6 +1088,45 @@ impl AcirContext {
         self.sub_var(one, comparison) // comparison_negated
     }
 
+    /// Returns an `AcirVar` which will be `1` if the signed integer lhs < rhs
+    /// and `0` otherwise.
+    ///
+    /// Flipping the sign bit of a two's complement integer maps the most negative integer to zero
+    /// and the largest positive integer to 2^bit_size - 1, so the signed comparison is the
+    /// unsigned comparison of the flipped operands.
+    pub(crate) fn signed_less_than_var(
+        &mut self,
+        lhs: AcirVar,
+        rhs: AcirVar,
+        bit_size: u32,
+        predicate: AcirVar,
+    ) -> Result<AcirVar, RuntimeError> {
+        let lhs = self.flip_sign_bit_var(lhs, bit_size)?;
+        let rhs = self.flip_sign_bit_var(rhs, bit_size)?;
+        self.less_than_var(lhs, rhs, bit_size, predicate)
+    }
+
+    /// Returns `value` with its sign bit flipped.
+    ///
+    /// The sign bit is the quotient of dividing by 2^{bit_size-1}, which only needs its remainder to
+    /// be range constrained to `bit_size - 1` bits, so that the flipped value is
+    /// `remainder + 2^{bit_size-1} * (1 - sign)` without any further constraints.
+    fn flip_sign_bit_var(
+        &mut self,
+        value: AcirVar,
+        bit_size: u32,
+    ) -> Result<AcirVar, RuntimeError> {
+        let one = self.add_constant(FieldElement::one());
+        let half_width = self.add_constant(
+            FieldElement::from(2_i128).pow(&FieldElement::from(bit_size as i128 - 1)),
+        );
+
+        let (sign, low) = self.euclidean_division_var(value, half_width, bit_size, one)?;
+        let not_sign = self.sub_var(one, sign)?;
+        let high = self.mul_var(not_sign, half_width)?;
+        self.add_var(low, high)
+    }
+
     /// Calls a Blackbox function on the given inputs and returns a given set of outputs
     /// to represent the result of the blackbox function.
     pub(crate) fn black_box_function(
@@ -1131,11 +1241,16 @@ impl AcirContext {
 
         let bit_size = u32::BITS - (radix - 1).leading_zeros();
         let limbs = self.acir_ir.radix_le_decompose(&input_expr, radix, limb_count, bit_size)?;
+        let limbs = vecmap(limbs, |witness| self.add_data(AcirVarData::Witness(witness)));
 
-        let mut limb_vars = vecmap(limbs, |witness| {
-            let witness = self.add_data(AcirVarData::Witness(witness));
-            AcirValue::Var(witness, result_element_type.clone())
-        });
+        // If the limbs can represent values larger than the field modulus then `input + modulus`
+        // would also satisfy the recomposition constraint, so we must also check that the limbs
+        // form the canonical representation of the input.
+        if limb_count * bit_size >= FieldElement::max_num_bits() {
+            self.assert_limbs_below_modulus(&limbs, radix, bit_size)?;
+        }
+
+        let mut limb_vars = vecmap(limbs, |limb| AcirValue::Var(limb, result_element_type.clone()));
 
         if endian == Endian::Big {
             limb_vars.reverse();
@@ -1152,6 +1267,46 @@ impl AcirContext {
         ])
     }
 
+    /// Constrains the little-endian `limbs` over the given radix to represent a value
+    /// strictly less than the field modulus.
+    ///
+    /// The limbs are compared against the modulus' limbs starting from the most significant one.
+    /// While all previous limbs are equal to those of the modulus, each limb may not exceed the
+    /// corresponding limb of the modulus and at least one limb must be strictly smaller.
+    fn assert_limbs_below_modulus(
+        &mut self,
+        limbs: &[AcirVar],
+        radix: u32,
+        bit_size: u32,
+    ) -> Result<(), RuntimeError> {
+        let mut modulus_limbs = FieldElement::modulus().to_radix_le(radix);
+        modulus_limbs.resize(limbs.len().max(modulus_limbs.len()), 0);
+        assert!(
+            modulus_limbs[limbs.len()..].iter().all(|limb| *limb == 0),
+            "ICE: limbs must be able to represent the field modulus"
+        );
+
+        let zero = self.add_constant(FieldElement::zero());
+        let one = self.add_constant(FieldElement::one());
+        let mut prefix_is_equal = one;
+        for (limb, modulus_limb) in limbs.iter().zip(modulus_limbs).rev() {
+            let modulus_limb = self.add_constant(FieldElement::from(modulus_limb as u128));
+            let is_less = self.less_than_var(*limb, modulus_limb, bit_size, one)?;
+            let is_equal = self.eq_var(*limb, modulus_limb)?;
+
+            // is_less and is_equal are mutually exclusive so `1 - is_less - is_equal` is
+            // one exactly when the limb is greater than the modulus' limb.
+            let is_less_or_equal = self.add_var(is_less, is_equal)?;
+            let is_greater = self.sub_var(one, is_less_or_equal)?;
+            let exceeds_modulus = self.mul_var(prefix_is_equal, is_greater)?;
+            self.assert_eq_var(exceeds_modulus, zero, None)?;
+
+            prefix_is_equal = self.mul_var(prefix_is_equal, is_equal)?;
+        }
+        // The limbs must not be equal to the modulus itself
+        self.assert_eq_var(prefix_is_equal, zero, None)
+    }
+
     /// Returns `AcirVar`s constrained to be the bit decomposition of the provided input
     pub(crate) fn bit_decompose(
         &mut self,
@@ -19,7 +19,7 @@ use acvm::acir::{
 };
 use acvm::{
     acir::{circuit::directives::Directive, native_types::Expression},
-    FieldElement,
+    FieldElement, Language,
 };
 use iter_extended::vecmap;
 use num_bigint::BigUint;
@@ -55,6 +55,10 @@ pub(crate) struct GeneratedAcir {
     pub(crate) assert_messages: BTreeMap<OpcodeLocation, String>,
 
     pub(crate) warnings: Vec<SsaReport>,
+
+    /// The language of the backend the program is compiled for. If known, arithmetic opcodes
+    /// are split up so that each fits in a single constraint of the backend.
+    pub(crate) np_language: Option<Language>,
 }
 
 impl GeneratedAcir {
@@ -372,9 +376,63 @@ impl GeneratedAcir {
     /// If `expr` is not zero, then the constraint system will
     /// fail upon verification.
     pub(crate) fn assert_is_zero(&mut self, expr: Expression) {
+        let expr = self.fit_to_language(expr);
         self.push_opcode(AcirOpcode::Arithmetic(expr));
     }
 
+    /// Moves parts of `expr` into intermediate witnesses until it fits in a single constraint
+    /// of the backend's language:
+    /// - An R1CS constraint may only hold a single multiplication term.
+    /// - A PLONK constraint of width `w` may additionally only hold `w` linear terms, fewer if
+    ///   they do not include the operands of the multiplication term.
+    ///
+    /// Each intermediate witness is constrained by an opcode which itself fits.
+    /// If the language is not known, `expr` is returned as is and is left to be split up
+    /// when the circuit is compiled for a backend.
+    fn fit_to_language(&mut self, mut expr: Expression) -> Expression {
+        let width = match self.np_language {
+            None => return expr,
+            Some(Language::R1CS) => None,
+            Some(Language::PLONKCSat { width }) => Some(width),
+        };
+
+        expr.mul_terms.retain(|(coefficient, _, _)| !coefficient.is_zero());
+        expr.linear_combinations.retain(|(coefficient, _)| !coefficient.is_zero());
+
+        // Each multiplication term past the first is replaced by a witness holding its product
+        while expr.mul_terms.len() > 1 {
+            let (coefficient, lhs, rhs) = expr.mul_terms.pop().expect("Expected a mul term");
+            let product = Expression {
+                mul_terms: vec![(FieldElement::one(), lhs, rhs)],
+                linear_combinations: Vec::new(),
+                q_c: FieldElement::zero(),
+            };
+            let product = self.create_witness_for_expression(&product);
+            expr.push_addition_term(coefficient, product);
+        }
+
+        // A width below 3 cannot hold a sum along with the witness it is assigned to
+        let Some(width) = width.filter(|width| *width >= 3) else {
+            return expr;
+        };
+
+        // Linear terms past the width are summed into a witness, `width - 1` at a time so that
+        // each sum fits in a single constraint along with the witness it is assigned to.
+        while !expr.fits_in_one_identity(width) {
+            let split_at = expr.linear_combinations.len() - (width - 1);
+            let terms = expr.linear_combinations.split_off(split_at);
+            let sum = Expression {
+                mul_terms: Vec::new(),
+                linear_combinations: terms,
+                q_c: FieldElement::zero(),
+            };
+            let sum = self.create_witness_for_expression(&sum);
+            expr.push_addition_term(FieldElement::one(), sum);
+        }
+
+        expr
+    }
+
     /// Returns a `Witness` that is constrained to be:
     /// - `1` if `lhs == rhs`
     /// - `0` otherwise
@@ -29,7 +29,7 @@ use acvm::acir::native_types::Witness;
 use acvm::acir::BlackBoxFunc;
 use acvm::{
     acir::{circuit::opcodes::BlockId, native_types::Expression},
-    FieldElement,
+    FieldElement, Language,
 };
 use fxhash::FxHashMap as HashMap;
 use im::Vector;
@@ -131,6 +131,15 @@ impl AcirValue {
         }
     }
 
+    /// Returns true if this value is, or contains, an array which lives in memory
+    fn contains_dynamic_array(&self) -> bool {
+        match self {
+            AcirValue::Var(..) => false,
+            AcirValue::Array(array) => array.iter().any(AcirValue::contains_dynamic_array),
+            AcirValue::DynamicArray(_) => true,
+        }
+    }
+
     fn flatten(self) -> Vec<(AcirVar, AcirType)> {
         match self {
             AcirValue::Var(var, typ) => vec![(var, typ)],
@@ -146,8 +155,9 @@ impl Ssa {
         brillig: Brillig,
         abi_distinctness: Distinctness,
         last_array_uses: &HashMap<ValueId, InstructionId>,
+        np_language: Option<Language>,
     ) -> Result<GeneratedAcir, RuntimeError> {
-        let context = Context::new();
+        let context = Context::new(np_language);
         let mut generated_acir = context.convert_ssa(self, brillig, last_array_uses)?;
 
         match abi_distinctness {
@@ -173,8 +183,8 @@ impl Ssa {
 }
 
 impl Context {
-    fn new() -> Context {
-        let mut acir_context = AcirContext::default();
+    fn new(np_language: Option<Language>) -> Context {
+        let mut acir_context = AcirContext::new(np_language);
         let current_side_effects_enabled_var = acir_context.add_constant(FieldElement::one());
 
         Context {
@@ -647,8 +657,9 @@ impl Context {
         Ok(())
     }
 
-    /// Handle constant index: if there is no predicate and we have the array values,
-    /// we can perform the operation directly on the array
+    /// Handle constant index: if we have the array values, we can perform the operation
+    /// directly on the array rather than through memory opcodes.
+    /// Under a predicate, a store selects between the new and existing value of the element.
     fn handle_constant_index(
         &mut self,
         instruction: InstructionId,
@@ -719,9 +730,27 @@ impl Context {
                         }
                     }
                     // If there is a predicate and the index is not out of range, we can directly perform the read
-                    else if index < array_size && store_value.is_none() {
-                        self.define_result(dfg, instruction, array[index].clone());
-                        return Ok(true);
+                    else if index < array_size {
+                        match store_value {
+                            None => {
+                                self.define_result(dfg, instruction, array[index].clone());
+                                return Ok(true);
+                            }
+                            // A store keeps the existing value when the predicate is false, so it can
+                            // also be applied to the array directly rather than through memory
+                            Some(store_value) if !array[index].contains_dynamic_array() => {
+                                let store_value = self.convert_value(store_value, dfg);
+                                let value = self
+                                    .convert_array_set_store_value(&store_value, &array[index])?;
+                                self.define_result(
+                                    dfg,
+                                    instruction,
+                                    AcirValue::Array(array.update(index, value)),
+                                );
+                                return Ok(true);
+                            }
+                            Some(_) => (),
+                        }
                     }
                 }
             }
@@ -805,7 +834,7 @@ impl Context {
         dummy_value: &AcirValue,
     ) -> Result<AcirValue, RuntimeError> {
         match (store_value, dummy_value) {
-            (AcirValue::Var(store_var, _), AcirValue::Var(dummy_var, _)) => {
+            (AcirValue::Var(store_var, _), AcirValue::Var(dummy_var, typ)) => {
                 let true_pred =
                     self.acir_context.mul_var(*store_var, self.current_side_effects_enabled_var)?;
                 let one = self.acir_context.add_constant(FieldElement::one());
@@ -814,7 +843,7 @@ impl Context {
                 let false_pred = self.acir_context.mul_var(not_pred, *dummy_var)?;
                 // predicate*value + (1-predicate)*dummy
                 let new_value = self.acir_context.add_var(true_pred, false_pred)?;
-                Ok(AcirValue::Var(new_value, AcirType::field()))
+                Ok(AcirValue::Var(new_value, typ.clone()))
             }
             (AcirValue::Array(values), AcirValue::Array(dummy_values)) => {
                 let mut elements = im::Vector::new();
@@ -1493,10 +1522,11 @@ impl Context {
         match &binary_type {
             Type::Numeric(NumericType::Unsigned { bit_size })
             | Type::Numeric(NumericType::Signed { bit_size }) => {
-                // Conservative max bit size that is small enough such that two operands can be
-                // multiplied and still fit within the field modulus. This is necessary for the
-                // truncation technique: result % 2^bit_size to be valid.
-                let max_integer_bit_size = FieldElement::max_num_bits() / 2;
+                // Conservative max bit size that is small enough such that the result of an
+                // operation can be truncated with the technique result % 2^bit_size.
+                // Multiplications of integers wider than half the field are checked separately
+                // during SSA generation so that their products cannot wrap around the modulus.
+                let max_integer_bit_size = 128;
                 if *bit_size > max_integer_bit_size {
                     return Err(RuntimeError::UnsupportedIntegerSize {
                         num_bits: *bit_size,
@@ -1524,19 +1554,29 @@ impl Context {
             // Note: that this produces unnecessary constraints when
             // this Eq instruction is being used for a constrain statement
             BinaryOp::Eq => self.acir_context.eq_var(lhs, rhs),
-            BinaryOp::Lt => self.acir_context.less_than_var(
-                lhs,
-                rhs,
-                bit_count,
-                self.current_side_effects_enabled_var,
-            ),
+            BinaryOp::Lt => match binary_type {
+                AcirType::NumericType(NumericType::Signed { .. }) => {
+                    self.acir_context.signed_less_than_var(
+                        lhs,
+                        rhs,
+                        bit_count,
+                        self.current_side_effects_enabled_var,
+                    )
+                }
+                _ => self.acir_context.less_than_var(
+                    lhs,
+                    rhs,
+                    bit_count,
+                    self.current_side_effects_enabled_var,
+                ),
+            },
             BinaryOp::Xor => self.acir_context.xor_var(lhs, rhs, binary_type),
             BinaryOp::And => self.acir_context.and_var(lhs, rhs, binary_type),
             BinaryOp::Or => self.acir_context.or_var(lhs, rhs, binary_type),
             BinaryOp::Mod => self.acir_context.modulo_var(
                 lhs,
                 rhs,
-                bit_count,
+                binary_type,
                 self.current_side_effects_enabled_var,
             ),
         }
@@ -1639,8 +1679,9 @@ impl Context {
                 ) {
                     // Subtractions must first have the integer modulus added before truncation can be
                     // applied. This is done in order to prevent underflow.
-                    let integer_modulus =
-                        self.acir_context.add_constant(FieldElement::from(2_u128.pow(bit_size)));
+                    let integer_modulus = self.acir_context.add_constant(
+                        FieldElement::from(2_i128).pow(&FieldElement::from(bit_size as i128)),
+                    );
                     var = self.acir_context.add_var(var, integer_modulus)?;
                 }
             }
@@ -1,7 +1,8 @@
-use std::{borrow::Cow, rc::Rc};
+use std::{borrow::Cow, sync::Arc};
 
 use acvm::FieldElement;
 use noirc_errors::Location;
+use noirc_frontend::token::InlineType;
 
 use crate::ssa::ir::{
     basic_block::BasicBlockId,
@@ -87,6 +88,11 @@ impl FunctionBuilder {
         self.new_function_with_type(name, function_id, RuntimeType::Brillig);
     }
 
+    /// Set how calls to the current function should be treated by the inliner.
+    pub(crate) fn set_inline_type(&mut self, inline_type: InlineType) {
+        self.current_function.set_inline_type(inline_type);
+    }
+
     /// Consume the FunctionBuilder returning all the functions it has generated.
     pub(crate) fn finish(mut self) -> Ssa {
         self.finished_functions.push(self.current_function);
@@ -282,32 +288,37 @@ impl FunctionBuilder {
     ) -> ValueId {
         let base = self.field_constant(FieldElement::from(2_u128));
         let typ = self.current_function.dfg.type_of_value(lhs);
-        let (max_bit, pow) = if let Some(rhs_constant) =
-            self.current_function.dfg.get_numeric_constant(rhs)
-        {
-            // Happy case is that we know precisely by how many bits the the integer will
-            // increase: lhs_bit_size + rhs
-            let (rhs_bit_size_pow_2, overflows) =
-                2_u32.overflowing_pow(rhs_constant.to_u128() as u32);
-            if overflows {
-                let zero = self.numeric_constant(FieldElement::zero(), typ);
-                return InsertInstructionResult::SimplifiedTo(zero).first();
-            }
-            let pow = self.numeric_constant(FieldElement::from(rhs_bit_size_pow_2 as u128), typ);
-            (bit_size + (rhs_constant.to_u128() as u32), pow)
-        } else {
-            // we use a predicate to nullify the result in case of overflow
-            let bit_size_var =
-                self.numeric_constant(FieldElement::from(bit_size as u128), typ.clone());
-            let overflow = self.insert_binary(rhs, BinaryOp::Lt, bit_size_var);
-            let one = self.numeric_constant(FieldElement::one(), Type::unsigned(1));
-            let predicate = self.insert_binary(overflow, BinaryOp::Eq, one);
-            let predicate = self.insert_cast(predicate, typ.clone());
-
-            let pow = self.pow(base, rhs);
-            let pow = self.insert_cast(pow, typ);
-            (FieldElement::max_num_bits(), self.insert_binary(predicate, BinaryOp::Mul, pow))
-        };
+        let (max_bit, pow) =
+            if let Some(rhs_constant) = self.current_function.dfg.get_numeric_constant(rhs) {
+                // Happy case is that we know precisely by how many bits the the integer will
+                // increase: lhs_bit_size + rhs
+                if rhs_constant.to_u128() >= bit_size as u128 {
+                    let zero = self.numeric_constant(FieldElement::zero(), typ);
+                    return InsertInstructionResult::SimplifiedTo(zero).first();
+                }
+                let rhs_constant = rhs_constant.to_u128() as u32;
+                let pow = self.numeric_constant(FieldElement::from(2_u128.pow(rhs_constant)), typ);
+                let max_bit = bit_size + rhs_constant;
+                if max_bit >= FieldElement::max_num_bits() {
+                    // The product could wrap around the field modulus, so we instead drop the bits
+                    // which would be shifted out before multiplying.
+                    let lhs = self.insert_truncate(lhs, bit_size - rhs_constant, bit_size);
+                    return self.insert_binary(lhs, BinaryOp::Mul, pow);
+                }
+                (max_bit, pow)
+            } else {
+                // we use a predicate to nullify the result in case of overflow
+                let bit_size_var =
+                    self.numeric_constant(FieldElement::from(bit_size as u128), typ.clone());
+                let overflow = self.insert_binary(rhs, BinaryOp::Lt, bit_size_var);
+                let one = self.numeric_constant(FieldElement::one(), Type::unsigned(1));
+                let predicate = self.insert_binary(overflow, BinaryOp::Eq, one);
+                let predicate = self.insert_cast(predicate, typ.clone());
+
+                let pow = self.pow(base, rhs);
+                let pow = self.insert_cast(pow, typ);
+                (FieldElement::max_num_bits(), self.insert_binary(predicate, BinaryOp::Mul, pow))
+            };
 
         let instruction = Instruction::Binary(Binary { lhs, rhs: pow, operator: BinaryOp::Mul });
         if max_bit <= bit_size {
@@ -344,7 +355,7 @@ impl FunctionBuilder {
             let to_bits = self.import_intrinsic_id(Intrinsic::ToBits(Endian::Little));
             let length = self.field_constant(FieldElement::from(bit_size as i128));
             let result_types =
-                vec![Type::field(), Type::Array(Rc::new(vec![Type::bool()]), bit_size as usize)];
+                vec![Type::field(), Type::Array(Arc::new(vec![Type::bool()]), bit_size as usize)];
             let rhs_bits = self.insert_call(to_bits, vec![rhs, length], result_types);
             let rhs_bits = rhs_bits[1];
             let one = self.field_constant(FieldElement::one());
@@ -484,7 +495,7 @@ impl std::ops::Index<BasicBlockId> for FunctionBuilder {
 
 #[cfg(test)]
 mod tests {
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     use acvm::FieldElement;
 
@@ -511,7 +522,7 @@ mod tests {
         let to_bits_id = builder.import_intrinsic_id(Intrinsic::ToBits(Endian::Little));
         let input = builder.numeric_constant(FieldElement::from(7_u128), Type::field());
         let length = builder.numeric_constant(FieldElement::from(8_u128), Type::field());
-        let result_types = vec![Type::Array(Rc::new(vec![Type::bool()]), 8)];
+        let result_types = vec![Type::Array(Arc::new(vec![Type::bool()]), 8)];
         let call_results =
             builder.insert_call(to_bits_id, vec![input, length], result_types).into_owned();
 
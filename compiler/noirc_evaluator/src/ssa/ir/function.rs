@@ -1,6 +1,7 @@
 use std::collections::BTreeSet;
 
 use iter_extended::vecmap;
+use noirc_frontend::token::InlineType;
 
 use super::basic_block::BasicBlockId;
 use super::dfg::DataFlowGraph;
@@ -35,6 +36,9 @@ pub(crate) struct Function {
 
     runtime: RuntimeType,
 
+    /// How calls to this function should be treated by the inliner
+    inline_type: InlineType,
+
     /// The DataFlowGraph holds the majority of data pertaining to the function
     /// including its blocks, instructions, and values.
     pub(crate) dfg: DataFlowGraph,
@@ -47,7 +51,14 @@ impl Function {
     pub(crate) fn new(name: String, id: FunctionId) -> Self {
         let mut dfg = DataFlowGraph::default();
         let entry_block = dfg.make_block();
-        Self { name, id, entry_block, dfg, runtime: RuntimeType::Acir }
+        Self {
+            name,
+            id,
+            entry_block,
+            dfg,
+            runtime: RuntimeType::Acir,
+            inline_type: InlineType::default(),
+        }
     }
 
     /// The name of the function.
@@ -71,6 +82,16 @@ impl Function {
         self.runtime = runtime;
     }
 
+    /// How calls to this function should be treated by the inliner.
+    pub(crate) fn inline_type(&self) -> InlineType {
+        self.inline_type
+    }
+
+    /// Set how calls to this function should be treated by the inliner.
+    pub(crate) fn set_inline_type(&mut self, inline_type: InlineType) {
+        self.inline_type = inline_type;
+    }
+
     /// Retrieves the entry block of a function.
     ///
     /// A function's entry block contains the instructions
@@ -34,6 +34,7 @@ pub(crate) enum Intrinsic {
     Sort,
     ArrayLen,
     AssertConstant,
+    StaticAssert,
     SlicePushBack,
     SlicePushFront,
     SlicePopBack,
@@ -54,6 +55,7 @@ impl std::fmt::Display for Intrinsic {
             Intrinsic::Sort => write!(f, "arraysort"),
             Intrinsic::ArrayLen => write!(f, "array_len"),
             Intrinsic::AssertConstant => write!(f, "assert_constant"),
+            Intrinsic::StaticAssert => write!(f, "static_assert"),
             Intrinsic::SlicePushBack => write!(f, "slice_push_back"),
             Intrinsic::SlicePushFront => write!(f, "slice_push_front"),
             Intrinsic::SlicePopBack => write!(f, "slice_pop_back"),
@@ -78,7 +80,7 @@ impl Intrinsic {
     /// If there are no side effects then the `Intrinsic` can be removed if the result is unused.
     pub(crate) fn has_side_effects(&self) -> bool {
         match self {
-            Intrinsic::AssertConstant => true,
+            Intrinsic::AssertConstant | Intrinsic::StaticAssert => true,
 
             Intrinsic::Sort
             | Intrinsic::ArrayLen
@@ -106,6 +108,7 @@ impl Intrinsic {
             "arraysort" => Some(Intrinsic::Sort),
             "array_len" => Some(Intrinsic::ArrayLen),
             "assert_constant" => Some(Intrinsic::AssertConstant),
+            "static_assert" => Some(Intrinsic::StaticAssert),
             "slice_push_back" => Some(Intrinsic::SlicePushBack),
             "slice_push_front" => Some(Intrinsic::SlicePushFront),
             "slice_pop_back" => Some(Intrinsic::SlicePopBack),
@@ -451,9 +454,11 @@ impl Instruction {
             }
             Instruction::Truncate { value, bit_size, .. } => {
                 if let Some((numeric_constant, typ)) = dfg.get_numeric_constant_with_type(*value) {
-                    let integer_modulus = 2_u128.pow(*bit_size);
-                    let truncated = numeric_constant.to_u128() % integer_modulus;
-                    SimplifiedTo(dfg.make_constant(truncated.into(), typ))
+                    let integer_modulus = BigUint::from(2u128).pow(*bit_size);
+                    let constant = BigUint::from_bytes_be(&numeric_constant.to_be_bytes());
+                    let truncated = constant % integer_modulus;
+                    let truncated = FieldElement::from_be_bytes_reduce(&truncated.to_bytes_be());
+                    SimplifiedTo(dfg.make_constant(truncated, typ))
                 } else {
                     None
                 }
@@ -730,6 +735,15 @@ impl Binary {
                 if rhs_is_one {
                     return SimplifyResult::SimplifiedTo(self.lhs);
                 }
+                if operand_type == Type::field() {
+                    // Dividing a Field by a non-zero constant is a multiplication by its inverse,
+                    // which avoids computing the inverse in ACIR or Brillig.
+                    if let Some(rhs) = rhs.filter(|rhs| !rhs.is_zero()) {
+                        let inverse = dfg.make_constant(rhs.inverse(), operand_type);
+                        let instruction = Instruction::binary(BinaryOp::Mul, self.lhs, inverse);
+                        return SimplifyResult::SimplifiedToInstruction(instruction);
+                    }
+                }
             }
             BinaryOp::Mod => {
                 if rhs_is_one {
@@ -844,8 +858,8 @@ fn eval_constant_binary_op(
                 return None;
             }
             let result = function(lhs, rhs)?;
-            // Check for overflow
-            if result >= 2u128.pow(*bit_size) {
+            // Check for overflow. Results which overflow a u128 were already caught by `function`.
+            if truncate(result, *bit_size) != result {
                 return None;
             }
             result.into()
@@ -888,8 +902,11 @@ fn eval_constant_binary_op(
 }
 
 fn truncate(int: u128, bit_size: u32) -> u128 {
-    let max = 2u128.pow(bit_size);
-    int % max
+    match 2u128.checked_pow(bit_size) {
+        Some(max) => int % max,
+        // Every u128 already fits within 128 bits or more
+        None => int,
+    }
 }
 
 impl BinaryOp {
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use acvm::FieldElement;
 use iter_extended::vecmap;
@@ -28,10 +28,10 @@ pub(crate) enum Type {
     Reference,
 
     /// An immutable array value with the given element type and length
-    Array(Rc<CompositeType>, usize),
+    Array(Arc<CompositeType>, usize),
 
     /// An immutable slice value with a given element type
-    Slice(Rc<CompositeType>),
+    Slice(Arc<CompositeType>),
 
     /// A function that may be called directly
     Function,
@@ -119,7 +119,7 @@ impl NumericType {
                 field <= max.into() || field >= min.into()
             }
             NumericType::Unsigned { bit_size } => {
-                let max = 2u128.pow(bit_size) - 1;
+                let max = 2u128.checked_pow(bit_size).map_or(u128::MAX, |max| max - 1);
                 field <= max.into()
             }
             NumericType::NativeField => true,
@@ -15,14 +15,38 @@ impl Ssa {
     /// to `assert_constant`, issuing an error if any arguments to the function are
     /// not constants.
     ///
+    /// Calls to `static_assert` whose condition is already known are evaluated here as well.
+    /// The remaining ones may depend on loop indices and are evaluated after loop unrolling
+    /// by `evaluate_static_assert`.
+    ///
     /// Note that this pass must be placed directly before loop unrolling to be
     /// useful. Any optimization passes between this and loop unrolling will cause
     /// the constants that this pass sees to be potentially different than the constants
     /// seen by loop unrolling. Furthermore, this pass cannot be a part of loop unrolling
     /// since we must go through every instruction to find all references to `assert_constant`
     /// while loop unrolling only touches blocks with loops in them.
-    pub(crate) fn evaluate_assert_constant(mut self) -> Result<Ssa, RuntimeError> {
-        for function in self.functions.values_mut() {
+    pub(crate) fn evaluate_assert_constant(self) -> Result<Ssa, RuntimeError> {
+        self.filter_instructions(|function, instruction| {
+            check_instruction(function, instruction, false)
+        })
+    }
+
+    /// Evaluates every remaining call to `static_assert`, issuing an error if its condition
+    /// is false or still not known at compile-time. This must be run after loop unrolling.
+    pub(crate) fn evaluate_static_assert(self) -> Result<Ssa, RuntimeError> {
+        self.filter_instructions(|function, instruction| {
+            check_instruction(function, instruction, true)
+        })
+    }
+
+    /// Keeps only the instructions for which `keep_instruction` returns true
+    fn filter_instructions(
+        mut self,
+        keep_instruction: impl Fn(&mut Function, InstructionId) -> Result<bool, RuntimeError>
+            + Sync
+            + Send,
+    ) -> Result<Ssa, RuntimeError> {
+        self.try_for_each_function(|function| {
             for block in function.reachable_blocks() {
                 // Unfortunately we can't just use instructions.retain(...) here since
                 // check_instruction can also return an error
@@ -30,14 +54,15 @@ impl Ssa {
                 let mut filtered_instructions = Vec::with_capacity(instructions.len());
 
                 for instruction in instructions {
-                    if check_instruction(function, instruction)? {
+                    if keep_instruction(function, instruction)? {
                         filtered_instructions.push(instruction);
                     }
                 }
 
                 *function.dfg[block].instructions_mut() = filtered_instructions;
             }
-        }
+            Ok(())
+        })?;
         Ok(self)
     }
 }
@@ -47,16 +72,21 @@ impl Ssa {
 /// if a value (the loop bounds) are not known constants.
 ///
 /// This returns Ok(true) if the given instruction should be kept in the block and
-/// Ok(false) if it should be removed.
+/// Ok(false) if it should be removed. `after_unrolling` is true if static assertions
+/// whose condition is not a constant should be reported rather than kept.
 fn check_instruction(
     function: &mut Function,
     instruction: InstructionId,
+    after_unrolling: bool,
 ) -> Result<bool, RuntimeError> {
     let assert_constant_id = function.dfg.import_intrinsic(Intrinsic::AssertConstant);
+    let static_assert_id = function.dfg.import_intrinsic(Intrinsic::StaticAssert);
     match &function.dfg[instruction] {
         Instruction::Call { func, arguments } => {
             if *func == assert_constant_id {
                 evaluate_assert_constant(function, instruction, arguments)
+            } else if *func == static_assert_id {
+                evaluate_static_assert(function, instruction, arguments, after_unrolling)
             } else {
                 Ok(true)
             }
@@ -81,3 +111,40 @@ fn evaluate_assert_constant(
         Err(RuntimeError::AssertConstantFailed { call_stack })
     }
 }
+
+/// Evaluate a call to `static_assert(predicate, message)`, returning an error with the given
+/// message if the predicate is false. Successful assertions are removed. If the predicate is
+/// not yet a constant the call is kept, unless loops have already been unrolled in which
+/// case it will never become one.
+fn evaluate_static_assert(
+    function: &Function,
+    instruction: InstructionId,
+    arguments: &[ValueId],
+    after_unrolling: bool,
+) -> Result<bool, RuntimeError> {
+    match function.dfg.get_numeric_constant(arguments[0]) {
+        Some(predicate) if predicate.is_one() => Ok(false),
+        Some(_) => {
+            let message = constant_string(function, arguments[1]);
+            let call_stack = function.dfg.get_call_stack(instruction);
+            Err(RuntimeError::StaticAssertFailed { message, call_stack })
+        }
+        None if after_unrolling => {
+            let call_stack = function.dfg.get_call_stack(instruction);
+            Err(RuntimeError::StaticAssertDynamicPredicate { call_stack })
+        }
+        None => Ok(true),
+    }
+}
+
+/// Reads a string constant, which is represented in SSA as an array of bytes.
+/// Any byte which is not a constant is shown as a replacement character.
+fn constant_string(function: &Function, value: ValueId) -> String {
+    let Some((bytes, _)) = function.dfg.get_array_constant(value) else {
+        return String::new();
+    };
+    let bytes = bytes.iter().map(|byte| {
+        function.dfg.get_numeric_constant(*byte).map_or(b'?', |byte| byte.to_u128() as u8)
+    });
+    String::from_utf8_lossy(&bytes.collect::<Vec<_>>()).into_owned()
+}
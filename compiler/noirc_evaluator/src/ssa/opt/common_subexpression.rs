@@ -0,0 +1,240 @@
+//! The common subexpression elimination (CSE) pass removes [pure][Instruction::is_pure()]
+//! instructions which compute the same value as an earlier instruction, replacing their results
+//! with the results of the earlier instruction.
+//!
+//! This differs from the deduplication done by [`constant_folding`][super::constant_folding]
+//! in two ways:
+//! - An instruction may be replaced by an equivalent instruction in any block which dominates it,
+//!   rather than only by one earlier in the same block.
+//! - Instructions are compared up to the order of the operands of commutative operators,
+//!   so `add v0, v1` and `add v1, v0` are recognised as the same value.
+//!
+//! Blocks are visited in reverse post order so that every block is visited after the blocks
+//! which dominate it.
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        dfg::DataFlowGraph,
+        dom::DominatorTree,
+        function::Function,
+        instruction::{Binary, BinaryOp, Instruction, InstructionId},
+        post_order::PostOrder,
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+use fxhash::FxHashMap as HashMap;
+
+impl Ssa {
+    /// Removes any pure instruction whose value was already computed by an equivalent
+    /// instruction which dominates it.
+    ///
+    /// See [`common_subexpression`][self] module for more information.
+    pub(crate) fn eliminate_common_subexpressions(mut self) -> Ssa {
+        self.for_each_function(eliminate_common_subexpressions);
+        self
+    }
+}
+
+fn eliminate_common_subexpressions(function: &mut Function) {
+    let mut context = Context {
+        dom_tree: DominatorTree::with_function(function),
+        available_instructions: HashMap::default(),
+    };
+
+    let mut block_order = PostOrder::with_function(function).into_vec();
+    block_order.reverse();
+
+    for block in block_order {
+        context.eliminate_in_block(function, block);
+    }
+}
+
+struct Context {
+    dom_tree: DominatorTree,
+    /// Each pure instruction seen so far, along with the block it is in and its results.
+    /// The same instruction may appear in several blocks which do not dominate one another.
+    available_instructions: HashMap<Instruction, Vec<(BasicBlockId, Vec<ValueId>)>>,
+}
+
+impl Context {
+    fn eliminate_in_block(&mut self, function: &mut Function, block: BasicBlockId) {
+        let instructions = function.dfg[block].take_instructions();
+
+        for instruction_id in instructions {
+            if !self.try_replace_instruction(&mut function.dfg, block, instruction_id) {
+                function.dfg[block].insert_instruction(instruction_id);
+            }
+        }
+    }
+
+    /// Replaces the results of the given instruction with those of an equivalent instruction
+    /// which dominates it, if there is one. Returns true if the instruction was replaced and
+    /// can be removed.
+    fn try_replace_instruction(
+        &mut self,
+        dfg: &mut DataFlowGraph,
+        block: BasicBlockId,
+        id: InstructionId,
+    ) -> bool {
+        let instruction = dfg[id].clone();
+        if !instruction.is_pure(dfg) {
+            return false;
+        }
+
+        let key = Self::normalize(instruction.map_values(|value| dfg.resolve(value)));
+        let results = dfg.instruction_results(id).to_vec();
+
+        let candidates = self.available_instructions.entry(key).or_default();
+        let dom_tree = &mut self.dom_tree;
+        let existing = candidates
+            .iter()
+            .find(|(existing_block, _)| dom_tree.dominates(*existing_block, block));
+
+        match existing {
+            Some((_, existing_results)) => {
+                for (old_result, new_result) in results.iter().zip(existing_results) {
+                    dfg.set_value_from_id(*old_result, *new_result);
+                }
+                true
+            }
+            None => {
+                candidates.push((block, results));
+                false
+            }
+        }
+    }
+
+    /// Orders the operands of commutative binary operators so that instructions which only
+    /// differ in the order of their operands are considered equal.
+    fn normalize(instruction: Instruction) -> Instruction {
+        match instruction {
+            Instruction::Binary(Binary { lhs, rhs, operator })
+                if Self::is_commutative(operator) && rhs < lhs =>
+            {
+                Instruction::Binary(Binary { lhs: rhs, rhs: lhs, operator })
+            }
+            other => other,
+        }
+    }
+
+    fn is_commutative(operator: BinaryOp) -> bool {
+        use BinaryOp::*;
+        match operator {
+            Add | Mul | Eq | And | Or | Xor => true,
+            Sub | Div | Mod | Lt => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{function::RuntimeType, instruction::BinaryOp, map::Id, types::Type},
+    };
+
+    #[test]
+    fn commutative_instructions_are_merged() {
+        // fn main f0 {
+        //   b0(v0: Field, v1: Field):
+        //     v2 = add v0, v1
+        //     v3 = add v1, v0
+        //     v4 = sub v0, v1
+        //     v5 = sub v1, v0
+        //     v6 = mul v3, v4
+        //     v7 = mul v2, v5
+        //     return v6, v7
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::field());
+
+        let v2 = builder.insert_binary(v0, BinaryOp::Add, v1);
+        let v3 = builder.insert_binary(v1, BinaryOp::Add, v0);
+        let v4 = builder.insert_binary(v0, BinaryOp::Sub, v1);
+        let v5 = builder.insert_binary(v1, BinaryOp::Sub, v0);
+        let v6 = builder.insert_binary(v3, BinaryOp::Mul, v4);
+        let v7 = builder.insert_binary(v2, BinaryOp::Mul, v5);
+        builder.terminate_with_return(vec![v6, v7]);
+
+        let ssa = builder.finish();
+        assert_eq!(ssa.main().dfg[ssa.main().entry_block()].instructions().len(), 6);
+
+        // Expected output:
+        //
+        // fn main f0 {
+        //   b0(v0: Field, v1: Field):
+        //     v2 = add v0, v1
+        //     v4 = sub v0, v1
+        //     v5 = sub v1, v0
+        //     v6 = mul v2, v4
+        //     v7 = mul v2, v5
+        //     return v6, v7
+        // }
+        let ssa = ssa.eliminate_common_subexpressions();
+        let main = ssa.main();
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert_eq!(instructions.len(), 5);
+        assert_eq!(main.dfg.resolve(v3), v2);
+    }
+
+    #[test]
+    fn instructions_in_dominating_blocks_are_reused() {
+        // fn main f0 {
+        //   b0(v0: Field, v1: u1):
+        //     v2 = mul v0, v0
+        //     jmpif v1 then: b1, else: b2
+        //   b1():
+        //     v3 = mul v0, v0
+        //     v4 = add v0, Field 1
+        //     jmp b3(v3)
+        //   b2():
+        //     v5 = add v0, Field 1
+        //     jmp b3(v5)
+        //   b3(v6: Field):
+        //     v7 = add v0, Field 1
+        //     return v2, v6, v7
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::bool());
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+        let one = builder.field_constant(1u128);
+
+        let v2 = builder.insert_binary(v0, BinaryOp::Mul, v0);
+        builder.terminate_with_jmpif(v1, b1, b2);
+
+        builder.switch_to_block(b1);
+        let v3 = builder.insert_binary(v0, BinaryOp::Mul, v0);
+        let v4 = builder.insert_binary(v0, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b3, vec![v3]);
+
+        builder.switch_to_block(b2);
+        let v5 = builder.insert_binary(v0, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b3, vec![v5]);
+
+        builder.switch_to_block(b3);
+        let v6 = builder.add_block_parameter(b3, Type::field());
+        let v7 = builder.insert_binary(v0, BinaryOp::Add, one);
+        builder.terminate_with_return(vec![v2, v6, v7]);
+
+        let ssa = builder.finish().eliminate_common_subexpressions();
+        let main = ssa.main();
+
+        // `v3` is computed by `v2` in the dominating entry block
+        assert_eq!(main.dfg[b1].instructions().len(), 1);
+        assert_eq!(main.dfg.resolve(v3), v2);
+
+        // Neither of b1 and b2 dominate the other or b3, so each keeps its own addition
+        assert_eq!(main.dfg[b2].instructions().len(), 1);
+        assert_eq!(main.dfg[b3].instructions().len(), 1);
+        assert_ne!(main.dfg.resolve(v5), main.dfg.resolve(v4));
+        assert_eq!(main.dfg.resolve(v7), v7);
+    }
+}
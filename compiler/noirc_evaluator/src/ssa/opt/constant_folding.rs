@@ -41,9 +41,7 @@ impl Ssa {
     ///
     /// See [`constant_folding`][self] module for more information.
     pub(crate) fn fold_constants(mut self) -> Ssa {
-        for function in self.functions.values_mut() {
-            constant_fold(function);
-        }
+        self.for_each_function(constant_fold);
         self
     }
 }
@@ -177,13 +175,15 @@ impl Context {
 
 #[cfg(test)]
 mod test {
-    use std::rc::Rc;
+    use std::sync::Arc;
+
+    use acvm::FieldElement;
 
     use crate::ssa::{
         function_builder::FunctionBuilder,
         ir::{
             function::RuntimeType,
-            instruction::{BinaryOp, Instruction, TerminatorInstruction},
+            instruction::{Binary, BinaryOp, Instruction, TerminatorInstruction},
             map::Id,
             types::Type,
             value::{Value, ValueId},
@@ -264,7 +264,7 @@ mod test {
         let one = builder.field_constant(1u128);
         let v1 = builder.insert_binary(v0, BinaryOp::Add, one);
 
-        let array_type = Type::Array(Rc::new(vec![Type::field()]), 1);
+        let array_type = Type::Array(Arc::new(vec![Type::field()]), 1);
         let arr = builder.current_function.dfg.make_array(vec![v1].into(), array_type);
         builder.terminate_with_return(vec![arr]);
 
@@ -333,4 +333,43 @@ mod test {
 
         assert_eq!(instruction, &Instruction::Cast(ValueId::test_new(0), Type::unsigned(32)));
     }
+
+    #[test]
+    fn field_division_by_constant_is_multiplication() {
+        // fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = div v0, Field 4
+        //     return v2
+        // }
+        let main_id = Id::test_new(0);
+
+        // Compiling main
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let v0 = builder.add_parameter(Type::field());
+
+        let four = builder.field_constant(4u128);
+        let v2 = builder.insert_binary(v0, BinaryOp::Div, four);
+        builder.terminate_with_return(vec![v2]);
+
+        // Expected output:
+        //
+        // fn main f0 {
+        //   b0(v0: Field):
+        //     v3 = mul v0, Field 1/4
+        //     return v3
+        // }
+        let ssa = builder.finish().fold_constants();
+        let main = ssa.main();
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert_eq!(instructions.len(), 1);
+
+        match &main.dfg[instructions[0]] {
+            Instruction::Binary(Binary { lhs, rhs, operator: BinaryOp::Mul }) => {
+                assert_eq!(*lhs, v0);
+                let inverse = FieldElement::from(4u128).inverse();
+                assert_eq!(main.dfg.get_numeric_constant(*rhs), Some(inverse));
+            }
+            other => panic!("Expected a multiplication, found {other:?}"),
+        }
+    }
 }
@@ -1,5 +1,5 @@
 //! Dead Instruction Elimination (DIE) pass: Removes any instruction without side-effects for
-//! which the results are unused.
+//! which the results are unused, along with any stores to allocations which are never read.
 use std::collections::HashSet;
 
 use crate::ssa::{
@@ -7,7 +7,7 @@ use crate::ssa::{
         basic_block::{BasicBlock, BasicBlockId},
         dfg::DataFlowGraph,
         function::Function,
-        instruction::InstructionId,
+        instruction::{Instruction, InstructionId},
         post_order::PostOrder,
         value::{Value, ValueId},
     },
@@ -18,9 +18,7 @@ impl Ssa {
     /// Performs Dead Instruction Elimination (DIE) to remove any instructions with
     /// unused results.
     pub(crate) fn dead_instruction_elimination(mut self) -> Ssa {
-        for function in self.functions.values_mut() {
-            dead_instruction_elimination(function);
-        }
+        self.for_each_function(dead_instruction_elimination);
         self
     }
 }
@@ -32,6 +30,8 @@ impl Ssa {
 /// If we did not iterate blocks in this order we could not safely say whether or not the results
 /// of its instructions are needed elsewhere.
 fn dead_instruction_elimination(function: &mut Function) {
+    remove_unread_stores(function);
+
     let mut context = Context::default();
     let blocks = PostOrder::with_function(function);
 
@@ -40,6 +40,75 @@ fn dead_instruction_elimination(function: &mut Function) {
     }
 }
 
+/// Removes any `Store` instruction to an allocation which is never loaded from.
+///
+/// Stores have side effects so they are otherwise always kept by DIE. However, if the
+/// allocation being stored to is only ever used as the address of other stores, the stored
+/// values can never be read. Once these stores are removed the allocation itself, along with
+/// any array built only to be stored in it, are unused and will be removed by DIE as normal.
+fn remove_unread_stores(function: &mut Function) {
+    let blocks = function.reachable_blocks();
+    let mut used_addresses = HashSet::new();
+    let mut stores = Vec::new();
+
+    for block in &blocks {
+        for instruction_id in function.dfg[*block].instructions() {
+            match &function.dfg[*instruction_id] {
+                Instruction::Store { address, value } => {
+                    stores.push((*instruction_id, function.dfg.resolve(*address)));
+                    mark_used_values(&function.dfg, *value, &mut used_addresses);
+                }
+                instruction => instruction.for_each_value(|value| {
+                    mark_used_values(&function.dfg, value, &mut used_addresses);
+                }),
+            }
+        }
+
+        function.dfg[*block].unwrap_terminator().for_each_value(|value| {
+            mark_used_values(&function.dfg, value, &mut used_addresses);
+        });
+    }
+
+    let unread_stores: HashSet<_> = stores
+        .into_iter()
+        .filter(|(_, address)| {
+            !used_addresses.contains(address) && is_allocation(&function.dfg, *address)
+        })
+        .map(|(instruction, _)| instruction)
+        .collect();
+
+    if !unread_stores.is_empty() {
+        for block in blocks {
+            function.dfg[block]
+                .instructions_mut()
+                .retain(|instruction| !unread_stores.contains(instruction));
+        }
+    }
+}
+
+/// Marks the given value, and any values within it if it is an array, as used.
+fn mark_used_values(dfg: &DataFlowGraph, value: ValueId, used: &mut HashSet<ValueId>) {
+    let value = dfg.resolve(value);
+    used.insert(value);
+
+    if let Value::Array { array, .. } = &dfg[value] {
+        for element in array {
+            mark_used_values(dfg, *element, used);
+        }
+    }
+}
+
+/// Returns true if the value is the result of an `Allocate` instruction within this function.
+/// References from any other source, e.g. function parameters, may be read by the caller.
+fn is_allocation(dfg: &DataFlowGraph, address: ValueId) -> bool {
+    match &dfg[address] {
+        Value::Instruction { instruction, .. } => {
+            matches!(dfg[*instruction], Instruction::Allocate)
+        }
+        _ => false,
+    }
+}
+
 /// Per function context for tracking unused values and which instructions to remove.
 #[derive(Default)]
 struct Context {
@@ -128,11 +197,15 @@ impl Context {
 
 #[cfg(test)]
 mod test {
+    use std::sync::Arc;
+
+    use im::vector;
+
     use crate::ssa::{
         function_builder::FunctionBuilder,
         ir::{
             function::RuntimeType,
-            instruction::{BinaryOp, Intrinsic},
+            instruction::{BinaryOp, Instruction, Intrinsic},
             map::Id,
             types::Type,
         },
@@ -219,4 +292,58 @@ mod test {
         assert_eq!(main.dfg[main.entry_block()].instructions().len(), 1);
         assert_eq!(main.dfg[b1].instructions().len(), 6);
     }
+
+    #[test]
+    fn removes_unread_stores() {
+        // fn main f0 {
+        //   b0(v0: Field):
+        //     v1 = allocate
+        //     store v0 at v1
+        //     v2 = allocate
+        //     store [v0, v0] at v2
+        //     store v0 at v1
+        //     v3 = load v1
+        //     return v3
+        // }
+        let main_id = Id::test_new(0);
+
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let v0 = builder.add_parameter(Type::field());
+
+        let v1 = builder.insert_allocate();
+        builder.insert_store(v1, v0);
+
+        let array_type = Type::Array(Arc::new(vec![Type::field()]), 2);
+        let array = builder.array_constant(vector![v0, v0], array_type);
+        let v2 = builder.insert_allocate();
+        builder.insert_store(v2, array);
+
+        builder.insert_store(v1, v0);
+        let v3 = builder.insert_load(v1, Type::field());
+        builder.terminate_with_return(vec![v3]);
+
+        let ssa = builder.finish();
+        let main = ssa.main();
+        assert_eq!(main.dfg[main.entry_block()].instructions().len(), 6);
+
+        // Expected output:
+        //
+        // fn main f0 {
+        //   b0(v0: Field):
+        //     v1 = allocate
+        //     store v0 at v1
+        //     store v0 at v1
+        //     v3 = load v1
+        //     return v3
+        // }
+        let ssa = ssa.dead_instruction_elimination();
+        let main = ssa.main();
+
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert_eq!(instructions.len(), 4);
+        assert!(!instructions.iter().any(|instruction| matches!(
+            main.dfg[*instruction],
+            Instruction::Store { value, .. } if main.dfg.get_array_constant(value).is_some()
+        )));
+    }
 }
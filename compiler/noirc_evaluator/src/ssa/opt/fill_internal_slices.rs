@@ -61,10 +61,10 @@ use fxhash::FxHashMap as HashMap;
 
 impl Ssa {
     pub(crate) fn fill_internal_slices(mut self) -> Ssa {
-        for function in self.functions.values_mut() {
+        self.for_each_function(|function| {
             let mut context = Context::new(function);
             context.process_blocks();
-        }
+        });
         self
     }
 }
@@ -524,7 +524,7 @@ impl<'f> Context<'f> {
 #[cfg(test)]
 mod tests {
 
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     use acvm::FieldElement;
     use im::vector;
@@ -571,7 +571,7 @@ mod tests {
         let one = builder.field_constant(1_u128);
         builder.insert_constrain(slice_access_check, one, Some("Index out of bounds".to_owned()));
 
-        let field_element_type = Rc::new(vec![Type::field()]);
+        let field_element_type = Arc::new(vec![Type::field()]);
         let inner_slice_contents_type = Type::Slice(field_element_type);
 
         let inner_slice_small_len = builder.field_constant(3_u128);
@@ -582,7 +582,7 @@ mod tests {
         let inner_slice_big_contents =
             builder.array_constant(vector![two, two, two, two], inner_slice_contents_type.clone());
 
-        let outer_slice_element_type = Rc::new(vec![Type::field(), inner_slice_contents_type]);
+        let outer_slice_element_type = Arc::new(vec![Type::field(), inner_slice_contents_type]);
         let outer_slice_type = Type::Slice(outer_slice_element_type);
 
         let outer_slice_contents = builder.array_constant(
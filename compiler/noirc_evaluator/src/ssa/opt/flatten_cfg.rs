@@ -208,6 +208,11 @@ struct Context<'f> {
     /// condition. If we are under multiple conditions (a nested if), the topmost condition is
     /// the most recent condition combined with all previous conditions via `And` instructions.
     conditions: Vec<(BasicBlockId, ValueId)>,
+
+    /// Casts of each condition to the numeric types of the constraints and range checks predicated
+    /// on it. Since every instruction is inserted into the entry block, these can be shared by all
+    /// predicated instructions under the same condition rather than cast again for each of them.
+    casted_conditions: HashMap<(ValueId, Type), ValueId>,
 }
 
 pub(crate) struct Store {
@@ -239,6 +244,7 @@ fn flatten_function_cfg(function: &mut Function) {
         branch_ends,
         conditions: Vec::new(),
         outer_block_stores: HashMap::default(),
+        casted_conditions: HashMap::default(),
     };
     context.flatten();
 }
@@ -643,10 +649,8 @@ impl<'f> Context<'f> {
 
                     // Condition needs to be cast to argument type in order to multiply them together.
                     let argument_type = self.inserter.function.dfg.type_of_value(lhs);
-                    let casted_condition = self.insert_instruction(
-                        Instruction::Cast(condition, argument_type),
-                        call_stack.clone(),
-                    );
+                    let casted_condition =
+                        self.cast_condition(condition, argument_type, call_stack.clone());
 
                     let lhs = self.insert_instruction(
                         Instruction::binary(BinaryOp::Mul, lhs, casted_condition),
@@ -667,10 +671,8 @@ impl<'f> Context<'f> {
 
                     // Condition needs to be cast to argument type in order to multiply them together.
                     let argument_type = self.inserter.function.dfg.type_of_value(value);
-                    let casted_condition = self.insert_instruction(
-                        Instruction::Cast(condition, argument_type),
-                        call_stack.clone(),
-                    );
+                    let casted_condition =
+                        self.cast_condition(condition, argument_type, call_stack.clone());
 
                     let value = self.insert_instruction(
                         Instruction::binary(BinaryOp::Mul, value, casted_condition),
@@ -685,6 +687,16 @@ impl<'f> Context<'f> {
         }
     }
 
+    /// Casts `condition` to `typ`, reusing an earlier cast of the same condition if there is one.
+    fn cast_condition(&mut self, condition: ValueId, typ: Type, call_stack: CallStack) -> ValueId {
+        if let Some(casted) = self.casted_conditions.get(&(condition, typ.clone())) {
+            return *casted;
+        }
+        let casted = self.insert_instruction(Instruction::Cast(condition, typ.clone()), call_stack);
+        self.casted_conditions.insert((condition, typ), casted);
+        casted
+    }
+
     fn undo_stores_in_then_branch(&mut self, then_branch: &Branch) {
         for (address, store) in &then_branch.store_values {
             let address = *address;
@@ -696,7 +708,7 @@ impl<'f> Context<'f> {
 
 #[cfg(test)]
 mod test {
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     use crate::ssa::{
         function_builder::FunctionBuilder,
@@ -814,6 +826,50 @@ mod test {
         assert_eq!(ssa.main().reachable_blocks().len(), 1);
     }
 
+    #[test]
+    fn shares_casted_conditions() {
+        // fn main f0 {
+        //   b0(v0: u1, v1: Field, v2: Field):
+        //     jmpif v0, then: b1, else: b2
+        //   b1():
+        //     constrain v1 == Field 1
+        //     constrain v2 == Field 2
+        //     jmp b2()
+        //   b2():
+        //     return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+
+        let v0 = builder.add_parameter(Type::bool());
+        let v1 = builder.add_parameter(Type::field());
+        let v2 = builder.add_parameter(Type::field());
+
+        builder.terminate_with_jmpif(v0, b1, b2);
+
+        builder.switch_to_block(b1);
+        let one = builder.field_constant(1u128);
+        let two = builder.field_constant(2u128);
+        builder.insert_constrain(v1, one, None);
+        builder.insert_constrain(v2, two, None);
+        builder.terminate_with_jmp(b2, vec![]);
+
+        builder.switch_to_block(b2);
+        builder.terminate_with_return(vec![]);
+
+        // Both constraints are multiplied by the same cast of v0
+        let ssa = builder.finish().flatten_cfg();
+        let main = ssa.main();
+        let casts = count_instruction(
+            main,
+            |instruction| matches!(instruction, Instruction::Cast(value, _) if *value == v0),
+        );
+        assert_eq!(casts, 1);
+    }
+
     #[test]
     fn merge_stores() {
         // fn main f0 {
@@ -1294,7 +1350,7 @@ mod test {
         let b2 = builder.insert_block();
         let b3 = builder.insert_block();
 
-        let element_type = Rc::new(vec![Type::field()]);
+        let element_type = Arc::new(vec![Type::field()]);
         let array_type = Type::Array(element_type.clone(), 1);
 
         let zero = builder.field_constant(0_u128);
@@ -1472,4 +1528,64 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn merges_only_changed_array_elements() {
+        // fn main f0 {
+        //   b0(v0: u1, v1: [Field; 10]):
+        //     jmpif v0 then: b1, else: b2
+        //   b1():
+        //     v2 = array_set v1, index Field 1, value Field 5
+        //     jmp b3(v2)
+        //   b2():
+        //     v3 = array_set v1, index Field 2, value Field 6
+        //     jmp b3(v3)
+        //   b3(v4: [Field; 10]):
+        //     return v4
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        let array_type = Type::Array(Arc::new(vec![Type::field()]), 10);
+        let v0 = builder.add_parameter(Type::bool());
+        let v1 = builder.add_parameter(array_type.clone());
+        let v4 = builder.add_block_parameter(b3, array_type);
+
+        let one = builder.field_constant(1u128);
+        let two = builder.field_constant(2u128);
+        let five = builder.field_constant(5u128);
+        let six = builder.field_constant(6u128);
+
+        builder.terminate_with_jmpif(v0, b1, b2);
+
+        builder.switch_to_block(b1);
+        let v2 = builder.insert_array_set(v1, one, five);
+        builder.terminate_with_jmp(b3, vec![v2]);
+
+        builder.switch_to_block(b2);
+        let v3 = builder.insert_array_set(v1, two, six);
+        builder.terminate_with_jmp(b3, vec![v3]);
+
+        builder.switch_to_block(b3);
+        builder.terminate_with_return(vec![v4]);
+
+        // Only the elements at indices 1 and 2 differ between the two arrays, so only they need
+        // to be merged with a `mul` by each condition. The others are read from v1 directly.
+        let ssa = builder.finish().flatten_cfg();
+        let main = ssa.main();
+        assert_eq!(main.reachable_blocks().len(), 1);
+
+        let is_mul = |ins: &Instruction| matches!(ins, Instruction::Binary(binary) if binary.operator == BinaryOp::Mul);
+        assert_eq!(count_instruction(main, is_mul), 4);
+
+        let gets_from_original = count_instruction(
+            main,
+            |ins| matches!(ins, Instruction::ArrayGet { array, .. } if *array == v1),
+        );
+        assert_eq!(gets_from_original, 8);
+    }
 }
@@ -1,5 +1,5 @@
 use acvm::FieldElement;
-use fxhash::FxHashMap as HashMap;
+use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
 use crate::ssa::ir::{
     basic_block::BasicBlockId,
@@ -17,6 +17,10 @@ pub(crate) struct ValueMerger<'a> {
     store_values: Option<&'a HashMap<ValueId, Store>>,
     outer_block_stores: Option<&'a HashMap<ValueId, ValueId>>,
     slice_sizes: HashMap<ValueId, usize>,
+
+    /// Casts of each condition to the numeric types of the values merged under it, so that
+    /// merging many values under the same condition, e.g. the elements of an array, shares them.
+    casted_conditions: HashMap<(ValueId, Type), ValueId>,
 }
 
 impl<'a> ValueMerger<'a> {
@@ -32,6 +36,7 @@ impl<'a> ValueMerger<'a> {
             store_values,
             outer_block_stores,
             slice_sizes: HashMap::default(),
+            casted_conditions: HashMap::default(),
         }
     }
 
@@ -87,24 +92,8 @@ impl<'a> ValueMerger<'a> {
         let call_stack = if then_call_stack.is_empty() { else_call_stack } else { then_call_stack };
 
         // We must cast the bool conditions to the actual numeric type used by each value.
-        let then_condition = self
-            .dfg
-            .insert_instruction_and_results(
-                Instruction::Cast(then_condition, then_type),
-                self.block,
-                None,
-                call_stack.clone(),
-            )
-            .first();
-        let else_condition = self
-            .dfg
-            .insert_instruction_and_results(
-                Instruction::Cast(else_condition, else_type),
-                self.block,
-                None,
-                call_stack.clone(),
-            )
-            .first();
+        let then_condition = self.cast_condition(then_condition, then_type, &call_stack);
+        let else_condition = self.cast_condition(else_condition, else_type, &call_stack);
 
         let mul = Instruction::binary(BinaryOp::Mul, then_condition, then_value);
         let then_value = self
@@ -122,9 +111,26 @@ impl<'a> ValueMerger<'a> {
         self.dfg.insert_instruction_and_results(add, self.block, None, call_stack).first()
     }
 
+    /// Casts `condition` to `typ`, reusing an earlier cast of the same condition if there is one.
+    fn cast_condition(&mut self, condition: ValueId, typ: Type, call_stack: &CallStack) -> ValueId {
+        if let Some(casted) = self.casted_conditions.get(&(condition, typ.clone())) {
+            return *casted;
+        }
+        let cast = Instruction::Cast(condition, typ.clone());
+        let casted = self
+            .dfg
+            .insert_instruction_and_results(cast, self.block, None, call_stack.clone())
+            .first();
+        self.casted_conditions.insert((condition, typ), casted);
+        casted
+    }
+
     /// Given an if expression that returns an array: `if c { array1 } else { array2 }`,
     /// this function will recursively merge array1 and array2 into a single resulting array
     /// by creating a new array containing the result of self.merge_values for each element.
+    ///
+    /// If both arrays were created by setting constant indices of a common original array,
+    /// only the elements at those indices are merged and the rest are read from the original.
     pub(crate) fn merge_array_values(
         &mut self,
         typ: Type,
@@ -140,6 +146,44 @@ impl<'a> ValueMerger<'a> {
             _ => panic!("Expected array type"),
         };
 
+        if let Some((original, changed_indices)) = self.find_changed_indices(then_value, else_value)
+        {
+            if changed_indices.len() < len * element_types.len() {
+                for index in 0..len * element_types.len() {
+                    let element_type = &element_types[index % element_types.len()];
+                    let typevars = Some(vec![element_type.clone()]);
+                    let index_value = self.dfg.make_constant((index as u128).into(), Type::field());
+
+                    let mut get_element = |array, typevars| {
+                        let get = Instruction::ArrayGet { array, index: index_value };
+                        self.dfg
+                            .insert_instruction_and_results(
+                                get,
+                                self.block,
+                                typevars,
+                                CallStack::new(),
+                            )
+                            .first()
+                    };
+
+                    if changed_indices.contains(&index) {
+                        let then_element = get_element(then_value, typevars.clone());
+                        let else_element = get_element(else_value, typevars);
+                        merged.push_back(self.merge_values(
+                            then_condition,
+                            else_condition,
+                            then_element,
+                            else_element,
+                        ));
+                    } else {
+                        merged.push_back(get_element(original, typevars));
+                    }
+                }
+
+                return self.dfg.make_array(merged, typ);
+            }
+        }
+
         for i in 0..len {
             for (element_index, element_type) in element_types.iter().enumerate() {
                 let index = ((i * element_types.len() + element_index) as u128).into();
@@ -169,6 +213,59 @@ impl<'a> ValueMerger<'a> {
         self.dfg.make_array(merged, typ)
     }
 
+    /// Searches for an array which both `then_value` and `else_value` were created from by
+    /// a chain of `array_set` instructions with constant indices.
+    ///
+    /// Returns this original array along with every index which was set on the way to either
+    /// value. Elements at any other index are the same in both values.
+    fn find_changed_indices(
+        &self,
+        then_value: ValueId,
+        else_value: ValueId,
+    ) -> Option<(ValueId, HashSet<usize>)> {
+        // Each array in the chain leading to `then_value`, along with the indices set after it
+        let mut then_ancestors = HashMap::default();
+        let mut changed_indices = HashSet::default();
+        let mut current = self.dfg.resolve(then_value);
+        loop {
+            then_ancestors.insert(current, changed_indices.clone());
+            match self.constant_index_array_set(current) {
+                Some((array, index)) => {
+                    changed_indices.insert(index);
+                    current = array;
+                }
+                None => break,
+            }
+        }
+
+        let mut changed_indices = HashSet::default();
+        let mut current = self.dfg.resolve(else_value);
+        loop {
+            if let Some(then_indices) = then_ancestors.remove(&current) {
+                changed_indices.extend(then_indices);
+                return Some((current, changed_indices));
+            }
+            let (array, index) = self.constant_index_array_set(current)?;
+            changed_indices.insert(index);
+            current = array;
+        }
+    }
+
+    /// If `value` is the result of an `array_set` with a constant index, returns the array
+    /// which was set along with the index.
+    fn constant_index_array_set(&self, value: ValueId) -> Option<(ValueId, usize)> {
+        match &self.dfg[value] {
+            Value::Instruction { instruction, .. } => match &self.dfg[*instruction] {
+                Instruction::ArraySet { array, index, .. } => {
+                    let index = self.dfg.get_numeric_constant(*index)?.to_u128() as usize;
+                    Some((self.dfg.resolve(*array), index))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     fn merge_slice_values(
         &mut self,
         typ: Type,
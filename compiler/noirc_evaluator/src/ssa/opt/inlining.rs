@@ -2,20 +2,30 @@
 //! The purpose of this pass is to inline the instructions of each function call
 //! within the function caller. If all function calls are known, there will only
 //! be a single function remaining when the pass finishes.
+//!
+//! Calls between constrained functions are always inlined since ACIR has no notion of
+//! function calls. Calls from unconstrained code to other unconstrained functions are only
+//! inlined if the callee is marked `#[inline(always)]`, or if it is small and not marked
+//! `#[no_inline]`. Recursive unconstrained calls are never inlined.
 use std::collections::{BTreeSet, HashSet};
 
-use iter_extended::{btree_map, vecmap};
+use iter_extended::{try_btree_map, vecmap};
 
-use crate::ssa::{
-    function_builder::FunctionBuilder,
-    ir::{
-        basic_block::BasicBlockId,
-        dfg::{CallStack, InsertInstructionResult},
-        function::{Function, FunctionId, RuntimeType},
-        instruction::{Instruction, InstructionId, TerminatorInstruction},
-        value::{Value, ValueId},
+use noirc_frontend::token::InlineType;
+
+use crate::{
+    errors::RuntimeError,
+    ssa::{
+        function_builder::FunctionBuilder,
+        ir::{
+            basic_block::BasicBlockId,
+            dfg::{CallStack, InsertInstructionResult},
+            function::{Function, FunctionId, RuntimeType},
+            instruction::{Instruction, InstructionId, TerminatorInstruction},
+            value::{Value, ValueId},
+        },
+        ssa_gen::Ssa,
     },
-    ssa_gen::Ssa,
 };
 use fxhash::FxHashMap as HashMap;
 
@@ -23,11 +33,18 @@ use fxhash::FxHashMap as HashMap;
 /// frames at any point in time.
 const RECURSION_LIMIT: u32 = 1000;
 
+/// The maximum number of instructions an unconstrained function without an inline attribute
+/// may have to be inlined into another unconstrained function.
+const INLINE_INSTRUCTION_LIMIT: usize = 20;
+
 impl Ssa {
     /// Inline all functions within the IR.
     ///
     /// In the case of recursive functions, this will attempt
-    /// to recursively inline until the RECURSION_LIMIT is reached.
+    /// to recursively inline until the RECURSION_LIMIT is reached,
+    /// in which case an error is returned. Recursion can only be inlined
+    /// if the condition guarding the recursive call becomes known, e.g. when
+    /// it depends on a constant argument which decreases with each call.
     ///
     /// Functions are recursively inlined into main until either we finish
     /// inlining all functions or we encounter a function whose function id is not known.
@@ -36,13 +53,23 @@ impl Ssa {
     /// changes. This is because if the function's id later becomes known by a later
     /// pass, we would need to re-run all of inlining anyway to inline it, so we might
     /// as well save the work for later instead of performing it twice.
-    pub(crate) fn inline_functions(mut self) -> Ssa {
-        self.functions = btree_map(get_entry_point_functions(&self), |entry_point| {
-            let new_function = InlineContext::new(&self, entry_point).inline_all(&self);
-            (entry_point, new_function)
-        });
+    pub(crate) fn inline_functions(self) -> Result<Ssa, RuntimeError> {
+        self.inline_functions_with_report(false)
+    }
 
-        self
+    /// The same as `inline_functions` but if `show_inlining` is true, each call considered for
+    /// inlining is printed along with whether it was inlined.
+    pub(crate) fn inline_functions_with_report(
+        mut self,
+        show_inlining: bool,
+    ) -> Result<Ssa, RuntimeError> {
+        self.functions = try_btree_map(get_entry_point_functions(&self), |entry_point| {
+            let context = InlineContext::new(&self, entry_point, show_inlining);
+            let new_function = context.inline_all(&self)?;
+            Ok((entry_point, new_function))
+        })?;
+
+        Ok(self)
     }
 }
 
@@ -59,6 +86,13 @@ struct InlineContext {
 
     // The FunctionId of the entry point function we're inlining into in the old, unmodified Ssa.
     entry_point: FunctionId,
+
+    /// The functions currently being inlined, innermost last. Used to avoid inlining recursive
+    /// unconstrained calls.
+    inlining_stack: Vec<FunctionId>,
+
+    /// If true, print each inlining decision as it is made.
+    show_inlining: bool,
 }
 
 /// The per-function inlining context contains information that is only valid for one function.
@@ -106,20 +140,73 @@ fn get_entry_point_functions(ssa: &Ssa) -> BTreeSet<FunctionId> {
     entry_points
 }
 
+/// The number of instructions within the reachable blocks of the given function
+fn instruction_count(function: &Function) -> usize {
+    function
+        .reachable_blocks()
+        .into_iter()
+        .map(|block| function.dfg[block].instructions().len())
+        .sum()
+}
+
 impl InlineContext {
     /// Create a new context object for the function inlining pass.
     /// This starts off with an empty mapping of instructions for main's parameters.
     /// The function being inlined into will always be the main function, although it is
     /// actually a copy that is created in case the original main is still needed from a function
     /// that could not be inlined calling it.
-    fn new(ssa: &Ssa, entry_point: FunctionId) -> InlineContext {
+    fn new(ssa: &Ssa, entry_point: FunctionId, show_inlining: bool) -> InlineContext {
         let source = &ssa.functions[&entry_point];
-        let builder = FunctionBuilder::new(source.name().to_owned(), entry_point, source.runtime());
-        Self { builder, recursion_level: 0, entry_point, call_stack: CallStack::new() }
+        let mut builder =
+            FunctionBuilder::new(source.name().to_owned(), entry_point, source.runtime());
+        builder.set_inline_type(source.inline_type());
+        Self {
+            builder,
+            recursion_level: 0,
+            entry_point,
+            call_stack: CallStack::new(),
+            inlining_stack: Vec::new(),
+            show_inlining,
+        }
+    }
+
+    /// Decide whether a call to the given function should be inlined into the function
+    /// currently being built.
+    fn should_inline_call(&self, ssa: &Ssa, callee: FunctionId) -> bool {
+        let callee_function = &ssa.functions[&callee];
+
+        let inline = match (self.builder.current_function.runtime(), callee_function.runtime()) {
+            // ACIR has no notion of function calls so these must always be inlined
+            (RuntimeType::Acir, RuntimeType::Acir) => true,
+            (RuntimeType::Brillig, RuntimeType::Acir) => true,
+            // Calls into unconstrained code from constrained code are kept as calls to Brillig
+            (RuntimeType::Acir, RuntimeType::Brillig) => false,
+            (RuntimeType::Brillig, RuntimeType::Brillig) => {
+                let is_recursive =
+                    callee == self.entry_point || self.inlining_stack.contains(&callee);
+
+                match callee_function.inline_type() {
+                    _ if is_recursive => false,
+                    InlineType::Never => false,
+                    InlineType::Always => true,
+                    InlineType::Default => {
+                        instruction_count(callee_function) <= INLINE_INSTRUCTION_LIMIT
+                    }
+                }
+            }
+        };
+
+        if self.show_inlining {
+            let caller = self.builder.current_function.name();
+            let decision = if inline { "inlined" } else { "kept" };
+            println!("{caller}: {decision} call to {} ({callee})", callee_function.name());
+        }
+
+        inline
     }
 
     /// Start inlining the entry point function and all functions reachable from it.
-    fn inline_all(mut self, ssa: &Ssa) -> Function {
+    fn inline_all(mut self, ssa: &Ssa) -> Result<Function, RuntimeError> {
         let entry_point = &ssa.functions[&self.entry_point];
 
         let mut context = PerFunctionContext::new(&mut self, entry_point);
@@ -138,12 +225,12 @@ impl InlineContext {
         }
 
         context.blocks.insert(context.source_function.entry_block(), entry_block);
-        context.inline_blocks(ssa);
+        context.inline_blocks(ssa)?;
 
         // Finally, we should have 1 function left representing the inlined version of the target function.
         let mut new_ssa = self.builder.finish();
         assert_eq!(new_ssa.functions.len(), 1);
-        new_ssa.functions.pop_first().unwrap().1
+        Ok(new_ssa.functions.pop_first().unwrap().1)
     }
 
     /// Inlines a function into the current function and returns the translated return values
@@ -153,16 +240,23 @@ impl InlineContext {
         ssa: &Ssa,
         id: FunctionId,
         arguments: &[ValueId],
-    ) -> Vec<ValueId> {
+    ) -> Result<Vec<ValueId>, RuntimeError> {
         self.recursion_level += 1;
 
         if self.recursion_level > RECURSION_LIMIT {
-            panic!(
-                "Attempted to recur more than {RECURSION_LIMIT} times during function inlining."
-            );
+            // Each recursive call repeats the same locations, only keep the first of each so
+            // that the error points to the call which recurred.
+            let mut call_stack = CallStack::new();
+            for location in self.call_stack.iter() {
+                if !call_stack.contains(location) {
+                    call_stack.push_back(*location);
+                }
+            }
+            return Err(RuntimeError::UnboundedRecursion { call_stack });
         }
 
         let source_function = &ssa.functions[&id];
+        self.inlining_stack.push(id);
         let mut context = PerFunctionContext::new(self, source_function);
 
         let parameters = source_function.parameters();
@@ -172,9 +266,10 @@ impl InlineContext {
         let current_block = context.context.builder.current_block();
         context.blocks.insert(source_function.entry_block(), current_block);
 
-        let return_values = context.inline_blocks(ssa);
+        let return_values = context.inline_blocks(ssa)?;
+        self.inlining_stack.pop();
         self.recursion_level -= 1;
-        return_values
+        Ok(return_values)
     }
 }
 
@@ -278,7 +373,7 @@ impl<'function> PerFunctionContext<'function> {
     }
 
     /// Inline all reachable blocks within the source_function into the destination function.
-    fn inline_blocks(&mut self, ssa: &Ssa) -> Vec<ValueId> {
+    fn inline_blocks(&mut self, ssa: &Ssa) -> Result<Vec<ValueId>, RuntimeError> {
         let mut seen_blocks = HashSet::new();
         let mut block_queue = vec![self.source_function.entry_block()];
 
@@ -294,7 +389,7 @@ impl<'function> PerFunctionContext<'function> {
             self.context.builder.switch_to_block(translated_block_id);
 
             seen_blocks.insert(source_block_id);
-            self.inline_block_instructions(ssa, source_block_id);
+            self.inline_block_instructions(ssa, source_block_id)?;
 
             if let Some((block, values)) =
                 self.handle_terminator_instruction(source_block_id, &mut block_queue)
@@ -303,7 +398,7 @@ impl<'function> PerFunctionContext<'function> {
             }
         }
 
-        self.handle_function_returns(function_returns)
+        Ok(self.handle_function_returns(function_returns))
     }
 
     /// Handle inlining a function's possibly multiple return instructions.
@@ -339,20 +434,24 @@ impl<'function> PerFunctionContext<'function> {
 
     /// Inline each instruction in the given block into the function being inlined into.
     /// This may recurse if it finds another function to inline if a call instruction is within this block.
-    fn inline_block_instructions(&mut self, ssa: &Ssa, block_id: BasicBlockId) {
+    fn inline_block_instructions(
+        &mut self,
+        ssa: &Ssa,
+        block_id: BasicBlockId,
+    ) -> Result<(), RuntimeError> {
         let block = &self.source_function.dfg[block_id];
         for id in block.instructions() {
             match &self.source_function.dfg[*id] {
                 Instruction::Call { func, arguments } => match self.get_function(*func) {
-                    Some(function) => match ssa.functions[&function].runtime() {
-                        RuntimeType::Acir => self.inline_function(ssa, *id, function, arguments),
-                        RuntimeType::Brillig => self.push_instruction(*id),
-                    },
-                    None => self.push_instruction(*id),
+                    Some(function) if self.context.should_inline_call(ssa, function) => {
+                        self.inline_function(ssa, *id, function, arguments)?;
+                    }
+                    _ => self.push_instruction(*id),
                 },
                 _ => self.push_instruction(*id),
             }
         }
+        Ok(())
     }
 
     /// Inline a function call and remember the inlined return values in the values map
@@ -362,7 +461,7 @@ impl<'function> PerFunctionContext<'function> {
         call_id: InstructionId,
         function: FunctionId,
         arguments: &[ValueId],
-    ) {
+    ) -> Result<(), RuntimeError> {
         let old_results = self.source_function.dfg.instruction_results(call_id);
         let arguments = vecmap(arguments, |arg| self.translate_value(*arg));
 
@@ -374,7 +473,7 @@ impl<'function> PerFunctionContext<'function> {
             self.context.call_stack.push_back(location);
         }
 
-        let new_results = self.context.inline_function(ssa, function, &arguments);
+        let new_results = self.context.inline_function(ssa, function, &arguments)?;
 
         if has_location {
             self.context.call_stack.pop_back();
@@ -382,6 +481,7 @@ impl<'function> PerFunctionContext<'function> {
 
         let new_results = InsertInstructionResult::Results(call_id, &new_results);
         Self::insert_new_instruction_results(&mut self.values, old_results, new_results);
+        Ok(())
     }
 
     /// Push the given instruction from the source_function into the current block of the
@@ -504,13 +604,14 @@ impl<'function> PerFunctionContext<'function> {
 #[cfg(test)]
 mod test {
     use acvm::FieldElement;
+    use noirc_frontend::token::InlineType;
 
     use crate::ssa::{
         function_builder::FunctionBuilder,
         ir::{
             basic_block::BasicBlockId,
             function::RuntimeType,
-            instruction::{BinaryOp, Intrinsic, TerminatorInstruction},
+            instruction::{BinaryOp, Instruction, Intrinsic, TerminatorInstruction},
             map::Id,
             types::Type,
         },
@@ -543,7 +644,7 @@ mod test {
         let ssa = builder.finish();
         assert_eq!(ssa.functions.len(), 2);
 
-        let inlined = ssa.inline_functions();
+        let inlined = ssa.inline_functions().unwrap();
         assert_eq!(inlined.functions.len(), 1);
     }
 
@@ -609,7 +710,7 @@ mod test {
         let ssa = builder.finish();
         assert_eq!(ssa.functions.len(), 4);
 
-        let inlined = ssa.inline_functions();
+        let inlined = ssa.inline_functions().unwrap();
         assert_eq!(inlined.functions.len(), 1);
     }
 
@@ -683,7 +784,7 @@ mod test {
         //   b6():
         //     return Field 120
         // }
-        let inlined = ssa.inline_functions();
+        let inlined = ssa.inline_functions().unwrap();
         assert_eq!(inlined.functions.len(), 1);
 
         let main = inlined.main();
@@ -766,7 +867,7 @@ mod test {
         builder.switch_to_block(join_block);
         builder.terminate_with_return(vec![join_param]);
 
-        let ssa = builder.finish().inline_functions();
+        let ssa = builder.finish().inline_functions().unwrap();
         // Expected result:
         // fn main f3 {
         //   b0(v0: u1):
@@ -782,4 +883,61 @@ mod test {
         let main = ssa.main();
         assert_eq!(main.reachable_blocks().len(), 4);
     }
+
+    #[test]
+    fn unconstrained_inlining_hints() {
+        // brillig fn main f0 {
+        //   b0(v0: Field):
+        //     v1 = call small(v0)
+        //     v2 = call never(v1)
+        //     v3 = call main(v2)
+        //     return v3
+        // }
+        // brillig fn small f1 {
+        //   b0(v0: Field):
+        //     v1 = add v0, Field 1
+        //     return v1
+        // }
+        // #[no_inline]
+        // brillig fn never f2 {
+        //   b0(v0: Field):
+        //     v1 = add v0, Field 1
+        //     return v1
+        // }
+        let main_id = Id::test_new(0);
+        let small_id = Id::test_new(1);
+        let never_id = Id::test_new(2);
+
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::field());
+        let small = builder.import_function(small_id);
+        let never = builder.import_function(never_id);
+        let main = builder.import_function(main_id);
+        let v1 = builder.insert_call(small, vec![v0], vec![Type::field()])[0];
+        let v2 = builder.insert_call(never, vec![v1], vec![Type::field()])[0];
+        let v3 = builder.insert_call(main, vec![v2], vec![Type::field()])[0];
+        builder.terminate_with_return(vec![v3]);
+
+        for (name, id) in [("small", small_id), ("never", never_id)] {
+            builder.new_brillig_function(name.into(), id);
+            let v0 = builder.add_parameter(Type::field());
+            let one = builder.field_constant(1u128);
+            let v1 = builder.insert_binary(v0, BinaryOp::Add, one);
+            builder.terminate_with_return(vec![v1]);
+        }
+        builder.set_inline_type(InlineType::Never);
+
+        let ssa = builder.finish().inline_functions().unwrap();
+
+        // Only the call to `small` is inlined. The call to `never` is kept due to its attribute
+        // and the recursive call to `main` is kept as it could never be fully inlined.
+        let main = ssa.main();
+        let instructions = main.dfg[main.entry_block()].instructions();
+        let calls = instructions
+            .iter()
+            .filter(|id| matches!(main.dfg[**id], Instruction::Call { .. }))
+            .count();
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(calls, 2);
+    }
 }
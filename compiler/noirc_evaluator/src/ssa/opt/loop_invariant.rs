@@ -0,0 +1,237 @@
+//! The loop invariant code motion pass moves instructions which compute the same value on every
+//! iteration of a loop out of the loop and into the loop's pre-header. It is run before
+//! [`unrolling`][super::unrolling] so that an invariant computation, such as the hash of a value
+//! defined before the loop, is only generated once rather than once per iteration.
+//!
+//! An instruction is hoisted if each of its inputs is defined outside of the loop, either
+//! originally or because the instruction defining it was hoisted itself. Hoisted instructions
+//! are executed even when the loop body would not have executed them, e.g. because they were
+//! within a conditional in the loop body, so only instructions which can never fail are moved:
+//! arithmetic, casts and side-effect free black box functions. Array accesses and intrinsics
+//! such as `to_le_bits` are left in the loop since they may fail on some inputs.
+use std::collections::HashSet;
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        dfg::DataFlowGraph,
+        function::{Function, RuntimeType},
+        instruction::{Instruction, InstructionId, Intrinsic},
+        post_order::PostOrder,
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+use super::unrolling::{find_all_loops, get_pre_header, Loop};
+
+impl Ssa {
+    /// Moves instructions which are invariant in a loop out of the loop.
+    ///
+    /// See [`loop_invariant`][self] module for more information.
+    pub(crate) fn loop_invariant_code_motion(mut self) -> Ssa {
+        self.for_each_function(|function| {
+            // Loops in brillig functions are not unrolled, so each instruction is only
+            // generated once regardless.
+            if function.runtime() != RuntimeType::Brillig {
+                hoist_loop_invariants(function);
+            }
+        });
+        self
+    }
+}
+
+fn hoist_loop_invariants(function: &mut Function) {
+    let loops = find_all_loops(function);
+
+    let mut block_order = PostOrder::with_function(function).into_vec();
+    block_order.reverse();
+
+    // Loops are sorted from smallest to largest, so the inner loops of nested loops are visited
+    // first. An instruction hoisted out of an inner loop may then be hoisted from the outer loop.
+    for loop_ in &loops.yet_to_unroll {
+        let pre_header = get_pre_header(&loops.cfg, loop_);
+        let blocks = block_order.iter().filter(|block| loop_.blocks.contains(block));
+
+        let mut context = LoopContext::new(function, loop_);
+        for block in blocks {
+            context.hoist_from_block(function, *block, pre_header);
+        }
+    }
+}
+
+struct LoopContext<'a> {
+    loop_: &'a Loop,
+    /// Every instruction remaining within the loop
+    instructions: HashSet<InstructionId>,
+}
+
+impl<'a> LoopContext<'a> {
+    fn new(function: &Function, loop_: &'a Loop) -> Self {
+        let instructions = loop_
+            .blocks
+            .iter()
+            .flat_map(|block| function.dfg[*block].instructions().iter().copied())
+            .collect();
+        LoopContext { loop_, instructions }
+    }
+
+    fn hoist_from_block(
+        &mut self,
+        function: &mut Function,
+        block: BasicBlockId,
+        pre_header: BasicBlockId,
+    ) {
+        let instructions = function.dfg[block].take_instructions();
+
+        for instruction_id in instructions {
+            if self.can_hoist(&function.dfg, instruction_id) {
+                self.instructions.remove(&instruction_id);
+                function.dfg[pre_header].insert_instruction(instruction_id);
+            } else {
+                function.dfg[block].insert_instruction(instruction_id);
+            }
+        }
+    }
+
+    fn can_hoist(&self, dfg: &DataFlowGraph, instruction_id: InstructionId) -> bool {
+        let instruction = &dfg[instruction_id];
+        if !Self::cannot_fail(dfg, instruction) {
+            return false;
+        }
+
+        let mut invariant = true;
+        instruction.for_each_value(|value| invariant &= self.is_invariant(dfg, value));
+        invariant
+    }
+
+    /// True if executing the instruction can never fail or affect anything other than its results
+    fn cannot_fail(dfg: &DataFlowGraph, instruction: &Instruction) -> bool {
+        match instruction {
+            Instruction::Binary(_) | Instruction::Cast(..) | Instruction::Not(_) => {
+                !instruction.has_side_effects(dfg)
+            }
+            Instruction::Call { func, .. } => {
+                matches!(dfg[*func], Value::Intrinsic(Intrinsic::BlackBox(_)))
+                    && !instruction.has_side_effects(dfg)
+            }
+            _ => false,
+        }
+    }
+
+    /// True if the given value is defined outside of the loop
+    fn is_invariant(&self, dfg: &DataFlowGraph, value: ValueId) -> bool {
+        match &dfg[dfg.resolve(value)] {
+            Value::Instruction { instruction, .. } => !self.instructions.contains(instruction),
+            Value::Param { block, .. } => !self.loop_.blocks.contains(block),
+            Value::Array { array, .. } => {
+                array.iter().all(|element| self.is_invariant(dfg, *element))
+            }
+            Value::NumericConstant { .. }
+            | Value::Function(_)
+            | Value::Intrinsic(_)
+            | Value::ForeignFunction(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{
+            function::RuntimeType,
+            instruction::{BinaryOp, Intrinsic},
+            map::Id,
+            types::Type,
+        },
+    };
+    use acvm::acir::BlackBoxFunc;
+
+    #[test]
+    fn hoists_invariant_instructions() {
+        // fn main(x: Field) {
+        //     for i in 0..3 {
+        //         assert(std::hash::pedersen_hash([x * x]) != i);
+        //     }
+        // }
+        //
+        // fn main f0 {
+        //   b0(v0: Field):
+        //     jmp b1(Field 0)
+        //   b1(v1: Field):  // loop header
+        //     v2 = lt v1, Field 3
+        //     jmpif v2, then: b2, else: b3
+        //   b2():
+        //     v3 = mul v0, v0
+        //     v4 = call pedersen_hash([v3])
+        //     v5 = eq v4, v1
+        //     constrain v5 == u1 0
+        //     v6 = add v1, Field 1
+        //     jmp b1(v6)
+        //   b3():
+        //     return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let v0 = builder.add_parameter(Type::field());
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        let zero = builder.field_constant(0u128);
+        let one = builder.field_constant(1u128);
+        let three = builder.field_constant(3u128);
+        builder.terminate_with_jmp(b1, vec![zero]);
+
+        builder.switch_to_block(b1);
+        let v1 = builder.add_block_parameter(b1, Type::field());
+        let v2 = builder.insert_binary(v1, BinaryOp::Lt, three);
+        builder.terminate_with_jmpif(v2, b2, b3);
+
+        builder.switch_to_block(b2);
+        let v3 = builder.insert_binary(v0, BinaryOp::Mul, v0);
+        let inputs =
+            builder.array_constant(vec![v3].into(), Type::Array(Arc::new(vec![Type::field()]), 1));
+        let pedersen = builder.import_intrinsic_id(Intrinsic::BlackBox(BlackBoxFunc::PedersenHash));
+        let v4 = builder.insert_call(pedersen, vec![inputs], vec![Type::field()])[0];
+        let v5 = builder.insert_binary(v4, BinaryOp::Eq, v1);
+        let false_value = builder.numeric_constant(0u128, Type::bool());
+        builder.insert_constrain(v5, false_value, None);
+        let v6 = builder.insert_binary(v1, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b1, vec![v6]);
+
+        builder.switch_to_block(b3);
+        builder.terminate_with_return(vec![]);
+
+        let ssa = builder.finish();
+        assert_eq!(ssa.main().dfg[b2].instructions().len(), 5);
+
+        // Expected output:
+        //
+        // fn main f0 {
+        //   b0(v0: Field):
+        //     v3 = mul v0, v0
+        //     v4 = call pedersen_hash([v3])
+        //     jmp b1(Field 0)
+        //   b1(v1: Field):
+        //     v2 = lt v1, Field 3
+        //     jmpif v2, then: b2, else: b3
+        //   b2():
+        //     v5 = eq v4, v1
+        //     constrain v5 == u1 0
+        //     v6 = add v1, Field 1
+        //     jmp b1(v6)
+        //   b3():
+        //     return
+        // }
+        let ssa = ssa.loop_invariant_code_motion();
+        let main = ssa.main();
+        assert_eq!(main.dfg[main.entry_block()].instructions().len(), 2);
+        assert_eq!(main.dfg[b1].instructions().len(), 1);
+        assert_eq!(main.dfg[b2].instructions().len(), 3);
+    }
+}
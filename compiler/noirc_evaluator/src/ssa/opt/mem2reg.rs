@@ -40,6 +40,16 @@
 //!     - Conservatively mark every alias in the block to `Unknown`.
 //!   - Additionally, if there were no Loads to any alias of the address between this Store and
 //!     the previous Store to the same address, the previous store can be removed.
+//! - On `Instruction::ArraySet { array, index, value }`:
+//!   - If `index` is a constant and side effects are unconditionally enabled, remember that the
+//!     result holds `value` at `index` and matches `array` at every other index.
+//! - On `Instruction::ArrayGet { array, index }`:
+//!   - If `index` is a constant, look back through the chain of remembered `ArraySet`s on `array`.
+//!     If one of them wrote to `index`, replace the result of the `ArrayGet` with the value written.
+//!     Since arrays of structs are flattened and nested arrays are updated with an `ArrayGet`,
+//!     `ArraySet` pair per level, this lets loads and stores of individual struct fields and nested
+//!     array elements be removed once the reference holding the outer array is known.
+//!   - If the result may contain references, it shares the aliases of `array`.
 //! - On `Instruction::Call { arguments }`:
 //!   - If any argument of the call is a reference, set the value of each alias of that
 //!     reference to `Unknown`
@@ -87,11 +97,11 @@ impl Ssa {
     /// Attempts to remove any load instructions that recover values that are already available in
     /// scope, and attempts to remove stores that are subsequently redundant.
     pub(crate) fn mem2reg(mut self) -> Ssa {
-        for function in self.functions.values_mut() {
+        self.for_each_function(|function| {
             let mut context = PerFunctionContext::new(function);
             context.mem2reg();
             context.remove_instructions();
-        }
+        });
         self
     }
 }
@@ -109,6 +119,15 @@ struct PerFunctionContext<'f> {
     /// We avoid removing individual instructions as we go since removing elements
     /// from the middle of Vecs many times will be slower than a single call to `retain`.
     instructions_to_remove: BTreeSet<InstructionId>,
+
+    /// Maps the result of each `ArraySet` with a constant index to the array it was set on,
+    /// the index, and the value written there. `ArraySet`s performed while side effects may be
+    /// disabled are not tracked since their result may still hold the original element.
+    array_sets: BTreeMap<ValueId, (ValueId, u64, ValueId)>,
+
+    /// False if the most recent `EnableSideEffects` instruction in the current block was
+    /// given a condition which is not known to be true.
+    side_effects_enabled: bool,
 }
 
 impl<'f> PerFunctionContext<'f> {
@@ -122,6 +141,8 @@ impl<'f> PerFunctionContext<'f> {
             inserter: FunctionInserter::new(function),
             blocks: BTreeMap::new(),
             instructions_to_remove: BTreeSet::new(),
+            array_sets: BTreeMap::new(),
+            side_effects_enabled: true,
         }
     }
 
@@ -168,6 +189,7 @@ impl<'f> PerFunctionContext<'f> {
     /// at the end of this block will be remembered in `self.blocks`.
     fn analyze_block(&mut self, block: BasicBlockId, mut references: Block) {
         let instructions = self.inserter.function.dfg[block].take_instructions();
+        self.side_effects_enabled = true;
 
         for instruction in instructions {
             self.analyze_instruction(block, &mut references, instruction);
@@ -260,28 +282,48 @@ impl<'f> PerFunctionContext<'f> {
                 references.expressions.insert(result, Expression::Other(result));
                 references.aliases.insert(Expression::Other(result), AliasSet::known(result));
             }
-            Instruction::ArrayGet { array, .. } => {
+            Instruction::ArrayGet { array, index } => {
                 let result = self.inserter.function.dfg.instruction_results(instruction)[0];
                 references.mark_value_used(*array, self.inserter.function);
 
-                if self.inserter.function.dfg.value_is_reference(result) {
-                    let array = self.inserter.function.dfg.resolve(*array);
-                    let expression = Expression::ArrayElement(Box::new(Expression::Other(array)));
+                let array = self.inserter.function.dfg.resolve(*array);
+                if let Some(value) = self.find_array_element(array, *index) {
+                    self.inserter.map_value(result, value);
+                    self.instructions_to_remove.insert(instruction);
+                    return;
+                }
+
+                let result_type = self.inserter.function.dfg.type_of_value(result);
+                if Self::contains_references(&result_type) {
+                    let expression = Self::array_expression(references, array);
 
                     if let Some(aliases) = references.aliases.get_mut(&expression) {
                         aliases.insert(result);
                     }
+
+                    // A nested array taken from `array` may only hold references already held
+                    // by `array`, so it shares the same aliases.
+                    if !self.inserter.function.dfg.value_is_reference(result) {
+                        references.expressions.insert(result, expression);
+                    }
                 }
             }
-            Instruction::ArraySet { array, value, .. } => {
-                references.mark_value_used(*array, self.inserter.function);
-                let element_type = self.inserter.function.dfg.type_of_value(*value);
+            Instruction::ArraySet { array, index, value } => {
+                let (array, index, value) = (*array, *index, *value);
+                references.mark_value_used(array, self.inserter.function);
+                let element_type = self.inserter.function.dfg.type_of_value(value);
+                let result = self.inserter.function.dfg.instruction_results(instruction)[0];
+                let array = self.inserter.function.dfg.resolve(array);
 
-                if Self::contains_references(&element_type) {
-                    let result = self.inserter.function.dfg.instruction_results(instruction)[0];
-                    let array = self.inserter.function.dfg.resolve(*array);
+                if self.side_effects_enabled {
+                    let index = self.inserter.function.dfg.get_numeric_constant(index);
+                    if let Some(index) = index.and_then(|index| index.try_to_u64()) {
+                        self.array_sets.insert(result, (array, index, value));
+                    }
+                }
 
-                    let expression = Expression::ArrayElement(Box::new(Expression::Other(array)));
+                if Self::contains_references(&element_type) {
+                    let expression = Self::array_expression(references, array);
 
                     let mut aliases = if let Some(aliases) = references.aliases.get_mut(&expression)
                     {
@@ -296,12 +338,16 @@ impl<'f> PerFunctionContext<'f> {
                         AliasSet::unknown()
                     };
 
-                    aliases.unify(&references.get_aliases_for_value(*value));
+                    aliases.unify(&references.get_aliases_for_value(value));
 
                     references.expressions.insert(result, expression.clone());
                     references.aliases.insert(expression, aliases);
                 }
             }
+            Instruction::EnableSideEffects { condition } => {
+                let condition = self.inserter.function.dfg.get_numeric_constant(*condition);
+                self.side_effects_enabled = condition.map_or(false, |condition| condition.is_one());
+            }
             Instruction::Call { arguments, .. } => self.mark_all_unknown(arguments, references),
             _ => (),
         }
@@ -322,6 +368,42 @@ impl<'f> PerFunctionContext<'f> {
         }
     }
 
+    /// Returns the expression used as the key for the aliases of any references held in `array`.
+    fn array_expression(references: &Block, array: ValueId) -> Expression {
+        match references.expressions.get(&array) {
+            Some(expression @ Expression::ArrayElement(_)) => expression.clone(),
+            _ => Expression::ArrayElement(Box::new(Expression::Other(array))),
+        }
+    }
+
+    /// Searches backwards through the `ArraySet`s which produced `array` for the value held at
+    /// the given constant `index`. Returns None if the index is not constant or if the value
+    /// may have been written by an `ArraySet` with a non-constant or predicated index.
+    fn find_array_element(&self, mut array: ValueId, index: ValueId) -> Option<ValueId> {
+        let dfg = &self.inserter.function.dfg;
+        let index = dfg.get_numeric_constant(index)?.try_to_u64()?;
+
+        // An out of bounds read must still fail, so only forward indices known to be in bounds.
+        let Type::Array(element_types, length) = dfg.type_of_value(array) else {
+            return None;
+        };
+        if index >= (element_types.len() * length) as u64 {
+            return None;
+        }
+
+        loop {
+            if let Some((elements, _)) = dfg.get_array_constant(array) {
+                return elements.get(index as usize).copied();
+            }
+
+            let (previous, set_index, value) = self.array_sets.get(&array)?;
+            if *set_index == index {
+                return Some(*value);
+            }
+            array = dfg.resolve(*previous);
+        }
+    }
+
     fn contains_references(typ: &Type) -> bool {
         match typ {
             Type::Numeric(_) => false,
@@ -397,7 +479,7 @@ impl<'f> PerFunctionContext<'f> {
 
 #[cfg(test)]
 mod tests {
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     use acvm::FieldElement;
     use im::vector;
@@ -431,7 +513,7 @@ mod tests {
         let one = builder.field_constant(FieldElement::one());
         let two = builder.field_constant(FieldElement::one());
 
-        let element_type = Rc::new(vec![Type::field()]);
+        let element_type = Arc::new(vec![Type::field()]);
         let array_type = Type::Array(element_type, 2);
         let array = builder.array_constant(vector![one, two], array_type.clone());
 
@@ -702,4 +784,118 @@ mod tests {
         // We expect the last eq to be optimized out
         assert_eq!(b1_instructions.len(), 1);
     }
+
+    #[test]
+    fn forwards_struct_fields_through_array_set() {
+        // fn main {
+        //   b0(v0: [(Field, Field); 2]):
+        //     v1 = allocate
+        //     store v0 at v1
+        //     v2 = load v1
+        //     v3 = array_set v2, index Field 3, value Field 5
+        //     store v3 at v1
+        //     v4 = load v1
+        //     v5 = array_get v4, index Field 3
+        //     return v5
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+
+        let element_type = Arc::new(vec![Type::field(), Type::field()]);
+        let array_type = Type::Array(element_type, 2);
+        let v0 = builder.add_parameter(array_type.clone());
+
+        let v1 = builder.insert_allocate();
+        builder.insert_store(v1, v0);
+        let v2 = builder.insert_load(v1, array_type.clone());
+
+        let three = builder.field_constant(3u128);
+        let five = builder.field_constant(5u128);
+        let v3 = builder.insert_array_set(v2, three, five);
+        builder.insert_store(v1, v3);
+
+        let v4 = builder.insert_load(v1, array_type);
+        let v5 = builder.insert_array_get(v4, three, Type::field());
+        builder.terminate_with_return(vec![v5]);
+
+        let ssa = builder.finish().mem2reg();
+
+        let main = ssa.main();
+        let block_id = main.entry_block();
+
+        assert_eq!(count_loads(block_id, &main.dfg), 0);
+        assert_eq!(count_stores(block_id, &main.dfg), 0);
+        assert_eq!(count_array_gets(block_id, &main.dfg), 0);
+
+        let ret_val_id = match main.dfg[block_id].terminator().unwrap() {
+            TerminatorInstruction::Return { return_values, .. } => return_values.first().unwrap(),
+            _ => unreachable!(),
+        };
+        assert_eq!(main.dfg.get_numeric_constant(*ret_val_id), Some(FieldElement::from(5u128)));
+    }
+
+    #[test]
+    fn forwards_nested_array_elements() {
+        // fn main {
+        //   b0(v0: [[Field; 2]; 2], v1: u1):
+        //     v2 = array_get v0, index Field 1
+        //     v3 = array_set v2, index Field 0, value Field 7
+        //     v4 = array_set v0, index Field 1, value v3
+        //     v5 = array_get v4, index Field 1
+        //     v6 = array_get v5, index Field 0
+        //     enable_side_effects v1
+        //     v7 = array_set v4, index Field 0, value v3
+        //     v8 = array_get v7, index Field 0
+        //     return v6, v8
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+
+        let inner_type = Type::Array(Arc::new(vec![Type::field()]), 2);
+        let outer_type = Type::Array(Arc::new(vec![inner_type.clone()]), 2);
+        let v0 = builder.add_parameter(outer_type);
+        let v1 = builder.add_parameter(Type::bool());
+
+        let zero = builder.field_constant(0u128);
+        let one = builder.field_constant(1u128);
+        let seven = builder.field_constant(7u128);
+
+        let v2 = builder.insert_array_get(v0, one, inner_type.clone());
+        let v3 = builder.insert_array_set(v2, zero, seven);
+        let v4 = builder.insert_array_set(v0, one, v3);
+        let v5 = builder.insert_array_get(v4, one, inner_type.clone());
+        let v6 = builder.insert_array_get(v5, zero, Type::field());
+
+        // Array sets under a predicate may not have written their value so they cannot be forwarded
+        builder.insert_instruction(Instruction::EnableSideEffects { condition: v1 }, None);
+        let v7 = builder.insert_array_set(v4, zero, v3);
+        let v8 = builder.insert_array_get(v7, zero, inner_type);
+        builder.terminate_with_return(vec![v6, v8]);
+
+        let ssa = builder.finish().mem2reg();
+
+        let main = ssa.main();
+        let block_id = main.entry_block();
+
+        // Only the initial array_get of v0 and the predicated array_get remain
+        assert_eq!(count_array_gets(block_id, &main.dfg), 2);
+
+        let return_values = match main.dfg[block_id].terminator().unwrap() {
+            TerminatorInstruction::Return { return_values, .. } => return_values,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            main.dfg.get_numeric_constant(return_values[0]),
+            Some(FieldElement::from(7u128))
+        );
+        assert!(main.dfg.get_array_constant(return_values[1]).is_none());
+    }
+
+    fn count_array_gets(block: BasicBlockId, dfg: &DataFlowGraph) -> usize {
+        dfg[block]
+            .instructions()
+            .iter()
+            .filter(|instruction_id| matches!(dfg[**instruction_id], Instruction::ArrayGet { .. }))
+            .count()
+    }
 }
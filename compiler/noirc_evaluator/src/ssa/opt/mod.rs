@@ -5,12 +5,16 @@
 //! Generally, these passes are also expected to minimize the final amount of instructions.
 mod array_use;
 mod assert_constant;
+mod common_subexpression;
 mod constant_folding;
 mod defunctionalize;
 mod die;
 mod fill_internal_slices;
 pub(crate) mod flatten_cfg;
 mod inlining;
+mod loop_invariant;
 mod mem2reg;
+mod range_analysis;
 mod simplify_cfg;
+mod unreachable_functions;
 mod unrolling;
@@ -0,0 +1,522 @@
+//! The range analysis pass tracks an upper bound on the number of bits needed to represent each
+//! numeric value in a constrained function, and uses it to remove range constraints which are
+//! already known to hold:
+//! - A `RangeCheck` is removed if its value is known to fit in `max_bit_size` bits.
+//! - A `Truncate` is removed if its value is known to fit in `bit_size` bits, as truncation would
+//!   leave it unchanged. Otherwise its `max_bit_size` is tightened to the known bound of the value,
+//!   which reduces the cost of the truncation in ACIR.
+//! - A truncation whose result is only added or multiplied before being truncated again is delayed
+//!   by using the untruncated value instead, as truncating modulo `2^bit_size` commutes with
+//!   addition and multiplication. This batches chains of wrapping operations into a single
+//!   truncation placed where the value is first used otherwise, e.g. by a comparison or a return.
+//!   Truncations are only delayed while the untruncated value cannot wrap around the field modulus.
+//!
+//! Bounds are derived as follows:
+//! - Constants need exactly as many bits as their value.
+//! - The results of calls are unbounded, as the outputs of Brillig calls are not range
+//!   constrained in ACIR and may be any field element. Reads from arrays returned by a call, or
+//!   holding a value which may not fit in its type, are unbounded too.
+//! - Other values which are not the result of an arithmetic instruction, such as parameters, are
+//!   range constrained to their type when they are created in ACIR, so they fit in the bit size
+//!   of their type.
+//! - Arithmetic results are bounded by their operands, e.g. the sum of two 8-bit values fits in
+//!   9 bits and their product in 16 bits. Results which may wrap around the field modulus, and the
+//!   results of subtractions which may underflow, are unbounded.
+//!
+//! Brillig functions are left untouched since they have no range constraints to remove.
+use acvm::FieldElement;
+use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+use crate::ssa::{
+    ir::{
+        dfg::DataFlowGraph,
+        function::{Function, RuntimeType},
+        instruction::{Binary, BinaryOp, Instruction, InstructionId},
+        post_order::PostOrder,
+        types::{NumericType, Type},
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+
+impl Ssa {
+    /// Removes any range constraints and truncations which are known to already hold from the
+    /// bounds of the values they are applied to.
+    ///
+    /// See [`range_analysis`][self] module for more information.
+    pub(crate) fn remove_redundant_range_constraints(mut self) -> Ssa {
+        self.for_each_function(|function| {
+            if function.runtime() == RuntimeType::Acir {
+                remove_redundant_range_constraints(function);
+            }
+        });
+        self
+    }
+}
+
+fn remove_redundant_range_constraints(function: &mut Function) {
+    let mut context = Context { uses: Uses::new(function), ..Context::default() };
+
+    let mut block_order = PostOrder::with_function(function).into_vec();
+    block_order.reverse();
+
+    for block in block_order {
+        let instructions = function.dfg[block].take_instructions();
+
+        for instruction_id in instructions {
+            if context.analyze_instruction(&mut function.dfg, instruction_id) {
+                function.dfg[block].insert_instruction(instruction_id);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    /// The maximum number of bits needed to represent the result of each arithmetic instruction
+    /// seen so far. Values not in this map are bounded by their type instead.
+    max_bits: HashMap<ValueId, u32>,
+
+    /// Maps the result of each truncation, and any casts of it which do not narrow it further, to
+    /// the value being truncated and the bit size it is truncated to.
+    truncated: HashMap<ValueId, (ValueId, u32)>,
+
+    /// Results of arithmetic instructions which had a truncated operand replaced by its untruncated
+    /// value, so any truncation of them must account for their larger bound.
+    widened: HashSet<ValueId>,
+
+    /// Arrays which may hold elements that don't fit in their type, such as those returned by
+    /// a call. Elements read from them are unbounded.
+    unbounded_arrays: HashSet<ValueId>,
+
+    uses: Uses,
+}
+
+impl Context {
+    /// Records the bounds of the results of the given instruction, simplifying it if its range
+    /// constraint is already known to hold. Returns false if the instruction should be removed.
+    fn analyze_instruction(
+        &mut self,
+        dfg: &mut DataFlowGraph,
+        instruction_id: InstructionId,
+    ) -> bool {
+        match dfg[instruction_id].clone() {
+            Instruction::RangeCheck { value, max_bit_size, .. } => {
+                self.max_bits(dfg, value) > max_bit_size
+            }
+            Instruction::Truncate { value, bit_size, max_bit_size } => {
+                let value_bits = self.max_bits(dfg, value);
+                let result = dfg.instruction_results(instruction_id)[0];
+
+                if value_bits <= bit_size {
+                    dfg.set_value_from_id(result, value);
+                    return false;
+                }
+
+                let is_widened = self.widened.contains(&dfg.resolve(value));
+                if value_bits < max_bit_size || (is_widened && value_bits > max_bit_size) {
+                    let max_bit_size = value_bits;
+                    dfg[instruction_id] = Instruction::Truncate { value, bit_size, max_bit_size };
+                }
+                self.max_bits.insert(result, bit_size);
+                self.truncated.insert(result, (dfg.resolve(value), bit_size));
+                true
+            }
+            Instruction::Binary(binary) => {
+                let result = dfg.instruction_results(instruction_id)[0];
+                let binary = self.delay_truncations(dfg, instruction_id, binary);
+                let bits = self.binary_max_bits(dfg, &binary);
+                self.max_bits.insert(result, bits);
+                true
+            }
+            Instruction::Cast(value, typ) => {
+                let result = dfg.instruction_results(instruction_id)[0];
+                let value_bits = self.max_bits(dfg, value);
+                let bits = match (dfg.type_of_value(value), typ) {
+                    // Casts to a smaller integer type truncate the value
+                    (Type::Numeric(source), Type::Numeric(target)) => {
+                        match (numeric_bit_size(source), target) {
+                            (source_bits, NumericType::Signed { bit_size })
+                            | (source_bits, NumericType::Unsigned { bit_size })
+                                if source_bits > bit_size =>
+                            {
+                                value_bits.min(bit_size)
+                            }
+                            _ => value_bits,
+                        }
+                    }
+                    _ => value_bits,
+                };
+                self.max_bits.insert(result, bits);
+
+                // Casts which do not narrow a truncated value keep it truncated to the same bit size
+                if let Some(&(untruncated, truncated_bits)) =
+                    self.truncated.get(&dfg.resolve(value))
+                {
+                    let preserves_truncation = match dfg.type_of_value(result) {
+                        Type::Numeric(NumericType::NativeField) => true,
+                        Type::Numeric(
+                            NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size },
+                        ) => bit_size >= truncated_bits,
+                        _ => false,
+                    };
+                    if preserves_truncation {
+                        self.truncated.insert(result, (untruncated, truncated_bits));
+                    }
+                }
+                true
+            }
+            Instruction::Call { .. } => {
+                for result in dfg.instruction_results(instruction_id) {
+                    self.mark_unbounded(dfg, *result);
+                }
+                true
+            }
+            Instruction::ArrayGet { array, .. } => {
+                if self.is_unbounded_array(dfg, array) {
+                    let result = dfg.instruction_results(instruction_id)[0];
+                    self.mark_unbounded(dfg, result);
+                }
+                true
+            }
+            Instruction::ArraySet { array, value, .. } => {
+                if self.is_unbounded_array(dfg, array) || !self.fits_type(dfg, value) {
+                    let result = dfg.instruction_results(instruction_id)[0];
+                    self.unbounded_arrays.insert(result);
+                }
+                true
+            }
+            _ => true,
+        }
+    }
+
+    /// Records that nothing is known about the bounds of the given value, or of the elements of
+    /// the given array.
+    fn mark_unbounded(&mut self, dfg: &DataFlowGraph, value: ValueId) {
+        match dfg.type_of_value(value) {
+            Type::Numeric(_) => {
+                self.max_bits.insert(value, FieldElement::max_num_bits());
+            }
+            Type::Array(..) | Type::Slice(_) => {
+                self.unbounded_arrays.insert(value);
+            }
+            Type::Reference | Type::Function => (),
+        }
+    }
+
+    /// Returns true if the given array may hold an element which doesn't fit in its type.
+    fn is_unbounded_array(&self, dfg: &DataFlowGraph, array: ValueId) -> bool {
+        let array = dfg.resolve(array);
+        if self.unbounded_arrays.contains(&array) {
+            return true;
+        }
+        match dfg.get_array_constant(array) {
+            Some((elements, _)) => elements.iter().any(|element| !self.fits_type(dfg, *element)),
+            None => false,
+        }
+    }
+
+    /// Returns true if the given value is known to fit in the bit size of its type.
+    fn fits_type(&self, dfg: &DataFlowGraph, value: ValueId) -> bool {
+        match dfg.type_of_value(value) {
+            Type::Numeric(numeric_type) => {
+                self.max_bits(dfg, value) <= numeric_bit_size(numeric_type)
+            }
+            _ => !self.is_unbounded_array(dfg, value),
+        }
+    }
+
+    /// Replaces the truncated operands of an addition or multiplication with their untruncated
+    /// values if its result is only ever truncated to at most as many bits, so that the truncation
+    /// of the operands is delayed until the truncation of the result.
+    ///
+    /// Returns the binary instruction after any replacement.
+    fn delay_truncations(
+        &mut self,
+        dfg: &mut DataFlowGraph,
+        instruction_id: InstructionId,
+        binary: Binary,
+    ) -> Binary {
+        if !matches!(binary.operator, BinaryOp::Add | BinaryOp::Mul) {
+            return binary;
+        }
+        let result = dfg.instruction_results(instruction_id)[0];
+        let Some(result_truncation) = self.uses.max_truncation(dfg, result) else {
+            return binary;
+        };
+
+        let untruncate = |operand: ValueId| match self.truncated.get(&dfg.resolve(operand)) {
+            Some((untruncated, bit_size))
+                if *bit_size >= result_truncation
+                    && dfg.type_of_value(*untruncated) == dfg.type_of_value(operand) =>
+            {
+                *untruncated
+            }
+            _ => operand,
+        };
+        let delayed = Binary { lhs: untruncate(binary.lhs), rhs: untruncate(binary.rhs), ..binary };
+
+        let unchanged = delayed.lhs == binary.lhs && delayed.rhs == binary.rhs;
+        if unchanged || self.binary_max_bits(dfg, &delayed) >= FieldElement::max_num_bits() {
+            return binary;
+        }
+
+        dfg[instruction_id] = Instruction::Binary(delayed.clone());
+        self.widened.insert(result);
+        delayed
+    }
+
+    /// Returns the maximum number of bits needed to represent the given value.
+    fn max_bits(&self, dfg: &DataFlowGraph, value: ValueId) -> u32 {
+        let value = dfg.resolve(value);
+
+        if let Some(constant) = dfg.get_numeric_constant(value) {
+            return constant.num_bits();
+        }
+        if let Some(bits) = self.max_bits.get(&value) {
+            return *bits;
+        }
+
+        match dfg.type_of_value(value) {
+            Type::Numeric(numeric_type) => numeric_bit_size(numeric_type),
+            _ => FieldElement::max_num_bits(),
+        }
+    }
+
+    fn binary_max_bits(&self, dfg: &DataFlowGraph, binary: &Binary) -> u32 {
+        let field_bits = FieldElement::max_num_bits();
+        let lhs = self.max_bits(dfg, binary.lhs);
+        let rhs = self.max_bits(dfg, binary.rhs);
+        let is_unsigned = dfg.type_of_value(binary.lhs).is_unsigned();
+
+        let bits = match binary.operator {
+            BinaryOp::Add => lhs.max(rhs) + 1,
+            // Multiplying by a boolean, e.g. when applying a predicate, never increases the bound
+            BinaryOp::Mul if lhs.min(rhs) <= 1 => lhs.max(rhs),
+            BinaryOp::Mul => lhs + rhs,
+            BinaryOp::Div if is_unsigned => lhs,
+            BinaryOp::Mod if is_unsigned => lhs.min(rhs),
+            BinaryOp::And => lhs.min(rhs),
+            BinaryOp::Or | BinaryOp::Xor => lhs.max(rhs),
+            BinaryOp::Eq | BinaryOp::Lt => 1,
+            BinaryOp::Sub | BinaryOp::Div | BinaryOp::Mod => field_bits,
+        };
+
+        // The result may have wrapped around the field modulus, so nothing is known about it
+        if bits >= field_bits {
+            field_bits
+        } else {
+            bits
+        }
+    }
+}
+
+/// The instructions using each value of a function.
+#[derive(Default)]
+struct Uses {
+    instructions: HashMap<ValueId, Vec<InstructionId>>,
+
+    /// Values used by a block terminator, e.g. those which are returned
+    terminators: HashSet<ValueId>,
+}
+
+impl Uses {
+    fn new(function: &Function) -> Self {
+        let dfg = &function.dfg;
+        let mut uses = Uses::default();
+
+        for block in function.reachable_blocks() {
+            for instruction_id in dfg[block].instructions() {
+                dfg[*instruction_id].for_each_value(|value| {
+                    uses.instructions.entry(dfg.resolve(value)).or_default().push(*instruction_id);
+                });
+            }
+            dfg[block].unwrap_terminator().for_each_value(|value| {
+                uses.terminators.insert(dfg.resolve(value));
+            });
+        }
+        uses
+    }
+
+    /// Returns the largest bit size `value` is truncated to if it is only ever used by truncations.
+    fn max_truncation(&self, dfg: &DataFlowGraph, value: ValueId) -> Option<u32> {
+        if self.terminators.contains(&value) {
+            return None;
+        }
+        let uses = self.instructions.get(&value)?;
+
+        uses.iter().try_fold(0, |max_bit_size, instruction_id| match &dfg[*instruction_id] {
+            Instruction::Truncate { bit_size, .. } => Some(max_bit_size.max(*bit_size)),
+            _ => None,
+        })
+    }
+}
+
+/// The number of bits needed to represent any value of the given numeric type.
+fn numeric_bit_size(typ: NumericType) -> u32 {
+    match typ {
+        NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => bit_size,
+        NumericType::NativeField => FieldElement::max_num_bits(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{
+            function::RuntimeType,
+            instruction::{BinaryOp, Instruction},
+            map::Id,
+            types::Type,
+        },
+    };
+
+    #[test]
+    fn removes_redundant_truncations() {
+        // fn main f0 {
+        //   b0(v0: u8, v1: u8):
+        //     v2 = mul v0, v1
+        //     v3 = truncate v2 to 16 bits, max_bit_size: 254
+        //     v4 = add v0, v1
+        //     v5 = truncate v4 to 8 bits, max_bit_size: 254
+        //     range_check v3 to 16 bits
+        //     return v3, v5
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let v0 = builder.add_parameter(Type::unsigned(8));
+        let v1 = builder.add_parameter(Type::unsigned(8));
+
+        let v2 = builder.insert_binary(v0, BinaryOp::Mul, v1);
+        let v3 = builder.insert_truncate(v2, 16, 254);
+        let v4 = builder.insert_binary(v0, BinaryOp::Add, v1);
+        let v5 = builder.insert_truncate(v4, 8, 254);
+        let range_check =
+            Instruction::RangeCheck { value: v3, max_bit_size: 16, assert_message: None };
+        builder.insert_instruction(range_check, None);
+        builder.terminate_with_return(vec![v3, v5]);
+
+        let ssa = builder.finish();
+        assert_eq!(ssa.main().dfg[ssa.main().entry_block()].instructions().len(), 5);
+
+        // Expected output:
+        //
+        // fn main f0 {
+        //   b0(v0: u8, v1: u8):
+        //     v2 = mul v0, v1
+        //     v4 = add v0, v1
+        //     v5 = truncate v4 to 8 bits, max_bit_size: 9
+        //     return v2, v5
+        // }
+        let ssa = ssa.remove_redundant_range_constraints();
+        let main = ssa.main();
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert_eq!(instructions.len(), 3);
+
+        match &main.dfg[instructions[2]] {
+            Instruction::Truncate { bit_size, max_bit_size, .. } => {
+                assert_eq!(*bit_size, 8);
+                assert_eq!(*max_bit_size, 9);
+            }
+            other => panic!("Expected a truncate instruction, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn delays_truncations_of_wrapping_additions() {
+        // fn main f0 {
+        //   b0(v0: u8, v1: u8, v2: u8):
+        //     v3 = cast v0 as Field
+        //     v4 = cast v1 as Field
+        //     v5 = add v3, v4
+        //     v6 = truncate v5 to 8 bits, max_bit_size: 9
+        //     v7 = cast v6 as u8
+        //     v8 = cast v7 as Field
+        //     v9 = cast v2 as Field
+        //     v10 = add v8, v9
+        //     v11 = truncate v10 to 8 bits, max_bit_size: 9
+        //     return v11
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let v0 = builder.add_parameter(Type::unsigned(8));
+        let v1 = builder.add_parameter(Type::unsigned(8));
+        let v2 = builder.add_parameter(Type::unsigned(8));
+
+        let v3 = builder.insert_cast(v0, Type::field());
+        let v4 = builder.insert_cast(v1, Type::field());
+        let v5 = builder.insert_binary(v3, BinaryOp::Add, v4);
+        let v6 = builder.insert_truncate(v5, 8, 9);
+        let v7 = builder.insert_cast(v6, Type::unsigned(8));
+        let v8 = builder.insert_cast(v7, Type::field());
+        let v9 = builder.insert_cast(v2, Type::field());
+        let v10 = builder.insert_binary(v8, BinaryOp::Add, v9);
+        let v11 = builder.insert_truncate(v10, 8, 9);
+        builder.terminate_with_return(vec![v11]);
+
+        // Expected output:
+        //
+        // fn main f0 {
+        //   b0(v0: u8, v1: u8, v2: u8):
+        //     ...
+        //     v10 = add v5, v9
+        //     v11 = truncate v10 to 8 bits, max_bit_size: 10
+        //     return v11
+        // }
+        let ssa = builder.finish().remove_redundant_range_constraints();
+        let main = ssa.main();
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert_eq!(instructions.len(), 9);
+
+        match &main.dfg[instructions[7]] {
+            Instruction::Binary(binary) => assert_eq!(binary.lhs, v5),
+            other => panic!("Expected a binary instruction, found {other:?}"),
+        }
+        match &main.dfg[instructions[8]] {
+            Instruction::Truncate { bit_size, max_bit_size, .. } => {
+                assert_eq!(*bit_size, 8);
+                assert_eq!(*max_bit_size, 10);
+            }
+            other => panic!("Expected a truncate instruction, found {other:?}"),
+        }
+    }
+    #[test]
+    fn keeps_range_checks_of_brillig_results() {
+        // fn main f0 {
+        //   b0(v0: u1):
+        //     v2 = call f1()
+        //     v3 = cast v0 as u8
+        //     v4 = mul v2, v3
+        //     range_check v4 to 8 bits
+        //     return v4
+        // }
+        // brillig fn hint f1 {
+        //   b0():
+        //     return u8 3
+        // }
+        let main_id = Id::test_new(0);
+        let hint_id = Id::test_new(1);
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let v0 = builder.add_parameter(Type::bool());
+        let hint = builder.import_function(hint_id);
+        let v2 = builder.insert_call(hint, vec![], vec![Type::unsigned(8)])[0];
+        let v3 = builder.insert_cast(v0, Type::unsigned(8));
+        let v4 = builder.insert_binary(v2, BinaryOp::Mul, v3);
+        let range_check =
+            Instruction::RangeCheck { value: v4, max_bit_size: 8, assert_message: None };
+        builder.insert_instruction(range_check, None);
+        builder.terminate_with_return(vec![v4]);
+
+        builder.new_brillig_function("hint".into(), hint_id);
+        let three = builder.numeric_constant(3u128, Type::unsigned(8));
+        builder.terminate_with_return(vec![three]);
+
+        // The output of the Brillig call is not range constrained, so the range check must remain
+        let ssa = builder.finish().remove_redundant_range_constraints();
+        let main = ssa.main();
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert_eq!(instructions.len(), 4);
+        assert!(matches!(main.dfg[instructions[3]], Instruction::RangeCheck { .. }));
+    }
+}
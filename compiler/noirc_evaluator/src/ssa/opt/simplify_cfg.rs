@@ -30,9 +30,7 @@ impl Ssa {
     ///
     /// Currently, 1 and 4 are unimplemented.
     pub(crate) fn simplify_cfg(mut self) -> Self {
-        for function in self.functions.values_mut() {
-            simplify_function(function);
-        }
+        self.for_each_function(simplify_function);
         self
     }
 }
@@ -0,0 +1,119 @@
+//! This module defines a pass which removes any function which can no longer be reached from
+//! `main`. Calls to functions are commonly removed by constant folding, inlining, or by dead
+//! instruction elimination, and any function left behind would otherwise still be compiled
+//! to Brillig and kept around for the rest of the pipeline.
+use std::collections::BTreeSet;
+
+use crate::ssa::{
+    ir::{
+        dfg::DataFlowGraph,
+        function::{Function, FunctionId},
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+impl Ssa {
+    /// Removes any function which is not referenced, directly or indirectly, by `main`.
+    ///
+    /// A function is considered referenced if it appears as a value anywhere within the
+    /// reachable blocks of another referenced function, not only as the target of a call.
+    pub(crate) fn remove_unreachable_functions(mut self) -> Ssa {
+        let mut reachable = BTreeSet::new();
+        let mut to_visit = vec![self.main_id];
+
+        while let Some(function_id) = to_visit.pop() {
+            if reachable.insert(function_id) {
+                let function = &self.functions[&function_id];
+                to_visit.extend(used_functions(function));
+            }
+        }
+
+        self.functions.retain(|id, _| reachable.contains(id));
+        self
+    }
+}
+
+/// Collects every function referenced by the reachable blocks of the given function.
+fn used_functions(function: &Function) -> BTreeSet<FunctionId> {
+    let mut used = BTreeSet::new();
+
+    for block in function.reachable_blocks() {
+        for instruction in function.dfg[block].instructions() {
+            function.dfg[*instruction].for_each_value(|value| {
+                find_functions_in_value(&function.dfg, value, &mut used);
+            });
+        }
+
+        function.dfg[block].unwrap_terminator().for_each_value(|value| {
+            find_functions_in_value(&function.dfg, value, &mut used);
+        });
+    }
+
+    used
+}
+
+/// Inspects a value recursively (as it could be an array) for any function references.
+fn find_functions_in_value(dfg: &DataFlowGraph, value: ValueId, used: &mut BTreeSet<FunctionId>) {
+    match &dfg[dfg.resolve(value)] {
+        Value::Function(id) => {
+            used.insert(*id);
+        }
+        Value::Array { array, .. } => {
+            for element in array {
+                find_functions_in_value(dfg, *element, used);
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{function::RuntimeType, map::Id, types::Type},
+    };
+
+    #[test]
+    fn removes_uncalled_functions() {
+        // acir fn main f0 {
+        //   b0(v0: Field):
+        //     v2 = call f1(v0)
+        //     return v2
+        // }
+        // brillig fn used f1 {
+        //   b0(v0: Field):
+        //     return v0
+        // }
+        // brillig fn unused f2 {
+        //   b0(v0: Field):
+        //     return v0
+        // }
+        let main_id = Id::test_new(0);
+        let used_id = Id::test_new(1);
+        let unused_id = Id::test_new(2);
+
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let v0 = builder.add_parameter(Type::field());
+        let used = builder.import_function(used_id);
+        let results = builder.insert_call(used, vec![v0], vec![Type::field()]).to_vec();
+        builder.terminate_with_return(results);
+
+        builder.new_brillig_function("used".into(), used_id);
+        let v0 = builder.add_parameter(Type::field());
+        builder.terminate_with_return(vec![v0]);
+
+        builder.new_brillig_function("unused".into(), unused_id);
+        let v0 = builder.add_parameter(Type::field());
+        builder.terminate_with_return(vec![v0]);
+
+        let ssa = builder.finish();
+        assert_eq!(ssa.functions.len(), 3);
+
+        let ssa = ssa.remove_unreachable_functions();
+        assert_eq!(ssa.functions.len(), 2);
+        assert!(ssa.functions.contains_key(&main_id));
+        assert!(ssa.functions.contains_key(&used_id));
+    }
+}
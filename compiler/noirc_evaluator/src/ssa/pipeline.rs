@@ -0,0 +1,307 @@
+//! The SSA optimization pipeline is the list of passes run after inlining, in order.
+//!
+//! The default pipeline may be replaced with `--passes` or the `[optimizer]` table of a package's
+//! Nargo.toml, either by listing every pass to run or by disabling passes of the default pipeline
+//! with a `-` prefix, e.g. `-constant_folding,-range_analysis`. Passes which later stages of
+//! compilation rely on cannot be disabled.
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::errors::RuntimeError;
+
+use super::ssa_gen::Ssa;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SsaPass {
+    Mem2Reg,
+    AssertConstant,
+    LoopInvariantCodeMotion,
+    Unrolling,
+    SimplifyCfg,
+    StaticAssert,
+    Flattening,
+    ConstantFolding,
+    CommonSubexpressionElimination,
+    RangeAnalysis,
+    DeadInstructionElimination,
+    RemoveUnreachableFunctions,
+}
+
+/// The pipeline run when none is given
+const DEFAULT_PASSES: [SsaPass; 14] = [
+    // Run mem2reg with the CFG separated into blocks
+    SsaPass::Mem2Reg,
+    SsaPass::AssertConstant,
+    SsaPass::LoopInvariantCodeMotion,
+    SsaPass::Unrolling,
+    SsaPass::SimplifyCfg,
+    SsaPass::StaticAssert,
+    // Run mem2reg before flattening to handle any promotion
+    // of values that can be accessed after loop unrolling.
+    // If there are slice mergers uncovered by loop unrolling
+    // and this pass is missed, slice merging will fail inside of flattening.
+    SsaPass::Mem2Reg,
+    SsaPass::Flattening,
+    // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
+    SsaPass::Mem2Reg,
+    SsaPass::ConstantFolding,
+    SsaPass::CommonSubexpressionElimination,
+    SsaPass::RangeAnalysis,
+    SsaPass::DeadInstructionElimination,
+    SsaPass::RemoveUnreachableFunctions,
+];
+
+/// Pairs of required passes where the first must run before the second
+const REQUIRED_ORDER: [(SsaPass, SsaPass); 3] = [
+    (SsaPass::AssertConstant, SsaPass::Unrolling),
+    (SsaPass::Unrolling, SsaPass::StaticAssert),
+    (SsaPass::Unrolling, SsaPass::Flattening),
+];
+
+impl SsaPass {
+    pub const ALL: [SsaPass; 12] = [
+        SsaPass::Mem2Reg,
+        SsaPass::AssertConstant,
+        SsaPass::LoopInvariantCodeMotion,
+        SsaPass::Unrolling,
+        SsaPass::SimplifyCfg,
+        SsaPass::StaticAssert,
+        SsaPass::Flattening,
+        SsaPass::ConstantFolding,
+        SsaPass::CommonSubexpressionElimination,
+        SsaPass::RangeAnalysis,
+        SsaPass::DeadInstructionElimination,
+        SsaPass::RemoveUnreachableFunctions,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SsaPass::Mem2Reg => "mem2reg",
+            SsaPass::AssertConstant => "assert_constant",
+            SsaPass::LoopInvariantCodeMotion => "loop_invariant_code_motion",
+            SsaPass::Unrolling => "unrolling",
+            SsaPass::SimplifyCfg => "simplify_cfg",
+            SsaPass::StaticAssert => "static_assert",
+            SsaPass::Flattening => "flattening",
+            SsaPass::ConstantFolding => "constant_folding",
+            SsaPass::CommonSubexpressionElimination => "common_subexpression_elimination",
+            SsaPass::RangeAnalysis => "range_analysis",
+            SsaPass::DeadInstructionElimination => "dead_instruction_elimination",
+            SsaPass::RemoveUnreachableFunctions => "remove_unreachable_functions",
+        }
+    }
+
+    pub fn lookup(name: &str) -> Option<SsaPass> {
+        SsaPass::ALL.into_iter().find(|pass| pass.name() == name)
+    }
+
+    /// Passes which ACIR generation relies on, or which report errors in the program
+    pub fn is_required(self) -> bool {
+        matches!(
+            self,
+            SsaPass::Mem2Reg
+                | SsaPass::AssertConstant
+                | SsaPass::Unrolling
+                | SsaPass::StaticAssert
+                | SsaPass::Flattening
+        )
+    }
+
+    /// The message the SSA is printed with after this pass
+    pub(crate) fn message(self) -> &'static str {
+        match self {
+            SsaPass::Mem2Reg => "After Mem2Reg:",
+            SsaPass::AssertConstant => "After Assert Constant:",
+            SsaPass::LoopInvariantCodeMotion => "After Loop Invariant Code Motion:",
+            SsaPass::Unrolling => "After Unrolling:",
+            SsaPass::SimplifyCfg => "After Simplifying:",
+            SsaPass::StaticAssert => "After Static Assert:",
+            SsaPass::Flattening => "After Flattening:",
+            SsaPass::ConstantFolding => "After Constant Folding:",
+            SsaPass::CommonSubexpressionElimination => "After Common Subexpression Elimination:",
+            SsaPass::RangeAnalysis => "After Range Analysis:",
+            SsaPass::DeadInstructionElimination => "After Dead Instruction Elimination:",
+            SsaPass::RemoveUnreachableFunctions => "After Removing Unreachable Functions:",
+        }
+    }
+
+    pub(crate) fn run(self, ssa: Ssa) -> Result<Ssa, RuntimeError> {
+        match self {
+            SsaPass::Mem2Reg => Ok(ssa.mem2reg()),
+            SsaPass::AssertConstant => ssa.evaluate_assert_constant(),
+            SsaPass::LoopInvariantCodeMotion => Ok(ssa.loop_invariant_code_motion()),
+            SsaPass::Unrolling => ssa.unroll_loops(),
+            SsaPass::SimplifyCfg => Ok(ssa.simplify_cfg()),
+            SsaPass::StaticAssert => ssa.evaluate_static_assert(),
+            SsaPass::Flattening => Ok(ssa.flatten_cfg()),
+            SsaPass::ConstantFolding => Ok(ssa.fold_constants()),
+            SsaPass::CommonSubexpressionElimination => Ok(ssa.eliminate_common_subexpressions()),
+            SsaPass::RangeAnalysis => Ok(ssa.remove_redundant_range_constraints()),
+            SsaPass::DeadInstructionElimination => Ok(ssa.dead_instruction_elimination()),
+            SsaPass::RemoveUnreachableFunctions => Ok(ssa.remove_unreachable_functions()),
+        }
+    }
+}
+
+impl Display for SsaPass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum SsaPipelineError {
+    #[error("Unknown SSA pass `{0}`")]
+    UnknownPass(String),
+    #[error("The `{0}` pass is required and cannot be disabled")]
+    MissingRequiredPass(SsaPass),
+    #[error("The `{later}` pass must run after the `{earlier}` pass")]
+    OutOfOrder { earlier: SsaPass, later: SsaPass },
+    #[error("Passes must either all be listed or all be disabled with a `-` prefix")]
+    MixedDisabledPasses,
+}
+
+/// An ordered list of SSA passes, which may contain the same pass more than once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct SsaPipeline {
+    passes: Vec<SsaPass>,
+}
+
+impl SsaPipeline {
+    /// Creates a pipeline from a list of pass names. If every name has a `-` prefix, the pipeline
+    /// is instead the default one without those passes.
+    pub fn from_names(names: &[impl AsRef<str>]) -> Result<SsaPipeline, SsaPipelineError> {
+        let names: Vec<&str> =
+            names.iter().map(|name| name.as_ref().trim()).filter(|name| !name.is_empty()).collect();
+        let disabled: Vec<&str> = names.iter().filter_map(|name| name.strip_prefix('-')).collect();
+
+        let passes = if disabled.is_empty() {
+            names.into_iter().map(lookup_pass).collect::<Result<_, _>>()?
+        } else if disabled.len() == names.len() {
+            let disabled: Vec<SsaPass> =
+                disabled.into_iter().map(lookup_pass).collect::<Result<_, _>>()?;
+            DEFAULT_PASSES.into_iter().filter(|pass| !disabled.contains(pass)).collect()
+        } else {
+            return Err(SsaPipelineError::MixedDisabledPasses);
+        };
+
+        let pipeline = SsaPipeline { passes };
+        pipeline.validate()?;
+        Ok(pipeline)
+    }
+
+    pub fn passes(&self) -> &[SsaPass] {
+        &self.passes
+    }
+
+    /// Checks that every required pass is run, and in an order ACIR generation can handle
+    fn validate(&self) -> Result<(), SsaPipelineError> {
+        if let Some(missing) =
+            SsaPass::ALL.into_iter().find(|pass| pass.is_required() && !self.passes.contains(pass))
+        {
+            return Err(SsaPipelineError::MissingRequiredPass(missing));
+        }
+
+        let first_run = |pass| self.passes.iter().position(|other| *other == pass);
+        for (earlier, later) in REQUIRED_ORDER {
+            if first_run(later) < first_run(earlier) {
+                return Err(SsaPipelineError::OutOfOrder { earlier, later });
+            }
+        }
+
+        // Flattening may leave loads and stores behind which ACIR generation can't handle
+        let last_mem2reg = self.passes.iter().rposition(|pass| *pass == SsaPass::Mem2Reg);
+        if last_mem2reg < first_run(SsaPass::Flattening) {
+            return Err(SsaPipelineError::OutOfOrder {
+                earlier: SsaPass::Flattening,
+                later: SsaPass::Mem2Reg,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn lookup_pass(name: &str) -> Result<SsaPass, SsaPipelineError> {
+    SsaPass::lookup(name).ok_or_else(|| SsaPipelineError::UnknownPass(name.to_string()))
+}
+
+impl Default for SsaPipeline {
+    fn default() -> Self {
+        SsaPipeline { passes: DEFAULT_PASSES.to_vec() }
+    }
+}
+
+impl FromStr for SsaPipeline {
+    type Err = SsaPipelineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let names: Vec<&str> = s.split(',').collect();
+        SsaPipeline::from_names(&names)
+    }
+}
+
+impl TryFrom<Vec<String>> for SsaPipeline {
+    type Error = SsaPipelineError;
+
+    fn try_from(names: Vec<String>) -> Result<Self, Self::Error> {
+        SsaPipeline::from_names(&names)
+    }
+}
+
+impl From<SsaPipeline> for Vec<String> {
+    fn from(pipeline: SsaPipeline) -> Self {
+        pipeline.passes.into_iter().map(|pass| pass.name().to_string()).collect()
+    }
+}
+
+impl Display for SsaPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = self.passes.iter().map(|pass| pass.name()).collect();
+        write!(f, "{}", names.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SsaPass, SsaPipeline, SsaPipelineError, DEFAULT_PASSES};
+
+    #[test]
+    fn default_pipeline_is_valid() {
+        let pipeline = SsaPipeline::default();
+        assert_eq!(pipeline.to_string().parse::<SsaPipeline>(), Ok(pipeline));
+    }
+
+    #[test]
+    fn disables_passes_of_default_pipeline() {
+        let pipeline: SsaPipeline = "-constant_folding, -range_analysis".parse().unwrap();
+
+        let expected: Vec<_> = DEFAULT_PASSES
+            .into_iter()
+            .filter(|pass| !matches!(pass, SsaPass::ConstantFolding | SsaPass::RangeAnalysis))
+            .collect();
+        assert_eq!(pipeline.passes(), expected);
+    }
+
+    #[test]
+    fn rejects_invalid_pipelines() {
+        let error = |passes: &str| passes.parse::<SsaPipeline>().unwrap_err();
+
+        assert_eq!(error("-inlining"), SsaPipelineError::UnknownPass("inlining".to_string()));
+        assert_eq!(error("-unrolling"), SsaPipelineError::MissingRequiredPass(SsaPass::Unrolling));
+        assert_eq!(error("mem2reg,-flattening"), SsaPipelineError::MixedDisabledPasses);
+        assert_eq!(
+            error("assert_constant,flattening,unrolling,static_assert,mem2reg"),
+            SsaPipelineError::OutOfOrder {
+                earlier: SsaPass::Unrolling,
+                later: SsaPass::Flattening
+            }
+        );
+        assert_eq!(
+            error("mem2reg,assert_constant,unrolling,static_assert,flattening"),
+            SsaPipelineError::OutOfOrder { earlier: SsaPass::Flattening, later: SsaPass::Mem2Reg }
+        );
+    }
+}
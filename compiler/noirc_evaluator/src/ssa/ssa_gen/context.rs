@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 use std::sync::{Mutex, RwLock};
 
 use acvm::FieldElement;
@@ -10,6 +10,7 @@ use noirc_frontend::{BinaryOpKind, Signedness};
 
 use crate::errors::RuntimeError;
 use crate::ssa::function_builder::FunctionBuilder;
+use crate::ssa::ir::basic_block::BasicBlockId;
 use crate::ssa::ir::dfg::DataFlowGraph;
 use crate::ssa::ir::function::FunctionId as IrFunctionId;
 use crate::ssa::ir::function::{Function, RuntimeType};
@@ -38,6 +39,22 @@ pub(super) struct FunctionContext<'a> {
 
     pub(super) builder: FunctionBuilder,
     shared_context: &'a SharedContext,
+
+    /// The loops enclosing the expression currently being compiled, innermost last.
+    /// These are only needed for the `break` and `continue` of unconstrained code,
+    /// loops in constrained code have these lowered away during monomorphization.
+    loops: Vec<Loop>,
+}
+
+/// The blocks a `break` or `continue` within a loop should jump to.
+#[derive(Clone, Copy)]
+pub(super) struct Loop {
+    /// The block which checks whether to run another iteration of the loop
+    pub(super) loop_entry: BasicBlockId,
+    /// The induction variable of a for loop, which `continue` must increment
+    pub(super) loop_index: Option<ValueId>,
+    /// The block following the loop
+    pub(super) loop_end: BasicBlockId,
 }
 
 /// Shared context for all functions during ssa codegen. This is the only
@@ -96,7 +113,8 @@ impl<'a> FunctionContext<'a> {
             .1;
 
         let builder = FunctionBuilder::new(function_name, function_id, runtime);
-        let mut this = Self { definitions: HashMap::default(), builder, shared_context };
+        let mut this =
+            Self { definitions: HashMap::default(), builder, shared_context, loops: Vec::new() };
         this.add_parameters_to_scope(parameters);
         this
     }
@@ -113,6 +131,7 @@ impl<'a> FunctionContext<'a> {
         } else {
             self.builder.new_function(func.name.clone(), id);
         }
+        self.builder.set_inline_type(func.inline_type);
         self.add_parameters_to_scope(&func.parameters);
     }
 
@@ -192,7 +211,7 @@ impl<'a> FunctionContext<'a> {
                 let element_types = Self::convert_type(elements).flatten();
                 Tree::Branch(vec![
                     Tree::Leaf(f(Type::field())),
-                    Tree::Leaf(f(Type::Slice(Rc::new(element_types)))),
+                    Tree::Leaf(f(Type::Slice(Arc::new(element_types)))),
                 ])
             }
             other => Tree::Leaf(f(Self::convert_non_tuple_type(other))),
@@ -216,12 +235,12 @@ impl<'a> FunctionContext<'a> {
             ast::Type::Field => Type::field(),
             ast::Type::Array(len, element) => {
                 let element_types = Self::convert_type(element).flatten();
-                Type::Array(Rc::new(element_types), *len as usize)
+                Type::Array(Arc::new(element_types), *len as usize)
             }
             ast::Type::Integer(Signedness::Signed, bits) => Type::signed(*bits),
             ast::Type::Integer(Signedness::Unsigned, bits) => Type::unsigned(*bits),
             ast::Type::Bool => Type::unsigned(1),
-            ast::Type::String(len) => Type::Array(Rc::new(vec![Type::char()]), *len as usize),
+            ast::Type::String(len) => Type::Array(Arc::new(vec![Type::char()]), *len as usize),
             ast::Type::FmtString(_, _) => {
                 panic!("convert_non_tuple_type called on a fmt string: {typ}")
             }
@@ -368,6 +387,11 @@ impl<'a> FunctionContext<'a> {
                     self.check_left_shift_overflow(result, rhs, bit_size, location)
                 } else {
                     let message = format!("attempt to {} with overflow", op_name);
+                    if operator == BinaryOpKind::Multiply
+                        && 2 * bit_size >= FieldElement::max_num_bits()
+                    {
+                        self.check_wide_multiplication_overflow(lhs, rhs, bit_size, location);
+                    }
                     let range_constraint = Instruction::RangeCheck {
                         value: result,
                         max_bit_size: bit_size,
@@ -405,6 +429,37 @@ impl<'a> FunctionContext<'a> {
         self.builder.insert_truncate(result, bit_size, bit_size + 1)
     }
 
+    /// Overflow check for the multiplication of unsigned integers which are wide enough that their
+    /// product may wrap around the field modulus, in which case a range check on the result alone
+    /// would accept an overflowing product.
+    /// Splitting each operand as `high * 2^h + low` with `h = ceil(bit_size / 2)`, the product can
+    /// only exceed 2^(bit_size + h + 2), which is below the modulus, when both high halves are non-zero.
+    /// Any such product is at least 2^bit_size and so overflows anyway, hence we forbid it.
+    fn check_wide_multiplication_overflow(
+        &mut self,
+        lhs: ValueId,
+        rhs: ValueId,
+        bit_size: u32,
+        location: Location,
+    ) {
+        let half_width = self.builder.numeric_constant(
+            FieldElement::from(2_u128.pow((bit_size + 1) / 2)),
+            Type::unsigned(bit_size),
+        );
+        let lhs_high = self.builder.insert_binary(lhs, BinaryOp::Div, half_width);
+        let rhs_high = self.builder.insert_binary(rhs, BinaryOp::Div, half_width);
+        // The high halves have at most bit_size - h bits so their product cannot wrap the field
+        let lhs_high = self.builder.insert_cast(lhs_high, Type::field());
+        let rhs_high = self.builder.insert_cast(rhs_high, Type::field());
+        let high_product = self.builder.insert_binary(lhs_high, BinaryOp::Mul, rhs_high);
+        let zero = self.builder.field_constant(FieldElement::zero());
+        self.builder.set_location(location).insert_constrain(
+            high_product,
+            zero,
+            Some("attempt to multiply with overflow".to_owned()),
+        );
+    }
+
     /// Insert constraints ensuring that the operation does not overflow the bit size of the result
     /// We assume that:
     /// lhs and rhs are signed integers of bit size bit_size
@@ -514,7 +569,18 @@ impl<'a> FunctionContext<'a> {
                 };
                 self.builder.insert_wrapping_shift_left(lhs, rhs, bit_size)
             }
-            BinaryOpKind::ShiftRight => self.builder.insert_shift_right(lhs, rhs),
+            BinaryOpKind::ShiftRight => match self.builder.type_of_value(lhs) {
+                Type::Numeric(NumericType::Signed { bit_size }) => {
+                    self.insert_signed_shift_right(lhs, rhs, bit_size)
+                }
+                _ => self.builder.insert_shift_right(lhs, rhs),
+            },
+            BinaryOpKind::WrappingAdd
+            | BinaryOpKind::WrappingSubtract
+            | BinaryOpKind::WrappingMultiply => {
+                self.builder.set_location(location);
+                self.insert_wrapping_binary(lhs, operator, rhs)
+            }
             BinaryOpKind::Equal | BinaryOpKind::NotEqual
                 if matches!(self.builder.type_of_value(lhs), Type::Array(..)) =>
             {
@@ -547,6 +613,139 @@ impl<'a> FunctionContext<'a> {
         result.into()
     }
 
+    /// Insert a `+%`, `-%` or `*%` operation, which wraps around on overflow instead of failing.
+    /// The operation is performed on the integers' bits as fields, which never overflow for the
+    /// bit sizes involved, and the result is then truncated to the bit size of the operands.
+    /// Since signed integers are represented in two's complement this is correct for them too.
+    fn insert_wrapping_binary(
+        &mut self,
+        lhs: ValueId,
+        operator: BinaryOpKind,
+        rhs: ValueId,
+    ) -> ValueId {
+        let result_type = self.builder.type_of_value(lhs);
+        let bit_size = match result_type {
+            Type::Numeric(NumericType::Signed { bit_size })
+            | Type::Numeric(NumericType::Unsigned { bit_size }) => bit_size,
+            _ => {
+                let op = match operator {
+                    BinaryOpKind::WrappingAdd => BinaryOp::Add,
+                    BinaryOpKind::WrappingSubtract => BinaryOp::Sub,
+                    _ => BinaryOp::Mul,
+                };
+                return self.builder.insert_binary(lhs, op, rhs);
+            }
+        };
+
+        let lhs_field = self.builder.insert_cast(lhs, Type::field());
+        let rhs_field = self.builder.insert_cast(rhs, Type::field());
+        let (result, max_bit_size) = match operator {
+            BinaryOpKind::WrappingAdd => {
+                (self.builder.insert_binary(lhs_field, BinaryOp::Add, rhs_field), bit_size + 1)
+            }
+            BinaryOpKind::WrappingSubtract => {
+                // lhs - rhs is computed as lhs + 2^bit_size - rhs so that it does not underflow
+                let offset = self.builder.field_constant(power_of_two(bit_size));
+                let lhs_offset = self.builder.insert_binary(lhs_field, BinaryOp::Add, offset);
+                (self.builder.insert_binary(lhs_offset, BinaryOp::Sub, rhs_field), bit_size + 1)
+            }
+            _ if 2 * bit_size < FieldElement::max_num_bits() => {
+                (self.builder.insert_binary(lhs_field, BinaryOp::Mul, rhs_field), 2 * bit_size)
+            }
+            _ => self.insert_wide_wrapping_multiplication(lhs, rhs, bit_size),
+        };
+        let result = self.builder.insert_truncate(result, bit_size, max_bit_size);
+        self.builder.insert_cast(result, result_type)
+    }
+
+    /// Insert an arithmetic `>>` of a signed integer, which rounds towards negative infinity.
+    /// Flipping the sign bit of `lhs` maps it to the unsigned integer `lhs + 2^(bit_size - 1)`,
+    /// which can be shifted as usual to `floor(lhs / 2^rhs) + 2^(bit_size - 1 - rhs)`. Subtracting
+    /// the shifted sign bit then leaves the two's complement bits of the result.
+    fn insert_signed_shift_right(&mut self, lhs: ValueId, rhs: ValueId, bit_size: u32) -> ValueId {
+        let unsigned = Type::unsigned(bit_size);
+        let lhs = self.builder.insert_cast(lhs, unsigned.clone());
+        let rhs = self.builder.insert_cast(rhs, unsigned.clone());
+
+        let sign_bit = self.builder.numeric_constant(power_of_two(bit_size - 1), unsigned);
+        let offset_lhs = self.builder.insert_binary(lhs, BinaryOp::Xor, sign_bit);
+        let shifted = self.builder.insert_shift_right(offset_lhs, rhs);
+        let shifted_sign_bit = self.builder.insert_shift_right(sign_bit, rhs);
+
+        let result =
+            self.insert_wrapping_binary(shifted, BinaryOpKind::WrappingSubtract, shifted_sign_bit);
+        self.builder.insert_cast(result, Type::signed(bit_size))
+    }
+
+    /// Wrapping multiplication of integers wide enough that their full product may wrap around
+    /// the field modulus. Splitting each operand as `high * 2^h + low` with `h = ceil(bit_size / 2)`,
+    /// the `high * high` term is a multiple of 2^bit_size so it can be dropped, leaving a field
+    /// element of at most `bit_size + h + 2` bits with the same low `bit_size` bits as the product.
+    fn insert_wide_wrapping_multiplication(
+        &mut self,
+        lhs: ValueId,
+        rhs: ValueId,
+        bit_size: u32,
+    ) -> (ValueId, u32) {
+        let half_bit_size = (bit_size + 1) / 2;
+        let half_width = self.builder.field_constant(power_of_two(half_bit_size));
+        let mut split = |value: ValueId| {
+            let value = self.builder.insert_cast(value, Type::unsigned(bit_size));
+            let low = self.builder.insert_truncate(value, half_bit_size, bit_size);
+            let low = self.builder.insert_cast(low, Type::field());
+            let value = self.builder.insert_cast(value, Type::field());
+            let high = self.builder.insert_binary(value, BinaryOp::Sub, low);
+            let high = self.builder.insert_binary(high, BinaryOp::Div, half_width);
+            (high, low)
+        };
+        let (lhs_high, lhs_low) = split(lhs);
+        let (rhs_high, rhs_low) = split(rhs);
+
+        let low_product = self.builder.insert_binary(lhs_low, BinaryOp::Mul, rhs_low);
+        let lhs_cross = self.builder.insert_binary(lhs_high, BinaryOp::Mul, rhs_low);
+        let rhs_cross = self.builder.insert_binary(lhs_low, BinaryOp::Mul, rhs_high);
+        let cross = self.builder.insert_binary(lhs_cross, BinaryOp::Add, rhs_cross);
+        let cross = self.builder.insert_binary(cross, BinaryOp::Mul, half_width);
+        let result = self.builder.insert_binary(low_product, BinaryOp::Add, cross);
+        (result, bit_size + half_bit_size + 2)
+    }
+
+    /// Casts the given value to the given numeric type. When widening a signed integer its
+    /// sign is extended, so that e.g. `-1 as i64` and `-1 as u64` have all of their bits set.
+    pub(super) fn insert_cast(&mut self, value: ValueId, typ: Type) -> ValueId {
+        let (Type::Numeric(NumericType::Signed { bit_size }), Type::Numeric(target)) =
+            (self.builder.type_of_value(value), &typ)
+        else {
+            return self.builder.insert_cast(value, typ);
+        };
+
+        let target_bit_size = match target {
+            NumericType::Signed { bit_size } | NumericType::Unsigned { bit_size } => *bit_size,
+            NumericType::NativeField => return self.builder.insert_cast(value, typ),
+        };
+        if target_bit_size <= bit_size {
+            return self.builder.insert_cast(value, typ);
+        }
+
+        // value + sign * (2^target_bit_size - 2^bit_size)
+        let unsigned = self.builder.insert_cast(value, Type::unsigned(bit_size));
+        let half_width = self.builder.numeric_constant(
+            FieldElement::from(2_u128.pow(bit_size - 1)),
+            Type::unsigned(bit_size),
+        );
+        let sign = self.builder.insert_binary(unsigned, BinaryOp::Div, half_width);
+        let sign = self.builder.insert_cast(sign, typ.clone());
+
+        let extension = FieldElement::from(2_i128)
+            .pow(&FieldElement::from(target_bit_size as i128))
+            - FieldElement::from(2_i128).pow(&FieldElement::from(bit_size as i128));
+        let extension = self.builder.numeric_constant(extension, typ.clone());
+        let extension = self.builder.insert_binary(sign, BinaryOp::Mul, extension);
+
+        let value = self.builder.insert_cast(value, typ);
+        self.builder.insert_binary(value, BinaryOp::Add, extension)
+    }
+
     /// The frontend claims to support equality (==) on arrays, so we must support it in SSA here.
     /// The actual BinaryOp::Eq in SSA is meant only for primitive numeric types so we encode an
     /// entire equality loop on each array element. The generated IR is as follows:
@@ -670,6 +869,23 @@ impl<'a> FunctionContext<'a> {
         address
     }
 
+    pub(super) fn enter_loop(
+        &mut self,
+        loop_entry: BasicBlockId,
+        loop_index: Option<ValueId>,
+        loop_end: BasicBlockId,
+    ) {
+        self.loops.push(Loop { loop_entry, loop_index, loop_end });
+    }
+
+    pub(super) fn exit_loop(&mut self) {
+        self.loops.pop();
+    }
+
+    pub(super) fn current_loop(&self) -> Loop {
+        *self.loops.last().expect("ICE: break or continue used outside of a loop")
+    }
+
     /// Define a local variable to be some Values that can later be retrieved
     /// by calling self.lookup(id)
     pub(super) fn define(&mut self, id: LocalId, value: Values) {
@@ -957,6 +1173,11 @@ impl<'a> FunctionContext<'a> {
     }
 }
 
+/// Returns 2^exponent as a field element
+fn power_of_two(exponent: u32) -> FieldElement {
+    FieldElement::from(2_u128).pow(&FieldElement::from(exponent as u128))
+}
+
 /// True if the given operator cannot be encoded directly and needs
 /// to be represented as !(some other operator)
 fn operator_requires_not(op: noirc_frontend::BinaryOpKind) -> bool {
@@ -1058,6 +1279,11 @@ fn convert_operator(op: noirc_frontend::BinaryOpKind) -> BinaryOp {
         BinaryOpKind::And => BinaryOp::And,
         BinaryOpKind::Or => BinaryOp::Or,
         BinaryOpKind::Xor => BinaryOp::Xor,
+        BinaryOpKind::WrappingAdd
+        | BinaryOpKind::WrappingSubtract
+        | BinaryOpKind::WrappingMultiply => {
+            unreachable!("ICE - wrapping operators should be handled by insert_wrapping_binary")
+        }
         BinaryOpKind::ShiftRight | BinaryOpKind::ShiftLeft => unreachable!(
             "ICE - bit shift operators do not exist in SSA and should have been replaced"
         ),
@@ -98,6 +98,11 @@ impl<'a> FunctionContext<'a> {
             Expression::Index(index) => self.codegen_index(index),
             Expression::Cast(cast) => self.codegen_cast(cast),
             Expression::For(for_expr) => self.codegen_for(for_expr),
+            Expression::While(while_expr) => self.codegen_while(while_expr),
+            Expression::Loop(block) => self.codegen_loop(block),
+            Expression::Break => Ok(self.codegen_break()),
+            Expression::Continue => Ok(self.codegen_continue()),
+            Expression::Return(expr) => self.codegen_return(expr),
             Expression::If(if_expr) => self.codegen_if(if_expr),
             Expression::Tuple(tuple) => self.codegen_tuple(tuple),
             Expression::ExtractTupleField(tuple, index) => {
@@ -384,7 +389,7 @@ impl<'a> FunctionContext<'a> {
         let lhs = self.codegen_non_tuple_expression(&cast.lhs)?;
         let typ = Self::convert_non_tuple_type(&cast.r#type);
         self.builder.set_location(cast.location);
-        Ok(self.builder.insert_cast(lhs, typ).into())
+        Ok(self.insert_cast(lhs, typ).into())
     }
 
     /// Codegens a for loop, creating three new blocks in the process.
@@ -437,7 +442,9 @@ impl<'a> FunctionContext<'a> {
         // Compile the loop body
         self.builder.switch_to_block(loop_body);
         self.define(for_expr.index_variable, loop_index.into());
+        self.enter_loop(loop_entry, Some(loop_index), loop_end);
         self.codegen_expression(&for_expr.block)?;
+        self.exit_loop();
         let new_loop_index = self.make_offset(loop_index, 1);
         self.builder.terminate_with_jmp(loop_entry, vec![new_loop_index]);
 
@@ -446,6 +453,102 @@ impl<'a> FunctionContext<'a> {
         Ok(Self::unit_value())
     }
 
+    /// Codegens a while loop. These only reach SSA in unconstrained code which is never
+    /// unrolled, so the loop is kept as is:
+    ///
+    ///   br loop_entry()
+    /// loop_entry():
+    ///   v0 = ... codegen condition ...
+    ///   brif v0, then: loop_body, else: loop_end
+    /// loop_body():
+    ///   ... codegen body ...
+    ///   br loop_entry()
+    /// loop_end():
+    ///   ... This is the current insert point after codegen_while finishes ...
+    fn codegen_while(&mut self, while_expr: &ast::While) -> Result<Values, RuntimeError> {
+        let loop_entry = self.builder.insert_block();
+        let loop_body = self.builder.insert_block();
+        let loop_end = self.builder.insert_block();
+
+        self.builder.terminate_with_jmp(loop_entry, vec![]);
+
+        self.builder.switch_to_block(loop_entry);
+        let condition = self.codegen_non_tuple_expression(&while_expr.condition)?;
+        self.builder.terminate_with_jmpif(condition, loop_body, loop_end);
+
+        self.builder.switch_to_block(loop_body);
+        self.enter_loop(loop_entry, None, loop_end);
+        self.codegen_expression(&while_expr.body)?;
+        self.exit_loop();
+        self.builder.terminate_with_jmp(loop_entry, vec![]);
+
+        self.builder.switch_to_block(loop_end);
+        Ok(Self::unit_value())
+    }
+
+    /// Codegens an unconstrained `loop`, which can only be exited through a `break`:
+    ///
+    ///   br loop_body()
+    /// loop_body():
+    ///   ... codegen body ...
+    ///   br loop_body()
+    /// loop_end():
+    ///   ... This is the current insert point after codegen_loop finishes ...
+    fn codegen_loop(&mut self, block: &Expression) -> Result<Values, RuntimeError> {
+        let loop_body = self.builder.insert_block();
+        let loop_end = self.builder.insert_block();
+
+        self.builder.terminate_with_jmp(loop_body, vec![]);
+
+        self.builder.switch_to_block(loop_body);
+        self.enter_loop(loop_body, None, loop_end);
+        self.codegen_expression(block)?;
+        self.exit_loop();
+        self.builder.terminate_with_jmp(loop_body, vec![]);
+
+        self.builder.switch_to_block(loop_end);
+        Ok(Self::unit_value())
+    }
+
+    /// Codegens a `break` as a jump to the end of the innermost loop. Any code following
+    /// the `break` is unreachable so it is placed in a fresh block with no predecessors.
+    fn codegen_break(&mut self) -> Values {
+        let loop_end = self.current_loop().loop_end;
+        self.builder.terminate_with_jmp(loop_end, vec![]);
+
+        let unreachable_block = self.builder.insert_block();
+        self.builder.switch_to_block(unreachable_block);
+        Self::unit_value()
+    }
+
+    /// Codegens a `continue` as a jump back to the entry of the innermost loop,
+    /// incrementing the loop index first if it is a for loop.
+    fn codegen_continue(&mut self) -> Values {
+        let loop_ = self.current_loop();
+        let arguments = match loop_.loop_index {
+            Some(loop_index) => vec![self.make_offset(loop_index, 1)],
+            None => vec![],
+        };
+        self.builder.terminate_with_jmp(loop_.loop_entry, arguments);
+
+        let unreachable_block = self.builder.insert_block();
+        self.builder.switch_to_block(unreachable_block);
+        Self::unit_value()
+    }
+
+    /// Codegens a `return` by terminating the current block with the returned value.
+    /// These only remain in unconstrained code, constrained functions have theirs
+    /// lowered during monomorphization.
+    fn codegen_return(&mut self, expr: &Expression) -> Result<Values, RuntimeError> {
+        let return_value = self.codegen_expression(expr)?;
+        let results = return_value.into_value_list(self);
+        self.builder.terminate_with_return(results);
+
+        let unreachable_block = self.builder.insert_block();
+        self.builder.switch_to_block(unreachable_block);
+        Ok(Self::unit_value())
+    }
+
     /// Codegens an if expression, handling the case of what to do if there is no 'else'.
     ///
     /// For example, the expression `if cond { a } else { b }` is codegen'd as:
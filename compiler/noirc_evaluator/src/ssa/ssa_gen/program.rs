@@ -1,10 +1,14 @@
 use std::{collections::BTreeMap, fmt::Display};
 
 use iter_extended::btree_map;
+use rayon::prelude::*;
 
-use crate::ssa::ir::{
-    function::{Function, FunctionId},
-    map::AtomicCounter,
+use crate::{
+    errors::RuntimeError,
+    ssa::ir::{
+        function::{Function, FunctionId},
+        map::AtomicCounter,
+    },
 };
 
 /// Contains the entire SSA representation of the program.
@@ -39,6 +43,23 @@ impl Ssa {
         self.functions.get_mut(&self.main_id).expect("ICE: Ssa should have a main function")
     }
 
+    /// Runs `pass` on each function of the program. Functions are independent of each other
+    /// at this level so they are processed in parallel.
+    pub(crate) fn for_each_function(&mut self, pass: impl Fn(&mut Function) + Sync + Send) {
+        self.functions.par_iter_mut().for_each(|(_, function)| pass(function));
+    }
+
+    /// The same as `for_each_function` but for passes that may fail. If several functions fail,
+    /// the error from the one with the lowest id is returned to keep error reporting deterministic.
+    pub(crate) fn try_for_each_function(
+        &mut self,
+        pass: impl Fn(&mut Function) -> Result<(), RuntimeError> + Sync + Send,
+    ) -> Result<(), RuntimeError> {
+        let results: Vec<_> =
+            self.functions.par_iter_mut().map(|(_, function)| pass(function)).collect();
+        results.into_iter().collect()
+    }
+
     /// Adds a new function to the program
     pub(crate) fn add_fn(
         &mut self,
@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+use crate::{token::SecondaryAttribute, Ident, UnresolvedGenerics, UnresolvedType};
+use iter_extended::vecmap;
+use noirc_errors::Span;
+
+/// Ast node for an enum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoirEnum {
+    pub name: Ident,
+    pub attributes: Vec<SecondaryAttribute>,
+    pub generics: UnresolvedGenerics,
+    /// Each variant along with the types of its fields, e.g. `Circle(Field)`
+    pub variants: Vec<(Ident, Vec<UnresolvedType>)>,
+    pub span: Span,
+}
+
+impl Display for NoirEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let generics = vecmap(&self.generics, |generic| generic.to_string());
+        let generics = if generics.is_empty() { "".into() } else { generics.join(", ") };
+
+        writeln!(f, "enum {}{} {{", self.name, generics)?;
+
+        for (name, fields) in self.variants.iter() {
+            if fields.is_empty() {
+                writeln!(f, "    {name},")?;
+            } else {
+                let fields = vecmap(fields, ToString::to_string);
+                writeln!(f, "    {name}({}),", fields.join(", "))?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
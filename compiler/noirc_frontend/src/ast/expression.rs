@@ -21,12 +21,18 @@ pub enum ExpressionKind {
     Constructor(Box<ConstructorExpression>),
     MemberAccess(Box<MemberAccessExpression>),
     Cast(Box<CastExpression>),
+    Try(Box<Expression>),
     Infix(Box<InfixExpression>),
     If(Box<IfExpression>),
+    Match(Box<MatchExpression>),
     Variable(Path),
     Tuple(Vec<Expression>),
     Lambda(Box<Lambda>),
     Parenthesized(Box<Expression>),
+    /// `comptime { ... }`, evaluated during compilation
+    Comptime(BlockExpression),
+    /// `unconstrained { ... }`, executed in Brillig to compute hints for the surrounding code
+    Unconstrained(BlockExpression),
     Error,
 }
 
@@ -80,8 +86,10 @@ impl ExpressionKind {
         ExpressionKind::Literal(Literal::FmtStr(contents))
     }
 
-    pub fn constructor((type_name, fields): (Path, Vec<(Ident, Expression)>)) -> ExpressionKind {
-        ExpressionKind::Constructor(Box::new(ConstructorExpression { type_name, fields }))
+    pub fn constructor(
+        ((type_name, fields), base): ((Path, Vec<(Ident, Expression)>), Option<Expression>),
+    ) -> ExpressionKind {
+        ExpressionKind::Constructor(Box::new(ConstructorExpression { type_name, fields, base }))
     }
 
     /// Returns true if the expression is a literal integer
@@ -165,6 +173,10 @@ impl Expression {
         Expression::new(kind, span)
     }
 
+    pub fn try_expr(expr: Expression, span: Span) -> Expression {
+        Expression::new(ExpressionKind::Try(Box::new(expr)), span)
+    }
+
     pub fn call(lhs: Expression, arguments: Vec<Expression>, span: Span) -> Expression {
         // Need to check if lhs is an if expression since users can sequence if expressions
         // with tuples without calling them. E.g. `if c { t } else { e }(a, b)` is interpreted
@@ -207,6 +219,12 @@ pub enum BinaryOpKind {
     ShiftRight,
     ShiftLeft,
     Modulo,
+    /// `+%`: addition which wraps around on overflow rather than failing
+    WrappingAdd,
+    /// `-%`: subtraction which wraps around on underflow rather than failing
+    WrappingSubtract,
+    /// `*%`: multiplication which wraps around on overflow rather than failing
+    WrappingMultiply,
 }
 
 impl BinaryOpKind {
@@ -247,6 +265,9 @@ impl BinaryOpKind {
             BinaryOpKind::ShiftRight => ">>",
             BinaryOpKind::ShiftLeft => "<<",
             BinaryOpKind::Modulo => "%",
+            BinaryOpKind::WrappingAdd => "+%",
+            BinaryOpKind::WrappingSubtract => "-%",
+            BinaryOpKind::WrappingMultiply => "*%",
         }
     }
 
@@ -268,6 +289,28 @@ impl BinaryOpKind {
             BinaryOpKind::ShiftLeft => Token::ShiftLeft,
             BinaryOpKind::ShiftRight => Token::ShiftRight,
             BinaryOpKind::Modulo => Token::Percent,
+            BinaryOpKind::WrappingAdd => Token::WrappingPlus,
+            BinaryOpKind::WrappingSubtract => Token::WrappingMinus,
+            BinaryOpKind::WrappingMultiply => Token::WrappingStar,
+        }
+    }
+
+    pub fn is_wrapping(&self) -> bool {
+        matches!(
+            self,
+            BinaryOpKind::WrappingAdd
+                | BinaryOpKind::WrappingSubtract
+                | BinaryOpKind::WrappingMultiply
+        )
+    }
+
+    /// Returns the wrapping counterpart of this operator if it has one.
+    pub fn into_wrapping(self) -> BinaryOpKind {
+        match self {
+            BinaryOpKind::Add => BinaryOpKind::WrappingAdd,
+            BinaryOpKind::Subtract => BinaryOpKind::WrappingSubtract,
+            BinaryOpKind::Multiply => BinaryOpKind::WrappingMultiply,
+            other => other,
         }
     }
 
@@ -343,6 +386,36 @@ pub struct IfExpression {
     pub alternative: Option<Expression>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MatchExpression {
+    pub expression: Expression,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MatchArm {
+    pub pattern: MatchPattern,
+    pub body: Expression,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MatchPattern {
+    /// `Enum::Variant(a, b)`, binding each field of the variant to a pattern.
+    /// Variants without fields are written without parentheses.
+    Variant(Path, Vec<Pattern>, Span),
+    /// Matches any value, binding it to the given pattern
+    Default(Pattern),
+}
+
+impl MatchPattern {
+    pub fn span(&self) -> Span {
+        match self {
+            MatchPattern::Variant(_, _, span) => *span,
+            MatchPattern::Default(pattern) => pattern.span(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Lambda {
     pub parameters: Vec<(Pattern, UnresolvedType)>,
@@ -366,6 +439,10 @@ pub struct FunctionDefinition {
     /// True if this function was defined with the 'unconstrained' keyword
     pub is_unconstrained: bool,
 
+    /// True if this function was defined with the 'comptime' keyword, allowing it to be
+    /// called within array lengths and global constants
+    pub is_comptime: bool,
+
     /// Indicate if this function was defined with the 'pub' keyword
     pub visibility: FunctionVisibility,
 
@@ -430,6 +507,8 @@ pub struct MethodCallExpression {
 pub struct ConstructorExpression {
     pub type_name: Path,
     pub fields: Vec<(Ident, Expression)>,
+    /// The struct in `..base` which any fields not listed in `fields` are copied from
+    pub base: Option<Expression>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -480,6 +559,7 @@ impl Display for ExpressionKind {
             Cast(cast) => cast.fmt(f),
             Infix(infix) => infix.fmt(f),
             If(if_expr) => if_expr.fmt(f),
+            Match(match_expr) => match_expr.fmt(f),
             Variable(path) => path.fmt(f),
             Constructor(constructor) => constructor.fmt(f),
             MemberAccess(access) => access.fmt(f),
@@ -488,7 +568,10 @@ impl Display for ExpressionKind {
                 write!(f, "({})", elements.join(", "))
             }
             Lambda(lambda) => lambda.fmt(f),
+            Try(expr) => write!(f, "{expr}?"),
             Parenthesized(sub_expr) => write!(f, "({sub_expr})"),
+            Comptime(block) => write!(f, "comptime {block}"),
+            Unconstrained(block) => write!(f, "unconstrained {block}"),
             Error => write!(f, "Error"),
         }
     }
@@ -571,8 +654,11 @@ impl Display for CastExpression {
 
 impl Display for ConstructorExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let fields =
+        let mut fields =
             self.fields.iter().map(|(ident, expr)| format!("{ident}: {expr}")).collect::<Vec<_>>();
+        if let Some(base) = &self.base {
+            fields.push(format!("..{base}"));
+        }
 
         write!(f, "({} {{ {} }})", self.type_name, fields.join(", "))
     }
@@ -609,6 +695,9 @@ impl Display for BinaryOpKind {
             BinaryOpKind::ShiftLeft => write!(f, "<<"),
             BinaryOpKind::ShiftRight => write!(f, ">>"),
             BinaryOpKind::Modulo => write!(f, "%"),
+            BinaryOpKind::WrappingAdd => write!(f, "+%"),
+            BinaryOpKind::WrappingSubtract => write!(f, "-%"),
+            BinaryOpKind::WrappingMultiply => write!(f, "*%"),
         }
     }
 }
@@ -623,6 +712,29 @@ impl Display for IfExpression {
     }
 }
 
+impl Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "match {} {{", self.expression)?;
+        for arm in &self.arms {
+            writeln!(f, "    {} => {},", arm.pattern, arm.body)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Display for MatchPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchPattern::Variant(path, fields, _) if fields.is_empty() => path.fmt(f),
+            MatchPattern::Variant(path, fields, _) => {
+                let fields = vecmap(fields, ToString::to_string);
+                write!(f, "{path}({})", fields.join(", "))
+            }
+            MatchPattern::Default(pattern) => pattern.fmt(f),
+        }
+    }
+}
+
 impl Display for Lambda {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let parameters = vecmap(&self.parameters, |(name, r#type)| format!("{name}: {type}"));
@@ -655,6 +767,7 @@ impl FunctionDefinition {
             is_open: false,
             is_internal: false,
             is_unconstrained: false,
+            is_comptime: false,
             visibility: FunctionVisibility::Private,
             generics: generics.clone(),
             parameters: p,
@@ -4,13 +4,16 @@
 //!
 //! Noir's Ast is produced by the parser and taken as input to name resolution,
 //! where it is converted into the Hir (defined in the hir_def module).
+mod enumeration;
 mod expression;
 mod function;
 mod statement;
 mod structure;
 mod traits;
 mod type_alias;
+pub mod visitor;
 
+pub use enumeration::*;
 pub use expression::*;
 pub use function::*;
 
@@ -25,7 +28,7 @@ use crate::{
     token::IntType,
     BinaryTypeOperator,
 };
-use iter_extended::vecmap;
+use iter_extended::{try_vecmap, vecmap};
 
 /// The parser parses types as 'UnresolvedType's which
 /// require name resolution to resolve any type names used
@@ -75,7 +78,7 @@ pub struct UnresolvedType {
 
 /// The precursor to TypeExpression, this is the type that the parser allows
 /// to be used in the length position of an array type. Only constants, variables,
-/// and numeric binary operators are allowed here.
+/// numeric binary operators, and calls to `comptime fn`s are allowed here.
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum UnresolvedTypeExpression {
     Variable(Path),
@@ -86,6 +89,7 @@ pub enum UnresolvedTypeExpression {
         Box<UnresolvedTypeExpression>,
         Span,
     ),
+    Call(Path, Vec<UnresolvedTypeExpression>, Span),
 }
 
 impl Recoverable for UnresolvedType {
@@ -170,6 +174,10 @@ impl std::fmt::Display for UnresolvedTypeExpression {
             UnresolvedTypeExpression::BinaryOperation(lhs, op, rhs, _) => {
                 write!(f, "({lhs} {op} {rhs})")
             }
+            UnresolvedTypeExpression::Call(function, arguments, _) => {
+                let arguments = vecmap(arguments, ToString::to_string);
+                write!(f, "{function}({})", arguments.join(", "))
+            }
         }
     }
 }
@@ -225,6 +233,7 @@ impl UnresolvedTypeExpression {
             UnresolvedTypeExpression::Variable(path) => path.span(),
             UnresolvedTypeExpression::Constant(_, span) => *span,
             UnresolvedTypeExpression::BinaryOperation(_, _, _, span) => *span,
+            UnresolvedTypeExpression::Call(_, _, span) => *span,
         }
     }
 
@@ -254,6 +263,12 @@ impl UnresolvedTypeExpression {
                 };
                 Ok(UnresolvedTypeExpression::BinaryOperation(lhs, op, rhs, expr.span))
             }
+            ExpressionKind::Call(call) if matches!(call.func.kind, ExpressionKind::Variable(_)) => {
+                let ExpressionKind::Variable(function) = call.func.kind else { unreachable!() };
+                let arguments =
+                    try_vecmap(call.arguments, UnresolvedTypeExpression::from_expr_helper)?;
+                Ok(UnresolvedTypeExpression::Call(function, arguments, expr.span))
+            }
             _ => Err(expr),
         }
     }
@@ -285,6 +300,17 @@ pub enum Visibility {
     // Constants are not allowed in the ABI for main at the moment.
     // Constant,
     Private,
+    /// A public parameter read from the call data of the databus.
+    CallData,
+    /// A public return value written to the return data of the databus.
+    ReturnData,
+}
+
+impl Visibility {
+    /// Returns true if the value is known to the verifier.
+    pub fn is_public(self) -> bool {
+        self != Visibility::Private
+    }
 }
 
 impl std::fmt::Display for Visibility {
@@ -292,6 +318,8 @@ impl std::fmt::Display for Visibility {
         match self {
             Self::Public => write!(f, "pub"),
             Self::Private => write!(f, "priv"),
+            Self::CallData => write!(f, "call_data"),
+            Self::ReturnData => write!(f, "return_data"),
         }
     }
 }
@@ -35,6 +35,11 @@ pub enum StatementKind {
     Expression(Expression),
     Assign(AssignStatement),
     For(ForLoopStatement),
+    While(WhileStatement),
+    Loop(LoopStatement),
+    Break(Span),
+    Continue(Span),
+    Return(ReturnStatement),
     // This is an expression with a trailing semi-colon
     Semi(Expression),
     // This statement is the result of a recovered parse error.
@@ -66,13 +71,25 @@ impl Statement {
                 }
                 self.kind
             }
-            // A semicolon on a for loop is optional and does nothing
-            StatementKind::For(_) => self.kind,
+            // A semicolon on a loop is optional and does nothing
+            StatementKind::For(_) | StatementKind::While(_) | StatementKind::Loop(_) => self.kind,
+
+            // Like other expressions, `break`, `continue` and `return` may omit their semicolon
+            // at the end of a block
+            StatementKind::Break(_) | StatementKind::Continue(_) | StatementKind::Return(_) => {
+                if semi.is_none() && !last_statement_in_block {
+                    emit_error(missing_semicolon);
+                }
+                self.kind
+            }
 
             StatementKind::Expression(expr) => {
                 match (&expr.kind, semi, last_statement_in_block) {
                     // Semicolons are optional for these expressions
-                    (ExpressionKind::Block(_), semi, _) | (ExpressionKind::If(_), semi, _) => {
+                    (ExpressionKind::Block(_), semi, _)
+                    | (ExpressionKind::If(_), semi, _)
+                    | (ExpressionKind::Comptime(_), semi, _)
+                    | (ExpressionKind::Unconstrained(_), semi, _) => {
                         if semi.is_some() {
                             StatementKind::Semi(expr)
                         } else {
@@ -240,6 +257,11 @@ pub trait Recoverable {
 pub struct ImportStatement {
     pub path: Path,
     pub alias: Option<Ident>,
+    /// True for `use path::*`, in which case `path` refers to the module whose items are imported
+    pub is_glob: bool,
+    /// The span of the `use` statement this import was written in, or `None` for imports added
+    /// by the compiler
+    pub span: Option<Span>,
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
@@ -263,12 +285,13 @@ impl Display for UseTree {
             UseTreeKind::Path(name, alias) => {
                 write!(f, "{name}")?;
 
-                while let Some(alias) = alias {
+                if let Some(alias) = alias {
                     write!(f, " as {alias}")?;
                 }
 
                 Ok(())
             }
+            UseTreeKind::Glob => write!(f, "::*"),
             UseTreeKind::List(trees) => {
                 write!(f, "::{{")?;
                 let tree = vecmap(trees, ToString::to_string).join(", ");
@@ -282,6 +305,7 @@ impl Display for UseTree {
 pub enum UseTreeKind {
     Path(Ident, Option<Ident>),
     List(Vec<UseTree>),
+    Glob,
 }
 
 impl UseTree {
@@ -295,7 +319,10 @@ impl UseTree {
 
         match self.kind {
             UseTreeKind::Path(name, alias) => {
-                vec![ImportStatement { path: prefix.join(name), alias }]
+                vec![ImportStatement { path: prefix.join(name), alias, is_glob: false, span: None }]
+            }
+            UseTreeKind::Glob => {
+                vec![ImportStatement { path: prefix, alias: None, is_glob: true, span: None }]
             }
             UseTreeKind::List(trees) => {
                 trees.into_iter().flat_map(|tree| tree.desugar(Some(prefix.clone()))).collect()
@@ -417,8 +444,10 @@ pub enum LValue {
     Dereference(Box<LValue>),
 }
 
+/// An `assert`, `assert_eq` or `constrain` statement. The optional message is
+/// always either a string literal or a format string literal.
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct ConstrainStatement(pub Expression, pub Option<String>, pub ConstrainKind);
+pub struct ConstrainStatement(pub Expression, pub Option<Expression>, pub ConstrainKind);
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ConstrainKind {
@@ -432,16 +461,18 @@ pub enum Pattern {
     Identifier(Ident),
     Mutable(Box<Pattern>, Span),
     Tuple(Vec<Pattern>, Span),
-    Struct(Path, Vec<(Ident, Pattern)>, Span),
+    /// A struct pattern such as `Foo { a, b: (c, d) }`. The flag is set if the fields
+    /// end with `..`, in which case any fields which are not listed are ignored.
+    Struct(Path, Vec<(Ident, Pattern)>, bool, Span),
 }
 
 impl Pattern {
     pub fn span(&self) -> Span {
         match self {
             Pattern::Identifier(ident) => ident.span(),
-            Pattern::Mutable(_, span) | Pattern::Tuple(_, span) | Pattern::Struct(_, _, span) => {
-                *span
-            }
+            Pattern::Mutable(_, span)
+            | Pattern::Tuple(_, span)
+            | Pattern::Struct(_, _, _, span) => *span,
         }
     }
     pub fn name_ident(&self) -> &Ident {
@@ -590,6 +621,7 @@ impl ForRange {
                         identifier: fresh_identifier,
                         range: ForRange::Range(start_range, end_range),
                         block: new_block,
+                        max_iterations: None,
                         span: for_loop_span,
                     }),
                     span: for_loop_span,
@@ -602,11 +634,42 @@ impl ForRange {
     }
 }
 
+/// A `for` loop over a range or an array. Outside of unconstrained code the range must be known
+/// at compile-time, unless the loop is given an upper bound with a `#[max_iterations(N)]`
+/// attribute.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ForLoopStatement {
     pub identifier: Ident,
     pub range: ForRange,
     pub block: Expression,
+    pub max_iterations: Option<u64>,
+    pub span: Span,
+}
+
+/// A `while` loop. Outside of unconstrained code the loop must have an upper bound on its
+/// number of iterations, either given by a `#[max_iterations(N)]` attribute or inferred
+/// from the condition, as the loop is unrolled when compiling to ACIR.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WhileStatement {
+    pub condition: Expression,
+    pub block: Expression,
+    pub max_iterations: Option<u64>,
+    pub span: Span,
+}
+
+/// An infinite `loop`, which is exited with `break`. As with while loops, constrained code
+/// requires a `#[max_iterations(N)]` attribute on the loop so that it can be unrolled.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LoopStatement {
+    pub block: Expression,
+    pub max_iterations: Option<u64>,
+    pub span: Span,
+}
+
+/// `return expr`, or just `return` for functions returning the unit type.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ReturnStatement {
+    pub expression: Option<Expression>,
     pub span: Span,
 }
 
@@ -618,6 +681,11 @@ impl Display for StatementKind {
             StatementKind::Expression(expression) => expression.fmt(f),
             StatementKind::Assign(assign) => assign.fmt(f),
             StatementKind::For(for_loop) => for_loop.fmt(f),
+            StatementKind::While(while_loop) => while_loop.fmt(f),
+            StatementKind::Loop(loop_statement) => loop_statement.fmt(f),
+            StatementKind::Break(_) => write!(f, "break"),
+            StatementKind::Continue(_) => write!(f, "continue"),
+            StatementKind::Return(return_statement) => return_statement.fmt(f),
             StatementKind::Semi(semi) => write!(f, "{semi};"),
             StatementKind::Error => write!(f, "Error"),
         }
@@ -673,6 +741,9 @@ impl Display for PathKind {
 impl Display for ImportStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "use {}", self.path)?;
+        if self.is_glob {
+            write!(f, "::*")?;
+        }
         if let Some(alias) = &self.alias {
             write!(f, " as {alias}")?;
         }
@@ -689,8 +760,11 @@ impl Display for Pattern {
                 let fields = vecmap(fields, ToString::to_string);
                 write!(f, "({})", fields.join(", "))
             }
-            Pattern::Struct(typename, fields, _) => {
-                let fields = vecmap(fields, |(name, pattern)| format!("{name}: {pattern}"));
+            Pattern::Struct(typename, fields, has_rest, _) => {
+                let mut fields = vecmap(fields, |(name, pattern)| format!("{name}: {pattern}"));
+                if *has_rest {
+                    fields.push("..".to_string());
+                }
                 write!(f, "{} {{ {} }}", typename, fields.join(", "))
             }
         }
@@ -704,6 +778,36 @@ impl Display for ForLoopStatement {
             ForRange::Array(expr) => expr.to_string(),
         };
 
+        if let Some(max_iterations) = self.max_iterations {
+            write!(f, "#[max_iterations({max_iterations})] ")?;
+        }
         write!(f, "for {} in {range} {}", self.identifier, self.block)
     }
 }
+
+impl Display for WhileStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(max_iterations) = self.max_iterations {
+            write!(f, "#[max_iterations({max_iterations})] ")?;
+        }
+        write!(f, "while {} {}", self.condition, self.block)
+    }
+}
+
+impl Display for LoopStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(max_iterations) = self.max_iterations {
+            write!(f, "#[max_iterations({max_iterations})] ")?;
+        }
+        write!(f, "loop {}", self.block)
+    }
+}
+
+impl Display for ReturnStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.expression {
+            Some(expression) => write!(f, "return {expression}"),
+            None => write!(f, "return"),
+        }
+    }
+}
@@ -48,6 +48,8 @@ pub struct TypeImpl {
     pub object_type: UnresolvedType,
     pub type_span: Span,
     pub generics: UnresolvedGenerics,
+    /// Constraints on the impl's generics which every method in the impl inherits
+    pub where_clause: Vec<UnresolvedTraitConstraint>,
     pub methods: Vec<NoirFunction>,
 }
 
@@ -99,7 +101,14 @@ impl Display for TypeImpl {
         let generics = vecmap(&self.generics, |generic| generic.to_string());
         let generics = if generics.is_empty() { "".into() } else { generics.join(", ") };
 
-        writeln!(f, "impl{} {} {{", generics, self.object_type)?;
+        let where_clause = vecmap(&self.where_clause, ToString::to_string);
+        let where_clause = if where_clause.is_empty() {
+            String::new()
+        } else {
+            format!(" where {}", where_clause.join(", "))
+        };
+
+        writeln!(f, "impl{} {}{} {{", generics, self.object_type, where_clause)?;
 
         for method in self.methods.iter() {
             let method = method.to_string();
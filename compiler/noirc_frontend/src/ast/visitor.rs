@@ -0,0 +1,496 @@
+//! A read-only traversal of the Ast, for tools such as linters which only need to look at some
+//! kinds of node.
+//!
+//! Each `visit_*` method of [`Visitor`] defaults to calling the `walk_*` function of the same
+//! name, which visits the node's children. An implementation overrides the methods for the nodes
+//! it is interested in, calling the matching `walk_*` function if it also wants to visit their
+//! children.
+use crate::parser::{Item, ItemKind, ParsedModule, ParsedSubModule};
+use crate::{
+    ArrayLiteral, BlockExpression, ConstrainStatement, Expression, ExpressionKind, ForRange,
+    FunctionReturnType, LValue, LetStatement, Literal, MatchPattern, NoirEnum, NoirFunction,
+    NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Path, Pattern, Statement, StatementKind,
+    TraitImplItem, TraitItem, TypeImpl, UnresolvedTraitConstraint, UnresolvedType,
+    UnresolvedTypeData, UnresolvedTypeExpression, UseTree,
+};
+
+pub trait Visitor: Sized {
+    fn visit_parsed_module(&mut self, module: &ParsedModule) {
+        walk_parsed_module(self, module);
+    }
+
+    fn visit_item(&mut self, item: &Item) {
+        walk_item(self, item);
+    }
+
+    fn visit_use_tree(&mut self, _use_tree: &UseTree) {}
+
+    fn visit_function(&mut self, function: &NoirFunction) {
+        walk_function(self, function);
+    }
+
+    fn visit_struct(&mut self, noir_struct: &NoirStruct) {
+        walk_struct(self, noir_struct);
+    }
+
+    fn visit_enum(&mut self, noir_enum: &NoirEnum) {
+        walk_enum(self, noir_enum);
+    }
+
+    fn visit_trait(&mut self, noir_trait: &NoirTrait) {
+        walk_trait(self, noir_trait);
+    }
+
+    fn visit_trait_impl(&mut self, trait_impl: &NoirTraitImpl) {
+        walk_trait_impl(self, trait_impl);
+    }
+
+    fn visit_impl(&mut self, type_impl: &TypeImpl) {
+        walk_impl(self, type_impl);
+    }
+
+    fn visit_type_alias(&mut self, type_alias: &NoirTypeAlias) {
+        walk_type_alias(self, type_alias);
+    }
+
+    fn visit_global(&mut self, global: &LetStatement) {
+        walk_let_statement(self, global);
+    }
+
+    fn visit_submodule(&mut self, submodule: &ParsedSubModule) {
+        walk_submodule(self, submodule);
+    }
+
+    fn visit_trait_constraint(&mut self, constraint: &UnresolvedTraitConstraint) {
+        walk_trait_constraint(self, constraint);
+    }
+
+    fn visit_block(&mut self, block: &BlockExpression) {
+        walk_block(self, block);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_lvalue(&mut self, lvalue: &LValue) {
+        walk_lvalue(self, lvalue);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_match_pattern(&mut self, pattern: &MatchPattern) {
+        walk_match_pattern(self, pattern);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        walk_pattern(self, pattern);
+    }
+
+    fn visit_type(&mut self, typ: &UnresolvedType) {
+        walk_type(self, typ);
+    }
+
+    fn visit_type_expression(&mut self, expression: &UnresolvedTypeExpression) {
+        walk_type_expression(self, expression);
+    }
+
+    /// Visits a path referring to an item, such as a variable, a function or a type.
+    /// The paths of `use` statements are not visited here but as part of their [`UseTree`].
+    fn visit_path(&mut self, _path: &Path) {}
+}
+
+pub fn walk_parsed_module<V: Visitor>(visitor: &mut V, module: &ParsedModule) {
+    for item in &module.items {
+        visitor.visit_item(item);
+    }
+}
+
+pub fn walk_item<V: Visitor>(visitor: &mut V, item: &Item) {
+    match &item.kind {
+        ItemKind::Import(use_tree) => visitor.visit_use_tree(use_tree),
+        ItemKind::Function(function) => visitor.visit_function(function),
+        ItemKind::Struct(noir_struct) => visitor.visit_struct(noir_struct),
+        ItemKind::Enum(noir_enum) => visitor.visit_enum(noir_enum),
+        ItemKind::Trait(noir_trait) => visitor.visit_trait(noir_trait),
+        ItemKind::TraitImpl(trait_impl) => visitor.visit_trait_impl(trait_impl),
+        ItemKind::Impl(type_impl) => visitor.visit_impl(type_impl),
+        ItemKind::TypeAlias(type_alias) => visitor.visit_type_alias(type_alias),
+        ItemKind::Global(global) => visitor.visit_global(global),
+        ItemKind::ModuleDecl(_) => (),
+        ItemKind::Submodules(submodule) => visitor.visit_submodule(submodule),
+    }
+}
+
+pub fn walk_function<V: Visitor>(visitor: &mut V, function: &NoirFunction) {
+    let def = &function.def;
+    for param in &def.parameters {
+        visitor.visit_pattern(&param.pattern);
+        visitor.visit_type(&param.typ);
+    }
+    walk_return_type(visitor, &def.return_type);
+    for constraint in &def.where_clause {
+        visitor.visit_trait_constraint(constraint);
+    }
+    visitor.visit_block(&def.body);
+}
+
+fn walk_return_type<V: Visitor>(visitor: &mut V, return_type: &FunctionReturnType) {
+    match return_type {
+        FunctionReturnType::Default(_) => (),
+        FunctionReturnType::Ty(typ) => visitor.visit_type(typ),
+    }
+}
+
+pub fn walk_struct<V: Visitor>(visitor: &mut V, noir_struct: &NoirStruct) {
+    for (_, typ) in &noir_struct.fields {
+        visitor.visit_type(typ);
+    }
+}
+
+pub fn walk_enum<V: Visitor>(visitor: &mut V, noir_enum: &NoirEnum) {
+    for typ in noir_enum.variants.iter().flat_map(|(_, fields)| fields) {
+        visitor.visit_type(typ);
+    }
+}
+
+pub fn walk_trait<V: Visitor>(visitor: &mut V, noir_trait: &NoirTrait) {
+    for constraint in &noir_trait.where_clause {
+        visitor.visit_trait_constraint(constraint);
+    }
+    for item in &noir_trait.items {
+        match item {
+            TraitItem::Function { parameters, return_type, where_clause, body, .. } => {
+                for (_, typ) in parameters {
+                    visitor.visit_type(typ);
+                }
+                walk_return_type(visitor, return_type);
+                for constraint in where_clause {
+                    visitor.visit_trait_constraint(constraint);
+                }
+                if let Some(body) = body {
+                    visitor.visit_block(body);
+                }
+            }
+            TraitItem::Constant { typ, default_value, .. } => {
+                visitor.visit_type(typ);
+                if let Some(default_value) = default_value {
+                    visitor.visit_expression(default_value);
+                }
+            }
+            TraitItem::Type { .. } => (),
+        }
+    }
+}
+
+pub fn walk_trait_impl<V: Visitor>(visitor: &mut V, trait_impl: &NoirTraitImpl) {
+    visitor.visit_path(&trait_impl.trait_name);
+    for typ in &trait_impl.trait_generics {
+        visitor.visit_type(typ);
+    }
+    visitor.visit_type(&trait_impl.object_type);
+    for constraint in &trait_impl.where_clause {
+        visitor.visit_trait_constraint(constraint);
+    }
+    for item in &trait_impl.items {
+        match item {
+            TraitImplItem::Function(function) => visitor.visit_function(function),
+            TraitImplItem::Constant(_, typ, expression) => {
+                visitor.visit_type(typ);
+                visitor.visit_expression(expression);
+            }
+            TraitImplItem::Type { alias, .. } => visitor.visit_type(alias),
+        }
+    }
+}
+
+pub fn walk_impl<V: Visitor>(visitor: &mut V, type_impl: &TypeImpl) {
+    visitor.visit_type(&type_impl.object_type);
+    for constraint in &type_impl.where_clause {
+        visitor.visit_trait_constraint(constraint);
+    }
+    for method in &type_impl.methods {
+        visitor.visit_function(method);
+    }
+}
+
+pub fn walk_type_alias<V: Visitor>(visitor: &mut V, type_alias: &NoirTypeAlias) {
+    visitor.visit_type(&type_alias.typ);
+}
+
+pub fn walk_let_statement<V: Visitor>(visitor: &mut V, let_statement: &LetStatement) {
+    visitor.visit_pattern(&let_statement.pattern);
+    visitor.visit_type(&let_statement.r#type);
+    visitor.visit_expression(&let_statement.expression);
+}
+
+pub fn walk_submodule<V: Visitor>(visitor: &mut V, submodule: &ParsedSubModule) {
+    visitor.visit_parsed_module(&submodule.contents);
+}
+
+pub fn walk_trait_constraint<V: Visitor>(visitor: &mut V, constraint: &UnresolvedTraitConstraint) {
+    visitor.visit_type(&constraint.typ);
+    visitor.visit_path(&constraint.trait_bound.trait_path);
+    for typ in &constraint.trait_bound.trait_generics {
+        visitor.visit_type(typ);
+    }
+}
+
+pub fn walk_block<V: Visitor>(visitor: &mut V, block: &BlockExpression) {
+    for statement in &block.0 {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: Visitor>(visitor: &mut V, statement: &Statement) {
+    match &statement.kind {
+        StatementKind::Let(let_statement) => walk_let_statement(visitor, let_statement),
+        StatementKind::Constrain(ConstrainStatement(condition, message, _)) => {
+            visitor.visit_expression(condition);
+            if let Some(message) = message {
+                visitor.visit_expression(message);
+            }
+        }
+        StatementKind::Expression(expression) | StatementKind::Semi(expression) => {
+            visitor.visit_expression(expression);
+        }
+        StatementKind::Assign(assign) => {
+            visitor.visit_lvalue(&assign.lvalue);
+            visitor.visit_expression(&assign.expression);
+        }
+        StatementKind::For(for_loop) => {
+            match &for_loop.range {
+                ForRange::Range(start, end) => {
+                    visitor.visit_expression(start);
+                    visitor.visit_expression(end);
+                }
+                ForRange::Array(array) => visitor.visit_expression(array),
+            }
+            visitor.visit_expression(&for_loop.block);
+        }
+        StatementKind::While(while_loop) => {
+            visitor.visit_expression(&while_loop.condition);
+            visitor.visit_expression(&while_loop.block);
+        }
+        StatementKind::Loop(loop_statement) => visitor.visit_expression(&loop_statement.block),
+        StatementKind::Return(return_statement) => {
+            if let Some(expression) = &return_statement.expression {
+                visitor.visit_expression(expression);
+            }
+        }
+        StatementKind::Break(_) | StatementKind::Continue(_) | StatementKind::Error => (),
+    }
+}
+
+pub fn walk_lvalue<V: Visitor>(visitor: &mut V, lvalue: &LValue) {
+    match lvalue {
+        LValue::Ident(_) => (),
+        LValue::MemberAccess { object, .. } => visitor.visit_lvalue(object),
+        LValue::Index { array, index } => {
+            visitor.visit_lvalue(array);
+            visitor.visit_expression(index);
+        }
+        LValue::Dereference(lvalue) => visitor.visit_lvalue(lvalue),
+    }
+}
+
+pub fn walk_expression<V: Visitor>(visitor: &mut V, expression: &Expression) {
+    match &expression.kind {
+        ExpressionKind::Literal(Literal::Array(ArrayLiteral::Standard(elements))) => {
+            elements.iter().for_each(|element| visitor.visit_expression(element));
+        }
+        ExpressionKind::Literal(Literal::Array(ArrayLiteral::Repeated {
+            repeated_element,
+            length,
+        })) => {
+            visitor.visit_expression(repeated_element);
+            visitor.visit_expression(length);
+        }
+        ExpressionKind::Literal(_) | ExpressionKind::Error => (),
+        ExpressionKind::Block(block)
+        | ExpressionKind::Comptime(block)
+        | ExpressionKind::Unconstrained(block) => visitor.visit_block(block),
+        ExpressionKind::Prefix(prefix) => visitor.visit_expression(&prefix.rhs),
+        ExpressionKind::Index(index) => {
+            visitor.visit_expression(&index.collection);
+            visitor.visit_expression(&index.index);
+        }
+        ExpressionKind::Call(call) => {
+            visitor.visit_expression(&call.func);
+            call.arguments.iter().for_each(|argument| visitor.visit_expression(argument));
+        }
+        ExpressionKind::MethodCall(call) => {
+            visitor.visit_expression(&call.object);
+            call.arguments.iter().for_each(|argument| visitor.visit_expression(argument));
+        }
+        ExpressionKind::Constructor(constructor) => {
+            visitor.visit_path(&constructor.type_name);
+            for (_, field) in &constructor.fields {
+                visitor.visit_expression(field);
+            }
+            if let Some(base) = &constructor.base {
+                visitor.visit_expression(base);
+            }
+        }
+        ExpressionKind::MemberAccess(access) => visitor.visit_expression(&access.lhs),
+        ExpressionKind::Cast(cast) => {
+            visitor.visit_expression(&cast.lhs);
+            visitor.visit_type(&cast.r#type);
+        }
+        ExpressionKind::Try(expression) | ExpressionKind::Parenthesized(expression) => {
+            visitor.visit_expression(expression);
+        }
+        ExpressionKind::Infix(infix) => {
+            visitor.visit_expression(&infix.lhs);
+            visitor.visit_expression(&infix.rhs);
+        }
+        ExpressionKind::If(if_expression) => {
+            visitor.visit_expression(&if_expression.condition);
+            visitor.visit_expression(&if_expression.consequence);
+            if let Some(alternative) = &if_expression.alternative {
+                visitor.visit_expression(alternative);
+            }
+        }
+        ExpressionKind::Match(match_expression) => {
+            visitor.visit_expression(&match_expression.expression);
+            for arm in &match_expression.arms {
+                visitor.visit_match_pattern(&arm.pattern);
+                visitor.visit_expression(&arm.body);
+            }
+        }
+        ExpressionKind::Variable(path) => visitor.visit_path(path),
+        ExpressionKind::Tuple(elements) => {
+            elements.iter().for_each(|element| visitor.visit_expression(element));
+        }
+        ExpressionKind::Lambda(lambda) => {
+            for (pattern, typ) in &lambda.parameters {
+                visitor.visit_pattern(pattern);
+                visitor.visit_type(typ);
+            }
+            visitor.visit_type(&lambda.return_type);
+            visitor.visit_expression(&lambda.body);
+        }
+    }
+}
+
+pub fn walk_match_pattern<V: Visitor>(visitor: &mut V, pattern: &MatchPattern) {
+    match pattern {
+        MatchPattern::Variant(path, fields, _) => {
+            visitor.visit_path(path);
+            fields.iter().for_each(|field| visitor.visit_pattern(field));
+        }
+        MatchPattern::Default(pattern) => visitor.visit_pattern(pattern),
+    }
+}
+
+pub fn walk_pattern<V: Visitor>(visitor: &mut V, pattern: &Pattern) {
+    match pattern {
+        Pattern::Identifier(_) => (),
+        Pattern::Mutable(pattern, _) => visitor.visit_pattern(pattern),
+        Pattern::Tuple(patterns, _) => {
+            patterns.iter().for_each(|pattern| visitor.visit_pattern(pattern));
+        }
+        Pattern::Struct(path, fields, _, _) => {
+            visitor.visit_path(path);
+            fields.iter().for_each(|(_, pattern)| visitor.visit_pattern(pattern));
+        }
+    }
+}
+
+pub fn walk_type<V: Visitor>(visitor: &mut V, typ: &UnresolvedType) {
+    match &typ.typ {
+        UnresolvedTypeData::Array(length, element) => {
+            if let Some(length) = length {
+                visitor.visit_type_expression(length);
+            }
+            visitor.visit_type(element);
+        }
+        UnresolvedTypeData::Expression(expression) => visitor.visit_type_expression(expression),
+        UnresolvedTypeData::String(length) => {
+            if let Some(length) = length {
+                visitor.visit_type_expression(length);
+            }
+        }
+        UnresolvedTypeData::FormatString(length, element) => {
+            visitor.visit_type_expression(length);
+            visitor.visit_type(element);
+        }
+        UnresolvedTypeData::Named(path, generics)
+        | UnresolvedTypeData::TraitAsType(path, generics) => {
+            visitor.visit_path(path);
+            generics.iter().for_each(|generic| visitor.visit_type(generic));
+        }
+        UnresolvedTypeData::MutableReference(element) => visitor.visit_type(element),
+        UnresolvedTypeData::Tuple(elements) => {
+            elements.iter().for_each(|element| visitor.visit_type(element));
+        }
+        UnresolvedTypeData::Function(arguments, return_type, environment) => {
+            arguments.iter().for_each(|argument| visitor.visit_type(argument));
+            visitor.visit_type(return_type);
+            visitor.visit_type(environment);
+        }
+        UnresolvedTypeData::FieldElement
+        | UnresolvedTypeData::Integer(..)
+        | UnresolvedTypeData::Bool
+        | UnresolvedTypeData::Unit
+        | UnresolvedTypeData::Unspecified
+        | UnresolvedTypeData::Error => (),
+    }
+}
+
+pub fn walk_type_expression<V: Visitor>(visitor: &mut V, expression: &UnresolvedTypeExpression) {
+    match expression {
+        UnresolvedTypeExpression::Variable(path) => visitor.visit_path(path),
+        UnresolvedTypeExpression::Constant(..) => (),
+        UnresolvedTypeExpression::BinaryOperation(lhs, _, rhs, _) => {
+            visitor.visit_type_expression(lhs);
+            visitor.visit_type_expression(rhs);
+        }
+        UnresolvedTypeExpression::Call(path, arguments, _) => {
+            visitor.visit_path(path);
+            arguments.iter().for_each(|argument| visitor.visit_type_expression(argument));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+
+    use super::Visitor;
+    use crate::parser::parse_program;
+    use crate::Path;
+
+    #[derive(Default)]
+    struct PathCollector(Vec<String>);
+
+    impl Visitor for PathCollector {
+        fn visit_path(&mut self, path: &Path) {
+            self.0.push(vecmap(&path.segments, ToString::to_string).join("::"));
+        }
+    }
+
+    #[test]
+    fn visits_paths_in_nested_items() {
+        let src = "
+            struct Foo { x: Field }
+            mod inner {
+                fn bar(foo: crate::Foo) -> Field {
+                    let Foo { x } = foo;
+                    std::hash::hash(x) + helper::double(x)
+                }
+            }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let mut collector = PathCollector::default();
+        collector.visit_parsed_module(&module);
+        assert_eq!(
+            collector.0,
+            vec!["Foo", "Foo", "foo", "std::hash::hash", "x", "helper::double", "x"]
+        );
+    }
+}
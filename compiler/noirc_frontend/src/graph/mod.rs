@@ -4,13 +4,15 @@
 // This version is also simpler due to not having macro_defs or proc_macros
 // XXX: Edition may be reintroduced or some sort of versioning
 
-use std::{fmt::Display, str::FromStr};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
 use fm::FileId;
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
+use crate::lints::{Lint, LintLevel};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CrateId {
     Root(usize),
@@ -112,6 +114,29 @@ pub const CHARACTER_BLACK_LIST: [char; 1] = ['-'];
 pub struct CrateData {
     pub root_file_id: FileId,
     pub dependencies: Vec<Dependency>,
+    pub overflow_mode: OverflowMode,
+    // The levels lints are reported at in this crate, unless overridden by lint attributes
+    pub lint_levels: BTreeMap<Lint, LintLevel>,
+}
+
+/// How integer `+`, `-` and `*` behave on overflow within a crate.
+/// The explicit wrapping operators `+%`, `-%` and `*%` always wrap regardless of this setting.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum OverflowMode {
+    /// Overflowing arithmetic fails the assertion guarding it.
+    #[default]
+    Trap,
+    /// Overflowing arithmetic wraps around modulo 2^bit_size.
+    Wrap,
+}
+
+impl Display for OverflowMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverflowMode::Trap => write!(f, "trap"),
+            OverflowMode::Wrap => write!(f, "wrap"),
+        }
+    }
 }
 
 /// A dependency is a crate name and a crate_id
@@ -154,7 +179,12 @@ impl CrateGraph {
             }
         }
 
-        let data = CrateData { root_file_id: file_id, dependencies: Vec::new() };
+        let data = CrateData {
+            root_file_id: file_id,
+            dependencies: Vec::new(),
+            overflow_mode: OverflowMode::default(),
+            lint_levels: BTreeMap::new(),
+        };
         let crate_id = CrateId::Root(self.arena.len());
         let prev = self.arena.insert(crate_id, data);
         assert!(prev.is_none());
@@ -185,7 +215,12 @@ impl CrateGraph {
                 panic!("ICE: A dummy CrateId should not exist in the CrateGraph")
             }
             None => {
-                let data = CrateData { root_file_id: file_id, dependencies: Vec::new() };
+                let data = CrateData {
+                    root_file_id: file_id,
+                    dependencies: Vec::new(),
+                    overflow_mode: OverflowMode::default(),
+                    lint_levels: BTreeMap::new(),
+                };
                 let crate_id = CrateId::Crate(self.arena.len());
                 let prev = self.arena.insert(crate_id, data);
                 assert!(prev.is_none());
@@ -205,7 +240,12 @@ impl CrateGraph {
             }
         }
 
-        let data = CrateData { root_file_id: file_id, dependencies: Vec::new() };
+        let data = CrateData {
+            root_file_id: file_id,
+            dependencies: Vec::new(),
+            overflow_mode: OverflowMode::default(),
+            lint_levels: BTreeMap::new(),
+        };
         let crate_id = CrateId::Stdlib(self.arena.len());
         let prev = self.arena.insert(crate_id, data);
         assert!(prev.is_none());
@@ -273,6 +313,14 @@ impl CrateGraph {
         false
     }
 
+    pub fn set_overflow_mode(&mut self, crate_id: CrateId, overflow_mode: OverflowMode) {
+        self.arena.get_mut(&crate_id).unwrap().overflow_mode = overflow_mode;
+    }
+
+    pub fn set_lint_levels(&mut self, crate_id: CrateId, lint_levels: BTreeMap<Lint, LintLevel>) {
+        self.arena.get_mut(&crate_id).unwrap().lint_levels = lint_levels;
+    }
+
     pub fn number_of_crates(&self) -> usize {
         self.arena.len()
     }
@@ -153,7 +153,7 @@ fn index_array_variable(array: Expression, index: &str) -> Expression {
 }
 
 fn import(path: Path) -> ImportStatement {
-    ImportStatement { path, alias: None }
+    ImportStatement { path, alias: None, is_glob: false, span: None }
 }
 
 //
@@ -495,6 +495,7 @@ fn generate_selector_impl(structure: &NoirStruct) -> TypeImpl {
         object_type: struct_type,
         type_span: structure.span,
         generics: vec![],
+        where_clause: vec![],
         methods: vec![NoirFunction::normal(selector_fn_def)],
     }
 }
@@ -898,6 +899,7 @@ fn create_loop_over(var: Expression, loop_body: Vec<Statement>) -> Statement {
         ),
         identifier: ident("i"),
         block: for_loop_block,
+        max_iterations: None,
         span,
     }))
 }
@@ -0,0 +1,116 @@
+use noirc_errors::{error_codes as codes, CustomDiagnostic as Diagnostic, Location};
+use thiserror::Error;
+
+use crate::Type;
+
+use super::value::Value;
+
+#[derive(Error, Debug, Clone)]
+pub enum InterpreterError {
+    #[error("Non-comptime variable `{name}` referenced in comptime code")]
+    NonComptimeVarReferenced { name: String, location: Location },
+    #[error("{item} is not supported in comptime code")]
+    Unsupported { item: String, location: Location },
+    #[error("Failed constraint in comptime code")]
+    FailingConstraint { message: Option<String>, location: Location },
+    #[error("Integer overflow in comptime code")]
+    IntegerOverflow { location: Location },
+    #[error("Division by zero in comptime code")]
+    DivisionByZero { location: Location },
+    #[error("Index {index} is out of bounds for an array of length {length}")]
+    IndexOutOfBounds { index: usize, length: usize, location: Location },
+    #[error("Loop exceeded its limit of {max_iterations} iterations")]
+    LoopLimitExceeded { max_iterations: u64, location: Location },
+    #[error("Comptime code exceeded the maximum call depth")]
+    CallDepthExceeded { location: Location },
+    #[error("Values of type {typ} cannot be inserted into the program")]
+    CannotSplice { typ: Type, location: Location },
+
+    // These are not errors but are used to unwind the interpreter's call stack
+    #[error("`break` outside of a loop")]
+    Break,
+    #[error("`continue` outside of a loop")]
+    Continue,
+    #[error("`return` outside of a function")]
+    Return(Value),
+}
+
+impl InterpreterError {
+    /// Returns the location of this error. Panics for the variants used for control flow,
+    /// which never escape the interpreter.
+    pub fn location(&self) -> Location {
+        match self {
+            InterpreterError::NonComptimeVarReferenced { location, .. }
+            | InterpreterError::Unsupported { location, .. }
+            | InterpreterError::FailingConstraint { location, .. }
+            | InterpreterError::IntegerOverflow { location }
+            | InterpreterError::DivisionByZero { location }
+            | InterpreterError::IndexOutOfBounds { location, .. }
+            | InterpreterError::LoopLimitExceeded { location, .. }
+            | InterpreterError::CallDepthExceeded { location }
+            | InterpreterError::CannotSplice { location, .. } => *location,
+            InterpreterError::Break | InterpreterError::Continue | InterpreterError::Return(_) => {
+                unreachable!("control flow should not escape the interpreter")
+            }
+        }
+    }
+
+    /// Returns the stable error code of this kind of error. Panics for the variants used for
+    /// control flow, which never escape the interpreter.
+    pub fn code(&self) -> &'static str {
+        match self {
+            InterpreterError::NonComptimeVarReferenced { .. } => codes::E0500,
+            InterpreterError::Unsupported { .. } => codes::E0501,
+            InterpreterError::FailingConstraint { .. } => codes::E0502,
+            InterpreterError::IntegerOverflow { .. } => codes::E0503,
+            InterpreterError::DivisionByZero { .. } => codes::E0504,
+            InterpreterError::IndexOutOfBounds { .. } => codes::E0505,
+            InterpreterError::LoopLimitExceeded { .. } => codes::E0506,
+            InterpreterError::CallDepthExceeded { .. } => codes::E0507,
+            InterpreterError::CannotSplice { .. } => codes::E0508,
+            InterpreterError::Break | InterpreterError::Continue | InterpreterError::Return(_) => {
+                unreachable!("control flow should not escape the interpreter")
+            }
+        }
+    }
+}
+
+impl From<InterpreterError> for Diagnostic {
+    fn from(error: InterpreterError) -> Diagnostic {
+        let span = error.location().span;
+        let code = error.code();
+        let diagnostic = match error {
+            InterpreterError::NonComptimeVarReferenced { name, .. } => Diagnostic::simple_error(
+                format!("Non-comptime variable `{name}` referenced in comptime code"),
+                "Only globals and variables declared within comptime code are known at compile time"
+                    .into(),
+                span,
+            ),
+            InterpreterError::FailingConstraint { message, .. } => Diagnostic::simple_error(
+                "Failed constraint in comptime code".into(),
+                message.unwrap_or_default(),
+                span,
+            ),
+            InterpreterError::LoopLimitExceeded { max_iterations, .. } => {
+                Diagnostic::simple_error(
+                    format!("Loop exceeded its limit of {max_iterations} iterations"),
+                    String::new(),
+                    span,
+                )
+            }
+            InterpreterError::CallDepthExceeded { .. } => Diagnostic::simple_error(
+                "Comptime code exceeded the maximum call depth".into(),
+                "This is likely caused by unbounded recursion".into(),
+                span,
+            ),
+            InterpreterError::CannotSplice { typ, .. } => Diagnostic::simple_error(
+                format!("Values of type {typ} cannot be inserted into the program"),
+                "comptime blocks may only evaluate to data such as integers, arrays, and structs"
+                    .into(),
+                span,
+            ),
+            error => Diagnostic::simple_error(error.to_string(), String::new(), span),
+        };
+        diagnostic.with_code(code)
+    }
+}
@@ -0,0 +1,1079 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use acvm::FieldElement;
+use iter_extended::try_vecmap;
+use noirc_errors::Location;
+
+use crate::hir_def::expr::{
+    HirArrayLiteral, HirBlockExpression, HirCallExpression, HirCastExpression,
+    HirConstructorExpression, HirExpression, HirIdent, HirIfExpression, HirIndexExpression,
+    HirInfixExpression, HirLambda, HirLiteral, HirMatchExpression, HirMatchPattern,
+    HirMemberAccess, HirPrefixExpression, HirTryExpression,
+};
+use crate::hir_def::stmt::{
+    HirConstrainStatement, HirForStatement, HirLValue, HirPattern, HirStatement,
+};
+use crate::node_interner::{
+    DefinitionId, DefinitionKind, ExprId, FuncId, NodeInterner, StmtId, TraitImplKind,
+    TraitMethodId,
+};
+use crate::{
+    BinaryOpKind, FunctionKind, Signedness, Type, TypeBinding, TypeBindings, TypeVariable, UnaryOp,
+};
+
+use super::errors::InterpreterError;
+use super::value::Value;
+
+type IResult<T> = Result<T, InterpreterError>;
+
+/// The maximum depth of nested function calls before evaluation is aborted
+const MAX_CALL_DEPTH: usize = 256;
+
+pub struct Interpreter<'interner> {
+    interner: &'interner mut NodeInterner,
+
+    /// The block scopes of the function currently being evaluated, innermost last.
+    /// Each call replaces these with a fresh set of scopes for the callee.
+    scopes: Vec<HashMap<DefinitionId, Value>>,
+
+    call_depth: usize,
+}
+
+impl<'interner> Interpreter<'interner> {
+    pub fn new(interner: &'interner mut NodeInterner) -> Self {
+        Self { interner, scopes: vec![HashMap::new()], call_depth: 0 }
+    }
+
+    /// Evaluates the given `comptime` expression and replaces it in the interner
+    /// with the value it evaluated to.
+    pub fn evaluate_comptime(&mut self, expr_id: ExprId) -> IResult<()> {
+        let HirExpression::Comptime(block) = self.interner.expression(&expr_id) else {
+            unreachable!("evaluate_comptime called on a non-comptime expression")
+        };
+        let location = self.interner.expr_location(&expr_id);
+
+        let value = match self.evaluate(block) {
+            Err(InterpreterError::Return(_)) => {
+                let item = "`return` outside of a function".to_string();
+                return Err(InterpreterError::Unsupported { item, location });
+            }
+            other => other?,
+        };
+
+        let typ = self.interner.id_type(expr_id).follow_bindings();
+        let expression = value.into_hir_expression(self.interner, &typ, location)?;
+        self.interner.replace_expr(&expr_id, expression);
+        Ok(())
+    }
+
+    pub fn evaluate(&mut self, id: ExprId) -> IResult<Value> {
+        match self.interner.expression(&id) {
+            HirExpression::Ident(ident) => self.evaluate_ident(ident, id),
+            HirExpression::Literal(literal) => self.evaluate_literal(literal, id),
+            HirExpression::Block(block) => self.evaluate_block(block),
+            HirExpression::Prefix(prefix) => self.evaluate_prefix(prefix, id),
+            HirExpression::Infix(infix) => self.evaluate_infix(infix, id),
+            HirExpression::Index(index) => self.evaluate_index(index, id),
+            HirExpression::Constructor(constructor) => self.evaluate_constructor(constructor),
+            HirExpression::EnumConstructor(constructor) => {
+                let arguments = try_vecmap(constructor.arguments, |arg| self.evaluate(arg))?;
+                Ok(Value::Enum(constructor.variant, arguments))
+            }
+            HirExpression::MemberAccess(access) => self.evaluate_access(access, id),
+            HirExpression::Call(call) => self.evaluate_call(call, id),
+            HirExpression::MethodCall(_) => {
+                unreachable!("method calls are replaced with function calls during type checking")
+            }
+            HirExpression::Cast(cast) => self.evaluate_cast(cast, id),
+            HirExpression::Try(try_expr) => self.evaluate_try(try_expr, id),
+            HirExpression::If(if_expr) => self.evaluate_if(if_expr),
+            HirExpression::Match(match_expr) => self.evaluate_match(match_expr),
+            HirExpression::Tuple(elements) => {
+                Ok(Value::Tuple(try_vecmap(elements, |element| self.evaluate(element))?))
+            }
+            HirExpression::Lambda(lambda) => self.evaluate_lambda(lambda),
+            HirExpression::TraitMethodReference(method) => {
+                let typ = self.interner.id_type(id);
+                let location = self.interner.expr_location(&id);
+                let (function, bindings) =
+                    self.trait_method_function(id, method, &typ, location)?;
+                Ok(Value::Function(function, bindings))
+            }
+            HirExpression::Comptime(block) => self.evaluate(block),
+            HirExpression::Error => unreachable!("comptime code is only evaluated without errors"),
+        }
+    }
+
+    fn evaluate_ident(&mut self, ident: HirIdent, id: ExprId) -> IResult<Value> {
+        let definition = self.interner.definition(ident.id);
+        match definition.kind.clone() {
+            DefinitionKind::Function(function) => {
+                let bindings = self.interner.get_instantiation_bindings(id);
+                Ok(Value::Function(function, follow_bindings(bindings)))
+            }
+            DefinitionKind::Global(expr) => self.evaluate(expr),
+            DefinitionKind::Local(_) => self.lookup(ident.id).ok_or_else(|| {
+                let name = definition.name.clone();
+                InterpreterError::NonComptimeVarReferenced { name, location: ident.location }
+            }),
+            DefinitionKind::GenericType(type_variable) => {
+                let value = match &*type_variable.borrow() {
+                    TypeBinding::Bound(binding) => binding.evaluate_to_u64(),
+                    TypeBinding::Unbound(_) => None,
+                };
+                match value {
+                    Some(value) => Ok(self.integer_value(FieldElement::from(value as u128), id)),
+                    None => {
+                        let name = definition.name.clone();
+                        let location = ident.location;
+                        Err(InterpreterError::NonComptimeVarReferenced { name, location })
+                    }
+                }
+            }
+        }
+    }
+
+    fn lookup(&self, id: DefinitionId) -> Option<Value> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&id).cloned())
+    }
+
+    fn define(&mut self, id: DefinitionId, value: Value) {
+        self.scopes.last_mut().expect("there is always at least one scope").insert(id, value);
+    }
+
+    fn define_pattern(&mut self, pattern: &HirPattern, value: Value) {
+        match (pattern, value) {
+            (HirPattern::Identifier(ident), value) => self.define(ident.id, value),
+            (HirPattern::Mutable(pattern, _), value) => self.define_pattern(pattern, value),
+            (HirPattern::Tuple(patterns, _), Value::Tuple(fields)) => {
+                for (pattern, field) in patterns.iter().zip(fields) {
+                    self.define_pattern(pattern, field);
+                }
+            }
+            (HirPattern::Struct(typ, patterns, _), Value::Struct(mut fields)) => {
+                let Type::Struct(definition, generics) = typ.follow_bindings() else {
+                    unreachable!("struct patterns always have a struct type")
+                };
+                for (name, pattern) in patterns {
+                    let (_, index) = definition
+                        .borrow()
+                        .get_field(&name.0.contents, &generics)
+                        .expect("struct pattern fields are checked during type checking");
+                    let field = std::mem::replace(&mut fields[index], Value::Unit);
+                    self.define_pattern(pattern, field);
+                }
+            }
+            (pattern, value) => {
+                unreachable!("pattern {pattern:?} does not match the value {value:?}")
+            }
+        }
+    }
+
+    /// Assigns a new value to an existing variable
+    fn assign(&mut self, id: DefinitionId, value: Value) {
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(existing) = scope.get_mut(&id) {
+                *existing = value;
+                return;
+            }
+        }
+        unreachable!("assigned to a variable which was never defined")
+    }
+
+    /// Evaluates `body` within a new block scope
+    fn in_scope<T>(&mut self, body: impl FnOnce(&mut Self) -> IResult<T>) -> IResult<T> {
+        self.scopes.push(HashMap::new());
+        let result = body(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn evaluate_literal(&mut self, literal: HirLiteral, id: ExprId) -> IResult<Value> {
+        match literal {
+            HirLiteral::Unit => Ok(Value::Unit),
+            HirLiteral::Bool(value) => Ok(Value::Bool(value)),
+            HirLiteral::Integer(value) => Ok(self.integer_value(value, id)),
+            HirLiteral::Str(string) => Ok(Value::String(Rc::new(string))),
+            HirLiteral::FmtStr(..) => {
+                let item = "Format strings".to_string();
+                Err(InterpreterError::Unsupported { item, location: self.location(id) })
+            }
+            HirLiteral::Array(HirArrayLiteral::Standard(elements)) => {
+                Ok(Value::Array(try_vecmap(elements, |element| self.evaluate(element))?))
+            }
+            HirLiteral::Array(HirArrayLiteral::Repeated { repeated_element, length }) => {
+                let element = self.evaluate(repeated_element)?;
+                match length.follow_bindings().evaluate_to_u64() {
+                    Some(length) => Ok(Value::Array(vec![element; length as usize])),
+                    None => {
+                        let item = "Arrays of non-constant length".to_string();
+                        Err(InterpreterError::Unsupported { item, location: self.location(id) })
+                    }
+                }
+            }
+        }
+    }
+
+    /// Creates an integer or field value for a literal, depending on the type inferred for it
+    fn integer_value(&self, value: FieldElement, id: ExprId) -> Value {
+        match self.interner.id_type(id).follow_bindings() {
+            Type::Integer(sign, bits) => Value::Integer(value.to_u128() as i128, sign, bits),
+            _ => Value::Field(value),
+        }
+    }
+
+    fn evaluate_block(&mut self, block: HirBlockExpression) -> IResult<Value> {
+        self.in_scope(|this| {
+            let mut result = Value::Unit;
+            for statement in block.statements() {
+                result = this.evaluate_statement(*statement)?;
+            }
+            Ok(result)
+        })
+    }
+
+    fn evaluate_prefix(&mut self, prefix: HirPrefixExpression, id: ExprId) -> IResult<Value> {
+        let rhs = self.evaluate(prefix.rhs)?;
+        let location = self.location(id);
+        match (prefix.operator, rhs) {
+            (UnaryOp::Minus, Value::Field(value)) => Ok(Value::Field(-value)),
+            (UnaryOp::Minus, Value::Integer(value, Signedness::Unsigned, bits)) => {
+                checked_unsigned(0u128.checked_sub(value as u128), bits, location)
+            }
+            (UnaryOp::Minus, Value::Integer(value, _, bits)) => {
+                checked_signed(value.checked_neg(), bits, location)
+            }
+            (UnaryOp::Not, Value::Bool(value)) => Ok(Value::Bool(!value)),
+            (UnaryOp::Not, Value::Integer(value, sign, bits)) => {
+                Ok(Value::Integer(wrap_integer(!value as u128, sign, bits), sign, bits))
+            }
+            _ => {
+                let item = "Mutable references".to_string();
+                Err(InterpreterError::Unsupported { item, location })
+            }
+        }
+    }
+
+    fn evaluate_infix(&mut self, infix: HirInfixExpression, id: ExprId) -> IResult<Value> {
+        let lhs = self.evaluate(infix.lhs)?;
+        let rhs = self.evaluate(infix.rhs)?;
+        let location = self.location(id);
+        let operator = infix.operator.kind;
+
+        if self.interner.get_selected_impl_for_ident(id).is_some() {
+            return self.evaluate_overloaded_operator(infix, id, lhs, rhs, location);
+        }
+
+        use BinaryOpKind::*;
+        match (lhs, rhs) {
+            (Value::Field(lhs), Value::Field(rhs)) => match operator {
+                Add | WrappingAdd => Ok(Value::Field(lhs + rhs)),
+                Subtract | WrappingSubtract => Ok(Value::Field(lhs - rhs)),
+                Multiply | WrappingMultiply => Ok(Value::Field(lhs * rhs)),
+                Divide if rhs.is_zero() => Err(InterpreterError::DivisionByZero { location }),
+                Divide => Ok(Value::Field(lhs / rhs)),
+                Equal => Ok(Value::Bool(lhs == rhs)),
+                NotEqual => Ok(Value::Bool(lhs != rhs)),
+                Less => Ok(Value::Bool(lhs < rhs)),
+                LessEqual => Ok(Value::Bool(lhs <= rhs)),
+                Greater => Ok(Value::Bool(lhs > rhs)),
+                GreaterEqual => Ok(Value::Bool(lhs >= rhs)),
+                _ => {
+                    let item = format!("The operator {operator} on fields");
+                    Err(InterpreterError::Unsupported { item, location })
+                }
+            },
+            (Value::Integer(lhs, Signedness::Unsigned, bits), Value::Integer(rhs, _, _)) => {
+                evaluate_unsigned_infix(lhs as u128, rhs as u128, operator, bits, location)
+            }
+            (Value::Integer(lhs, sign, bits), Value::Integer(rhs, _, _)) => match operator {
+                Add => checked_signed(lhs.checked_add(rhs), bits, location),
+                Subtract => checked_signed(lhs.checked_sub(rhs), bits, location),
+                Multiply => checked_signed(lhs.checked_mul(rhs), bits, location),
+                WrappingAdd => Ok(wrapping_signed(lhs.wrapping_add(rhs), bits)),
+                WrappingSubtract => Ok(wrapping_signed(lhs.wrapping_sub(rhs), bits)),
+                WrappingMultiply => Ok(wrapping_signed(lhs.wrapping_mul(rhs), bits)),
+                Divide | Modulo if rhs == 0 => Err(InterpreterError::DivisionByZero { location }),
+                Divide => checked_signed(lhs.checked_div(rhs), bits, location),
+                Modulo => checked_signed(lhs.checked_rem(rhs), bits, location),
+                Equal => Ok(Value::Bool(lhs == rhs)),
+                NotEqual => Ok(Value::Bool(lhs != rhs)),
+                Less => Ok(Value::Bool(lhs < rhs)),
+                LessEqual => Ok(Value::Bool(lhs <= rhs)),
+                Greater => Ok(Value::Bool(lhs > rhs)),
+                GreaterEqual => Ok(Value::Bool(lhs >= rhs)),
+                And => Ok(Value::Integer(lhs & rhs, sign, bits)),
+                Or => Ok(Value::Integer(lhs | rhs, sign, bits)),
+                Xor => Ok(Value::Integer(lhs ^ rhs, sign, bits)),
+                ShiftLeft => {
+                    let shifted = u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shl(rhs));
+                    let shifted = shifted.unwrap_or(0) as u128;
+                    Ok(Value::Integer(wrap_integer(shifted, sign, bits), sign, bits))
+                }
+                ShiftRight => {
+                    let shifted = u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shr(rhs));
+                    let shifted = shifted.unwrap_or(if lhs < 0 { -1 } else { 0 });
+                    Ok(Value::Integer(shifted, sign, bits))
+                }
+            },
+            (Value::Bool(lhs), Value::Bool(rhs)) => match operator {
+                And => Ok(Value::Bool(lhs & rhs)),
+                Or => Ok(Value::Bool(lhs | rhs)),
+                Xor | NotEqual => Ok(Value::Bool(lhs ^ rhs)),
+                Equal => Ok(Value::Bool(lhs == rhs)),
+                _ => {
+                    let item = format!("The operator {operator} on booleans");
+                    Err(InterpreterError::Unsupported { item, location })
+                }
+            },
+            (lhs, rhs) => match operator {
+                Equal => Ok(Value::Bool(lhs == rhs)),
+                NotEqual => Ok(Value::Bool(lhs != rhs)),
+                _ => {
+                    let item = format!("The operator {operator} on these operands");
+                    Err(InterpreterError::Unsupported { item, location })
+                }
+            },
+        }
+    }
+
+    /// Evaluates an operator on a type implementing its operator trait by calling the trait
+    /// method. As in the monomorphizer, comparisons are made against the returned `Ordering`.
+    fn evaluate_overloaded_operator(
+        &mut self,
+        infix: HirInfixExpression,
+        id: ExprId,
+        lhs: Value,
+        rhs: Value,
+        location: Location,
+    ) -> IResult<Value> {
+        let operator = infix.operator.kind;
+        let method = self
+            .interner
+            .get_operator_trait_method(operator)
+            .expect("ICE: expected an operator trait for an overloaded operator");
+
+        let operand_type = self.interner.id_type(infix.lhs);
+        let return_type = self.interner.next_type_variable();
+        let function_type = Type::Function(
+            vec![operand_type.clone(), operand_type],
+            Box::new(return_type),
+            Box::new(Type::Unit),
+        );
+
+        let (function, bindings) =
+            self.trait_method_function(id, method, &function_type, location)?;
+        let result = self.call_function(function, bindings, vec![lhs, rhs], location)?;
+
+        let (variant, expected) = match operator {
+            BinaryOpKind::NotEqual => return Ok(Value::Bool(result != Value::Bool(true))),
+            BinaryOpKind::Less => ("Less", true),
+            BinaryOpKind::LessEqual => ("Greater", false),
+            BinaryOpKind::Greater => ("Greater", true),
+            BinaryOpKind::GreaterEqual => ("Less", false),
+            _ => return Ok(result),
+        };
+
+        let ordering = match self.interner.function_meta(&function).return_type().follow_bindings()
+        {
+            Type::Struct(definition, _) => definition.borrow().get_variant(variant),
+            _ => None,
+        };
+        let tag = ordering.expect("ICE: expected `Ord::cmp` to return an `Ordering`");
+        match result {
+            Value::Enum(result_tag, _) => Ok(Value::Bool((result_tag == tag) == expected)),
+            other => unreachable!("expected `Ord::cmp` to return an `Ordering`, found {other:?}"),
+        }
+    }
+
+    /// Returns the function implementing a trait method for the impl selected during type
+    /// checking, along with the bindings for its generics after unifying it with `typ`.
+    fn trait_method_function(
+        &mut self,
+        id: ExprId,
+        method: TraitMethodId,
+        typ: &Type,
+        location: Location,
+    ) -> IResult<(FuncId, TypeBindings)> {
+        let selected = self
+            .interner
+            .get_selected_impl_for_ident(id)
+            .expect("ICE: missing trait impl - should be caught during type checking");
+
+        let impl_id = match selected {
+            TraitImplKind::Normal(impl_id) => Some(impl_id),
+            TraitImplKind::Assumed { object_type, trait_generics } => {
+                match self.interner.lookup_trait_implementation(
+                    &object_type,
+                    method.trait_id,
+                    &trait_generics,
+                ) {
+                    Ok(TraitImplKind::Normal(impl_id)) => Some(impl_id),
+                    _ => None,
+                }
+            }
+        };
+
+        let Some(impl_id) = impl_id else {
+            let item = "Calling trait methods on generic types".to_string();
+            return Err(InterpreterError::Unsupported { item, location });
+        };
+
+        let function =
+            self.interner.get_trait_implementation(impl_id).borrow().methods[method.method_index];
+
+        // The impl may be generic, so its type variables are bound by unifying its
+        // signature with the type the method was used at.
+        let (impl_type, bindings) =
+            self.interner.function_meta(&function).typ.instantiate(self.interner);
+        impl_type
+            .try_unify(&typ.follow_bindings())
+            .expect("ICE: trait impl does not match the type of its method reference");
+        Ok((function, follow_bindings(&bindings)))
+    }
+
+    fn evaluate_index(&mut self, index: HirIndexExpression, id: ExprId) -> IResult<Value> {
+        let collection = self.evaluate(index.collection)?;
+        let index = self.evaluate(index.index)?;
+        let location = self.location(id);
+
+        match collection {
+            Value::Array(mut elements) => {
+                let index = checked_index(&index, elements.len(), location)?;
+                Ok(elements.swap_remove(index))
+            }
+            _ => {
+                let item = "Indexing this value".to_string();
+                Err(InterpreterError::Unsupported { item, location })
+            }
+        }
+    }
+
+    fn evaluate_constructor(&mut self, constructor: HirConstructorExpression) -> IResult<Value> {
+        let definition = constructor.r#type.borrow();
+        let mut fields = try_vecmap(constructor.fields, |(name, field)| {
+            let (_, index) = definition
+                .get_field(&name.0.contents, &constructor.struct_generics)
+                .expect("constructor fields are checked during type checking");
+            Ok((index, self.evaluate(field)?))
+        })?;
+
+        // Fields are evaluated in source order but stored in declaration order
+        fields.sort_by_key(|(index, _)| *index);
+        Ok(Value::Struct(fields.into_iter().map(|(_, field)| field).collect()))
+    }
+
+    fn evaluate_access(&mut self, access: HirMemberAccess, id: ExprId) -> IResult<Value> {
+        match self.evaluate(access.lhs)? {
+            Value::Struct(mut fields) | Value::Tuple(mut fields) => {
+                let index = self.interner.get_field_index(id);
+                Ok(fields.swap_remove(index))
+            }
+            other => unreachable!("member access on a value without fields: {other:?}"),
+        }
+    }
+
+    fn evaluate_call(&mut self, call: HirCallExpression, id: ExprId) -> IResult<Value> {
+        let function = self.evaluate(call.func)?;
+        let arguments = try_vecmap(call.arguments, |argument| self.evaluate(argument))?;
+
+        match function {
+            Value::Function(function, bindings) => {
+                match self.interner.function_meta(&function).kind {
+                    FunctionKind::Normal => {
+                        self.call_function(function, bindings, arguments, call.location)
+                    }
+                    FunctionKind::Builtin => self.call_builtin(function, arguments, id),
+                    FunctionKind::LowLevel | FunctionKind::Oracle => {
+                        let name = self.interner.function_name(&function);
+                        let item = format!("Calling `{name}`");
+                        Err(InterpreterError::Unsupported { item, location: call.location })
+                    }
+                }
+            }
+            Value::Closure(lambda, captures) => {
+                self.call_closure(lambda, captures, arguments, call.location)
+            }
+            other => unreachable!("called a value which is not a function: {other:?}"),
+        }
+    }
+
+    fn call_function(
+        &mut self,
+        function: FuncId,
+        bindings: TypeBindings,
+        arguments: Vec<Value>,
+        location: Location,
+    ) -> IResult<Value> {
+        let meta = self.interner.function_meta(&function);
+        let body = *self.interner.function(&function).as_expr();
+
+        let previous_bindings = perform_bindings(&bindings);
+        let result = self.in_new_call(location, |this| {
+            for ((pattern, _, _), argument) in meta.parameters.0.iter().zip(arguments) {
+                this.define_pattern(pattern, argument);
+            }
+            this.evaluate(body)
+        });
+        undo_bindings(previous_bindings);
+        result
+    }
+
+    fn call_closure(
+        &mut self,
+        lambda: HirLambda,
+        captures: Vec<(DefinitionId, Value)>,
+        arguments: Vec<Value>,
+        location: Location,
+    ) -> IResult<Value> {
+        self.in_new_call(location, |this| {
+            for (id, value) in captures {
+                this.define(id, value);
+            }
+            for ((pattern, _), argument) in lambda.parameters.iter().zip(arguments) {
+                this.define_pattern(pattern, argument);
+            }
+            this.evaluate(lambda.body)
+        })
+    }
+
+    /// Evaluates `body` with a fresh set of scopes, as the body of a function call
+    fn in_new_call(
+        &mut self,
+        location: Location,
+        body: impl FnOnce(&mut Self) -> IResult<Value>,
+    ) -> IResult<Value> {
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(InterpreterError::CallDepthExceeded { location });
+        }
+
+        let caller_scopes = std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+        self.call_depth += 1;
+        let result = body(self);
+        self.call_depth -= 1;
+        self.scopes = caller_scopes;
+
+        match result {
+            Err(InterpreterError::Return(value)) => Ok(value),
+            other => other,
+        }
+    }
+
+    fn call_builtin(
+        &mut self,
+        function: FuncId,
+        mut arguments: Vec<Value>,
+        id: ExprId,
+    ) -> IResult<Value> {
+        let location = self.location(id);
+        let attributes = self.interner.function_attributes(&function);
+        let name = attributes.function.clone().and_then(|attribute| attribute.builtin());
+
+        match (name.as_deref(), arguments.as_mut_slice()) {
+            (Some("array_len"), [Value::Array(elements)]) => {
+                Ok(self.integer_value(FieldElement::from(elements.len() as u128), id))
+            }
+            (Some("assert_constant"), _) => Ok(Value::Unit),
+            (Some("static_assert"), [Value::Bool(predicate), message]) => {
+                if *predicate {
+                    Ok(Value::Unit)
+                } else {
+                    let message = match message {
+                        Value::String(message) => Some(message.to_string()),
+                        _ => None,
+                    };
+                    Err(InterpreterError::FailingConstraint { message, location })
+                }
+            }
+            (Some("as_field"), [value]) => Ok(Value::Field(as_field(value))),
+            (Some("from_field"), [Value::Field(value)]) => {
+                let value = *value;
+                self.cast_field(value, &self.interner.id_type(id), location)
+            }
+            (Some("slice_push_back"), [Value::Array(elements), element]) => {
+                let mut elements = std::mem::take(elements);
+                elements.push(std::mem::replace(element, Value::Unit));
+                Ok(Value::Array(elements))
+            }
+            (Some("slice_push_front"), [Value::Array(elements), element]) => {
+                let mut elements = std::mem::take(elements);
+                elements.insert(0, std::mem::replace(element, Value::Unit));
+                Ok(Value::Array(elements))
+            }
+            (Some("slice_pop_back"), [Value::Array(elements)]) => {
+                let mut elements = std::mem::take(elements);
+                let last = elements.pop().ok_or(InterpreterError::IndexOutOfBounds {
+                    index: 0,
+                    length: 0,
+                    location,
+                })?;
+                Ok(Value::Tuple(vec![Value::Array(elements), last]))
+            }
+            (Some("slice_pop_front"), [Value::Array(elements)]) => {
+                let mut elements = std::mem::take(elements);
+                if elements.is_empty() {
+                    return Err(InterpreterError::IndexOutOfBounds {
+                        index: 0,
+                        length: 0,
+                        location,
+                    });
+                }
+                let first = elements.remove(0);
+                Ok(Value::Tuple(vec![first, Value::Array(elements)]))
+            }
+            (Some("slice_insert"), [Value::Array(elements), index, element]) => {
+                let index = checked_index(index, elements.len() + 1, location)?;
+                let mut elements = std::mem::take(elements);
+                elements.insert(index, std::mem::replace(element, Value::Unit));
+                Ok(Value::Array(elements))
+            }
+            (Some("slice_remove"), [Value::Array(elements), index]) => {
+                let index = checked_index(index, elements.len(), location)?;
+                let mut elements = std::mem::take(elements);
+                let removed = elements.remove(index);
+                Ok(Value::Tuple(vec![Value::Array(elements), removed]))
+            }
+            _ => {
+                let name = self.interner.function_name(&function);
+                let item = format!("Calling `{name}`");
+                Err(InterpreterError::Unsupported { item, location })
+            }
+        }
+    }
+
+    fn evaluate_cast(&mut self, cast: HirCastExpression, id: ExprId) -> IResult<Value> {
+        let value = self.evaluate(cast.lhs)?;
+        let location = self.location(id);
+        // Signed integers are sign extended when cast to wider integer types
+        if let (Value::Integer(value, Signedness::Signed, _), Type::Integer(sign, bits)) =
+            (&value, cast.r#type.follow_bindings())
+        {
+            return Ok(Value::Integer(wrap_integer(*value as u128, sign, bits), sign, bits));
+        }
+        let value = as_field(&value);
+        self.cast_field(value, &cast.r#type, location)
+    }
+
+    /// Converts the given field to a value of the given type, truncating it if needed
+    fn cast_field(&self, value: FieldElement, typ: &Type, location: Location) -> IResult<Value> {
+        match typ.follow_bindings() {
+            Type::FieldElement => Ok(Value::Field(value)),
+            Type::Integer(sign, bits) => {
+                Ok(Value::Integer(wrap_integer(value.to_u128(), sign, bits), sign, bits))
+            }
+            Type::Bool => Ok(Value::Bool(!value.is_zero())),
+            typ => {
+                let item = format!("Casting to {typ}");
+                Err(InterpreterError::Unsupported { item, location })
+            }
+        }
+    }
+
+    fn evaluate_try(&mut self, try_expr: HirTryExpression, id: ExprId) -> IResult<Value> {
+        let value = self.evaluate(try_expr.expression)?;
+        let variants = match self.interner.id_type(try_expr.expression).follow_bindings() {
+            Type::Struct(definition, _) => definition.borrow().try_variants(),
+            _ => None,
+        };
+        let (success, _) = variants.expect("the `?` operator is only used on Option and Result");
+
+        match value {
+            Value::Enum(variant, mut fields) if variant == success => Ok(fields.swap_remove(0)),
+            // The propagated Option or Result has the same variant and payload as the operand
+            failure @ Value::Enum(..) => Err(InterpreterError::Return(failure)),
+            other => {
+                unreachable!("the `?` operator applied to {other:?} at {:?}", self.location(id))
+            }
+        }
+    }
+
+    fn evaluate_if(&mut self, if_expr: HirIfExpression) -> IResult<Value> {
+        match self.evaluate(if_expr.condition)? {
+            Value::Bool(true) => self.evaluate(if_expr.consequence),
+            Value::Bool(false) => match if_expr.alternative {
+                Some(alternative) => self.evaluate(alternative),
+                None => Ok(Value::Unit),
+            },
+            other => unreachable!("if conditions are always booleans, found {other:?}"),
+        }
+    }
+
+    fn evaluate_match(&mut self, match_expr: HirMatchExpression) -> IResult<Value> {
+        let value = self.evaluate(match_expr.expression)?;
+
+        for arm in match_expr.arms {
+            match arm.pattern {
+                HirMatchPattern::Variant { variant, fields, .. } => {
+                    let Value::Enum(tag, payload) = &value else {
+                        unreachable!("variant patterns are only used on enums, found {value:?}")
+                    };
+                    if variant == *tag {
+                        let payload = payload.clone();
+                        return self.in_scope(|this| {
+                            for (pattern, field) in fields.iter().zip(payload) {
+                                this.define_pattern(pattern, field);
+                            }
+                            this.evaluate(arm.body)
+                        });
+                    }
+                }
+                HirMatchPattern::Default(pattern) => {
+                    return self.in_scope(|this| {
+                        this.define_pattern(&pattern, value);
+                        this.evaluate(arm.body)
+                    });
+                }
+                HirMatchPattern::Error(_) => {
+                    unreachable!("comptime code is only evaluated without errors")
+                }
+            }
+        }
+        unreachable!("match exhaustiveness is checked during type checking")
+    }
+
+    fn evaluate_lambda(&mut self, lambda: HirLambda) -> IResult<Value> {
+        let captures = try_vecmap(&lambda.captures, |capture| {
+            let ident = capture.ident;
+            let value = self.lookup(ident.id).ok_or_else(|| {
+                let name = self.interner.definition_name(ident.id).to_string();
+                InterpreterError::NonComptimeVarReferenced { name, location: ident.location }
+            })?;
+            Ok((ident.id, value))
+        })?;
+        Ok(Value::Closure(lambda, captures))
+    }
+
+    fn evaluate_statement(&mut self, statement: StmtId) -> IResult<Value> {
+        match self.interner.statement(&statement) {
+            HirStatement::Let(let_statement) => {
+                let value = self.evaluate(let_statement.expression)?;
+                self.define_pattern(&let_statement.pattern, value);
+                Ok(Value::Unit)
+            }
+            HirStatement::Constrain(constrain) => self.evaluate_constrain(constrain),
+            HirStatement::Assign(assign) => {
+                let value = self.evaluate(assign.expression)?;
+                let location = self.location(assign.expression);
+                self.store_lvalue(assign.lvalue, value, location)?;
+                Ok(Value::Unit)
+            }
+            HirStatement::For(for_loop) => self.evaluate_for(for_loop),
+            HirStatement::While(while_loop) => {
+                let location = self.location(while_loop.condition);
+                let mut iterations = 0;
+                loop {
+                    match self.evaluate(while_loop.condition)? {
+                        Value::Bool(true) => (),
+                        Value::Bool(false) => break,
+                        other => {
+                            unreachable!("loop conditions are always booleans, found {other:?}")
+                        }
+                    }
+                    check_iteration_limit(&mut iterations, while_loop.max_iterations, location)?;
+                    if !self.evaluate_loop_body(while_loop.block)? {
+                        break;
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            HirStatement::Loop(loop_statement) => {
+                let location = self.location(loop_statement.block);
+                let mut iterations = 0;
+                loop {
+                    check_iteration_limit(
+                        &mut iterations,
+                        loop_statement.max_iterations,
+                        location,
+                    )?;
+                    if !self.evaluate_loop_body(loop_statement.block)? {
+                        break;
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            HirStatement::Break => Err(InterpreterError::Break),
+            HirStatement::Continue => Err(InterpreterError::Continue),
+            HirStatement::Return(expression) => {
+                Err(InterpreterError::Return(self.evaluate(expression)?))
+            }
+            HirStatement::Expression(expression) => self.evaluate(expression),
+            HirStatement::Semi(expression) => {
+                self.evaluate(expression)?;
+                Ok(Value::Unit)
+            }
+            HirStatement::Error => unreachable!("comptime code is only evaluated without errors"),
+        }
+    }
+
+    fn evaluate_constrain(&mut self, constrain: HirConstrainStatement) -> IResult<Value> {
+        let HirConstrainStatement(expression, _, message, _) = constrain;
+        match self.evaluate(expression)? {
+            Value::Bool(true) => Ok(Value::Unit),
+            Value::Bool(false) => {
+                let location = self.location(expression);
+                Err(InterpreterError::FailingConstraint { message, location })
+            }
+            other => unreachable!("constraints are always booleans, found {other:?}"),
+        }
+    }
+
+    fn evaluate_for(&mut self, for_loop: HirForStatement) -> IResult<Value> {
+        let start = self.evaluate(for_loop.start_range)?;
+        let end = self.evaluate(for_loop.end_range)?;
+
+        let values: Box<dyn Iterator<Item = Value>> = match (start, end) {
+            (Value::Integer(start, Signedness::Unsigned, bits), Value::Integer(end, _, _)) => {
+                let range = start as u128..end as u128;
+                Box::new(range.map(move |i| Value::Integer(i as i128, Signedness::Unsigned, bits)))
+            }
+            (Value::Integer(start, sign, bits), Value::Integer(end, _, _)) => {
+                Box::new((start..end).map(move |i| Value::Integer(i, sign, bits)))
+            }
+            (start, end) => {
+                let range = as_field(&start).to_u128()..as_field(&end).to_u128();
+                Box::new(range.map(|i| Value::Field(FieldElement::from(i))))
+            }
+        };
+
+        for value in values {
+            let keep_going = self.in_scope(|this| {
+                this.define(for_loop.identifier.id, value);
+                this.evaluate_loop_body(for_loop.block)
+            })?;
+            if !keep_going {
+                break;
+            }
+        }
+        Ok(Value::Unit)
+    }
+
+    /// Evaluates a loop body, returning false if the loop was broken out of
+    fn evaluate_loop_body(&mut self, block: ExprId) -> IResult<bool> {
+        match self.evaluate(block) {
+            Ok(_) | Err(InterpreterError::Continue) => Ok(true),
+            Err(InterpreterError::Break) => Ok(false),
+            Err(other) => Err(other),
+        }
+    }
+
+    fn store_lvalue(&mut self, lvalue: HirLValue, value: Value, location: Location) -> IResult<()> {
+        match lvalue {
+            HirLValue::Ident(ident, _) => {
+                self.assign(ident.id, value);
+                Ok(())
+            }
+            HirLValue::MemberAccess { object, field_index, .. } => {
+                let index = field_index.expect("field indices are set during type checking");
+                match self.evaluate_lvalue(&object)? {
+                    Value::Struct(mut fields) => {
+                        fields[index] = value;
+                        self.store_lvalue(*object, Value::Struct(fields), location)
+                    }
+                    Value::Tuple(mut fields) => {
+                        fields[index] = value;
+                        self.store_lvalue(*object, Value::Tuple(fields), location)
+                    }
+                    other => unreachable!("member access on a value without fields: {other:?}"),
+                }
+            }
+            HirLValue::Index { array, index, .. } => {
+                let index_location = self.location(index);
+                let index = self.evaluate(index)?;
+                match self.evaluate_lvalue(&array)? {
+                    Value::Array(mut elements) => {
+                        let index = checked_index(&index, elements.len(), index_location)?;
+                        elements[index] = value;
+                        self.store_lvalue(*array, Value::Array(elements), location)
+                    }
+                    other => unreachable!("indexed a value which is not an array: {other:?}"),
+                }
+            }
+            HirLValue::Dereference { .. } => {
+                let item = "Mutable references".to_string();
+                Err(InterpreterError::Unsupported { item, location })
+            }
+        }
+    }
+
+    fn evaluate_lvalue(&mut self, lvalue: &HirLValue) -> IResult<Value> {
+        match lvalue {
+            HirLValue::Ident(ident, _) => {
+                Ok(self.lookup(ident.id).expect("assigned to a variable which was never defined"))
+            }
+            HirLValue::MemberAccess { object, field_index, .. } => {
+                let index = field_index.expect("field indices are set during type checking");
+                match self.evaluate_lvalue(object)? {
+                    Value::Struct(mut fields) | Value::Tuple(mut fields) => {
+                        Ok(fields.swap_remove(index))
+                    }
+                    other => unreachable!("member access on a value without fields: {other:?}"),
+                }
+            }
+            HirLValue::Index { array, index, .. } => {
+                let location = self.location(*index);
+                let index = self.evaluate(*index)?;
+                match self.evaluate_lvalue(array)? {
+                    Value::Array(mut elements) => {
+                        let index = checked_index(&index, elements.len(), location)?;
+                        Ok(elements.swap_remove(index))
+                    }
+                    other => unreachable!("indexed a value which is not an array: {other:?}"),
+                }
+            }
+            HirLValue::Dereference { lvalue, .. } => self.evaluate_lvalue(lvalue),
+        }
+    }
+
+    fn location(&self, id: ExprId) -> Location {
+        self.interner.expr_location(&id)
+    }
+}
+
+/// Follows each binding so that they remain valid once any bindings they refer to are undone
+fn follow_bindings(bindings: &TypeBindings) -> TypeBindings {
+    bindings
+        .iter()
+        .map(|(id, (variable, binding))| (*id, (variable.clone(), binding.follow_bindings())))
+        .collect()
+}
+
+/// Binds each generic of a function being called, returning their previous bindings so they
+/// can be restored afterward. Unlike in the monomorphizer, calls may be nested recursively
+/// so a generic may already be bound by an outer call.
+fn perform_bindings(bindings: &TypeBindings) -> Vec<(TypeVariable, TypeBinding)> {
+    let mut previous = Vec::with_capacity(bindings.len());
+    for (id, (variable, binding)) in bindings {
+        // A generic function referenced from within itself may map a generic onto itself
+        if let Type::NamedGeneric(other, _) | Type::TypeVariable(other, _) = binding {
+            if *other.borrow() == TypeBinding::Unbound(*id) {
+                continue;
+            }
+        }
+        previous.push((variable.clone(), variable.borrow().clone()));
+        *variable.borrow_mut() = TypeBinding::Bound(binding.clone());
+    }
+    previous
+}
+
+fn undo_bindings(previous: Vec<(TypeVariable, TypeBinding)>) {
+    for (variable, binding) in previous.into_iter().rev() {
+        *variable.borrow_mut() = binding;
+    }
+}
+
+/// Returns the given signed integer if it is within the range of its type
+fn checked_signed(value: Option<i128>, bits: u32, location: Location) -> IResult<Value> {
+    let (min, max) = (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1);
+    match value {
+        Some(value) if (min..=max).contains(&value) => {
+            Ok(Value::Integer(value, Signedness::Signed, bits))
+        }
+        _ => Err(InterpreterError::IntegerOverflow { location }),
+    }
+}
+
+/// Wraps the given signed integer around to fit within the given bit size
+fn wrapping_signed(value: i128, bits: u32) -> Value {
+    Value::Integer(wrap_integer(value as u128, Signedness::Signed, bits), Signedness::Signed, bits)
+}
+
+/// Returns the given unsigned integer if it fits within the given bit size
+fn checked_unsigned(value: Option<u128>, bits: u32, location: Location) -> IResult<Value> {
+    match value {
+        Some(value) if value <= max_unsigned(bits) => {
+            Ok(Value::Integer(value as i128, Signedness::Unsigned, bits))
+        }
+        _ => Err(InterpreterError::IntegerOverflow { location }),
+    }
+}
+
+/// Evaluates a binary operator on unsigned integers. These are evaluated separately from
+/// signed integers since a `u128` may not fit within an `i128`.
+fn evaluate_unsigned_infix(
+    lhs: u128,
+    rhs: u128,
+    operator: BinaryOpKind,
+    bits: u32,
+    location: Location,
+) -> IResult<Value> {
+    use BinaryOpKind::*;
+    let unsigned = |value: u128| Value::Integer(value as i128, Signedness::Unsigned, bits);
+    match operator {
+        Add => checked_unsigned(lhs.checked_add(rhs), bits, location),
+        Subtract => checked_unsigned(lhs.checked_sub(rhs), bits, location),
+        Multiply => checked_unsigned(lhs.checked_mul(rhs), bits, location),
+        WrappingAdd => Ok(unsigned(lhs.wrapping_add(rhs) & max_unsigned(bits))),
+        WrappingSubtract => Ok(unsigned(lhs.wrapping_sub(rhs) & max_unsigned(bits))),
+        WrappingMultiply => Ok(unsigned(lhs.wrapping_mul(rhs) & max_unsigned(bits))),
+        Divide | Modulo if rhs == 0 => Err(InterpreterError::DivisionByZero { location }),
+        Divide => Ok(unsigned(lhs / rhs)),
+        Modulo => Ok(unsigned(lhs % rhs)),
+        Equal => Ok(Value::Bool(lhs == rhs)),
+        NotEqual => Ok(Value::Bool(lhs != rhs)),
+        Less => Ok(Value::Bool(lhs < rhs)),
+        LessEqual => Ok(Value::Bool(lhs <= rhs)),
+        Greater => Ok(Value::Bool(lhs > rhs)),
+        GreaterEqual => Ok(Value::Bool(lhs >= rhs)),
+        And => Ok(unsigned(lhs & rhs)),
+        Or => Ok(unsigned(lhs | rhs)),
+        Xor => Ok(unsigned(lhs ^ rhs)),
+        ShiftLeft => {
+            let shifted = u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shl(rhs));
+            Ok(unsigned(shifted.unwrap_or(0) & max_unsigned(bits)))
+        }
+        ShiftRight => {
+            let shifted = u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_shr(rhs));
+            Ok(unsigned(shifted.unwrap_or(0)))
+        }
+    }
+}
+
+/// Returns the largest unsigned integer with the given bit size
+fn max_unsigned(bits: u32) -> u128 {
+    u128::MAX >> (128 - bits)
+}
+
+/// Truncates the given bits to an integer of the given type, interpreting them as
+/// two's complement if the type is signed
+fn wrap_integer(value: u128, sign: Signedness, bits: u32) -> i128 {
+    let value = value & max_unsigned(bits);
+    match sign {
+        Signedness::Signed if value >> (bits - 1) == 1 => value as i128 - (1i128 << bits),
+        _ => value as i128,
+    }
+}
+
+/// Converts a primitive value to a field. Signed integers are converted from their
+/// two's complement representation.
+fn as_field(value: &Value) -> FieldElement {
+    match value {
+        Value::Field(value) => *value,
+        Value::Integer(value, _, bits) => FieldElement::from(*value as u128 & max_unsigned(*bits)),
+        Value::Bool(value) => FieldElement::from(*value),
+        other => unreachable!("expected a primitive value, found {other:?}"),
+    }
+}
+
+fn checked_index(index: &Value, length: usize, location: Location) -> IResult<usize> {
+    let index = as_field(index).try_to_u64().map_or(usize::MAX, |index| index as usize);
+    if index < length {
+        Ok(index)
+    } else {
+        Err(InterpreterError::IndexOutOfBounds { index, length, location })
+    }
+}
+
+fn check_iteration_limit(
+    iterations: &mut u64,
+    max_iterations: Option<u64>,
+    location: Location,
+) -> IResult<()> {
+    if let Some(max_iterations) = max_iterations {
+        if *iterations >= max_iterations {
+            return Err(InterpreterError::LoopLimitExceeded { max_iterations, location });
+        }
+    }
+    *iterations += 1;
+    Ok(())
+}
@@ -0,0 +1,13 @@
+//! Evaluation of `comptime` blocks.
+//!
+//! Once every function in a crate has been type checked, each `comptime { ... }` expression is
+//! evaluated by walking its HIR directly. The resulting value is converted back into HIR literals
+//! and constructors which replace the `comptime` expression, so later passes only ever see the
+//! constant it evaluated to.
+mod errors;
+mod interpreter;
+mod value;
+
+pub use errors::InterpreterError;
+pub use interpreter::Interpreter;
+pub use value::Value;
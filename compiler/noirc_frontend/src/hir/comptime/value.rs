@@ -0,0 +1,148 @@
+use std::rc::Rc;
+
+use acvm::FieldElement;
+use iter_extended::try_vecmap;
+use noirc_errors::Location;
+
+use crate::hir_def::expr::{
+    HirArrayLiteral, HirConstructorExpression, HirEnumConstructorExpression, HirExpression,
+    HirLambda, HirLiteral, HirPrefixExpression,
+};
+use crate::hir_def::types::TypeBindings;
+use crate::node_interner::{DefinitionId, ExprId, FuncId, NodeInterner};
+use crate::{Ident, Signedness, Type, UnaryOp};
+
+use super::errors::InterpreterError;
+
+/// A value computed by the comptime interpreter
+#[derive(Debug, Clone)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    Field(FieldElement),
+    /// An integer of the given signedness and bit size. The value is always within its type's range.
+    /// Unsigned integers are stored as their bit pattern since a `u128` may not fit within an `i128`.
+    Integer(i128, Signedness, u32),
+    String(Rc<String>),
+    /// Arrays and slices
+    Array(Vec<Value>),
+    Tuple(Vec<Value>),
+    /// A struct's fields, in the order they were declared
+    Struct(Vec<Value>),
+    /// An enum's variant index along with its payload
+    Enum(usize, Vec<Value>),
+    /// A function along with the bindings for its generics at the point it was referenced
+    Function(FuncId, TypeBindings),
+    Closure(HirLambda, Vec<(DefinitionId, Value)>),
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Unit, Value::Unit) => true,
+            (Value::Bool(lhs), Value::Bool(rhs)) => lhs == rhs,
+            (Value::Field(lhs), Value::Field(rhs)) => lhs == rhs,
+            (Value::Integer(lhs, ..), Value::Integer(rhs, ..)) => lhs == rhs,
+            (Value::String(lhs), Value::String(rhs)) => lhs == rhs,
+            (Value::Array(lhs), Value::Array(rhs))
+            | (Value::Tuple(lhs), Value::Tuple(rhs))
+            | (Value::Struct(lhs), Value::Struct(rhs)) => lhs == rhs,
+            (Value::Enum(lhs_tag, lhs), Value::Enum(rhs_tag, rhs)) => {
+                lhs_tag == rhs_tag && lhs == rhs
+            }
+            (Value::Function(lhs, _), Value::Function(rhs, _)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl Value {
+    /// Converts this value back into a HIR expression of the given type so that it can replace
+    /// the `comptime` expression it was computed from. Any sub-expressions are pushed to the interner.
+    pub(crate) fn into_hir_expression(
+        self,
+        interner: &mut NodeInterner,
+        typ: &Type,
+        location: Location,
+    ) -> Result<HirExpression, InterpreterError> {
+        let expression = match (self, typ) {
+            (Value::Unit, _) => HirExpression::Literal(HirLiteral::Unit),
+            (Value::Bool(value), _) => HirExpression::Literal(HirLiteral::Bool(value)),
+            (Value::Field(value), _) => HirExpression::Literal(HirLiteral::Integer(value)),
+            (Value::Integer(value, Signedness::Signed, _), _) if value < 0 => {
+                let magnitude = FieldElement::from(value.unsigned_abs());
+                let magnitude = HirExpression::Literal(HirLiteral::Integer(magnitude));
+                let rhs = push_expression(interner, magnitude, typ.clone(), location);
+                HirExpression::Prefix(HirPrefixExpression { operator: UnaryOp::Minus, rhs })
+            }
+            (Value::Integer(value, _, _), _) => {
+                HirExpression::Literal(HirLiteral::Integer(FieldElement::from(value as u128)))
+            }
+            (Value::String(value), _) => {
+                HirExpression::Literal(HirLiteral::Str(value.as_ref().clone()))
+            }
+            (Value::Array(elements), Type::Array(_, element_type)) => {
+                let elements = try_vecmap(elements, |element| {
+                    element.into_expression_id(interner, element_type, location)
+                })?;
+                HirExpression::Literal(HirLiteral::Array(HirArrayLiteral::Standard(elements)))
+            }
+            (Value::Tuple(fields), Type::Tuple(field_types)) => {
+                let fields = try_vecmap(fields.into_iter().zip(field_types), |(field, typ)| {
+                    field.into_expression_id(interner, typ, location)
+                })?;
+                HirExpression::Tuple(fields)
+            }
+            (Value::Struct(fields), Type::Struct(definition, generics)) => {
+                let field_types = definition.borrow().get_fields(generics);
+                let fields =
+                    try_vecmap(fields.into_iter().zip(field_types), |(field, (name, typ))| {
+                        let id = field.into_expression_id(interner, &typ, location)?;
+                        Ok((Ident::new(name, location.span), id))
+                    })?;
+                HirExpression::Constructor(HirConstructorExpression {
+                    r#type: definition.clone(),
+                    struct_generics: generics.clone(),
+                    fields,
+                })
+            }
+            (Value::Enum(variant, fields), Type::Struct(definition, generics)) => {
+                let field_types = definition.borrow().get_variant_fields(variant, generics);
+                let arguments = try_vecmap(fields.into_iter().zip(field_types), |(field, typ)| {
+                    field.into_expression_id(interner, &typ, location)
+                })?;
+                HirExpression::EnumConstructor(HirEnumConstructorExpression {
+                    r#type: definition.clone(),
+                    variant,
+                    arguments,
+                    location,
+                })
+            }
+            _ => return Err(InterpreterError::CannotSplice { typ: typ.clone(), location }),
+        };
+        Ok(expression)
+    }
+
+    fn into_expression_id(
+        self,
+        interner: &mut NodeInterner,
+        typ: &Type,
+        location: Location,
+    ) -> Result<ExprId, InterpreterError> {
+        let typ = typ.follow_bindings();
+        let expression = self.into_hir_expression(interner, &typ, location)?;
+        Ok(push_expression(interner, expression, typ, location))
+    }
+}
+
+fn push_expression(
+    interner: &mut NodeInterner,
+    expression: HirExpression,
+    typ: Type,
+    location: Location,
+) -> ExprId {
+    let id = interner.push_expr(expression);
+    interner.push_expr_location(id, location.span, location.file);
+    interner.push_expr_type(&id, typ);
+    id
+}
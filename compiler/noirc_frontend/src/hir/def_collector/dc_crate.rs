@@ -1,7 +1,10 @@
 use super::dc_mod::collect_defs;
 use super::errors::{DefCollectorErrorKind, DuplicateType};
 use crate::graph::CrateId;
-use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleData, ModuleDefId, ModuleId};
+use crate::hir::comptime::{Interpreter, InterpreterError};
+use crate::hir::def_map::{
+    CrateDefMap, LocalModuleId, ModuleData, ModuleDefId, ModuleId, MAIN_FUNCTION,
+};
 use crate::hir::resolution::errors::ResolverError;
 use crate::hir::resolution::import::PathResolutionError;
 use crate::hir::resolution::path_resolver::PathResolver;
@@ -13,15 +16,17 @@ use crate::hir::resolution::{
 use crate::hir::type_check::{type_check_func, TypeCheckError, TypeChecker};
 use crate::hir::Context;
 use crate::hir_def::traits::{Trait, TraitConstant, TraitFunction, TraitImpl, TraitType};
+use crate::lints::Lint;
 use crate::node_interner::{
-    FuncId, NodeInterner, StmtId, StructId, TraitId, TraitImplId, TypeAliasId,
+    ExprId, FuncId, NodeInterner, StmtId, StructId, TraitId, TraitImplId, TypeAliasId,
 };
 
 use crate::parser::{ParserError, SortedModule};
 use crate::{
-    ExpressionKind, Generics, Ident, LetStatement, Literal, NoirFunction, NoirStruct, NoirTrait,
-    NoirTypeAlias, Path, Shared, StructType, TraitItem, Type, TypeBinding, TypeVariableKind,
-    UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType,
+    ArrayLiteral, Expression, ExpressionKind, FunctionVisibility, Generics, Ident, LetStatement,
+    Literal, NoirEnum, NoirFunction, NoirStruct, NoirTrait, NoirTypeAlias, Path, Shared,
+    StructType, TraitItem, Type, TypeBinding, TypeVariable, TypeVariableKind, UnresolvedGenerics,
+    UnresolvedTraitConstraint, UnresolvedType,
 };
 use fm::FileId;
 use iter_extended::vecmap;
@@ -76,6 +81,12 @@ pub struct UnresolvedStruct {
     pub struct_def: NoirStruct,
 }
 
+pub struct UnresolvedEnum {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub enum_def: NoirEnum,
+}
+
 #[derive(Clone)]
 pub struct UnresolvedTrait {
     pub file_id: FileId,
@@ -90,6 +101,7 @@ pub struct UnresolvedTraitImpl {
     pub module_id: LocalModuleId,
     pub trait_id: Option<TraitId>,
     pub trait_path: Path,
+    pub trait_generics: Vec<UnresolvedType>,
     pub object_type: UnresolvedType,
     pub methods: UnresolvedFunctions,
     pub generics: UnresolvedGenerics,
@@ -117,6 +129,7 @@ pub struct DefCollector {
     pub(crate) collected_imports: Vec<ImportDirective>,
     pub(crate) collected_functions: Vec<UnresolvedFunctions>,
     pub(crate) collected_types: BTreeMap<StructId, UnresolvedStruct>,
+    pub(crate) collected_enums: BTreeMap<StructId, UnresolvedEnum>,
     pub(crate) collected_type_aliases: BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
     pub(crate) collected_traits: BTreeMap<TraitId, UnresolvedTrait>,
     pub(crate) collected_globals: Vec<UnresolvedGlobal>,
@@ -130,6 +143,18 @@ pub enum CompilationError {
     DefinitionError(DefCollectorErrorKind),
     ResolverError(ResolverError),
     TypeError(TypeCheckError),
+    InterpreterError(InterpreterError),
+}
+
+impl CompilationError {
+    /// Returns the lint controlling this warning, if it is one
+    pub fn lint(&self) -> Option<Lint> {
+        match self {
+            CompilationError::ResolverError(error) => error.lint(),
+            CompilationError::TypeError(error) => error.lint(),
+            _ => None,
+        }
+    }
 }
 
 impl From<CompilationError> for CustomDiagnostic {
@@ -139,6 +164,7 @@ impl From<CompilationError> for CustomDiagnostic {
             CompilationError::DefinitionError(error) => error.into(),
             CompilationError::ResolverError(error) => error.into(),
             CompilationError::TypeError(error) => error.into(),
+            CompilationError::InterpreterError(error) => error.into(),
         }
     }
 }
@@ -166,6 +192,12 @@ impl From<TypeCheckError> for CompilationError {
     }
 }
 
+impl From<InterpreterError> for CompilationError {
+    fn from(value: InterpreterError) -> Self {
+        CompilationError::InterpreterError(value)
+    }
+}
+
 /// Maps the type and the module id in which the impl is defined to the functions contained in that
 /// impl along with the generics declared on the impl itself. This also contains the Span
 /// of the object_type of the impl, used to issue an error if the object type fails to resolve.
@@ -183,6 +215,7 @@ impl DefCollector {
             collected_imports: vec![],
             collected_functions: vec![],
             collected_types: BTreeMap::new(),
+            collected_enums: BTreeMap::new(),
             collected_type_aliases: BTreeMap::new(),
             collected_traits: BTreeMap::new(),
             collected_impls: HashMap::new(),
@@ -220,6 +253,10 @@ impl DefCollector {
             def_map.extern_prelude.insert(dep.as_name(), module_id);
         }
 
+        // Lints in the root file are reported at the levels configured for the crate
+        let lint_levels = context.crate_graph[crate_id].lint_levels.clone();
+        context.def_interner.lint_levels_mut().set_file_levels(root_file_id, lint_levels);
+
         // At this point, all dependencies are resolved and type checked.
         //
         // It is now possible to collect all of the definitions of this crate.
@@ -242,6 +279,14 @@ impl DefCollector {
         // Add the current crate to the collection of DefMaps
         context.def_maps.insert(crate_id, def_collector.def_map);
 
+        // An import written in a `use` statement of its own is removed by removing the statement
+        let mut imports_per_statement: HashMap<(LocalModuleId, Span), usize> = HashMap::new();
+        for import in &def_collector.collected_imports {
+            if let Some(span) = import.span {
+                *imports_per_statement.entry((import.module_id, span)).or_default() += 1;
+            }
+        }
+
         // Resolve unresolved imports collected from the crate
         let (resolved, unresolved_imports) =
             resolve_imports(crate_id, def_collector.collected_imports, &context.def_maps);
@@ -257,11 +302,26 @@ impl DefCollector {
 
         // Populate module namespaces according to the imports used
         let current_def_map = context.def_maps.get_mut(&crate_id).unwrap();
+        let mut written_imports = Vec::new();
         for resolved_import in resolved {
+            let module = &mut current_def_map.modules[resolved_import.module_scope.0];
+            if let Some(glob_items) = resolved_import.glob_items {
+                // Glob imports never conflict with other items, they are shadowed by them instead
+                for (name, item) in glob_items {
+                    module.import_glob(name, item);
+                }
+                continue;
+            }
+
             let name = resolved_import.name;
+            if let Some(span) = resolved_import.span {
+                let module_scope = resolved_import.module_scope;
+                let is_only_import = imports_per_statement[&(module_scope, span)] == 1;
+                written_imports.push((module_scope, name.clone(), is_only_import.then_some(span)));
+            }
+
             for ns in resolved_import.resolved_namespace.iter_defs() {
-                let result = current_def_map.modules[resolved_import.module_scope.0]
-                    .import(name.clone(), ns);
+                let result = module.import(name.clone(), ns);
 
                 if let Err((first_def, second_def)) = result {
                     let err = DefCollectorErrorKind::Duplicate {
@@ -280,7 +340,7 @@ impl DefCollector {
         // Additionally, we must resolve integer globals before structs since structs may refer to
         // the values of integer globals as numeric generics.
         let (literal_globals, other_globals) =
-            filter_literal_globals(def_collector.collected_globals);
+            order_globals(context, crate_id, def_collector.collected_globals);
 
         let mut resolved_globals = resolve_globals(context, literal_globals, crate_id);
 
@@ -293,6 +353,7 @@ impl DefCollector {
         errors.extend(resolve_traits(context, def_collector.collected_traits, crate_id));
         // Must resolve structs before we resolve globals.
         errors.extend(resolve_structs(context, def_collector.collected_types, crate_id));
+        errors.extend(resolve_enums(context, def_collector.collected_enums, crate_id));
 
         // We must wait to resolve non-integer globals until after we resolve structs since structs
         // globals will need to reference the struct type they're initialized to to ensure they are valid.
@@ -315,6 +376,9 @@ impl DefCollector {
         // over trait methods if there are name conflicts.
         errors.extend(collect_impls(context, crate_id, &def_collector.collected_impls));
 
+        let private_functions =
+            collect_private_functions(context, crate_id, &def_collector.collected_functions);
+
         // Lower each function in the crate. This is now possible since imports have been resolved
         let file_func_ids = resolve_free_functions(
             &mut context.def_interner,
@@ -351,10 +415,79 @@ impl DefCollector {
         errors.extend(type_check_functions(&mut context.def_interner, file_func_ids));
         errors.extend(type_check_functions(&mut context.def_interner, file_method_ids));
         errors.extend(type_check_functions(&mut context.def_interner, file_trait_impls_ids));
+
+        // Every path in the crate has now been resolved, so anything not referred to is unused
+        if !crate_id.is_stdlib() {
+            errors.extend(unused_imports(context, crate_id, written_imports));
+            errors.extend(unused_functions(context, private_functions));
+        }
+
+        // comptime code is only evaluated once the whole crate is known to be well-typed
+        let comptime_expressions = context.def_interner.take_comptime_expressions();
+        let has_errors =
+            errors.iter().any(|(error, _)| CustomDiagnostic::from(error.clone()).is_error());
+        if !has_errors {
+            errors.extend(evaluate_comptime(&mut context.def_interner, comptime_expressions));
+        }
         errors
     }
 }
 
+/// Returns the private free functions of a crate which may go unused. This excludes functions
+/// which are used from outside the program's code, such as `main`, tests and contract functions,
+/// as well as those whose name starts with an underscore.
+fn collect_private_functions(
+    context: &Context,
+    crate_id: CrateId,
+    collected_functions: &[UnresolvedFunctions],
+) -> Vec<(FileId, FuncId, Ident)> {
+    let def_map = &context.def_maps[&crate_id];
+    let mut private_functions = Vec::new();
+    for unresolved_functions in collected_functions {
+        for (module_id, func_id, function) in &unresolved_functions.functions {
+            let may_be_unused = function.def.visibility == FunctionVisibility::Private
+                && function.name() != MAIN_FUNCTION
+                && !function.name().starts_with('_')
+                && !function.attributes().is_test_function()
+                && !def_map.modules[module_id.0].is_contract;
+            if may_be_unused {
+                let name = function.name_ident().clone();
+                private_functions.push((unresolved_functions.file_id, *func_id, name));
+            }
+        }
+    }
+    private_functions
+}
+
+/// Reports each import written in the crate whose name is never looked up in its module.
+fn unused_imports(
+    context: &Context,
+    crate_id: CrateId,
+    imports: Vec<(LocalModuleId, Ident, Option<Span>)>,
+) -> Vec<(CompilationError, FileId)> {
+    let def_map = &context.def_maps[&crate_id];
+    imports
+        .into_iter()
+        .filter(|(module_id, ident, _)| !def_map.modules[module_id.0].is_name_used(ident))
+        .map(|(module_id, ident, statement_span)| {
+            let error = ResolverError::UnusedImport { ident, statement_span };
+            (error.into(), def_map.file_id(module_id))
+        })
+        .collect()
+}
+
+/// Reports each of `functions` which is never referred to.
+fn unused_functions(
+    context: &Context,
+    functions: Vec<(FileId, FuncId, Ident)>,
+) -> Vec<(CompilationError, FileId)> {
+    functions
+        .into_iter()
+        .filter(|(_, func_id, _)| !context.def_interner.is_function_referenced(*func_id))
+        .map(|(file_id, _, ident)| (ResolverError::UnusedFunction { ident }.into(), file_id))
+        .collect()
+}
+
 /// Go through the list of impls and add each function within to the scope
 /// of the module defined by its type.
 fn collect_impls(
@@ -532,6 +665,9 @@ fn collect_trait_impl(
         let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
         resolver.add_generics(&trait_impl.generics);
         let typ = resolver.resolve_type(unresolved_type);
+        for generic in &trait_impl.trait_generics {
+            resolver.resolve_type(generic.clone());
+        }
         errors.extend(take_errors(trait_impl.file_id, resolver));
 
         if let Some(struct_type) = get_struct_type(&typ) {
@@ -613,16 +749,168 @@ fn get_struct_type(typ: &Type) -> Option<&Shared<StructType>> {
     }
 }
 
-/// Separate the globals Vec into two. The first element in the tuple will be the
-/// literal globals, except for arrays, and the second will be all other globals.
-/// We exclude array literals as they can contain complex types
-fn filter_literal_globals(
+/// Orders the given globals such that each is resolved after any other globals its value refers
+/// to, then splits off the globals computed only from literals and other such globals. These may
+/// be used as array lengths, so must be resolved before any types.
+fn order_globals(
+    context: &Context,
+    crate_id: CrateId,
     globals: Vec<UnresolvedGlobal>,
 ) -> (Vec<UnresolvedGlobal>, Vec<UnresolvedGlobal>) {
-    globals.into_iter().partition(|global| match &global.stmt_def.expression.kind {
+    let indices: HashMap<StmtId, usize> =
+        globals.iter().enumerate().map(|(index, global)| (global.stmt_id, index)).collect();
+
+    let mut has_literal_value = Vec::with_capacity(globals.len());
+    let dependencies = vecmap(&globals, |global| {
+        let module = ModuleId { krate: crate_id, local_id: global.module_id };
+        let path_resolver = StandardPathResolver::new(module);
+
+        let is_comptime_function =
+            |path: &Path| match path_resolver.resolve(&context.def_maps, path.clone()) {
+                Ok(ModuleDefId::FunctionId(id)) => {
+                    context.def_interner.function_modifiers(&id).is_comptime
+                }
+                _ => false,
+            };
+        has_literal_value
+            .push(is_literal_expression(&global.stmt_def.expression, &is_comptime_function));
+
+        let mut paths = Vec::new();
+        global_references(&global.stmt_def.expression, &mut paths);
+        paths
+            .into_iter()
+            .filter_map(|path| match path_resolver.resolve(&context.def_maps, path) {
+                Ok(ModuleDefId::GlobalId(id)) => indices.get(&id).copied(),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut order = Vec::with_capacity(globals.len());
+    let mut visited = vec![false; globals.len()];
+    for index in 0..globals.len() {
+        visit_global(index, &dependencies, &mut visited, &mut order);
+    }
+
+    let mut is_literal = vec![false; globals.len()];
+    let mut globals = vecmap(globals, Some);
+    let mut literal_globals = Vec::new();
+    let mut other_globals = Vec::new();
+
+    for index in order {
+        let global = globals[index].take().expect("each global is ordered once");
+        is_literal[index] = has_literal_value[index]
+            && dependencies[index].iter().all(|dependency| is_literal[*dependency]);
+
+        if is_literal[index] {
+            literal_globals.push(global);
+        } else {
+            other_globals.push(global);
+        }
+    }
+    (literal_globals, other_globals)
+}
+
+/// Pushes the given global to `order` after its dependencies. Globals in a cycle are left in
+/// declaration order, for the resolver to report the cycle.
+fn visit_global(
+    index: usize,
+    dependencies: &[Vec<usize>],
+    visited: &mut [bool],
+    order: &mut Vec<usize>,
+) {
+    if !visited[index] {
+        visited[index] = true;
+        for dependency in &dependencies[index] {
+            visit_global(*dependency, dependencies, visited, order);
+        }
+        order.push(index);
+    }
+}
+
+/// True if the given expression is built only from non-array literals, paths to other values,
+/// and calls to `comptime fn`s, e.g. `2 * LEVELS + 1` or `tree_size(LEVELS)`.
+fn is_literal_expression(
+    expression: &Expression,
+    is_comptime_function: &impl Fn(&Path) -> bool,
+) -> bool {
+    let is_literal =
+        |expression: &Expression| is_literal_expression(expression, is_comptime_function);
+    match &expression.kind {
         ExpressionKind::Literal(literal) => !matches!(literal, Literal::Array(_)),
+        ExpressionKind::Variable(_) => true,
+        ExpressionKind::Prefix(prefix) => is_literal(&prefix.rhs),
+        ExpressionKind::Infix(infix) => is_literal(&infix.lhs) && is_literal(&infix.rhs),
+        ExpressionKind::Cast(cast) => is_literal(&cast.lhs),
+        ExpressionKind::Parenthesized(expression) => is_literal(expression),
+        ExpressionKind::Call(call) => match &call.func.kind {
+            ExpressionKind::Variable(path) => {
+                is_comptime_function(path) && call.arguments.iter().all(is_literal)
+            }
+            _ => false,
+        },
         _ => false,
-    })
+    }
+}
+
+/// Collects every path within the given expression which may refer to a global
+fn global_references(expression: &Expression, paths: &mut Vec<Path>) {
+    match &expression.kind {
+        ExpressionKind::Variable(path) => paths.push(path.clone()),
+        ExpressionKind::Literal(Literal::Array(ArrayLiteral::Standard(elements)))
+        | ExpressionKind::Tuple(elements) => {
+            elements.iter().for_each(|element| global_references(element, paths));
+        }
+        ExpressionKind::Literal(Literal::Array(ArrayLiteral::Repeated {
+            repeated_element,
+            length,
+        })) => {
+            global_references(repeated_element, paths);
+            global_references(length, paths);
+        }
+        ExpressionKind::Prefix(prefix) => global_references(&prefix.rhs, paths),
+        ExpressionKind::Infix(infix) => {
+            global_references(&infix.lhs, paths);
+            global_references(&infix.rhs, paths);
+        }
+        ExpressionKind::Cast(cast) => global_references(&cast.lhs, paths),
+        ExpressionKind::Index(index) => {
+            global_references(&index.collection, paths);
+            global_references(&index.index, paths);
+        }
+        ExpressionKind::MemberAccess(access) => global_references(&access.lhs, paths),
+        ExpressionKind::Call(call) => {
+            global_references(&call.func, paths);
+            call.arguments.iter().for_each(|argument| global_references(argument, paths));
+        }
+        ExpressionKind::MethodCall(call) => {
+            global_references(&call.object, paths);
+            call.arguments.iter().for_each(|argument| global_references(argument, paths));
+        }
+        ExpressionKind::Constructor(constructor) => {
+            constructor.fields.iter().for_each(|(_, field)| global_references(field, paths));
+            if let Some(base) = &constructor.base {
+                global_references(base, paths);
+            }
+        }
+        ExpressionKind::If(if_expr) => {
+            global_references(&if_expr.condition, paths);
+            global_references(&if_expr.consequence, paths);
+            if let Some(alternative) = &if_expr.alternative {
+                global_references(alternative, paths);
+            }
+        }
+        ExpressionKind::Try(expression) | ExpressionKind::Parenthesized(expression) => {
+            global_references(expression, paths);
+        }
+        ExpressionKind::Literal(_)
+        | ExpressionKind::Block(_)
+        | ExpressionKind::Match(_)
+        | ExpressionKind::Lambda(_)
+        | ExpressionKind::Comptime(_)
+        | ExpressionKind::Unconstrained(_)
+        | ExpressionKind::Error => (),
+    }
 }
 
 pub struct ResolvedGlobals {
@@ -701,6 +989,22 @@ fn type_check_functions(
         .collect()
 }
 
+/// Evaluates each `comptime` expression, replacing it with the value it evaluated to
+fn evaluate_comptime(
+    interner: &mut NodeInterner,
+    comptime_expressions: Vec<ExprId>,
+) -> Vec<(CompilationError, FileId)> {
+    let mut interpreter = Interpreter::new(interner);
+    comptime_expressions
+        .into_iter()
+        .filter_map(|expr_id| {
+            let error = interpreter.evaluate_comptime(expr_id).err()?;
+            let file = error.location().file;
+            Some((error.into(), file))
+        })
+        .collect()
+}
+
 /// Create the mappings from TypeId -> StructType
 /// so that expressions can access the fields of structs
 fn resolve_structs(
@@ -723,6 +1027,30 @@ fn resolve_structs(
     errors
 }
 
+/// Resolve the payload types of each enum variant. Like struct fields these may refer to
+/// any other type, so this is done only after every type has been collected.
+fn resolve_enums(
+    context: &mut Context,
+    enums: BTreeMap<StructId, UnresolvedEnum>,
+    crate_id: CrateId,
+) -> Vec<(CompilationError, FileId)> {
+    let mut errors: Vec<(CompilationError, FileId)> = vec![];
+    for (type_id, typ) in enums {
+        let file_id = typ.file_id;
+        let path_resolver =
+            StandardPathResolver::new(ModuleId { local_id: typ.module_id, krate: crate_id });
+        let (generics, variants, resolver_errors) =
+            Resolver::new(&mut context.def_interner, &path_resolver, &context.def_maps, file_id)
+                .resolve_enum_variants(typ.enum_def);
+        errors.extend(vecmap(resolver_errors, |err| (err.into(), file_id)));
+        context.def_interner.update_struct(type_id, |enum_def| {
+            enum_def.set_variants(variants);
+            enum_def.generics = generics;
+        });
+    }
+    errors
+}
+
 fn resolve_trait_types(
     _context: &mut Context,
     _crate_id: CrateId,
@@ -745,6 +1073,7 @@ fn resolve_trait_methods(
     trait_id: TraitId,
     crate_id: CrateId,
     unresolved_trait: &UnresolvedTrait,
+    trait_generics: &[(Rc<String>, TypeVariable, Span)],
 ) -> (Vec<TraitFunction>, Vec<(CompilationError, FileId)>) {
     let interner = &mut context.def_interner;
     let def_maps = &mut context.def_maps;
@@ -772,12 +1101,14 @@ fn resolve_trait_methods(
                 Type::TypeVariable(the_trait.self_type_typevar.clone(), TypeVariableKind::Normal);
 
             let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
+            resolver.set_generics(trait_generics.to_vec());
             resolver.add_generics(generics);
             resolver.set_self_type(Some(self_type));
 
             let arguments = vecmap(parameters, |param| resolver.resolve_type(param.1.clone()));
             let resolved_return_type = resolver.resolve_type(return_type.get_type().into_owned());
-            let generics = resolver.get_generics().to_vec();
+            // Only the method's own generics are counted, not those of the trait
+            let generics = resolver.get_generics()[trait_generics.len()..].to_vec();
 
             let name = name.clone();
             let span: Span = name.span();
@@ -810,6 +1141,26 @@ fn resolve_trait_methods(
     (res, resolver_errors)
 }
 
+/// Resolves the generics declared by a trait, e.g. `N` in `trait Serialize<N>`,
+/// giving each a fresh type variable shared by all of the trait's methods.
+fn resolve_trait_generics(
+    context: &mut Context,
+    crate_id: CrateId,
+    unresolved_trait: &UnresolvedTrait,
+) -> (Vec<(Rc<String>, TypeVariable, Span)>, Vec<(CompilationError, FileId)>) {
+    let path_resolver = StandardPathResolver::new(ModuleId {
+        local_id: unresolved_trait.module_id,
+        krate: crate_id,
+    });
+    let file = context.def_maps[&crate_id].file_id(unresolved_trait.module_id);
+
+    let mut resolver =
+        Resolver::new(&mut context.def_interner, &path_resolver, &context.def_maps, file);
+    resolver.add_generics(&unresolved_trait.trait_def.generics);
+    let generics = resolver.get_generics().to_vec();
+    (generics, take_errors(file, resolver))
+}
+
 fn take_errors_filter_self_not_resolved(
     file_id: FileId,
     resolver: Resolver<'_>,
@@ -818,7 +1169,7 @@ fn take_errors_filter_self_not_resolved(
         .take_errors()
         .iter()
         .filter(|resolution_error| match resolution_error {
-            ResolverError::PathResolutionError(PathResolutionError::Unresolved(ident)) => {
+            ResolverError::PathResolutionError(PathResolutionError::Unresolved(ident, _)) => {
                 &ident.0.contents != "Self"
             }
             _ => true,
@@ -845,17 +1196,30 @@ fn resolve_traits(
     let mut res: Vec<(CompilationError, FileId)> = vec![];
     for (trait_id, unresolved_trait) in traits {
         // Resolve order
-        // 1. Trait Types ( Trait constants can have a trait type, therefore types before constants)
+        // 1. Trait Generics ( These can be used anywhere within the trait )
+        let (generics, errors) = resolve_trait_generics(context, crate_id, &unresolved_trait);
+        res.extend(errors);
+        // 2. Trait Types ( Trait constants can have a trait type, therefore types before constants)
         let _ = resolve_trait_types(context, crate_id, &unresolved_trait);
-        // 2. Trait Constants ( Trait's methods can use trait types & constants, therefore they should be after)
+        // 3. Trait Constants ( Trait's methods can use trait types & constants, therefore they should be after)
         let _ = resolve_trait_constants(context, crate_id, &unresolved_trait);
-        // 3. Trait Methods
+        // 4. Trait Methods
         let (methods, errors) =
-            resolve_trait_methods(context, trait_id, crate_id, &unresolved_trait);
+            resolve_trait_methods(context, trait_id, crate_id, &unresolved_trait, &generics);
         res.extend(errors);
         context.def_interner.update_trait(trait_id, |trait_def| {
+            trait_def.set_generics(vecmap(&generics, |(_, typevar, _)| {
+                let TypeBinding::Unbound(id) = *typevar.borrow() else {
+                    unreachable!("trait generics are unbound when resolved")
+                };
+                (id, typevar.clone())
+            }));
             trait_def.set_methods(methods);
         });
+
+        if crate_id.is_stdlib() {
+            context.def_interner.try_add_operator_trait(trait_id);
+        }
     }
     res
 }
@@ -1005,10 +1369,17 @@ fn resolve_trait_impls(
         new_resolver.set_self_type(Some(self_type.clone()));
 
         if let Some(trait_id) = maybe_trait_id {
+            // Any errors in these were already reported when the impl was collected
+            let trait_generics =
+                vecmap(trait_impl.trait_generics, |generic| new_resolver.resolve_type(generic));
+
             check_methods_signatures(
                 &mut new_resolver,
                 &impl_methods,
                 trait_id,
+                trait_impl.file_id,
+                &trait_impl.trait_path,
+                &trait_generics,
                 trait_impl.generics.len(),
                 errors,
             );
@@ -1026,6 +1397,7 @@ fn resolve_trait_impls(
                 file: trait_impl.file_id,
                 where_clause,
                 methods: vecmap(&impl_methods, |(_, func_id)| *func_id),
+                trait_generics,
             });
 
             if let Err((prev_span, prev_file)) = interner.add_trait_implementation(
@@ -1058,6 +1430,9 @@ fn check_methods_signatures(
     resolver: &mut Resolver,
     impl_methods: &Vec<(FileId, FuncId)>,
     trait_id: TraitId,
+    file_id: FileId,
+    trait_path: &Path,
+    trait_generics: &[Type],
     trait_impl_generic_count: usize,
     errors: &mut Vec<(CompilationError, FileId)>,
 ) {
@@ -1068,6 +1443,21 @@ fn check_methods_signatures(
     // Temporarily bind the trait's Self type to self_type so we can type check
     let _ = the_trait.self_type_typevar.borrow_mut().bind_to(self_type.clone(), the_trait.span);
 
+    if the_trait.generics.len() != trait_generics.len() {
+        let error = DefCollectorErrorKind::MismatchTraitImplementationNumTraitGenerics {
+            impl_generic_count: trait_generics.len(),
+            trait_generic_count: the_trait.generics.len(),
+            trait_name: the_trait.name.to_string(),
+            span: trait_path.span(),
+        };
+        errors.push((error.into(), file_id));
+    }
+
+    // Likewise bind each of the trait's generics to the arguments given for them by the impl
+    for ((_, generic), argument) in the_trait.generics.iter().zip(trait_generics) {
+        let _ = generic.borrow_mut().bind_to(argument.clone(), the_trait.span);
+    }
+
     for (file_id, func_id) in impl_methods {
         let impl_method = resolver.interner.function_meta(func_id);
         let func_name = resolver.interner.function_name(func_id).to_owned();
@@ -1146,6 +1536,9 @@ fn check_methods_signatures(
     }
 
     the_trait.self_type_typevar.borrow_mut().unbind(the_trait.self_type_typevar_id);
+    for (id, generic) in &the_trait.generics {
+        generic.borrow_mut().unbind(*id);
+    }
 }
 
 fn resolve_free_functions(
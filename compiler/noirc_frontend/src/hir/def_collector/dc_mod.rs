@@ -1,16 +1,16 @@
 use std::vec;
 
 use acvm::acir::acir_field::FieldOptions;
-use fm::FileId;
+use fm::{FileId, ModuleError};
 use noirc_errors::Location;
 
 use crate::{
     graph::CrateId,
-    hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait},
+    hir::def_collector::dc_crate::{UnresolvedEnum, UnresolvedStruct, UnresolvedTrait},
     node_interner::{FunctionModifiers, TraitId, TypeAliasId},
-    parser::{SortedModule, SortedSubModule},
-    FunctionDefinition, Ident, LetStatement, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl,
-    NoirTypeAlias, TraitImplItem, TraitItem, TypeImpl,
+    parser::{ModuleDeclaration, SortedModule, SortedSubModule},
+    FunctionDefinition, Ident, LetStatement, NoirEnum, NoirFunction, NoirStruct, NoirTrait,
+    NoirTraitImpl, NoirTypeAlias, TraitImplItem, TraitItem, TypeImpl,
 };
 
 use super::{
@@ -18,9 +18,10 @@ use super::{
         CompilationError, DefCollector, UnresolvedFunctions, UnresolvedGlobal, UnresolvedTraitImpl,
         UnresolvedTypeAlias,
     },
+    derive::expand_derives,
     errors::{DefCollectorErrorKind, DuplicateType},
 };
-use crate::hir::def_map::{parse_file, LocalModuleId, ModuleData, ModuleId};
+use crate::hir::def_map::{LocalModuleId, ModuleData, ModuleId};
 use crate::hir::resolution::import::ImportDirective;
 use crate::hir::Context;
 
@@ -36,7 +37,7 @@ struct ModCollector<'a> {
 /// This performs the entirety of the definition collection phase of the name resolution pass.
 pub fn collect_defs(
     def_collector: &mut DefCollector,
-    ast: SortedModule,
+    mut ast: SortedModule,
     file_id: FileId,
     module_id: LocalModuleId,
     crate_id: CrateId,
@@ -44,6 +45,10 @@ pub fn collect_defs(
 ) -> Vec<(CompilationError, FileId)> {
     let mut collector = ModCollector { def_collector, file_id, module_id };
     let mut errors: Vec<(CompilationError, FileId)> = vec![];
+
+    // Expand any `#[derive(...)]` attributes into impls collected along with the rest
+    errors.extend(expand_derives(&mut ast).into_iter().map(|error| (error.into(), file_id)));
+
     // First resolve the module declarations
     for decl in ast.module_decls {
         errors.extend(collector.parse_module_declaration(context, &decl, crate_id));
@@ -57,6 +62,8 @@ pub fn collect_defs(
             module_id: collector.module_id,
             path: import.path,
             alias: import.alias,
+            is_glob: import.is_glob,
+            span: import.span,
         });
     }
 
@@ -66,6 +73,8 @@ pub fn collect_defs(
 
     errors.extend(collector.collect_structs(context, ast.types, crate_id));
 
+    errors.extend(collector.collect_enums(context, ast.enums, crate_id));
+
     errors.extend(collector.collect_type_aliases(context, ast.type_aliases));
 
     errors.extend(collector.collect_functions(context, ast.functions, crate_id));
@@ -124,7 +133,8 @@ impl<'a> ModCollector<'a> {
                 trait_id: None,
             };
 
-            for method in r#impl.methods {
+            for mut method in r#impl.methods {
+                method.def.where_clause.extend(r#impl.where_clause.iter().cloned());
                 let func_id = context.def_interner.push_empty_fn();
                 context.def_interner.push_function(func_id, &method.def, module_id);
                 unresolved_functions.push_fn(self.module_id, func_id, method);
@@ -152,6 +162,8 @@ impl<'a> ModCollector<'a> {
 
             for (_, func_id, noir_function) in &mut unresolved_functions.functions {
                 noir_function.def.where_clause.append(&mut trait_impl.where_clause.clone());
+                // Methods of a trait impl are visible wherever the trait is
+                noir_function.def.visibility = crate::FunctionVisibility::Public;
                 context.def_interner.push_function(*func_id, &noir_function.def, module);
             }
 
@@ -159,6 +171,7 @@ impl<'a> ModCollector<'a> {
                 file_id: self.file_id,
                 module_id: self.module_id,
                 trait_path: trait_name,
+                trait_generics: trait_impl.trait_generics,
                 methods: unresolved_functions,
                 object_type: trait_impl.object_type,
                 generics: trait_impl.impl_generics,
@@ -292,6 +305,51 @@ impl<'a> ModCollector<'a> {
         definition_errors
     }
 
+    /// Collect any enum definitions declared within the ast.
+    /// Returns a vector of errors if any enums were already defined.
+    fn collect_enums(
+        &mut self,
+        context: &mut Context,
+        enums: Vec<NoirEnum>,
+        krate: CrateId,
+    ) -> Vec<(CompilationError, FileId)> {
+        let mut definition_errors = vec![];
+        for enum_definition in enums {
+            let name = enum_definition.name.clone();
+
+            let unresolved = UnresolvedEnum {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                enum_def: enum_definition,
+            };
+
+            // Create the corresponding module for the enum namespace
+            let id = match self.push_child_module(&name, self.file_id, false, false) {
+                Ok(local_id) => context.def_interner.new_enum(&unresolved, krate, local_id),
+                Err(error) => {
+                    definition_errors.push((error.into(), self.file_id));
+                    continue;
+                }
+            };
+
+            // Enums are declared as struct types so that impls and paths resolve the same way
+            let result =
+                self.def_collector.def_map.modules[self.module_id.0].declare_struct(name, id);
+
+            if let Err((first_def, second_def)) = result {
+                let error = DefCollectorErrorKind::Duplicate {
+                    typ: DuplicateType::TypeDefinition,
+                    first_def,
+                    second_def,
+                };
+                definition_errors.push((error.into(), self.file_id));
+            }
+
+            self.def_collector.collected_enums.insert(id, unresolved);
+        }
+        definition_errors
+    }
+
     /// Collect any type aliases definitions declared within the ast.
     /// Returns a vector of errors if any type aliases were already defined.
     fn collect_type_aliases(
@@ -387,6 +445,7 @@ impl<'a> ModCollector<'a> {
                             // TODO(Maddiaa): Investigate trait implementations with attributes see: https://github.com/noir-lang/noir/issues/2629
                             attributes: crate::token::Attributes::empty(),
                             is_unconstrained: false,
+                            is_comptime: false,
                             contract_function_type: None,
                             is_internal: None,
                         };
@@ -478,6 +537,11 @@ impl<'a> ModCollector<'a> {
     ) -> Vec<(CompilationError, FileId)> {
         let mut errors: Vec<(CompilationError, FileId)> = vec![];
         for submodule in submodules {
+            let lint_levels = context.def_interner.lint_levels_mut();
+            for (lint, level) in submodule.attributes.iter().filter_map(|attr| attr.lint_level()) {
+                lint_levels.add_item_level(file_id, submodule.span, lint, level);
+            }
+
             match self.push_child_module(&submodule.name, file_id, true, submodule.is_contract) {
                 Ok(child) => {
                     errors.extend(collect_defs(
@@ -503,21 +567,34 @@ impl<'a> ModCollector<'a> {
     fn parse_module_declaration(
         &mut self,
         context: &mut Context,
-        mod_name: &Ident,
+        mod_decl: &ModuleDeclaration,
         crate_id: CrateId,
     ) -> Vec<(CompilationError, FileId)> {
+        let mod_name = &mod_decl.ident;
         let mut errors: Vec<(CompilationError, FileId)> = vec![];
-        let child_file_id =
-            match context.file_manager.find_module(self.file_id, &mod_name.0.contents) {
-                Ok(child_file_id) => child_file_id,
-                Err(expected_path) => {
-                    let mod_name = mod_name.clone();
-                    let err =
-                        DefCollectorErrorKind::UnresolvedModuleDecl { mod_name, expected_path };
-                    errors.push((err.into(), self.file_id));
-                    return errors;
-                }
-            };
+        let child_file_id = match context
+            .file_manager
+            .find_module(self.file_id, &mod_name.0.contents)
+        {
+            Ok(child_file_id) => child_file_id,
+            Err(error) => {
+                let mod_name = mod_name.clone();
+                let err = match error {
+                    ModuleError::NotFound { file_path, dir_path } => {
+                        DefCollectorErrorKind::UnresolvedModuleDecl {
+                            mod_name,
+                            expected_path: file_path,
+                            alternative_path: dir_path,
+                        }
+                    }
+                    ModuleError::Ambiguous { file_path, dir_path } => {
+                        DefCollectorErrorKind::AmbiguousModuleDecl { mod_name, file_path, dir_path }
+                    }
+                };
+                errors.push((err.into(), self.file_id));
+                return errors;
+            }
+        };
 
         let location = Location { file: self.file_id, span: mod_name.span() };
 
@@ -538,8 +615,14 @@ impl<'a> ModCollector<'a> {
 
         context.visited_files.insert(child_file_id, location);
 
+        // The child file inherits the lint levels in effect where it is declared
+        let lint_levels = context.def_interner.lint_levels_mut();
+        let mut child_levels = lint_levels.levels_at(self.file_id, mod_name.span());
+        child_levels.extend(mod_decl.attributes.iter().filter_map(|attr| attr.lint_level()));
+        lint_levels.set_file_levels(child_file_id, child_levels);
+
         // Parse the AST for the module we just found and then recursively look for it's defs
-        let (ast, parsing_errors) = parse_file(&context.file_manager, child_file_id);
+        let (ast, parsing_errors) = context.parse_file(child_file_id);
         let ast = ast.into_sorted();
 
         errors.extend(
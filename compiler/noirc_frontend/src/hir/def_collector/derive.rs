@@ -0,0 +1,859 @@
+//! Expansion of `#[derive(...)]` attributes on structs.
+//!
+//! Each derive is expanded into ordinary impls which are added to the module before its
+//! definitions are collected, so the generated code is resolved and type checked exactly
+//! like code the user could have written by hand:
+//!
+//! - `Eq` implements `std::cmp::Eq` by comparing each field in turn.
+//! - `Default` implements `std::default::Default` by defaulting each field.
+//! - `Serialize` and `Deserialize` implement `std::serialization::Serialize<N>` and
+//!   `std::serialization::Deserialize<N>`. Both are built on `to_fields` and `from_fields`
+//!   methods which flatten the struct into an array of `Field`s in field declaration order,
+//!   along with the `write_fields` and `read_fields` helpers used to (de)serialize a struct
+//!   nested inside another. These methods are added once even if both traits are derived.
+use acvm::FieldElement;
+use iter_extended::vecmap;
+use noirc_errors::{Span, Spanned};
+
+use crate::hir::def_collector::errors::DefCollectorErrorKind;
+use crate::parser::SortedModule;
+use crate::token::SecondaryAttribute;
+use crate::{
+    ArrayLiteral, AssignStatement, BinaryOpKind, BinaryTypeOperator, BlockExpression,
+    CallExpression, CastExpression, ConstrainKind, ConstrainStatement, ConstructorExpression,
+    Expression, ExpressionKind, ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType,
+    FunctionVisibility, Ident, IndexExpression, InfixExpression, LValue, LetStatement, Literal,
+    MemberAccessExpression, MethodCallExpression, NoirFunction, NoirStruct, NoirTraitImpl, Path,
+    PathKind, Pattern, Statement, StatementKind, TraitBound, TraitImplItem, TypeImpl,
+    UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression,
+};
+
+/// The name of the numeric generic giving the length of the serialized array.
+const SERIALIZED_LEN: &str = "SerializedLen";
+
+/// Expands the `#[derive(...)]` attributes of every struct in the module, adding the
+/// generated impls to the module's impls and trait impls.
+pub(crate) fn expand_derives(module: &mut SortedModule) -> Vec<DefCollectorErrorKind> {
+    let mut errors = Vec::new();
+
+    for structure in &module.types {
+        for attribute in &structure.attributes {
+            let SecondaryAttribute::Derive(traits) = attribute else { continue };
+            let mut serialization_methods_derived = false;
+
+            for name in traits {
+                let mut builder = DeriveBuilder::new(structure, name);
+                let result = match name.as_str() {
+                    "Eq" => builder.derive_eq().map(|r#impl| module.trait_impls.push(r#impl)),
+                    "Default" => {
+                        builder.derive_default().map(|r#impl| module.trait_impls.push(r#impl))
+                    }
+                    "Serialize" | "Deserialize" => {
+                        let methods = if serialization_methods_derived {
+                            Ok(None)
+                        } else {
+                            builder.derive_serialization_methods().map(Some)
+                        };
+                        methods.map(|methods| {
+                            if let Some(methods) = methods {
+                                module.impls.push(methods);
+                                serialization_methods_derived = true;
+                            }
+                            let r#impl = if name == "Serialize" {
+                                builder.derive_serialize()
+                            } else {
+                                builder.derive_deserialize()
+                            };
+                            module.trait_impls.push(r#impl);
+                        })
+                    }
+                    _ => Err(DefCollectorErrorKind::UnknownDerive {
+                        name: name.clone(),
+                        span: structure.name.span(),
+                    }),
+                };
+
+                if let Err(error) = result {
+                    errors.push(error);
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// Builds the AST of a derived impl. Every node is given the span of the struct's name so
+/// that any error in the generated code points at the struct it was derived for.
+struct DeriveBuilder<'a> {
+    structure: &'a NoirStruct,
+    derive: &'a str,
+    span: Span,
+    /// Counter used to give each variable introduced by the generated code a unique name
+    next_id: usize,
+}
+
+impl<'a> DeriveBuilder<'a> {
+    fn new(structure: &'a NoirStruct, derive: &'a str) -> Self {
+        DeriveBuilder { structure, derive, span: structure.name.span(), next_id: 0 }
+    }
+
+    /// ```noir
+    /// impl<T> dep::std::cmp::Eq for Foo<T> where T: dep::std::cmp::Eq {
+    ///     fn eq(self, other: Self) -> bool {
+    ///         let mut equal = true;
+    ///         equal = equal & (self.a == other.a);
+    ///         for i_1 in 0..N {
+    ///             equal = equal & (self.b[i_1] == other.b[i_1]);
+    ///         }
+    ///         equal
+    ///     }
+    /// }
+    /// ```
+    fn derive_eq(&mut self) -> Result<NoirTraitImpl, DefCollectorErrorKind> {
+        let structure = self.structure;
+        let true_value = self.literal(Literal::Bool(true));
+        let mut statements = vec![self.let_statement(
+            self.mutable("equal"),
+            UnresolvedType::unspecified(),
+            true_value,
+        )];
+        for (name, typ) in &structure.fields {
+            let lhs = self.member_access(self.variable("self"), name.clone());
+            let rhs = self.member_access(self.variable("other"), name.clone());
+            self.compare_values(lhs, rhs, typ, &mut statements);
+        }
+
+        let parameters =
+            vec![(self.ident("self"), self.self_type()), (self.ident("other"), self.self_type())];
+        let body = self.block(statements, self.variable("equal"));
+        let eq = self.function("eq", vec![], parameters, body, self.bool_type());
+        Ok(self.trait_impl(&["cmp", "Eq"], eq))
+    }
+
+    /// ```noir
+    /// impl<T> dep::std::default::Default for Foo<T> where T: dep::std::default::Default {
+    ///     fn default() -> Self {
+    ///         Foo { a: 0, b: T::default() }
+    ///     }
+    /// }
+    /// ```
+    fn derive_default(&self) -> Result<NoirTraitImpl, DefCollectorErrorKind> {
+        let fields = self
+            .structure
+            .fields
+            .iter()
+            .map(|(name, typ)| (name.clone(), self.default_value(typ)))
+            .collect();
+        let value = self.constructor(fields);
+
+        let default =
+            self.function("default", vec![], vec![], self.block(vec![], value), self.self_type());
+        Ok(self.trait_impl(&["default", "Default"], default))
+    }
+
+    /// ```noir
+    /// impl<T, SerializedLen> dep::std::serialization::Serialize<SerializedLen> for Foo<T> {
+    ///     fn serialize(self) -> [Field; SerializedLen] {
+    ///         self.to_fields()
+    ///     }
+    /// }
+    /// ```
+    fn derive_serialize(&self) -> NoirTraitImpl {
+        let to_fields = self.method_call(self.variable("self"), "to_fields", vec![]);
+        let parameters = vec![(self.ident("self"), self.self_type())];
+        let body = self.block(vec![], to_fields);
+        let serialize = self.function("serialize", vec![], parameters, body, self.fields_type());
+        self.serialization_trait_impl("Serialize", serialize)
+    }
+
+    /// ```noir
+    /// impl<T, SerializedLen> dep::std::serialization::Deserialize<SerializedLen> for Foo<T> {
+    ///     fn deserialize(fields: [Field; SerializedLen]) -> Self {
+    ///         Foo::from_fields(fields)
+    ///     }
+    /// }
+    /// ```
+    fn derive_deserialize(&self) -> NoirTraitImpl {
+        let from_fields =
+            self.call(self.struct_path(&["from_fields"]), vec![self.variable("fields")]);
+        let parameters = vec![(self.ident("fields"), self.fields_type())];
+        let body = self.block(vec![], from_fields);
+        let deserialize = self.function("deserialize", vec![], parameters, body, self.self_type());
+        self.serialization_trait_impl("Deserialize", deserialize)
+    }
+
+    /// ```noir
+    /// impl<T> Foo<T> {
+    ///     pub fn to_fields<SerializedLen>(self) -> [Field; SerializedLen] { ... }
+    ///     pub fn from_fields<SerializedLen>(fields: [Field; SerializedLen]) -> Self { ... }
+    ///     pub fn write_fields<SerializedLen>(self, mut fields: [Field; SerializedLen], mut offset: Field) -> ([Field; SerializedLen], Field) { ... }
+    ///     pub fn read_fields<SerializedLen>(fields: [Field; SerializedLen], mut offset: Field) -> (Self, Field) { ... }
+    /// }
+    /// ```
+    fn derive_serialization_methods(&mut self) -> Result<TypeImpl, DefCollectorErrorKind> {
+        let methods =
+            vec![self.to_fields(), self.from_fields(), self.write_fields()?, self.read_fields()?];
+
+        Ok(TypeImpl {
+            object_type: self.self_type(),
+            type_span: self.span,
+            generics: self.structure.generics.clone(),
+            where_clause: vec![],
+            methods,
+        })
+    }
+
+    /// ```noir
+    /// let (fields, offset) = self.write_fields([0; SerializedLen], 0);
+    /// assert(offset == SerializedLen);
+    /// fields
+    /// ```
+    fn to_fields(&self) -> NoirFunction {
+        let empty = self.literal(Literal::Array(ArrayLiteral::Repeated {
+            repeated_element: Box::new(self.integer(0)),
+            length: Box::new(self.variable(SERIALIZED_LEN)),
+        }));
+        let written =
+            self.method_call(self.variable("self"), "write_fields", vec![empty, self.integer(0)]);
+        let statements = vec![
+            self.let_tuple(&["fields", "offset"], UnresolvedType::unspecified(), written),
+            self.assert_length(),
+        ];
+
+        let parameters = vec![(self.ident("self"), self.self_type())];
+        let body = self.block(statements, self.variable("fields"));
+        self.public_function("to_fields", parameters, body, self.fields_type())
+    }
+
+    /// ```noir
+    /// let (value, offset): (Self, Field) = Foo::read_fields(fields, 0);
+    /// assert(offset == SerializedLen);
+    /// value
+    /// ```
+    fn from_fields(&self) -> NoirFunction {
+        let read = self.call(
+            self.struct_path(&["read_fields"]),
+            vec![self.variable("fields"), self.integer(0)],
+        );
+        let typ = self.tuple_type(vec![self.self_type(), self.field_type()]);
+        let statements =
+            vec![self.let_tuple(&["value", "offset"], typ, read), self.assert_length()];
+
+        let parameters = vec![(self.ident("fields"), self.fields_type())];
+        let body = self.block(statements, self.variable("value"));
+        self.public_function("from_fields", parameters, body, self.self_type())
+    }
+
+    fn write_fields(&mut self) -> Result<NoirFunction, DefCollectorErrorKind> {
+        let structure = self.structure;
+        let mut statements = Vec::new();
+        for (name, typ) in &structure.fields {
+            let value = self.member_access(self.variable("self"), name.clone());
+            self.write_value(value, typ, name, &mut statements)?;
+        }
+
+        let parameters = vec![
+            (self.ident("self"), self.self_type()),
+            (self.ident("fields"), self.fields_type()),
+            (self.ident("offset"), self.field_type()),
+        ];
+        let result = self.tuple(vec![self.variable("fields"), self.variable("offset")]);
+        let return_type = self.tuple_type(vec![self.fields_type(), self.field_type()]);
+
+        let mut function = self.public_function(
+            "write_fields",
+            parameters,
+            self.block(statements, result),
+            return_type,
+        );
+        self.make_parameters_mutable(&mut function, &["fields", "offset"]);
+        Ok(function)
+    }
+
+    fn read_fields(&mut self) -> Result<NoirFunction, DefCollectorErrorKind> {
+        let mut statements = Vec::new();
+        let structure = self.structure;
+        let mut fields = Vec::new();
+        for (name, typ) in &structure.fields {
+            let value = self.read_value(typ, name, &mut statements)?;
+            fields.push((name.clone(), value));
+        }
+
+        let parameters = vec![
+            (self.ident("fields"), self.fields_type()),
+            (self.ident("offset"), self.field_type()),
+        ];
+        let result = self.tuple(vec![self.constructor(fields), self.variable("offset")]);
+        let return_type = self.tuple_type(vec![self.self_type(), self.field_type()]);
+
+        let mut function = self.public_function(
+            "read_fields",
+            parameters,
+            self.block(statements, result),
+            return_type,
+        );
+        self.make_parameters_mutable(&mut function, &["offset"]);
+        Ok(function)
+    }
+
+    /// Pushes the statements writing `value`, of type `typ`, into `fields` at `offset`.
+    fn write_value(
+        &mut self,
+        value: Expression,
+        typ: &UnresolvedType,
+        field: &Ident,
+        statements: &mut Vec<Statement>,
+    ) -> Result<(), DefCollectorErrorKind> {
+        match &typ.typ {
+            UnresolvedTypeData::FieldElement => self.write_field(value, statements),
+            UnresolvedTypeData::Integer(..) | UnresolvedTypeData::Bool => {
+                let value = self.cast(value, UnresolvedTypeData::FieldElement);
+                self.write_field(value, statements);
+            }
+            UnresolvedTypeData::Unit => (),
+            UnresolvedTypeData::Tuple(elements) => {
+                for (i, element) in elements.iter().enumerate() {
+                    let value = self.member_access(value.clone(), self.ident(&i.to_string()));
+                    self.write_value(value, element, field, statements)?;
+                }
+            }
+            UnresolvedTypeData::Array(Some(length), element) => {
+                let index = self.fresh_name("i");
+                let element_value = self.index(value, self.variable(&index));
+
+                let mut body = Vec::new();
+                self.write_value(element_value, element, field, &mut body)?;
+                statements.push(self.for_loop(&index, self.type_expression(length), body));
+            }
+            UnresolvedTypeData::Named(..) if !self.is_generic(typ) => {
+                // let (fields_1, offset_1) = value.write_fields(fields, offset);
+                // fields = fields_1;
+                // offset = offset_1;
+                let new_fields = self.fresh_name("fields");
+                let new_offset = self.fresh_name("offset");
+                let arguments = vec![self.variable("fields"), self.variable("offset")];
+                let written = self.method_call(value, "write_fields", arguments);
+
+                let unspecified = UnresolvedType::unspecified();
+                statements.push(self.let_tuple(&[&new_fields, &new_offset], unspecified, written));
+                statements.push(self.assign("fields", self.variable(&new_fields)));
+                statements.push(self.assign("offset", self.variable(&new_offset)));
+            }
+            _ => return Err(self.unsupported_field(field, typ)),
+        }
+        Ok(())
+    }
+
+    /// Pushes the statements reading a value of type `typ` from `fields` at `offset` and
+    /// returns an expression evaluating to the value read.
+    fn read_value(
+        &mut self,
+        typ: &UnresolvedType,
+        field: &Ident,
+        statements: &mut Vec<Statement>,
+    ) -> Result<Expression, DefCollectorErrorKind> {
+        let value = match &typ.typ {
+            UnresolvedTypeData::FieldElement => self.read_field(statements),
+            UnresolvedTypeData::Integer(..) => {
+                let value = self.read_field(statements);
+                self.cast(value, typ.typ.clone())
+            }
+            UnresolvedTypeData::Bool => {
+                let value = self.read_field(statements);
+                self.infix(value, BinaryOpKind::NotEqual, self.integer(0))
+            }
+            UnresolvedTypeData::Unit => self.literal(Literal::Unit),
+            UnresolvedTypeData::Tuple(elements) => {
+                let mut values = Vec::new();
+                for element in elements {
+                    values.push(self.read_value(element, field, statements)?);
+                }
+                self.tuple(values)
+            }
+            UnresolvedTypeData::Array(Some(length), element) => {
+                // let mut array_1: [T; N] = dep::std::unsafe::zeroed();
+                // for i_2 in 0..N {
+                //     array_1[i_2] = <element read>;
+                // }
+                let array = self.fresh_name("array");
+                let index = self.fresh_name("i");
+                let zeroed = self.call(self.std_path(&["unsafe", "zeroed"]), vec![]);
+                statements.push(self.let_statement(self.mutable(&array), typ.clone(), zeroed));
+
+                let mut body = Vec::new();
+                let element_value = self.read_value(element, field, &mut body)?;
+                let lvalue = LValue::Index {
+                    array: Box::new(LValue::Ident(self.ident(&array))),
+                    index: self.variable(&index),
+                };
+                body.push(self.statement(StatementKind::Assign(AssignStatement {
+                    lvalue,
+                    expression: element_value,
+                })));
+
+                statements.push(self.for_loop(&index, self.type_expression(length), body));
+                self.variable(&array)
+            }
+            UnresolvedTypeData::Named(path, _) if !self.is_generic(typ) => {
+                // let (value_1, offset_1): (T, Field) = T::read_fields(fields, offset);
+                // offset = offset_1;
+                let value = self.fresh_name("value");
+                let new_offset = self.fresh_name("offset");
+
+                let mut function = path.clone();
+                function.segments.push(self.ident("read_fields"));
+                let arguments = vec![self.variable("fields"), self.variable("offset")];
+                let read = self.call(self.path_expression(function), arguments);
+
+                let read_type = self.tuple_type(vec![typ.clone(), self.field_type()]);
+                statements.push(self.let_tuple(&[&value, &new_offset], read_type, read));
+                statements.push(self.assign("offset", self.variable(&new_offset)));
+                self.variable(&value)
+            }
+            _ => return Err(self.unsupported_field(field, typ)),
+        };
+        Ok(value)
+    }
+
+    /// Pushes the statements and-ing whether `lhs` and `rhs`, of type `typ`, are equal into
+    /// `equal`. Arrays and tuples are compared element-wise since `==` does not support arrays
+    /// of structs or tuples.
+    fn compare_values(
+        &mut self,
+        lhs: Expression,
+        rhs: Expression,
+        typ: &UnresolvedType,
+        statements: &mut Vec<Statement>,
+    ) {
+        match &typ.typ {
+            UnresolvedTypeData::Unit => (),
+            UnresolvedTypeData::Tuple(elements) => {
+                for (i, element) in elements.iter().enumerate() {
+                    let lhs = self.member_access(lhs.clone(), self.ident(&i.to_string()));
+                    let rhs = self.member_access(rhs.clone(), self.ident(&i.to_string()));
+                    self.compare_values(lhs, rhs, element, statements);
+                }
+            }
+            UnresolvedTypeData::Array(Some(length), element) => {
+                let index = self.fresh_name("i");
+                let lhs = self.index(lhs, self.variable(&index));
+                let rhs = self.index(rhs, self.variable(&index));
+
+                let mut body = Vec::new();
+                self.compare_values(lhs, rhs, element, &mut body);
+                statements.push(self.for_loop(&index, self.type_expression(length), body));
+            }
+            _ => {
+                let equal = self.infix(lhs, BinaryOpKind::Equal, rhs);
+                let equal = self.infix(self.variable("equal"), BinaryOpKind::And, equal);
+                statements.push(self.assign("equal", equal));
+            }
+        }
+    }
+
+    /// Returns an expression evaluating to the default value of `typ`.
+    fn default_value(&self, typ: &UnresolvedType) -> Expression {
+        match &typ.typ {
+            UnresolvedTypeData::FieldElement | UnresolvedTypeData::Integer(..) => self.integer(0),
+            UnresolvedTypeData::Bool => self.literal(Literal::Bool(false)),
+            UnresolvedTypeData::Unit => self.literal(Literal::Unit),
+            UnresolvedTypeData::Tuple(elements) => {
+                self.tuple(elements.iter().map(|element| self.default_value(element)).collect())
+            }
+            UnresolvedTypeData::Array(Some(length), element) => {
+                self.literal(Literal::Array(ArrayLiteral::Repeated {
+                    repeated_element: Box::new(self.default_value(element)),
+                    length: Box::new(self.type_expression(length)),
+                }))
+            }
+            UnresolvedTypeData::Named(path, _) => {
+                let mut function = path.clone();
+                function.segments.push(self.ident("default"));
+                self.call(self.path_expression(function), vec![])
+            }
+            _ => self.call(self.std_path(&["unsafe", "zeroed"]), vec![]),
+        }
+    }
+
+    /// Builds `impl<generics> dep::std::<trait_path> for Self where ... { method }`, requiring
+    /// each generic used as a field type to implement the same trait.
+    fn trait_impl(&self, trait_path: &[&str], method: NoirFunction) -> NoirTraitImpl {
+        let trait_name = self.std_path(trait_path);
+        let where_clause = self
+            .type_generics()
+            .into_iter()
+            .map(|generic| UnresolvedTraitConstraint {
+                typ: self.named_type(Path::from_ident(generic), vec![]),
+                trait_bound: TraitBound {
+                    trait_path: trait_name.clone(),
+                    trait_id: None,
+                    trait_generics: vec![],
+                },
+            })
+            .collect();
+
+        NoirTraitImpl {
+            impl_generics: self.structure.generics.clone(),
+            trait_name,
+            trait_generics: vec![],
+            object_type: self.self_type(),
+            where_clause,
+            items: vec![TraitImplItem::Function(method)],
+        }
+    }
+
+    /// Builds the impl of a trait in `std::serialization`, which is generic over the length
+    /// of the serialized array as well as the struct's own generics.
+    fn serialization_trait_impl(&self, trait_name: &str, method: NoirFunction) -> NoirTraitImpl {
+        let mut r#impl = self.trait_impl(&["serialization", trait_name], method);
+        r#impl.impl_generics.push(self.ident(SERIALIZED_LEN));
+        let length = self.named_type(Path::from_ident(self.ident(SERIALIZED_LEN)), vec![]);
+        r#impl.trait_generics = vec![length];
+        r#impl
+    }
+
+    /// The struct's generics which are used as the type of a field, as opposed to only
+    /// within array lengths.
+    fn type_generics(&self) -> Vec<Ident> {
+        let mut generics = Vec::new();
+        for (_, typ) in &self.structure.fields {
+            self.collect_type_generics(typ, &mut generics);
+        }
+        self.structure
+            .generics
+            .iter()
+            .filter(|generic| generics.contains(generic))
+            .cloned()
+            .collect()
+    }
+
+    fn collect_type_generics(&self, typ: &UnresolvedType, generics: &mut Vec<Ident>) {
+        match &typ.typ {
+            UnresolvedTypeData::Named(path, args) => {
+                if self.is_generic(typ) {
+                    generics.push(path.segments[0].clone());
+                }
+                for arg in args {
+                    self.collect_type_generics(arg, generics);
+                }
+            }
+            UnresolvedTypeData::Array(_, element)
+            | UnresolvedTypeData::MutableReference(element) => {
+                self.collect_type_generics(element, generics);
+            }
+            UnresolvedTypeData::Tuple(elements) => {
+                for element in elements {
+                    self.collect_type_generics(element, generics);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// True if `typ` is one of the struct's generics
+    fn is_generic(&self, typ: &UnresolvedType) -> bool {
+        match &typ.typ {
+            UnresolvedTypeData::Named(path, args) => {
+                path.kind == PathKind::Plain
+                    && path.segments.len() == 1
+                    && args.is_empty()
+                    && self.structure.generics.contains(&path.segments[0])
+            }
+            _ => false,
+        }
+    }
+
+    fn unsupported_field(&self, field: &Ident, typ: &UnresolvedType) -> DefCollectorErrorKind {
+        DefCollectorErrorKind::UnsupportedDeriveField {
+            derive: self.derive.to_string(),
+            field: field.clone(),
+            typ: typ.clone(),
+        }
+    }
+
+    fn fresh_name(&mut self, name: &str) -> String {
+        self.next_id += 1;
+        format!("{name}_{}", self.next_id)
+    }
+
+    /// Pushes `fields[offset] = value; offset += 1;`
+    fn write_field(&self, value: Expression, statements: &mut Vec<Statement>) {
+        let lvalue = LValue::Index {
+            array: Box::new(LValue::Ident(self.ident("fields"))),
+            index: self.variable("offset"),
+        };
+        let write = AssignStatement { lvalue, expression: value };
+        statements.push(self.statement(StatementKind::Assign(write)));
+        statements.push(self.increment_offset());
+    }
+
+    /// Pushes `let value_1 = fields[offset]; offset += 1;` and returns `value_1`
+    fn read_field(&mut self, statements: &mut Vec<Statement>) -> Expression {
+        let name = self.fresh_name("value");
+        let value = self.index(self.variable("fields"), self.variable("offset"));
+        statements.push(self.let_statement(
+            self.pattern(&name),
+            UnresolvedType::unspecified(),
+            value,
+        ));
+        statements.push(self.increment_offset());
+        self.variable(&name)
+    }
+
+    fn increment_offset(&self) -> Statement {
+        let incremented = self.infix(self.variable("offset"), BinaryOpKind::Add, self.integer(1));
+        self.assign("offset", incremented)
+    }
+
+    /// `assert(offset == SerializedLen);`
+    fn assert_length(&self) -> Statement {
+        let condition =
+            self.infix(self.variable("offset"), BinaryOpKind::Equal, self.variable(SERIALIZED_LEN));
+        self.statement(StatementKind::Constrain(ConstrainStatement(
+            condition,
+            None,
+            ConstrainKind::Assert,
+        )))
+    }
+
+    fn make_parameters_mutable(&self, function: &mut NoirFunction, names: &[&str]) {
+        for parameter in &mut function.def.parameters {
+            if let Pattern::Identifier(name) = &parameter.pattern {
+                if names.contains(&name.0.contents.as_str()) {
+                    parameter.pattern = self.mutable(&name.0.contents);
+                }
+            }
+        }
+    }
+
+    fn function(
+        &self,
+        name: &str,
+        generics: Vec<Ident>,
+        parameters: Vec<(Ident, UnresolvedType)>,
+        body: BlockExpression,
+        return_type: UnresolvedType,
+    ) -> NoirFunction {
+        let mut def = FunctionDefinition::normal(
+            &self.ident(name),
+            &generics,
+            &parameters,
+            &body,
+            &[],
+            &FunctionReturnType::Ty(return_type),
+        );
+        def.span = self.span;
+        NoirFunction::normal(def)
+    }
+
+    fn public_function(
+        &self,
+        name: &str,
+        parameters: Vec<(Ident, UnresolvedType)>,
+        body: BlockExpression,
+        return_type: UnresolvedType,
+    ) -> NoirFunction {
+        let generics = vec![self.ident(SERIALIZED_LEN)];
+        let mut function = self.function(name, generics, parameters, body, return_type);
+        function.def.visibility = FunctionVisibility::Public;
+        function
+    }
+
+    fn block(&self, mut statements: Vec<Statement>, result: Expression) -> BlockExpression {
+        statements.push(self.statement(StatementKind::Expression(result)));
+        BlockExpression(statements)
+    }
+
+    fn for_loop(&self, index: &str, end: Expression, body: Vec<Statement>) -> Statement {
+        let block = self.block(body, self.literal(Literal::Unit));
+        self.statement(StatementKind::For(ForLoopStatement {
+            identifier: self.ident(index),
+            range: ForRange::Range(self.integer(0), end),
+            block: self.expression(ExpressionKind::Block(block)),
+            max_iterations: None,
+            span: self.span,
+        }))
+    }
+
+    fn let_statement(
+        &self,
+        pattern: Pattern,
+        r#type: UnresolvedType,
+        value: Expression,
+    ) -> Statement {
+        self.statement(StatementKind::Let(LetStatement { pattern, r#type, expression: value }))
+    }
+
+    fn let_tuple(&self, names: &[&str], r#type: UnresolvedType, value: Expression) -> Statement {
+        let patterns = names.iter().map(|name| self.pattern(name)).collect();
+        self.let_statement(Pattern::Tuple(patterns, self.span), r#type, value)
+    }
+
+    fn assign(&self, name: &str, value: Expression) -> Statement {
+        self.statement(StatementKind::Assign(AssignStatement {
+            lvalue: LValue::Ident(self.ident(name)),
+            expression: value,
+        }))
+    }
+
+    fn statement(&self, kind: StatementKind) -> Statement {
+        Statement { kind, span: self.span }
+    }
+
+    /// Converts an array length to the equivalent expression
+    fn type_expression(&self, length: &UnresolvedTypeExpression) -> Expression {
+        match length {
+            UnresolvedTypeExpression::Variable(path) => self.path_expression(path.clone()),
+            UnresolvedTypeExpression::Constant(value, _) => self.integer(*value as u128),
+            UnresolvedTypeExpression::BinaryOperation(lhs, operator, rhs, _) => {
+                let operator = match operator {
+                    BinaryTypeOperator::Addition => BinaryOpKind::Add,
+                    BinaryTypeOperator::Subtraction => BinaryOpKind::Subtract,
+                    BinaryTypeOperator::Multiplication => BinaryOpKind::Multiply,
+                    BinaryTypeOperator::Division => BinaryOpKind::Divide,
+                    BinaryTypeOperator::Modulo => BinaryOpKind::Modulo,
+                };
+                self.infix(self.type_expression(lhs), operator, self.type_expression(rhs))
+            }
+            UnresolvedTypeExpression::Call(function, arguments, _) => {
+                let arguments = vecmap(arguments, |argument| self.type_expression(argument));
+                self.call(self.path_expression(function.clone()), arguments)
+            }
+        }
+    }
+
+    fn constructor(&self, fields: Vec<(Ident, Expression)>) -> Expression {
+        self.expression(ExpressionKind::Constructor(Box::new(ConstructorExpression {
+            type_name: Path::from_ident(self.structure.name.clone()),
+            fields,
+            base: None,
+        })))
+    }
+
+    fn infix(&self, lhs: Expression, operator: BinaryOpKind, rhs: Expression) -> Expression {
+        let operator = Spanned::from(self.span, operator);
+        self.expression(ExpressionKind::Infix(Box::new(InfixExpression { lhs, operator, rhs })))
+    }
+
+    fn cast(&self, lhs: Expression, typ: UnresolvedTypeData) -> Expression {
+        let r#type = UnresolvedType { typ, span: Some(self.span) };
+        self.expression(ExpressionKind::Cast(Box::new(CastExpression { lhs, r#type })))
+    }
+
+    fn index(&self, collection: Expression, index: Expression) -> Expression {
+        self.expression(ExpressionKind::Index(Box::new(IndexExpression { collection, index })))
+    }
+
+    fn member_access(&self, lhs: Expression, rhs: Ident) -> Expression {
+        self.expression(ExpressionKind::MemberAccess(Box::new(MemberAccessExpression { lhs, rhs })))
+    }
+
+    fn method_call(
+        &self,
+        object: Expression,
+        name: &str,
+        arguments: Vec<Expression>,
+    ) -> Expression {
+        let method_name = self.ident(name);
+        self.expression(ExpressionKind::MethodCall(Box::new(MethodCallExpression {
+            object,
+            method_name,
+            arguments,
+        })))
+    }
+
+    fn call(&self, func: Expression, arguments: Vec<Expression>) -> Expression {
+        let func = Box::new(func);
+        self.expression(ExpressionKind::Call(Box::new(CallExpression { func, arguments })))
+    }
+
+    fn tuple(&self, elements: Vec<Expression>) -> Expression {
+        self.expression(ExpressionKind::Tuple(elements))
+    }
+
+    fn integer(&self, value: u128) -> Expression {
+        self.expression(ExpressionKind::integer(FieldElement::from(value)))
+    }
+
+    fn literal(&self, literal: Literal) -> Expression {
+        self.expression(ExpressionKind::Literal(literal))
+    }
+
+    fn variable(&self, name: &str) -> Expression {
+        self.path_expression(Path::from_ident(self.ident(name)))
+    }
+
+    fn path_expression(&self, path: Path) -> Expression {
+        self.expression(ExpressionKind::Variable(path))
+    }
+
+    fn expression(&self, kind: ExpressionKind) -> Expression {
+        Expression::new(kind, self.span)
+    }
+
+    /// `Foo::<segments>`
+    fn struct_path(&self, segments: &[&str]) -> Expression {
+        let mut path = Path::from_ident(self.structure.name.clone());
+        path.segments.extend(segments.iter().map(|segment| self.ident(segment)));
+        self.path_expression(path)
+    }
+
+    /// `dep::std::<segments>`
+    fn std_path(&self, segments: &[&str]) -> Path {
+        let mut path = Path::from_ident(self.ident("std"));
+        path.kind = PathKind::Dep;
+        path.segments.extend(segments.iter().map(|segment| self.ident(segment)));
+        path
+    }
+
+    fn pattern(&self, name: &str) -> Pattern {
+        Pattern::Identifier(self.ident(name))
+    }
+
+    fn mutable(&self, name: &str) -> Pattern {
+        Pattern::Mutable(Box::new(self.pattern(name)), self.span)
+    }
+
+    fn ident(&self, name: &str) -> Ident {
+        Ident::new(name.to_string(), self.span)
+    }
+
+    /// `Foo<generics>`
+    fn self_type(&self) -> UnresolvedType {
+        let generics = self
+            .structure
+            .generics
+            .iter()
+            .map(|generic| self.named_type(Path::from_ident(generic.clone()), vec![]))
+            .collect();
+        self.named_type(Path::from_ident(self.structure.name.clone()), generics)
+    }
+
+    /// `[Field; SerializedLen]`
+    fn fields_type(&self) -> UnresolvedType {
+        let length =
+            UnresolvedTypeExpression::Variable(Path::from_ident(self.ident(SERIALIZED_LEN)));
+        self.make_type(UnresolvedTypeData::Array(Some(length), Box::new(self.field_type())))
+    }
+
+    fn field_type(&self) -> UnresolvedType {
+        self.make_type(UnresolvedTypeData::FieldElement)
+    }
+
+    fn bool_type(&self) -> UnresolvedType {
+        self.make_type(UnresolvedTypeData::Bool)
+    }
+
+    fn tuple_type(&self, elements: Vec<UnresolvedType>) -> UnresolvedType {
+        self.make_type(UnresolvedTypeData::Tuple(elements))
+    }
+
+    fn named_type(&self, path: Path, generics: Vec<UnresolvedType>) -> UnresolvedType {
+        self.make_type(UnresolvedTypeData::Named(path, generics))
+    }
+
+    fn make_type(&self, typ: UnresolvedTypeData) -> UnresolvedType {
+        UnresolvedType { typ, span: Some(self.span) }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::hir::resolution::import::PathResolutionError;
 use crate::Ident;
 use crate::Path;
+use crate::UnresolvedType;
 
+use noirc_errors::error_codes as codes;
 use noirc_errors::CustomDiagnostic as Diagnostic;
 use noirc_errors::FileDiagnostic;
 use noirc_errors::Span;
@@ -28,7 +30,9 @@ pub enum DefCollectorErrorKind {
     #[error("duplicate {typ} found in namespace")]
     Duplicate { typ: DuplicateType, first_def: Ident, second_def: Ident },
     #[error("unresolved import")]
-    UnresolvedModuleDecl { mod_name: Ident, expected_path: String },
+    UnresolvedModuleDecl { mod_name: Ident, expected_path: String, alternative_path: String },
+    #[error("ambiguous module declaration")]
+    AmbiguousModuleDecl { mod_name: Ident, file_path: String, dir_path: String },
     #[error("path resolution error")]
     PathResolutionError(PathResolutionError),
     #[error("Non-struct type used in impl")]
@@ -57,6 +61,13 @@ pub enum DefCollectorErrorKind {
         method_name: String,
         span: Span,
     },
+    #[error("Mismatched number of generics in trait implementation")]
+    MismatchTraitImplementationNumTraitGenerics {
+        impl_generic_count: usize,
+        trait_generic_count: usize,
+        trait_name: String,
+        span: Span,
+    },
     #[error("Method is not defined in trait")]
     MethodNotInTrait { trait_name: Ident, impl_method: Ident },
     #[error("Only traits can be implemented")]
@@ -73,6 +84,10 @@ pub enum DefCollectorErrorKind {
         "Either the type or the trait must be from the same crate as the trait implementation"
     )]
     TraitImplOrphaned { span: Span },
+    #[error("Unknown derive")]
+    UnknownDerive { name: String, span: Span },
+    #[error("Cannot derive trait for struct field")]
+    UnsupportedDeriveField { derive: String, field: Ident, typ: UnresolvedType },
 
     // Aztec feature flag errors
     // TODO(benesjan): https://github.com/AztecProtocol/aztec-packages/issues/2905
@@ -88,6 +103,39 @@ impl DefCollectorErrorKind {
     pub fn into_file_diagnostic(self, file: fm::FileId) -> FileDiagnostic {
         Diagnostic::from(self).in_file(file)
     }
+
+    /// Returns the stable error code of this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DefCollectorErrorKind::Duplicate { .. } => codes::E0200,
+            DefCollectorErrorKind::UnresolvedModuleDecl { .. } => codes::E0201,
+            DefCollectorErrorKind::AmbiguousModuleDecl { .. } => codes::E0202,
+            DefCollectorErrorKind::PathResolutionError(error) => error.code(),
+            DefCollectorErrorKind::NonStructTypeInImpl { .. } => codes::E0203,
+            DefCollectorErrorKind::MutableReferenceInTraitImpl { .. } => codes::E0204,
+            DefCollectorErrorKind::OverlappingImpl { .. }
+            | DefCollectorErrorKind::OverlappingImplNote { .. } => codes::E0205,
+            DefCollectorErrorKind::ForeignImpl { .. } => codes::E0206,
+            DefCollectorErrorKind::MismatchTraitImplementationNumParameters { .. } => codes::E0207,
+            DefCollectorErrorKind::MismatchTraitImplementationNumGenerics { .. } => codes::E0208,
+            DefCollectorErrorKind::MismatchTraitImplementationNumTraitGenerics { .. } => {
+                codes::E0209
+            }
+            DefCollectorErrorKind::MethodNotInTrait { .. } => codes::E0210,
+            DefCollectorErrorKind::NotATrait { .. } => codes::E0211,
+            DefCollectorErrorKind::TraitNotFound { .. } => codes::E0212,
+            DefCollectorErrorKind::TraitMissingMethod { .. } => codes::E0213,
+            DefCollectorErrorKind::ModuleAlreadyPartOfCrate { .. }
+            | DefCollectorErrorKind::ModuleOriginallyDefined { .. } => codes::E0214,
+            DefCollectorErrorKind::TraitImplOrphaned { .. } => codes::E0215,
+            DefCollectorErrorKind::UnknownDerive { .. } => codes::E0216,
+            DefCollectorErrorKind::UnsupportedDeriveField { .. } => codes::E0217,
+            #[cfg(feature = "aztec")]
+            DefCollectorErrorKind::AztecNotFound {} => codes::E0218,
+            #[cfg(feature = "aztec")]
+            DefCollectorErrorKind::AztecComputeNoteHashAndNullifierNotFound { .. } => codes::E0219,
+        }
+    }
 }
 
 impl fmt::Display for DuplicateType {
@@ -109,7 +157,8 @@ impl fmt::Display for DuplicateType {
 
 impl From<DefCollectorErrorKind> for Diagnostic {
     fn from(error: DefCollectorErrorKind) -> Diagnostic {
-        match error {
+        let code = error.code();
+        let diagnostic = match error {
             DefCollectorErrorKind::Duplicate { typ, first_def, second_def } => {
                 let primary_message = format!(
                     "Duplicate definitions of {} with name {} found",
@@ -127,16 +176,30 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                     diag
                 }
             }
-            DefCollectorErrorKind::UnresolvedModuleDecl { mod_name, expected_path } => {
+            DefCollectorErrorKind::UnresolvedModuleDecl {
+                mod_name,
+                expected_path,
+                alternative_path,
+            } => {
                 let span = mod_name.0.span();
                 let mod_name = &mod_name.0.contents;
 
                 Diagnostic::simple_error(
-                    format!("No module `{mod_name}` at path `{expected_path}`"),
+                    format!("No module `{mod_name}` at path `{expected_path}` or `{alternative_path}`"),
                     String::new(),
                     span,
                 )
             }
+            DefCollectorErrorKind::AmbiguousModuleDecl { mod_name, file_path, dir_path } => {
+                let span = mod_name.0.span();
+                let mod_name = &mod_name.0.contents;
+
+                Diagnostic::simple_error(
+                    format!("Module `{mod_name}` is found at both `{file_path}` and `{dir_path}`"),
+                    "Delete or rename one of these files".to_string(),
+                    span,
+                )
+            }
             DefCollectorErrorKind::PathResolutionError(error) => error.into(),
             DefCollectorErrorKind::NonStructTypeInImpl { span } => Diagnostic::simple_error(
                 "Non-struct type used in impl".into(),
@@ -199,6 +262,17 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                     "`{trait_name}::{method_name}` expects {trait_method_generic_count} generic{plural}, but this method has {impl_method_generic_count}");
                 Diagnostic::simple_error(primary_message, "".to_string(), span)
             }
+            DefCollectorErrorKind::MismatchTraitImplementationNumTraitGenerics {
+                impl_generic_count,
+                trait_generic_count,
+                trait_name,
+                span,
+            } => {
+                let plural = if trait_generic_count == 1 { "" } else { "s" };
+                let primary_message = format!(
+                    "`{trait_name}` expects {trait_generic_count} generic{plural}, but this impl has {impl_generic_count}");
+                Diagnostic::simple_error(primary_message, "".to_string(), span)
+            }
             DefCollectorErrorKind::MethodNotInTrait { trait_name, impl_method } => {
                 let trait_name = trait_name.0.contents;
                 let impl_method_span = impl_method.span();
@@ -245,6 +319,18 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                 "Either the type or the trait must be from the same crate as the trait implementation".into(),
                 span,
             ),
+            DefCollectorErrorKind::UnknownDerive { name, span } => Diagnostic::simple_error(
+                format!("`{name}` cannot be derived"),
+                "Only `Eq`, `Default` and `Serialize` can be derived".into(),
+                span,
+            ),
+            DefCollectorErrorKind::UnsupportedDeriveField { derive, field, typ } => {
+                Diagnostic::simple_error(
+                    format!("Cannot derive `{derive}` for field `{field}` of type `{typ}`"),
+                    format!("`{derive}` cannot be derived for this field"),
+                    field.span(),
+                )
+            }
             #[cfg(feature = "aztec")]
             DefCollectorErrorKind::AztecNotFound {} => Diagnostic::from_message(
                 "Aztec dependency not found. Please add aztec as a dependency in your Cargo.toml",
@@ -255,6 +341,7 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                 "".into(),
                 span
             ),
-        }
+        };
+        diagnostic.with_code(code)
     }
 }
@@ -15,6 +15,12 @@ pub struct ItemScope {
     types: HashMap<Ident, HashMap<Option<TraitId>, (ModuleDefId, Visibility)>>,
     values: HashMap<Ident, HashMap<Option<TraitId>, (ModuleDefId, Visibility)>>,
 
+    /// Items brought into scope by glob imports. These are shadowed by any item which is
+    /// defined or explicitly imported with the same name. A name provided by several glob
+    /// imports referring to different items is ambiguous.
+    glob_types: HashMap<Ident, Vec<ModuleDefId>>,
+    glob_values: HashMap<Ident, Vec<ModuleDefId>>,
+
     defs: Vec<ModuleDefId>,
 }
 
@@ -68,6 +74,50 @@ impl ItemScope {
         }
     }
 
+    /// Adds an item imported by a glob import. Unlike `add_item_to_namespace` this never
+    /// fails: conflicting glob imports are only reported if the name is used.
+    pub fn add_glob_import(&mut self, name: Ident, mod_def: ModuleDefId) {
+        let map = match mod_def {
+            ModuleDefId::FunctionId(_) | ModuleDefId::GlobalId(_) => &mut self.glob_values,
+            ModuleDefId::ModuleId(_)
+            | ModuleDefId::TypeId(_)
+            | ModuleDefId::TypeAliasId(_)
+            | ModuleDefId::TraitId(_) => &mut self.glob_types,
+        };
+
+        let items = map.entry(name).or_default();
+        if !items.contains(&mod_def) {
+            items.push(mod_def);
+        }
+    }
+
+    /// Returns true if `name` is not defined or explicitly imported in a namespace
+    /// but several glob imports provide different items for it.
+    pub fn is_ambiguous(&self, name: &Ident) -> bool {
+        let ambiguous_in = |explicit: &HashMap<Ident, _>, globs: &HashMap<Ident, Vec<_>>| {
+            !explicit.contains_key(name) && globs.get(name).map_or(false, |items| items.len() > 1)
+        };
+
+        ambiguous_in(&self.types, &self.glob_types) || ambiguous_in(&self.values, &self.glob_values)
+    }
+
+    /// Returns each item visible through this scope by name, which is what a glob import of
+    /// this scope brings into another module.
+    pub fn visible_items(&self) -> Vec<(Ident, ModuleDefId)> {
+        let names = self.types.keys().chain(self.values.keys());
+        let names = names.chain(self.glob_types.keys()).chain(self.glob_values.keys());
+
+        let mut items = Vec::new();
+        for name in names {
+            for item in self.find_name(name).iter_defs() {
+                if !items.contains(&(name.clone(), item)) {
+                    items.push((name.clone(), item));
+                }
+            }
+        }
+        items
+    }
+
     pub fn find_module_with_name(&self, mod_name: &Ident) -> Option<&ModuleId> {
         let (module_def, _) = self.types.get(mod_name)?.get(&None)?;
         match module_def {
@@ -130,7 +180,16 @@ impl ItemScope {
                 }
             };
 
-        PerNs { types: find_name_in(&self.types), values: find_name_in(&self.values) }
+        // Items imported by globs are only used if there is no other item with that name.
+        let find_glob_in = |globs: &HashMap<Ident, Vec<ModuleDefId>>| match globs.get(name) {
+            Some(items) if items.len() == 1 => Some((items[0], Visibility::Public)),
+            _ => None,
+        };
+
+        PerNs {
+            types: find_name_in(&self.types).or_else(|| find_glob_in(&self.glob_types)),
+            values: find_name_in(&self.values).or_else(|| find_glob_in(&self.glob_values)),
+        }
     }
 
     pub fn find_name_for_trait_id(&self, name: &Ident, trait_id: &Option<TraitId>) -> PerNs {
@@ -1,9 +1,9 @@
-use crate::graph::CrateId;
+use crate::graph::{CrateId, OverflowMode};
 use crate::hir::def_collector::dc_crate::{CompilationError, DefCollector};
 use crate::hir::Context;
 use crate::node_interner::{FuncId, NodeInterner, StructId};
 use crate::parser::{parse_program, ParsedModule, ParserError};
-use crate::token::{FunctionAttribute, SecondaryAttribute, TestScope};
+use crate::token::{FunctionAttribute, SecondaryAttribute, TestMode, TestScope};
 use arena::{Arena, Index};
 use fm::{FileId, FileManager};
 use noirc_errors::Location;
@@ -62,6 +62,8 @@ pub struct CrateDefMap {
     pub(crate) krate: CrateId,
 
     pub(crate) extern_prelude: BTreeMap<String, ModuleId>,
+
+    pub(crate) overflow_mode: OverflowMode,
 }
 
 impl CrateDefMap {
@@ -82,7 +84,7 @@ impl CrateDefMap {
 
         // First parse the root file.
         let root_file_id = context.crate_graph[crate_id].root_file_id;
-        let (ast, parsing_errors) = parse_file(&context.file_manager, root_file_id);
+        let (ast, parsing_errors) = context.parse_file(root_file_id);
         let ast = ast.into_sorted();
 
         #[cfg(feature = "aztec")]
@@ -104,6 +106,7 @@ impl CrateDefMap {
             modules,
             krate: crate_id,
             extern_prelude: BTreeMap::new(),
+            overflow_mode: context.crate_graph[crate_id].overflow_mode,
         };
 
         // Now we want to populate the CrateDefMap using the DefCollector
@@ -149,9 +152,9 @@ impl CrateDefMap {
                 if let Some(func_id) = id.as_function() {
                     let attributes = interner.function_attributes(&func_id);
                     match &attributes.function {
-                        Some(FunctionAttribute::Test(scope)) => {
+                        Some(FunctionAttribute::Test(scope, mode)) => {
                             let location = interner.function_meta(&func_id).name.location;
-                            Some(TestFunction::new(func_id, scope.clone(), location))
+                            Some(TestFunction::new(func_id, scope.clone(), mode.clone(), location))
                         }
                         _ => None,
                     }
@@ -162,6 +165,20 @@ impl CrateDefMap {
         })
     }
 
+    /// Go through all modules in this crate, and find all functions in
+    /// each module with the #[export] attribute
+    pub fn get_all_exported_functions<'a>(
+        &'a self,
+        interner: &'a NodeInterner,
+    ) -> impl Iterator<Item = FuncId> + 'a {
+        self.modules.iter().flat_map(|(_, module)| {
+            module.value_definitions().filter_map(|id| {
+                id.as_function()
+                    .filter(|func_id| interner.function_attributes(func_id).has_export())
+            })
+        })
+    }
+
     /// Go through all modules in this crate, find all `contract ... { ... }` declarations,
     /// and collect them all into a Vec.
     pub fn get_all_contracts(&self, interner: &NodeInterner) -> Vec<Contract> {
@@ -278,12 +295,13 @@ impl std::ops::IndexMut<LocalModuleId> for CrateDefMap {
 pub struct TestFunction {
     id: FuncId,
     scope: TestScope,
+    mode: TestMode,
     location: Location,
 }
 
 impl TestFunction {
-    fn new(id: FuncId, scope: TestScope, location: Location) -> Self {
-        TestFunction { id, scope, location }
+    fn new(id: FuncId, scope: TestScope, mode: TestMode, location: Location) -> Self {
+        TestFunction { id, scope, mode, location }
     }
 
     /// Returns the function id of the test function
@@ -313,4 +331,11 @@ impl TestFunction {
             TestScope::ShouldFailWith { reason } => reason.as_deref(),
         }
     }
+
+    /// Returns the execution pipelines the test function should be run in.
+    /// This is set by annotating the function with `#[test(only_acir)]`,
+    /// `#[test(only_brillig)]` or `#[test(ignore)]`
+    pub fn mode(&self) -> &TestMode {
+        &self.mode
+    }
 }
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 
 use noirc_errors::Location;
 
@@ -27,6 +28,10 @@ pub struct ModuleData {
 
     /// True if this module is a `contract Foo { ... }` module containing contract functions
     pub is_contract: bool,
+
+    /// The names which paths have looked up in this module's scope, used to find unused imports.
+    /// Paths are resolved against a shared def map, so these are recorded through a `RefCell`.
+    used_names: RefCell<HashSet<String>>,
 }
 
 impl ModuleData {
@@ -38,6 +43,7 @@ impl ModuleData {
             definitions: ItemScope::default(),
             location,
             is_contract,
+            used_names: RefCell::default(),
         }
     }
 
@@ -108,10 +114,38 @@ impl ModuleData {
         self.scope.add_item_to_namespace(name, id, None)
     }
 
+    pub fn import_glob(&mut self, name: Ident, id: ModuleDefId) {
+        self.scope.add_glob_import(name, id);
+    }
+
     pub fn find_name(&self, name: &Ident) -> PerNs {
         self.scope.find_name(name)
     }
 
+    /// Records that a path has looked up `name` in this module's scope.
+    pub fn mark_name_used(&self, name: &Ident) {
+        self.used_names.borrow_mut().insert(name.0.contents.clone());
+    }
+
+    /// Returns true if any path has looked up `name` in this module's scope.
+    pub fn is_name_used(&self, name: &Ident) -> bool {
+        self.used_names.borrow().contains(&name.0.contents)
+    }
+
+    /// Returns all items visible in this module, which a glob import of it brings into scope.
+    pub fn visible_items(&self) -> Vec<(Ident, ModuleDefId)> {
+        self.scope.visible_items()
+    }
+
+    /// Returns the items defined directly in this module, excluding any imports.
+    pub fn defined_items(&self) -> Vec<(Ident, ModuleDefId)> {
+        self.definitions.visible_items()
+    }
+
+    pub fn is_ambiguous(&self, name: &Ident) -> bool {
+        self.scope.is_ambiguous(name)
+    }
+
     pub fn type_definitions(&self) -> impl Iterator<Item = ModuleDefId> + '_ {
         self.definitions.types().values().flat_map(|a| a.values().map(|(id, _)| *id))
     }
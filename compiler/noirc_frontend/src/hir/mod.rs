@@ -1,3 +1,4 @@
+pub mod comptime;
 pub mod def_collector;
 pub mod def_map;
 pub mod resolution;
@@ -10,10 +11,12 @@ pub(crate) mod aztec_library;
 use crate::graph::{CrateGraph, CrateId};
 use crate::hir_def::function::FuncMeta;
 use crate::node_interner::{FuncId, NodeInterner, StructId};
+use crate::parser::{parse_program, ParsedModule, ParserError};
 use def_map::{Contract, CrateDefMap};
-use fm::FileManager;
+use fm::{FileId, FileManager};
 use noirc_errors::Location;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
 use self::def_map::TestFunction;
 
@@ -33,8 +36,17 @@ pub struct Context {
     /// Maps a given (contract) module id to the next available storage slot
     /// for that contract.
     pub storage_slots: BTreeMap<def_map::ModuleId, StorageSlot>,
+
+    /// The modules parsed by a previous compilation of the same sources, if they are being kept.
+    /// Tools which compile repeatedly, such as the language server, set this so that files
+    /// which haven't changed since are not parsed again.
+    pub parsed_files: Option<ParsedFiles>,
 }
 
+/// The modules parsed from each file, keyed by the file's path, along with the source
+/// they were parsed from.
+pub type ParsedFiles = HashMap<PathBuf, (String, ParsedModule, Vec<ParserError>)>;
+
 #[derive(Debug, Copy, Clone)]
 pub enum FunctionNameMatch<'a> {
     Anything,
@@ -53,9 +65,30 @@ impl Context {
             crate_graph,
             file_manager,
             storage_slots: BTreeMap::new(),
+            parsed_files: None,
         }
     }
 
+    /// Parses a file, reusing the module in `parsed_files` if the file's source is unchanged.
+    pub(crate) fn parse_file(&mut self, file_id: FileId) -> (ParsedModule, Vec<ParserError>) {
+        let Some(parsed_files) = &mut self.parsed_files else {
+            return def_map::parse_file(&self.file_manager, file_id);
+        };
+
+        let path = self.file_manager.path(file_id);
+        let source = self.file_manager.fetch_file(file_id).source();
+        if let Some((parsed_source, module, errors)) = parsed_files.get(path) {
+            if parsed_source == source {
+                return (module.clone(), errors.clone());
+            }
+        }
+
+        let (module, errors) = parse_program(source);
+        parsed_files
+            .insert(path.to_path_buf(), (source.to_string(), module.clone(), errors.clone()));
+        (module, errors)
+    }
+
     /// Returns the CrateDefMap for a given CrateId.
     /// It is perfectly valid for the compiler to look
     /// up a CrateDefMap and it is not available.
@@ -190,6 +223,18 @@ impl Context {
             .collect()
     }
 
+    /// Returns the fully qualified name and id of all functions in the current crate
+    /// marked with #[export]
+    pub fn get_all_exported_functions_in_crate(&self, crate_id: &CrateId) -> Vec<(String, FuncId)> {
+        let interner = &self.def_interner;
+        let def_map = self.def_map(crate_id).expect("The local crate should be analyzed already");
+
+        def_map
+            .get_all_exported_functions(interner)
+            .map(|func_id| (self.fully_qualified_function_name(crate_id, &func_id), func_id))
+            .collect()
+    }
+
     /// Return a Vec of all `contract` declarations in the source code and the functions they contain
     pub fn get_all_contracts(&self, crate_id: &CrateId) -> Vec<Contract> {
         self.def_map(crate_id)
@@ -0,0 +1,481 @@
+//! Evaluation of array lengths, numeric globals and calls to `comptime fn`s during name resolution.
+//!
+//! Array lengths must be known before any function is type checked, so `comptime fn`s called
+//! within them cannot be evaluated by the HIR interpreter in `hir::comptime`. Instead their
+//! unresolved definitions are kept in the interner and walked directly. Only a small subset of
+//! the language is supported: integer and boolean values, local and global variables, arithmetic
+//! and comparison operators, `if`, `let`, assignment, loops, `assert`, and calls to other
+//! `comptime fn`s. Integers are evaluated as unsigned 128-bit values without being truncated to
+//! their declared types, and any overflow is reported as an error.
+use std::collections::{BTreeMap, HashMap};
+
+use iter_extended::{try_vecmap, vecmap};
+use noirc_errors::Span;
+
+use crate::graph::CrateId;
+use crate::hir::def_map::{CrateDefMap, ModuleDefId};
+use crate::hir_def::expr::{HirExpression, HirLiteral};
+use crate::hir_def::stmt::HirStatement;
+use crate::node_interner::{DefinitionKind, ExprId, FuncId, NodeInterner};
+use crate::{
+    BinaryOpKind, BlockExpression, ConstrainStatement, Expression, ExpressionKind, ForRange,
+    LValue, Literal, Path, Pattern, Statement, StatementKind, UnaryOp,
+};
+
+use super::errors::ResolverError;
+use super::path_resolver::{PathResolver, StandardPathResolver};
+
+/// The maximum depth of nested `comptime fn` calls before evaluation is aborted
+const MAX_CALL_DEPTH: usize = 256;
+
+/// The number of iterations a `while` or `loop` without an explicit limit may run for
+const DEFAULT_MAX_ITERATIONS: u64 = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Value {
+    Unit,
+    Bool(bool),
+    Integer(u128),
+}
+
+enum Interrupt {
+    /// Evaluation failed for the given reason
+    Error(String),
+    // These are not errors but are used to unwind the evaluator's call stack
+    Break,
+    Continue,
+    Return(Value),
+}
+
+type EvalResult<T> = Result<T, Interrupt>;
+
+fn error<T>(reason: impl Into<String>) -> EvalResult<T> {
+    Err(Interrupt::Error(reason.into()))
+}
+
+pub(super) struct ComptimeFnEvaluator<'a> {
+    interner: &'a NodeInterner,
+    def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
+
+    /// The span of the expression being evaluated. Errors within `comptime fn`s are
+    /// reported here since their bodies may be in another file.
+    span: Span,
+
+    /// The block scopes of the `comptime fn` currently being evaluated, innermost last
+    scopes: Vec<HashMap<String, Value>>,
+
+    /// The `comptime fn` currently being evaluated, used to resolve paths within it
+    function: Option<FuncId>,
+
+    call_depth: usize,
+}
+
+impl<'a> ComptimeFnEvaluator<'a> {
+    pub(super) fn new(
+        interner: &'a NodeInterner,
+        def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
+        span: Span,
+    ) -> Self {
+        Self { interner, def_maps, span, scopes: Vec::new(), function: None, call_depth: 0 }
+    }
+
+    /// Evaluates the resolved expression of a global used as an array length
+    pub(super) fn evaluate_global(&mut self, rhs: ExprId) -> Result<u128, Option<ResolverError>> {
+        let span = self.span;
+        match self.interner.expression(&rhs) {
+            HirExpression::Literal(HirLiteral::Integer(int)) => {
+                int.try_into_u128().ok_or(Some(ResolverError::IntegerTooLarge { span }))
+            }
+            HirExpression::Ident(ident) => match self.interner.definition(ident.id).kind {
+                DefinitionKind::Global(global) => self.evaluate_global(global),
+                _ => Err(Some(ResolverError::InvalidArrayLengthExpr { span })),
+            },
+            HirExpression::Infix(infix) => {
+                let lhs = self.evaluate_global(infix.lhs)?;
+                let rhs = self.evaluate_global(infix.rhs)?;
+                let shift = u32::try_from(rhs).ok();
+
+                use BinaryOpKind::*;
+                let (result, error) = match infix.operator.kind {
+                    Add | WrappingAdd => {
+                        (lhs.checked_add(rhs), ResolverError::IntegerTooLarge { span })
+                    }
+                    Multiply | WrappingMultiply => {
+                        (lhs.checked_mul(rhs), ResolverError::IntegerTooLarge { span })
+                    }
+                    ShiftLeft => (
+                        shift.and_then(|rhs| lhs.checked_shl(rhs)),
+                        ResolverError::IntegerTooLarge { span },
+                    ),
+                    Subtract | WrappingSubtract => {
+                        (lhs.checked_sub(rhs), ResolverError::InvalidArrayLengthExpr { span })
+                    }
+                    Divide => {
+                        (lhs.checked_div(rhs), ResolverError::InvalidArrayLengthExpr { span })
+                    }
+                    Modulo => {
+                        (lhs.checked_rem(rhs), ResolverError::InvalidArrayLengthExpr { span })
+                    }
+                    ShiftRight => (
+                        shift.and_then(|rhs| lhs.checked_shr(rhs)),
+                        ResolverError::InvalidArrayLengthExpr { span },
+                    ),
+                    And => (Some(lhs & rhs), ResolverError::InvalidArrayLengthExpr { span }),
+                    Or => (Some(lhs | rhs), ResolverError::InvalidArrayLengthExpr { span }),
+                    Xor => (Some(lhs ^ rhs), ResolverError::InvalidArrayLengthExpr { span }),
+                    Equal | NotEqual | Less | LessEqual | Greater | GreaterEqual => {
+                        (None, ResolverError::InvalidArrayLengthExpr { span })
+                    }
+                };
+                result.ok_or(Some(error))
+            }
+            HirExpression::Cast(cast) => self.evaluate_global(cast.lhs),
+            HirExpression::Call(call) => {
+                let function = match self.interner.expression(&call.func) {
+                    HirExpression::Ident(ident) => self.interner.definition(ident.id).kind.clone(),
+                    _ => return Err(Some(ResolverError::InvalidArrayLengthExpr { span })),
+                };
+                let DefinitionKind::Function(function) = function else {
+                    return Err(Some(ResolverError::InvalidArrayLengthExpr { span }));
+                };
+                let arguments =
+                    try_vecmap(call.arguments, |argument| self.evaluate_global(argument))?;
+                self.call(function, arguments).map_err(Some)
+            }
+            _other => Err(Some(ResolverError::InvalidArrayLengthExpr { span })),
+        }
+    }
+
+    /// Calls the given `comptime fn` with integer arguments, returning the integer it evaluates to
+    pub(super) fn call(
+        &mut self,
+        function: FuncId,
+        arguments: Vec<u128>,
+    ) -> Result<u128, ResolverError> {
+        let span = self.span;
+        let name = self.interner.function_name(&function).to_owned();
+        if self.interner.comptime_function(function).is_none() {
+            return Err(ResolverError::NotAComptimeFunction { name, span });
+        }
+
+        let arguments = vecmap(arguments, Value::Integer);
+        let reason = match self.call_function(function, arguments) {
+            Ok(Value::Integer(value)) => return Ok(value),
+            Ok(_) => "it did not evaluate to an integer".to_owned(),
+            Err(Interrupt::Error(reason)) => reason,
+            Err(Interrupt::Break | Interrupt::Continue) => {
+                "`break` or `continue` outside of a loop".to_owned()
+            }
+            Err(Interrupt::Return(_)) => unreachable!("returns are caught by call_function"),
+        };
+        Err(ResolverError::ComptimeFnFailed { name, reason, span })
+    }
+
+    fn call_function(&mut self, function: FuncId, arguments: Vec<Value>) -> EvalResult<Value> {
+        let interner = self.interner;
+        let Some(definition) = interner.comptime_function(function) else {
+            let name = interner.function_name(&function);
+            return error(format!("`{name}` is not a `comptime fn`"));
+        };
+
+        if definition.parameters.len() != arguments.len() {
+            let expected = definition.parameters.len();
+            let name = &definition.name;
+            return error(format!(
+                "`{name}` expects {expected} arguments, found {}",
+                arguments.len()
+            ));
+        }
+
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return error("the maximum call depth was exceeded");
+        }
+
+        let mut scope = HashMap::new();
+        for (parameter, argument) in definition.parameters.iter().zip(arguments) {
+            scope.insert(pattern_name(&parameter.pattern)?, argument);
+        }
+
+        let old_scopes = std::mem::replace(&mut self.scopes, vec![scope]);
+        let old_function = self.function.replace(function);
+        self.call_depth += 1;
+
+        let result = match self.evaluate_block(&definition.body) {
+            Err(Interrupt::Return(value)) => Ok(value),
+            other => other,
+        };
+
+        self.call_depth -= 1;
+        self.function = old_function;
+        self.scopes = old_scopes;
+        result
+    }
+
+    fn evaluate(&mut self, expression: &Expression) -> EvalResult<Value> {
+        match &expression.kind {
+            ExpressionKind::Literal(Literal::Integer(int)) => match int.try_into_u128() {
+                Some(int) => Ok(Value::Integer(int)),
+                None => error(format!("`{int}` is too large")),
+            },
+            ExpressionKind::Literal(Literal::Bool(value)) => Ok(Value::Bool(*value)),
+            ExpressionKind::Literal(Literal::Unit) => Ok(Value::Unit),
+            ExpressionKind::Variable(path) => self.evaluate_variable(path),
+            ExpressionKind::Block(block) => self.evaluate_block(block),
+            ExpressionKind::Parenthesized(expression) => self.evaluate(expression),
+            ExpressionKind::Prefix(prefix) => {
+                match (prefix.operator, self.evaluate(&prefix.rhs)?) {
+                    (UnaryOp::Not, Value::Bool(value)) => Ok(Value::Bool(!value)),
+                    (UnaryOp::Minus, Value::Integer(0)) => Ok(Value::Integer(0)),
+                    (UnaryOp::Minus, Value::Integer(_)) => {
+                        error("negative values are not supported")
+                    }
+                    _ => error(format!("unsupported expression `{expression}`")),
+                }
+            }
+            ExpressionKind::Infix(infix) => {
+                let lhs = self.evaluate(&infix.lhs)?;
+                let rhs = self.evaluate(&infix.rhs)?;
+                evaluate_infix(lhs, infix.operator.contents, rhs)
+            }
+            ExpressionKind::Cast(cast) => match self.evaluate(&cast.lhs)? {
+                Value::Bool(value) => Ok(Value::Integer(value as u128)),
+                value => Ok(value),
+            },
+            ExpressionKind::If(if_expression) => match self.evaluate(&if_expression.condition)? {
+                Value::Bool(true) => self.evaluate(&if_expression.consequence),
+                Value::Bool(false) => match &if_expression.alternative {
+                    Some(alternative) => self.evaluate(alternative),
+                    None => Ok(Value::Unit),
+                },
+                _ => error("`if` conditions must be booleans"),
+            },
+            ExpressionKind::Call(call) => {
+                let ExpressionKind::Variable(path) = &call.func.kind else {
+                    return error(format!("unsupported expression `{expression}`"));
+                };
+                let function = match self.resolve_path(path)? {
+                    ModuleDefId::FunctionId(function) => function,
+                    _ => return error(format!("`{path}` is not a function")),
+                };
+                let arguments = try_vecmap(&call.arguments, |argument| self.evaluate(argument))?;
+                self.call_function(function, arguments)
+            }
+            _ => error(format!("unsupported expression `{expression}`")),
+        }
+    }
+
+    fn evaluate_variable(&mut self, path: &Path) -> EvalResult<Value> {
+        if let Some(ident) = path.as_ident() {
+            let local = self.scopes.iter().rev().find_map(|scope| scope.get(&ident.0.contents));
+            if let Some(value) = local {
+                return Ok(*value);
+            }
+        }
+
+        let ModuleDefId::GlobalId(global) = self.resolve_path(path)? else {
+            return error(format!("`{path}` is not a variable or global"));
+        };
+        let HirStatement::Let(let_statement) = self.interner.statement(&global) else {
+            return error(format!("global `{path}` has not been resolved"));
+        };
+        match self.evaluate_global(let_statement.expression) {
+            Ok(value) => Ok(Value::Integer(value)),
+            Err(_) => error(format!("global `{path}` is not a constant integer")),
+        }
+    }
+
+    fn resolve_path(&self, path: &Path) -> EvalResult<ModuleDefId> {
+        let Some(function) = self.function else {
+            return error(format!("`{path}` cannot be resolved"));
+        };
+        let resolver = StandardPathResolver::new(self.interner.function_module(function));
+        match resolver.resolve(self.def_maps, path.clone()) {
+            Ok(id) => Ok(id),
+            Err(_) => error(format!("could not resolve `{path}`")),
+        }
+    }
+
+    fn evaluate_block(&mut self, block: &BlockExpression) -> EvalResult<Value> {
+        self.scopes.push(HashMap::new());
+        let result = self.evaluate_statements(&block.0);
+        self.scopes.pop();
+        result
+    }
+
+    fn evaluate_statements(&mut self, statements: &[Statement]) -> EvalResult<Value> {
+        let mut result = Value::Unit;
+        for statement in statements {
+            result = self.evaluate_statement(statement)?;
+        }
+        Ok(result)
+    }
+
+    fn evaluate_statement(&mut self, statement: &Statement) -> EvalResult<Value> {
+        match &statement.kind {
+            StatementKind::Expression(expression) => self.evaluate(expression),
+            StatementKind::Semi(expression) => {
+                self.evaluate(expression)?;
+                Ok(Value::Unit)
+            }
+            StatementKind::Let(let_statement) => {
+                let value = self.evaluate(&let_statement.expression)?;
+                let name = pattern_name(&let_statement.pattern)?;
+                self.scopes.last_mut().expect("there is always a scope").insert(name, value);
+                Ok(Value::Unit)
+            }
+            StatementKind::Assign(assign) => {
+                let LValue::Ident(ident) = &assign.lvalue else {
+                    return error(format!("unsupported assignment to `{}`", assign.lvalue));
+                };
+                let value = self.evaluate(&assign.expression)?;
+                let name = &ident.0.contents;
+                match self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name)) {
+                    Some(variable) => *variable = value,
+                    None => return error(format!("cannot assign to `{name}`")),
+                }
+                Ok(Value::Unit)
+            }
+            StatementKind::Constrain(ConstrainStatement(predicate, message, _)) => {
+                match self.evaluate(predicate)? {
+                    Value::Bool(true) => Ok(Value::Unit),
+                    Value::Bool(false) => match message {
+                        Some(Expression {
+                            kind: ExpressionKind::Literal(Literal::Str(message)),
+                            ..
+                        }) => error(format!("assertion failed: {message}")),
+                        _ => error("assertion failed"),
+                    },
+                    _ => error("assertions must be booleans"),
+                }
+            }
+            StatementKind::For(for_loop) => {
+                let ForRange::Range(start, end) = &for_loop.range else {
+                    return error("only ranged `for` loops are supported");
+                };
+                let (Value::Integer(start), Value::Integer(end)) =
+                    (self.evaluate(start)?, self.evaluate(end)?)
+                else {
+                    return error("`for` loop bounds must be integers");
+                };
+
+                for index in start..end {
+                    self.scopes.push(HashMap::from([(
+                        for_loop.identifier.0.contents.clone(),
+                        Value::Integer(index),
+                    )]));
+                    let result = self.evaluate(&for_loop.block);
+                    self.scopes.pop();
+
+                    match result {
+                        Ok(_) | Err(Interrupt::Continue) => (),
+                        Err(Interrupt::Break) => break,
+                        Err(other) => return Err(other),
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            StatementKind::While(while_loop) => {
+                let max_iterations = while_loop.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS);
+                let mut iterations = 0;
+                loop {
+                    match self.evaluate(&while_loop.condition)? {
+                        Value::Bool(true) => (),
+                        Value::Bool(false) => break,
+                        _ => return error("`while` conditions must be booleans"),
+                    }
+                    check_iteration_limit(&mut iterations, max_iterations)?;
+                    match self.evaluate(&while_loop.block) {
+                        Ok(_) | Err(Interrupt::Continue) => (),
+                        Err(Interrupt::Break) => break,
+                        Err(other) => return Err(other),
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            StatementKind::Loop(loop_statement) => {
+                let max_iterations =
+                    loop_statement.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS);
+                let mut iterations = 0;
+                loop {
+                    check_iteration_limit(&mut iterations, max_iterations)?;
+                    match self.evaluate(&loop_statement.block) {
+                        Ok(_) | Err(Interrupt::Continue) => (),
+                        Err(Interrupt::Break) => break,
+                        Err(other) => return Err(other),
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            StatementKind::Break(_) => Err(Interrupt::Break),
+            StatementKind::Continue(_) => Err(Interrupt::Continue),
+            StatementKind::Return(return_statement) => {
+                let value = match &return_statement.expression {
+                    Some(expression) => self.evaluate(expression)?,
+                    None => Value::Unit,
+                };
+                Err(Interrupt::Return(value))
+            }
+            StatementKind::Error => error("the function contains a parse error"),
+        }
+    }
+}
+
+/// Returns the name bound by a parameter or `let` pattern. Only plain (optionally mutable)
+/// identifiers are supported in `comptime fn`s.
+fn pattern_name(pattern: &Pattern) -> EvalResult<String> {
+    match pattern {
+        Pattern::Identifier(ident) => Ok(ident.0.contents.clone()),
+        Pattern::Mutable(pattern, _) => pattern_name(pattern),
+        _ => error(format!("unsupported pattern `{pattern}`")),
+    }
+}
+
+fn check_iteration_limit(iterations: &mut u64, max_iterations: u64) -> EvalResult<()> {
+    if *iterations >= max_iterations {
+        return error(format!("loop exceeded its limit of {max_iterations} iterations"));
+    }
+    *iterations += 1;
+    Ok(())
+}
+
+fn evaluate_infix(lhs: Value, operator: BinaryOpKind, rhs: Value) -> EvalResult<Value> {
+    use BinaryOpKind::*;
+    let (lhs, rhs) = match (lhs, rhs) {
+        (Value::Integer(lhs), Value::Integer(rhs)) => (lhs, rhs),
+        (Value::Bool(lhs), Value::Bool(rhs)) => {
+            return match operator {
+                Equal => Ok(Value::Bool(lhs == rhs)),
+                NotEqual => Ok(Value::Bool(lhs != rhs)),
+                And => Ok(Value::Bool(lhs & rhs)),
+                Or => Ok(Value::Bool(lhs | rhs)),
+                Xor => Ok(Value::Bool(lhs ^ rhs)),
+                _ => error(format!("`{}` is not supported on booleans", operator.as_string())),
+            }
+        }
+        _ => return error(format!("mismatched operands to `{}`", operator.as_string())),
+    };
+
+    let shift = u32::try_from(rhs).ok();
+    let result = match operator {
+        Add | WrappingAdd => lhs.checked_add(rhs).ok_or("integer overflow"),
+        Subtract | WrappingSubtract => lhs.checked_sub(rhs).ok_or("integer underflow"),
+        Multiply | WrappingMultiply => lhs.checked_mul(rhs).ok_or("integer overflow"),
+        Divide => lhs.checked_div(rhs).ok_or("division by zero"),
+        Modulo => lhs.checked_rem(rhs).ok_or("division by zero"),
+        ShiftLeft => shift.and_then(|rhs| lhs.checked_shl(rhs)).ok_or("integer overflow"),
+        ShiftRight => shift.and_then(|rhs| lhs.checked_shr(rhs)).ok_or("integer overflow"),
+        And => Ok(lhs & rhs),
+        Or => Ok(lhs | rhs),
+        Xor => Ok(lhs ^ rhs),
+        Equal => return Ok(Value::Bool(lhs == rhs)),
+        NotEqual => return Ok(Value::Bool(lhs != rhs)),
+        Less => return Ok(Value::Bool(lhs < rhs)),
+        LessEqual => return Ok(Value::Bool(lhs <= rhs)),
+        Greater => return Ok(Value::Bool(lhs > rhs)),
+        GreaterEqual => return Ok(Value::Bool(lhs >= rhs)),
+    };
+    match result {
+        Ok(result) => Ok(Value::Integer(result)),
+        Err(reason) => error(reason),
+    }
+}
@@ -1,10 +1,12 @@
 pub use noirc_errors::Span;
-use noirc_errors::{CustomDiagnostic as Diagnostic, FileDiagnostic};
+use noirc_errors::{
+    error_codes as codes, Applicability, CustomDiagnostic as Diagnostic, FileDiagnostic, Suggestion,
+};
 use thiserror::Error;
 
-use crate::{parser::ParserError, Ident, Type};
+use crate::{lints::Lint, parser::ParserError, Ident, Type, Visibility};
 
-use super::import::PathResolutionError;
+use super::{import::PathResolutionError, suggestions::NameSuggestions};
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum PubPosition {
@@ -20,8 +22,10 @@ pub enum ResolverError {
     DuplicateDefinition { name: String, first_span: Span, second_span: Span },
     #[error("Unused variable")]
     UnusedVariable { ident: Ident },
+    #[error("Unreachable code")]
+    UnreachableCode { span: Span },
     #[error("Could not find variable in this scope")]
-    VariableNotDeclared { name: String, span: Span },
+    VariableNotDeclared { name: String, span: Span, suggestions: NameSuggestions },
     #[error("path is not an identifier")]
     PathIsNotIdent { span: Span },
     #[error("could not resolve path")]
@@ -42,6 +46,8 @@ pub enum ResolverError {
     NecessaryPub { ident: Ident },
     #[error("'distinct' keyword can only be used with main method")]
     DistinctNotAllowed { ident: Ident },
+    #[error("databus visibilities can only be used with main method")]
+    DataBusNotAllowed { ident: Ident, visibility: Visibility },
     #[error("Missing expression for declared constant")]
     MissingRhsExpr { name: String, span: Span },
     #[error("Expression invalid in an array length context")]
@@ -68,7 +74,9 @@ pub enum ResolverError {
     ContractFunctionTypeInNormalFunction { span: Span },
     #[error("Cannot create a mutable reference to {variable}, it was declared to be immutable")]
     MutableReferenceToImmutableVariable { variable: String, span: Span },
-    #[error("Mutable references to array indices are unsupported")]
+    #[error("Assertion messages must be strings known at compile-time")]
+    NonConstantAssertMessage { span: Span },
+    #[error("Mutable references to array indices are only supported as function arguments")]
     MutableReferenceToArrayElement { span: Span },
     #[error("Function is not defined in a contract yet sets is_internal")]
     ContractFunctionInternalInNormalFunction { span: Span },
@@ -76,18 +84,125 @@ pub enum ResolverError {
     NumericConstantInFormatString { name: String, span: Span },
     #[error("Closure environment must be a tuple or unit type")]
     InvalidClosureEnvironment { typ: Type, span: Span },
-    #[error("{name} is private and not visible from the current module")]
+    #[error("function {name} is private")]
     PrivateFunctionCalled { name: String, span: Span },
-    #[error("{name} is not visible from the current crate")]
+    #[error("function {name} is not visible from the current crate")]
     NonCrateFunctionCalled { name: String, span: Span },
     #[error("Only sized types may be used in the entry point to a program")]
     InvalidTypeForEntryPoint { span: Span },
+    #[error("Parameters of the entry point to a program must be named")]
+    DestructuredEntryPointParameter { span: Span },
+    #[error(
+        "while loops in constrained code require an upper bound on their number of iterations"
+    )]
+    UnboundedWhileLoop { span: Span },
+    #[error("loops in constrained code require a #[max_iterations(N)] attribute")]
+    UnboundedLoop { span: Span },
+    #[error("`{keyword}` is only allowed within a loop")]
+    JumpOutsideLoop { keyword: &'static str, span: Span },
+    #[error("No such variant in enum")]
+    NoSuchVariant { variant: Ident, enum_definition: Ident },
+    #[error("Expected an enum variant")]
+    ExpectedEnumVariant { path: crate::Path },
+    #[error("Global depends on its own value")]
+    GlobalDependencyCycle { span: Span },
+    #[error("Cannot assign to a variable declared outside of an unconstrained block")]
+    AssignmentInUnconstrainedBlock { name: String, span: Span },
+    #[error("`return` is not allowed within an unconstrained block")]
+    ReturnInUnconstrainedBlock { span: Span },
+    #[error("Oracle functions must be unconstrained")]
+    OracleNotUnconstrained { ident: Ident },
+    #[error("Oracle functions cannot have a body")]
+    OracleWithBody { ident: Ident },
+    #[error("Parameters of an oracle must be named")]
+    DestructuredOracleParameter { span: Span },
+    #[error("`{name}` is not a comptime function")]
+    NotAComptimeFunction { name: String, span: Span },
+    #[error("Failed to evaluate comptime function `{name}`")]
+    ComptimeFnFailed { name: String, reason: String, span: Span },
+    #[error("Unused import")]
+    UnusedImport { ident: Ident, statement_span: Option<Span> },
+    #[error("Unused function")]
+    UnusedFunction { ident: Ident },
+    #[error("`?` must be applied to the whole value of a `let` or an expression statement")]
+    NestedTry { span: Span },
 }
 
 impl ResolverError {
     pub fn into_file_diagnostic(self, file: fm::FileId) -> FileDiagnostic {
         Diagnostic::from(self).in_file(file)
     }
+
+    /// Returns the lint controlling this warning, if it is one
+    pub fn lint(&self) -> Option<Lint> {
+        match self {
+            ResolverError::UnusedVariable { .. } => Some(Lint::UnusedVariables),
+            ResolverError::UnreachableCode { .. } => Some(Lint::UnreachableCode),
+            ResolverError::UnnecessaryPub { .. } => Some(Lint::UnnecessaryPub),
+            ResolverError::UnusedImport { .. } => Some(Lint::UnusedImports),
+            ResolverError::UnusedFunction { .. } => Some(Lint::UnusedFunctions),
+            _ => None,
+        }
+    }
+
+    /// Returns the stable error code of this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ResolverError::DuplicateDefinition { .. } => codes::E0300,
+            ResolverError::UnusedVariable { .. } => codes::E0301,
+            ResolverError::UnreachableCode { .. } => codes::E0302,
+            ResolverError::VariableNotDeclared { .. } => codes::E0303,
+            ResolverError::PathIsNotIdent { .. } => codes::E0304,
+            ResolverError::PathResolutionError(error) => error.code(),
+            ResolverError::Expected { .. } => codes::E0305,
+            ResolverError::DuplicateField { .. } => codes::E0306,
+            ResolverError::NoSuchField { .. } => codes::E0307,
+            ResolverError::MissingFields { .. } => codes::E0308,
+            ResolverError::UnnecessaryMut { .. } => codes::E0309,
+            ResolverError::UnnecessaryPub { .. } => codes::E0310,
+            ResolverError::NecessaryPub { .. } => codes::E0311,
+            ResolverError::DistinctNotAllowed { .. } => codes::E0312,
+            ResolverError::DataBusNotAllowed { .. } => codes::E0313,
+            ResolverError::MissingRhsExpr { .. } => codes::E0314,
+            ResolverError::InvalidArrayLengthExpr { .. } => codes::E0315,
+            ResolverError::IntegerTooLarge { .. } => codes::E0316,
+            ResolverError::NoSuchNumericTypeVariable { .. } => codes::E0317,
+            ResolverError::CapturedMutableVariable { .. } => codes::E0318,
+            ResolverError::TestFunctionHasParameters { .. } => codes::E0319,
+            ResolverError::NonStructUsedInConstructor { .. } => codes::E0320,
+            ResolverError::NonStructWithGenerics { .. } => codes::E0321,
+            ResolverError::GenericsOnSelfType { .. } => codes::E0322,
+            ResolverError::IncorrectGenericCount { .. } => codes::E0323,
+            ResolverError::ParserError(error) => error.code(),
+            ResolverError::ContractFunctionTypeInNormalFunction { .. } => codes::E0324,
+            ResolverError::MutableReferenceToImmutableVariable { .. } => codes::E0325,
+            ResolverError::NonConstantAssertMessage { .. } => codes::E0326,
+            ResolverError::MutableReferenceToArrayElement { .. } => codes::E0327,
+            ResolverError::ContractFunctionInternalInNormalFunction { .. } => codes::E0328,
+            ResolverError::NumericConstantInFormatString { .. } => codes::E0329,
+            ResolverError::InvalidClosureEnvironment { .. } => codes::E0330,
+            ResolverError::PrivateFunctionCalled { .. } => codes::E0331,
+            ResolverError::NonCrateFunctionCalled { .. } => codes::E0332,
+            ResolverError::InvalidTypeForEntryPoint { .. } => codes::E0333,
+            ResolverError::DestructuredEntryPointParameter { .. } => codes::E0334,
+            ResolverError::UnboundedWhileLoop { .. } => codes::E0335,
+            ResolverError::UnboundedLoop { .. } => codes::E0336,
+            ResolverError::JumpOutsideLoop { .. } => codes::E0337,
+            ResolverError::NoSuchVariant { .. } => codes::E0338,
+            ResolverError::ExpectedEnumVariant { .. } => codes::E0339,
+            ResolverError::GlobalDependencyCycle { .. } => codes::E0340,
+            ResolverError::AssignmentInUnconstrainedBlock { .. } => codes::E0341,
+            ResolverError::ReturnInUnconstrainedBlock { .. } => codes::E0342,
+            ResolverError::OracleNotUnconstrained { .. } => codes::E0343,
+            ResolverError::OracleWithBody { .. } => codes::E0344,
+            ResolverError::DestructuredOracleParameter { .. } => codes::E0345,
+            ResolverError::NotAComptimeFunction { .. } => codes::E0346,
+            ResolverError::ComptimeFnFailed { .. } => codes::E0347,
+            ResolverError::UnusedImport { .. } => codes::E0348,
+            ResolverError::UnusedFunction { .. } => codes::E0349,
+            ResolverError::NestedTry { .. } => codes::E0350,
+        }
+    }
 }
 
 impl From<ResolverError> for Diagnostic {
@@ -95,7 +210,8 @@ impl From<ResolverError> for Diagnostic {
     /// ICEs will make the compiler panic, as they could affect the
     /// soundness of the generated program
     fn from(error: ResolverError) -> Diagnostic {
-        match error {
+        let code = error.code();
+        let diagnostic = match error {
             ResolverError::DuplicateDefinition { name, first_span, second_span } => {
                 let mut diag = Diagnostic::simple_error(
                     format!("duplicate definitions of {name} found"),
@@ -108,17 +224,33 @@ impl From<ResolverError> for Diagnostic {
             ResolverError::UnusedVariable { ident } => {
                 let name = &ident.0.contents;
 
-                Diagnostic::simple_warning(
+                let mut diagnostic = Diagnostic::simple_warning(
                     format!("unused variable {name}"),
                     "unused variable ".to_string(),
                     ident.span(),
-                )
+                );
+                diagnostic.add_suggestion(Suggestion::new(
+                    "if this is intentional, prefix it with an underscore",
+                    ident.span(),
+                    format!("_{name}"),
+                    Applicability::MachineApplicable,
+                ));
+                diagnostic
             }
-            ResolverError::VariableNotDeclared { name, span } => Diagnostic::simple_error(
-                format!("cannot find `{name}` in this scope "),
-                "not found in this scope".to_string(),
+            ResolverError::UnreachableCode { span } => Diagnostic::simple_warning(
+                "unreachable code".to_string(),
+                "this code is never executed".to_string(),
                 span,
             ),
+            ResolverError::VariableNotDeclared { name, span, suggestions } => {
+                let mut diagnostic = Diagnostic::simple_error(
+                    format!("cannot find `{name}` in this scope "),
+                    "not found in this scope".to_string(),
+                    span,
+                );
+                suggestions.add_to(&mut diagnostic, span);
+                diagnostic
+            }
             ResolverError::PathIsNotIdent { span } => Diagnostic::simple_error(
                 "cannot use path as an identifier".to_string(),
                 String::new(),
@@ -212,6 +344,18 @@ impl From<ResolverError> for Diagnostic {
                 diag.add_note("The `distinct` keyword is only valid when used on the main function of a program, as its only purpose is to ensure that all witness indices that occur in the abi are unique".to_owned());
                 diag
             }
+            ResolverError::DataBusNotAllowed { ident, visibility } => {
+                let name = &ident.0.contents;
+
+                let mut diag = Diagnostic::simple_error(
+                    format!("Invalid `{visibility}` keyword in function {name}"),
+                    format!("Invalid {visibility} visibility"),
+                    ident.0.span(),
+                );
+
+                diag.add_note("The `call_data` and `return_data` keywords are only valid on the main function of a program, as they place its public inputs and outputs on the databus".to_owned());
+                diag
+            }
             ResolverError::MissingRhsExpr { name, span } => Diagnostic::simple_error(
                 format!(
                     "no expression specifying the value stored by the constant variable {name}"
@@ -278,8 +422,13 @@ impl From<ResolverError> for Diagnostic {
             ResolverError::MutableReferenceToImmutableVariable { variable, span } => {
                 Diagnostic::simple_error(format!("Cannot mutably reference the immutable variable {variable}"), format!("{variable} is immutable"), span)
             },
+            ResolverError::NonConstantAssertMessage { span } => Diagnostic::simple_error(
+                "Assertion messages must be strings known at compile-time".into(),
+                "Use a string literal, a format string or a global string".into(),
+                span,
+            ),
             ResolverError::MutableReferenceToArrayElement { span } => {
-                Diagnostic::simple_error("Mutable references to array elements are currently unsupported".into(), "Try storing the element in a fresh variable first".into(), span)
+                Diagnostic::simple_error("Mutable references to array elements are only supported as function arguments".into(), "Try storing the element in a fresh variable first".into(), span)
             },
             ResolverError::ContractFunctionInternalInNormalFunction { span } => Diagnostic::simple_error(
                 "Only functions defined within contracts can set their functions to be internal".into(),
@@ -294,16 +443,123 @@ impl From<ResolverError> for Diagnostic {
             ResolverError::InvalidClosureEnvironment { span, typ } => Diagnostic::simple_error(
                 format!("{typ} is not a valid closure environment type"),
                 "Closure environment must be a tuple or unit type".to_string(), span),
-            // This will be upgraded to an error in future versions
-            ResolverError::PrivateFunctionCalled { span, name } => Diagnostic::simple_warning(
-                format!("{name} is private and not visible from the current module"),
-                format!("{name} is private"), span),
-            ResolverError::NonCrateFunctionCalled { span, name } => Diagnostic::simple_warning(
-                    format!("{name} is not visible from the current crate"),
-                    format!("{name} is only visible within its crate"), span),
+            ResolverError::PrivateFunctionCalled { span, name } => Diagnostic::simple_error(
+                format!("function {name} is private"),
+                format!("{name} is only visible within its module and any child modules"), span),
+            ResolverError::NonCrateFunctionCalled { span, name } => Diagnostic::simple_error(
+                format!("function {name} is not visible from the current crate"),
+                format!("{name} is only visible within its crate"), span),
             ResolverError::InvalidTypeForEntryPoint { span } => Diagnostic::simple_error(
                 "Only sized types may be used in the entry point to a program".to_string(),
                 "Slices, references, or any type containing them may not be used in main or a contract function".to_string(), span),
-        }
+            ResolverError::DestructuredEntryPointParameter { span } => Diagnostic::simple_error(
+                "Parameters of the entry point to a program must be named".to_string(),
+                "Program inputs are matched to parameters by name, destructure this parameter within the function body instead".to_string(), span),
+            ResolverError::UnboundedWhileLoop { span } => Diagnostic::simple_error(
+                "while loops in constrained code require an upper bound on their number of iterations".to_string(),
+                "Add a #[max_iterations(N)] attribute to the loop or compare against an integer literal, e.g. `i < 10`".to_string(), span),
+            ResolverError::UnboundedLoop { span } => Diagnostic::simple_error(
+                "loops in constrained code require a #[max_iterations(N)] attribute".to_string(),
+                "The loop is unrolled so it needs an upper bound on its number of iterations".to_string(), span),
+            ResolverError::JumpOutsideLoop { keyword, span } => Diagnostic::simple_error(
+                format!("`{keyword}` is only allowed within a loop"),
+                format!("`{keyword}` outside of a loop"), span),
+            ResolverError::NoSuchVariant { variant, enum_definition } => {
+                let mut error = Diagnostic::simple_error(
+                    format!("no such variant {variant} defined in enum {enum_definition}"),
+                    String::new(),
+                    variant.span(),
+                );
+
+                error.add_secondary(
+                    format!("{enum_definition} defined here with no {variant} variant"),
+                    enum_definition.span(),
+                );
+                error
+            }
+            ResolverError::ExpectedEnumVariant { path } => Diagnostic::simple_error(
+                format!("expected an enum variant, found {path}"),
+                "Only enum variants can be matched with a payload pattern".to_string(),
+                path.span(),
+            ),
+            ResolverError::GlobalDependencyCycle { span } => Diagnostic::simple_error(
+                "global depends on its own value".to_string(),
+                "Globals may only refer to globals which do not depend on them".to_string(),
+                span,
+            ),
+            ResolverError::AssignmentInUnconstrainedBlock { name, span } => Diagnostic::simple_error(
+                format!("cannot assign to `{name}` within an unconstrained block"),
+                "Variables declared outside of the block are copied into it, return the new value from the block instead".to_string(),
+                span,
+            ),
+            ResolverError::ReturnInUnconstrainedBlock { span } => Diagnostic::simple_error(
+                "`return` is not allowed within an unconstrained block".to_string(),
+                "The block's value is the value of its last expression".to_string(),
+                span,
+            ),
+            ResolverError::OracleNotUnconstrained { ident } => Diagnostic::simple_error(
+                format!("Oracle function `{ident}` must be unconstrained"),
+                "Oracle calls can only be made from unconstrained code, mark this function as `unconstrained`".to_string(),
+                ident.span(),
+            ),
+            ResolverError::OracleWithBody { ident } => Diagnostic::simple_error(
+                format!("Oracle function `{ident}` cannot have a body"),
+                "Oracles are resolved outside of the program, remove the statements from this function".to_string(),
+                ident.span(),
+            ),
+            ResolverError::DestructuredOracleParameter { span } => Diagnostic::simple_error(
+                "Parameters of an oracle must be named".to_string(),
+                "Oracle parameter names are part of the program's ABI, destructure this parameter at the call site instead".to_string(),
+                span,
+            ),
+            ResolverError::NotAComptimeFunction { name, span } => Diagnostic::simple_error(
+                format!("`{name}` is not a comptime function"),
+                "Only functions declared with `comptime fn` can be called in array lengths and globals".to_string(),
+                span,
+            ),
+            ResolverError::ComptimeFnFailed { name, reason, span } => Diagnostic::simple_error(
+                format!("Failed to evaluate comptime function `{name}`"),
+                reason,
+                span,
+            ),
+            ResolverError::UnusedImport { ident, statement_span } => {
+                let mut diagnostic = Diagnostic::simple_warning(
+                    format!("unused import {ident}"),
+                    "unused import".to_string(),
+                    ident.span(),
+                );
+                if let Some(span) = statement_span {
+                    diagnostic.add_suggestion(Suggestion::new(
+                        "remove the unused import",
+                        span,
+                        "",
+                        Applicability::MachineApplicable,
+                    ));
+                }
+                diagnostic
+            }
+            ResolverError::UnusedFunction { ident } => {
+                let name = &ident.0.contents;
+
+                let mut diagnostic = Diagnostic::simple_warning(
+                    format!("function {name} is never used"),
+                    "unused function".to_string(),
+                    ident.span(),
+                );
+                diagnostic.add_suggestion(Suggestion::new(
+                    "if this is intentional, prefix it with an underscore",
+                    ident.span(),
+                    format!("_{name}"),
+                    Applicability::MachineApplicable,
+                ));
+                diagnostic
+            }
+            ResolverError::NestedTry { span } => Diagnostic::simple_error(
+                "`?` must be applied to the whole value of a `let` or an expression statement".into(),
+                "Bind the value with `let` first, e.g. `let value = expr?;`".into(),
+                span,
+            ),
+        };
+        diagnostic.with_code(code)
     }
 }
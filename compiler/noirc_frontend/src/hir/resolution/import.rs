@@ -1,25 +1,33 @@
 use iter_extended::partition_results;
-use noirc_errors::CustomDiagnostic;
+use noirc_errors::{error_codes as codes, CustomDiagnostic, Span};
 
 use crate::graph::CrateId;
 use std::collections::BTreeMap;
 
-use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId, PerNs};
+use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleData, ModuleDefId, ModuleId, PerNs};
 use crate::{Ident, Path, PathKind};
 
+use super::suggestions::{similar_names, NameSuggestions};
+
 #[derive(Debug, Clone)]
 pub struct ImportDirective {
     pub module_id: LocalModuleId,
     pub path: Path,
     pub alias: Option<Ident>,
+    /// True for `use path::*`, which imports every item of the module `path` refers to
+    pub is_glob: bool,
+    /// The span of the `use` statement this import was written in, if any
+    pub span: Option<Span>,
 }
 
 pub type PathResolution = Result<PerNs, PathResolutionError>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PathResolutionError {
-    Unresolved(Ident),
+    Unresolved(Ident, NameSuggestions),
     ExternalContractUsed(Ident),
+    Ambiguous(Ident),
+    GlobOfNonModule(Ident),
 }
 
 #[derive(Debug)]
@@ -30,22 +38,54 @@ pub struct ResolvedImport {
     pub resolved_namespace: PerNs,
     // The module which we must add the resolved namespace to
     pub module_scope: LocalModuleId,
+    // For glob imports, the items of the resolved module which are added to the module scope
+    pub glob_items: Option<Vec<(Ident, ModuleDefId)>>,
+    // The span of the `use` statement the import was written in, if any
+    pub span: Option<Span>,
+}
+
+impl PathResolutionError {
+    /// Returns the stable error code of this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PathResolutionError::Unresolved(..) => codes::E0250,
+            PathResolutionError::ExternalContractUsed(_) => codes::E0251,
+            PathResolutionError::Ambiguous(_) => codes::E0252,
+            PathResolutionError::GlobOfNonModule(_) => codes::E0253,
+        }
+    }
 }
 
 impl From<PathResolutionError> for CustomDiagnostic {
     fn from(error: PathResolutionError) -> Self {
-        match error {
-            PathResolutionError::Unresolved(ident) => CustomDiagnostic::simple_error(
-                format!("Could not resolve '{ident}' in path"),
-                String::new(),
-                ident.span(),
-            ),
+        let code = error.code();
+        let diagnostic = match error {
+            PathResolutionError::Unresolved(ident, suggestions) => {
+                let mut diagnostic = CustomDiagnostic::simple_error(
+                    format!("Could not resolve '{ident}' in path"),
+                    String::new(),
+                    ident.span(),
+                );
+                suggestions.add_to(&mut diagnostic, ident.span());
+                diagnostic
+            }
             PathResolutionError::ExternalContractUsed(ident) => CustomDiagnostic::simple_error(
                 format!("Contract variable '{ident}' referenced from outside the contract"),
                 "Contracts may only be referenced from within a contract".to_string(),
                 ident.span(),
             ),
-        }
+            PathResolutionError::Ambiguous(ident) => CustomDiagnostic::simple_error(
+                format!("'{ident}' is ambiguous"),
+                format!("'{ident}' is imported by several glob imports"),
+                ident.span(),
+            ),
+            PathResolutionError::GlobOfNonModule(ident) => CustomDiagnostic::simple_error(
+                format!("Cannot glob import from '{ident}'"),
+                "Only the items of a module can be imported with `*`".to_string(),
+                ident.span(),
+            ),
+        };
+        diagnostic.with_code(code)
     }
 }
 
@@ -66,10 +106,34 @@ pub fn resolve_imports(
                 .map_err(|error| (error, module_scope))?;
 
         let name = resolve_path_name(&import_directive);
-        Ok(ResolvedImport { name, resolved_namespace, module_scope })
+        let glob_items = if import_directive.is_glob {
+            let items = resolve_glob_items(&name, &resolved_namespace, def_maps)
+                .map_err(|error| (error, module_scope))?;
+            Some(items)
+        } else {
+            None
+        };
+
+        let span = import_directive.span;
+        Ok(ResolvedImport { name, resolved_namespace, module_scope, glob_items, span })
     })
 }
 
+/// Returns the items a glob import of `namespace` brings into scope.
+/// Only modules can be glob imported.
+fn resolve_glob_items(
+    name: &Ident,
+    namespace: &PerNs,
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
+) -> Result<Vec<(Ident, ModuleDefId)>, PathResolutionError> {
+    match namespace.types {
+        Some((ModuleDefId::ModuleId(module_id), _)) => {
+            Ok(module_id.module(def_maps).visible_items())
+        }
+        _ => Err(PathResolutionError::GlobOfNonModule(name.clone())),
+    }
+}
+
 pub(super) fn allow_referencing_contracts(
     def_maps: &BTreeMap<CrateId, CrateDefMap>,
     krate: CrateId,
@@ -135,14 +199,23 @@ fn resolve_name_in_module(
 
     let mut import_path = import_path.iter();
     let first_segment = import_path.next().expect("ice: could not fetch first segment");
+    if current_mod.is_ambiguous(first_segment) {
+        return Err(PathResolutionError::Ambiguous(first_segment.clone()));
+    }
+    current_mod.mark_name_used(first_segment);
     let mut current_ns = current_mod.find_name(first_segment);
     if current_ns.is_none() {
-        return Err(PathResolutionError::Unresolved(first_segment.clone()));
+        return Err(unresolved_in_module(first_segment, current_mod));
     }
 
     for segment in import_path {
         let typ = match current_ns.take_types() {
-            None => return Err(PathResolutionError::Unresolved(segment.clone())),
+            None => {
+                return Err(PathResolutionError::Unresolved(
+                    segment.clone(),
+                    NameSuggestions::default(),
+                ))
+            }
             Some(typ) => typ,
         };
 
@@ -159,11 +232,16 @@ fn resolve_name_in_module(
 
         current_mod = &def_maps[&new_module_id.krate].modules[new_module_id.local_id.0];
 
+        if current_mod.is_ambiguous(segment) {
+            return Err(PathResolutionError::Ambiguous(segment.clone()));
+        }
+
         // Check if namespace
+        current_mod.mark_name_used(segment);
         let found_ns = current_mod.find_name(segment);
 
         if found_ns.is_none() {
-            return Err(PathResolutionError::Unresolved(segment.clone()));
+            return Err(unresolved_in_module(segment, current_mod));
         }
         // Check if it is a contract and we're calling from a non-contract context
         if current_mod.is_contract && !allow_contracts {
@@ -175,6 +253,14 @@ fn resolve_name_in_module(
     Ok(current_ns)
 }
 
+/// Creates the error for `name` not being found in `module`, suggesting similar names in its scope.
+fn unresolved_in_module(name: &Ident, module: &ModuleData) -> PathResolutionError {
+    let items = module.visible_items();
+    let similar =
+        similar_names(&name.0.contents, items.iter().map(|(item, _)| item.0.contents.as_str()));
+    PathResolutionError::Unresolved(name.clone(), NameSuggestions { similar, imports: Vec::new() })
+}
+
 fn resolve_path_name(import_directive: &ImportDirective) -> Ident {
     match &import_directive.alias {
         None => import_directive.path.segments.last().unwrap().clone(),
@@ -194,17 +280,25 @@ fn resolve_external_dep(
     //
     // Fetch the root module from the prelude
     let crate_name = path.first().unwrap();
-    let dep_module = current_def_map
-        .extern_prelude
-        .get(&crate_name.0.contents)
-        .ok_or_else(|| PathResolutionError::Unresolved(crate_name.to_owned()))?;
+    let dep_module =
+        current_def_map.extern_prelude.get(&crate_name.0.contents).ok_or_else(|| {
+            let dependencies = current_def_map.extern_prelude.keys().map(String::as_str);
+            let similar = similar_names(&crate_name.0.contents, dependencies);
+            let suggestions = NameSuggestions { similar, imports: Vec::new() };
+            PathResolutionError::Unresolved(crate_name.to_owned(), suggestions)
+        })?;
 
     // Create an import directive for the dependency crate
     let path_without_crate_name = &path[1..]; // XXX: This will panic if the path is of the form `use dep::std` Ideal algorithm will not distinguish between crate and module
 
     let path = Path { segments: path_without_crate_name.to_vec(), kind: PathKind::Plain };
-    let dep_directive =
-        ImportDirective { module_id: dep_module.local_id, path, alias: directive.alias.clone() };
+    let dep_directive = ImportDirective {
+        module_id: dep_module.local_id,
+        path,
+        alias: directive.alias.clone(),
+        is_glob: directive.is_glob,
+        span: directive.span,
+    };
 
     let dep_def_map = def_maps.get(&dep_module.krate).unwrap();
 
@@ -5,7 +5,9 @@
 //! redefined multiple times in the same scope. Once this is validated, it is linked
 //! to that definition via a matching DefinitionId. All references to the same definition
 //! will have the same DefinitionId.
+mod comptime_fn;
 pub mod errors;
 pub mod import;
 pub mod path_resolver;
 pub mod resolver;
+pub mod suggestions;
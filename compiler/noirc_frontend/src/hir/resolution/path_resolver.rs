@@ -57,7 +57,13 @@ pub fn resolve_path(
     path: Path,
 ) -> Result<ModuleDefId, PathResolutionError> {
     // lets package up the path into an ImportDirective and resolve it using that
-    let import = ImportDirective { module_id: module_id.local_id, path, alias: None };
+    let import = ImportDirective {
+        module_id: module_id.local_id,
+        path,
+        alias: None,
+        is_glob: false,
+        span: None,
+    };
     let allow_referencing_contracts =
         allow_referencing_contracts(def_maps, module_id.krate, module_id.local_id);
 
@@ -13,9 +13,10 @@
 // XXX: Resolver does not check for unused functions
 use crate::hir_def::expr::{
     HirArrayLiteral, HirBinaryOp, HirBlockExpression, HirCallExpression, HirCapturedVar,
-    HirCastExpression, HirConstructorExpression, HirExpression, HirIdent, HirIfExpression,
-    HirIndexExpression, HirInfixExpression, HirLambda, HirLiteral, HirMemberAccess,
-    HirMethodCallExpression, HirPrefixExpression,
+    HirCastExpression, HirConstructorExpression, HirEnumConstructorExpression, HirExpression,
+    HirIdent, HirIfExpression, HirIndexExpression, HirInfixExpression, HirLambda, HirLiteral,
+    HirMatchArm, HirMatchExpression, HirMatchPattern, HirMemberAccess, HirMethodCallExpression,
+    HirPrefixExpression, HirTryExpression,
 };
 
 use crate::hir_def::traits::{Trait, TraitConstraint};
@@ -24,27 +25,29 @@ use regex::Regex;
 use std::collections::{BTreeMap, HashSet};
 use std::rc::Rc;
 
-use crate::graph::CrateId;
+use crate::graph::{CrateId, OverflowMode};
 use crate::hir::def_map::{LocalModuleId, ModuleDefId, TryFromModuleDefId, MAIN_FUNCTION};
-use crate::hir_def::stmt::{HirAssignStatement, HirForStatement, HirLValue, HirPattern};
+use crate::hir_def::stmt::{
+    HirAssignStatement, HirForStatement, HirLValue, HirLoopStatement, HirPattern, HirWhileStatement,
+};
 use crate::node_interner::{
-    DefinitionId, DefinitionKind, ExprId, FuncId, NodeInterner, StmtId, StructId, TraitId,
-    TraitImplId, TraitImplKind,
+    DefinitionId, DefinitionKind, ExprId, FuncId, NodeInterner, ReferenceId, StmtId, StructId,
+    TraitId, TraitImplId, TraitImplKind,
 };
 use crate::{
     hir::{def_map::CrateDefMap, resolution::path_resolver::PathResolver},
-    BlockExpression, Expression, ExpressionKind, FunctionKind, Ident, Literal, NoirFunction,
-    StatementKind,
+    BlockExpression, Expression, ExpressionKind, FunctionKind, Ident, InfixExpression, Literal,
+    MemberAccessExpression, NoirFunction, StatementKind,
 };
 use crate::{
-    ArrayLiteral, ContractFunctionType, Distinctness, ForRange, FunctionVisibility, Generics,
-    LValue, NoirStruct, NoirTypeAlias, Param, Path, PathKind, Pattern, Shared, StructType, Type,
-    TypeAliasType, TypeBinding, TypeVariable, UnaryOp, UnresolvedGenerics,
-    UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression,
-    Visibility, ERROR_IDENT,
+    ArrayLiteral, BinaryOpKind, ContractFunctionType, Distinctness, ForRange, FunctionVisibility,
+    Generics, LValue, MatchPattern, NoirEnum, NoirStruct, NoirTypeAlias, Param, Path, PathKind,
+    Pattern, Shared, Statement, StructType, Type, TypeAliasType, TypeBinding, TypeVariable,
+    UnaryOp, UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData,
+    UnresolvedTypeExpression, Visibility, ERROR_IDENT,
 };
 use fm::FileId;
-use iter_extended::vecmap;
+use iter_extended::{try_vecmap, vecmap};
 use noirc_errors::{Location, Span, Spanned};
 
 use crate::hir::scope::{
@@ -55,7 +58,10 @@ use crate::hir_def::{
     stmt::{HirConstrainStatement, HirLetStatement, HirStatement},
 };
 
+use super::comptime_fn::ComptimeFnEvaluator;
 use super::errors::{PubPosition, ResolverError};
+use super::import::PathResolutionError;
+use super::suggestions::{importable_items, similar_names, ImportSuggestion, NameSuggestions};
 
 const SELF_TYPE_NAME: &str = "Self";
 
@@ -68,6 +74,8 @@ pub struct LambdaContext {
     /// the index in the scope tree
     /// (sometimes being filled by ScopeTree's find method)
     scope_index: usize,
+    /// True if this is the lambda an `unconstrained { ... }` block is lowered to
+    unconstrained: bool,
 }
 
 /// The primary jobs of the Resolver are to validate that every variable found refers to exactly 1
@@ -101,6 +109,10 @@ pub struct Resolver<'a> {
     /// for these so we can still resolve them in the parent module without them being in a contract.
     in_contract: bool,
 
+    /// Whether `+`, `-` and `*` on integers wrap on overflow in the crate being resolved.
+    /// If so they are resolved to their wrapping counterparts.
+    overflow_mode: OverflowMode,
+
     /// Contains a mapping of the current struct or functions's generics to
     /// unique type variables if we're resolving a struct. Empty otherwise.
     /// This is a Vec rather than a map to preserve the order a functions generics
@@ -111,6 +123,24 @@ pub struct Resolver<'a> {
     /// that are captured. We do this in order to create the hidden environment
     /// parameter for the lambda function.
     lambda_stack: Vec<LambdaContext>,
+
+    /// True while resolving the body of an unconstrained (or open contract) function.
+    /// Loops in such functions are not unrolled so they need no upper bound.
+    in_unconstrained_fn: bool,
+
+    /// The number of loops enclosing the current statement, used to check that
+    /// `break` and `continue` only occur within loops.
+    loop_depth: usize,
+
+    /// True if the value of the expression about to be resolved is only bound by a `let` or
+    /// discarded, possibly after passing through the blocks and branches enclosing it.
+    /// In constrained code `?` is only allowed in this position, since the rest of an enclosing
+    /// expression would still be evaluated after it returns.
+    in_statement_position: bool,
+
+    /// True if the statements of the block being resolved are in statement position,
+    /// see `in_statement_position`.
+    in_statement_block: bool,
 }
 
 /// ResolverMetas are tagged onto each definition to track how many times they are used
@@ -135,6 +165,7 @@ impl<'a> Resolver<'a> {
     ) -> Resolver<'a> {
         let module_id = path_resolver.module_id();
         let in_contract = module_id.module(def_maps).is_contract;
+        let overflow_mode = def_maps[&module_id.krate].overflow_mode;
 
         Self {
             path_resolver,
@@ -150,6 +181,11 @@ impl<'a> Resolver<'a> {
             current_trait_impl: None,
             file,
             in_contract,
+            overflow_mode,
+            in_unconstrained_fn: false,
+            loop_depth: 0,
+            in_statement_position: false,
+            in_statement_block: true,
         }
     }
 
@@ -173,6 +209,10 @@ impl<'a> Resolver<'a> {
         self.errors.push(err);
     }
 
+    fn add_reference(&mut self, id: ReferenceId, span: Span) {
+        self.interner.add_reference(id, Location::new(span, self.file));
+    }
+
     /// Resolving a function involves interning the metadata
     /// interning any statements inside of the function
     /// and interning the function itself
@@ -191,10 +231,14 @@ impl<'a> Resolver<'a> {
         self.add_generics(&func.def.generics);
         self.trait_bounds = func.def.where_clause.clone();
 
+        let is_oracle = func.kind == FunctionKind::Oracle;
         let (hir_func, func_meta) = self.intern_function(func, func_id);
         let func_scope_tree = self.scopes.end_function();
 
-        self.check_for_unused_variables_in_scope_tree(func_scope_tree);
+        // An oracle's parameters are never used, their names only document its ABI
+        if !is_oracle {
+            self.check_for_unused_variables_in_scope_tree(func_scope_tree);
+        }
 
         self.trait_bounds.clear();
         (hir_func, func_meta, self.errors)
@@ -256,8 +300,13 @@ impl<'a> Resolver<'a> {
             return self.add_global_variable_decl(name, definition);
         }
 
-        let id = self.interner.push_definition(name.0.contents.clone(), mutable, definition);
         let location = Location::new(name.span(), self.file);
+        let id = self.interner.push_definition(
+            name.0.contents.clone(),
+            mutable,
+            definition,
+            Some(location),
+        );
         let ident = HirIdent { location, id };
         let resolver_meta = ResolverMeta { num_times_used: 0, ident, warn_if_unused };
 
@@ -300,8 +349,13 @@ impl<'a> Resolver<'a> {
             ident = hir_let_stmt.ident();
             resolver_meta = ResolverMeta { num_times_used: 0, ident, warn_if_unused: true };
         } else {
-            let id = self.interner.push_definition(name.0.contents.clone(), false, definition);
             let location = Location::new(name.span(), self.file);
+            let id = self.interner.push_definition(
+                name.0.contents.clone(),
+                false,
+                definition,
+                Some(location),
+            );
             ident = HirIdent { location, id };
             resolver_meta = ResolverMeta { num_times_used: 0, ident, warn_if_unused: true };
         }
@@ -342,15 +396,22 @@ impl<'a> Resolver<'a> {
         if let Some((variable_found, scope)) = variable {
             variable_found.num_times_used += 1;
             let id = variable_found.ident.id;
+            self.add_reference(ReferenceId::Definition(id), name.span());
             Ok((HirIdent { location, id }, scope))
         } else {
-            Err(ResolverError::VariableNotDeclared {
-                name: name.0.contents.clone(),
-                span: name.0.span(),
-            })
+            Err(self.variable_not_declared(&name.0.contents, name.0.span()))
         }
     }
 
+    /// Creates the error for `name` not being declared, suggesting similarly named variables in scope.
+    fn variable_not_declared(&mut self, name: &str, span: Span) -> ResolverError {
+        let scope_tree = self.scopes.current_scope_tree();
+        let variables = scope_tree.0.iter().flat_map(|scope| scope.0.keys().map(String::as_str));
+        let similar = similar_names(name, variables);
+        let suggestions = NameSuggestions { similar, imports: Vec::new() };
+        ResolverError::VariableNotDeclared { name: name.to_owned(), span, suggestions }
+    }
+
     fn intern_function(&mut self, func: NoirFunction, id: FuncId) -> (HirFunction, FuncMeta) {
         let func_meta = self.extract_meta(&func, id);
         let hir_func = match func.kind {
@@ -358,7 +419,9 @@ impl<'a> Resolver<'a> {
                 HirFunction::empty()
             }
             FunctionKind::Normal => {
+                self.in_unconstrained_fn = func.def.is_unconstrained || func.def.is_open;
                 let expr_id = self.intern_block(func.def.body);
+                self.in_unconstrained_fn = false;
                 self.interner.push_expr_location(expr_id, func.def.span, self.file);
                 HirFunction::unchecked_from_expr(expr_id)
             }
@@ -372,8 +435,10 @@ impl<'a> Resolver<'a> {
         constraint: UnresolvedTraitConstraint,
     ) -> Option<TraitConstraint> {
         let typ = self.resolve_type(constraint.typ);
+        let trait_generics =
+            vecmap(constraint.trait_bound.trait_generics, |generic| self.resolve_type(generic));
         let trait_id = self.lookup_trait_or_error(constraint.trait_bound.trait_path)?.id;
-        Some(TraitConstraint { typ, trait_id })
+        Some(TraitConstraint { typ, trait_id, trait_generics })
     }
 
     /// Translates an UnresolvedType into a Type and appends any
@@ -550,7 +615,10 @@ impl<'a> Resolver<'a> {
         if path.segments.len() == 1 {
             let name = &path.last_segment().0.contents;
             if let Some((name, var, _)) = self.find_generic(name) {
-                return Some(Type::NamedGeneric(var.clone(), name.clone()));
+                let typ = Type::NamedGeneric(var.clone(), name.clone());
+                let location = Location::new(path.span(), self.file);
+                self.interner.add_type_parameter_location(location);
+                return Some(typ);
             }
         }
 
@@ -609,21 +677,88 @@ impl<'a> Resolver<'a> {
                     }
                 }
             }
+            UnresolvedTypeExpression::Call(path, arguments, span) => {
+                let arguments = try_vecmap(arguments, |argument| {
+                    let argument_span = argument.span();
+                    match self.convert_expression_type(argument) {
+                        Type::Constant(argument) => Ok(argument as u128),
+                        _ => Err(argument_span),
+                    }
+                });
+
+                match arguments {
+                    Ok(arguments) => self.eval_comptime_call(path, arguments, span),
+                    Err(span) => {
+                        self.push_err(ResolverError::InvalidArrayLengthExpr { span });
+                        Type::Constant(0)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Evaluates a call to a `comptime fn` within an array length
+    fn eval_comptime_call(&mut self, path: Path, arguments: Vec<u128>, span: Span) -> Type {
+        let function = match self.path_resolver.resolve(self.def_maps, path.clone()) {
+            Ok(ModuleDefId::FunctionId(function)) => function,
+            Ok(_) => {
+                let name = path.to_string();
+                self.push_err(ResolverError::NotAComptimeFunction { name, span });
+                return Type::Constant(0);
+            }
+            Err(error) => {
+                self.push_err(ResolverError::PathResolutionError(error));
+                return Type::Constant(0);
+            }
+        };
+
+        self.interner.mark_function_referenced(function);
+        let mut evaluator = ComptimeFnEvaluator::new(self.interner, self.def_maps, span);
+        match evaluator.call(function, arguments).map(|length| length.try_into()) {
+            Ok(Ok(length)) => Type::Constant(length),
+            Ok(Err(_cast_err)) => {
+                self.push_err(ResolverError::IntegerTooLarge { span });
+                Type::Constant(0)
+            }
+            Err(error) => {
+                self.push_err(error);
+                Type::Constant(0)
+            }
         }
     }
 
     fn get_ident_from_path(&mut self, path: Path) -> (HirIdent, usize) {
         let location = Location::new(path.span(), self.file);
+        let name_span = path.last_segment().span();
 
         let error = match path.as_ident().map(|ident| self.find_variable(ident)) {
             Some(Ok(found)) => return found,
             // Try to look it up as a global, but still issue the first error if we fail
-            Some(Err(error)) => match self.lookup_global(path) {
-                Ok(id) => return (HirIdent { location, id }, 0),
-                Err(_) => error,
+            Some(Err(mut error)) => match self.lookup_global(path) {
+                Ok(id) => {
+                    self.add_reference(ReferenceId::Definition(id), name_span);
+                    return (HirIdent { location, id }, 0);
+                }
+                Err(global_error) => {
+                    // The name may have been meant to refer to an item rather than a variable
+                    if let (
+                        ResolverError::VariableNotDeclared { suggestions, .. },
+                        ResolverError::PathResolutionError(PathResolutionError::Unresolved(
+                            _,
+                            item_suggestions,
+                        )),
+                    ) = (&mut error, global_error)
+                    {
+                        suggestions.extend(item_suggestions);
+                    }
+                    error
+                }
             },
             None => match self.lookup_global(path) {
-                Ok(id) => return (HirIdent { location, id }, 0),
+                Ok(id) => {
+                    self.add_reference(ReferenceId::Definition(id), name_span);
+                    return (HirIdent { location, id }, 0);
+                }
                 Err(error) => error,
             },
         };
@@ -683,6 +818,7 @@ impl<'a> Resolver<'a> {
             let id = self.interner.next_type_variable_id();
             let typevar = Shared::new(TypeBinding::Unbound(id));
             let span = generic.0.span();
+            self.interner.add_type_parameter_location(Location::new(span, self.file));
 
             // Check for name collisions of this generic
             let name = Rc::new(generic.0.contents.clone());
@@ -716,6 +852,33 @@ impl<'a> Resolver<'a> {
         (generics, fields, self.errors)
     }
 
+    pub fn resolve_enum_variants(
+        mut self,
+        unresolved: NoirEnum,
+    ) -> (Generics, Vec<(Ident, Vec<Type>)>, Vec<ResolverError>) {
+        let generics = self.add_generics(&unresolved.generics);
+
+        // Check whether the enum definition has globals in the local module and add them to the scope
+        self.resolve_local_globals();
+
+        let mut variants: Vec<(Ident, Vec<Type>)> = Vec::new();
+        for (name, fields) in unresolved.variants {
+            let previous = variants.iter().find(|(other, _)| other.0.contents == name.0.contents);
+            if let Some((first, _)) = previous {
+                self.errors.push(ResolverError::DuplicateDefinition {
+                    name: name.0.contents.clone(),
+                    first_span: first.span(),
+                    second_span: name.span(),
+                });
+            }
+
+            let fields = vecmap(fields, |typ| self.resolve_type(typ));
+            variants.push((name, fields));
+        }
+
+        (generics, variants, self.errors)
+    }
+
     fn resolve_local_globals(&mut self) {
         for (stmt_id, global_info) in self.interner.get_all_globals() {
             if global_info.local_id == self.path_resolver.local_module_id() {
@@ -735,6 +898,9 @@ impl<'a> Resolver<'a> {
         vecmap(where_clause, |constraint| TraitConstraint {
             typ: self.resolve_type(constraint.typ.clone()),
             trait_id: constraint.trait_bound.trait_id.unwrap_or_else(TraitId::dummy_id),
+            trait_generics: vecmap(&constraint.trait_bound.trait_generics, |generic| {
+                self.resolve_type(generic.clone())
+            }),
         })
     }
 
@@ -749,6 +915,12 @@ impl<'a> Resolver<'a> {
 
         let attributes = func.attributes().clone();
 
+        // Lint attributes apply to the whole function, from its name to the end of its body
+        let function_span = func.name_ident().span().merge(func.span());
+        for (lint, level) in attributes.lint_levels() {
+            self.interner.lint_levels_mut().add_item_level(self.file, function_span, lint, level);
+        }
+
         let mut generics =
             vecmap(self.generics.clone(), |(name, typevar, _)| match &*typevar.borrow() {
                 TypeBinding::Unbound(id) => (*id, typevar.clone()),
@@ -768,8 +940,23 @@ impl<'a> Resolver<'a> {
                 });
             }
 
+            if visibility == Visibility::CallData && !self.databus_allowed(func) {
+                let ident = func.name_ident().clone();
+                self.push_err(ResolverError::DataBusNotAllowed { ident, visibility });
+            }
+
             if self.is_entry_point_function(func) {
                 self.verify_type_valid_for_program_input(&typ);
+
+                if !is_named_pattern(&pattern) {
+                    let span = pattern.span();
+                    self.push_err(ResolverError::DestructuredEntryPointParameter { span });
+                }
+            }
+
+            if func.kind == FunctionKind::Oracle && !is_named_pattern(&pattern) {
+                let span = pattern.span();
+                self.push_err(ResolverError::DestructuredOracleParameter { span });
             }
 
             let pattern = self.resolve_pattern(pattern, DefinitionKind::Local(None));
@@ -781,6 +968,17 @@ impl<'a> Resolver<'a> {
 
         let return_type = Box::new(self.resolve_type(func.return_type()));
 
+        if func.kind == FunctionKind::Oracle {
+            if !func.def.is_unconstrained {
+                let ident = func.name_ident().clone();
+                self.push_err(ResolverError::OracleNotUnconstrained { ident });
+            }
+            if !func.def.body.is_empty() {
+                let ident = func.name_ident().clone();
+                self.push_err(ResolverError::OracleWithBody { ident });
+            }
+        }
+
         self.declare_numeric_generics(&parameter_types, &return_type);
 
         if !self.pub_allowed(func) && func.def.return_visibility == Visibility::Public {
@@ -790,10 +988,16 @@ impl<'a> Resolver<'a> {
             });
         }
 
+        if func.def.return_visibility == Visibility::ReturnData && !self.databus_allowed(func) {
+            let ident = func.name_ident().clone();
+            let visibility = Visibility::ReturnData;
+            self.push_err(ResolverError::DataBusNotAllowed { ident, visibility });
+        }
+
         // 'pub_allowed' also implies 'pub' is required on return types
         if self.pub_allowed(func)
             && return_type.as_ref() != &Type::Unit
-            && func.def.return_visibility != Visibility::Public
+            && !func.def.return_visibility.is_public()
         {
             self.push_err(ResolverError::NecessaryPub { ident: func.name_ident().clone() });
         }
@@ -854,6 +1058,11 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    /// Values can only be placed on the databus by the main function of a program
+    fn databus_allowed(&self, func: &NoirFunction) -> bool {
+        !self.in_contract && func.name() == MAIN_FUNCTION
+    }
+
     fn is_entry_point_function(&self, func: &NoirFunction) -> bool {
         if self.in_contract {
             func.attributes().is_contract_entry_point()
@@ -1004,6 +1213,7 @@ impl<'a> Resolver<'a> {
     pub fn resolve_stmt(&mut self, stmt: StatementKind) -> HirStatement {
         match stmt {
             StatementKind::Let(let_stmt) => {
+                self.in_statement_position = self.in_statement_block;
                 let expression = self.resolve_expression(let_stmt.expression);
                 let definition = DefinitionKind::Local(Some(expression));
                 HirStatement::Let(HirLetStatement {
@@ -1013,14 +1223,47 @@ impl<'a> Resolver<'a> {
                 })
             }
             StatementKind::Constrain(constrain_stmt) => {
+                let assert_message = match constrain_stmt.1 {
+                    Some(Expression {
+                        kind: ExpressionKind::Literal(Literal::Str(message)),
+                        ..
+                    }) => Some(message),
+                    // Format strings are only resolved at runtime if the condition fails.
+                    Some(Expression {
+                        kind: ExpressionKind::Literal(Literal::FmtStr(message)),
+                        ..
+                    }) => {
+                        return self.resolve_constrain_with_format_string(constrain_stmt.0, message)
+                    }
+                    // Any other message must be a string known at compile-time,
+                    // such as the concatenation of a literal and a global.
+                    Some(message) => {
+                        let span = message.span;
+                        let message = self.resolve_expression(message);
+                        let message = self.interner.compile_time_string(&message);
+                        if message.is_none() {
+                            self.push_err(ResolverError::NonConstantAssertMessage { span });
+                        }
+                        message
+                    }
+                    None => None,
+                };
                 let expr_id = self.resolve_expression(constrain_stmt.0);
-                let assert_message = constrain_stmt.1;
-                HirStatement::Constrain(HirConstrainStatement(expr_id, self.file, assert_message))
+                HirStatement::Constrain(HirConstrainStatement(
+                    expr_id,
+                    self.file,
+                    assert_message,
+                    None,
+                ))
             }
             StatementKind::Expression(expr) => {
+                self.in_statement_position = self.in_statement_block;
                 HirStatement::Expression(self.resolve_expression(expr))
             }
-            StatementKind::Semi(expr) => HirStatement::Semi(self.resolve_expression(expr)),
+            StatementKind::Semi(expr) => {
+                self.in_statement_position = self.in_statement_block;
+                HirStatement::Semi(self.resolve_expression(expr))
+            }
             StatementKind::Assign(assign_stmt) => {
                 let identifier = self.resolve_lvalue(assign_stmt.lvalue);
                 let expression = self.resolve_expression(assign_stmt.expression);
@@ -1033,6 +1276,7 @@ impl<'a> Resolver<'a> {
                         let start_range = self.resolve_expression(start_range);
                         let end_range = self.resolve_expression(end_range);
                         let (identifier, block) = (for_loop.identifier, for_loop.block);
+                        let max_iterations = for_loop.max_iterations;
 
                         // TODO: For loop variables are currently mutable by default since we haven't
                         //       yet implemented syntax for them to be optionally mutable.
@@ -1043,7 +1287,7 @@ impl<'a> Resolver<'a> {
                                 true,
                                 DefinitionKind::Local(None),
                             );
-                            (decl, this.resolve_expression(block))
+                            (decl, this.resolve_loop_body(block))
                         });
 
                         HirStatement::For(HirForStatement {
@@ -1051,6 +1295,7 @@ impl<'a> Resolver<'a> {
                             end_range,
                             block,
                             identifier,
+                            max_iterations,
                         })
                     }
                     range @ ForRange::Array(_) => {
@@ -1060,10 +1305,70 @@ impl<'a> Resolver<'a> {
                     }
                 }
             }
+            StatementKind::While(while_loop) => {
+                let max_iterations = while_loop
+                    .max_iterations
+                    .or_else(|| infer_max_iterations(&while_loop.condition));
+
+                if max_iterations.is_none() && !self.in_unconstrained_fn {
+                    let span = while_loop.condition.span;
+                    self.push_err(ResolverError::UnboundedWhileLoop { span });
+                }
+
+                let condition = self.resolve_expression(while_loop.condition);
+                let block = self.resolve_loop_body(while_loop.block);
+                HirStatement::While(HirWhileStatement { condition, block, max_iterations })
+            }
+            StatementKind::Loop(loop_statement) => {
+                if loop_statement.max_iterations.is_none() && !self.in_unconstrained_fn {
+                    let span = loop_statement.span;
+                    self.push_err(ResolverError::UnboundedLoop { span });
+                }
+
+                let block = self.resolve_loop_body(loop_statement.block);
+                let max_iterations = loop_statement.max_iterations;
+                HirStatement::Loop(HirLoopStatement { block, max_iterations })
+            }
+            StatementKind::Break(span) => {
+                if self.loop_depth == 0 {
+                    self.push_err(ResolverError::JumpOutsideLoop { keyword: "break", span });
+                }
+                HirStatement::Break
+            }
+            StatementKind::Continue(span) => {
+                if self.loop_depth == 0 {
+                    self.push_err(ResolverError::JumpOutsideLoop { keyword: "continue", span });
+                }
+                HirStatement::Continue
+            }
+            StatementKind::Return(return_statement) => {
+                if self.lambda_stack.last().map_or(false, |lambda| lambda.unconstrained) {
+                    let span = return_statement.span;
+                    self.push_err(ResolverError::ReturnInUnconstrainedBlock { span });
+                }
+                let expression = match return_statement.expression {
+                    Some(expression) => self.resolve_expression(expression),
+                    None => {
+                        let unit =
+                            self.interner.push_expr(HirExpression::Literal(HirLiteral::Unit));
+                        self.interner.push_expr_location(unit, return_statement.span, self.file);
+                        unit
+                    }
+                };
+                HirStatement::Return(expression)
+            }
             StatementKind::Error => HirStatement::Error,
         }
     }
 
+    fn resolve_loop_body(&mut self, body: Expression) -> ExprId {
+        self.loop_depth += 1;
+        self.in_statement_position = self.in_statement_block;
+        let body = self.resolve_expression(body);
+        self.loop_depth -= 1;
+        body
+    }
+
     pub fn intern_stmt(&mut self, stmt: StatementKind) -> StmtId {
         let hir_stmt = self.resolve_stmt(stmt);
         self.interner.push_stmt(hir_stmt)
@@ -1072,10 +1377,22 @@ impl<'a> Resolver<'a> {
     fn resolve_lvalue(&mut self, lvalue: LValue) -> HirLValue {
         match lvalue {
             LValue::Ident(ident) => {
-                let ident = self.find_variable_or_default(&ident);
-                self.resolve_local_variable(ident.0, ident.1);
+                let (hir_ident, var_scope_index) = self.find_variable_or_default(&ident);
+
+                // The block only has a copy of any variable declared outside of it
+                let outside_unconstrained_block = self
+                    .lambda_stack
+                    .iter()
+                    .any(|lambda| lambda.unconstrained && lambda.scope_index > var_scope_index);
+                if outside_unconstrained_block && hir_ident.id != DefinitionId::dummy_id() {
+                    self.push_err(ResolverError::AssignmentInUnconstrainedBlock {
+                        name: ident.0.contents.clone(),
+                        span: ident.span(),
+                    });
+                }
 
-                HirLValue::Ident(ident.0, Type::Error)
+                self.resolve_local_variable(hir_ident, var_scope_index);
+                HirLValue::Ident(hir_ident, Type::Error)
             }
             LValue::MemberAccess { object, field_name } => {
                 let object = Box::new(self.resolve_lvalue(*object));
@@ -1101,31 +1418,39 @@ impl<'a> Resolver<'a> {
         span: Span,
         visibility: FunctionVisibility,
     ) {
+        if self.can_reference_function(func, visibility) {
+            return;
+        }
+
+        let name = self.interner.function_name(&func).to_string();
+        match visibility {
+            FunctionVisibility::Public => (),
+            FunctionVisibility::Private => {
+                self.errors.push(ResolverError::PrivateFunctionCalled { span, name });
+            }
+            FunctionVisibility::PublicCrate => {
+                self.errors.push(ResolverError::NonCrateFunctionCalled { span, name });
+            }
+        }
+    }
+
+    // Returns true if a function with the given visibility may be referenced from the current module.
+    fn can_reference_function(&self, func: FuncId, visibility: FunctionVisibility) -> bool {
         let function_module = self.interner.function_module(func);
         let current_module = self.path_resolver.module_id();
 
         let same_crate = function_module.krate == current_module.krate;
-        let krate = function_module.krate;
-        let current_module = current_module.local_id;
-        let name = self.interner.function_name(&func).to_string();
         match visibility {
-            FunctionVisibility::Public => (),
+            FunctionVisibility::Public => true,
             FunctionVisibility::Private => {
-                if !same_crate
-                    || !self.module_descendent_of_target(
-                        krate,
+                same_crate
+                    && self.module_descendent_of_target(
+                        function_module.krate,
                         function_module.local_id,
-                        current_module,
+                        current_module.local_id,
                     )
-                {
-                    self.errors.push(ResolverError::PrivateFunctionCalled { span, name });
-                }
-            }
-            FunctionVisibility::PublicCrate => {
-                if !same_crate {
-                    self.errors.push(ResolverError::NonCrateFunctionCalled { span, name });
-                }
             }
+            FunctionVisibility::PublicCrate => same_crate,
         }
     }
 
@@ -1180,6 +1505,7 @@ impl<'a> Resolver<'a> {
     }
 
     pub fn resolve_expression(&mut self, expr: Expression) -> ExprId {
+        let in_statement_position = std::mem::take(&mut self.in_statement_position);
         let hir_expr = match expr.kind {
             ExpressionKind::Literal(literal) => HirExpression::Literal(match literal {
                 Literal::Bool(b) => HirLiteral::Bool(b),
@@ -1207,11 +1533,24 @@ impl<'a> Resolver<'a> {
                 Literal::Unit => HirLiteral::Unit,
             }),
             ExpressionKind::Variable(path) => {
-                if let Some((hir_expr, object_type)) = self.resolve_trait_generic_path(&path) {
+                if let Some((r#type, variant)) = self.lookup_enum_variant(&path) {
+                    let location = Location::new(expr.span, self.file);
+                    let arguments = Vec::new();
+                    HirExpression::EnumConstructor(HirEnumConstructorExpression {
+                        r#type,
+                        variant,
+                        arguments,
+                        location,
+                    })
+                } else if let Some((hir_expr, object_type)) = self.resolve_trait_generic_path(&path)
+                {
                     let expr_id = self.interner.push_expr(hir_expr);
                     self.interner.push_expr_location(expr_id, expr.span, self.file);
-                    self.interner
-                        .select_impl_for_ident(expr_id, TraitImplKind::Assumed { object_type });
+                    let trait_generics = Vec::new();
+                    self.interner.select_impl_for_ident(
+                        expr_id,
+                        TraitImplKind::Assumed { object_type, trait_generics },
+                    );
                     return expr_id;
                 } else {
                     // If the Path is being used as an Expression, then it is referring to a global from a separate module
@@ -1260,7 +1599,7 @@ impl<'a> Resolver<'a> {
                 let rhs = self.resolve_expression(prefix.rhs);
 
                 if operator == UnaryOp::MutableReference {
-                    if let Err(error) = verify_mutable_reference(self.interner, rhs) {
+                    if let Err(error) = verify_mutable_reference(self.interner, rhs, false) {
                         self.errors.push(error);
                     }
                 }
@@ -1268,27 +1607,54 @@ impl<'a> Resolver<'a> {
                 HirExpression::Prefix(HirPrefixExpression { operator, rhs })
             }
             ExpressionKind::Infix(infix) => {
-                let lhs = self.resolve_expression(infix.lhs);
-                let rhs = self.resolve_expression(infix.rhs);
+                // Adding two string literals together is folded into a single literal
+                // so that the resulting `str<N>` has a length known at compile-time.
+                if let Some(str) = concatenate_str_literals(&infix) {
+                    HirExpression::Literal(HirLiteral::Str(str))
+                } else {
+                    let lhs = self.resolve_expression(infix.lhs);
+                    let rhs = self.resolve_expression(infix.rhs);
 
-                HirExpression::Infix(HirInfixExpression {
-                    lhs,
-                    operator: HirBinaryOp::new(infix.operator, self.file),
-                    rhs,
-                })
+                    let mut operator = infix.operator;
+                    if self.overflow_mode == OverflowMode::Wrap {
+                        operator.contents = operator.contents.into_wrapping();
+                    }
+
+                    HirExpression::Infix(HirInfixExpression {
+                        lhs,
+                        operator: HirBinaryOp::new(operator, self.file),
+                        rhs,
+                    })
+                }
             }
             ExpressionKind::Call(call_expr) => {
+                // A call to an enum variant such as `Option::Some(x)` constructs the enum
+                if let ExpressionKind::Variable(path) = &call_expr.func.kind {
+                    if let Some((r#type, variant)) = self.lookup_enum_variant(path) {
+                        let arguments =
+                            vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
+                        let location = Location::new(expr.span, self.file);
+                        let constructor =
+                            HirEnumConstructorExpression { r#type, variant, arguments, location };
+
+                        let expr_id =
+                            self.interner.push_expr(HirExpression::EnumConstructor(constructor));
+                        self.interner.push_expr_location(expr_id, expr.span, self.file);
+                        return expr_id;
+                    }
+                }
+
                 // Get the span and name of path for error reporting
                 let func = self.resolve_expression(*call_expr.func);
 
-                let arguments = vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
+                let arguments = vecmap(call_expr.arguments, |arg| self.resolve_call_argument(arg));
                 let location = Location::new(expr.span, self.file);
                 HirExpression::Call(HirCallExpression { func, arguments, location })
             }
             ExpressionKind::MethodCall(call_expr) => {
                 let method = call_expr.method_name;
                 let object = self.resolve_expression(call_expr.object);
-                let arguments = vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
+                let arguments = vecmap(call_expr.arguments, |arg| self.resolve_call_argument(arg));
                 let location = Location::new(expr.span, self.file);
                 HirExpression::MethodCall(HirMethodCallExpression {
                     arguments,
@@ -1301,31 +1667,77 @@ impl<'a> Resolver<'a> {
                 lhs: self.resolve_expression(cast_expr.lhs),
                 r#type: self.resolve_type(cast_expr.r#type),
             }),
-            ExpressionKind::If(if_expr) => HirExpression::If(HirIfExpression {
-                condition: self.resolve_expression(if_expr.condition),
-                consequence: self.resolve_expression(if_expr.consequence),
-                alternative: if_expr.alternative.map(|e| self.resolve_expression(e)),
-            }),
+            ExpressionKind::Try(operand) => {
+                if !in_statement_position && !self.in_unconstrained_fn {
+                    self.push_err(ResolverError::NestedTry { span: expr.span });
+                }
+                HirExpression::Try(HirTryExpression {
+                    expression: self.resolve_expression(*operand),
+                    return_type: self.interner.next_type_variable(),
+                })
+            }
+            ExpressionKind::If(if_expr) => {
+                let condition = self.resolve_expression(if_expr.condition);
+                self.in_statement_position = in_statement_position;
+                let consequence = self.resolve_expression(if_expr.consequence);
+                let alternative = if_expr.alternative.map(|alternative| {
+                    self.in_statement_position = in_statement_position;
+                    self.resolve_expression(alternative)
+                });
+                HirExpression::If(HirIfExpression { condition, consequence, alternative })
+            }
+            ExpressionKind::Match(match_expr) => {
+                let expression = self.resolve_expression(match_expr.expression);
+
+                // Each arm gets its own scope for the variables bound by its pattern
+                let arms = vecmap(match_expr.arms, |arm| {
+                    self.in_new_scope(|this| {
+                        let pattern = this.resolve_match_pattern(arm.pattern);
+                        this.in_statement_position = in_statement_position;
+                        let body = this.resolve_expression(arm.body);
+                        HirMatchArm { pattern, body }
+                    })
+                });
+
+                HirExpression::Match(HirMatchExpression { expression, arms })
+            }
             ExpressionKind::Index(indexed_expr) => HirExpression::Index(HirIndexExpression {
                 collection: self.resolve_expression(indexed_expr.collection),
                 index: self.resolve_expression(indexed_expr.index),
             }),
-            ExpressionKind::Block(block_expr) => self.resolve_block(block_expr),
+            ExpressionKind::Block(block_expr) => {
+                let in_statement_block =
+                    std::mem::replace(&mut self.in_statement_block, in_statement_position);
+                let block = self.resolve_block(block_expr);
+                self.in_statement_block = in_statement_block;
+                block
+            }
+            ExpressionKind::Comptime(block_expr) => {
+                HirExpression::Comptime(self.intern_block(block_expr))
+            }
+            ExpressionKind::Unconstrained(block_expr) => {
+                self.resolve_unconstrained_block(block_expr, expr.span)
+            }
             ExpressionKind::Constructor(constructor) => {
                 let span = constructor.type_name.span();
 
                 match self.lookup_type_or_error(constructor.type_name) {
-                    Some(Type::Struct(r#type, struct_generics)) => {
-                        let typ = r#type.clone();
-                        let fields = constructor.fields;
-                        let resolve_expr = Resolver::resolve_expression;
-                        let fields =
-                            self.resolve_constructor_fields(typ, fields, span, resolve_expr);
-                        HirExpression::Constructor(HirConstructorExpression {
-                            fields,
-                            r#type,
-                            struct_generics,
-                        })
+                    Some(Type::Struct(r#type, struct_generics)) if !r#type.borrow().is_enum() => {
+                        if let Some(base) = constructor.base {
+                            let fields = constructor.fields;
+                            self.resolve_struct_update(r#type, struct_generics, fields, base, span)
+                        } else {
+                            let typ = r#type.clone();
+                            let fields = constructor.fields;
+                            let resolve_expr = Resolver::resolve_expression;
+                            let fields =
+                                self.resolve_constructor_fields(typ, fields, span, resolve_expr);
+                            HirExpression::Constructor(HirConstructorExpression {
+                                fields,
+                                r#type,
+                                struct_generics,
+                            })
+                        }
                     }
                     Some(typ) => {
                         self.push_err(ResolverError::NonStructUsedInConstructor { typ, span });
@@ -1352,7 +1764,11 @@ impl<'a> Resolver<'a> {
             ExpressionKind::Lambda(lambda) => self.in_new_scope(|this| {
                 let scope_index = this.scopes.current_scope_index();
 
-                this.lambda_stack.push(LambdaContext { captures: Vec::new(), scope_index });
+                this.lambda_stack.push(LambdaContext {
+                    captures: Vec::new(),
+                    scope_index,
+                    unconstrained: false,
+                });
 
                 let parameters = vecmap(lambda.parameters, |(pattern, typ)| {
                     let parameter = DefinitionKind::Local(None);
@@ -1360,7 +1776,12 @@ impl<'a> Resolver<'a> {
                 });
 
                 let return_type = this.resolve_inferred_type(lambda.return_type);
+
+                // Loops outside of a lambda cannot be exited from within it
+                let loop_depth = std::mem::take(&mut this.loop_depth);
+                this.in_statement_position = true;
                 let body = this.resolve_expression(lambda.body);
+                this.loop_depth = loop_depth;
 
                 let lambda_context = this.lambda_stack.pop().unwrap();
 
@@ -1369,9 +1790,13 @@ impl<'a> Resolver<'a> {
                     return_type,
                     body,
                     captures: lambda_context.captures,
+                    unconstrained: false,
                 })
             }),
-            ExpressionKind::Parenthesized(sub_expr) => return self.resolve_expression(*sub_expr),
+            ExpressionKind::Parenthesized(sub_expr) => {
+                self.in_statement_position = in_statement_position;
+                return self.resolve_expression(*sub_expr);
+            }
         };
 
         // If these lines are ever changed, make sure to change the early return
@@ -1381,6 +1806,31 @@ impl<'a> Resolver<'a> {
         expr_id
     }
 
+    /// Resolves an argument to a function call. Unlike other expressions, arguments may be
+    /// mutable references to array elements since these only need to live for the duration
+    /// of the call. These are verified during type checking instead, once any arrays behind
+    /// references have been automatically dereferenced.
+    fn resolve_call_argument(&mut self, argument: Expression) -> ExprId {
+        match argument.kind {
+            ExpressionKind::Prefix(prefix) if prefix.operator == UnaryOp::MutableReference => {
+                let operator = prefix.operator;
+                let rhs = self.resolve_expression(prefix.rhs);
+                if !is_array_element(self.interner, rhs) {
+                    if let Err(error) = verify_mutable_reference(self.interner, rhs, false) {
+                        self.errors.push(error);
+                    }
+                }
+
+                let hir_expr = HirExpression::Prefix(HirPrefixExpression { operator, rhs });
+                let expr_id = self.interner.push_expr(hir_expr);
+                self.interner.push_expr_location(expr_id, argument.span, self.file);
+                expr_id
+            }
+            ExpressionKind::Parenthesized(argument) => self.resolve_call_argument(*argument),
+            kind => self.resolve_expression(Expression::new(kind, argument.span)),
+        }
+    }
+
     fn resolve_pattern(&mut self, pattern: Pattern, definition: DefinitionKind) -> HirPattern {
         self.resolve_pattern_mutable(pattern, None, definition)
     }
@@ -1416,7 +1866,7 @@ impl<'a> Resolver<'a> {
                 });
                 HirPattern::Tuple(fields, span)
             }
-            Pattern::Struct(name, fields, span) => {
+            Pattern::Struct(name, mut fields, has_rest, span) => {
                 let error_identifier = |this: &mut Self| {
                     // Must create a name here to return a HirPattern::Identifier. Allowing
                     // shadowing here lets us avoid further errors if we define ERROR_IDENT
@@ -1427,7 +1877,11 @@ impl<'a> Resolver<'a> {
                 };
 
                 let (struct_type, generics) = match self.lookup_type_or_error(name) {
-                    Some(Type::Struct(struct_type, generics)) => (struct_type, generics),
+                    Some(Type::Struct(struct_type, generics))
+                        if !struct_type.borrow().is_enum() =>
+                    {
+                        (struct_type, generics)
+                    }
                     None => return error_identifier(self),
                     Some(typ) => {
                         self.push_err(ResolverError::NonStructUsedInConstructor { typ, span });
@@ -1439,6 +1893,19 @@ impl<'a> Resolver<'a> {
                     this.resolve_pattern_mutable(pattern, mutable, definition.clone())
                 };
 
+                // Any fields which are skipped with `..` are bound to wildcards so that
+                // later passes only ever see patterns which list every field.
+                if has_rest {
+                    let mut unlisted_fields = struct_type.borrow().field_names();
+                    for (field, _) in &fields {
+                        unlisted_fields.remove(field);
+                    }
+                    for field in unlisted_fields {
+                        let wildcard = Pattern::Identifier(Ident::new("_".into(), span));
+                        fields.push((field, wildcard));
+                    }
+                }
+
                 let typ = struct_type.clone();
                 let fields = self.resolve_constructor_fields(typ, fields, span, resolve_field);
 
@@ -1448,6 +1915,27 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    fn resolve_match_pattern(&mut self, pattern: MatchPattern) -> HirMatchPattern {
+        match pattern {
+            MatchPattern::Variant(path, fields, span) => match self.lookup_enum_variant(&path) {
+                Some((r#type, variant)) => {
+                    let fields = vecmap(fields, |field| {
+                        self.resolve_pattern(field, DefinitionKind::Local(None))
+                    });
+                    HirMatchPattern::Variant { r#type, variant, fields, span }
+                }
+                None => {
+                    let error = self.enum_variant_error(path);
+                    self.push_err(error);
+                    HirMatchPattern::Error(span)
+                }
+            },
+            MatchPattern::Default(pattern) => {
+                HirMatchPattern::Default(self.resolve_pattern(pattern, DefinitionKind::Local(None)))
+            }
+        }
+    }
+
     /// Resolve all the fields of a struct constructor expression.
     /// Ensures all fields are present, none are repeated, and all
     /// are part of the struct.
@@ -1465,9 +1953,15 @@ impl<'a> Resolver<'a> {
         let mut seen_fields = HashSet::new();
         let mut unseen_fields = struct_type.borrow().field_names();
 
+        let struct_id = struct_type.borrow().id;
         for (field, expr) in fields {
             let resolved = resolve_function(self, expr);
 
+            if unseen_fields.contains(&field) || seen_fields.contains(&field) {
+                let field_reference = ReferenceId::StructField(struct_id, field.0.contents.clone());
+                self.add_reference(field_reference, field.span());
+            }
+
             if unseen_fields.contains(&field) {
                 unseen_fields.remove(&field);
                 seen_fields.insert(field.clone());
@@ -1496,6 +1990,62 @@ impl<'a> Resolver<'a> {
         ret
     }
 
+    /// Resolves `MyStruct { field: value, ..base }` into a block which binds `base` to a
+    /// fresh variable and copies each field which isn't listed out of that variable:
+    ///
+    /// `{ let $base: MyStruct<..> = base; MyStruct { field: value, other: $base.other } }`
+    fn resolve_struct_update(
+        &mut self,
+        r#type: Shared<StructType>,
+        struct_generics: Vec<Type>,
+        mut fields: Vec<(Ident, Expression)>,
+        base: Expression,
+        span: Span,
+    ) -> HirExpression {
+        let base_span = base.span;
+        let base = self.resolve_expression(base);
+
+        self.in_new_scope(|this| {
+            // `$` can't appear in identifiers so user code can never refer to this variable
+            let base_name = Ident::from(Spanned::from(base_span, "$base".to_owned()));
+            let definition = DefinitionKind::Local(Some(base));
+            let base_ident =
+                this.add_variable_decl_inner(base_name, false, true, false, definition);
+            let let_stmt = HirStatement::Let(HirLetStatement {
+                pattern: HirPattern::Identifier(base_ident),
+                r#type: Type::Struct(r#type.clone(), struct_generics.clone()),
+                expression: base,
+            });
+            let let_stmt = this.interner.push_stmt(let_stmt);
+
+            let listed_fields: HashSet<_> = fields.iter().map(|(name, _)| name.clone()).collect();
+            let field_names = r#type.borrow().field_names();
+            for name in field_names {
+                if !listed_fields.contains(&name) {
+                    let lhs = Path::from_single("$base".to_owned(), base_span);
+                    let lhs = Expression::new(ExpressionKind::Variable(lhs), base_span);
+                    let access = MemberAccessExpression { lhs, rhs: name.clone() };
+                    let access = ExpressionKind::MemberAccess(Box::new(access));
+                    fields.push((name, Expression::new(access, base_span)));
+                }
+            }
+
+            let resolve_expr = Resolver::resolve_expression;
+            let fields =
+                this.resolve_constructor_fields(r#type.clone(), fields, span, resolve_expr);
+            let constructor = HirExpression::Constructor(HirConstructorExpression {
+                fields,
+                r#type,
+                struct_generics,
+            });
+            let constructor = this.interner.push_expr(constructor);
+            this.interner.push_expr_location(constructor, span, this.file);
+            let constructor = this.interner.push_stmt(HirStatement::Expression(constructor));
+
+            HirExpression::Block(HirBlockExpression(vec![let_stmt, constructor]))
+        })
+    }
+
     pub fn get_struct(&self, type_id: StructId) -> Shared<StructType> {
         self.interner.get_struct(type_id)
     }
@@ -1515,16 +2065,25 @@ impl<'a> Resolver<'a> {
     }
 
     fn lookup_global(&mut self, path: Path) -> Result<DefinitionId, ResolverError> {
+        if let Some(function) = self.lookup_self_method(&path) {
+            return Ok(self.interner.function_definition_id(function));
+        }
+
         let span = path.span();
         let id = self.resolve_path(path)?;
 
         if let Some(function) = TryFromModuleDefId::try_from(id) {
+            self.interner.mark_function_referenced(function);
             return Ok(self.interner.function_definition_id(function));
         }
 
         if let Some(global) = TryFromModuleDefId::try_from(id) {
-            let let_stmt = self.interner.let_statement(&global);
-            return Ok(let_stmt.ident().id);
+            // Globals are resolved after any globals they refer to, so an unresolved
+            // global here can only be part of a cycle
+            return match self.interner.statement(&global) {
+                HirStatement::Let(let_stmt) => Ok(let_stmt.ident().id),
+                _ => Err(ResolverError::GlobalDependencyCycle { span }),
+            };
         }
 
         let expected = "global variable".into();
@@ -1532,10 +2091,31 @@ impl<'a> Resolver<'a> {
         Err(ResolverError::Expected { span, expected, got })
     }
 
+    /// Looks up a function such as `Self::new` within an impl on a struct. Methods are declared
+    /// within the module of their struct, which `Self` does not otherwise resolve to.
+    fn lookup_self_method(&self, path: &Path) -> Option<FuncId> {
+        let [self_segment, method] = path.segments.as_slice() else {
+            return None;
+        };
+        if self_segment != SELF_TYPE_NAME {
+            return None;
+        }
+
+        let Some(Type::Struct(struct_type, _)) = &self.self_type else {
+            return None;
+        };
+        let module = struct_type.borrow().id.module_id();
+        self.def_maps[&module.krate].modules()[module.local_id.0].find_func_with_name(method)
+    }
+
     /// Lookup a given struct type by name.
     fn lookup_struct_or_error(&mut self, path: Path) -> Option<Shared<StructType>> {
+        let name_span = path.last_segment().span();
         match self.lookup(path) {
-            Ok(struct_id) => Some(self.get_struct(struct_id)),
+            Ok(struct_id) => {
+                self.add_reference(ReferenceId::Struct(struct_id), name_span);
+                Some(self.get_struct(struct_id))
+            }
             Err(error) => {
                 self.push_err(error);
                 None
@@ -1543,6 +2123,47 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    /// Lookup the enum a path such as `Option::Some` refers to a variant of.
+    /// The enum itself is referred to by every segment of the path except the last.
+    fn lookup_enum(&mut self, path: &Path) -> Option<Shared<StructType>> {
+        let (_, prefix) = path.segments.split_last()?;
+        if prefix.is_empty() {
+            return None;
+        }
+
+        let enum_type = if prefix.len() == 1 && &prefix[0] == SELF_TYPE_NAME {
+            match &self.self_type {
+                Some(Type::Struct(enum_type, _)) => enum_type.clone(),
+                _ => return None,
+            }
+        } else {
+            let prefix = Path { segments: prefix.to_vec(), kind: path.kind };
+            let id = self.path_resolver.resolve(self.def_maps, prefix).ok()?;
+            self.get_struct(TryFromModuleDefId::try_from(id)?)
+        };
+
+        let is_enum = enum_type.borrow().is_enum();
+        is_enum.then_some(enum_type)
+    }
+
+    /// If the given path refers to a variant of an enum, returns the enum and the variant's index.
+    /// Other paths, including those to functions within an enum's impl, return None.
+    fn lookup_enum_variant(&mut self, path: &Path) -> Option<(Shared<StructType>, usize)> {
+        let enum_type = self.lookup_enum(path)?;
+        let variant = enum_type.borrow().get_variant(&path.last_segment().0.contents)?;
+        Some((enum_type, variant))
+    }
+
+    fn enum_variant_error(&mut self, path: Path) -> ResolverError {
+        match self.lookup_enum(&path) {
+            Some(enum_type) => ResolverError::NoSuchVariant {
+                variant: path.last_segment(),
+                enum_definition: enum_type.borrow().name.clone(),
+            },
+            None => ResolverError::ExpectedEnumVariant { path },
+        }
+    }
+
     /// Lookup a given trait by name/path.
     fn lookup_trait_or_error(&mut self, path: Path) -> Option<Trait> {
         match self.lookup(path) {
@@ -1564,8 +2185,10 @@ impl<'a> Resolver<'a> {
             }
         }
 
+        let name_span = path.last_segment().span();
         match self.lookup(path) {
             Ok(struct_id) => {
+                self.add_reference(ReferenceId::Struct(struct_id), name_span);
                 let struct_type = self.get_struct(struct_id);
                 let generics = struct_type.borrow().instantiate(self.interner);
                 Some(Type::Struct(struct_type, generics))
@@ -1647,15 +2270,82 @@ impl<'a> Resolver<'a> {
     }
 
     fn resolve_path(&mut self, path: Path) -> Result<ModuleDefId, ResolverError> {
-        self.path_resolver.resolve(self.def_maps, path).map_err(ResolverError::PathResolutionError)
+        let first_segment_span = match path.kind {
+            PathKind::Plain => path.segments.first().map(Ident::span),
+            PathKind::Crate | PathKind::Dep => None,
+        };
+
+        self.path_resolver.resolve(self.def_maps, path).map_err(|mut error| {
+            // A name which isn't in scope at all may be defined in another module
+            if let PathResolutionError::Unresolved(ident, suggestions) = &mut error {
+                if Some(ident.span()) == first_segment_span {
+                    suggestions.imports = self.import_suggestions(&ident.0.contents);
+                }
+            }
+            ResolverError::PathResolutionError(error)
+        })
+    }
+
+    /// Finds the items named `name` elsewhere in the crate or its dependencies which could be
+    /// imported into the current module.
+    fn import_suggestions(&self, name: &str) -> Vec<ImportSuggestion> {
+        let krate = self.path_resolver.module_id().krate;
+        vecmap(importable_items(name, krate, self.def_maps), |(path, item)| {
+            let is_private = match item {
+                ModuleDefId::FunctionId(func) => {
+                    !self.can_reference_function(func, self.interner.function_visibility(func))
+                }
+                _ => false,
+            };
+            ImportSuggestion { path, item, is_private }
+        })
     }
 
     fn resolve_block(&mut self, block_expr: BlockExpression) -> HirExpression {
+        if let Some(span) = unreachable_statements(&block_expr.0) {
+            self.push_err(ResolverError::UnreachableCode { span });
+        }
+
         let statements =
             self.in_new_scope(|this| vecmap(block_expr.0, |stmt| this.intern_stmt(stmt.kind)));
         HirExpression::Block(HirBlockExpression(statements))
     }
 
+    /// An `unconstrained { ... }` block is lowered to an unconstrained lambda which is called
+    /// immediately. The lambda captures any variables from outside of the block, so the block
+    /// is executed in Brillig and its results are returned to the surrounding code as witnesses.
+    fn resolve_unconstrained_block(&mut self, block: BlockExpression, span: Span) -> HirExpression {
+        let lambda = self.in_new_scope(|this| {
+            let scope_index = this.scopes.current_scope_index();
+            let context = LambdaContext { captures: Vec::new(), scope_index, unconstrained: true };
+            this.lambda_stack.push(context);
+
+            // Loops outside of the block cannot be exited from within it, while loops within
+            // it need no bound as they are not unrolled.
+            let loop_depth = std::mem::take(&mut this.loop_depth);
+            let in_unconstrained_fn = std::mem::replace(&mut this.in_unconstrained_fn, true);
+            let body = this.resolve_expression(Expression::new(ExpressionKind::Block(block), span));
+            this.in_unconstrained_fn = in_unconstrained_fn;
+            this.loop_depth = loop_depth;
+
+            let lambda_context = this.lambda_stack.pop().unwrap();
+
+            HirExpression::Lambda(HirLambda {
+                parameters: Vec::new(),
+                return_type: this.interner.next_type_variable(),
+                body,
+                captures: lambda_context.captures,
+                unconstrained: true,
+            })
+        });
+
+        let func = self.interner.push_expr(lambda);
+        self.interner.push_expr_location(func, span, self.file);
+
+        let location = Location::new(span, self.file);
+        HirExpression::Call(HirCallExpression { func, arguments: Vec::new(), location })
+    }
+
     pub fn intern_block(&mut self, block: BlockExpression) -> ExprId {
         let hir_block = self.resolve_block(block);
         self.interner.push_expr(hir_block)
@@ -1688,12 +2378,7 @@ impl<'a> Resolver<'a> {
         rhs: ExprId,
         span: Span,
     ) -> Result<u128, Option<ResolverError>> {
-        match self.interner.expression(&rhs) {
-            HirExpression::Literal(HirLiteral::Integer(int)) => {
-                int.try_into_u128().ok_or(Some(ResolverError::IntegerTooLarge { span }))
-            }
-            _other => Err(Some(ResolverError::InvalidArrayLengthExpr { span })),
-        }
+        ComptimeFnEvaluator::new(self.interner, self.def_maps, span).evaluate_global(rhs)
     }
 
     fn resolve_fmt_str_literal(&mut self, str: String, call_expr_span: Span) -> HirLiteral {
@@ -1717,15 +2402,82 @@ impl<'a> Resolver<'a> {
                     span: call_expr_span,
                 });
             } else {
-                self.errors.push(ResolverError::VariableNotDeclared {
-                    name: ident_name.to_owned(),
-                    span: call_expr_span,
-                });
+                let error = self.variable_not_declared(ident_name, call_expr_span);
+                self.errors.push(error);
             }
         }
         HirLiteral::FmtStr(str, fmt_str_idents)
     }
 
+    /// Resolves `assert(condition, f"message")` into
+    /// `{ let $condition = condition; constrain $condition }`, where the constrain first calls
+    /// `std::resolve_assert_message(f"message", $condition)`. Binding the condition means it is
+    /// only evaluated once, so the constraints and calls within it aren't repeated for the message.
+    ///
+    /// The raw format string is kept as a fallback for the failure message.
+    fn resolve_constrain_with_format_string(
+        &mut self,
+        condition: Expression,
+        message: String,
+    ) -> HirStatement {
+        let span = condition.span;
+        let condition = self.resolve_expression(condition);
+
+        let block = self.in_new_scope(|this| {
+            // `$` can't appear in identifiers so user code can never refer to this variable
+            let name = Ident::from(Spanned::from(span, "$condition".to_owned()));
+            let definition = DefinitionKind::Local(Some(condition));
+            let ident = this.add_variable_decl_inner(name, false, true, false, definition);
+            let let_stmt = HirStatement::Let(HirLetStatement {
+                pattern: HirPattern::Identifier(ident),
+                r#type: Type::Error,
+                expression: condition,
+            });
+            let let_stmt = this.interner.push_stmt(let_stmt);
+
+            let condition_variable = || {
+                let path = Path::from_single("$condition".to_owned(), span);
+                Expression::new(ExpressionKind::Variable(path), span)
+            };
+            let resolve_message_call =
+                this.resolve_assert_message_call(message.clone(), condition_variable(), span);
+            let condition = this.resolve_expression(condition_variable());
+            let constrain = HirStatement::Constrain(HirConstrainStatement(
+                condition,
+                this.file,
+                Some(message),
+                Some(resolve_message_call),
+            ));
+            let constrain = this.interner.push_stmt(constrain);
+
+            HirExpression::Block(HirBlockExpression(vec![let_stmt, constrain]))
+        });
+        let block = self.interner.push_expr(block);
+        self.interner.push_expr_location(block, span, self.file);
+        HirStatement::Expression(block)
+    }
+
+    /// Builds a call to `std::resolve_assert_message(message, condition)`, which passes the
+    /// formatted assertion message to the executor when `condition` is false.
+    fn resolve_assert_message_call(
+        &mut self,
+        message: String,
+        condition: Expression,
+        span: Span,
+    ) -> ExprId {
+        let (kind, mut segments) = if self.path_resolver.module_id().krate.is_stdlib() {
+            (PathKind::Crate, vec![])
+        } else {
+            (PathKind::Dep, vec![Ident::from(Spanned::from(span, "std".to_owned()))])
+        };
+        segments.push(Ident::from(Spanned::from(span, "resolve_assert_message".to_owned())));
+
+        let func = Expression::new(ExpressionKind::Variable(Path { segments, kind }), span);
+        let message = Expression::new(ExpressionKind::format_string(message), span);
+        let call = Expression::call(func, vec![message, condition], span);
+        self.resolve_expression(call)
+    }
+
     /// Only sized types are valid to be used as main's parameters or the parameters to a contract
     /// function. If the given type is not sized (e.g. contains a slice or NamedGeneric type), an
     /// error is issued.
@@ -1805,16 +2557,109 @@ impl<'a> Resolver<'a> {
                 self.verify_type_expression_valid_for_program_input(lhs);
                 self.verify_type_expression_valid_for_program_input(rhs);
             }
+            UnresolvedTypeExpression::Call(_, arguments, _) => {
+                for argument in arguments {
+                    self.verify_type_expression_valid_for_program_input(argument);
+                }
+            }
         }
     }
 }
 
+/// True if the pattern binds the whole value to a single name, e.g. `x` or `mut x`.
+fn is_named_pattern(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::Identifier(_) => true,
+        Pattern::Mutable(inner, _) => matches!(inner.as_ref(), Pattern::Identifier(_)),
+        Pattern::Tuple(..) | Pattern::Struct(..) => false,
+    }
+}
+
+/// If the given infix expression adds together string literals, returns the
+/// concatenation of those literals. Nested additions such as `"a" + "b" + "c"`
+/// are folded recursively.
+fn concatenate_str_literals(infix: &InfixExpression) -> Option<String> {
+    fn as_str_literal(expr: &Expression) -> Option<String> {
+        match &expr.kind {
+            ExpressionKind::Literal(Literal::Str(str)) => Some(str.clone()),
+            ExpressionKind::Parenthesized(expr) => as_str_literal(expr),
+            ExpressionKind::Infix(infix) => concatenate_str_literals(infix),
+            _ => None,
+        }
+    }
+
+    if infix.operator.contents != BinaryOpKind::Add {
+        return None;
+    }
+    let lhs = as_str_literal(&infix.lhs)?;
+    let rhs = as_str_literal(&infix.rhs)?;
+    Some(lhs + &rhs)
+}
+
+/// Returns the span of any statements in a block following a `break`, `continue` or `return`,
+/// which are never executed.
+fn unreachable_statements(statements: &[Statement]) -> Option<Span> {
+    let exits = statements.iter().position(|statement| {
+        matches!(
+            statement.kind,
+            StatementKind::Break(_) | StatementKind::Continue(_) | StatementKind::Return(_)
+        )
+    })?;
+
+    let mut unreachable =
+        statements[exits + 1..].iter().filter(|statement| statement.kind != StatementKind::Error);
+    let first = unreachable.next()?.span;
+    Some(unreachable.fold(first, |span, statement| span.merge(statement.span)))
+}
+
+/// Infers an upper bound on the number of iterations of a `while` loop from
+/// conditions of the form `x < N`, `x <= N`, `N > x` or `N >= x` where `N` is an
+/// integer literal. The bound is not trusted blindly: unrolled loops assert that
+/// their condition no longer holds once the bound is reached.
+fn infer_max_iterations(condition: &Expression) -> Option<u64> {
+    let ExpressionKind::Infix(infix) = &condition.kind else {
+        return None;
+    };
+    let as_integer = |expr: &Expression| match &expr.kind {
+        ExpressionKind::Literal(Literal::Integer(value)) => value.try_to_u64(),
+        _ => None,
+    };
+
+    match infix.operator.contents {
+        BinaryOpKind::Less => as_integer(&infix.rhs),
+        BinaryOpKind::LessEqual => as_integer(&infix.rhs)?.checked_add(1),
+        BinaryOpKind::Greater => as_integer(&infix.lhs),
+        BinaryOpKind::GreaterEqual => as_integer(&infix.lhs)?.checked_add(1),
+        _ => None,
+    }
+}
+
+/// Returns true if the given expression is an array element, possibly within
+/// a struct field such as `foo.bar[i].baz`.
+pub fn is_array_element(interner: &NodeInterner, expr: ExprId) -> bool {
+    match interner.expression(&expr) {
+        HirExpression::Index(_) => true,
+        HirExpression::MemberAccess(access) => is_array_element(interner, access.lhs),
+        _ => false,
+    }
+}
+
 /// Gives an error if a user tries to create a mutable reference
 /// to an immutable variable.
-pub fn verify_mutable_reference(interner: &NodeInterner, rhs: ExprId) -> Result<(), ResolverError> {
+///
+/// References to array elements are only allowed if `allow_array_elements` is true,
+/// which is the case for references passed directly as function arguments.
+pub fn verify_mutable_reference(
+    interner: &NodeInterner,
+    rhs: ExprId,
+    allow_array_elements: bool,
+) -> Result<(), ResolverError> {
     match interner.expression(&rhs) {
         HirExpression::MemberAccess(member_access) => {
-            verify_mutable_reference(interner, member_access.lhs)
+            verify_mutable_reference(interner, member_access.lhs, allow_array_elements)
+        }
+        HirExpression::Index(index) if allow_array_elements => {
+            verify_mutable_reference(interner, index.collection, allow_array_elements)
         }
         HirExpression::Index(_) => {
             let span = interner.expr_span(&rhs);
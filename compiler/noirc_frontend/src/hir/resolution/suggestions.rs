@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use noirc_errors::{Applicability, CustomDiagnostic, Span, Suggestion};
+
+use crate::graph::CrateId;
+use crate::hir::def_map::{CrateDefMap, ModuleDefId};
+
+/// The most names suggested in place of a name which could not be resolved.
+const MAX_SIMILAR_NAMES: usize = 3;
+
+/// Names which may have been meant in place of a name which could not be resolved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameSuggestions {
+    /// Names in scope which are spelled similarly to the unresolved name, closest first.
+    pub similar: Vec<String>,
+    /// Items with the unresolved name which are defined elsewhere and could be imported.
+    pub imports: Vec<ImportSuggestion>,
+}
+
+/// An item which is not in scope but could be brought into scope with a `use` statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportSuggestion {
+    /// The path to import the item with, such as `crate::foo::bar` or `dep::std::hash`.
+    pub path: String,
+    pub item: ModuleDefId,
+    /// True if the item is private to its module, so it must be made public to be imported.
+    pub is_private: bool,
+}
+
+impl NameSuggestions {
+    /// Adds the suggestions of `other`, keeping those already found first.
+    pub fn extend(&mut self, other: NameSuggestions) {
+        for name in other.similar {
+            if !self.similar.contains(&name) {
+                self.similar.push(name);
+            }
+        }
+        self.similar.truncate(MAX_SIMILAR_NAMES);
+
+        for import in other.imports {
+            if !self.imports.contains(&import) {
+                self.imports.push(import);
+            }
+        }
+    }
+
+    /// Adds the suggested names to `diagnostic`, replacing the unresolved name at `span`.
+    pub fn add_to(&self, diagnostic: &mut CustomDiagnostic, span: Span) {
+        for name in &self.similar {
+            diagnostic.add_suggestion(Suggestion::new(
+                "a similar name exists in scope",
+                span,
+                name,
+                Applicability::MaybeIncorrect,
+            ));
+        }
+
+        for import in &self.imports {
+            if import.is_private {
+                diagnostic.add_help(format!(
+                    "`{}` exists but is private, it must be made `pub` to be imported",
+                    import.path
+                ));
+            } else {
+                diagnostic.add_help(format!("consider importing it: `use {};`", import.path));
+            }
+        }
+    }
+}
+
+/// Returns the candidates spelled most similarly to `name`, closest first.
+pub(crate) fn similar_names<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    // Allow roughly one typo for every three characters, as rustc does.
+    let max_distance = name.chars().count().max(3) / 3;
+
+    let mut similar: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .filter_map(|candidate| {
+            let distance = edit_distance(name, candidate);
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        .collect();
+    similar.sort();
+    similar.dedup();
+
+    similar.into_iter().take(MAX_SIMILAR_NAMES).map(|(_, name)| name.to_owned()).collect()
+}
+
+/// Returns the paths of the items named `name` in the modules of `crate_id` and its dependencies,
+/// relative to `crate_id`.
+pub(crate) fn importable_items(
+    name: &str,
+    crate_id: CrateId,
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
+) -> Vec<(String, ModuleDefId)> {
+    let def_map = &def_maps[&crate_id];
+
+    let mut items = items_in_crate(name, def_map, "crate");
+    for (dep_name, dep_module) in &def_map.extern_prelude {
+        if let Some(dep_def_map) = def_maps.get(&dep_module.krate) {
+            items.extend(items_in_crate(name, dep_def_map, &format!("dep::{dep_name}")));
+        }
+    }
+    items
+}
+
+fn items_in_crate(name: &str, def_map: &CrateDefMap, prefix: &str) -> Vec<(String, ModuleDefId)> {
+    let mut items = Vec::new();
+    for (index, module) in def_map.modules.iter() {
+        for (item_name, item) in module.defined_items() {
+            if item_name.0.contents != name {
+                continue;
+            }
+            let module_path = def_map.get_module_path_with_separator(index, module.parent, "::");
+            let path = if module_path.is_empty() {
+                format!("{prefix}::{name}")
+            } else {
+                format!("{prefix}::{module_path}::{name}")
+            };
+            items.push((path, item));
+        }
+    }
+    items.sort_by(|(path, _), (other, _)| path.cmp(other));
+    items
+}
+
+/// The number of single character insertions, deletions and substitutions needed to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -1,11 +1,13 @@
 use acvm::FieldElement;
+use noirc_errors::error_codes as codes;
 use noirc_errors::CustomDiagnostic as Diagnostic;
-use noirc_errors::Span;
+use noirc_errors::{Location, Span};
 use thiserror::Error;
 
 use crate::hir::resolution::errors::ResolverError;
 use crate::hir_def::expr::HirBinaryOp;
 use crate::hir_def::types::Type;
+use crate::lints::Lint;
 use crate::BinaryOpKind;
 use crate::FunctionReturnType;
 use crate::Signedness;
@@ -53,7 +55,22 @@ pub enum TypeCheckError {
     #[error("Type {lhs_type} has no member named {field_name}")]
     AccessUnknownMember { lhs_type: Type, field_name: String, span: Span },
     #[error("Function expects {expected} parameters but {found} given")]
-    ParameterCountMismatch { expected: usize, found: usize, span: Span },
+    ParameterCountMismatch {
+        expected: usize,
+        found: usize,
+        span: Span,
+        /// The location of the called function, if it is a named function
+        definition: Option<Location>,
+    },
+    #[error("Expected type {expected_typ} for this argument, found type {expr_typ}")]
+    ArgumentTypeMismatch {
+        expected_typ: String,
+        expr_typ: String,
+        expr_span: Span,
+        /// The location of the parameter this argument is passed to, if the called function is
+        /// a named function
+        parameter: Option<Location>,
+    },
     #[error("Only integer and Field types may be casted to")]
     UnsupportedCast { span: Span },
     #[error("Index {index} is out of bounds for this tuple {lhs_type} of length {length}")]
@@ -115,17 +132,95 @@ pub enum TypeCheckError {
     NoMatchingImplFound { constraints: Vec<(Type, String)>, span: Span },
     #[error("Constraint for `{typ}: {trait_name}` is not needed, another matching impl is already in scope")]
     UnneededTraitConstraint { trait_name: String, typ: Type, span: Span },
+    #[error("Variant {variant} expects {expected} field(s) but {found} were given")]
+    VariantArityMismatch { variant: String, expected: usize, found: usize, span: Span },
+    #[error("Match is not exhaustive")]
+    NonExhaustiveMatch { missing: Vec<String>, span: Span },
+    #[error("Unreachable match arm")]
+    UnreachableMatchArm { span: Span },
+    #[error(
+        "{operation} of strings not known at compile-time is only supported in unconstrained code"
+    )]
+    RuntimeStringOperation { operation: &'static str, span: Span },
+    #[error("The lengths of concatenated strings must be known at compile-time")]
+    UnknownStringLength { span: Span },
+    #[error("The `?` operator can only be applied to an Option or Result, not {typ}")]
+    InvalidTryOperand { typ: Type, span: Span },
+    #[error("The `?` operator cannot propagate {operand} from a function returning {return_type}")]
+    TryReturnTypeMismatch { operand: Type, return_type: Type, span: Span },
 }
 
 impl TypeCheckError {
     pub fn add_context(self, ctx: &'static str) -> Self {
         TypeCheckError::Context { err: Box::new(self), ctx }
     }
+
+    /// Returns the lint controlling this warning, if it is one
+    pub fn lint(&self) -> Option<Lint> {
+        match self {
+            TypeCheckError::Context { err, .. } => err.lint(),
+            TypeCheckError::CallDeprecated { .. } => Some(Lint::Deprecated),
+            TypeCheckError::UnusedResultError { .. } => Some(Lint::UnusedResults),
+            TypeCheckError::UnneededTraitConstraint { .. } => Some(Lint::UnneededTraitConstraints),
+            TypeCheckError::UnreachableMatchArm { .. } => Some(Lint::UnreachablePatterns),
+            _ => None,
+        }
+    }
+
+    /// Returns the stable error code of this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TypeCheckError::OpCannotBeUsed { .. } => codes::E0400,
+            TypeCheckError::OverflowingAssignment { .. } => codes::E0401,
+            TypeCheckError::TypeCannotBeUsed { .. } => codes::E0402,
+            TypeCheckError::TypeMismatch { .. } => codes::E0403,
+            TypeCheckError::TypeMismatchWithSource { .. } => codes::E0404,
+            TypeCheckError::ArityMisMatch { .. } => codes::E0405,
+            TypeCheckError::PublicReturnType { .. } => codes::E0406,
+            TypeCheckError::InvalidCast { .. } => codes::E0407,
+            TypeCheckError::ExpectedFunction { .. } => codes::E0408,
+            TypeCheckError::AccessUnknownMember { .. } => codes::E0409,
+            TypeCheckError::ParameterCountMismatch { .. } => codes::E0410,
+            TypeCheckError::UnsupportedCast { .. } => codes::E0411,
+            TypeCheckError::TupleIndexOutOfBounds { .. } => codes::E0412,
+            TypeCheckError::VariableMustBeMutable { .. } => codes::E0413,
+            TypeCheckError::UnresolvedMethodCall { .. } => codes::E0414,
+            TypeCheckError::InvalidComparisonOnField { .. } => codes::E0415,
+            TypeCheckError::IntegerSignedness { .. } => codes::E0416,
+            TypeCheckError::IntegerBitWidth { .. } => codes::E0417,
+            TypeCheckError::InvalidInfixOp { .. } => codes::E0418,
+            TypeCheckError::InvalidUnaryOp { .. } => codes::E0419,
+            TypeCheckError::InvalidBitwiseOperationOnField { .. } => codes::E0420,
+            TypeCheckError::IntegerTypeMismatch { .. } => codes::E0421,
+            TypeCheckError::IntegerAndFieldBinaryOperation { .. } => codes::E0422,
+            TypeCheckError::FieldModulo { .. } => codes::E0423,
+            TypeCheckError::FieldComparison { .. } => codes::E0424,
+            TypeCheckError::AmbiguousBitWidth { .. } => codes::E0425,
+            TypeCheckError::Context { err, .. } => err.code(),
+            TypeCheckError::NonHomogeneousArray { .. } => codes::E0426,
+            TypeCheckError::TypeAnnotationsNeeded { .. } => codes::E0427,
+            TypeCheckError::CallDeprecated { .. } => codes::E0428,
+            TypeCheckError::ResolverError(error) => error.code(),
+            TypeCheckError::UnusedResultError { .. } => codes::E0429,
+            TypeCheckError::TraitMethodParameterTypeMismatch { .. } => codes::E0430,
+            TypeCheckError::NoMatchingImplFound { .. } => codes::E0431,
+            TypeCheckError::UnneededTraitConstraint { .. } => codes::E0432,
+            TypeCheckError::VariantArityMismatch { .. } => codes::E0433,
+            TypeCheckError::NonExhaustiveMatch { .. } => codes::E0434,
+            TypeCheckError::UnreachableMatchArm { .. } => codes::E0435,
+            TypeCheckError::RuntimeStringOperation { .. } => codes::E0436,
+            TypeCheckError::UnknownStringLength { .. } => codes::E0437,
+            TypeCheckError::InvalidTryOperand { .. } => codes::E0438,
+            TypeCheckError::TryReturnTypeMismatch { .. } => codes::E0439,
+            TypeCheckError::ArgumentTypeMismatch { .. } => codes::E0440,
+        }
+    }
 }
 
 impl From<TypeCheckError> for Diagnostic {
     fn from(error: TypeCheckError) -> Diagnostic {
-        match error {
+        let code = error.code();
+        let diagnostic = match error {
             TypeCheckError::TypeCannotBeUsed { typ, place, span } => Diagnostic::simple_error(
                 format!("The type {} cannot be used in a {}", &typ, place),
                 String::new(),
@@ -178,11 +273,26 @@ impl From<TypeCheckError> for Diagnostic {
                 let msg = format!("Expected {expected} argument{plural}, but found {found}");
                 Diagnostic::simple_error(msg, String::new(), span)
             }
-            TypeCheckError::ParameterCountMismatch { expected, found, span } => {
+            TypeCheckError::ParameterCountMismatch { expected, found, span, definition } => {
                 let empty_or_s = if expected == 1 { "" } else { "s" };
                 let was_or_were = if found == 1 { "was" } else { "were" };
                 let msg = format!("Function expects {expected} parameter{empty_or_s} but {found} {was_or_were} given");
-                Diagnostic::simple_error(msg, String::new(), span)
+                let mut diagnostic = Diagnostic::simple_error(msg, String::new(), span);
+                if let Some(definition) = definition {
+                    diagnostic.add_secondary_with_location("Function defined here".to_string(), definition);
+                }
+                diagnostic
+            }
+            TypeCheckError::ArgumentTypeMismatch { expected_typ, expr_typ, expr_span, parameter } => {
+                let mut diagnostic = Diagnostic::simple_error(
+                    format!("Expected type {expected_typ}, found type {expr_typ}"),
+                    format!("Expected {expected_typ} because of this function's parameter"),
+                    expr_span,
+                );
+                if let Some(parameter) = parameter {
+                    diagnostic.add_secondary_with_location(format!("Parameter of type {expected_typ} declared here"), parameter);
+                }
+                diagnostic
             }
             TypeCheckError::InvalidCast { span, .. }
             | TypeCheckError::ExpectedFunction { span, .. }
@@ -202,6 +312,10 @@ impl From<TypeCheckError> for Diagnostic {
             | TypeCheckError::AmbiguousBitWidth { span, .. }
             | TypeCheckError::IntegerAndFieldBinaryOperation { span }
             | TypeCheckError::OverflowingAssignment { span, .. }
+            | TypeCheckError::VariantArityMismatch { span, .. }
+            | TypeCheckError::InvalidTryOperand { span, .. }
+            | TypeCheckError::RuntimeStringOperation { span, .. }
+            | TypeCheckError::UnknownStringLength { span }
             | TypeCheckError::FieldModulo { span } => {
                 Diagnostic::simple_error(error.to_string(), String::new(), span)
             }
@@ -236,12 +350,12 @@ impl From<TypeCheckError> for Diagnostic {
                         let mut diagnostic = Diagnostic::simple_error(format!("expected type {expected}, found type {actual}"), format!("expected {expected} because of return type"), ret_ty_span);
 
                         if let FunctionReturnType::Default(_) = ret_ty {
-                            diagnostic.add_note(format!("help: try adding a return type: `-> {actual}`"));
+                            diagnostic.add_help(format!("try adding a return type: `-> {actual}`"));
                         }
 
                         diagnostic.add_secondary(format!("{actual} returned here"), expr_span);
 
-                        return diagnostic
+                        return diagnostic.with_code(code)
                     },
                 };
 
@@ -275,6 +389,19 @@ impl From<TypeCheckError> for Diagnostic {
                 let msg = format!("Constraint for `{typ}: {trait_name}` is not needed, another matching impl is already in scope");
                 Diagnostic::simple_warning(msg, "Unnecessary trait constraint in where clause".into(), span)
             }
-        }
+            TypeCheckError::NonExhaustiveMatch { missing, span } => {
+                let plural = if missing.len() == 1 { "" } else { "s" };
+                let msg = format!("Missing match arm{plural} for {}", missing.join(", "));
+                Diagnostic::simple_error(msg, "Add the missing arms or a `_` pattern to match any other value".into(), span)
+            }
+            TypeCheckError::UnreachableMatchArm { span } => {
+                Diagnostic::simple_warning(error.to_string(), "Every value is matched by an earlier arm".into(), span)
+            }
+            TypeCheckError::TryReturnTypeMismatch { span, .. } => {
+                let secondary = "An Option may only be propagated from a function returning an Option, and a Result from one returning a Result with the same error type".into();
+                Diagnostic::simple_error(error.to_string(), secondary, span)
+            }
+        };
+        diagnostic.with_code(code)
     }
 }
@@ -1,17 +1,22 @@
+use fm::FileId;
 use iter_extended::vecmap;
-use noirc_errors::Span;
+use noirc_errors::{Location, Span};
 
 use crate::{
-    hir::{resolution::resolver::verify_mutable_reference, type_check::errors::Source},
+    hir::{
+        resolution::resolver::{is_array_element, verify_mutable_reference},
+        type_check::errors::Source,
+    },
     hir_def::{
         expr::{
-            self, HirArrayLiteral, HirBinaryOp, HirExpression, HirLiteral, HirMethodCallExpression,
-            HirMethodReference, HirPrefixExpression,
+            self, HirArrayLiteral, HirBinaryOp, HirExpression, HirInfixExpression, HirLiteral,
+            HirMatchPattern, HirMethodCallExpression, HirMethodReference, HirPrefixExpression,
         },
-        types::Type,
+        traits::TraitConstraint,
+        types::{Type, TypeBindings},
     },
-    node_interner::{DefinitionKind, ExprId, FuncId, TraitId, TraitMethodId},
-    BinaryOpKind, Signedness, TypeBinding, TypeVariableKind, UnaryOp,
+    node_interner::{DefinitionKind, ExprId, FuncId, ReferenceId, TraitId, TraitMethodId},
+    BinaryOpKind, Ident, Shared, Signedness, StructType, TypeBinding, TypeVariableKind, UnaryOp,
 };
 
 use super::{errors::TypeCheckError, TypeChecker};
@@ -36,6 +41,17 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Returns the function called by `expr` if it refers to a function by name.
+    fn called_function(&self, expr: &ExprId) -> Option<FuncId> {
+        match self.interner.expression(expr) {
+            HirExpression::Ident(ident) => match self.interner.try_definition(ident.id)?.kind {
+                DefinitionKind::Function(func_id) => Some(func_id),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     /// Infers a type for a given expression, and return this type.
     /// As a side-effect, this function will also remember this type in the NodeInterner
     /// for the given expr_id key.
@@ -61,6 +77,10 @@ impl<'interner> TypeChecker<'interner> {
                         let function = self.interner.function_meta(&function);
                         for mut constraint in function.trait_constraints.clone() {
                             constraint.typ = constraint.typ.substitute(&bindings);
+                            constraint.trait_generics =
+                                vecmap(&constraint.trait_generics, |generic| {
+                                    generic.substitute(&bindings)
+                                });
                             self.trait_constraints.push((constraint, *expr_id));
                         }
                     }
@@ -136,11 +156,22 @@ impl<'interner> TypeChecker<'interner> {
                 let rhs_span = self.interner.expr_span(&infix_expr.rhs);
                 let span = lhs_span.merge(rhs_span);
 
-                self.infix_operand_type_rules(&lhs_type, &infix_expr.operator, &rhs_type, span)
-                    .unwrap_or_else(|error| {
-                        self.errors.push(error);
-                        Type::Error
-                    })
+                let operator = infix_expr.operator.kind;
+                if let Some(typ) =
+                    self.check_string_infix(expr_id, &infix_expr, &lhs_type, &rhs_type)
+                {
+                    typ
+                } else if let Some(trait_id) = self.operator_trait(&lhs_type, operator) {
+                    self.check_overloaded_operator(
+                        expr_id, trait_id, lhs_type, &rhs_type, operator, span,
+                    )
+                } else {
+                    self.infix_operand_type_rules(&lhs_type, &infix_expr.operator, &rhs_type, span)
+                        .unwrap_or_else(|error| {
+                            self.errors.push(error);
+                            Type::Error
+                        })
+                }
             }
             HirExpression::Index(index_expr) => self.check_index_expression(expr_id, index_expr),
             HirExpression::Call(call_expr) => {
@@ -149,11 +180,12 @@ impl<'interner> TypeChecker<'interner> {
                 let function = self.check_expression(&call_expr.func);
 
                 let args = vecmap(&call_expr.arguments, |arg| {
-                    let typ = self.check_expression(arg);
+                    let typ = self.check_call_argument(arg);
                     (typ, *arg, self.interner.expr_span(arg))
                 });
                 let span = self.interner.expr_span(expr_id);
-                self.bind_function_type(function, args, span)
+                let callee = self.called_function(&call_expr.func);
+                self.bind_function_type(function, args, callee, span)
             }
             HirExpression::MethodCall(mut method_call) => {
                 let object_type = self.check_expression(&method_call.object).follow_bindings();
@@ -167,7 +199,7 @@ impl<'interner> TypeChecker<'interner> {
                         )];
 
                         for arg in &method_call.arguments {
-                            let typ = self.check_expression(arg);
+                            let typ = self.check_call_argument(arg);
                             args.push((typ, *arg, self.interner.expr_span(arg)));
                         }
 
@@ -209,7 +241,15 @@ impl<'interner> TypeChecker<'interner> {
                         let ret = self.check_method_call(&function_id, method_ref, args, span);
 
                         if let Some(trait_id) = trait_id {
-                            self.verify_trait_constraint(&object_type, trait_id, function_id, span);
+                            let trait_generics =
+                                self.instantiated_trait_generics(trait_id, function_id);
+                            self.verify_trait_constraint(
+                                &object_type,
+                                trait_id,
+                                &trait_generics,
+                                function_id,
+                                span,
+                            );
                         }
 
                         self.interner.replace_expr(expr_id, function_call);
@@ -224,6 +264,10 @@ impl<'interner> TypeChecker<'interner> {
                 let span = self.interner.expr_span(expr_id);
                 self.check_cast(lhs_type, cast_expr.r#type, span)
             }
+            HirExpression::Try(try_expr) => {
+                let span = self.interner.expr_span(expr_id);
+                self.check_try(try_expr, span)
+            }
             HirExpression::Block(block_expr) => {
                 let mut block_type = Type::Unit;
 
@@ -259,6 +303,8 @@ impl<'interner> TypeChecker<'interner> {
             }
             HirExpression::If(if_expr) => self.check_if_expr(&if_expr, expr_id),
             HirExpression::Constructor(constructor) => self.check_constructor(constructor, expr_id),
+            HirExpression::EnumConstructor(constructor) => self.check_enum_constructor(constructor),
+            HirExpression::Match(match_expr) => self.check_match(match_expr),
             HirExpression::MemberAccess(access) => self.check_member_access(access, *expr_id),
             HirExpression::Error => Type::Error,
             HirExpression::Tuple(elements) => {
@@ -276,7 +322,12 @@ impl<'interner> TypeChecker<'interner> {
                     typ
                 });
 
+                self.return_types.push(lambda.return_type.clone());
+                let in_unconstrained_block = self.in_unconstrained_block;
+                self.in_unconstrained_block |= lambda.unconstrained;
                 let actual_return = self.check_expression(&lambda.body);
+                self.in_unconstrained_block = in_unconstrained_block;
+                self.return_types.pop();
 
                 let span = self.interner.expr_span(&lambda.body);
                 self.unify(&actual_return, &lambda.return_type, || TypeCheckError::TypeMismatch {
@@ -297,24 +348,93 @@ impl<'interner> TypeChecker<'interner> {
                     Box::new(Type::Unit),
                 );
 
+                // The trait's generics may differ at each use, so they are instantiated too
+                let typ = Type::Forall(the_trait.generics.clone(), Box::new(typ));
                 let (typ, bindings) = typ.instantiate(self.interner);
                 self.interner.store_instantiation_bindings(*expr_id, bindings);
                 typ
             }
+            HirExpression::Comptime(block) => {
+                self.interner.push_comptime_expression(*expr_id);
+                self.check_expression(&block)
+            }
         };
 
         self.interner.push_expr_type(expr_id, typ.clone());
         typ
     }
 
+    /// Returns the trait overloading the given operator if values of the given type should use it.
+    /// Primitive types use the builtin operators instead, as do generics which are not constrained
+    /// to implement the trait.
+    fn operator_trait(&self, lhs_type: &Type, operator: BinaryOpKind) -> Option<TraitId> {
+        let trait_id = self.interner.get_operator_trait_method(operator)?.trait_id;
+        match lhs_type.follow_bindings() {
+            Type::Struct(..) => Some(trait_id),
+            // The `where` clause of the current function is in scope as assumed impls
+            typ @ Type::NamedGeneric(..) => self
+                .interner
+                .lookup_trait_implementation(&typ, trait_id, &[])
+                .ok()
+                .map(|_| trait_id),
+            _ => None,
+        }
+    }
+
+    /// An overloaded operator is a call to its trait's method, which takes both operands as the
+    /// same type. The impl is selected once the function has been checked, at which point the
+    /// operand types are known.
+    fn check_overloaded_operator(
+        &mut self,
+        expr_id: &ExprId,
+        trait_id: TraitId,
+        lhs_type: Type,
+        rhs_type: &Type,
+        operator: BinaryOpKind,
+        span: Span,
+    ) -> Type {
+        self.unify(rhs_type, &lhs_type, || TypeCheckError::TypeMismatchWithSource {
+            expected: lhs_type.clone(),
+            actual: rhs_type.clone(),
+            span,
+            source: Source::Binary,
+        });
+
+        // Operator traits have no generics of their own to instantiate
+        self.interner.store_instantiation_bindings(*expr_id, TypeBindings::new());
+        let constraint = TraitConstraint::new(lhs_type.clone(), trait_id, Vec::new());
+        self.trait_constraints.push((constraint, *expr_id));
+
+        if operator.is_comparator() {
+            Type::Bool
+        } else {
+            lhs_type
+        }
+    }
+
+    /// Returns what each of the trait's generics were instantiated to when the given function
+    /// was referenced, or an empty list if the function was not generic over them.
+    fn instantiated_trait_generics(&self, trait_id: TraitId, function_id: ExprId) -> Vec<Type> {
+        let bindings = self.interner.get_instantiation_bindings(function_id);
+        let the_trait = self.interner.get_trait(trait_id);
+
+        the_trait
+            .generics
+            .iter()
+            .map(|(id, _)| bindings.get(id).map(|(_, typ)| typ.clone()))
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default()
+    }
+
     pub fn verify_trait_constraint(
         &mut self,
         object_type: &Type,
         trait_id: TraitId,
+        trait_generics: &[Type],
         function_ident_id: ExprId,
         span: Span,
     ) {
-        match self.interner.lookup_trait_implementation(object_type, trait_id) {
+        match self.interner.lookup_trait_implementation(object_type, trait_id, trait_generics) {
             Ok(impl_kind) => self.interner.select_impl_for_ident(function_ident_id, impl_kind),
             Err(erroring_constraints) => {
                 // Don't show any errors where try_get_trait returns None.
@@ -364,7 +484,8 @@ impl<'interner> TypeChecker<'interner> {
 
             if matches!(expected_object_type.follow_bindings(), Type::MutableReference(_)) {
                 if !matches!(actual_type, Type::MutableReference(_)) {
-                    if let Err(error) = verify_mutable_reference(self.interner, method_call.object)
+                    if let Err(error) =
+                        verify_mutable_reference(self.interner, method_call.object, true)
                     {
                         self.errors.push(TypeCheckError::ResolverError(error));
                     }
@@ -454,6 +575,89 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Type checks an operation on two strings. Strings known at compile-time can be concatenated
+    /// with `+` and compared regardless of their lengths, in which case the operation is replaced
+    /// by its result. Concatenating other strings is only supported in unconstrained code.
+    ///
+    /// Returns None for operations which should be checked like any other infix operation.
+    fn check_string_infix(
+        &mut self,
+        expr_id: &ExprId,
+        infix: &HirInfixExpression,
+        lhs_type: &Type,
+        rhs_type: &Type,
+    ) -> Option<Type> {
+        use BinaryOpKind::*;
+        let (Type::String(lhs_length), Type::String(rhs_length)) =
+            (lhs_type.follow_bindings(), rhs_type.follow_bindings())
+        else {
+            return None;
+        };
+
+        let lhs = self.interner.compile_time_string(&infix.lhs);
+        let rhs = self.interner.compile_time_string(&infix.rhs);
+
+        match (infix.operator.kind, lhs, rhs) {
+            (Add | WrappingAdd, Some(lhs), Some(rhs)) => {
+                let string = lhs + &rhs;
+                let length = Type::Constant(string.len() as u64);
+                let literal = HirExpression::Literal(HirLiteral::Str(string));
+                self.interner.replace_expr(expr_id, literal);
+                Some(Type::String(Box::new(length)))
+            }
+            (Equal | NotEqual, Some(lhs), Some(rhs)) => {
+                let result = (lhs == rhs) == (infix.operator.kind == Equal);
+                self.interner
+                    .replace_expr(expr_id, HirExpression::Literal(HirLiteral::Bool(result)));
+                Some(Type::Bool)
+            }
+            (Add | WrappingAdd, ..) => {
+                let span = self.interner.expr_span(expr_id);
+                if !self.in_unconstrained_code() {
+                    let operation = "Concatenation";
+                    self.errors.push(TypeCheckError::RuntimeStringOperation { operation, span });
+                }
+                match (lhs_length.evaluate_to_u64(), rhs_length.evaluate_to_u64()) {
+                    (Some(lhs), Some(rhs)) => {
+                        Some(Type::String(Box::new(Type::Constant(lhs + rhs))))
+                    }
+                    _ => {
+                        self.errors.push(TypeCheckError::UnknownStringLength { span });
+                        Some(Type::Error)
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// True if the expression being checked is within an unconstrained function
+    /// or an `unconstrained { ... }` block.
+    fn in_unconstrained_code(&self) -> bool {
+        self.in_unconstrained_block
+            || self.current_function.map_or(false, |function| {
+                self.interner.function_modifiers(&function).is_unconstrained
+            })
+    }
+
+    /// Type checks an argument to a function call. Mutable references to array elements are
+    /// verified here rather than during name resolution since indexing an array behind a
+    /// reference automatically dereferences it.
+    fn check_call_argument(&mut self, argument: &ExprId) -> Type {
+        let typ = self.check_expression(argument);
+
+        if let HirExpression::Prefix(prefix) = self.interner.expression(argument) {
+            if prefix.operator == UnaryOp::MutableReference
+                && is_array_element(self.interner, prefix.rhs)
+            {
+                if let Err(error) = verify_mutable_reference(self.interner, prefix.rhs, true) {
+                    self.errors.push(TypeCheckError::ResolverError(error));
+                }
+            }
+        }
+        typ
+    }
+
     fn check_index_expression(
         &mut self,
         id: &ExprId,
@@ -481,6 +685,17 @@ impl<'interner> TypeChecker<'interner> {
             // XXX: We can check the array bounds here also, but it may be better to constant fold first
             // and have ConstId instead of ExprId for constants
             Type::Array(_, base_type) => *base_type,
+            // Indexing a string returns the byte at that index
+            Type::String(_) => {
+                if !self.in_unconstrained_code()
+                    && self.interner.compile_time_string(&new_lhs).is_none()
+                {
+                    let span = self.interner.expr_span(id);
+                    let operation = "Indexing";
+                    self.errors.push(TypeCheckError::RuntimeStringOperation { operation, span });
+                }
+                Type::Integer(Signedness::Unsigned, 8)
+            }
             Type::Error => Type::Error,
             typ => {
                 let span = self.interner.expr_span(&new_lhs);
@@ -533,7 +748,7 @@ impl<'interner> TypeChecker<'interner> {
         arguments: Vec<(Type, ExprId, Span)>,
         span: Span,
     ) -> Type {
-        let (fn_typ, param_len) = match method_ref {
+        let (fn_typ, param_len, callee) = match method_ref {
             HirMethodReference::FuncId(func_id) => {
                 if func_id == FuncId::dummy_id() {
                     return Type::Error;
@@ -541,12 +756,12 @@ impl<'interner> TypeChecker<'interner> {
 
                 let func_meta = self.interner.function_meta(&func_id);
                 let param_len = func_meta.parameters.len();
-                (func_meta.typ, param_len)
+                (func_meta.typ, param_len, Some(func_id))
             }
             HirMethodReference::TraitMethodId(method) => {
                 let the_trait = self.interner.get_trait(method.trait_id);
                 let method = &the_trait.methods[method.method_index];
-                (method.get_type(), method.arguments.len())
+                (method.get_type(), method.arguments.len(), None)
             }
         };
 
@@ -564,7 +779,7 @@ impl<'interner> TypeChecker<'interner> {
 
         self.interner.store_instantiation_bindings(*function_ident_id, instantiation_bindings);
         self.interner.push_expr_type(function_ident_id, function_type.clone());
-        self.bind_function_type(function_type, arguments, span)
+        self.bind_function_type(function_type, arguments, callee, span)
     }
 
     fn check_if_expr(&mut self, if_expr: &expr::HirIfExpression, expr_id: &ExprId) -> Type {
@@ -647,6 +862,159 @@ impl<'interner> TypeChecker<'interner> {
         Type::Struct(typ, generics)
     }
 
+    fn check_enum_constructor(&mut self, constructor: expr::HirEnumConstructorExpression) -> Type {
+        let typ = constructor.r#type;
+        let generics = typ.borrow().instantiate(self.interner);
+        let fields = typ.borrow().get_variant_fields(constructor.variant, &generics);
+
+        let arguments = vecmap(&constructor.arguments, |arg| (*arg, self.check_expression(arg)));
+
+        if fields.len() != arguments.len() {
+            self.errors.push(TypeCheckError::VariantArityMismatch {
+                variant: variant_path(&typ, constructor.variant),
+                expected: fields.len(),
+                found: arguments.len(),
+                span: constructor.location.span,
+            });
+        }
+
+        for ((arg, arg_type), field_type) in arguments.into_iter().zip(fields) {
+            let span = self.interner.expr_span(&arg);
+            self.unify_with_coercions(&arg_type, &field_type, arg, || {
+                TypeCheckError::TypeMismatch {
+                    expected_typ: field_type.to_string(),
+                    expr_typ: arg_type.to_string(),
+                    expr_span: span,
+                }
+            });
+        }
+
+        Type::Struct(typ, generics)
+    }
+
+    /// `expr?` unwraps the `Some` or `Ok` value of an Option or Result, returning the
+    /// `None` or `Err` value from the enclosing function instead if there is none.
+    fn check_try(&mut self, try_expr: expr::HirTryExpression, span: Span) -> Type {
+        let operand = self.check_expression(&try_expr.expression);
+        let (enum_type, generics) = match operand.follow_bindings() {
+            Type::Struct(enum_type, generics) if enum_type.borrow().try_variants().is_some() => {
+                (enum_type, generics)
+            }
+            Type::Error => return Type::Error,
+            typ => {
+                self.errors.push(TypeCheckError::InvalidTryOperand { typ, span });
+                return Type::Error;
+            }
+        };
+
+        // The function may return a different type of value than is unwrapped here,
+        // but a Result's error type must be the same so that it can be returned as is.
+        let mut return_generics = generics.clone();
+        return_generics[0] = self.interner.next_type_variable();
+        let expected = Type::Struct(enum_type, return_generics);
+
+        let make_error = |return_type: &Type| TypeCheckError::TryReturnTypeMismatch {
+            operand: operand.clone(),
+            return_type: return_type.clone(),
+            span,
+        };
+
+        match self.return_types.last().cloned() {
+            // The value returned by a function returning `impl Trait` is only known from its body
+            Some(Type::TraitAsType(_)) | None => (),
+            Some(return_type) => {
+                self.unify(&return_type, &expected, || make_error(&return_type));
+            }
+        }
+        self.unify(&try_expr.return_type, &expected, || make_error(&try_expr.return_type));
+
+        generics[0].clone()
+    }
+
+    fn check_match(&mut self, match_expr: expr::HirMatchExpression) -> Type {
+        let scrutinee_type = self.check_expression(&match_expr.expression);
+        let result_type = self.interner.next_type_variable();
+
+        // Set once an arm is found which matches any remaining value
+        let mut matches_all = false;
+        let mut matched_variants = Vec::new();
+
+        for arm in match_expr.arms {
+            let mut unreachable = matches_all;
+
+            match arm.pattern {
+                HirMatchPattern::Variant { r#type, variant, fields, span } => {
+                    let generics = r#type.borrow().instantiate(self.interner);
+                    let enum_type = Type::Struct(r#type.clone(), generics.clone());
+
+                    self.unify(&scrutinee_type, &enum_type, || TypeCheckError::TypeMismatch {
+                        expected_typ: enum_type.to_string(),
+                        expr_typ: scrutinee_type.to_string(),
+                        expr_span: span,
+                    });
+
+                    let field_types = r#type.borrow().get_variant_fields(variant, &generics);
+                    if field_types.len() != fields.len() {
+                        self.errors.push(TypeCheckError::VariantArityMismatch {
+                            variant: variant_path(&r#type, variant),
+                            expected: field_types.len(),
+                            found: fields.len(),
+                            span,
+                        });
+                    }
+
+                    // Any extra patterns are still bound so that their variables have a type
+                    let field_types = field_types.into_iter().chain(std::iter::repeat(Type::Error));
+                    for (field, field_type) in fields.iter().zip(field_types) {
+                        self.bind_pattern(field, field_type);
+                    }
+
+                    unreachable |= matched_variants.contains(&variant);
+                    matched_variants.push(variant);
+                }
+                HirMatchPattern::Default(pattern) => {
+                    self.bind_pattern(&pattern, scrutinee_type.clone());
+                    matches_all = true;
+                }
+                // An error was already issued for this pattern, treat it as matching
+                // everything to avoid issuing further exhaustiveness errors.
+                HirMatchPattern::Error(_) => matches_all = true,
+            }
+
+            if unreachable {
+                let span = self.interner.expr_span(&arm.body);
+                self.errors.push(TypeCheckError::UnreachableMatchArm { span });
+            }
+
+            let body_type = self.check_expression(&arm.body);
+            let span = self.interner.expr_span(&arm.body);
+            self.unify(&body_type, &result_type, || TypeCheckError::TypeMismatch {
+                expected_typ: result_type.to_string(),
+                expr_typ: body_type.to_string(),
+                expr_span: span,
+            });
+        }
+
+        if !matches_all {
+            let span = self.interner.expr_span(&match_expr.expression);
+            let missing = match scrutinee_type.follow_bindings() {
+                Type::Error => Vec::new(),
+                Type::Struct(def, _) if def.borrow().is_enum() => {
+                    let variants = 0..def.borrow().num_variants();
+                    let missing = variants.filter(|variant| !matched_variants.contains(variant));
+                    missing.map(|variant| variant_path(&def, variant)).collect()
+                }
+                _ => vec!["_".to_string()],
+            };
+
+            if !missing.is_empty() {
+                self.errors.push(TypeCheckError::NonExhaustiveMatch { missing, span });
+            }
+        }
+
+        result_type
+    }
+
     fn check_member_access(&mut self, mut access: expr::HirMemberAccess, expr_id: ExprId) -> Type {
         let lhs_type = self.check_expression(&access.lhs).follow_bindings();
         let span = self.interner.expr_span(&expr_id);
@@ -667,6 +1035,8 @@ impl<'interner> TypeChecker<'interner> {
 
         match self.check_field_access(&lhs_type, &access.rhs.0.contents, span, dereference_lhs) {
             Some((element_type, index)) => {
+                let file = self.interner.id_location(expr_id).file;
+                self.add_field_reference(&lhs_type, &access.rhs, file);
                 self.interner.set_field_index(expr_id, index);
                 // We must update `access` in case we added any dereferences to it
                 self.interner.replace_expr(&expr_id, HirExpression::MemberAccess(access));
@@ -676,6 +1046,20 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Records `field` as a reference to a field of the struct `lhs_type` is, or refers to.
+    pub(super) fn add_field_reference(&mut self, lhs_type: &Type, field: &Ident, file: FileId) {
+        let mut lhs_type = lhs_type.follow_bindings();
+        while let Type::MutableReference(element) = lhs_type {
+            lhs_type = element.follow_bindings();
+        }
+
+        if let Type::Struct(struct_type, _) = lhs_type {
+            let struct_id = struct_type.borrow().id;
+            let reference = ReferenceId::StructField(struct_id, field.0.contents.clone());
+            self.interner.add_reference(reference, Location::new(field.span(), file));
+        }
+    }
+
     /// This will verify that an expression in the form `lhs.rhs_name` has the given field and will push
     /// a type error if it does not. If there is no error, the type of the struct/tuple field is returned
     /// along with the index of the field in question.
@@ -968,22 +1352,40 @@ impl<'interner> TypeChecker<'interner> {
         fn_params: &Vec<Type>,
         fn_ret: &Type,
         callsite_args: &Vec<(Type, ExprId, Span)>,
+        callee: Option<FuncId>,
         span: Span,
     ) -> Type {
+        // Errors point at the definition of the called function where it is known, as it is
+        // often in a different file than the call.
+        let (definition, parameters) = match callee {
+            Some(func_id) => {
+                let meta = self.interner.function_meta(&func_id);
+                let file = meta.location.file;
+                let parameters = vecmap(meta.parameters.iter(), |(pattern, _, _)| {
+                    Location::new(pattern.span(), file)
+                });
+                (Some(meta.location), parameters)
+            }
+            None => (None, Vec::new()),
+        };
+
         if fn_params.len() != callsite_args.len() {
             self.errors.push(TypeCheckError::ParameterCountMismatch {
                 expected: fn_params.len(),
                 found: callsite_args.len(),
                 span,
+                definition,
             });
             return Type::Error;
         }
 
-        for (param, (arg, _, arg_span)) in fn_params.iter().zip(callsite_args) {
-            self.unify(arg, param, || TypeCheckError::TypeMismatch {
+        for (index, (param, (arg, _, arg_span))) in fn_params.iter().zip(callsite_args).enumerate()
+        {
+            self.unify(arg, param, || TypeCheckError::ArgumentTypeMismatch {
                 expected_typ: param.to_string(),
                 expr_typ: arg.to_string(),
                 expr_span: *arg_span,
+                parameter: parameters.get(index).copied(),
             });
         }
 
@@ -994,6 +1396,7 @@ impl<'interner> TypeChecker<'interner> {
         &mut self,
         function: Type,
         args: Vec<(Type, ExprId, Span)>,
+        callee: Option<FuncId>,
         span: Span,
     ) -> Type {
         // Could do a single unification for the entire function type, but matching beforehand
@@ -1001,7 +1404,7 @@ impl<'interner> TypeChecker<'interner> {
         match function {
             Type::TypeVariable(binding, TypeVariableKind::Normal) => {
                 if let TypeBinding::Bound(typ) = &*binding.borrow() {
-                    return self.bind_function_type(typ.clone(), args, span);
+                    return self.bind_function_type(typ.clone(), args, callee, span);
                 }
 
                 let ret = self.interner.next_type_variable();
@@ -1016,7 +1419,13 @@ impl<'interner> TypeChecker<'interner> {
             }
             Type::Function(parameters, ret, _env) => {
                 // ignoring env for subtype on purpose
-                self.bind_function_type_impl(parameters.as_ref(), ret.as_ref(), args.as_ref(), span)
+                self.bind_function_type_impl(
+                    parameters.as_ref(),
+                    ret.as_ref(),
+                    args.as_ref(),
+                    callee,
+                    span,
+                )
             }
             Type::Error => Type::Error,
             found => {
@@ -1065,10 +1474,10 @@ impl<'interner> TypeChecker<'interner> {
                             }
                         } else if other.is_bindable() {
                             Err(TypeCheckError::AmbiguousBitWidth { span })
-                        } else if kind.is_bit_shift() && other.is_signed() {
+                        } else if kind == BinaryOpKind::ShiftLeft && other.is_signed() {
                             Err(TypeCheckError::TypeCannotBeUsed {
                                 typ: other,
-                                place: "bit shift",
+                                place: "left shift",
                                 span,
                             })
                         } else {
@@ -1103,9 +1512,8 @@ impl<'interner> TypeChecker<'interner> {
                         span,
                     });
                 }
-                if op.is_bit_shift()
-                    && (*sign_x == Signedness::Signed || *sign_y == Signedness::Signed)
-                {
+                // Signed integers are shifted right arithmetically, but may not be shifted left
+                if op.kind == BinaryOpKind::ShiftLeft && *sign_x == Signedness::Signed {
                     Err(TypeCheckError::InvalidInfixOp { kind: "Signed integer", span })
                 } else {
                     Ok(Integer(*sign_x, *bit_width_x))
@@ -1204,3 +1612,9 @@ where
 {
     xs.sort_by(|x, y| key(x).cmp(key(y)));
 }
+
+/// Formats a variant for error messages, e.g. `Option::Some`
+fn variant_path(enum_type: &Shared<StructType>, variant: usize) -> String {
+    let enum_type = enum_type.borrow();
+    format!("{}::{}", enum_type.name, enum_type.variant_name(variant))
+}
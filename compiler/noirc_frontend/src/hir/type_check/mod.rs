@@ -29,6 +29,13 @@ pub struct TypeChecker<'interner> {
     errors: Vec<TypeCheckError>,
     current_function: Option<FuncId>,
 
+    /// The return types of the function and any lambdas enclosing the expression being
+    /// checked, innermost last. `return` statements are checked against the last of these.
+    return_types: Vec<Type>,
+
+    /// True while checking the body of an `unconstrained { ... }` block
+    in_unconstrained_block: bool,
+
     /// Trait constraints are collected during type checking until they are
     /// verified at the end of a function. This is because constraints arise
     /// on each variable, but it is only until function calls when the types
@@ -48,6 +55,7 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
 
     let mut type_checker = TypeChecker::new(interner);
     type_checker.current_function = Some(func_id);
+    type_checker.return_types.push(declared_return_type.clone());
 
     let meta = type_checker.interner.function_meta(&func_id);
     let mut errors = Vec::new();
@@ -56,8 +64,10 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
     for constraint in &meta.trait_constraints {
         let object = constraint.typ.clone();
         let trait_id = constraint.trait_id;
+        let trait_generics = constraint.trait_generics.clone();
 
-        if !type_checker.interner.add_assumed_trait_implementation(object, trait_id) {
+        if !type_checker.interner.add_assumed_trait_implementation(object, trait_id, trait_generics)
+        {
             if let Some(the_trait) = type_checker.interner.try_get_trait(trait_id) {
                 let trait_name = the_trait.name.to_string();
                 let typ = constraint.typ.clone();
@@ -87,7 +97,13 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
     // Verify any remaining trait constraints arising from the function body
     for (constraint, expr_id) in std::mem::take(&mut type_checker.trait_constraints) {
         let span = type_checker.interner.expr_span(&expr_id);
-        type_checker.verify_trait_constraint(&constraint.typ, constraint.trait_id, expr_id, span);
+        type_checker.verify_trait_constraint(
+            &constraint.typ,
+            constraint.trait_id,
+            &constraint.trait_generics,
+            expr_id,
+            span,
+        );
     }
 
     errors.append(&mut type_checker.errors);
@@ -102,7 +118,7 @@ pub fn type_check_func(interner: &mut NodeInterner, func_id: FuncId) -> Vec<Type
         let (expr_span, empty_function) = function_info(interner, function_body_id);
         let func_span = interner.expr_span(function_body_id); // XXX: We could be more specific and return the span of the last stmt, however stmts do not have spans yet
         if let Type::TraitAsType(t) = &declared_return_type {
-            if interner.lookup_trait_implementation(&function_last_type, t.id).is_err() {
+            if interner.lookup_trait_implementation(&function_last_type, t.id, &[]).is_err() {
                 let error = TypeCheckError::TypeMismatchWithSource {
                     expected: declared_return_type.clone(),
                     actual: function_last_type,
@@ -166,6 +182,8 @@ impl<'interner> TypeChecker<'interner> {
             errors: Vec::new(),
             trait_constraints: Vec::new(),
             current_function: None,
+            return_types: Vec::new(),
+            in_unconstrained_block: false,
         }
     }
 
@@ -185,6 +203,8 @@ impl<'interner> TypeChecker<'interner> {
             errors: Vec::new(),
             trait_constraints: Vec::new(),
             current_function: None,
+            return_types: Vec::new(),
+            in_unconstrained_block: false,
         };
         this.check_statement(id);
         this.errors
@@ -229,7 +249,7 @@ mod test {
     use iter_extended::vecmap;
     use noirc_errors::{Location, Span};
 
-    use crate::graph::CrateId;
+    use crate::graph::{CrateId, OverflowMode};
     use crate::hir::def_map::{ModuleData, ModuleId};
     use crate::hir::resolution::import::PathResolutionError;
     use crate::hir_def::expr::HirIdent;
@@ -259,7 +279,7 @@ mod test {
         // let z = x + y;
         //
         // Push x variable
-        let x_id = interner.push_definition("x".into(), false, DefinitionKind::Local(None));
+        let x_id = interner.push_definition("x".into(), false, DefinitionKind::Local(None), None);
 
         // Safety: The FileId in a location isn't used for tests
         let file = FileId::default();
@@ -268,11 +288,11 @@ mod test {
         let x = HirIdent { id: x_id, location };
 
         // Push y variable
-        let y_id = interner.push_definition("y".into(), false, DefinitionKind::Local(None));
+        let y_id = interner.push_definition("y".into(), false, DefinitionKind::Local(None), None);
         let y = HirIdent { id: y_id, location };
 
         // Push z variable
-        let z_id = interner.push_definition("z".into(), false, DefinitionKind::Local(None));
+        let z_id = interner.push_definition("z".into(), false, DefinitionKind::Local(None), None);
         let z = HirIdent { id: z_id, location };
 
         // Push x and y as expressions
@@ -304,7 +324,12 @@ mod test {
 
         let name = HirIdent {
             location,
-            id: interner.push_definition("test_func".into(), false, DefinitionKind::Local(None)),
+            id: interner.push_definition(
+                "test_func".into(),
+                false,
+                DefinitionKind::Local(None),
+                None,
+            ),
         };
 
         // Add function meta
@@ -427,10 +452,9 @@ mod test {
         ) -> Result<ModuleDefId, PathResolutionError> {
             // Not here that foo::bar and hello::foo::bar would fetch the same thing
             let name = path.segments.last().unwrap();
-            self.0
-                .get(&name.0.contents)
-                .cloned()
-                .ok_or_else(move || PathResolutionError::Unresolved(name.clone()))
+            self.0.get(&name.0.contents).cloned().ok_or_else(move || {
+                PathResolutionError::Unresolved(name.clone(), Default::default())
+            })
         }
 
         fn local_module_id(&self) -> LocalModuleId {
@@ -495,6 +519,7 @@ mod test {
                 modules,
                 krate: CrateId::dummy_id(),
                 extern_prelude: BTreeMap::new(),
+                overflow_mode: OverflowMode::Trap,
             },
         );
 
@@ -4,7 +4,7 @@ use noirc_errors::{Location, Span};
 use crate::hir_def::expr::{HirExpression, HirIdent, HirLiteral};
 use crate::hir_def::stmt::{
     HirAssignStatement, HirConstrainStatement, HirForStatement, HirLValue, HirLetStatement,
-    HirPattern, HirStatement,
+    HirPattern, HirStatement, HirWhileStatement,
 };
 use crate::hir_def::types::Type;
 use crate::node_interner::{DefinitionId, ExprId, StmtId};
@@ -51,6 +51,16 @@ impl<'interner> TypeChecker<'interner> {
             HirStatement::Constrain(constrain_stmt) => self.check_constrain_stmt(constrain_stmt),
             HirStatement::Assign(assign_stmt) => self.check_assign_stmt(assign_stmt, stmt_id),
             HirStatement::For(for_loop) => self.check_for_loop(for_loop),
+            HirStatement::While(while_loop) => self.check_while_loop(while_loop),
+            HirStatement::Loop(loop_statement) => {
+                self.check_expression(&loop_statement.block);
+            }
+            HirStatement::Break | HirStatement::Continue => (),
+            HirStatement::Return(expr_id) => {
+                self.check_return(expr_id);
+                // Nothing after a `return` is reachable, so the statement may take on any type
+                return self.interner.next_type_variable();
+            }
             HirStatement::Error => (),
         }
         Type::Unit
@@ -89,6 +99,35 @@ impl<'interner> TypeChecker<'interner> {
         self.check_expression(&for_loop.block);
     }
 
+    fn check_while_loop(&mut self, while_loop: HirWhileStatement) {
+        let condition_type = self.check_expression(&while_loop.condition);
+        let expr_span = self.interner.expr_span(&while_loop.condition);
+
+        self.unify(&condition_type, &Type::Bool, || TypeCheckError::TypeMismatch {
+            expr_typ: condition_type.to_string(),
+            expected_typ: Type::Bool.to_string(),
+            expr_span,
+        });
+
+        self.check_expression(&while_loop.block);
+    }
+
+    fn check_return(&mut self, expr_id: ExprId) {
+        let actual = self.check_expression(&expr_id);
+        let expected = match self.return_types.last() {
+            // The value returned by a function returning `impl Trait` is only known from its body
+            Some(Type::TraitAsType(_)) | None => return,
+            Some(expected) => expected.clone(),
+        };
+
+        let expr_span = self.interner.expr_span(&expr_id);
+        self.unify_with_coercions(&actual, &expected, expr_id, || TypeCheckError::TypeMismatch {
+            expected_typ: expected.to_string(),
+            expr_typ: actual.to_string(),
+            expr_span,
+        });
+    }
+
     /// Associate a given HirPattern with the given Type, and remember
     /// this association in the NodeInterner.
     pub(crate) fn bind_pattern(&mut self, pattern: &HirPattern, typ: Type) {
@@ -202,6 +241,7 @@ impl<'interner> TypeChecker<'interner> {
             }
             HirLValue::MemberAccess { object, field_name, .. } => {
                 let (lhs_type, object, mut mutable) = self.check_lvalue(object, assign_span);
+                self.add_field_reference(&lhs_type, field_name, lvalue_file(&object));
                 let mut object = Box::new(object);
                 let span = field_name.span();
                 let field_name = field_name.clone();
@@ -313,6 +353,10 @@ impl<'interner> TypeChecker<'interner> {
             expected_typ: Type::Bool.to_string(),
             expr_span,
         });
+
+        if let Some(resolve_message_call) = stmt.3 {
+            self.check_expression(&resolve_message_call);
+        }
     }
 
     /// All declaration statements check that the user specified type(UST) is equal to the
@@ -351,14 +395,13 @@ impl<'interner> TypeChecker<'interner> {
         let span = self.interner.expr_span(rhs_expr);
         match expr {
             HirExpression::Literal(HirLiteral::Integer(value)) => {
-                let v = value.to_u128();
                 if let Type::Integer(_, bit_count) = annotated_type {
-                    let max = 1 << bit_count;
-                    if v >= max {
+                    if value.num_bits() > *bit_count {
+                        let max = u128::MAX >> (128 - bit_count);
                         self.errors.push(TypeCheckError::OverflowingAssignment {
                             expr: value,
                             ty: annotated_type.clone(),
-                            range: format!("0..={}", max - 1),
+                            range: format!("0..={max}"),
                             span,
                         });
                     };
@@ -375,3 +418,13 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 }
+
+/// Returns the file the variable an lvalue assigns to was referred to in.
+fn lvalue_file(lvalue: &HirLValue) -> fm::FileId {
+    match lvalue {
+        HirLValue::Ident(ident, _) => ident.location.file,
+        HirLValue::MemberAccess { object, .. } => lvalue_file(object),
+        HirLValue::Index { array, .. } => lvalue_file(array),
+        HirLValue::Dereference { lvalue, .. } => lvalue_file(lvalue),
+    }
+}
@@ -1,8 +1,10 @@
 use acvm::FieldElement;
 use fm::FileId;
-use noirc_errors::Location;
+use noirc_errors::{Location, Span};
 
-use crate::node_interner::{DefinitionId, ExprId, FuncId, NodeInterner, StmtId, TraitMethodId};
+use crate::node_interner::{
+    DefinitionId, ExprId, FuncId, NodeInterner, ReferenceId, StmtId, TraitMethodId,
+};
 use crate::{BinaryOp, BinaryOpKind, Ident, Shared, UnaryOp};
 
 use super::stmt::HirPattern;
@@ -22,14 +24,20 @@ pub enum HirExpression {
     Infix(HirInfixExpression),
     Index(HirIndexExpression),
     Constructor(HirConstructorExpression),
+    EnumConstructor(HirEnumConstructorExpression),
     MemberAccess(HirMemberAccess),
     Call(HirCallExpression),
     MethodCall(HirMethodCallExpression),
     Cast(HirCastExpression),
+    Try(HirTryExpression),
     If(HirIfExpression),
+    Match(HirMatchExpression),
     Tuple(Vec<ExprId>),
     Lambda(HirLambda),
     TraitMethodReference(TraitMethodId),
+    /// A `comptime` block, which is replaced with the value it evaluates to once every
+    /// function in its crate has been type checked.
+    Comptime(ExprId),
     Error,
 }
 
@@ -127,6 +135,15 @@ pub struct HirCastExpression {
     pub r#type: Type,
 }
 
+// `expr?` in the source code
+#[derive(Debug, Clone)]
+pub struct HirTryExpression {
+    pub expression: ExprId,
+    /// The return type of the enclosing function or lambda, which the `None` or `Err`
+    /// case is returned as. This is bound during type checking.
+    pub return_type: Type,
+}
+
 #[derive(Debug, Clone)]
 pub struct HirCallExpression {
     pub func: ExprId,
@@ -172,6 +189,8 @@ impl HirMethodCallExpression {
         let expr = match method {
             HirMethodReference::FuncId(func_id) => {
                 let id = interner.function_definition_id(func_id);
+                let method_location = Location::new(self.method.span(), location.file);
+                interner.add_reference(ReferenceId::Definition(id), method_location);
                 HirExpression::Ident(HirIdent { location, id })
             }
             HirMethodReference::TraitMethodId(method_id) => {
@@ -179,6 +198,7 @@ impl HirMethodCallExpression {
             }
         };
         let func = interner.push_expr(expr);
+        interner.push_expr_location(func, self.method.span(), location.file);
         (func, HirExpression::Call(HirCallExpression { func, arguments, location }))
     }
 }
@@ -196,6 +216,51 @@ pub struct HirConstructorExpression {
     pub fields: Vec<(Ident, ExprId)>,
 }
 
+/// Constructs a value of an enum variant, as in `Option::Some(x)` or `Option::None`.
+/// Fieldless variants have no arguments.
+#[derive(Debug, Clone)]
+pub struct HirEnumConstructorExpression {
+    pub r#type: Shared<StructType>,
+
+    /// The index of the variant within the enum's variants
+    pub variant: usize,
+    pub arguments: Vec<ExprId>,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone)]
+pub struct HirMatchExpression {
+    pub expression: ExprId,
+    pub arms: Vec<HirMatchArm>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HirMatchArm {
+    pub pattern: HirMatchPattern,
+    pub body: ExprId,
+}
+
+#[derive(Debug, Clone)]
+pub enum HirMatchPattern {
+    /// Matches a single variant of an enum, binding its payload to the given patterns
+    Variant { r#type: Shared<StructType>, variant: usize, fields: Vec<HirPattern>, span: Span },
+
+    /// Matches any value, binding it to the given pattern
+    Default(HirPattern),
+
+    /// A pattern which failed to resolve. An error has already been issued for it.
+    Error(Span),
+}
+
+impl HirMatchPattern {
+    pub fn span(&self) -> Span {
+        match self {
+            HirMatchPattern::Variant { span, .. } | HirMatchPattern::Error(span) => *span,
+            HirMatchPattern::Default(pattern) => pattern.span(),
+        }
+    }
+}
+
 /// Indexing, as in `array[index]`
 #[derive(Debug, Clone)]
 pub struct HirIndexExpression {
@@ -233,4 +298,7 @@ pub struct HirLambda {
     pub return_type: Type,
     pub body: ExprId,
     pub captures: Vec<HirCapturedVar>,
+    /// True for the lambda an `unconstrained { ... }` block is lowered to, whose body is
+    /// executed in Brillig
+    pub unconstrained: bool,
 }
@@ -14,6 +14,12 @@ pub enum HirStatement {
     Constrain(HirConstrainStatement),
     Assign(HirAssignStatement),
     For(HirForStatement),
+    While(HirWhileStatement),
+    Loop(HirLoopStatement),
+    Break,
+    Continue,
+    /// `return expr`, where a bare `return` returns a unit literal
+    Return(ExprId),
     Expression(ExprId),
     Semi(ExprId),
     Error,
@@ -35,12 +41,34 @@ impl HirLetStatement {
     }
 }
 
+/// Corresponds to `for identifier in start_range..end_range { block }` in the source code.
+/// `max_iterations` is only present if the loop was given a `#[max_iterations(N)]` attribute,
+/// in which case the range does not need to be known at compile-time.
 #[derive(Debug, Clone)]
 pub struct HirForStatement {
     pub identifier: HirIdent,
     pub start_range: ExprId,
     pub end_range: ExprId,
     pub block: ExprId,
+    pub max_iterations: Option<u64>,
+}
+
+/// Corresponds to `while condition { block }` in the source code.
+/// `max_iterations` is the upper bound on the number of iterations, either given
+/// explicitly or inferred from the condition. It is only absent in unconstrained code.
+#[derive(Debug, Clone)]
+pub struct HirWhileStatement {
+    pub condition: ExprId,
+    pub block: ExprId,
+    pub max_iterations: Option<u64>,
+}
+
+/// Corresponds to `loop { block }` in the source code. As with while loops,
+/// `max_iterations` is only absent in unconstrained code.
+#[derive(Debug, Clone)]
+pub struct HirLoopStatement {
+    pub block: ExprId,
+    pub max_iterations: Option<u64>,
 }
 
 /// Corresponds to `lvalue = expression;` in the source code
@@ -54,8 +82,13 @@ pub struct HirAssignStatement {
 /// This node also contains the FileId of the file the constrain
 /// originates from. This is used later in the SSA pass to issue
 /// an error if a constrain is found to be always false.
+///
+/// When the assertion message is a format string, the last field holds a call
+/// which resolves the formatted message at runtime if the constraint fails.
+/// The condition is then a variable bound before the constraint, so that the call
+/// and the constraint don't each evaluate it.
 #[derive(Debug, Clone)]
-pub struct HirConstrainStatement(pub ExprId, pub FileId, pub Option<String>);
+pub struct HirConstrainStatement(pub ExprId, pub FileId, pub Option<String>, pub Option<ExprId>);
 
 #[derive(Debug, Clone, Hash)]
 pub enum HirPattern {
@@ -68,6 +68,10 @@ pub struct TraitImpl {
     pub file: FileId,
     pub methods: Vec<FuncId>, // methods[i] is the implementation of trait.methods[i] for Type typ
 
+    /// The arguments given for the trait's generics, e.g. `[Field; 3]` in
+    /// `impl From<[Field; 3]> for Foo`.
+    pub trait_generics: Vec<Type>,
+
     /// The where clause, if present, contains each trait requirement which must
     /// be satisfied for this impl to be selected. E.g. in `impl Eq for [T] where T: Eq`,
     /// `where_clause` would contain the one `T: Eq` constraint. If there is no where clause,
@@ -79,12 +83,14 @@ pub struct TraitImpl {
 pub struct TraitConstraint {
     pub typ: Type,
     pub trait_id: TraitId,
-    // pub trait_generics: Generics, TODO
+    /// The arguments required for the trait's generics. These may be empty if they are not
+    /// known, in which case any impl of the trait for `typ` satisfies the constraint.
+    pub trait_generics: Vec<Type>,
 }
 
 impl TraitConstraint {
-    pub fn new(typ: Type, trait_id: TraitId) -> Self {
-        Self { typ, trait_id }
+    pub fn new(typ: Type, trait_id: TraitId, trait_generics: Vec<Type>) -> Self {
+        Self { typ, trait_id, trait_generics }
     }
 }
 
@@ -128,6 +134,10 @@ impl Trait {
         self.methods = methods;
     }
 
+    pub fn set_generics(&mut self, generics: Generics) {
+        self.generics = generics;
+    }
+
     pub fn find_method(&self, name: Ident) -> Option<TraitMethodId> {
         for (idx, method) in self.methods.iter().enumerate() {
             if method.name == name {
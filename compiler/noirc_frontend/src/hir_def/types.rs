@@ -115,6 +115,12 @@ impl Type {
                 let typ = typ.as_ref();
                 (length as u32) * typ.field_count()
             }
+            Type::Struct(ref def, args) if def.borrow().is_enum() => {
+                // Enums are encoded as a tag followed by the payloads of every variant
+                let variants = def.borrow().get_variants(args);
+                let payloads = variants.iter().flat_map(|(_, fields)| fields);
+                payloads.fold(1, |acc, field_type| acc + field_type.field_count())
+            }
             Type::Struct(ref def, args) => {
                 let struct_type = def.borrow();
                 let fields = struct_type.get_fields(args);
@@ -165,6 +171,12 @@ pub struct StructType {
     /// since these will handle applying generic arguments to fields as well.
     fields: Vec<(Ident, Type)>,
 
+    /// The variants of this type if it was declared as an enum, or None
+    /// for a struct. Each variant holds the types of its payload fields.
+    /// Like fields, these should be accessed through get_variant_fields()
+    /// or get_variants() so that generic arguments are applied.
+    variants: Option<Vec<(Ident, Vec<Type>)>>,
+
     pub generics: Generics,
     pub span: Span,
 }
@@ -195,7 +207,13 @@ impl StructType {
         fields: Vec<(Ident, Type)>,
         generics: Generics,
     ) -> StructType {
-        StructType { id, fields, name, span, generics }
+        StructType { id, fields, name, span, generics, variants: None }
+    }
+
+    /// Creates a new enum type. Its variants are set later
+    /// via set_variants(), analogous to a struct's fields.
+    pub fn new_enum(id: StructId, name: Ident, span: Span, generics: Generics) -> StructType {
+        StructType { id, fields: Vec::new(), name, span, generics, variants: Some(Vec::new()) }
     }
 
     /// To account for cyclic references between structs, a struct's
@@ -211,6 +229,75 @@ impl StructType {
         self.fields.len()
     }
 
+    pub fn is_enum(&self) -> bool {
+        self.variants.is_some()
+    }
+
+    /// Sets the variants of this enum once they have been resolved.
+    /// Panics if this type is a struct.
+    pub fn set_variants(&mut self, variants: Vec<(Ident, Vec<Type>)>) {
+        let this_variants = self.variants.as_mut().expect("set_variants called on a struct");
+        assert!(this_variants.is_empty());
+        *this_variants = variants;
+    }
+
+    pub fn num_variants(&self) -> usize {
+        self.variants.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Returns the index of the variant with the given name, if this is an enum with such a variant.
+    pub fn get_variant(&self, variant_name: &str) -> Option<usize> {
+        self.variants.as_ref()?.iter().position(|(name, _)| name.0.contents == variant_name)
+    }
+
+    /// If this is the standard library's `Option` or `Result`, returns the index of the variant
+    /// unwrapped by the `?` operator followed by the index of the variant it propagates.
+    pub fn try_variants(&self) -> Option<(usize, usize)> {
+        if !self.id.krate().is_stdlib() {
+            return None;
+        }
+
+        let (success, failure) = match self.name.0.contents.as_str() {
+            "Option" => ("Some", "None"),
+            "Result" => ("Ok", "Err"),
+            _ => return None,
+        };
+        Some((self.get_variant(success)?, self.get_variant(failure)?))
+    }
+
+    pub fn variant_name(&self, index: usize) -> &Ident {
+        &self.variants.as_ref().expect("variant_name called on a struct")[index].0
+    }
+
+    /// Returns the payload types of the variant at the given index,
+    /// after being applied to the given generic arguments.
+    pub fn get_variant_fields(&self, index: usize, generic_args: &[Type]) -> Vec<Type> {
+        let variants = self.variants.as_ref().expect("get_variant_fields called on a struct");
+        let substitutions = self.substitutions(generic_args);
+        vecmap(&variants[index].1, |typ| typ.substitute(&substitutions))
+    }
+
+    /// Returns every variant of this enum along with its payload types,
+    /// after being applied to the given generic arguments.
+    pub fn get_variants(&self, generic_args: &[Type]) -> Vec<(String, Vec<Type>)> {
+        let variants = self.variants.as_ref().expect("get_variants called on a struct");
+        let substitutions = self.substitutions(generic_args);
+        vecmap(variants, |(name, fields)| {
+            let fields = vecmap(fields, |typ| typ.substitute(&substitutions));
+            (name.0.contents.clone(), fields)
+        })
+    }
+
+    fn substitutions(&self, generic_args: &[Type]) -> TypeBindings {
+        assert_eq!(self.generics.len(), generic_args.len());
+
+        self.generics
+            .iter()
+            .zip(generic_args)
+            .map(|((old_id, old_var), new)| (*old_id, (old_var.clone(), new.clone())))
+            .collect()
+    }
+
     /// Returns the field matching the given field name, as well as its field index.
     pub fn get_field(&self, field_name: &str, generic_args: &[Type]) -> Option<(Type, usize)> {
         assert_eq!(self.generics.len(), generic_args.len());
@@ -255,7 +342,12 @@ impl StructType {
     /// This is needed because we infer type kinds in Noir and don't have extensive kind checking.
     pub fn generic_is_numeric(&self, index_of_generic: usize) -> bool {
         let target_id = self.generics[index_of_generic].0;
-        self.fields.iter().any(|(_, field)| field.contains_numeric_typevar(target_id))
+        let variant_fields = self.variants.iter().flatten().flat_map(|(_, fields)| fields);
+        self.fields
+            .iter()
+            .map(|(_, field)| field)
+            .chain(variant_fields)
+            .any(|field| field.contains_numeric_typevar(target_id))
     }
 
     /// Instantiate this struct type, returning a Vec of the new generic args (in
@@ -569,7 +661,7 @@ impl Type {
     /// This function should match the same check done in `create_value_from_type` in acir_gen.
     /// If this function does not catch a case where a type should be valid, it will later lead to a
     /// panic in that function instead of a user-facing compiler error message.
-    pub(crate) fn is_valid_for_program_input(&self) -> bool {
+    pub fn is_valid_for_program_input(&self) -> bool {
         match self {
             // Type::Error is allowed as usual since it indicates an error was already issued and
             // we don't need to issue further errors about this likely unresolved type
@@ -594,6 +686,12 @@ impl Type {
             }
             Type::String(length) => length.is_valid_for_program_input(),
             Type::Tuple(elements) => elements.iter().all(|elem| elem.is_valid_for_program_input()),
+            Type::Struct(definition, generics) if definition.borrow().is_enum() => definition
+                .borrow()
+                .get_variants(generics)
+                .into_iter()
+                .flat_map(|(_, fields)| fields)
+                .all(|field| field.is_valid_for_program_input()),
             Type::Struct(definition, generics) => definition
                 .borrow()
                 .get_fields(generics)
@@ -1499,6 +1597,13 @@ impl From<&Type> for PrintableType {
             Type::Error => unreachable!(),
             Type::Unit => unreachable!(),
             Type::Constant(_) => unreachable!(),
+            Type::Struct(def, ref args) if def.borrow().is_enum() => {
+                let enum_type = def.borrow();
+                let variants = enum_type.get_variants(args);
+                let variants =
+                    vecmap(variants, |(name, fields)| (name, vecmap(fields, Into::into)));
+                PrintableType::Enum { variants, name: enum_type.name.to_string() }
+            }
             Type::Struct(def, ref args) => {
                 let struct_type = def.borrow();
                 let fields = struct_type.get_fields(args);
@@ -1,10 +1,12 @@
+use crate::lints::Lint;
 use crate::parser::ParserError;
 use crate::parser::ParserErrorReason;
 use crate::token::SpannedToken;
 
 use super::token::Token;
+use noirc_errors::error_codes as codes;
 use noirc_errors::CustomDiagnostic as Diagnostic;
-use noirc_errors::Span;
+use noirc_errors::{Applicability, Span, Suggestion};
 use thiserror::Error;
 
 #[derive(Error, Clone, Debug, PartialEq, Eq)]
@@ -17,7 +19,9 @@ pub enum LexerErrorKind {
     InvalidIntegerLiteral { span: Span, found: String },
     #[error("{:?} is not a valid attribute", found)]
     MalformedFuncAttribute { span: Span, found: String },
-    #[error("Integer type is larger than the maximum supported size of u127")]
+    #[error("Unknown lint {:?}", found)]
+    UnknownLint { span: Span, found: String },
+    #[error("Integer type is larger than the maximum supported size of {max} bits")]
     TooManyBits { span: Span, max: u32, got: u32 },
     #[error("Logical and used instead of bitwise and")]
     LogicalAnd { span: Span },
@@ -45,6 +49,7 @@ impl LexerErrorKind {
             LexerErrorKind::NotADoubleChar { span, .. } => *span,
             LexerErrorKind::InvalidIntegerLiteral { span, .. } => *span,
             LexerErrorKind::MalformedFuncAttribute { span, .. } => *span,
+            LexerErrorKind::UnknownLint { span, .. } => *span,
             LexerErrorKind::TooManyBits { span, .. } => *span,
             LexerErrorKind::LogicalAnd { span } => *span,
             LexerErrorKind::UnterminatedBlockComment { span } => *span,
@@ -53,6 +58,22 @@ impl LexerErrorKind {
         }
     }
 
+    /// Returns the stable error code of this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexerErrorKind::UnexpectedCharacter { .. } => codes::E0001,
+            LexerErrorKind::NotADoubleChar { .. } => codes::E0002,
+            LexerErrorKind::InvalidIntegerLiteral { .. } => codes::E0003,
+            LexerErrorKind::MalformedFuncAttribute { .. } => codes::E0004,
+            LexerErrorKind::UnknownLint { .. } => codes::E0005,
+            LexerErrorKind::TooManyBits { .. } => codes::E0006,
+            LexerErrorKind::LogicalAnd { .. } => codes::E0007,
+            LexerErrorKind::UnterminatedBlockComment { .. } => codes::E0008,
+            LexerErrorKind::UnterminatedStringLiteral { .. } => codes::E0009,
+            LexerErrorKind::InvalidEscape { .. } => codes::E0010,
+        }
+    }
+
     fn parts(&self) -> (String, String, Span) {
         match self {
             LexerErrorKind::UnexpectedCharacter {
@@ -85,10 +106,15 @@ impl LexerErrorKind {
                 format!(" {found} is not a valid attribute"),
                 *span,
             ),
+            LexerErrorKind::UnknownLint { span, found } => (
+                format!("Unknown lint `{found}`"),
+                format!("Expected one of {}", Lint::ALL.map(Lint::name).join(", ")),
+                *span,
+            ),
             LexerErrorKind::TooManyBits { span, max, got } => (
                 "Integer literal too large".to_string(),
                 format!(
-                    "The maximum number of bits needed to represent a field is {max}, This integer type needs {got} bits"
+                    "Integer types of this signedness may be at most {max} bits wide, this type is {got} bits wide"
                 ),
                 *span,
             ),
@@ -109,7 +135,17 @@ impl LexerErrorKind {
 impl From<LexerErrorKind> for Diagnostic {
     fn from(error: LexerErrorKind) -> Diagnostic {
         let (primary, secondary, span) = error.parts();
-        Diagnostic::simple_error(primary, secondary, span)
+        let mut diagnostic =
+            Diagnostic::simple_error(primary, secondary, span).with_code(error.code());
+        if let LexerErrorKind::LogicalAnd { span } = error {
+            diagnostic.add_suggestion(Suggestion::new(
+                "use the bitwise-and operator",
+                span,
+                "&",
+                Applicability::MaybeIncorrect,
+            ));
+        }
+        diagnostic
     }
 }
 
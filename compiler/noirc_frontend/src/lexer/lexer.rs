@@ -115,11 +115,12 @@ impl<'a> Lexer<'a> {
             Some('%') => self.single_char_token(Token::Percent),
             Some('^') => self.single_char_token(Token::Caret),
             Some(';') => self.single_char_token(Token::Semicolon),
-            Some('*') => self.single_char_token(Token::Star),
+            Some('?') => self.single_char_token(Token::Question),
+            Some('*') => self.glue(Token::Star),
             Some('(') => self.single_char_token(Token::LeftParen),
             Some(')') => self.single_char_token(Token::RightParen),
             Some(',') => self.single_char_token(Token::Comma),
-            Some('+') => self.single_char_token(Token::Plus),
+            Some('+') => self.glue(Token::Plus),
             Some('{') => self.single_char_token(Token::LeftBrace),
             Some('}') => self.single_char_token(Token::RightBrace),
             Some('[') => self.single_char_token(Token::LeftBracket),
@@ -192,8 +193,32 @@ impl<'a> Lexer<'a> {
                 }
             }
             Token::Bang => self.single_double_peek_token('=', prev_token, Token::NotEqual),
-            Token::Assign => self.single_double_peek_token('=', prev_token, Token::Equal),
-            Token::Minus => self.single_double_peek_token('>', prev_token, Token::Arrow),
+            Token::Assign => {
+                let start = self.position;
+                if self.peek_char_is('=') {
+                    self.next_char();
+                    Ok(Token::Equal.into_span(start, start + 1))
+                } else if self.peek_char_is('>') {
+                    self.next_char();
+                    Ok(Token::FatArrow.into_span(start, start + 1))
+                } else {
+                    Ok(prev_token.into_single_span(start))
+                }
+            }
+            Token::Minus => {
+                let start = self.position;
+                if self.peek_char_is('>') {
+                    self.next_char();
+                    Ok(Token::Arrow.into_span(start, start + 1))
+                } else if self.peek_char_is('%') {
+                    self.next_char();
+                    Ok(Token::WrappingMinus.into_span(start, start + 1))
+                } else {
+                    Ok(prev_token.into_single_span(start))
+                }
+            }
+            Token::Plus => self.single_double_peek_token('%', prev_token, Token::WrappingPlus),
+            Token::Star => self.single_double_peek_token('%', prev_token, Token::WrappingStar),
             Token::Colon => self.single_double_peek_token(':', prev_token, Token::DoubleColon),
             Token::Slash => {
                 let start = self.position;
@@ -490,10 +515,11 @@ impl<'a> Iterator for Lexer<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::token::{FunctionAttribute, SecondaryAttribute, TestScope};
+    use crate::token::{FunctionAttribute, SecondaryAttribute, TestMode, TestScope};
     #[test]
     fn test_single_double_char() {
-        let input = "! != + ( ) { } [ ] | , ; : :: < <= > >= & - -> . .. % / * = == << >>";
+        let input =
+            "! != + ( ) { } [ ] | , ; : :: < <= > >= & - -> . .. % / * = == => << >> +% -% *%";
 
         let expected = vec![
             Token::Bang,
@@ -524,9 +550,13 @@ mod tests {
             Token::Star,
             Token::Assign,
             Token::Equal,
+            Token::FatArrow,
             Token::ShiftLeft,
             Token::Greater,
             Token::Greater,
+            Token::WrappingPlus,
+            Token::WrappingMinus,
+            Token::WrappingStar,
             Token::EOF,
         ];
 
@@ -568,7 +598,8 @@ mod tests {
         assert_eq!(
             token,
             Token::Attribute(Attribute::Function(FunctionAttribute::Test(
-                TestScope::ShouldFailWith { reason: "the eagle's feathers".to_owned().into() }
+                TestScope::ShouldFailWith { reason: "the eagle's feathers".to_owned().into() },
+                TestMode::Any
             )))
         );
     }
@@ -587,6 +618,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn max_iterations_attribute() {
+        let input = "#[max_iterations(10)]";
+        let mut lexer = Lexer::new(input);
+
+        let token = lexer.next_token().unwrap();
+        assert_eq!(
+            token.token(),
+            &Token::Attribute(Attribute::Secondary(SecondaryAttribute::MaxIterations(10)))
+        );
+
+        let mut lexer = Lexer::new("#[max_iterations(ten)]");
+        assert!(lexer.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn max_recursion_depth_attribute() {
+        let input = "#[max_recursion_depth(16)]";
+        let mut lexer = Lexer::new(input);
+
+        let token = lexer.next_token().unwrap();
+        assert_eq!(
+            token.token(),
+            &Token::Attribute(Attribute::Secondary(SecondaryAttribute::MaxRecursionDepth(16)))
+        );
+
+        let mut lexer = Lexer::new("#[max_recursion_depth(sixteen)]");
+        assert!(lexer.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn inline_attributes() {
+        let mut lexer = Lexer::new("#[inline(always)]");
+        assert_eq!(
+            lexer.next_token().unwrap().token(),
+            &Token::Attribute(Attribute::Secondary(SecondaryAttribute::InlineAlways))
+        );
+
+        let mut lexer = Lexer::new("#[no_inline]");
+        assert_eq!(
+            lexer.next_token().unwrap().token(),
+            &Token::Attribute(Attribute::Secondary(SecondaryAttribute::NoInline))
+        );
+    }
+
+    #[test]
+    fn export_attribute() {
+        let mut lexer = Lexer::new("#[export]");
+        assert_eq!(
+            lexer.next_token().unwrap().token(),
+            &Token::Attribute(Attribute::Secondary(SecondaryAttribute::Export))
+        );
+    }
+
+    #[test]
+    fn derive_attribute() {
+        let input = "#[derive(Eq, Default)]";
+        let mut lexer = Lexer::new(input);
+
+        let token = lexer.next_token().unwrap();
+        assert_eq!(
+            token.token(),
+            &Token::Attribute(Attribute::Secondary(SecondaryAttribute::Derive(vec![
+                "Eq".to_string(),
+                "Default".to_string()
+            ])))
+        );
+
+        let mut lexer = Lexer::new("#[derive(Eq,)]");
+        assert!(lexer.next().unwrap().is_err());
+    }
+
     #[test]
     fn test_custom_gate_syntax() {
         let input = "#[foreign(sha256)]#[foreign(blake2s)]#[builtin(sum)]";
@@ -628,7 +731,10 @@ mod tests {
         let token = lexer.next_token().unwrap();
         assert_eq!(
             token.token(),
-            &Token::Attribute(Attribute::Function(FunctionAttribute::Test(TestScope::None)))
+            &Token::Attribute(Attribute::Function(FunctionAttribute::Test(
+                TestScope::None,
+                TestMode::Any
+            )))
         );
     }
 
@@ -653,7 +759,8 @@ mod tests {
         assert_eq!(
             token.token(),
             &Token::Attribute(Attribute::Function(FunctionAttribute::Test(
-                TestScope::ShouldFailWith { reason: None }
+                TestScope::ShouldFailWith { reason: None },
+                TestMode::Any
             )))
         );
     }
@@ -667,11 +774,61 @@ mod tests {
         assert_eq!(
             token.token(),
             &Token::Attribute(Attribute::Function(FunctionAttribute::Test(
-                TestScope::ShouldFailWith { reason: Some("hello".to_owned()) }
+                TestScope::ShouldFailWith { reason: Some("hello".to_owned()) },
+                TestMode::Any
             )))
         );
     }
 
+    #[test]
+    fn test_attribute_with_execution_mode() {
+        let cases = [
+            ("#[test(only_acir)]", TestMode::OnlyAcir),
+            ("#[test(only_brillig)]", TestMode::OnlyBrillig),
+            ("#[test(ignore)]", TestMode::Ignore { reason: None }),
+            (
+                r#"#[test(ignore = "too slow for now")]"#,
+                TestMode::Ignore { reason: Some("too slow for now".to_owned()) },
+            ),
+        ];
+
+        for (input, mode) in cases {
+            let mut lexer = Lexer::new(input);
+            let token = lexer.next_token().unwrap();
+            assert_eq!(
+                token.token(),
+                &Token::Attribute(Attribute::Function(FunctionAttribute::Test(
+                    TestScope::None,
+                    mode
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn test_attribute_with_scope_and_execution_mode() {
+        let input = r#"#[test(only_brillig, should_fail_with = "hello")]"#;
+        let mut lexer = Lexer::new(input);
+
+        let token = lexer.next_token().unwrap();
+        assert_eq!(
+            token.token(),
+            &Token::Attribute(Attribute::Function(FunctionAttribute::Test(
+                TestScope::ShouldFailWith { reason: Some("hello".to_owned()) },
+                TestMode::OnlyBrillig
+            )))
+        );
+    }
+
+    #[test]
+    fn test_attribute_with_duplicate_execution_mode() {
+        let input = r#"#[test(only_acir, only_brillig)]"#;
+        let mut lexer = Lexer::new(input);
+
+        let token = lexer.next().unwrap();
+        assert!(matches!(token, Err(LexerErrorKind::MalformedFuncAttribute { .. })));
+    }
+
     #[test]
     fn test_attribute_with_invalid_scope() {
         let input = r#"#[test(invalid_scope)]"#;
@@ -975,4 +1132,16 @@ mod tests {
             assert_eq!(got, token);
         }
     }
+
+    #[test]
+    fn integer_type_widths() {
+        let mut lexer = Lexer::new("u128 i127");
+        assert_eq!(lexer.next_token().unwrap().token(), &Token::IntType(IntType::Unsigned(128)));
+        assert_eq!(lexer.next_token().unwrap().token(), &Token::IntType(IntType::Signed(127)));
+
+        for input in ["u129", "i128"] {
+            let mut lexer = Lexer::new(input);
+            assert!(matches!(lexer.next_token(), Err(LexerErrorKind::TooManyBits { .. })));
+        }
+    }
 }
@@ -3,6 +3,7 @@ use noirc_errors::{Position, Span, Spanned};
 use std::{fmt, iter::Map, vec::IntoIter};
 
 use crate::lexer::errors::LexerErrorKind;
+use crate::lints::{Lint, LintLevel};
 
 /// Represents a token in noir's grammar - a word, number,
 /// or symbol that can be used in noir's syntax. This is the
@@ -39,6 +40,12 @@ pub enum Token {
     Minus,
     /// *
     Star,
+    /// +%
+    WrappingPlus,
+    /// -%
+    WrappingMinus,
+    /// *%
+    WrappingStar,
     /// /
     Slash,
     /// %
@@ -69,6 +76,8 @@ pub enum Token {
     RightBracket,
     /// ->
     Arrow,
+    /// =>
+    FatArrow,
     /// |
     Pipe,
     /// #
@@ -83,6 +92,8 @@ pub enum Token {
     Semicolon,
     /// !
     Bang,
+    /// ?
+    Question,
     /// =
     Assign,
     #[allow(clippy::upper_case_acronyms)]
@@ -171,6 +182,9 @@ impl fmt::Display for Token {
             Token::Plus => write!(f, "+"),
             Token::Minus => write!(f, "-"),
             Token::Star => write!(f, "*"),
+            Token::WrappingPlus => write!(f, "+%"),
+            Token::WrappingMinus => write!(f, "-%"),
+            Token::WrappingStar => write!(f, "*%"),
             Token::Slash => write!(f, "/"),
             Token::Percent => write!(f, "%"),
             Token::Ampersand => write!(f, "&"),
@@ -186,6 +200,7 @@ impl fmt::Display for Token {
             Token::LeftBracket => write!(f, "["),
             Token::RightBracket => write!(f, "]"),
             Token::Arrow => write!(f, "->"),
+            Token::FatArrow => write!(f, "=>"),
             Token::Pipe => write!(f, "|"),
             Token::Pound => write!(f, "#"),
             Token::Comma => write!(f, ","),
@@ -194,6 +209,7 @@ impl fmt::Display for Token {
             Token::Semicolon => write!(f, ";"),
             Token::Assign => write!(f, "="),
             Token::Bang => write!(f, "!"),
+            Token::Question => write!(f, "?"),
             Token::EOF => write!(f, "end of input"),
             Token::Invalid(c) => write!(f, "{c}"),
             Token::Whitespace(ref s) => write!(f, "{s}"),
@@ -248,9 +264,23 @@ impl Token {
 
     /// These are all the operators allowed as part of
     /// a short-hand assignment: a <op>= b
-    pub fn assign_shorthand_operators() -> [Token; 10] {
+    pub fn assign_shorthand_operators() -> [Token; 13] {
         use Token::*;
-        [Plus, Minus, Star, Slash, Percent, Ampersand, Caret, ShiftLeft, ShiftRight, Pipe]
+        [
+            Plus,
+            Minus,
+            Star,
+            WrappingPlus,
+            WrappingMinus,
+            WrappingStar,
+            Slash,
+            Percent,
+            Ampersand,
+            Caret,
+            ShiftLeft,
+            ShiftRight,
+            Pipe,
+        ]
     }
 
     pub fn try_into_binary_op(self, span: Span) -> Option<Spanned<crate::BinaryOpKind>> {
@@ -264,6 +294,9 @@ impl Token {
             Token::Pipe => Or,
             Token::Minus => Subtract,
             Token::Star => Multiply,
+            Token::WrappingPlus => WrappingAdd,
+            Token::WrappingMinus => WrappingSubtract,
+            Token::WrappingStar => WrappingMultiply,
             Token::Slash => Divide,
             Token::Equal => Equal,
             Token::NotEqual => NotEqual,
@@ -315,7 +348,9 @@ impl IntType {
             Err(_) => return Ok(None),
         };
 
-        let max_bits = FieldElement::max_num_bits() / 2;
+        // Unsigned integers may be as wide as u128, the widest integer whose overflow checks
+        // still fit within the field. Signed integers need an extra bit for their sign.
+        let max_bits = if is_signed { FieldElement::max_num_bits() / 2 } else { 128 };
 
         if str_as_u32 > max_bits {
             return Err(LexerErrorKind::TooManyBits { span, max: max_bits, got: str_as_u32 });
@@ -364,13 +399,79 @@ impl fmt::Display for TestScope {
         match self {
             TestScope::None => write!(f, ""),
             TestScope::ShouldFailWith { reason } => match reason {
-                Some(failure_reason) => write!(f, "(should_fail_with = ({failure_reason}))"),
+                Some(failure_reason) => write!(f, "should_fail_with = \"{failure_reason}\""),
                 None => write!(f, "should_fail"),
             },
         }
     }
 }
 
+/// TestMode specifies which execution pipelines a test function is run in
+#[derive(PartialEq, Eq, Hash, Debug, Clone, PartialOrd, Ord)]
+pub enum TestMode {
+    /// The test is compiled to ACIR, unless all tests are forced to run in Brillig
+    Any,
+    /// The test is always compiled to ACIR
+    OnlyAcir,
+    /// The test is always compiled to Brillig
+    OnlyBrillig,
+    /// The test is not run, optionally recording why
+    Ignore { reason: Option<String> },
+}
+
+impl TestMode {
+    fn lookup_str(string: &str) -> Option<TestMode> {
+        match string.trim() {
+            "only_acir" => Some(TestMode::OnlyAcir),
+            "only_brillig" => Some(TestMode::OnlyBrillig),
+            "ignore" => Some(TestMode::Ignore { reason: None }),
+            s if s.starts_with("ignore") => {
+                let parts: Vec<&str> = s.splitn(2, '=').collect();
+                if parts.len() == 2 && parts[0].trim() == "ignore" {
+                    let reason = parts[1].trim().trim_matches('"');
+                    Some(TestMode::Ignore { reason: Some(reason.to_string()) })
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TestMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TestMode::Any => write!(f, ""),
+            TestMode::OnlyAcir => write!(f, "only_acir"),
+            TestMode::OnlyBrillig => write!(f, "only_brillig"),
+            TestMode::Ignore { reason: Some(reason) } => write!(f, "ignore = \"{reason}\""),
+            TestMode::Ignore { reason: None } => write!(f, "ignore"),
+        }
+    }
+}
+
+/// Splits the arguments of a `#[test(..)]` attribute on each comma which is not within a string
+fn split_test_arguments(arguments: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_string = false;
+    let mut start = 0;
+
+    for (index, ch) in arguments.char_indices() {
+        match ch {
+            '"' => in_string = !in_string,
+            ',' if !in_string => {
+                parts.push(&arguments[start..index]);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+
+    parts.push(&arguments[start..]);
+    parts
+}
+
 #[derive(PartialEq, Eq, Hash, Debug, Clone, PartialOrd, Ord)]
 // Attributes are special language markers in the target language
 // An example of one is `#[SHA256]` . Currently only Foreign attributes are supported
@@ -398,7 +499,12 @@ impl Attributes {
     }
 
     pub fn is_test_function(&self) -> bool {
-        matches!(self.function, Some(FunctionAttribute::Test(_)))
+        matches!(self.function, Some(FunctionAttribute::Test(..)))
+    }
+
+    /// Returns true if one of the secondary attributes is `export`
+    pub fn has_export(&self) -> bool {
+        self.secondary.iter().any(|attribute| attribute == &SecondaryAttribute::Export)
     }
 
     /// True if these attributes mean the given function is an entry point function if it was
@@ -408,6 +514,32 @@ impl Attributes {
         !self.has_contract_library_method() && !self.is_test_function()
     }
 
+    /// Returns the bound given by a `#[max_recursion_depth(N)]` attribute, if any
+    pub fn max_recursion_depth(&self) -> Option<u64> {
+        self.secondary.iter().find_map(|attr| match attr {
+            SecondaryAttribute::MaxRecursionDepth(depth) => Some(*depth),
+            _ => None,
+        })
+    }
+
+    /// Returns how the inliner should treat this function as set by `#[inline(always)]`
+    /// or `#[no_inline]`
+    pub fn inline_type(&self) -> InlineType {
+        self.secondary
+            .iter()
+            .find_map(|attr| match attr {
+                SecondaryAttribute::InlineAlways => Some(InlineType::Always),
+                SecondaryAttribute::NoInline => Some(InlineType::Never),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the level set for each lint by `#[allow]`, `#[warn]` and `#[deny]` attributes
+    pub fn lint_levels(&self) -> impl Iterator<Item = (Lint, LintLevel)> + '_ {
+        self.secondary.iter().filter_map(SecondaryAttribute::lint_level)
+    }
+
     /// Returns note if a deprecated secondary attribute is found
     pub fn get_deprecated_note(&self) -> Option<Option<String>> {
         self.secondary.iter().find_map(|attr| match attr {
@@ -426,6 +558,21 @@ impl Attributes {
     }
 }
 
+/// How calls to a function should be treated by the SSA inliner.
+///
+/// Calls between constrained functions are always inlined as ACIR has no notion of calls,
+/// so this only affects calls made from unconstrained code.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone, PartialOrd, Ord)]
+pub enum InlineType {
+    /// Inline the function if it is small enough
+    #[default]
+    Default,
+    /// `#[inline(always)]`: inline the function regardless of its size
+    Always,
+    /// `#[no_inline]`: never inline the function
+    Never,
+}
+
 /// An Attribute can be either a Primary Attribute or a Secondary Attribute
 /// A Primary Attribute can alter the function type, thus there can only be one
 /// A secondary attribute has no effect and is either consumed by a library or used as a notice for the developer
@@ -486,15 +633,33 @@ impl Attribute {
                 validate(name)?;
                 Attribute::Function(FunctionAttribute::Oracle(name.to_string()))
             }
-            ["test"] => Attribute::Function(FunctionAttribute::Test(TestScope::None)),
-            ["test", name] => {
-                validate(name)?;
+            ["test"] => {
+                Attribute::Function(FunctionAttribute::Test(TestScope::None, TestMode::Any))
+            }
+            ["test", arguments] => {
                 let malformed_scope =
-                    LexerErrorKind::MalformedFuncAttribute { span, found: word.to_owned() };
-                match TestScope::lookup_str(name) {
-                    Some(scope) => Attribute::Function(FunctionAttribute::Test(scope)),
-                    None => return Err(malformed_scope),
+                    || LexerErrorKind::MalformedFuncAttribute { span, found: word.to_owned() };
+
+                // A test may have at most one scope and one mode, in any order
+                let mut scope = None;
+                let mut mode = None;
+                for argument in split_test_arguments(arguments) {
+                    validate(argument)?;
+                    let duplicate = if let Some(new_scope) = TestScope::lookup_str(argument) {
+                        scope.replace(new_scope).is_some()
+                    } else if let Some(new_mode) = TestMode::lookup_str(argument) {
+                        mode.replace(new_mode).is_some()
+                    } else {
+                        true
+                    };
+                    if duplicate {
+                        return Err(malformed_scope());
+                    }
                 }
+
+                let scope = scope.unwrap_or(TestScope::None);
+                let mode = mode.unwrap_or(TestMode::Any);
+                Attribute::Function(FunctionAttribute::Test(scope, mode))
             }
             ["field", name] => {
                 validate(name)?;
@@ -506,6 +671,43 @@ impl Attribute {
                 Attribute::Secondary(SecondaryAttribute::ContractLibraryMethod)
             }
             ["event"] => Attribute::Secondary(SecondaryAttribute::Event),
+            ["export"] => Attribute::Secondary(SecondaryAttribute::Export),
+            ["derive", traits] => {
+                let traits: Vec<String> =
+                    traits.split(',').map(|name| name.trim().to_string()).collect();
+                let is_valid = traits.iter().all(|name| {
+                    !name.is_empty()
+                        && name.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+                });
+                if !is_valid {
+                    return Err(LexerErrorKind::MalformedFuncAttribute {
+                        span,
+                        found: word.to_owned(),
+                    });
+                }
+                Attribute::Secondary(SecondaryAttribute::Derive(traits))
+            }
+            ["max_iterations", count] => {
+                let count = count.trim().parse::<u64>().map_err(|_| {
+                    LexerErrorKind::MalformedFuncAttribute { span, found: word.to_owned() }
+                })?;
+                Attribute::Secondary(SecondaryAttribute::MaxIterations(count))
+            }
+            ["max_recursion_depth", depth] => {
+                let depth = depth.trim().parse::<u64>().map_err(|_| {
+                    LexerErrorKind::MalformedFuncAttribute { span, found: word.to_owned() }
+                })?;
+                Attribute::Secondary(SecondaryAttribute::MaxRecursionDepth(depth))
+            }
+            ["inline", "always"] => Attribute::Secondary(SecondaryAttribute::InlineAlways),
+            ["no_inline"] => Attribute::Secondary(SecondaryAttribute::NoInline),
+            [level @ ("allow" | "warn" | "deny"), name] => {
+                let level = LintLevel::lookup(level).expect("level names are matched above");
+                let lint = Lint::lookup(name.trim()).ok_or_else(|| {
+                    LexerErrorKind::UnknownLint { span, found: name.trim().to_owned() }
+                })?;
+                Attribute::Secondary(SecondaryAttribute::Lint(level, lint))
+            }
             ["deprecated", name] => {
                 if !name.starts_with('"') && !name.ends_with('"') {
                     return Err(LexerErrorKind::MalformedFuncAttribute {
@@ -535,7 +737,7 @@ pub enum FunctionAttribute {
     Foreign(String),
     Builtin(String),
     Oracle(String),
-    Test(TestScope),
+    Test(TestScope, TestMode),
 }
 
 impl FunctionAttribute {
@@ -565,7 +767,16 @@ impl FunctionAttribute {
 impl fmt::Display for FunctionAttribute {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            FunctionAttribute::Test(scope) => write!(f, "#[test{scope}]"),
+            FunctionAttribute::Test(scope, mode) => {
+                let arguments = [scope.to_string(), mode.to_string()];
+                let arguments: Vec<_> =
+                    arguments.into_iter().filter(|argument| !argument.is_empty()).collect();
+                if arguments.is_empty() {
+                    write!(f, "#[test]")
+                } else {
+                    write!(f, "#[test({})]", arguments.join(", "))
+                }
+            }
             FunctionAttribute::Foreign(ref k) => write!(f, "#[foreign({k})]"),
             FunctionAttribute::Builtin(ref k) => write!(f, "#[builtin({k})]"),
             FunctionAttribute::Oracle(ref k) => write!(f, "#[oracle({k})]"),
@@ -584,7 +795,22 @@ pub enum SecondaryAttribute {
     // the entry point.
     ContractLibraryMethod,
     Event,
+    // The function this is attached to is compiled into its own artifact by `nargo export`.
+    Export,
     Field(String),
+    // Upper bound on the number of iterations of the `while` or `loop` loop it is attached to.
+    MaxIterations(u64),
+    // Upper bound on the number of nested calls a constrained function may make to itself.
+    MaxRecursionDepth(u64),
+    // Traits the compiler should implement for the struct this is attached to.
+    Derive(Vec<String>),
+    // Calls to the function this is attached to should be inlined wherever possible.
+    InlineAlways,
+    // Calls to the function this is attached to should never be inlined unless required.
+    NoInline,
+    // `#[allow(lint)]`, `#[warn(lint)]` or `#[deny(lint)]`, setting the level a lint is reported at
+    // within the item this is attached to.
+    Lint(LintLevel, Lint),
     Custom(String),
 }
 
@@ -598,7 +824,16 @@ impl fmt::Display for SecondaryAttribute {
             SecondaryAttribute::Custom(ref k) => write!(f, "#[{k}]"),
             SecondaryAttribute::ContractLibraryMethod => write!(f, "#[contract_library_method]"),
             SecondaryAttribute::Event => write!(f, "#[event]"),
+            SecondaryAttribute::Export => write!(f, "#[export]"),
             SecondaryAttribute::Field(ref k) => write!(f, "#[field({k})]"),
+            SecondaryAttribute::MaxIterations(count) => write!(f, "#[max_iterations({count})]"),
+            SecondaryAttribute::MaxRecursionDepth(depth) => {
+                write!(f, "#[max_recursion_depth({depth})]")
+            }
+            SecondaryAttribute::Derive(traits) => write!(f, "#[derive({})]", traits.join(", ")),
+            SecondaryAttribute::InlineAlways => write!(f, "#[inline(always)]"),
+            SecondaryAttribute::NoInline => write!(f, "#[no_inline]"),
+            SecondaryAttribute::Lint(level, lint) => write!(f, "#[{level}({lint})]"),
         }
     }
 }
@@ -614,6 +849,16 @@ impl AsRef<str> for FunctionAttribute {
     }
 }
 
+impl SecondaryAttribute {
+    /// Returns the lint and the level it is set to if this is a lint attribute
+    pub fn lint_level(&self) -> Option<(Lint, LintLevel)> {
+        match self {
+            SecondaryAttribute::Lint(level, lint) => Some((*lint, *level)),
+            _ => None,
+        }
+    }
+}
+
 impl AsRef<str> for SecondaryAttribute {
     fn as_ref(&self) -> &str {
         match self {
@@ -622,6 +867,13 @@ impl AsRef<str> for SecondaryAttribute {
             SecondaryAttribute::Custom(string) | SecondaryAttribute::Field(string) => string,
             SecondaryAttribute::ContractLibraryMethod => "",
             SecondaryAttribute::Event => "",
+            SecondaryAttribute::Export => "",
+            SecondaryAttribute::MaxIterations(_) => "",
+            SecondaryAttribute::MaxRecursionDepth(_) => "",
+            SecondaryAttribute::Derive(_) => "",
+            SecondaryAttribute::InlineAlways => "",
+            SecondaryAttribute::NoInline => "",
+            SecondaryAttribute::Lint(..) => "",
         }
     }
 }
@@ -635,14 +887,18 @@ pub enum Keyword {
     Assert,
     AssertEq,
     Bool,
+    Break,
+    CallData,
     Char,
     CompTime,
     Constrain,
+    Continue,
     Contract,
     Crate,
     Dep,
     Distinct,
     Else,
+    Enum,
     Field,
     Fn,
     For,
@@ -653,11 +909,14 @@ pub enum Keyword {
     In,
     Internal,
     Let,
+    Loop,
+    Match,
     Mod,
     Mut,
     Open,
     Pub,
     Return,
+    ReturnData,
     String,
     Struct,
     Trait,
@@ -675,14 +934,18 @@ impl fmt::Display for Keyword {
             Keyword::Assert => write!(f, "assert"),
             Keyword::AssertEq => write!(f, "assert_eq"),
             Keyword::Bool => write!(f, "bool"),
+            Keyword::Break => write!(f, "break"),
+            Keyword::CallData => write!(f, "call_data"),
             Keyword::Char => write!(f, "char"),
             Keyword::CompTime => write!(f, "comptime"),
             Keyword::Constrain => write!(f, "constrain"),
+            Keyword::Continue => write!(f, "continue"),
             Keyword::Contract => write!(f, "contract"),
             Keyword::Crate => write!(f, "crate"),
             Keyword::Dep => write!(f, "dep"),
             Keyword::Distinct => write!(f, "distinct"),
             Keyword::Else => write!(f, "else"),
+            Keyword::Enum => write!(f, "enum"),
             Keyword::Field => write!(f, "Field"),
             Keyword::Fn => write!(f, "fn"),
             Keyword::For => write!(f, "for"),
@@ -693,11 +956,14 @@ impl fmt::Display for Keyword {
             Keyword::In => write!(f, "in"),
             Keyword::Internal => write!(f, "internal"),
             Keyword::Let => write!(f, "let"),
+            Keyword::Loop => write!(f, "loop"),
+            Keyword::Match => write!(f, "match"),
             Keyword::Mod => write!(f, "mod"),
             Keyword::Mut => write!(f, "mut"),
             Keyword::Open => write!(f, "open"),
             Keyword::Pub => write!(f, "pub"),
             Keyword::Return => write!(f, "return"),
+            Keyword::ReturnData => write!(f, "return_data"),
             Keyword::String => write!(f, "str"),
             Keyword::Struct => write!(f, "struct"),
             Keyword::Trait => write!(f, "trait"),
@@ -718,14 +984,18 @@ impl Keyword {
             "assert" => Keyword::Assert,
             "assert_eq" => Keyword::AssertEq,
             "bool" => Keyword::Bool,
+            "break" => Keyword::Break,
+            "call_data" => Keyword::CallData,
             "char" => Keyword::Char,
             "comptime" => Keyword::CompTime,
             "constrain" => Keyword::Constrain,
+            "continue" => Keyword::Continue,
             "contract" => Keyword::Contract,
             "crate" => Keyword::Crate,
             "dep" => Keyword::Dep,
             "distinct" => Keyword::Distinct,
             "else" => Keyword::Else,
+            "enum" => Keyword::Enum,
             "Field" => Keyword::Field,
             "fn" => Keyword::Fn,
             "for" => Keyword::For,
@@ -736,11 +1006,14 @@ impl Keyword {
             "in" => Keyword::In,
             "internal" => Keyword::Internal,
             "let" => Keyword::Let,
+            "loop" => Keyword::Loop,
+            "match" => Keyword::Match,
             "mod" => Keyword::Mod,
             "mut" => Keyword::Mut,
             "open" => Keyword::Open,
             "pub" => Keyword::Pub,
             "return" => Keyword::Return,
+            "return_data" => Keyword::ReturnData,
             "str" => Keyword::String,
             "struct" => Keyword::Struct,
             "trait" => Keyword::Trait,
@@ -5,6 +5,11 @@
 //!
 //! After the monomorphized ast is created, it is passed to the noirc_evaluator crate to convert it to SSA form,
 //! perform optimizations, convert to ACIR and eventually prove/verify the program.
+//!
+//! Tools which only work with Noir syntax, such as formatters and linters, can use the first two
+//! passes on their own: [`parse_program_with_comments`] returns the Ast of a file, with the span
+//! of each node, along with the comments the Ast leaves out, and [`ast::visitor::Visitor`] walks
+//! the Ast.
 #![forbid(unsafe_code)]
 #![warn(unused_crate_dependencies, unused_extern_crates)]
 #![warn(unreachable_pub)]
@@ -13,6 +18,7 @@
 pub mod ast;
 pub mod graph;
 pub mod lexer;
+pub mod lints;
 pub mod monomorphization;
 pub mod node_interner;
 pub mod parser;
@@ -24,9 +30,12 @@ pub mod hir_def;
 pub use lexer::token;
 
 // Parser API
-pub use parser::{parse_program, ParsedModule};
+pub use parser::{
+    comments, parse_program, parse_program_with_comments, Comment, CommentKind, Item, ItemKind,
+    ParsedModule, ParsedSubModule,
+};
 
-// AST API
+// AST API, with `ast::visitor` for traversing it
 pub use ast::*;
 
 // Type API
@@ -0,0 +1,175 @@
+//! Lints are the named warnings of the compiler. The level each lint is reported at can be set
+//! with `#[allow(..)]`, `#[warn(..)]` and `#[deny(..)]` attributes on functions, modules and `mod`
+//! declarations, or for a whole package in the `[lints]` table of its Nargo.toml.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use fm::FileId;
+use noirc_errors::{CustomDiagnostic, DiagnosticKind, Span};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lint {
+    UnusedVariables,
+    UnusedResults,
+    UnreachableCode,
+    UnreachablePatterns,
+    UnnecessaryPub,
+    Deprecated,
+    UnneededTraitConstraints,
+    UnusedImports,
+    UnusedFunctions,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 9] = [
+        Lint::UnusedVariables,
+        Lint::UnusedResults,
+        Lint::UnreachableCode,
+        Lint::UnreachablePatterns,
+        Lint::UnnecessaryPub,
+        Lint::Deprecated,
+        Lint::UnneededTraitConstraints,
+        Lint::UnusedImports,
+        Lint::UnusedFunctions,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::UnusedVariables => "unused_variables",
+            Lint::UnusedResults => "unused_results",
+            Lint::UnreachableCode => "unreachable_code",
+            Lint::UnreachablePatterns => "unreachable_patterns",
+            Lint::UnnecessaryPub => "unnecessary_pub",
+            Lint::Deprecated => "deprecated",
+            Lint::UnneededTraitConstraints => "unneeded_trait_constraints",
+            Lint::UnusedImports => "unused_imports",
+            Lint::UnusedFunctions => "unused_functions",
+        }
+    }
+
+    pub fn lookup(name: &str) -> Option<Lint> {
+        Lint::ALL.into_iter().find(|lint| lint.name() == name)
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Lint::lookup(name).ok_or_else(|| {
+            let names: Vec<_> = Lint::ALL.into_iter().map(Lint::name).collect();
+            format!("unknown lint `{name}`, expected one of: {}", names.join(", "))
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum LintLevel {
+    /// The lint is not reported
+    Allow,
+    /// The lint is reported as a warning
+    #[default]
+    Warn,
+    /// The lint is reported as an error
+    Deny,
+}
+
+impl LintLevel {
+    pub fn lookup(name: &str) -> Option<LintLevel> {
+        match name {
+            "allow" => Some(LintLevel::Allow),
+            "warn" => Some(LintLevel::Warn),
+            "deny" => Some(LintLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+impl Display for LintLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintLevel::Allow => write!(f, "allow"),
+            LintLevel::Warn => write!(f, "warn"),
+            LintLevel::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// The levels lints are reported at throughout the files of a program.
+#[derive(Debug, Default)]
+pub struct LintLevels {
+    /// Levels applying to a whole file. These are inherited from the crate for a crate's root
+    /// file, or from the `mod` declaration of any other file.
+    files: HashMap<FileId, BTreeMap<Lint, LintLevel>>,
+
+    /// Levels set by attributes on functions and inline modules, applying to the item's span.
+    items: HashMap<FileId, Vec<(Span, Lint, LintLevel)>>,
+}
+
+impl LintLevels {
+    pub fn set_file_levels(&mut self, file: FileId, levels: BTreeMap<Lint, LintLevel>) {
+        self.files.insert(file, levels);
+    }
+
+    pub fn add_item_level(&mut self, file: FileId, span: Span, lint: Lint, level: LintLevel) {
+        self.items.entry(file).or_default().push((span, lint, level));
+    }
+
+    /// Returns the level of `lint` for the code at `span`, which is set by the innermost
+    /// item containing it, then by the file itself.
+    pub fn level(&self, lint: Lint, file: FileId, span: Span) -> LintLevel {
+        let contains = |item: &Span| item.start() <= span.start() && span.end() <= item.end();
+
+        let items = self.items.get(&file).into_iter().flatten();
+        let innermost = items
+            .filter(|(item_span, item_lint, _)| *item_lint == lint && contains(item_span))
+            .min_by_key(|(item_span, ..)| item_span.end() - item_span.start());
+
+        match innermost {
+            Some((_, _, level)) => *level,
+            None => self.file_level(lint, file),
+        }
+    }
+
+    fn file_level(&self, lint: Lint, file: FileId) -> LintLevel {
+        self.files.get(&file).and_then(|levels| levels.get(&lint).copied()).unwrap_or_default()
+    }
+
+    /// Returns the level of every lint at `span`, which a module declared there inherits.
+    pub fn levels_at(&self, file: FileId, span: Span) -> BTreeMap<Lint, LintLevel> {
+        Lint::ALL.into_iter().map(|lint| (lint, self.level(lint, file, span))).collect()
+    }
+
+    /// Reports `diagnostic` for `lint` at the level set where it occurred, returning `None`
+    /// if the lint is allowed there.
+    pub fn apply(
+        &self,
+        lint: Lint,
+        mut diagnostic: CustomDiagnostic,
+        file: FileId,
+    ) -> Option<CustomDiagnostic> {
+        let level = match diagnostic.secondaries.first() {
+            Some(label) => self.level(lint, file, label.span),
+            None => self.file_level(lint, file),
+        };
+
+        match level {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(diagnostic),
+            LintLevel::Deny => {
+                diagnostic.kind = DiagnosticKind::Error;
+                diagnostic.add_note(format!("the `{lint}` lint is denied"));
+                Some(diagnostic)
+            }
+        }
+    }
+}
@@ -2,7 +2,10 @@ use acvm::FieldElement;
 use iter_extended::vecmap;
 use noirc_errors::Location;
 
-use crate::{hir_def::function::FunctionSignature, BinaryOpKind, Distinctness, Signedness};
+use crate::{
+    hir_def::function::FunctionSignature, node_interner, token::InlineType, BinaryOpKind,
+    Distinctness, Signedness,
+};
 
 /// The monomorphized AST is expression-based, all statements are also
 /// folded into this expression enum. Compared to the HIR, the monomorphized
@@ -24,6 +27,11 @@ pub enum Expression {
     Index(Index),
     Cast(Cast),
     For(For),
+    While(While),
+    Loop(Box<Expression>),
+    Break,
+    Continue,
+    Return(Box<Expression>),
     If(If),
     Tuple(Vec<Expression>),
     ExtractTupleField(Box<Expression>, usize),
@@ -78,6 +86,15 @@ pub struct For {
     pub end_range_location: Location,
 }
 
+/// A while loop which is not unrolled. These only occur in unconstrained functions,
+/// constrained while loops are lowered to bounded `For` loops during monomorphization.
+/// The same is true of `Loop`, `Break`, `Continue` and `Return`.
+#[derive(Debug, Clone, Hash)]
+pub struct While {
+    pub condition: Box<Expression>,
+    pub body: Box<Expression>,
+}
+
 #[derive(Debug, Clone, Hash)]
 pub enum Literal {
     Array(ArrayLiteral),
@@ -202,6 +219,7 @@ pub struct Function {
 
     pub return_type: Type,
     pub unconstrained: bool,
+    pub inline_type: InlineType,
 }
 
 /// Compared to hir_def::types::Type, this monomorphized Type has:
@@ -243,6 +261,9 @@ pub struct Program {
     /// forwarding to the next phase.
     pub return_distinctness: Distinctness,
     pub return_location: Option<Location>,
+    /// The `#[oracle]` functions called by the program, in the order they were first reached.
+    /// Their signatures are embedded in the program's ABI.
+    pub oracles: Vec<node_interner::FuncId>,
 }
 
 impl Program {
@@ -251,8 +272,15 @@ impl Program {
         main_function_signature: FunctionSignature,
         return_distinctness: Distinctness,
         return_location: Option<Location>,
+        oracles: Vec<node_interner::FuncId>,
     ) -> Program {
-        Program { functions, main_function_signature, return_distinctness, return_location }
+        Program {
+            functions,
+            main_function_signature,
+            return_distinctness,
+            return_location,
+            oracles,
+        }
     }
 
     pub fn main(&self) -> &Function {
@@ -267,6 +295,14 @@ impl Program {
         FuncId(0)
     }
 
+    /// Marks every function in the program as unconstrained so that
+    /// the whole program is compiled to Brillig.
+    pub fn force_brillig(&mut self) {
+        for function in &mut self.functions {
+            function.unconstrained = true;
+        }
+    }
+
     pub fn take_main_body(&mut self) -> Expression {
         self.take_function_body(FuncId(0))
     }
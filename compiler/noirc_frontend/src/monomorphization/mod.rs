@@ -21,13 +21,16 @@ use crate::{
     hir_def::{
         expr::*,
         function::{FuncMeta, FunctionSignature, Parameters},
-        stmt::{HirAssignStatement, HirLValue, HirLetStatement, HirPattern, HirStatement},
+        stmt::{
+            HirAssignStatement, HirForStatement, HirLValue, HirLetStatement, HirLoopStatement,
+            HirPattern, HirStatement, HirWhileStatement,
+        },
         types,
     },
     node_interner::{self, DefinitionKind, NodeInterner, StmtId, TraitImplKind, TraitMethodId},
-    token::FunctionAttribute,
-    ContractFunctionType, FunctionKind, Type, TypeBinding, TypeBindings, TypeVariableKind,
-    Visibility,
+    token::{FunctionAttribute, InlineType},
+    BinaryOpKind, ContractFunctionType, FunctionKind, Signedness, Type, TypeBinding, TypeBindings,
+    TypeVariableKind, UnaryOp, Visibility,
 };
 
 use self::ast::{Definition, FuncId, Function, LocalId, Program};
@@ -75,11 +78,72 @@ struct Monomorphizer<'interner> {
 
     is_range_loop: bool,
 
+    /// True while monomorphizing the body of a function which will be compiled to Brillig.
+    /// While loops are only unrolled outside of these functions.
+    in_unconstrained_function: bool,
+
+    /// The loops enclosing the current expression which will be unrolled.
+    loops: Vec<UnrolledLoop>,
+
+    /// The flags used to lower `return` within the current function, if it is constrained.
+    returns: Option<EarlyReturns>,
+
     return_location: Option<Location>,
+
+    /// The oracle functions referenced by the program, in the order they were first reached.
+    oracles: Vec<node_interner::FuncId>,
+
+    /// The function currently being monomorphized, used to detect recursive calls.
+    current_function: Option<(node_interner::FuncId, FuncId)>,
+
+    /// Copies of constrained functions with a `#[max_recursion_depth(N)]` attribute, keyed by
+    /// the function, its type, and the number of recursive calls leading to the copy.
+    recursive_copies: HashMap<(node_interner::FuncId, HirType, u64), FuncId>,
+
+    /// The recursion depth of each function in `recursive_copies`.
+    recursion_depths: HashMap<FuncId, u64>,
 }
 
 type HirType = crate::Type;
 
+/// The flags used to lower `break` and `continue` within a loop that will be unrolled,
+/// along with how many of each, and of `return`, were found in the loop.
+struct UnrolledLoop {
+    broke: LocalId,
+    continued: LocalId,
+    location: Location,
+    breaks: usize,
+    continues: usize,
+    returns: usize,
+}
+
+/// The locals used to lower `return` within a constrained function, which cannot simply
+/// jump out of the function since its control flow is flattened.
+struct EarlyReturns {
+    returned: LocalId,
+    return_value: LocalId,
+    typ: ast::Type,
+    location: Location,
+    count: usize,
+}
+
+impl EarlyReturns {
+    fn return_value(&self) -> ast::Ident {
+        ast::Ident {
+            location: None,
+            definition: Definition::Local(self.return_value),
+            mutable: true,
+            name: "$return_value".into(),
+            typ: self.typ.clone(),
+        }
+    }
+}
+
+enum LoopExit {
+    Break,
+    Continue,
+}
+
 /// Starting from the given `main` function, monomorphize the entire program,
 /// replacing all references to type variables and NamedGenerics with concrete
 /// types, duplicating definitions as necessary to do so.
@@ -106,7 +170,13 @@ pub fn monomorphize(main: node_interner::FuncId, interner: &NodeInterner) -> Pro
 
     let functions = vecmap(monomorphizer.finished_functions, |(_, f)| f);
     let FuncMeta { return_distinctness, .. } = interner.function_meta(&main);
-    Program::new(functions, function_sig, return_distinctness, monomorphizer.return_location)
+    Program::new(
+        functions,
+        function_sig,
+        return_distinctness,
+        monomorphizer.return_location,
+        monomorphizer.oracles,
+    )
 }
 
 impl<'interner> Monomorphizer<'interner> {
@@ -121,7 +191,14 @@ impl<'interner> Monomorphizer<'interner> {
             interner,
             lambda_envs_stack: Vec::new(),
             is_range_loop: false,
+            in_unconstrained_function: false,
+            loops: Vec::new(),
+            returns: None,
             return_location: None,
+            oracles: Vec::new(),
+            current_function: None,
+            recursive_copies: HashMap::new(),
+            recursion_depths: HashMap::new(),
         }
     }
 
@@ -148,6 +225,10 @@ impl<'interner> Monomorphizer<'interner> {
         typ: &HirType,
     ) -> Definition {
         let typ = typ.follow_bindings();
+        if let Some(id) = self.lookup_recursive_copy(id, expr_id, &typ) {
+            return Definition::Function(id);
+        }
+
         match self.globals.get(&id).and_then(|inner_map| inner_map.get(&typ)) {
             Some(id) => Definition::Function(*id),
             None => {
@@ -173,6 +254,10 @@ impl<'interner> Monomorphizer<'interner> {
                         Definition::Function(id)
                     }
                     FunctionKind::Oracle => {
+                        if !self.oracles.contains(&id) {
+                            self.oracles.push(id);
+                        }
+
                         let attr = attributes
                             .function
                             .clone()
@@ -188,6 +273,36 @@ impl<'interner> Monomorphizer<'interner> {
         }
     }
 
+    /// Constrained functions cannot recur at runtime, so a recursive call within a function
+    /// with a `#[max_recursion_depth(N)]` attribute refers to a fresh copy of the function one
+    /// level deeper instead of to the function itself. Inlining these copies terminates since
+    /// the copy past the maximum depth fails an assertion rather than recurring again.
+    fn lookup_recursive_copy(
+        &mut self,
+        id: node_interner::FuncId,
+        expr_id: node_interner::ExprId,
+        typ: &HirType,
+    ) -> Option<FuncId> {
+        let (current_function, current_id) = self.current_function?;
+        if current_function != id || self.interner.function_modifiers(&id).is_unconstrained {
+            return None;
+        }
+        self.interner.function_attributes(&id).max_recursion_depth()?;
+
+        let depth = self.recursion_depths.get(&current_id).map_or(1, |depth| depth + 1);
+        let key = (id, typ.clone(), depth);
+        if let Some(copy) = self.recursive_copies.get(&key) {
+            return Some(*copy);
+        }
+
+        let new_id = self.next_function_id();
+        let bindings = self.follow_bindings(self.interner.get_instantiation_bindings(expr_id));
+        self.queue.push_back((id, new_id, bindings));
+        self.recursive_copies.insert(key, new_id);
+        self.recursion_depths.insert(new_id, depth);
+        Some(new_id)
+    }
+
     fn define_local(&mut self, id: node_interner::DefinitionId, new_id: LocalId) {
         self.locals.insert(id, new_id);
     }
@@ -229,15 +344,126 @@ impl<'interner> Monomorphizer<'interner> {
             _ => meta.return_type(),
         });
 
-        let parameters = self.parameters(meta.parameters);
-        let body = self.expr(body_expr_id);
         let unconstrained = modifiers.is_unconstrained
             || matches!(modifiers.contract_function_type, Some(ContractFunctionType::Open));
 
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let attributes = self.interner.function_attributes(&f);
+        let max_recursion_depth = attributes.max_recursion_depth();
+        let inline_type = attributes.inline_type();
+        let depth = self.recursion_depths.get(&id).copied().unwrap_or(0);
+
+        let main_parameters = (id == Program::main_id()).then(|| meta.parameters.clone());
+        let parameters = self.parameters(meta.parameters);
+        self.current_function = Some((f, id));
+        self.in_unconstrained_function = unconstrained;
+        let body = match max_recursion_depth {
+            Some(max_depth) if depth > max_depth => {
+                self.recursion_limit_body(max_depth, &return_type, meta.location)
+            }
+            _ => self.function_body(body_expr_id, &return_type),
+        };
+        let body = match main_parameters {
+            Some(main_parameters) => self.constrain_enum_tags(main_parameters, body),
+            None => body,
+        };
+        self.in_unconstrained_function = false;
+        self.current_function = None;
+
+        let function =
+            ast::Function { id, name, parameters, body, return_type, unconstrained, inline_type };
         self.push_function(id, function);
     }
 
+    /// The body of the copy of a recursive function past its maximum recursion depth:
+    /// ```nr
+    /// assert(false, "...");
+    /// zeroed()
+    /// ```
+    /// Since it is only reached through a chain of recursive calls, the assertion only fails
+    /// if the function actually recurs more than `max_depth` times.
+    fn recursion_limit_body(
+        &mut self,
+        max_depth: u64,
+        return_type: &ast::Type,
+        location: Location,
+    ) -> ast::Expression {
+        let message = format!("function exceeded its maximum recursion depth of {max_depth}");
+        let fail = Box::new(ast::Expression::Literal(ast::Literal::Bool(false)));
+        let constrain = ast::Expression::Constrain(fail, location, Some(message));
+        let zeroed = self.zeroed_value_of_type(return_type, location);
+        ast::Expression::Block(vec![constrain, zeroed])
+    }
+
+    /// Monomorphizes the body of a function or lambda. Unconstrained code keeps any `return`
+    /// within it as is, elsewhere they are lowered to setting a `$returned` flag which guards
+    /// the remainder of the function:
+    /// ```nr
+    /// let mut $returned = false;
+    /// let mut $return_value = zeroed();
+    /// let $body = body;
+    /// if $returned { $return_value } else { $body }
+    /// ```
+    fn function_body(
+        &mut self,
+        body: node_interner::ExprId,
+        return_type: &ast::Type,
+    ) -> ast::Expression {
+        let location = self.interner.expr_location(&body);
+        let returns = (!self.in_unconstrained_function).then(|| EarlyReturns {
+            returned: self.next_local_id(),
+            return_value: self.next_local_id(),
+            typ: return_type.clone(),
+            location,
+            count: 0,
+        });
+
+        let enclosing_loops = std::mem::take(&mut self.loops);
+        let enclosing_returns = std::mem::replace(&mut self.returns, returns);
+        let body = self.expr(body);
+        let returns = std::mem::replace(&mut self.returns, enclosing_returns);
+        self.loops = enclosing_loops;
+
+        let returns = match returns {
+            Some(returns) if returns.count > 0 => returns,
+            _ => return body,
+        };
+
+        let mut statements = vec![declare_flag(returns.returned, "$returned")];
+        if returns.typ == ast::Type::Unit {
+            statements.push(body);
+            return ast::Expression::Block(statements);
+        }
+
+        let body_id = self.next_local_id();
+        let body_ident = ast::Ident {
+            location: None,
+            definition: Definition::Local(body_id),
+            mutable: false,
+            name: "$body".into(),
+            typ: returns.typ.clone(),
+        };
+
+        statements.push(ast::Expression::Let(ast::Let {
+            id: returns.return_value,
+            mutable: true,
+            name: "$return_value".into(),
+            expression: Box::new(self.zeroed_value_of_type(&returns.typ, location)),
+        }));
+        statements.push(ast::Expression::Let(ast::Let {
+            id: body_id,
+            mutable: false,
+            name: "$body".into(),
+            expression: Box::new(body),
+        }));
+        statements.push(ast::Expression::If(ast::If {
+            condition: Box::new(flag(returns.returned, "$returned")),
+            consequence: Box::new(ast::Expression::Ident(returns.return_value())),
+            alternative: Some(Box::new(ast::Expression::Ident(body_ident))),
+            typ: returns.typ,
+        }));
+        ast::Expression::Block(statements)
+    }
+
     fn push_function(&mut self, id: FuncId, function: ast::Function) {
         let existing = self.finished_functions.insert(id, function);
         assert!(existing.is_none());
@@ -253,6 +479,147 @@ impl<'interner> Monomorphizer<'interner> {
         new_params
     }
 
+    /// The inputs to `main` aren't built by an enum constructor, so the tag of any enum within
+    /// them may hold a value without a variant. Since a match treats its last arm as the else
+    /// branch, such a value would be matched by that arm. This prefixes `body` with constraints
+    /// that each of these tags is one of its enum's variants.
+    fn constrain_enum_tags(
+        &mut self,
+        parameters: Parameters,
+        body: ast::Expression,
+    ) -> ast::Expression {
+        let mut constraints = Vec::new();
+        for (pattern, typ, _) in parameters {
+            self.parameter_enum_tag_constraints(pattern, &typ, &mut constraints);
+        }
+
+        if constraints.is_empty() {
+            return body;
+        }
+        constraints.push(body);
+        ast::Expression::Block(constraints)
+    }
+
+    fn parameter_enum_tag_constraints(
+        &mut self,
+        param: HirPattern,
+        typ: &HirType,
+        constraints: &mut Vec<ast::Expression>,
+    ) {
+        match param {
+            HirPattern::Identifier(ident) => {
+                let value = self.local_ident(&ident).expect("Parameters should be defined");
+                let value = ast::Expression::Ident(value);
+                self.enum_tag_constraints(value, typ, ident.location, constraints);
+            }
+            HirPattern::Mutable(pattern, _) => {
+                self.parameter_enum_tag_constraints(*pattern, typ, constraints);
+            }
+            HirPattern::Tuple(fields, _) => {
+                for (field, typ) in fields.into_iter().zip(unwrap_tuple_type(typ)) {
+                    self.parameter_enum_tag_constraints(field, &typ, constraints);
+                }
+            }
+            HirPattern::Struct(_, fields, _) => {
+                let mut fields = btree_map(fields, |(name, field)| (name.0.contents, field));
+                for (field_name, field_type) in unwrap_struct_type(typ) {
+                    if let Some(field) = fields.remove(&field_name) {
+                        self.parameter_enum_tag_constraints(field, &field_type, constraints);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pushes a constraint for the tag of every enum within `value`, including those nested
+    /// in the payloads of other enums. A tag is valid if it is a root of
+    /// `(tag - 0) * (tag - 1) * ... * (tag - (variants - 1))`.
+    fn enum_tag_constraints(
+        &mut self,
+        value: ast::Expression,
+        typ: &HirType,
+        location: Location,
+        constraints: &mut Vec<ast::Expression>,
+    ) {
+        let constant = |value: u128| {
+            ast::Expression::Literal(ast::Literal::Integer(
+                value.into(),
+                ast::Type::Field,
+                location,
+            ))
+        };
+        let binary = |lhs, operator, rhs| {
+            ast::Expression::Binary(ast::Binary {
+                lhs: Box::new(lhs),
+                operator,
+                rhs: Box::new(rhs),
+                location,
+            })
+        };
+
+        match typ.follow_bindings() {
+            HirType::Struct(def, args) if def.borrow().is_enum() => {
+                let variants = def.borrow().get_variants(&args);
+                let tag = ast::Expression::ExtractTupleField(Box::new(value.clone()), 0);
+                let product = (0..variants.len() as u128)
+                    .map(|variant| binary(tag.clone(), BinaryOpKind::Subtract, constant(variant)))
+                    .reduce(|product, factor| binary(product, BinaryOpKind::Multiply, factor));
+
+                // Enums without variants can't be constructed, so there is no tag to check
+                if let Some(product) = product {
+                    let condition = binary(product, BinaryOpKind::Equal, constant(0));
+                    let message = format!("Invalid variant for enum {}", def.borrow().name);
+                    let constrain =
+                        ast::Expression::Constrain(Box::new(condition), location, Some(message));
+                    constraints.push(constrain);
+                }
+
+                for (variant, (_, fields)) in variants.into_iter().enumerate() {
+                    let payload =
+                        ast::Expression::ExtractTupleField(Box::new(value.clone()), variant + 1);
+                    for (index, field_type) in fields.iter().enumerate() {
+                        let field =
+                            ast::Expression::ExtractTupleField(Box::new(payload.clone()), index);
+                        self.enum_tag_constraints(field, field_type, location, constraints);
+                    }
+                }
+            }
+            HirType::Struct(def, args) => {
+                let fields = def.borrow().get_fields(&args);
+                for (index, (_, field_type)) in fields.iter().enumerate() {
+                    let field = ast::Expression::ExtractTupleField(Box::new(value.clone()), index);
+                    self.enum_tag_constraints(field, field_type, location, constraints);
+                }
+            }
+            HirType::Tuple(fields) => {
+                for (index, field_type) in fields.iter().enumerate() {
+                    let field = ast::Expression::ExtractTupleField(Box::new(value.clone()), index);
+                    self.enum_tag_constraints(field, field_type, location, constraints);
+                }
+            }
+            HirType::Array(length, element_type) => {
+                let length = length.evaluate_to_u64().unwrap_or(0);
+                let converted_element_type = self.convert_type(&element_type);
+                for index in 0..length {
+                    let element = ast::Expression::Index(ast::Index {
+                        collection: Box::new(value.clone()),
+                        index: Box::new(constant(index as u128)),
+                        element_type: converted_element_type.clone(),
+                        location,
+                    });
+                    let previous_constraints = constraints.len();
+                    self.enum_tag_constraints(element, &element_type, location, constraints);
+
+                    // Every element has the same type, so if the first has no enums neither do the rest
+                    if constraints.len() == previous_constraints {
+                        break;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
     fn parameter(
         &mut self,
         param: HirPattern,
@@ -322,7 +689,10 @@ impl<'interner> Monomorphizer<'interner> {
                 }
             },
             HirExpression::Literal(HirLiteral::Unit) => ast::Expression::Block(vec![]),
-            HirExpression::Block(block) => self.block(block.0),
+            HirExpression::Block(block) => {
+                let typ = self.convert_type(&self.interner.id_type(expr));
+                self.block(block.0, typ)
+            }
 
             HirExpression::Prefix(prefix) => {
                 let location = self.interner.expr_location(&expr);
@@ -335,6 +705,12 @@ impl<'interner> Monomorphizer<'interner> {
             }
 
             HirExpression::Infix(infix) => {
+                if self.interner.get_selected_impl_for_ident(expr).is_some() {
+                    return self.overloaded_operator(infix, expr);
+                }
+                if let Type::String(_) = self.interner.id_type(expr).follow_bindings() {
+                    return self.string_concatenation(infix, expr);
+                }
                 let lhs = Box::new(self.expr(infix.lhs));
                 let rhs = Box::new(self.expr(infix.rhs));
                 let operator = infix.operator.kind;
@@ -357,6 +733,7 @@ impl<'interner> Monomorphizer<'interner> {
                 r#type: self.convert_type(&cast.r#type),
                 location: self.interner.expr_location(&expr),
             }),
+            HirExpression::Try(try_expr) => self.try_expr(try_expr, expr),
 
             HirExpression::If(if_expr) => {
                 let cond = self.expr(if_expr.condition);
@@ -375,6 +752,8 @@ impl<'interner> Monomorphizer<'interner> {
                 ast::Expression::Tuple(fields)
             }
             HirExpression::Constructor(constructor) => self.constructor(constructor, expr),
+            HirExpression::EnumConstructor(constructor) => self.enum_constructor(constructor, expr),
+            HirExpression::Match(match_expr) => self.match_expr(match_expr, expr),
 
             HirExpression::Lambda(lambda) => self.lambda(lambda, expr),
 
@@ -391,6 +770,9 @@ impl<'interner> Monomorphizer<'interner> {
             HirExpression::MethodCall(hir_method_call) => {
                 unreachable!("Encountered HirExpression::MethodCall during monomorphization {hir_method_call:?}")
             }
+            HirExpression::Comptime(_) => {
+                unreachable!("comptime expressions are evaluated before monomorphization")
+            }
             HirExpression::Error => unreachable!("Encountered Error node during monomorphization"),
         }
     }
@@ -430,16 +812,77 @@ impl<'interner> Monomorphizer<'interner> {
         ast::Expression::Index(ast::Index { collection, index, element_type, location })
     }
 
+    /// Concatenates two strings which are not known at compile-time, by copying the bytes
+    /// of both into a new array. Compile-time strings are already concatenated during type
+    /// checking, so this is only reachable from unconstrained code.
+    fn string_concatenation(
+        &mut self,
+        infix: HirInfixExpression,
+        id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let location = self.interner.expr_location(&id);
+        let mut statements = Vec::new();
+        let mut bytes = Vec::new();
+
+        for operand in [infix.lhs, infix.rhs] {
+            let typ = self.convert_type(&self.interner.id_type(operand));
+            let ast::Type::String(length) = typ else {
+                unreachable!("Expected a string operand, found {typ}")
+            };
+            let value = self.expr(operand);
+            let (let_operand, operand) = self.hoist_expression("operand", false, typ, value);
+            statements.push(let_operand);
+
+            bytes.extend((0..length).map(|index| {
+                let index =
+                    ast::Literal::Integer((index as u128).into(), ast::Type::Field, location);
+                ast::Expression::Index(ast::Index {
+                    collection: Box::new(ast::Expression::Ident(operand.clone())),
+                    index: Box::new(ast::Expression::Literal(index)),
+                    element_type: ast::Type::Integer(Signedness::Unsigned, 8),
+                    location,
+                })
+            }));
+        }
+
+        // Strings are represented as arrays of bytes once compiled
+        let byte = Box::new(ast::Type::Integer(Signedness::Unsigned, 8));
+        let typ = ast::Type::Array(bytes.len() as u64, byte);
+        let array = ast::ArrayLiteral { contents: bytes, typ };
+        statements.push(ast::Expression::Literal(ast::Literal::Array(array)));
+        ast::Expression::Block(statements)
+    }
+
     fn statement(&mut self, id: StmtId) -> ast::Expression {
         match self.interner.statement(&id) {
             HirStatement::Let(let_statement) => self.let_statement(let_statement),
             HirStatement::Constrain(constrain) => {
                 let expr = self.expr(constrain.0);
                 let location = self.interner.expr_location(&constrain.0);
-                ast::Expression::Constrain(Box::new(expr), location, constrain.2)
+                let constrain_expr =
+                    ast::Expression::Constrain(Box::new(expr), location, constrain.2);
+
+                // The formatted assertion message must be resolved before the constraint fails
+                match constrain.3 {
+                    Some(resolve_message_call) => {
+                        let call = self.expr(resolve_message_call);
+                        ast::Expression::Block(vec![
+                            ast::Expression::Semi(Box::new(call)),
+                            constrain_expr,
+                        ])
+                    }
+                    None => constrain_expr,
+                }
             }
             HirStatement::Assign(assign) => self.assign(assign),
             HirStatement::For(for_loop) => {
+                match for_loop.max_iterations {
+                    Some(max_iterations) if !self.in_unconstrained_function => {
+                        return self.bounded_for_loop(for_loop, max_iterations);
+                    }
+                    _ => (),
+                }
+
                 self.is_range_loop = true;
                 let start = self.expr(for_loop.start_range);
                 let end = self.expr(for_loop.end_range);
@@ -447,25 +890,450 @@ impl<'interner> Monomorphizer<'interner> {
                 let index_variable = self.next_local_id();
                 self.define_local(for_loop.identifier.id, index_variable);
 
-                let block = Box::new(self.expr(for_loop.block));
+                let start_range_location = self.interner.expr_location(&for_loop.start_range);
+                self.push_unrolled_loop(start_range_location);
+                let block = self.expr(for_loop.block);
+                let (mut statements, block, _) = self.pop_unrolled_loop(block);
 
-                ast::Expression::For(ast::For {
+                statements.push(ast::Expression::For(ast::For {
                     index_variable,
                     index_name: self.interner.definition_name(for_loop.identifier.id).to_owned(),
                     index_type: self.convert_type(&self.interner.id_type(for_loop.start_range)),
                     start_range: Box::new(start),
                     end_range: Box::new(end),
-                    start_range_location: self.interner.expr_location(&for_loop.start_range),
+                    start_range_location,
                     end_range_location: self.interner.expr_location(&for_loop.end_range),
-                    block,
-                })
+                    block: Box::new(block),
+                }));
+
+                if statements.len() == 1 {
+                    statements.pop().unwrap()
+                } else {
+                    ast::Expression::Block(statements)
+                }
             }
+            HirStatement::While(while_loop) => self.while_loop(while_loop),
+            HirStatement::Loop(loop_statement) => self.loop_statement(loop_statement),
+            HirStatement::Break => self.loop_exit(LoopExit::Break),
+            HirStatement::Continue => self.loop_exit(LoopExit::Continue),
+            HirStatement::Return(expr) => self.return_statement(expr),
             HirStatement::Expression(expr) => self.expr(expr),
             HirStatement::Semi(expr) => ast::Expression::Semi(Box::new(self.expr(expr))),
             HirStatement::Error => unreachable!(),
         }
     }
 
+    /// Unconstrained code keeps while loops as they are. Elsewhere they are lowered to a
+    /// bounded for loop which can be unrolled:
+    /// ```nr
+    /// for _ in 0..max_iterations {
+    ///     if condition { block }
+    /// }
+    /// assert(!condition, "...");
+    /// ```
+    /// The trailing assertion ensures the loop actually terminated within its bound.
+    /// It is skipped if the loop was exited with `break` or `return`.
+    fn while_loop(&mut self, while_loop: HirWhileStatement) -> ast::Expression {
+        let max_iterations = match while_loop.max_iterations {
+            Some(max_iterations) if !self.in_unconstrained_function => max_iterations,
+            _ => {
+                let condition = Box::new(self.expr(while_loop.condition));
+                let body = Box::new(self.expr(while_loop.block));
+                return ast::Expression::While(ast::While { condition, body });
+            }
+        };
+
+        let location = self.interner.expr_location(&while_loop.condition);
+        self.push_unrolled_loop(location);
+        let condition = Box::new(self.expr(while_loop.condition));
+        let consequence = Box::new(self.expr(while_loop.block));
+        let body = ast::Expression::If(ast::If {
+            condition,
+            consequence,
+            alternative: None,
+            typ: ast::Type::Unit,
+        });
+        let (mut statements, body, exited) = self.pop_unrolled_loop(body);
+        let index_variable = self.next_local_id();
+        statements.push(self.unrolled_for_loop(index_variable, max_iterations, body, location));
+
+        // The condition is monomorphized a second time so that any definitions within it are fresh
+        let terminated = ast::Expression::Unary(ast::Unary {
+            operator: UnaryOp::Not,
+            rhs: Box::new(self.expr(while_loop.condition)),
+            result_type: ast::Type::Bool,
+            location,
+        });
+        let message = format!("while loop exceeded its maximum of {max_iterations} iterations");
+        let constrain = ast::Expression::Constrain(Box::new(terminated), location, Some(message));
+
+        statements.push(match exited {
+            Some(exited) => ast::Expression::If(ast::If {
+                condition: Box::new(not(exited, location)),
+                consequence: Box::new(constrain),
+                alternative: None,
+                typ: ast::Type::Unit,
+            }),
+            None => constrain,
+        });
+        ast::Expression::Block(statements)
+    }
+
+    /// As with while loops, `loop` is only kept as is in unconstrained code. Elsewhere it is
+    /// unrolled into a bounded for loop which must be exited through a `break` or `return`:
+    /// ```nr
+    /// for _ in 0..max_iterations { block }
+    /// assert(exited, "...");
+    /// ```
+    fn loop_statement(&mut self, loop_statement: HirLoopStatement) -> ast::Expression {
+        let max_iterations = match loop_statement.max_iterations {
+            Some(max_iterations) if !self.in_unconstrained_function => max_iterations,
+            _ => return ast::Expression::Loop(Box::new(self.expr(loop_statement.block))),
+        };
+
+        let location = self.interner.expr_location(&loop_statement.block);
+        self.push_unrolled_loop(location);
+        let body = self.expr(loop_statement.block);
+        let (mut statements, body, exited) = self.pop_unrolled_loop(body);
+        let index_variable = self.next_local_id();
+        statements.push(self.unrolled_for_loop(index_variable, max_iterations, body, location));
+
+        let exited = exited.unwrap_or(ast::Expression::Literal(ast::Literal::Bool(false)));
+        let message = format!("loop exceeded its maximum of {max_iterations} iterations");
+        statements.push(ast::Expression::Constrain(Box::new(exited), location, Some(message)));
+        ast::Expression::Block(statements)
+    }
+
+    fn unrolled_for_loop(
+        &mut self,
+        index_variable: LocalId,
+        max_iterations: u64,
+        block: ast::Expression,
+        location: Location,
+    ) -> ast::Expression {
+        let index_type = ast::Type::Integer(Signedness::Unsigned, 64);
+        let range_bound = |value: u64| {
+            Box::new(ast::Expression::Literal(ast::Literal::Integer(
+                value.into(),
+                index_type.clone(),
+                location,
+            )))
+        };
+
+        ast::Expression::For(ast::For {
+            index_variable,
+            index_name: "$i".into(),
+            index_type: index_type.clone(),
+            start_range: range_bound(0),
+            end_range: range_bound(max_iterations),
+            block: Box::new(block),
+            start_range_location: location,
+            end_range_location: location,
+        })
+    }
+
+    /// A for loop with a `#[max_iterations(N)]` attribute may have a range which is only known
+    /// at runtime. Outside of unconstrained code it is unrolled up to its maximum instead, with
+    /// each iteration guarded by a running predicate which holds while the index is within the
+    /// range:
+    /// ```nr
+    /// let $start = start;
+    /// let $end = end;
+    /// let mut $active = $start < $end;
+    /// for $i in 0..max_iterations {
+    ///     let i = (($start as Field) + ($i as Field)) as T;
+    ///     $active = $active & (i != $end);
+    ///     if $active { block }
+    /// }
+    /// let $next = (($start as Field) + max_iterations) as T;
+    /// assert(!($active & ($next != $end)), "...");
+    /// ```
+    /// Since the index only increases, the predicate only needs to compare it for equality with
+    /// the end of the range, which is much cheaper in ACIR than a comparison on every iteration.
+    /// The indices are computed in a `Field` and truncated, as those past the end of the range
+    /// would overflow `T` if the range ends near its maximum, or if `max_iterations` exceeds it.
+    /// The end of the range is always reached before the index wraps around, so the truncation
+    /// never changes an index while the predicate holds.
+    /// The trailing assertion fails if the next index would still have been within the range.
+    /// As with while loops, it is skipped if the loop was exited with `break` or `return`.
+    fn bounded_for_loop(
+        &mut self,
+        for_loop: HirForStatement,
+        max_iterations: u64,
+    ) -> ast::Expression {
+        let location = self.interner.expr_location(&for_loop.end_range);
+        let index_type = self.convert_type(&self.interner.id_type(for_loop.start_range));
+        let local = |id, name: &str| {
+            ast::Expression::Ident(ast::Ident {
+                location: None,
+                definition: Definition::Local(id),
+                mutable: false,
+                name: name.to_owned(),
+                typ: index_type.clone(),
+            })
+        };
+        let binary = |lhs, operator, rhs| {
+            ast::Expression::Binary(ast::Binary {
+                lhs: Box::new(lhs),
+                operator,
+                rhs: Box::new(rhs),
+                location,
+            })
+        };
+        let cast =
+            |lhs, r#type| ast::Expression::Cast(ast::Cast { lhs: Box::new(lhs), r#type, location });
+
+        self.is_range_loop = true;
+        let start = self.expr(for_loop.start_range);
+        let end = self.expr(for_loop.end_range);
+        self.is_range_loop = false;
+
+        let start_id = self.next_local_id();
+        let end_id = self.next_local_id();
+        let active = self.next_local_id();
+        let offset_id = self.next_local_id();
+        let index_variable = self.next_local_id();
+        let index_name = self.interner.definition_name(for_loop.identifier.id).to_owned();
+        self.define_local(for_loop.identifier.id, index_variable);
+
+        self.push_unrolled_loop(location);
+        let block = self.expr(for_loop.block);
+
+        let offset = cast(local(offset_id, "$i"), ast::Type::Field);
+        let index =
+            binary(cast(local(start_id, "$start"), ast::Type::Field), BinaryOpKind::Add, offset);
+        let index = ast::Expression::Let(ast::Let {
+            id: index_variable,
+            mutable: false,
+            name: index_name.clone(),
+            expression: Box::new(cast(index, index_type.clone())),
+        });
+        let not_end = binary(
+            local(index_variable, &index_name),
+            BinaryOpKind::NotEqual,
+            local(end_id, "$end"),
+        );
+        let update_active = ast::Expression::Assign(ast::Assign {
+            lvalue: ast::LValue::Ident(flag_ident(active, "$active")),
+            expression: Box::new(binary(flag(active, "$active"), BinaryOpKind::And, not_end)),
+        });
+        let body = ast::Expression::Block(vec![
+            index,
+            update_active,
+            ast::Expression::If(ast::If {
+                condition: Box::new(flag(active, "$active")),
+                consequence: Box::new(block),
+                alternative: None,
+                typ: ast::Type::Unit,
+            }),
+        ]);
+        let (declarations, body, exited) = self.pop_unrolled_loop(body);
+
+        let in_range = binary(local(start_id, "$start"), BinaryOpKind::Less, local(end_id, "$end"));
+        let mut statements = vec![
+            ast::Expression::Let(ast::Let {
+                id: start_id,
+                mutable: false,
+                name: "$start".into(),
+                expression: Box::new(start),
+            }),
+            ast::Expression::Let(ast::Let {
+                id: end_id,
+                mutable: false,
+                name: "$end".into(),
+                expression: Box::new(end),
+            }),
+            ast::Expression::Let(ast::Let {
+                id: active,
+                mutable: true,
+                name: "$active".into(),
+                expression: Box::new(in_range),
+            }),
+        ];
+        statements.extend(declarations);
+        statements.push(self.unrolled_for_loop(offset_id, max_iterations, body, location));
+
+        let max_iterations_literal = ast::Expression::Literal(ast::Literal::Integer(
+            max_iterations.into(),
+            ast::Type::Field,
+            location,
+        ));
+        let next_index = binary(
+            cast(local(start_id, "$start"), ast::Type::Field),
+            BinaryOpKind::Add,
+            max_iterations_literal,
+        );
+        let next_index = cast(next_index, index_type.clone());
+        let not_end = binary(next_index, BinaryOpKind::NotEqual, local(end_id, "$end"));
+        let exceeded = binary(flag(active, "$active"), BinaryOpKind::And, not_end);
+        let message = format!("for loop exceeded its maximum of {max_iterations} iterations");
+        let constrain =
+            ast::Expression::Constrain(Box::new(not(exceeded, location)), location, Some(message));
+
+        statements.push(match exited {
+            Some(exited) => ast::Expression::If(ast::If {
+                condition: Box::new(not(exited, location)),
+                consequence: Box::new(constrain),
+                alternative: None,
+                typ: ast::Type::Unit,
+            }),
+            None => constrain,
+        });
+        ast::Expression::Block(statements)
+    }
+
+    /// Starts monomorphizing the body of a loop. Loops in constrained code are unrolled, so
+    /// `break` and `continue` within them are lowered to setting a flag which guards the
+    /// remainder of the loop.
+    fn push_unrolled_loop(&mut self, location: Location) {
+        if !self.in_unconstrained_function {
+            let broke = self.next_local_id();
+            let continued = self.next_local_id();
+            self.loops.push(UnrolledLoop {
+                broke,
+                continued,
+                location,
+                breaks: 0,
+                continues: 0,
+                returns: 0,
+            });
+        }
+    }
+
+    /// Finishes monomorphizing the body of a loop, returning the declarations of any flags the
+    /// loop needs, the body wrapped to respect those flags, and a condition which holds once
+    /// the loop has been exited through `break` or `return`, if it can be.
+    fn pop_unrolled_loop(
+        &mut self,
+        body: ast::Expression,
+    ) -> (Vec<ast::Expression>, ast::Expression, Option<ast::Expression>) {
+        if self.in_unconstrained_function {
+            return (Vec::new(), body, None);
+        }
+
+        let unrolled_loop = self.loops.pop().expect("ICE: expected to be within a loop");
+        let location = unrolled_loop.location;
+        let mut declarations = Vec::new();
+        let mut body = body;
+
+        if unrolled_loop.continues > 0 {
+            let continued = unrolled_loop.continued;
+            declarations.push(declare_flag(continued, "$continued"));
+            body = ast::Expression::Block(vec![set_flag(continued, "$continued", false), body]);
+        }
+
+        let mut exits = Vec::new();
+        if unrolled_loop.breaks > 0 {
+            declarations.push(declare_flag(unrolled_loop.broke, "$broke"));
+            exits.push(flag(unrolled_loop.broke, "$broke"));
+        }
+        if unrolled_loop.returns > 0 {
+            exits.push(self.returned_flag());
+        }
+
+        let Some(exited) = any(exits, location) else {
+            return (declarations, body, None);
+        };
+
+        body = ast::Expression::If(ast::If {
+            condition: Box::new(not(exited.clone(), location)),
+            consequence: Box::new(body),
+            alternative: None,
+            typ: ast::Type::Unit,
+        });
+        (declarations, body, Some(exited))
+    }
+
+    fn loop_exit(&mut self, exit: LoopExit) -> ast::Expression {
+        let Some(unrolled_loop) = self.loops.last_mut() else {
+            return match exit {
+                LoopExit::Break => ast::Expression::Break,
+                LoopExit::Continue => ast::Expression::Continue,
+            };
+        };
+
+        match exit {
+            LoopExit::Break => {
+                unrolled_loop.breaks += 1;
+                set_flag(unrolled_loop.broke, "$broke", true)
+            }
+            LoopExit::Continue => {
+                unrolled_loop.continues += 1;
+                set_flag(unrolled_loop.continued, "$continued", true)
+            }
+        }
+    }
+
+    /// Lowers `return` in constrained code to storing the returned value and setting the
+    /// `$returned` flag, which guards the remainder of the function and any enclosing loops.
+    fn return_statement(&mut self, expr: node_interner::ExprId) -> ast::Expression {
+        let value = self.expr(expr);
+        self.return_value(value)
+    }
+
+    fn return_value(&mut self, value: ast::Expression) -> ast::Expression {
+        let Some(returns) = self.returns.as_mut() else {
+            return ast::Expression::Return(Box::new(value));
+        };
+
+        returns.count += 1;
+        for unrolled_loop in &mut self.loops {
+            unrolled_loop.returns += 1;
+        }
+
+        let store_value = if returns.typ == ast::Type::Unit {
+            ast::Expression::Semi(Box::new(value))
+        } else {
+            ast::Expression::Assign(ast::Assign {
+                lvalue: ast::LValue::Ident(returns.return_value()),
+                expression: Box::new(value),
+            })
+        };
+        ast::Expression::Block(vec![store_value, set_flag(returns.returned, "$returned", true)])
+    }
+
+    fn returned_flag(&self) -> ast::Expression {
+        let returns =
+            self.returns.as_ref().expect("ICE: expected to be within a constrained function");
+        flag(returns.returned, "$returned")
+    }
+
+    /// The number of `break`, `continue` and `return` statements lowered so far which may
+    /// skip the remainder of the current block.
+    fn exits(&self) -> usize {
+        let loop_exits = self
+            .loops
+            .last()
+            .map_or(0, |unrolled_loop| unrolled_loop.breaks + unrolled_loop.continues);
+        let returns = self.returns.as_ref().map_or(0, |returns| returns.count);
+        loop_exits + returns
+    }
+
+    /// Returns a condition which holds if neither the innermost unrolled loop has been exited
+    /// from in the current iteration nor the current function has returned.
+    fn not_exited(&self) -> ast::Expression {
+        let mut exits = Vec::new();
+        let mut location = None;
+
+        if let Some(unrolled_loop) = self.loops.last() {
+            location = Some(unrolled_loop.location);
+            if unrolled_loop.breaks > 0 {
+                exits.push(flag(unrolled_loop.broke, "$broke"));
+            }
+            if unrolled_loop.continues > 0 {
+                exits.push(flag(unrolled_loop.continued, "$continued"));
+            }
+        }
+        if let Some(returns) = self.returns.as_ref().filter(|returns| returns.count > 0) {
+            location = location.or(Some(returns.location));
+            exits.push(flag(returns.returned, "$returned"));
+        }
+
+        let location = location.expect("ICE: expected to be within a loop or function");
+        let exited = any(exits, location).expect("ICE: expected a loop or function to be exited");
+        not(exited, location)
+    }
+
     fn let_statement(&mut self, let_statement: HirLetStatement) -> ast::Expression {
         let expr = self.expr(let_statement.expression);
         let expected_type = self.interner.id_type(let_statement.expression);
@@ -521,8 +1389,254 @@ impl<'interner> Monomorphizer<'interner> {
         ast::Expression::Block(new_exprs)
     }
 
-    fn block(&mut self, statement_ids: Vec<StmtId>) -> ast::Expression {
-        ast::Expression::Block(vecmap(statement_ids, |id| self.statement(id)))
+    /// Enums are represented as a tuple of a Field tag holding the index of the active variant,
+    /// followed by a tuple with the payload of each variant. The payloads of the inactive variants
+    /// are zeroed.
+    fn enum_constructor(
+        &mut self,
+        constructor: HirEnumConstructorExpression,
+        id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let location = constructor.location;
+        let field_types = match self.convert_type(&self.interner.id_type(id)) {
+            ast::Type::Tuple(field_types) => field_types,
+            other => unreachable!("Expected an enum to be represented by a tuple, found {other}"),
+        };
+
+        let tag = (constructor.variant as u128).into();
+        let mut fields =
+            vec![ast::Expression::Literal(ast::Literal::Integer(tag, ast::Type::Field, location))];
+
+        let mut arguments = Some(vecmap(constructor.arguments, |arg| self.expr(arg)));
+        for (variant, payload_type) in field_types.iter().skip(1).enumerate() {
+            if variant == constructor.variant {
+                fields.push(ast::Expression::Tuple(arguments.take().unwrap()));
+            } else {
+                fields.push(self.zeroed_value_of_type(payload_type, location));
+            }
+        }
+
+        ast::Expression::Tuple(fields)
+    }
+
+    /// A match is lowered into a chain of if expressions comparing the tag of the matched value
+    /// against the variant of each arm. Since matches are checked to be exhaustive, the last
+    /// reachable arm becomes the final else branch.
+    fn match_expr(
+        &mut self,
+        match_expr: HirMatchExpression,
+        id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let location = self.interner.expr_location(&id);
+        let typ = self.convert_type(&self.interner.id_type(id));
+
+        let scrutinee_type = self.interner.id_type(match_expr.expression);
+        let scrutinee = Box::new(self.expr(match_expr.expression));
+        let scrutinee_id = self.next_local_id();
+        let scrutinee_ident = ast::Expression::Ident(ast::Ident {
+            location: None,
+            definition: Definition::Local(scrutinee_id),
+            mutable: false,
+            name: "$match".into(),
+            typ: self.convert_type(&scrutinee_type),
+        });
+
+        let mut arms = Vec::with_capacity(match_expr.arms.len());
+        for arm in match_expr.arms {
+            match arm.pattern {
+                HirMatchPattern::Variant { variant, fields, .. } => {
+                    let field_types = unwrap_enum_variant_type(&scrutinee_type, variant);
+                    let payload = Box::new(scrutinee_ident.clone());
+                    let payload = ast::Expression::ExtractTupleField(payload, variant + 1);
+                    let bindings =
+                        self.unpack_tuple_pattern(payload, fields.into_iter().zip(field_types));
+
+                    let tag = Box::new(scrutinee_ident.clone());
+                    let tag = ast::Expression::ExtractTupleField(tag, 0);
+                    let variant = (variant as u128).into();
+                    let variant = ast::Literal::Integer(variant, ast::Type::Field, location);
+                    let condition = ast::Expression::Binary(ast::Binary {
+                        lhs: Box::new(tag),
+                        operator: BinaryOpKind::Equal,
+                        rhs: Box::new(ast::Expression::Literal(variant)),
+                        location,
+                    });
+
+                    let body = self.expr(arm.body);
+                    arms.push((Some(condition), ast::Expression::Block(vec![bindings, body])));
+                }
+                HirMatchPattern::Default(pattern) => {
+                    let value = scrutinee_ident.clone();
+                    let bindings = self.unpack_pattern(pattern, value, &scrutinee_type);
+                    let body = self.expr(arm.body);
+                    arms.push((None, ast::Expression::Block(vec![bindings, body])));
+
+                    // Any arms after this one are unreachable
+                    break;
+                }
+                HirMatchPattern::Error(_) => {
+                    unreachable!("Encountered Error node during monomorphization")
+                }
+            }
+        }
+
+        let mut arms = arms.into_iter().rev();
+        let mut result = match arms.next() {
+            Some((_, body)) => body,
+            None => self.zeroed_value_of_type(&typ, location),
+        };
+
+        for (condition, body) in arms {
+            let condition = condition.expect("Only the last reachable arm may match any value");
+            result = ast::Expression::If(ast::If {
+                condition: Box::new(condition),
+                consequence: Box::new(body),
+                alternative: Some(Box::new(result)),
+                typ: typ.clone(),
+            });
+        }
+
+        let scrutinee = ast::Expression::Let(ast::Let {
+            id: scrutinee_id,
+            mutable: false,
+            name: "$match".into(),
+            expression: scrutinee,
+        });
+        ast::Expression::Block(vec![scrutinee, result])
+    }
+
+    /// `expr?` is lowered into returning the `None` or `Err` variant from the current function
+    /// if the operand holds it:
+    /// ```nr
+    /// let $try = expr;
+    /// if $try.0 != SUCCESS_TAG { return (FAILURE_TAG, .., $try.FAILURE_PAYLOAD, ..); }
+    /// $try.SUCCESS_PAYLOAD.0
+    /// ```
+    fn try_expr(
+        &mut self,
+        try_expr: HirTryExpression,
+        id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let location = self.interner.expr_location(&id);
+        let operand_type = self.interner.id_type(try_expr.expression);
+        let (success, failure) = match operand_type.follow_bindings() {
+            HirType::Struct(def, _) => def
+                .borrow()
+                .try_variants()
+                .expect("ICE: expected `?` to be applied to an Option or Result"),
+            other => unreachable!("Expected `?` to be applied to an enum, found {other}"),
+        };
+
+        let operand = Box::new(self.expr(try_expr.expression));
+        let operand_id = self.next_local_id();
+        let operand_ident = ast::Expression::Ident(ast::Ident {
+            location: None,
+            definition: Definition::Local(operand_id),
+            mutable: false,
+            name: "$try".into(),
+            typ: self.convert_type(&operand_type),
+        });
+
+        let payload_types = match self.convert_type(&try_expr.return_type) {
+            ast::Type::Tuple(field_types) => field_types,
+            other => unreachable!("Expected an enum to be represented by a tuple, found {other}"),
+        };
+
+        // Only the type of the unwrapped value may differ between the operand and the returned
+        // value, so the propagated variant's payload can be returned as is.
+        let failure_tag =
+            ast::Literal::Integer((failure as u128).into(), ast::Type::Field, location);
+        let mut returned = vec![ast::Expression::Literal(failure_tag)];
+        for (variant, payload_type) in payload_types.iter().skip(1).enumerate() {
+            if variant == failure {
+                let operand = Box::new(operand_ident.clone());
+                returned.push(ast::Expression::ExtractTupleField(operand, failure + 1));
+            } else {
+                returned.push(self.zeroed_value_of_type(payload_type, location));
+            }
+        }
+
+        let tag = ast::Expression::ExtractTupleField(Box::new(operand_ident.clone()), 0);
+        let success_tag =
+            ast::Literal::Integer((success as u128).into(), ast::Type::Field, location);
+        let condition = ast::Expression::Binary(ast::Binary {
+            lhs: Box::new(tag),
+            operator: BinaryOpKind::NotEqual,
+            rhs: Box::new(ast::Expression::Literal(success_tag)),
+            location,
+        });
+
+        let early_return = self.return_value(ast::Expression::Tuple(returned));
+        let early_return = ast::Expression::If(ast::If {
+            condition: Box::new(condition),
+            consequence: Box::new(early_return),
+            alternative: None,
+            typ: ast::Type::Unit,
+        });
+
+        let payload = ast::Expression::ExtractTupleField(Box::new(operand_ident), success + 1);
+        let value = ast::Expression::ExtractTupleField(Box::new(payload), 0);
+
+        let operand = ast::Expression::Let(ast::Let {
+            id: operand_id,
+            mutable: false,
+            name: "$try".into(),
+            expression: operand,
+        });
+        ast::Expression::Block(vec![operand, early_return, value])
+    }
+
+    fn block(&mut self, statement_ids: Vec<StmtId>, typ: ast::Type) -> ast::Expression {
+        let trailing_return = match statement_ids.last().map(|id| self.interner.statement(id)) {
+            Some(HirStatement::Return(expr)) if typ != ast::Type::Unit => {
+                Some(self.interner.expr_location(&expr))
+            }
+            _ => None,
+        };
+
+        let mut statements = Vec::with_capacity(statement_ids.len());
+        let mut remaining = statement_ids.into_iter();
+
+        while let Some(id) = remaining.next() {
+            let exits = self.exits();
+            statements.push(self.statement(id));
+
+            // If the statement may have exited the loop being unrolled or returned from the
+            // function, the rest of the block must only run if it did not.
+            if self.exits() > exits && remaining.len() > 0 {
+                statements.push(self.guarded_block(remaining.collect(), typ));
+                return ast::Expression::Block(statements);
+            }
+        }
+
+        // A block ending in `return` never produces its own value, but still needs one of its type
+        if let Some(location) = trailing_return {
+            statements.push(self.zeroed_value_of_type(&typ, location));
+        }
+        ast::Expression::Block(statements)
+    }
+
+    /// Monomorphizes the remaining statements of a block so that they only execute if neither
+    /// the innermost unrolled loop has been exited from nor the function has returned. If the
+    /// block has a value, a zeroed value is returned in its place after an exit.
+    fn guarded_block(&mut self, statement_ids: Vec<StmtId>, typ: ast::Type) -> ast::Expression {
+        let condition = Box::new(self.not_exited());
+
+        let result = statement_ids.last().and_then(|id| match self.interner.statement(id) {
+            HirStatement::Expression(expr) | HirStatement::Return(expr) => Some(expr),
+            _ => None,
+        });
+
+        let consequence = Box::new(self.block(statement_ids, typ.clone()));
+        let alternative = match result {
+            Some(expr) if typ != ast::Type::Unit => {
+                let location = self.interner.expr_location(&expr);
+                Some(Box::new(self.zeroed_value_of_type(&typ, location)))
+            }
+            _ => None,
+        };
+
+        ast::Expression::If(ast::If { condition, consequence, alternative, typ })
     }
 
     fn unpack_pattern(
@@ -744,6 +1858,15 @@ impl<'interner> Monomorphizer<'interner> {
                 monomorphized_default
             }
 
+            HirType::Struct(def, args) if def.borrow().is_enum() => {
+                // The tag is followed by the payload of every variant, see enum_constructor
+                let variants = def.borrow().get_variants(args);
+                let payloads = variants.into_iter().map(|(_, fields)| {
+                    ast::Type::Tuple(vecmap(fields, |field| self.convert_type(&field)))
+                });
+                ast::Type::Tuple(std::iter::once(ast::Type::Field).chain(payloads).collect())
+            }
+
             HirType::Struct(def, args) => {
                 let fields = def.borrow().get_fields(args);
                 let fields = vecmap(fields, |(_, field)| self.convert_type(&field));
@@ -816,19 +1939,48 @@ impl<'interner> Monomorphizer<'interner> {
         method: TraitMethodId,
     ) -> ast::Expression {
         let function_type = self.interner.id_type(expr_id);
+        let hir_func_id = self.trait_method_impl(expr_id, method);
+
+        let func_def = self.lookup_function(hir_func_id, expr_id, &function_type);
+        let func_id = match func_def {
+            Definition::Function(func_id) => func_id,
+            _ => unreachable!(),
+        };
+
+        let the_trait = self.interner.get_trait(method.trait_id);
+
+        ast::Expression::Ident(ast::Ident {
+            definition: Definition::Function(func_id),
+            mutable: false,
+            location: None,
+            name: the_trait.methods[method.method_index].name.0.contents.clone(),
+            typ: self.convert_type(&function_type),
+        })
+    }
 
+    /// Returns the function implementing the given trait method for the impl selected for
+    /// `expr_id` during type checking.
+    fn trait_method_impl(
+        &self,
+        expr_id: node_interner::ExprId,
+        method: TraitMethodId,
+    ) -> node_interner::FuncId {
         let trait_impl = self
             .interner
             .get_selected_impl_for_ident(expr_id)
             .expect("ICE: missing trait impl - should be caught during type checking");
 
-        let hir_func_id = match trait_impl {
+        match trait_impl {
             node_interner::TraitImplKind::Normal(impl_id) => {
                 self.interner.get_trait_implementation(impl_id).borrow().methods
                     [method.method_index]
             }
-            node_interner::TraitImplKind::Assumed { object_type } => {
-                match self.interner.lookup_trait_implementation(&object_type, method.trait_id) {
+            node_interner::TraitImplKind::Assumed { object_type, trait_generics } => {
+                match self.interner.lookup_trait_implementation(
+                    &object_type,
+                    method.trait_id,
+                    &trait_generics,
+                ) {
                     Ok(TraitImplKind::Normal(impl_id)) => {
                         self.interner.get_trait_implementation(impl_id).borrow().methods
                             [method.method_index]
@@ -848,22 +2000,79 @@ impl<'interner> Monomorphizer<'interner> {
                     }
                 }
             }
-        };
+        }
+    }
 
-        let func_def = self.lookup_function(hir_func_id, expr_id, &function_type);
-        let func_id = match func_def {
-            Definition::Function(func_id) => func_id,
-            _ => unreachable!(),
+    /// Lowers an operator on a type implementing its operator trait into a call to the trait
+    /// method. Comparisons through `Ord::cmp` are then made against the returned `Ordering`.
+    fn overloaded_operator(
+        &mut self,
+        infix: HirInfixExpression,
+        id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let operator = infix.operator.kind;
+        let location = self.interner.expr_location(&id);
+        let method = self
+            .interner
+            .get_operator_trait_method(operator)
+            .expect("ICE: expected an operator trait for an overloaded operator");
+        let hir_func_id = self.trait_method_impl(id, method);
+
+        // The impl may be generic, so its type variables are bound by unifying its
+        // signature with the operand types.
+        let operand_type = self.interner.id_type(infix.lhs);
+        let (impl_type, bindings) =
+            self.interner.function_meta(&hir_func_id).typ.instantiate(self.interner);
+        let return_type = self.interner.next_type_variable();
+        let function_type = HirType::Function(
+            vec![operand_type.clone(), operand_type],
+            Box::new(return_type.clone()),
+            Box::new(HirType::Unit),
+        );
+        impl_type
+            .try_unify(&function_type)
+            .expect("ICE: operator trait impl does not match its operand types");
+
+        let typ = function_type.follow_bindings();
+        let func_id = match self.globals.get(&hir_func_id).and_then(|functions| functions.get(&typ))
+        {
+            Some(func_id) => *func_id,
+            None => self.queue_function_with_bindings(hir_func_id, typ, &bindings),
         };
-
-        let the_trait = self.interner.get_trait(method.trait_id);
-
-        ast::Expression::Ident(ast::Ident {
+        let func = Box::new(ast::Expression::Ident(ast::Ident {
             definition: Definition::Function(func_id),
             mutable: false,
             location: None,
-            name: the_trait.methods[method.method_index].name.0.contents.clone(),
+            name: self.interner.function_name(&hir_func_id).to_owned(),
             typ: self.convert_type(&function_type),
+        }));
+
+        let arguments = vec![self.expr(infix.lhs), self.expr(infix.rhs)];
+        let return_type = self.convert_type(&return_type);
+        let call = ast::Expression::Call(ast::Call { func, arguments, return_type, location });
+
+        let (variant, operator) = match operator {
+            BinaryOpKind::NotEqual => return not(call, location),
+            BinaryOpKind::Less => ("Less", BinaryOpKind::Equal),
+            BinaryOpKind::LessEqual => ("Greater", BinaryOpKind::NotEqual),
+            BinaryOpKind::Greater => ("Greater", BinaryOpKind::Equal),
+            BinaryOpKind::GreaterEqual => ("Less", BinaryOpKind::NotEqual),
+            _ => return call,
+        };
+
+        let ordering =
+            match self.interner.function_meta(&hir_func_id).return_type().follow_bindings() {
+                HirType::Struct(def, _) => def.borrow().get_variant(variant),
+                _ => None,
+            };
+        let tag = ordering.expect("ICE: expected `Ord::cmp` to return an `Ordering`");
+        let tag = ast::Literal::Integer((tag as u128).into(), ast::Type::Field, location);
+
+        ast::Expression::Binary(ast::Binary {
+            lhs: Box::new(ast::Expression::ExtractTupleField(Box::new(call), 0)),
+            operator,
+            rhs: Box::new(ast::Expression::Literal(tag)),
+            location,
         })
     }
 
@@ -873,7 +2082,19 @@ impl<'interner> Monomorphizer<'interner> {
         id: node_interner::ExprId,
     ) -> ast::Expression {
         let original_func = Box::new(self.expr(call.func));
-        let mut arguments = vecmap(&call.arguments, |id| self.expr(*id));
+
+        // Mutable references to array elements are passed as references to a copy of the element
+        // which is written back to the array once the call returns. If there are any, every
+        // argument is bound to a variable before the call to preserve their evaluation order.
+        let mut argument_setup = Vec::new();
+        let mut write_backs = Vec::new();
+        let mut arguments = if call.arguments.iter().any(|id| self.is_element_reference(*id)) {
+            vecmap(&call.arguments, |id| {
+                self.hoisted_argument(*id, &mut argument_setup, &mut write_backs)
+            })
+        } else {
+            vecmap(&call.arguments, |id| self.expr(*id))
+        };
         let hir_arguments = vecmap(&call.arguments, |id| self.interner.expression(id));
         let func: Box<ast::Expression>;
         let return_type = self.interner.id_type(id);
@@ -883,7 +2104,7 @@ impl<'interner> Monomorphizer<'interner> {
 
         if let ast::Expression::Ident(ident) = original_func.as_ref() {
             if let Definition::Oracle(name) = &ident.definition {
-                if name.as_str() == "println" {
+                if name.as_str() == "println" || name.as_str() == "assert_message" {
                     // Oracle calls are required to be wrapped in an unconstrained function
                     // Thus, the only argument to the `println` oracle is expected to always be an ident
                     self.append_printable_type_info(&hir_arguments[0], &mut arguments);
@@ -927,11 +2148,19 @@ impl<'interner> Monomorphizer<'interner> {
             func = original_func.clone();
         };
 
-        let call = self
-            .try_evaluate_call(&func, &id, &return_type)
-            .unwrap_or(ast::Expression::Call(ast::Call { func, arguments, return_type, location }));
+        let call = self.try_evaluate_call(&func, &id, &return_type).unwrap_or_else(|| {
+            let return_type = return_type.clone();
+            ast::Expression::Call(ast::Call { func, arguments, return_type, location })
+        });
 
-        if !block_expressions.is_empty() {
+        if !write_backs.is_empty() {
+            block_expressions.append(&mut argument_setup);
+            let (let_result, result) = self.hoist_expression("result", false, return_type, call);
+            block_expressions.push(let_result);
+            block_expressions.append(&mut write_backs);
+            block_expressions.push(ast::Expression::Ident(result));
+            ast::Expression::Block(block_expressions)
+        } else if !block_expressions.is_empty() {
             block_expressions.push(call);
             ast::Expression::Block(block_expressions)
         } else {
@@ -939,6 +2168,164 @@ impl<'interner> Monomorphizer<'interner> {
         }
     }
 
+    /// Returns true if the given call argument is a mutable reference to an array element,
+    /// possibly within a struct field such as `&mut foo.bar[i].baz`.
+    fn is_element_reference(&self, argument: node_interner::ExprId) -> bool {
+        match self.interner.expression(&argument) {
+            HirExpression::Prefix(prefix) if prefix.operator == UnaryOp::MutableReference => {
+                self.contains_element(prefix.rhs)
+            }
+            _ => false,
+        }
+    }
+
+    fn contains_element(&self, expr: node_interner::ExprId) -> bool {
+        match self.interner.expression(&expr) {
+            HirExpression::Index(index) => self.is_lvalue(index.collection),
+            HirExpression::MemberAccess(access) => self.contains_element(access.lhs),
+            _ => false,
+        }
+    }
+
+    fn is_lvalue(&self, expr: node_interner::ExprId) -> bool {
+        match self.interner.expression(&expr) {
+            HirExpression::Ident(_) => true,
+            HirExpression::Index(index) => self.is_lvalue(index.collection),
+            HirExpression::MemberAccess(access) => self.is_lvalue(access.lhs),
+            HirExpression::Prefix(prefix) => {
+                matches!(prefix.operator, UnaryOp::Dereference { .. }) && self.is_lvalue(prefix.rhs)
+            }
+            _ => false,
+        }
+    }
+
+    /// Binds a call argument to a new variable, pushing the `let` statements needed into `setup`.
+    /// For a mutable reference to an array element, this binds a mutable copy of the element
+    /// instead, pushing the assignment which copies it back into the array into `write_backs`.
+    fn hoisted_argument(
+        &mut self,
+        argument: node_interner::ExprId,
+        setup: &mut Vec<ast::Expression>,
+        write_backs: &mut Vec<ast::Expression>,
+    ) -> ast::Expression {
+        let typ = self.convert_type(&self.interner.id_type(argument));
+
+        if !self.is_element_reference(argument) {
+            let expression = self.expr(argument);
+            let (let_argument, argument) =
+                self.hoist_expression("argument", false, typ, expression);
+            setup.push(let_argument);
+            return ast::Expression::Ident(argument);
+        }
+
+        let HirExpression::Prefix(prefix) = self.interner.expression(&argument) else {
+            unreachable!("element references are always prefix expressions")
+        };
+        let (lvalue, current_value) = self.element_lvalue(prefix.rhs, setup);
+
+        let element_type = self.convert_type(&self.interner.id_type(prefix.rhs));
+        let (let_element, element) =
+            self.hoist_expression("element", true, element_type, current_value);
+        setup.push(let_element);
+
+        let expression = Box::new(ast::Expression::Ident(element.clone()));
+        write_backs.push(ast::Expression::Assign(ast::Assign { expression, lvalue }));
+
+        ast::Expression::Unary(ast::Unary {
+            operator: UnaryOp::MutableReference,
+            rhs: Box::new(ast::Expression::Ident(element)),
+            result_type: typ,
+            location: self.interner.expr_location(&argument),
+        })
+    }
+
+    /// Converts the given expression into an lvalue along with an expression reading its
+    /// current value. Each array index is bound to a variable in `setup` so that it is only
+    /// evaluated once.
+    fn element_lvalue(
+        &mut self,
+        expr: node_interner::ExprId,
+        setup: &mut Vec<ast::Expression>,
+    ) -> (ast::LValue, ast::Expression) {
+        match self.interner.expression(&expr) {
+            HirExpression::Ident(ident) => {
+                let lvalue = self
+                    .lookup_captured_lvalue(ident.id)
+                    .unwrap_or_else(|| ast::LValue::Ident(self.local_ident(&ident).unwrap()));
+                (lvalue, self.expr(expr))
+            }
+            HirExpression::MemberAccess(access) => {
+                let (object, object_value) = self.element_lvalue(access.lhs, setup);
+                let field_index = self.interner.get_field_index(expr);
+                let lvalue = ast::LValue::MemberAccess { object: Box::new(object), field_index };
+                (lvalue, ast::Expression::ExtractTupleField(Box::new(object_value), field_index))
+            }
+            HirExpression::Index(index) => {
+                let (array, array_value) = self.element_lvalue(index.collection, setup);
+                let location = self.interner.expr_location(&index.index);
+
+                let index_type = self.convert_type(&self.interner.id_type(index.index));
+                let index_value = self.expr(index.index);
+                let (let_index, index) =
+                    self.hoist_expression("index", false, index_type, index_value);
+                setup.push(let_index);
+
+                let element_type = self.convert_type(&self.interner.id_type(expr));
+                let lvalue = ast::LValue::Index {
+                    array: Box::new(array),
+                    index: Box::new(ast::Expression::Ident(index.clone())),
+                    element_type: element_type.clone(),
+                    location,
+                };
+                let value = ast::Expression::Index(ast::Index {
+                    collection: Box::new(array_value),
+                    index: Box::new(ast::Expression::Ident(index)),
+                    element_type,
+                    location,
+                });
+                (lvalue, value)
+            }
+            HirExpression::Prefix(prefix) => {
+                let (reference, reference_value) = self.element_lvalue(prefix.rhs, setup);
+                let element_type = self.convert_type(&self.interner.id_type(expr));
+                let lvalue = ast::LValue::Dereference {
+                    reference: Box::new(reference),
+                    element_type: element_type.clone(),
+                };
+                let value = ast::Expression::Unary(ast::Unary {
+                    operator: prefix.operator,
+                    rhs: Box::new(reference_value),
+                    result_type: element_type,
+                    location: self.interner.expr_location(&expr),
+                });
+                (lvalue, value)
+            }
+            other => unreachable!("Expected an lvalue for a mutable reference, found {other:?}"),
+        }
+    }
+
+    /// Binds the given expression to a new local variable, returning the `let` expression
+    /// along with an identifier referring to the variable.
+    fn hoist_expression(
+        &mut self,
+        name: &str,
+        mutable: bool,
+        typ: ast::Type,
+        expression: ast::Expression,
+    ) -> (ast::Expression, ast::Ident) {
+        let id = self.next_local_id();
+        let name = name.to_string();
+        let let_statement = ast::Expression::Let(ast::Let {
+            id,
+            mutable,
+            name: name.clone(),
+            expression: Box::new(expression),
+        });
+        let ident =
+            ast::Ident { location: None, definition: Definition::Local(id), mutable, name, typ };
+        (let_statement, ident)
+    }
+
     /// Adds a function argument that contains type metadata that is required to tell
     /// `println` how to convert values passed to an foreign call  back to a human-readable string.
     /// The values passed to an foreign call will be a simple list of field elements,
@@ -1072,11 +2459,20 @@ impl<'interner> Monomorphizer<'interner> {
         id: node_interner::FuncId,
         expr_id: node_interner::ExprId,
         function_type: HirType,
+    ) -> FuncId {
+        let bindings = self.interner.get_instantiation_bindings(expr_id);
+        self.queue_function_with_bindings(id, function_type, bindings)
+    }
+
+    fn queue_function_with_bindings(
+        &mut self,
+        id: node_interner::FuncId,
+        function_type: HirType,
+        bindings: &TypeBindings,
     ) -> FuncId {
         let new_id = self.next_function_id();
         self.define_global(id, function_type, new_id);
 
-        let bindings = self.interner.get_instantiation_bindings(expr_id);
         let bindings = self.follow_bindings(bindings);
 
         self.queue.push_back((id, new_id, bindings));
@@ -1150,14 +2546,16 @@ impl<'interner> Monomorphizer<'interner> {
             vecmap(lambda.parameters, |(pattern, typ)| (pattern, typ, Visibility::Private)).into();
 
         let parameters = self.parameters(parameters);
-        let body = self.expr(lambda.body);
+        let body = self.lambda_body(lambda.body, &ret_type, lambda.unconstrained);
 
         let id = self.next_function_id();
         let return_type = ret_type.clone();
         let name = lambda_name.to_owned();
-        let unconstrained = false;
+        let unconstrained = lambda.unconstrained;
 
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let inline_type = InlineType::default();
+        let function =
+            ast::Function { id, name, parameters, body, return_type, unconstrained, inline_type };
         self.push_function(id, function);
 
         let typ =
@@ -1173,6 +2571,21 @@ impl<'interner> Monomorphizer<'interner> {
         })
     }
 
+    /// The body of an unconstrained lambda, lowered from an `unconstrained { ... }` block, is
+    /// monomorphized as unconstrained code even within a constrained function.
+    fn lambda_body(
+        &mut self,
+        body: node_interner::ExprId,
+        return_type: &ast::Type,
+        unconstrained: bool,
+    ) -> ast::Expression {
+        let in_unconstrained_function = self.in_unconstrained_function;
+        self.in_unconstrained_function |= unconstrained;
+        let body = self.function_body(body, return_type);
+        self.in_unconstrained_function = in_unconstrained_function;
+        body
+    }
+
     fn lambda_with_setup(
         &mut self,
         lambda: HirLambda,
@@ -1253,7 +2666,7 @@ impl<'interner> Monomorphizer<'interner> {
 
         self.lambda_envs_stack
             .push(LambdaContext { env_ident: env_ident.clone(), captures: lambda.captures });
-        let body = self.expr(lambda.body);
+        let body = self.lambda_body(lambda.body, &ret_type, lambda.unconstrained);
         self.lambda_envs_stack.pop();
 
         let lambda_fn_typ: ast::Type =
@@ -1270,8 +2683,10 @@ impl<'interner> Monomorphizer<'interner> {
         parameters.push((env_local_id, true, env_name.to_string(), env_typ.clone()));
         parameters.append(&mut converted_parameters);
 
-        let unconstrained = false;
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let unconstrained = lambda.unconstrained;
+        let inline_type = InlineType::default();
+        let function =
+            ast::Function { id, name, parameters, body, return_type, unconstrained, inline_type };
         self.push_function(id, function);
 
         let lambda_value =
@@ -1389,7 +2804,9 @@ impl<'interner> Monomorphizer<'interner> {
         let name = lambda_name.to_owned();
 
         let unconstrained = false;
-        let function = ast::Function { id, name, parameters, body, return_type, unconstrained };
+        let inline_type = InlineType::default();
+        let function =
+            ast::Function { id, name, parameters, body, return_type, unconstrained, inline_type };
         self.push_function(id, function);
 
         ast::Expression::Ident(ast::Ident {
@@ -1409,7 +2826,8 @@ impl<'interner> Monomorphizer<'interner> {
 fn unwrap_tuple_type(typ: &HirType) -> Vec<HirType> {
     match typ {
         HirType::Tuple(fields) => fields.clone(),
-        HirType::TypeVariable(binding, TypeVariableKind::Normal) => match &*binding.borrow() {
+        HirType::TypeVariable(binding, TypeVariableKind::Normal)
+        | HirType::NamedGeneric(binding, _) => match &*binding.borrow() {
             TypeBinding::Bound(binding) => unwrap_tuple_type(binding),
             TypeBinding::Unbound(_) => unreachable!(),
         },
@@ -1420,7 +2838,8 @@ fn unwrap_tuple_type(typ: &HirType) -> Vec<HirType> {
 fn unwrap_struct_type(typ: &HirType) -> Vec<(String, HirType)> {
     match typ {
         HirType::Struct(def, args) => def.borrow().get_fields(args),
-        HirType::TypeVariable(binding, TypeVariableKind::Normal) => match &*binding.borrow() {
+        HirType::TypeVariable(binding, TypeVariableKind::Normal)
+        | HirType::NamedGeneric(binding, _) => match &*binding.borrow() {
             TypeBinding::Bound(binding) => unwrap_struct_type(binding),
             TypeBinding::Unbound(_) => unreachable!(),
         },
@@ -1428,6 +2847,67 @@ fn unwrap_struct_type(typ: &HirType) -> Vec<(String, HirType)> {
     }
 }
 
+/// Returns the payload types of the given variant of an enum type
+fn unwrap_enum_variant_type(typ: &HirType, variant: usize) -> Vec<HirType> {
+    match typ {
+        HirType::Struct(def, args) => def.borrow().get_variant_fields(variant, args),
+        HirType::TypeVariable(binding, TypeVariableKind::Normal)
+        | HirType::NamedGeneric(binding, _) => match &*binding.borrow() {
+            TypeBinding::Bound(binding) => unwrap_enum_variant_type(binding, variant),
+            TypeBinding::Unbound(_) => unreachable!(),
+        },
+        other => unreachable!("unwrap_enum_variant_type: expected enum, found {:?}", other),
+    }
+}
+
+/// Identifies one of the boolean flags used to lower control flow in unrolled loops
+fn flag_ident(id: LocalId, name: &str) -> ast::Ident {
+    ast::Ident {
+        location: None,
+        definition: Definition::Local(id),
+        mutable: true,
+        name: name.to_owned(),
+        typ: ast::Type::Bool,
+    }
+}
+
+fn flag(id: LocalId, name: &str) -> ast::Expression {
+    ast::Expression::Ident(flag_ident(id, name))
+}
+
+fn declare_flag(id: LocalId, name: &str) -> ast::Expression {
+    let expression = Box::new(ast::Expression::Literal(ast::Literal::Bool(false)));
+    ast::Expression::Let(ast::Let { id, mutable: true, name: name.to_owned(), expression })
+}
+
+fn set_flag(id: LocalId, name: &str, value: bool) -> ast::Expression {
+    ast::Expression::Assign(ast::Assign {
+        lvalue: ast::LValue::Ident(flag_ident(id, name)),
+        expression: Box::new(ast::Expression::Literal(ast::Literal::Bool(value))),
+    })
+}
+
+/// Combines the given conditions with `|`, returning None if there are none
+fn any(conditions: Vec<ast::Expression>, location: Location) -> Option<ast::Expression> {
+    conditions.into_iter().reduce(|lhs, rhs| {
+        ast::Expression::Binary(ast::Binary {
+            lhs: Box::new(lhs),
+            operator: BinaryOpKind::Or,
+            rhs: Box::new(rhs),
+            location,
+        })
+    })
+}
+
+fn not(rhs: ast::Expression, location: Location) -> ast::Expression {
+    ast::Expression::Unary(ast::Unary {
+        operator: UnaryOp::Not,
+        rhs: Box::new(rhs),
+        result_type: ast::Type::Bool,
+        location,
+    })
+}
+
 fn perform_instantiation_bindings(bindings: &TypeBindings) {
     for (var, binding) in bindings.values() {
         *var.borrow_mut() = TypeBinding::Bound(binding.clone());
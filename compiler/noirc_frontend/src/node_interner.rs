@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use arena::{Arena, Index};
 use fm::FileId;
@@ -7,7 +7,9 @@ use noirc_errors::{Location, Span, Spanned};
 
 use crate::ast::Ident;
 use crate::graph::CrateId;
-use crate::hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias};
+use crate::hir::def_collector::dc_crate::{
+    UnresolvedEnum, UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias,
+};
 use crate::hir::def_map::{LocalModuleId, ModuleId};
 use crate::hir::StorageSlot;
 use crate::hir_def::stmt::HirLetStatement;
@@ -15,14 +17,15 @@ use crate::hir_def::traits::TraitImpl;
 use crate::hir_def::traits::{Trait, TraitConstraint};
 use crate::hir_def::types::{StructType, Type};
 use crate::hir_def::{
-    expr::HirExpression,
+    expr::{HirExpression, HirLiteral},
     function::{FuncMeta, HirFunction},
     stmt::HirStatement,
 };
+use crate::lints::LintLevels;
 use crate::token::{Attributes, SecondaryAttribute};
 use crate::{
-    ContractFunctionType, FunctionDefinition, FunctionVisibility, Generics, Shared, TypeAliasType,
-    TypeBinding, TypeBindings, TypeVariable, TypeVariableId, TypeVariableKind,
+    BinaryOpKind, ContractFunctionType, FunctionDefinition, FunctionVisibility, Generics, Shared,
+    TypeAliasType, TypeBinding, TypeBindings, TypeVariable, TypeVariableId, TypeVariableKind,
 };
 
 /// An arbitrary number to limit the recursion depth when searching for trait impls.
@@ -50,6 +53,10 @@ pub struct NodeInterner {
     // Contains the source module each function was defined in
     function_modules: HashMap<FuncId, ModuleId>,
 
+    // The unresolved definitions of each `comptime fn`. These are evaluated directly
+    // during name resolution when they are called within an array length or global.
+    comptime_functions: HashMap<FuncId, FunctionDefinition>,
+
     // Map each `Index` to it's own location
     id_to_location: HashMap<Index, Location>,
 
@@ -73,6 +80,17 @@ pub struct NodeInterner {
     structs: HashMap<StructId, Shared<StructType>>,
 
     struct_attributes: HashMap<StructId, StructAttributes>,
+
+    // The location of each struct and enum definition
+    struct_locations: HashMap<StructId, Location>,
+
+    // Every place an item is referred to by name, recorded during name resolution
+    // and type checking so that the item can be renamed.
+    references: HashMap<ReferenceId, Vec<Location>>,
+
+    // Every place a generic type parameter is declared or used in a type
+    type_parameter_locations: Vec<Location>,
+
     // Type Aliases map.
     //
     // Map type aliases to the actual type.
@@ -109,6 +127,13 @@ pub struct NodeInterner {
     /// the context to get the concrete type of the object and select the correct impl itself.
     selected_trait_implementations: HashMap<ExprId, TraitImplKind>,
 
+    /// The standard library traits used to overload each binary operator, e.g. `Add` for `+`.
+    /// Each of these traits has a single method.
+    operator_traits: HashMap<BinaryOpKind, TraitId>,
+
+    /// Every `comptime` block which has been type checked but not yet evaluated.
+    comptime_expressions: Vec<ExprId>,
+
     /// Map from ExprId (referring to a Function/Method call) to its corresponding TypeBindings,
     /// filled out during type checking from instantiated variables. Used during monomorphization
     /// to map call site types back onto function parameter types, and undo this binding as needed.
@@ -135,6 +160,12 @@ pub struct NodeInterner {
 
     // For trait implementation functions, this is their self type and trait they belong to
     func_id_to_trait: HashMap<FuncId, (Type, TraitId)>,
+
+    /// The levels lints are reported at, set by lint attributes and each crate's configuration.
+    lint_levels: LintLevels,
+
+    /// Functions which are referred to by name from other code, used to find unused functions.
+    referenced_functions: HashSet<FuncId>,
 }
 
 /// A trait implementation is either a normal implementation that is present in the source
@@ -146,6 +177,7 @@ pub enum TraitImplKind {
     /// Assumed impls don't have an impl id since they don't link back to any concrete part of the source code.
     Assumed {
         object_type: Type,
+        trait_generics: Vec<Type>,
     },
 }
 
@@ -176,6 +208,9 @@ pub struct FunctionModifiers {
 
     pub is_unconstrained: bool,
 
+    /// Whether the function is `comptime` and may be evaluated during name resolution.
+    pub is_comptime: bool,
+
     /// This function's type in its contract.
     /// If this function is not in a contract, this is always 'Secret'.
     pub contract_function_type: Option<ContractFunctionType>,
@@ -196,6 +231,7 @@ impl FunctionModifiers {
             visibility: FunctionVisibility::Public,
             attributes: Attributes::empty(),
             is_unconstrained: false,
+            is_comptime: false,
             is_internal: None,
             contract_function_type: None,
         }
@@ -218,6 +254,14 @@ impl From<DefinitionId> for Index {
     }
 }
 
+/// An item which can be referred to by name from elsewhere in the program.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReferenceId {
+    Definition(DefinitionId),
+    Struct(StructId),
+    StructField(StructId, String),
+}
+
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 pub struct StmtId(Index);
 
@@ -353,6 +397,8 @@ pub struct DefinitionInfo {
     pub name: String,
     pub mutable: bool,
     pub kind: DefinitionKind,
+    /// Where the definition's name is declared. Functions are located through their metadata instead.
+    pub location: Option<Location>,
 }
 
 impl DefinitionInfo {
@@ -413,23 +459,31 @@ impl Default for NodeInterner {
             function_definition_ids: HashMap::new(),
             function_modifiers: HashMap::new(),
             function_modules: HashMap::new(),
+            comptime_functions: HashMap::new(),
             func_id_to_trait: HashMap::new(),
             id_to_location: HashMap::new(),
             definitions: vec![],
             id_to_type: HashMap::new(),
             structs: HashMap::new(),
             struct_attributes: HashMap::new(),
+            struct_locations: HashMap::new(),
+            references: HashMap::new(),
+            type_parameter_locations: Vec::new(),
             type_aliases: Vec::new(),
             traits: HashMap::new(),
             trait_implementations: Vec::new(),
             trait_implementation_map: HashMap::new(),
             selected_trait_implementations: HashMap::new(),
+            operator_traits: HashMap::new(),
+            comptime_expressions: Vec::new(),
             instantiation_bindings: HashMap::new(),
             field_indices: HashMap::new(),
             next_type_variable_id: std::cell::Cell::new(0),
             globals: HashMap::new(),
             struct_methods: HashMap::new(),
             primitive_methods: HashMap::new(),
+            lint_levels: LintLevels::default(),
+            referenced_functions: HashSet::new(),
         };
 
         // An empty block expression is used often, we add this into the `node` on startup
@@ -514,9 +568,35 @@ impl NodeInterner {
         let new_struct = StructType::new(struct_id, name, typ.struct_def.span, no_fields, generics);
         self.structs.insert(struct_id, Shared::new(new_struct));
         self.struct_attributes.insert(struct_id, typ.struct_def.attributes.clone());
+        self.struct_locations.insert(struct_id, Location::new(typ.struct_def.span, typ.file_id));
         struct_id
     }
 
+    /// Enums share the StructId namespace with structs so that they may
+    /// be referred to, given impls, and have methods looked up the same way.
+    pub fn new_enum(
+        &mut self,
+        typ: &UnresolvedEnum,
+        krate: CrateId,
+        local_id: LocalModuleId,
+    ) -> StructId {
+        let enum_id = StructId(ModuleId { krate, local_id });
+        let name = typ.enum_def.name.clone();
+
+        // Variants will be filled in later
+        let generics = vecmap(&typ.enum_def.generics, |_| {
+            // Temporary type variable ids, see new_struct
+            let id = TypeVariableId(0);
+            (id, Shared::new(TypeBinding::Unbound(id)))
+        });
+
+        let new_enum = StructType::new_enum(enum_id, name, typ.enum_def.span, generics);
+        self.structs.insert(enum_id, Shared::new(new_enum));
+        self.struct_attributes.insert(enum_id, typ.enum_def.attributes.clone());
+        self.struct_locations.insert(enum_id, Location::new(typ.enum_def.span, typ.file_id));
+        enum_id
+    }
+
     pub fn push_type_alias(&mut self, typ: &UnresolvedTypeAlias) -> TypeAliasId {
         let type_id = TypeAliasId(self.type_aliases.len());
 
@@ -633,6 +713,11 @@ impl NodeInterner {
             .map(|(func_id, _meta)| *func_id)
     }
 
+    /// Returns the ids of every function which has been given metadata.
+    pub fn function_ids(&self) -> impl Iterator<Item = FuncId> + '_ {
+        self.func_meta.keys().copied()
+    }
+
     ///Interns a function's metadata.
     ///
     /// Note that the FuncId has been created already.
@@ -646,13 +731,14 @@ impl NodeInterner {
         name: String,
         mutable: bool,
         definition: DefinitionKind,
+        location: Option<Location>,
     ) -> DefinitionId {
         let id = DefinitionId(self.definitions.len());
         if let DefinitionKind::Function(func_id) = definition {
             self.function_definition_ids.insert(func_id, id);
         }
 
-        self.definitions.push(DefinitionInfo { name, mutable, kind: definition });
+        self.definitions.push(DefinitionInfo { name, mutable, kind: definition, location });
         id
     }
 
@@ -682,9 +768,13 @@ impl NodeInterner {
             visibility: function.visibility,
             attributes: function.attributes.clone(),
             is_unconstrained: function.is_unconstrained,
+            is_comptime: function.is_comptime,
             contract_function_type: Some(if function.is_open { Open } else { Secret }),
             is_internal: Some(function.is_internal),
         };
+        if function.is_comptime {
+            self.comptime_functions.insert(id, function.clone());
+        }
         self.push_function_definition(id, modifiers, module)
     }
 
@@ -697,7 +787,7 @@ impl NodeInterner {
         let name = modifiers.name.clone();
         self.function_modifiers.insert(func, modifiers);
         self.function_modules.insert(func, module);
-        self.push_definition(name, false, DefinitionKind::Function(func))
+        self.push_definition(name, false, DefinitionKind::Function(func), None)
     }
 
     pub fn set_function_trait(&mut self, func: FuncId, self_type: Type, trait_id: TraitId) {
@@ -721,6 +811,12 @@ impl NodeInterner {
         self.function_modules[&func]
     }
 
+    /// Returns the unresolved definition of a `comptime fn`, or None if
+    /// this function was not declared `comptime`.
+    pub fn comptime_function(&self, func: FuncId) -> Option<&FunctionDefinition> {
+        self.comptime_functions.get(&func)
+    }
+
     /// Returns the interned HIR function corresponding to `func_id`
     //
     // Cloning HIR structures is cheap, so we return owned structures
@@ -822,6 +918,33 @@ impl NodeInterner {
         self.definitions.get(id.0)
     }
 
+    /// Returns the value of the given expression if it is a string known at compile-time:
+    /// a string literal, an immutable variable or global defined as one, or a concatenation
+    /// of these.
+    pub fn compile_time_string(&self, expr: &ExprId) -> Option<String> {
+        match self.expression(expr) {
+            HirExpression::Literal(HirLiteral::Str(string)) => Some(string),
+            HirExpression::Ident(ident) => {
+                let definition = self.try_definition(ident.id)?;
+                match definition.kind {
+                    DefinitionKind::Global(expr) => self.compile_time_string(&expr),
+                    DefinitionKind::Local(Some(expr)) if !definition.mutable => {
+                        self.compile_time_string(&expr)
+                    }
+                    _ => None,
+                }
+            }
+            HirExpression::Infix(infix)
+                if matches!(infix.operator.kind, BinaryOpKind::Add | BinaryOpKind::WrappingAdd) =>
+            {
+                let lhs = self.compile_time_string(&infix.lhs)?;
+                let rhs = self.compile_time_string(&infix.rhs)?;
+                Some(lhs + &rhs)
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the name of the definition
     ///
     /// This is needed as the Environment needs to map variable names to witness indices
@@ -841,6 +964,10 @@ impl NodeInterner {
         self.structs[&id].clone()
     }
 
+    pub fn struct_location(&self, id: StructId) -> Location {
+        self.struct_locations[&id]
+    }
+
     pub fn get_trait(&self, id: TraitId) -> Trait {
         self.traits[&id].clone()
     }
@@ -849,6 +976,55 @@ impl NodeInterner {
         self.traits.get(&id).cloned()
     }
 
+    /// Registers the given standard library trait as overloading any operators it is named for.
+    pub fn try_add_operator_trait(&mut self, trait_id: TraitId) {
+        use BinaryOpKind::*;
+        let operators: &[BinaryOpKind] = match self.traits[&trait_id].name.0.contents.as_str() {
+            "Add" => &[Add, WrappingAdd],
+            "Sub" => &[Subtract, WrappingSubtract],
+            "Mul" => &[Multiply, WrappingMultiply],
+            "Div" => &[Divide],
+            "Eq" => &[Equal, NotEqual],
+            "Ord" => &[Less, LessEqual, Greater, GreaterEqual],
+            _ => return,
+        };
+
+        for operator in operators {
+            self.operator_traits.insert(*operator, trait_id);
+        }
+    }
+
+    /// Returns the method of the trait overloading the given operator, if there is one.
+    pub fn get_operator_trait_method(&self, operator: BinaryOpKind) -> Option<TraitMethodId> {
+        let trait_id = *self.operator_traits.get(&operator)?;
+        Some(TraitMethodId { trait_id, method_index: 0 })
+    }
+
+    pub fn lint_levels(&self) -> &LintLevels {
+        &self.lint_levels
+    }
+
+    pub fn lint_levels_mut(&mut self) -> &mut LintLevels {
+        &mut self.lint_levels
+    }
+
+    pub fn mark_function_referenced(&mut self, func: FuncId) {
+        self.referenced_functions.insert(func);
+    }
+
+    pub fn is_function_referenced(&self, func: FuncId) -> bool {
+        self.referenced_functions.contains(&func)
+    }
+
+    pub fn push_comptime_expression(&mut self, expr_id: ExprId) {
+        self.comptime_expressions.push(expr_id);
+    }
+
+    /// Returns every `comptime` expression pushed since the last call, in the order they were checked
+    pub fn take_comptime_expressions(&mut self) -> Vec<ExprId> {
+        std::mem::take(&mut self.comptime_expressions)
+    }
+
     pub fn get_type_alias(&self, id: TypeAliasId) -> &TypeAliasType {
         &self.type_aliases[id.0]
     }
@@ -888,6 +1064,214 @@ impl NodeInterner {
         self.id_to_location.get(&index.into()).copied().unwrap()
     }
 
+    /// Returns the innermost expression whose span contains `location`.
+    pub fn find_expression_at(&self, location: Location) -> Option<ExprId> {
+        let contains =
+            |span: Span| span.start() <= location.span.start() && location.span.end() <= span.end();
+
+        self.id_to_location
+            .iter()
+            .filter(|(index, expr_location)| {
+                expr_location.file == location.file
+                    && contains(expr_location.span)
+                    && matches!(self.nodes.get(**index), Some(Node::Expression(_)))
+            })
+            .min_by_key(|(_, expr_location)| expr_location.span.end() - expr_location.span.start())
+            .map(|(index, _)| ExprId(*index))
+    }
+
+    /// Returns the location of the definition referred to by the given expression:
+    /// the declaration of an identifier, the struct of a constructor or the field of a member access.
+    pub fn referenced_location(&self, expr_id: ExprId) -> Option<Location> {
+        match self.expression(&expr_id) {
+            HirExpression::Ident(ident) => {
+                let definition = self.try_definition(ident.id)?;
+                match definition.kind {
+                    DefinitionKind::Function(func_id) => {
+                        self.try_function_meta(&func_id).map(|meta| meta.name.location)
+                    }
+                    _ => definition.location,
+                }
+            }
+            HirExpression::Constructor(constructor) => {
+                Some(self.struct_location(constructor.r#type.borrow().id))
+            }
+            HirExpression::MemberAccess(access) => {
+                let Type::Struct(struct_type, _) = self.id_type(access.lhs).follow_bindings()
+                else {
+                    return None;
+                };
+                let struct_type = struct_type.borrow();
+                let field =
+                    struct_type.field_names().into_iter().find(|field| *field == access.rhs)?;
+                Some(Location::new(field.span(), self.struct_location(struct_type.id).file))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn add_reference(&mut self, id: ReferenceId, location: Location) {
+        self.references.entry(id).or_default().push(location);
+    }
+
+    /// Returns every location which refers to `id`, not including its declaration.
+    pub fn references(&self, id: &ReferenceId) -> &[Location] {
+        self.references.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the location of the name `id` is declared with.
+    pub fn declaration_location(&self, id: &ReferenceId) -> Option<Location> {
+        match id {
+            ReferenceId::Definition(definition_id) => {
+                let definition = self.try_definition(*definition_id)?;
+                match definition.kind {
+                    DefinitionKind::Function(func_id) => {
+                        self.try_function_meta(&func_id).map(|meta| meta.name.location)
+                    }
+                    _ => definition.location,
+                }
+            }
+            ReferenceId::Struct(struct_id) => {
+                let file = self.struct_locations.get(struct_id)?.file;
+                Some(Location::new(self.get_struct(*struct_id).borrow().name.span(), file))
+            }
+            ReferenceId::StructField(struct_id, field_name) => {
+                let file = self.struct_locations.get(struct_id)?.file;
+                let struct_type = self.get_struct(*struct_id);
+                let field_names = struct_type.borrow().field_names();
+                let field =
+                    field_names.into_iter().find(|field| field.0.contents == *field_name)?;
+                Some(Location::new(field.span(), file))
+            }
+        }
+    }
+
+    /// Returns the item whose name, either where it is declared or where it is referred to,
+    /// contains `location`.
+    pub fn find_reference_at(&self, location: Location) -> Option<ReferenceId> {
+        let contains = |other: &Location| {
+            other.file == location.file
+                && other.span.start() <= location.span.start()
+                && location.span.end() <= other.span.end()
+        };
+
+        let referenced = self
+            .references
+            .iter()
+            .find_map(|(id, locations)| locations.iter().any(contains).then(|| id.clone()));
+        if referenced.is_some() {
+            return referenced;
+        }
+
+        self.named_items().find(|id| {
+            self.declaration_location(id).map_or(false, |declaration| contains(&declaration))
+        })
+    }
+
+    /// Returns every item which has a declaration or reference, along with each location it is
+    /// declared or referred to at.
+    pub fn reference_locations(&self) -> impl Iterator<Item = (ReferenceId, Location)> + '_ {
+        let declarations = self
+            .named_items()
+            .filter_map(|id| self.declaration_location(&id).map(|location| (id, location)));
+        let references = self.references.iter().flat_map(|(id, locations)| {
+            locations.iter().map(move |location| (id.clone(), *location))
+        });
+        declarations.chain(references)
+    }
+
+    pub fn add_type_parameter_location(&mut self, location: Location) {
+        self.type_parameter_locations.push(location);
+    }
+
+    /// Returns every location a generic type parameter is declared or used in a type at.
+    pub fn type_parameter_locations(&self) -> &[Location] {
+        &self.type_parameter_locations
+    }
+
+    /// Returns the name `id` is declared with.
+    pub fn reference_name(&self, id: &ReferenceId) -> String {
+        match id {
+            ReferenceId::Definition(definition_id) => {
+                self.definition_name(*definition_id).to_owned()
+            }
+            ReferenceId::Struct(struct_id) => self.get_struct(*struct_id).borrow().name.to_string(),
+            ReferenceId::StructField(_, field_name) => field_name.clone(),
+        }
+    }
+
+    /// Finds an item named `new_name` which would clash with `id` if `id` were renamed to
+    /// `new_name`, returning where that item is declared or used.
+    ///
+    /// A field clashes with another field of the same struct. Other items clash with items
+    /// declared in the same file if both are top-level items, and otherwise with any item
+    /// used between the first and last mention of `id` in the file it is declared in.
+    pub fn find_rename_conflict(&self, id: &ReferenceId, new_name: &str) -> Option<Location> {
+        if let ReferenceId::StructField(struct_id, _) = id {
+            let field = ReferenceId::StructField(*struct_id, new_name.to_owned());
+            return self.declaration_location(&field);
+        }
+
+        let declaration = self.declaration_location(id)?;
+        let mentions = |id: &ReferenceId| {
+            let declaration = self.declaration_location(id);
+            declaration.into_iter().chain(self.references(id).iter().copied())
+        };
+        let in_file = |location: &Location| location.file == declaration.file;
+        let start = mentions(id).filter(in_file).map(|location| location.span.start()).min()?;
+        let end = mentions(id).filter(in_file).map(|location| location.span.end()).max()?;
+
+        let is_top_level = |id: &ReferenceId| match id {
+            ReferenceId::Definition(definition_id) => matches!(
+                self.definition(*definition_id).kind,
+                DefinitionKind::Function(_) | DefinitionKind::Global(_)
+            ),
+            ReferenceId::Struct(_) => true,
+            ReferenceId::StructField(..) => false,
+        };
+
+        self.named_items()
+            .filter(|other| {
+                !matches!(other, ReferenceId::StructField(..))
+                    && other != id
+                    && self.reference_name(other) == new_name
+            })
+            .find_map(|other| {
+                let other_declaration = self.declaration_location(&other)?;
+                if is_top_level(id) && is_top_level(&other) && in_file(&other_declaration) {
+                    return Some(other_declaration);
+                }
+                mentions(&other).find(|location| {
+                    in_file(location)
+                        && start <= location.span.start()
+                        && location.span.end() <= end
+                })
+            })
+    }
+
+    /// Returns every definition, struct and struct field.
+    fn named_items(&self) -> impl Iterator<Item = ReferenceId> + '_ {
+        let definitions =
+            (0..self.definitions.len()).map(|index| ReferenceId::Definition(DefinitionId(index)));
+        let structs = self.structs.iter().flat_map(|(struct_id, struct_type)| {
+            let fields = struct_type.borrow().field_names();
+            std::iter::once(ReferenceId::Struct(*struct_id)).chain(
+                fields
+                    .into_iter()
+                    .map(|field| ReferenceId::StructField(*struct_id, field.0.contents)),
+            )
+        });
+        definitions.chain(structs)
+    }
+
+    /// Returns every `let` statement, including those of globals.
+    pub fn let_statements(&self) -> impl Iterator<Item = &HirLetStatement> + '_ {
+        self.nodes.iter().filter_map(|(_, node)| match node {
+            Node::Statement(HirStatement::Let(let_stmt)) => Some(let_stmt),
+            _ => None,
+        })
+    }
+
     /// Replaces the HirExpression at the given ExprId with a new HirExpression
     pub fn replace_expr(&mut self, id: &ExprId, new: HirExpression) {
         let old = self.nodes.get_mut(id.into()).unwrap();
@@ -979,21 +1363,32 @@ impl NodeInterner {
     /// constraint, but when where clauses are involved, the failing constraint may be several
     /// constraints deep. In this case, all of the constraints are returned, starting with the
     /// failing one.
+    ///
+    /// If `trait_generics` is non-empty, the impl found must also implement the trait with
+    /// these generics, e.g. `Serialize<2>` rather than `Serialize<3>`.
     pub fn lookup_trait_implementation(
         &self,
         object_type: &Type,
         trait_id: TraitId,
+        trait_generics: &[Type],
     ) -> Result<TraitImplKind, Vec<TraitConstraint>> {
-        self.lookup_trait_implementation_helper(object_type, trait_id, IMPL_SEARCH_RECURSION_LIMIT)
+        self.lookup_trait_implementation_helper(
+            object_type,
+            trait_id,
+            trait_generics,
+            IMPL_SEARCH_RECURSION_LIMIT,
+        )
     }
 
     fn lookup_trait_implementation_helper(
         &self,
         object_type: &Type,
         trait_id: TraitId,
+        trait_generics: &[Type],
         recursion_limit: u32,
     ) -> Result<TraitImplKind, Vec<TraitConstraint>> {
-        let make_constraint = || TraitConstraint::new(object_type.clone(), trait_id);
+        let make_constraint =
+            || TraitConstraint::new(object_type.clone(), trait_id, trait_generics.to_vec());
 
         // Prevent infinite recursion when looking for impls
         if recursion_limit == 0 {
@@ -1003,10 +1398,24 @@ impl NodeInterner {
         let impls =
             self.trait_implementation_map.get(&trait_id).ok_or_else(|| vec![make_constraint()])?;
 
+        let requested_type = Self::with_trait_generics(object_type, trait_generics);
+
         for (existing_object_type, impl_kind) in impls {
             let (existing_object_type, type_bindings) = existing_object_type.instantiate(self);
 
-            if object_type.try_unify(&existing_object_type).is_ok() {
+            let existing_trait_generics = match impl_kind {
+                _ if trait_generics.is_empty() => Vec::new(),
+                TraitImplKind::Normal(impl_id) => {
+                    let trait_impl = self.get_trait_implementation(*impl_id);
+                    let trait_impl = trait_impl.borrow();
+                    vecmap(&trait_impl.trait_generics, |generic| generic.substitute(&type_bindings))
+                }
+                TraitImplKind::Assumed { trait_generics, .. } => trait_generics.clone(),
+            };
+            let existing_type =
+                Self::with_trait_generics(&existing_object_type, &existing_trait_generics);
+
+            if requested_type.try_unify(&existing_type).is_ok() {
                 if let TraitImplKind::Normal(impl_id) = impl_kind {
                     let trait_impl = self.get_trait_implementation(*impl_id);
                     let trait_impl = trait_impl.borrow();
@@ -1028,6 +1437,18 @@ impl NodeInterner {
         Err(vec![make_constraint()])
     }
 
+    /// Pairs an object type with the trait generics it is implemented for so both can
+    /// be unified against an existing impl at once.
+    fn with_trait_generics(object_type: &Type, trait_generics: &[Type]) -> Type {
+        if trait_generics.is_empty() {
+            object_type.clone()
+        } else {
+            let mut types = vec![object_type.clone()];
+            types.extend(trait_generics.iter().cloned());
+            Type::Tuple(types)
+        }
+    }
+
     /// Verifies that each constraint in the given where clause is valid.
     /// If an impl cannot be found for any constraint, the erroring constraint is returned.
     fn validate_where_clause(
@@ -1038,9 +1459,12 @@ impl NodeInterner {
     ) -> Result<(), Vec<TraitConstraint>> {
         for constraint in where_clause {
             let constraint_type = constraint.typ.substitute(type_bindings);
+            let trait_generics =
+                vecmap(&constraint.trait_generics, |generic| generic.substitute(type_bindings));
             self.lookup_trait_implementation_helper(
                 &constraint_type,
                 constraint.trait_id,
+                &trait_generics,
                 recursion_limit - 1,
             )?;
         }
@@ -1059,14 +1483,16 @@ impl NodeInterner {
         &mut self,
         object_type: Type,
         trait_id: TraitId,
+        trait_generics: Vec<Type>,
     ) -> bool {
         // Make sure there are no overlapping impls
-        if self.lookup_trait_implementation(&object_type, trait_id).is_ok() {
+        if self.lookup_trait_implementation(&object_type, trait_id, &trait_generics).is_ok() {
             return false;
         }
 
         let entries = self.trait_implementation_map.entry(trait_id).or_default();
-        entries.push((object_type.clone(), TraitImplKind::Assumed { object_type }));
+        let kind = TraitImplKind::Assumed { object_type: object_type.clone(), trait_generics };
+        entries.push((object_type, kind));
         true
     }
 
@@ -1086,12 +1512,26 @@ impl NodeInterner {
         // It should never happen since impls are defined at global scope, but even
         // if they were, we should never prevent defining a new impl because a where
         // clause already assumes it exists.
-        let (instantiated_object_type, substitutions) =
+        let (instantiated_object_type, mut substitutions) =
             object_type.instantiate_type_variables(self);
 
-        if let Ok(TraitImplKind::Normal(existing)) =
-            self.lookup_trait_implementation(&instantiated_object_type, trait_id)
-        {
+        // Generics only used by the trait, such as `N` in `impl<N> Serialize<N> for Foo`,
+        // must be instantiated and generalized along with those of the object type.
+        for generic in &trait_impl.borrow().trait_generics {
+            let (_, generic_substitutions) = generic.instantiate_type_variables(self);
+            for (id, substitution) in generic_substitutions {
+                substitutions.entry(id).or_insert(substitution);
+            }
+        }
+        let instantiated_trait_generics = vecmap(&trait_impl.borrow().trait_generics, |generic| {
+            generic.substitute(&substitutions)
+        });
+
+        if let Ok(TraitImplKind::Normal(existing)) = self.lookup_trait_implementation(
+            &instantiated_object_type,
+            trait_id,
+            &instantiated_trait_generics,
+        ) {
             let existing_impl = self.get_trait_implementation(existing);
             let existing_impl = existing_impl.borrow();
             return Err((existing_impl.ident.span(), existing_impl.file));
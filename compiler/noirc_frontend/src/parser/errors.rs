@@ -5,6 +5,7 @@ use small_ord_set::SmallOrdSet;
 use thiserror::Error;
 
 use iter_extended::vecmap;
+use noirc_errors::error_codes as codes;
 use noirc_errors::CustomDiagnostic as Diagnostic;
 use noirc_errors::Span;
 
@@ -18,12 +19,12 @@ pub enum ParserErrorReason {
     ExpectedPatternButFoundType(Token),
     #[error("Expected a ; separating these two statements")]
     MissingSeparatingSemi,
+    #[error("Expected a , separating these two match arms")]
+    MissingSeparatingComma,
     #[error("constrain keyword is deprecated")]
     ConstrainDeprecated,
     #[error("Expression is invalid in an array-length type: '{0}'. Only unsigned integer constants, globals, generics, +, -, *, /, and % may be used in this context.")]
     InvalidArrayLengthExpression(Expression),
-    #[error("Early 'return' is unsupported")]
-    EarlyReturn,
     #[error("Patterns aren't allowed in a trait's function declarations")]
     PatternInTraitFunctionParameter,
     #[error("comptime keyword is deprecated")]
@@ -34,10 +35,14 @@ pub enum ParserErrorReason {
         "Multiple primary attributes found. Only one function attribute is allowed per function"
     )]
     MultipleFunctionAttributesFound,
-    #[error("A function attribute cannot be placed on a struct")]
+    #[error("A function attribute cannot be placed on a struct or enum")]
     NoFunctionAttributesAllowedOnStruct,
-    #[error("Assert statements can only accept string literals")]
+    #[error("A function attribute cannot be placed on a module")]
+    NoFunctionAttributesAllowedOnModule,
+    #[error("Assert statements can only accept string or format string literals")]
     AssertMessageNotString,
+    #[error("The only attribute allowed on a loop is #[max_iterations(N)]")]
+    InvalidLoopAttribute,
     #[error("{0}")]
     Lexer(LexerErrorKind),
 }
@@ -101,6 +106,13 @@ impl ParserError {
     pub fn is_warning(&self) -> bool {
         matches!(self.reason(), Some(ParserErrorReason::ExperimentalFeature(_)))
     }
+
+    /// Returns the stable error code of this error.
+    ///
+    /// Errors without a reason are reported when an unexpected token is found.
+    pub fn code(&self) -> &'static str {
+        self.reason().map_or(codes::E0100, ParserErrorReason::code)
+    }
 }
 
 impl std::fmt::Display for ParserError {
@@ -128,9 +140,33 @@ impl std::fmt::Display for ParserError {
     }
 }
 
+impl ParserErrorReason {
+    /// Returns the stable error code of this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParserErrorReason::ExpectedFieldName(_) => codes::E0101,
+            ParserErrorReason::ExpectedPatternButFoundType(_) => codes::E0102,
+            ParserErrorReason::MissingSeparatingSemi => codes::E0103,
+            ParserErrorReason::MissingSeparatingComma => codes::E0104,
+            ParserErrorReason::ConstrainDeprecated => codes::E0105,
+            ParserErrorReason::InvalidArrayLengthExpression(_) => codes::E0106,
+            ParserErrorReason::PatternInTraitFunctionParameter => codes::E0107,
+            ParserErrorReason::ComptimeDeprecated => codes::E0108,
+            ParserErrorReason::ExperimentalFeature(_) => codes::E0109,
+            ParserErrorReason::MultipleFunctionAttributesFound => codes::E0110,
+            ParserErrorReason::NoFunctionAttributesAllowedOnStruct => codes::E0111,
+            ParserErrorReason::NoFunctionAttributesAllowedOnModule => codes::E0112,
+            ParserErrorReason::AssertMessageNotString => codes::E0113,
+            ParserErrorReason::InvalidLoopAttribute => codes::E0114,
+            ParserErrorReason::Lexer(error) => error.code(),
+        }
+    }
+}
+
 impl From<ParserError> for Diagnostic {
     fn from(error: ParserError) -> Diagnostic {
-        match error.reason {
+        let code = error.code();
+        let diagnostic = match error.reason {
             Some(reason) => {
                 match reason {
                     ParserErrorReason::ConstrainDeprecated => Diagnostic::simple_error(
@@ -161,7 +197,8 @@ impl From<ParserError> for Diagnostic {
                 let primary = error.to_string();
                 Diagnostic::simple_error(primary, String::new(), error.span)
             }
-        }
+        };
+        diagnostic.with_code(code)
     }
 }
 
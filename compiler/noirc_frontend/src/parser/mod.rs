@@ -11,8 +11,9 @@ mod labels;
 #[allow(clippy::module_inception)]
 mod parser;
 
-use crate::token::{Keyword, Token};
-use crate::{ast::ImportStatement, Expression, NoirStruct};
+use crate::lexer::Lexer;
+use crate::token::{DocStyle, Keyword, SecondaryAttribute, Token};
+use crate::{ast::ImportStatement, Expression, NoirEnum, NoirStruct};
 use crate::{
     Ident, LetStatement, NoirFunction, NoirTrait, NoirTraitImpl, NoirTypeAlias, Recoverable,
     StatementKind, TypeImpl, UseTree,
@@ -25,12 +26,57 @@ pub use errors::ParserErrorReason;
 use noirc_errors::Span;
 pub use parser::parse_program;
 
+/// A comment in a source file. Comments are skipped by the parser so they are not part of the
+/// Ast, but tools which reproduce the source, such as formatters, need to know where they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    /// The text of the comment, without its `//` or `/* */` delimiters or doc comment marker
+    pub text: String,
+    pub kind: CommentKind,
+    /// Whether this is an outer (`///`, `/**`) or inner (`//!`, `/*!`) doc comment
+    pub doc_style: Option<DocStyle>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    Line,
+    Block,
+}
+
+/// Parses a source file like [`parse_program`], also returning the comments in it, in the
+/// order they appear.
+pub fn parse_program_with_comments(
+    source_program: &str,
+) -> (ParsedModule, Vec<Comment>, Vec<ParserError>) {
+    let (module, errors) = parse_program(source_program);
+    (module, comments(source_program), errors)
+}
+
+/// Returns the comments in a source file. Any lexing errors are reported by the parser instead.
+pub fn comments(source_program: &str) -> Vec<Comment> {
+    Lexer::new(source_program)
+        .skip_comments(false)
+        .filter_map(Result::ok)
+        .filter_map(|token| {
+            let span = token.to_span();
+            let (text, kind, doc_style) = match token.into_token() {
+                Token::LineComment(text, doc_style) => (text, CommentKind::Line, doc_style),
+                Token::BlockComment(text, doc_style) => (text, CommentKind::Block, doc_style),
+                _ => return None,
+            };
+            Some(Comment { text, kind, doc_style, span })
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum TopLevelStatement {
     Function(NoirFunction),
-    Module(Ident),
+    Module(ModuleDeclaration),
     Import(UseTree),
     Struct(NoirStruct),
+    Enum(NoirEnum),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -213,6 +259,7 @@ pub struct SortedModule {
     pub imports: Vec<ImportStatement>,
     pub functions: Vec<NoirFunction>,
     pub types: Vec<NoirStruct>,
+    pub enums: Vec<NoirEnum>,
     pub traits: Vec<NoirTrait>,
     pub trait_impls: Vec<NoirTraitImpl>,
     pub impls: Vec<TypeImpl>,
@@ -220,7 +267,7 @@ pub struct SortedModule {
     pub globals: Vec<LetStatement>,
 
     /// Module declarations like `mod foo;`
-    pub module_decls: Vec<Ident>,
+    pub module_decls: Vec<ModuleDeclaration>,
 
     /// Full submodules as in `mod foo { ... definitions ... }`
     pub submodules: Vec<SortedSubModule>,
@@ -229,7 +276,7 @@ pub struct SortedModule {
 impl std::fmt::Display for SortedModule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for decl in &self.module_decls {
-            writeln!(f, "mod {decl};")?;
+            writeln!(f, "{decl}")?;
         }
 
         for import in &self.imports {
@@ -244,6 +291,10 @@ impl std::fmt::Display for SortedModule {
             write!(f, "{type_}")?;
         }
 
+        for enum_ in &self.enums {
+            write!(f, "{enum_}")?;
+        }
+
         for function in &self.functions {
             write!(f, "{function}")?;
         }
@@ -276,9 +327,10 @@ impl ParsedModule {
 
         for item in self.items {
             match item.kind {
-                ItemKind::Import(import) => module.push_import(import),
+                ItemKind::Import(import) => module.push_import(import, item.span),
                 ItemKind::Function(func) => module.push_function(func),
                 ItemKind::Struct(typ) => module.push_type(typ),
+                ItemKind::Enum(typ) => module.push_enum(typ),
                 ItemKind::Trait(noir_trait) => module.push_trait(noir_trait),
                 ItemKind::TraitImpl(trait_impl) => module.push_trait_impl(trait_impl),
                 ItemKind::Impl(r#impl) => module.push_impl(r#impl),
@@ -304,15 +356,32 @@ pub enum ItemKind {
     Import(UseTree),
     Function(NoirFunction),
     Struct(NoirStruct),
+    Enum(NoirEnum),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
     TypeAlias(NoirTypeAlias),
     Global(LetStatement),
-    ModuleDecl(Ident),
+    ModuleDecl(ModuleDeclaration),
     Submodules(ParsedSubModule),
 }
 
+/// A module declared via `mod name;` whose contents are in another file
+#[derive(Clone, Debug)]
+pub struct ModuleDeclaration {
+    pub ident: Ident,
+    pub attributes: Vec<SecondaryAttribute>,
+}
+
+impl std::fmt::Display for ModuleDeclaration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for attribute in &self.attributes {
+            writeln!(f, "{attribute}")?;
+        }
+        write!(f, "mod {};", self.ident)
+    }
+}
+
 /// A submodule defined via `mod name { contents }` in some larger file.
 /// These submodules always share the same file as some larger ParsedModule
 #[derive(Clone, Debug)]
@@ -320,6 +389,8 @@ pub struct ParsedSubModule {
     pub name: Ident,
     pub contents: ParsedModule,
     pub is_contract: bool,
+    pub attributes: Vec<SecondaryAttribute>,
+    pub span: Span,
 }
 
 impl ParsedSubModule {
@@ -328,12 +399,17 @@ impl ParsedSubModule {
             name: self.name,
             contents: self.contents.into_sorted(),
             is_contract: self.is_contract,
+            attributes: self.attributes,
+            span: self.span,
         }
     }
 }
 
 impl std::fmt::Display for SortedSubModule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for attribute in &self.attributes {
+            writeln!(f, "{attribute}")?;
+        }
         write!(f, "mod {} {{", self.name)?;
 
         for line in self.contents.to_string().lines() {
@@ -348,6 +424,8 @@ pub struct SortedSubModule {
     pub name: Ident,
     pub contents: SortedModule,
     pub is_contract: bool,
+    pub attributes: Vec<SecondaryAttribute>,
+    pub span: Span,
 }
 
 impl SortedModule {
@@ -359,6 +437,10 @@ impl SortedModule {
         self.types.push(typ);
     }
 
+    fn push_enum(&mut self, typ: NoirEnum) {
+        self.enums.push(typ);
+    }
+
     fn push_trait(&mut self, noir_trait: NoirTrait) {
         self.traits.push(noir_trait);
     }
@@ -375,12 +457,15 @@ impl SortedModule {
         self.type_aliases.push(type_alias);
     }
 
-    fn push_import(&mut self, import_stmt: UseTree) {
-        self.imports.extend(import_stmt.desugar(None));
+    fn push_import(&mut self, import_stmt: UseTree, span: Span) {
+        let imports = import_stmt.desugar(None);
+        self.imports.extend(
+            imports.into_iter().map(|import| ImportStatement { span: Some(span), ..import }),
+        );
     }
 
-    fn push_module_decl(&mut self, mod_name: Ident) {
-        self.module_decls.push(mod_name);
+    fn push_module_decl(&mut self, mod_decl: ModuleDeclaration) {
+        self.module_decls.push(mod_decl);
     }
 
     fn push_submodule(&mut self, submodule: SortedSubModule) {
@@ -423,8 +508,11 @@ impl Precedence {
             Token::ShiftRight => Precedence::Shift,
             Token::Plus => Precedence::Sum,
             Token::Minus => Precedence::Sum,
+            Token::WrappingPlus => Precedence::Sum,
+            Token::WrappingMinus => Precedence::Sum,
             Token::Slash => Precedence::Product,
             Token::Star => Precedence::Product,
+            Token::WrappingStar => Precedence::Product,
             Token::Percent => Precedence::Product,
             _ => return None,
         };
@@ -478,6 +566,7 @@ impl std::fmt::Display for TopLevelStatement {
             TopLevelStatement::Trait(t) => t.fmt(f),
             TopLevelStatement::TraitImpl(i) => i.fmt(f),
             TopLevelStatement::Struct(s) => s.fmt(f),
+            TopLevelStatement::Enum(e) => e.fmt(f),
             TopLevelStatement::Impl(i) => i.fmt(f),
             TopLevelStatement::TypeAlias(t) => t.fmt(f),
             TopLevelStatement::SubModule(s) => s.fmt(f),
@@ -26,8 +26,8 @@
 use super::{
     foldl_with_span, labels::ParsingRuleLabel, parameter_name_recovery, parameter_recovery,
     parenthesized, then_commit, then_commit_ignore, top_level_statement_recovery, ExprParser,
-    NoirParser, ParsedModule, ParsedSubModule, ParserError, ParserErrorReason, Precedence,
-    TopLevelStatement,
+    ModuleDeclaration, NoirParser, ParsedModule, ParsedSubModule, ParserError, ParserErrorReason,
+    Precedence, TopLevelStatement,
 };
 use super::{spanned, Item, ItemKind};
 use crate::ast::{
@@ -39,10 +39,11 @@ use crate::token::{Attribute, Attributes, Keyword, SecondaryAttribute, Token, To
 use crate::{
     BinaryOp, BinaryOpKind, BlockExpression, ConstrainKind, ConstrainStatement, Distinctness,
     ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, FunctionVisibility, Ident,
-    IfExpression, InfixExpression, LValue, Lambda, Literal, NoirFunction, NoirStruct, NoirTrait,
-    NoirTraitImpl, NoirTypeAlias, Param, Path, PathKind, Pattern, Recoverable, Statement,
-    TraitBound, TraitImplItem, TraitItem, TypeImpl, UnaryOp, UnresolvedTraitConstraint,
-    UnresolvedTypeExpression, UseTree, UseTreeKind, Visibility,
+    IfExpression, InfixExpression, LValue, Lambda, Literal, LoopStatement, MatchArm,
+    MatchExpression, MatchPattern, NoirEnum, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl,
+    NoirTypeAlias, Param, Path, PathKind, Pattern, Recoverable, Statement, TraitBound,
+    TraitImplItem, TraitItem, TypeImpl, UnaryOp, UnresolvedTraitConstraint,
+    UnresolvedTypeExpression, UseTree, UseTreeKind, Visibility, WhileStatement,
 };
 
 use chumsky::prelude::*;
@@ -84,6 +85,7 @@ fn module() -> impl NoirParser<ParsedModule> {
                     TopLevelStatement::Module(m) => push_item(ItemKind::ModuleDecl(m)),
                     TopLevelStatement::Import(i) => push_item(ItemKind::Import(i)),
                     TopLevelStatement::Struct(s) => push_item(ItemKind::Struct(s)),
+                    TopLevelStatement::Enum(e) => push_item(ItemKind::Enum(e)),
                     TopLevelStatement::Trait(t) => push_item(ItemKind::Trait(t)),
                     TopLevelStatement::TraitImpl(t) => push_item(ItemKind::TraitImpl(t)),
                     TopLevelStatement::Impl(i) => push_item(ItemKind::Impl(i)),
@@ -99,6 +101,7 @@ fn module() -> impl NoirParser<ParsedModule> {
 
 /// top_level_statement: function_definition
 ///                    | struct_definition
+///                    | enum_definition
 ///                    | trait_definition
 ///                    | implementation
 ///                    | submodule
@@ -111,6 +114,7 @@ fn top_level_statement(
     choice((
         function_definition(false).map(TopLevelStatement::Function),
         struct_definition(),
+        enum_definition(),
         trait_definition(),
         trait_implementation(),
         implementation(),
@@ -124,7 +128,7 @@ fn top_level_statement(
     .recover_via(top_level_statement_recovery())
 }
 
-/// global_declaration: 'global' ident global_type_annotation '=' literal
+/// global_declaration: 'global' ident global_type_annotation '=' expression
 fn global_declaration() -> impl NoirParser<TopLevelStatement> {
     let p = ignore_then_commit(
         keyword(Keyword::Global).labelled(ParsingRuleLabel::Global),
@@ -132,31 +136,49 @@ fn global_declaration() -> impl NoirParser<TopLevelStatement> {
     );
     let p = then_commit(p, optional_type_annotation());
     let p = then_commit_ignore(p, just(Token::Assign));
-    let p = then_commit(p, literal_or_collection(expression()).map_with_span(Expression::new));
+    let p = then_commit(p, expression());
     p.map(LetStatement::new_let).map(TopLevelStatement::Global)
 }
 
-/// submodule: 'mod' ident '{' module '}'
+/// submodule: attributes? 'mod' ident '{' module '}'
 fn submodule(module_parser: impl NoirParser<ParsedModule>) -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Mod)
-        .ignore_then(ident())
+    attributes()
+        .or_not()
+        .then_ignore(keyword(Keyword::Mod))
+        .then(ident())
         .then_ignore(just(Token::LeftBrace))
         .then(module_parser)
         .then_ignore(just(Token::RightBrace))
-        .map(|(name, contents)| {
-            TopLevelStatement::SubModule(ParsedSubModule { name, contents, is_contract: false })
+        .validate(|((attributes, name), contents), span, emit| {
+            let attributes = validate_module_attributes(attributes, span, emit);
+            TopLevelStatement::SubModule(ParsedSubModule {
+                name,
+                contents,
+                is_contract: false,
+                attributes,
+                span,
+            })
         })
 }
 
-/// contract: 'contract' ident '{' module '}'
+/// contract: attributes? 'contract' ident '{' module '}'
 fn contract(module_parser: impl NoirParser<ParsedModule>) -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Contract)
-        .ignore_then(ident())
+    attributes()
+        .or_not()
+        .then_ignore(keyword(Keyword::Contract))
+        .then(ident())
         .then_ignore(just(Token::LeftBrace))
         .then(module_parser)
         .then_ignore(just(Token::RightBrace))
-        .map(|(name, contents)| {
-            TopLevelStatement::SubModule(ParsedSubModule { name, contents, is_contract: true })
+        .validate(|((attributes, name), contents), span, emit| {
+            let attributes = validate_module_attributes(attributes, span, emit);
+            TopLevelStatement::SubModule(ParsedSubModule {
+                name,
+                contents,
+                is_contract: true,
+                attributes,
+                span,
+            })
         })
 }
 
@@ -185,6 +207,7 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
                 is_unconstrained: modifiers.0,
                 is_open: modifiers.2,
                 is_internal: modifiers.3,
+                is_comptime: modifiers.5,
                 visibility: if modifiers.1 {
                     FunctionVisibility::PublicCrate
                 } else if modifiers.4 {
@@ -204,23 +227,25 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
         })
 }
 
-/// function_modifiers: 'unconstrained'? 'pub(crate)'? 'pub'? 'open'? 'internal'?
+/// function_modifiers: 'unconstrained'? 'pub(crate)'? 'pub'? 'open'? 'internal'? 'comptime'?
 ///
-/// returns (is_unconstrained, is_pub_crate, is_open, is_internal, is_pub) for whether each keyword was present
-fn function_modifiers() -> impl NoirParser<(bool, bool, bool, bool, bool)> {
+/// returns (is_unconstrained, is_pub_crate, is_open, is_internal, is_pub, is_comptime) for whether each keyword was present
+fn function_modifiers() -> impl NoirParser<(bool, bool, bool, bool, bool, bool)> {
     keyword(Keyword::Unconstrained)
         .or_not()
         .then(is_pub_crate())
         .then(keyword(Keyword::Pub).or_not())
         .then(keyword(Keyword::Open).or_not())
         .then(keyword(Keyword::Internal).or_not())
-        .map(|((((unconstrained, pub_crate), public), open), internal)| {
+        .then(keyword(Keyword::CompTime).or_not())
+        .map(|(((((unconstrained, pub_crate), public), open), internal), comptime)| {
             (
                 unconstrained.is_some(),
                 pub_crate,
                 open.is_some(),
                 internal.is_some(),
                 public.is_some(),
+                comptime.is_some(),
             )
         })
 }
@@ -274,6 +299,44 @@ fn struct_definition() -> impl NoirParser<TopLevelStatement> {
         })
 }
 
+/// enum_definition: attributes 'enum' ident generics '{' enum_variants '}'
+///
+/// enum_variants: ident ( '(' type, ... ')' )? , ...
+fn enum_definition() -> impl NoirParser<TopLevelStatement> {
+    use self::Keyword::Enum;
+    use Token::*;
+
+    let variant_fields = parse_type()
+        .separated_by(just(Comma))
+        .allow_trailing()
+        .delimited_by(just(LeftParen), just(RightParen))
+        .or_not()
+        .map(Option::unwrap_or_default);
+
+    let variants = ident()
+        .then(variant_fields)
+        .separated_by(just(Comma))
+        .allow_trailing()
+        .delimited_by(just(LeftBrace), just(RightBrace))
+        .recover_with(nested_delimiters(
+            LeftBrace,
+            RightBrace,
+            [(LeftParen, RightParen), (LeftBracket, RightBracket)],
+            |_| vec![],
+        ));
+
+    attributes()
+        .or_not()
+        .then_ignore(keyword(Enum))
+        .then(ident())
+        .then(generics())
+        .then(variants)
+        .validate(|(((raw_attributes, name), generics), variants), span, emit| {
+            let attributes = validate_struct_attributes(raw_attributes, span, emit);
+            TopLevelStatement::Enum(NoirEnum { name, attributes, generics, variants, span })
+        })
+}
+
 fn type_alias_definition() -> impl NoirParser<TopLevelStatement> {
     use self::Keyword::Type;
 
@@ -297,7 +360,7 @@ fn lambda_return_type() -> impl NoirParser<UnresolvedType> {
 fn function_return_type() -> impl NoirParser<((Distinctness, Visibility), FunctionReturnType)> {
     just(Token::Arrow)
         .ignore_then(optional_distinctness())
-        .then(optional_visibility())
+        .then(optional_return_visibility())
         .then(spanned(parse_type()))
         .or_not()
         .map_with_span(|ret, span| match ret {
@@ -348,7 +411,7 @@ fn function_parameters<'a>(allow_self: bool) -> impl NoirParser<Vec<Param>> + 'a
     let full_parameter = pattern()
         .recover_via(parameter_name_recovery())
         .then_ignore(just(Token::Colon))
-        .then(optional_visibility())
+        .then(optional_parameter_visibility())
         .then(typ)
         .map_with_span(|((pattern, visibility), typ), span| Param {
             visibility,
@@ -512,6 +575,28 @@ fn validate_struct_attributes(
     struct_attributes
 }
 
+fn validate_module_attributes(
+    attributes: Option<Vec<Attribute>>,
+    span: Span,
+    emit: &mut dyn FnMut(ParserError),
+) -> Vec<SecondaryAttribute> {
+    let mut module_attributes = vec![];
+
+    for attribute in attributes.unwrap_or_default() {
+        match attribute {
+            Attribute::Function(..) => {
+                emit(ParserError::with_reason(
+                    ParserErrorReason::NoFunctionAttributesAllowedOnModule,
+                    span,
+                ));
+            }
+            Attribute::Secondary(attr) => module_attributes.push(attr),
+        }
+    }
+
+    module_attributes
+}
+
 /// Function declaration parameters differ from other parameters in that parameter
 /// patterns are not allowed in declarations. All parameters must be identifiers.
 fn function_declaration_parameters() -> impl NoirParser<Vec<(Ident, UnresolvedType)>> {
@@ -552,16 +637,23 @@ fn trait_type_declaration() -> impl NoirParser<TraitItem> {
 
 /// Parses a non-trait implementation, adding a set of methods to a type.
 ///
-/// implementation: 'impl' generics type '{' function_definition ... '}'
+/// implementation: 'impl' generics type where_clause '{' function_definition ... '}'
 fn implementation() -> impl NoirParser<TopLevelStatement> {
     keyword(Keyword::Impl)
         .ignore_then(generics())
         .then(parse_type().map_with_span(|typ, span| (typ, span)))
+        .then(where_clause())
         .then_ignore(just(Token::LeftBrace))
         .then(function_definition(true).repeated())
         .then_ignore(just(Token::RightBrace))
-        .map(|((generics, (object_type, type_span)), methods)| {
-            TopLevelStatement::Impl(TypeImpl { generics, object_type, type_span, methods })
+        .map(|(((generics, (object_type, type_span)), where_clause), methods)| {
+            TopLevelStatement::Impl(TypeImpl {
+                generics,
+                object_type,
+                type_span,
+                where_clause,
+                methods,
+            })
         })
 }
 
@@ -624,7 +716,7 @@ fn where_clause() -> impl NoirParser<Vec<UnresolvedTraitConstraint>> {
     );
 
     keyword(Keyword::Where)
-        .ignore_then(constraints.separated_by(just(Token::Comma)))
+        .ignore_then(constraints.separated_by(just(Token::Comma)).allow_trailing())
         .or_not()
         .map(|option| option.unwrap_or_default())
         .map(|x: Vec<MultiTraitConstraint>| {
@@ -659,6 +751,18 @@ fn block_expr<'a>(
     block(statement).map(ExpressionKind::Block).map_with_span(Expression::new)
 }
 
+fn comptime_expr<'a>(
+    statement: impl NoirParser<StatementKind> + 'a,
+) -> impl NoirParser<ExpressionKind> + 'a {
+    keyword(Keyword::CompTime).ignore_then(block(statement)).map(ExpressionKind::Comptime)
+}
+
+fn unconstrained_expr<'a>(
+    statement: impl NoirParser<StatementKind> + 'a,
+) -> impl NoirParser<ExpressionKind> + 'a {
+    keyword(Keyword::Unconstrained).ignore_then(block(statement)).map(ExpressionKind::Unconstrained)
+}
+
 fn block<'a>(
     statement: impl NoirParser<StatementKind> + 'a,
 ) -> impl NoirParser<BlockExpression> + 'a {
@@ -699,7 +803,12 @@ fn optional_type_annotation<'a>() -> impl NoirParser<UnresolvedType> + 'a {
 }
 
 fn module_declaration() -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Mod).ignore_then(ident()).map(TopLevelStatement::Module)
+    attributes().or_not().then_ignore(keyword(Keyword::Mod)).then(ident()).validate(
+        |(attributes, ident), span, emit| {
+            let attributes = validate_module_attributes(attributes, span, emit);
+            TopLevelStatement::Module(ModuleDeclaration { ident, attributes })
+        },
+    )
 }
 
 fn use_statement() -> impl NoirParser<TopLevelStatement> {
@@ -767,7 +876,12 @@ fn use_tree() -> impl NoirParser<UseTree> {
             prefix.then(tree).map(|(prefix, kind)| UseTree { prefix, kind })
         };
 
-        choice((list, simple))
+        let glob = path()
+            .then_ignore(just(Token::DoubleColon))
+            .then_ignore(just(Token::Star))
+            .map(|prefix| UseTree { prefix, kind: UseTreeKind::Glob });
+
+        choice((list, glob, simple))
     })
 }
 
@@ -790,7 +904,11 @@ where
             assertion_eq(expr_parser.clone()),
             declaration(expr_parser.clone()),
             assignment(expr_parser.clone()),
-            for_loop(expr_no_constructors, statement),
+            for_loop(expr_no_constructors.clone(), statement.clone()),
+            while_loop(expr_no_constructors, statement.clone()),
+            loop_statement(statement),
+            break_statement(),
+            continue_statement(),
             return_statement(expr_parser.clone()),
             expr_parser.map(StatementKind::Expression),
         ))
@@ -827,21 +945,9 @@ where
         .labelled(ParsingRuleLabel::Statement)
         .validate(|expressions, span, emit| {
             let condition = expressions.get(0).unwrap_or(&Expression::error(span)).clone();
-            let mut message_str = None;
-
-            if let Some(message) = expressions.get(1) {
-                if let ExpressionKind::Literal(Literal::Str(message)) = &message.kind {
-                    message_str = Some(message.clone());
-                } else {
-                    emit(ParserError::with_reason(ParserErrorReason::AssertMessageNotString, span));
-                }
-            }
+            let message = assert_message(expressions.get(1), span, emit);
 
-            StatementKind::Constrain(ConstrainStatement(
-                condition,
-                message_str,
-                ConstrainKind::Assert,
-            ))
+            StatementKind::Constrain(ConstrainStatement(condition, message, ConstrainKind::Assert))
         })
 }
 
@@ -863,23 +969,33 @@ where
                 })),
                 span,
             );
-            let mut message_str = None;
+            let message = assert_message(exprs.get(2), span, emit);
 
-            if let Some(message) = exprs.get(2) {
-                if let ExpressionKind::Literal(Literal::Str(message)) = &message.kind {
-                    message_str = Some(message.clone());
-                } else {
-                    emit(ParserError::with_reason(ParserErrorReason::AssertMessageNotString, span));
-                }
-            }
             StatementKind::Constrain(ConstrainStatement(
                 predicate,
-                message_str,
+                message,
                 ConstrainKind::AssertEq,
             ))
         })
 }
 
+/// Validates the optional message argument of an `assert` or `assert_eq` statement,
+/// which must be either a string literal or a format string literal.
+fn assert_message(
+    message: Option<&Expression>,
+    span: Span,
+    emit: &mut dyn FnMut(ParserError),
+) -> Option<Expression> {
+    let message = message?;
+    match &message.kind {
+        ExpressionKind::Literal(Literal::Str(_) | Literal::FmtStr(_)) => Some(message.clone()),
+        _ => {
+            emit(ParserError::with_reason(ParserErrorReason::AssertMessageNotString, span));
+            None
+        }
+    }
+}
+
 fn declaration<'a, P>(expr_parser: P) -> impl NoirParser<StatementKind> + 'a
 where
     P: ExprParser + 'a,
@@ -915,11 +1031,15 @@ fn pattern() -> impl NoirParser<Pattern> {
         let struct_pattern_fields = long_field
             .or(short_field)
             .separated_by(just(Token::Comma))
+            .allow_trailing()
+            .then(just(Token::DoubleDot).or_not().map(|rest| rest.is_some()))
             .delimited_by(just(Token::LeftBrace), just(Token::RightBrace));
 
-        let struct_pattern = path()
-            .then(struct_pattern_fields)
-            .map_with_span(|(typename, fields), span| Pattern::Struct(typename, fields, span));
+        let struct_pattern = path().then(struct_pattern_fields).map_with_span(
+            |(typename, (fields, has_rest)), span| {
+                Pattern::Struct(typename, fields, has_rest, span)
+            },
+        );
 
         let tuple_pattern = pattern
             .separated_by(just(Token::Comma))
@@ -1022,11 +1142,20 @@ fn parse_type_inner(
     ))
 }
 
-fn optional_visibility() -> impl NoirParser<Visibility> {
-    keyword(Keyword::Pub).or_not().map(|opt| match opt {
-        Some(_) => Visibility::Public,
-        None => Visibility::Private,
-    })
+fn optional_parameter_visibility() -> impl NoirParser<Visibility> {
+    keyword(Keyword::Pub)
+        .to(Visibility::Public)
+        .or(keyword(Keyword::CallData).to(Visibility::CallData))
+        .or_not()
+        .map(|visibility| visibility.unwrap_or(Visibility::Private))
+}
+
+fn optional_return_visibility() -> impl NoirParser<Visibility> {
+    keyword(Keyword::Pub)
+        .to(Visibility::Public)
+        .or(keyword(Keyword::ReturnData).to(Visibility::ReturnData))
+        .or_not()
+        .map(|visibility| visibility.unwrap_or(Visibility::Private))
 }
 
 fn optional_distinctness() -> impl NoirParser<Distinctness> {
@@ -1237,9 +1366,8 @@ where
     P: ExprParser + 'a,
 {
     ignore_then_commit(keyword(Keyword::Return), expr_parser.or_not())
-        .validate(|_, span, emit| {
-            emit(ParserError::with_reason(ParserErrorReason::EarlyReturn, span));
-            StatementKind::Error
+        .map_with_span(|expression, span| {
+            StatementKind::Return(ReturnStatement { expression, span })
         })
         .labelled(ParsingRuleLabel::Statement)
 }
@@ -1352,7 +1480,7 @@ where
 }
 
 /// The equivalent of a 'term' for use in type expressions. Unlike regular terms, the grammar here
-/// is restricted to no longer include right-unary expressions, unary not, and most atoms.
+/// is restricted to no longer include right-unary expressions other than calls, unary not, and most atoms.
 fn type_expression_term<'a, P>(expr_parser: P) -> impl NoirParser<Expression> + 'a
 where
     P: ExprParser + 'a,
@@ -1378,6 +1506,7 @@ where
         ArrayIndex(Expression),
         Cast(UnresolvedType),
         MemberAccess((Ident, Option<Vec<Expression>>)),
+        Try,
     }
 
     // `(arg1, ..., argN)` in `my_func(arg1, ..., argN)`
@@ -1402,7 +1531,10 @@ where
         .map(UnaryRhs::MemberAccess)
         .labelled(ParsingRuleLabel::FieldAccess);
 
-    let rhs = choice((call_rhs, array_rhs, cast_rhs, member_rhs));
+    // `?` in `atom?`
+    let try_rhs = just(Token::Question).to(UnaryRhs::Try);
+
+    let rhs = choice((call_rhs, array_rhs, cast_rhs, member_rhs, try_rhs));
 
     foldl_with_span(
         atom(expr_parser, expr_no_constructors, statement, allow_constructors),
@@ -1414,6 +1546,7 @@ where
             UnaryRhs::MemberAccess(field) => {
                 Expression::member_access_or_method_call(lhs, field, span)
             }
+            UnaryRhs::Try => Expression::try_expr(lhs, span),
         },
     )
 }
@@ -1447,6 +1580,65 @@ where
     })
 }
 
+/// match_expr: 'match' expression '{' (match_pattern '=>' expression ','?)* '}'
+///
+/// The comma after an arm is optional if its body is a block.
+fn match_expr<'a, P, P2>(
+    expr_no_constructors: P,
+    expr_parser: P2,
+) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P: ExprParser + 'a,
+    P2: ExprParser + 'a,
+{
+    let arm = match_pattern()
+        .then_ignore(just(Token::FatArrow))
+        .then(expr_parser)
+        .map(|(pattern, body)| MatchArm { pattern, body });
+
+    let arms = arm
+        .then(just(Token::Comma).or_not())
+        .repeated()
+        .validate(|arms, _span, emit| {
+            let last = arms.len().saturating_sub(1);
+            vecmap(arms.into_iter().enumerate(), |(i, (arm, comma))| {
+                let is_block = matches!(arm.body.kind, ExpressionKind::Block(_));
+                if comma.is_none() && !is_block && i != last {
+                    let span = arm.body.span;
+                    emit(ParserError::with_reason(ParserErrorReason::MissingSeparatingComma, span));
+                }
+                arm
+            })
+        })
+        .delimited_by(just(Token::LeftBrace), just(Token::RightBrace));
+
+    keyword(Keyword::Match).ignore_then(expr_no_constructors).then(arms).map(
+        |(expression, arms)| ExpressionKind::Match(Box::new(MatchExpression { expression, arms })),
+    )
+}
+
+/// match_pattern: path ( '(' pattern, ... ')' )?
+///              | pattern
+///
+/// A path with a single segment and no fields, such as `x` or `_`, matches any value.
+fn match_pattern() -> impl NoirParser<MatchPattern> {
+    let fields = pattern()
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .delimited_by(just(Token::LeftParen), just(Token::RightParen));
+
+    let variant =
+        path().then(fields.or_not()).map_with_span(|(mut path, fields), span| match fields {
+            Some(fields) => MatchPattern::Variant(path, fields, span),
+            None if path.segments.len() == 1 && path.kind == PathKind::Plain => {
+                MatchPattern::Default(Pattern::Identifier(path.segments.pop().unwrap()))
+            }
+            None => MatchPattern::Variant(path, Vec::new(), span),
+        });
+
+    variant.or(pattern().map(MatchPattern::Default)).labelled(ParsingRuleLabel::Pattern)
+}
+
 fn lambda<'a>(
     expr_parser: impl NoirParser<Expression> + 'a,
 ) -> impl NoirParser<ExpressionKind> + 'a {
@@ -1464,16 +1656,75 @@ where
     P: ExprParser + 'a,
     S: NoirParser<StatementKind> + 'a,
 {
-    keyword(Keyword::For)
-        .ignore_then(ident())
+    attribute()
+        .or_not()
+        .then_ignore(keyword(Keyword::For))
+        .then(ident())
         .then_ignore(keyword(Keyword::In))
         .then(for_range(expr_no_constructors))
         .then(block_expr(statement))
-        .map_with_span(|((identifier, range), block), span| {
-            StatementKind::For(ForLoopStatement { identifier, range, block, span })
+        .validate(|(((attribute, identifier), range), block), span, emit| {
+            let max_iterations = max_iterations(attribute, span, emit);
+            StatementKind::For(ForLoopStatement { identifier, range, block, max_iterations, span })
+        })
+}
+
+fn while_loop<'a, P, S>(
+    expr_no_constructors: P,
+    statement: S,
+) -> impl NoirParser<StatementKind> + 'a
+where
+    P: ExprParser + 'a,
+    S: NoirParser<StatementKind> + 'a,
+{
+    attribute()
+        .or_not()
+        .then_ignore(keyword(Keyword::While))
+        .then(expr_no_constructors)
+        .then(block_expr(statement))
+        .validate(|((attribute, condition), block), span, emit| {
+            let max_iterations = max_iterations(attribute, span, emit);
+            StatementKind::While(WhileStatement { condition, block, max_iterations, span })
         })
 }
 
+fn loop_statement<'a, S>(statement: S) -> impl NoirParser<StatementKind> + 'a
+where
+    S: NoirParser<StatementKind> + 'a,
+{
+    attribute().or_not().then_ignore(keyword(Keyword::Loop)).then(block_expr(statement)).validate(
+        |(attribute, block), span, emit| {
+            let max_iterations = max_iterations(attribute, span, emit);
+            StatementKind::Loop(LoopStatement { block, max_iterations, span })
+        },
+    )
+}
+
+/// Extracts the bound given by a `#[max_iterations(N)]` attribute on a loop,
+/// erroring on any other attribute.
+fn max_iterations(
+    attribute: Option<Attribute>,
+    span: Span,
+    emit: &mut dyn FnMut(ParserError),
+) -> Option<u64> {
+    match attribute {
+        None => None,
+        Some(Attribute::Secondary(SecondaryAttribute::MaxIterations(count))) => Some(count),
+        Some(_) => {
+            emit(ParserError::with_reason(ParserErrorReason::InvalidLoopAttribute, span));
+            None
+        }
+    }
+}
+
+fn break_statement() -> impl NoirParser<StatementKind> {
+    keyword(Keyword::Break).map_with_span(|_, span| StatementKind::Break(span))
+}
+
+fn continue_statement() -> impl NoirParser<StatementKind> {
+    keyword(Keyword::Continue).map_with_span(|_, span| StatementKind::Continue(span))
+}
+
 /// The 'range' of a for loop. Either an actual range `start .. end` or an array expression.
 fn for_range<P>(expr_no_constructors: P) -> impl NoirParser<ForRange>
 where
@@ -1573,7 +1824,8 @@ where
     S: NoirParser<StatementKind> + 'a,
 {
     choice((
-        if_expr(expr_no_constructors, statement.clone()),
+        if_expr(expr_no_constructors.clone(), statement.clone()),
+        match_expr(expr_no_constructors, expr_parser.clone()),
         array_expr(expr_parser.clone()),
         if allow_constructors {
             constructor(expr_parser.clone()).boxed()
@@ -1581,6 +1833,8 @@ where
             nothing().boxed()
         },
         lambda(expr_parser.clone()),
+        comptime_expr(statement.clone()),
+        unconstrained_expr(statement.clone()),
         block(statement).map(ExpressionKind::Block),
         variable(),
         literal(),
@@ -1599,9 +1853,18 @@ fn type_expression_atom<'a, P>(expr_parser: P) -> impl NoirParser<Expression> +
 where
     P: ExprParser + 'a,
 {
-    variable()
-        .or(literal())
+    // `my_func(arg1, ..., argN)` is only valid in a type expression when calling a `comptime fn`
+    let arguments = parenthesized(expression_list(expr_parser.clone()));
+    let variable_or_call = variable()
         .map_with_span(Expression::new)
+        .then(arguments.or_not())
+        .map_with_span(|(variable, arguments), span| match arguments {
+            Some(arguments) => Expression::call(variable, arguments, span),
+            None => variable,
+        });
+
+    variable_or_call
+        .or(literal().map_with_span(Expression::new))
         .or(parenthesized(expr_parser))
         .labelled(ParsingRuleLabel::Atom)
 }
@@ -1631,9 +1894,11 @@ fn field_name() -> impl NoirParser<Ident> {
 }
 
 fn constructor(expr_parser: impl ExprParser) -> impl NoirParser<ExpressionKind> {
+    let base = just(Token::DoubleDot).ignore_then(expr_parser.clone()).or_not();
     let args = constructor_field(expr_parser)
         .separated_by(just(Token::Comma))
         .allow_trailing()
+        .then(base)
         .delimited_by(just(Token::LeftBrace), just(Token::RightBrace));
 
     path().then(args).map(ExpressionKind::constructor)
@@ -1662,12 +1927,6 @@ fn literal() -> impl NoirParser<ExpressionKind> {
     })
 }
 
-fn literal_or_collection<'a>(
-    expr_parser: impl ExprParser + 'a,
-) -> impl NoirParser<ExpressionKind> + 'a {
-    choice((literal(), constructor(expr_parser.clone()), array_expr(expr_parser)))
-}
-
 #[cfg(test)]
 mod test {
     use noirc_errors::CustomDiagnostic;
@@ -1816,7 +2075,14 @@ mod test {
 
     #[test]
     fn parse_infix() {
-        let valid = vec!["x + 6", "x - k", "x + (x + a)", " x * (x + a) + (x - 4)"];
+        let valid = vec![
+            "x + 6",
+            "x - k",
+            "x + (x + a)",
+            " x * (x + a) + (x - 4)",
+            "x +% y -% z",
+            "x *% (y +% 1)",
+        ];
         parse_all(expression(), valid);
         parse_all_failing(expression(), vec!["y ! x"]);
     }
@@ -1856,6 +2122,28 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_try() {
+        parse_all(
+            atom_or_right_unary(
+                expression(),
+                expression_no_constructors(expression()),
+                fresh_statement(),
+                true,
+            ),
+            vec!["x?", "foo(x)?.bar()?", "array[0]?", "(x + 1)? as u8", "x??"],
+        );
+        parse_all_failing(
+            atom_or_right_unary(
+                expression(),
+                expression_no_constructors(expression()),
+                fresh_statement(),
+                true,
+            ),
+            vec!["?x", "x as ?"],
+        );
+    }
+
     #[test]
     fn parse_array_index() {
         let valid = vec![
@@ -2058,10 +2346,34 @@ mod test {
         match parse_with(assertion(expression()), "assert(x == y, \"assertion message\")").unwrap()
         {
             StatementKind::Constrain(ConstrainStatement(_, message, _)) => {
-                assert_eq!(message, Some("assertion message".to_owned()));
+                let message = message.unwrap();
+                match message.kind {
+                    ExpressionKind::Literal(Literal::Str(message_string)) => {
+                        assert_eq!(message_string, "assertion message".to_owned())
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn parse_assert_with_format_string_message() {
+        match parse_with(assertion(expression()), "assert(x == y, f\"x is {x}\")").unwrap() {
+            StatementKind::Constrain(ConstrainStatement(_, message, _)) => {
+                let message = message.unwrap();
+                match message.kind {
+                    ExpressionKind::Literal(Literal::FmtStr(message_string)) => {
+                        assert_eq!(message_string, "x is {x}".to_owned())
+                    }
+                    _ => unreachable!(),
+                }
             }
             _ => unreachable!(),
         }
+
+        parse_all_failing(assertion(expression()), vec!["assert(x == y, x)"]);
     }
 
     /// This is the standard way to assert that two expressions are equivalent
@@ -2082,7 +2394,13 @@ mod test {
             .unwrap()
         {
             StatementKind::Constrain(ConstrainStatement(_, message, _)) => {
-                assert_eq!(message, Some("assertion message".to_owned()));
+                let message = message.unwrap();
+                match message.kind {
+                    ExpressionKind::Literal(Literal::Str(message_string)) => {
+                        assert_eq!(message_string, "assertion message".to_owned())
+                    }
+                    _ => unreachable!(),
+                }
             }
             _ => unreachable!(),
         }
@@ -2106,17 +2424,81 @@ mod test {
 
     #[test]
     fn parse_for_loop() {
-        parse_all(
+        let loops = parse_all(
             for_loop(expression_no_constructors(expression()), fresh_statement()),
-            vec!["for i in x+y..z {}", "for i in 0..100 { foo; bar }"],
+            vec![
+                "for i in x+y..z {}",
+                "for i in 0..100 { foo; bar }",
+                "#[max_iterations(16)] for i in 0..n { foo; }",
+            ],
         );
+        let bounds = vecmap(loops, |kind| match kind {
+            StatementKind::For(for_loop) => for_loop.max_iterations,
+            _ => unreachable!("Expected a for loop"),
+        });
+        assert_eq!(bounds, vec![None, None, Some(16)]);
 
         parse_all_failing(
             for_loop(expression_no_constructors(expression()), fresh_statement()),
             vec![
-                "for 1 in x+y..z {}",  // Cannot have a literal as the loop identifier
+                "for 1 in x+y..z {}",             // Cannot have a literal as the loop identifier
                 "for i in 0...100 {}", // Only '..' is supported, there are no inclusive ranges yet
                 "for i in 0..=100 {}", // Only '..' is supported, there are no inclusive ranges yet
+                "#[deprecated] for i in 0..n {}", // Only max_iterations may annotate a loop
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_while_loop() {
+        let loops = parse_all(
+            while_loop(expression_no_constructors(expression()), fresh_statement()),
+            vec![
+                "while i < 10 { i += 1; }",
+                "while x.is_some() {}",
+                "#[max_iterations(32)] while x != y { x = x * 2; }",
+            ],
+        );
+        let bounds = vecmap(loops, |kind| match kind {
+            StatementKind::While(while_loop) => while_loop.max_iterations,
+            _ => unreachable!("Expected a while loop"),
+        });
+        assert_eq!(bounds, vec![None, None, Some(32)]);
+
+        parse_all_failing(
+            while_loop(expression_no_constructors(expression()), fresh_statement()),
+            vec![
+                "while {}",                      // A condition is required
+                "while i < 10",                  // A body is required
+                "#[deprecated] while x { }",     // Only max_iterations may annotate a loop
+                "#[max_iterations] while x { }", // A bound must be given
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_loop() {
+        let loops = parse_all(
+            loop_statement(fresh_statement()),
+            vec![
+                "loop {}",
+                "loop { if x { break; } continue; }",
+                "#[max_iterations(8)] loop { break }",
+            ],
+        );
+        let bounds = vecmap(loops, |kind| match kind {
+            StatementKind::Loop(loop_statement) => loop_statement.max_iterations,
+            _ => unreachable!("Expected a loop"),
+        });
+        assert_eq!(bounds, vec![None, None, Some(8)]);
+
+        parse_all_failing(
+            loop_statement(fresh_statement()),
+            vec![
+                "loop",                    // A body is required
+                "loop x {}",               // Loops take no condition
+                "#[deprecated] loop { }",  // Only max_iterations may annotate a loop
+                "loop { break continue }", // Statements must be separated by semicolons
             ],
         );
     }
@@ -2134,6 +2516,7 @@ mod test {
                 "fn f<T>(f: pub Field, y : T, z : Field) -> u8 { x + a }",
                 "fn func_name(x: [Field], y : [Field;2],y : pub [Field;2], z : pub [u8;5])  {}",
                 "fn main(x: pub u8, y: pub u8) -> distinct pub [u8; 2] { [x, y] }",
+                "fn main(x: call_data u8, y: pub u8) -> return_data [u8; 2] { [x, y] }",
                 "fn f(f: pub Field, y : Field, z : comptime Field) -> u8 { x + a }",
                 "fn f<T>(f: pub Field, y : T, z : comptime Field) -> u8 { x + a }",
                 "fn func_name<T>(f: Field, y : T) where T: SomeTrait {}",
@@ -2168,6 +2551,9 @@ mod test {
                 // A leading plus is not allowed.
                 "fn func_name<T>(f: Field, y : T) where T: + SomeTrait {}",
                 "fn func_name<T>(f: Field, y : T) where T: TraitX + <Y> {}",
+                // Call data is only read from and return data only written to.
+                "fn main(x: return_data u8) {}",
+                "fn main(x: u8) -> call_data u8 { x }",
             ],
         );
     }
@@ -2274,6 +2660,8 @@ mod test {
     fn parse_module_declaration() {
         parse_with(module_declaration(), "mod foo").unwrap();
         parse_with(module_declaration(), "mod 1").unwrap_err();
+        parse_with(module_declaration(), "#[allow(unused_variables)] mod foo").unwrap();
+        parse_with(module_declaration(), "#[test] mod foo").unwrap_err();
     }
 
     #[test]
@@ -2342,6 +2730,8 @@ mod test {
                 "use foo::{bar as bar2, hello}",
                 "use foo::{bar as bar2, hello::{foo}, nested::{foo, bar}}",
                 "use dep::{std::println, bar::baz}",
+                "use dep::std::hash::*",
+                "use foo::{bar::*, baz as qux}",
             ],
         );
 
@@ -2354,6 +2744,8 @@ mod test {
                 "use foo bar::baz",
                 "use foo bar::{baz}",
                 "use foo::{,}",
+                "use *",
+                "use foo::* as bar",
             ],
         );
     }
@@ -2378,6 +2770,56 @@ mod test {
         parse_all_failing(struct_definition(), failing);
     }
 
+    #[test]
+    fn parse_enums() {
+        let cases = vec![
+            "enum Foo { }",
+            "enum Bar { A, B, }",
+            "enum Baz { A(Field), B(Field, [u8; 2]), C }",
+            "enum Option<T> { None, Some(T) }",
+            "#[attribute] enum Baz { A, B }",
+        ];
+        parse_all(enum_definition(), cases);
+
+        let failing = vec![
+            "enum {  }",
+            "enum Foo;",
+            "enum Foo { A: Field }",
+            "enum Foo { A(pub Field) }",
+            "#[oracle(some)] enum Foo { A }",
+        ];
+        parse_all_failing(enum_definition(), failing);
+    }
+
+    #[test]
+    fn parse_match() {
+        let matches = parse_all(
+            match_expr(expression_no_constructors(expression()), expression()),
+            vec![
+                "match x { }",
+                "match x { Foo::A => 1, Foo::B(y) => y }",
+                "match x { Foo::A => 1, _ => 2, }",
+                "match x { Foo::A(a, (b, c)) => { a + b + c } other => 0 }",
+                "match foo.bar() { crate::Foo::A => 1, y => y.baz() }",
+            ],
+        );
+        let arm_counts = vecmap(matches, |kind| match kind {
+            ExpressionKind::Match(match_expr) => match_expr.arms.len(),
+            _ => unreachable!("Expected a match expression"),
+        });
+        assert_eq!(arm_counts, vec![0, 2, 2, 2, 2]);
+
+        parse_all_failing(
+            match_expr(expression_no_constructors(expression()), expression()),
+            vec![
+                "match { Foo::A => 1 }",          // A scrutinee is required
+                "match x { Foo::A => 1 _ => 2 }", // Arms must be separated by commas
+                "match x { Foo::A 1 }",           // Arms need a `=>`
+                "match x Foo::A => 1",            // Arms must be in braces
+            ],
+        );
+    }
+
     #[test]
     fn parse_type_aliases() {
         let cases = vec!["type foo = u8", "type bar = String", "type baz<T> = Vec<T>"];
@@ -2400,10 +2842,14 @@ mod test {
             "Bar { ident: 32 }",
             "Baz { other: 2 + 42, ident: foo() + 1 }",
             "Baz { other, ident: foo() + 1, foo }",
+            "Baz { ident: 32, ..other }",
+            "Baz { ident: 32, ..foo() }",
+            "Baz { ..other }",
         ];
 
         parse_all(expression(), cases);
         parse_with(expression(), "Foo { a + b }").unwrap_err();
+        parse_with(expression(), "Foo { ..a, b: 1 }").unwrap_err();
     }
 
     // Semicolons are:
@@ -2472,25 +2918,35 @@ mod test {
         let cases = vec![
             Case {
                 source: "{ return 42; }",
-                expect: concat!("{\n", "    Error\n", "}",),
-                errors: 1,
+                expect: concat!("{\n", "    return 42\n", "}",),
+                errors: 0,
             },
             Case {
                 source: "{ return 1; return 2; }",
-                expect: concat!("{\n", "    Error\n", "    Error\n", "}"),
-                errors: 2,
+                expect: concat!("{\n", "    return 1\n", "    return 2\n", "}"),
+                errors: 0,
             },
             Case {
                 source: "{ return 123; let foo = 4 + 3; }",
-                expect: concat!("{\n", "    Error\n", "    let foo: unspecified = (4 + 3)\n", "}"),
-                errors: 1,
+                expect: concat!(
+                    "{\n",
+                    "    return 123\n",
+                    "    let foo: unspecified = (4 + 3)\n",
+                    "}"
+                ),
+                errors: 0,
             },
             Case {
                 source: "{ return 1 + 2 }",
-                expect: concat!("{\n", "    Error\n", "}",),
-                errors: 2,
+                expect: concat!("{\n", "    return (1 + 2)\n", "}",),
+                errors: 0,
             },
-            Case { source: "{ return; }", expect: concat!("{\n", "    Error\n", "}",), errors: 1 },
+            Case {
+                source: "{ return 1 let x = 2; }",
+                expect: concat!("{\n", "    return 1\n", "    let x: unspecified = 2\n", "}",),
+                errors: 1,
+            },
+            Case { source: "{ return; }", expect: concat!("{\n", "    return\n", "}",), errors: 0 },
         ];
 
         check_cases_with_errors(&cases[..], block(fresh_statement()));
@@ -2549,4 +3005,39 @@ mod test {
 
         check_cases_with_errors(&cases[..], block(fresh_statement()));
     }
+
+    #[test]
+    fn parse_program_with_comments_returns_comments_in_order() {
+        use crate::parser::{parse_program_with_comments, CommentKind};
+        use crate::token::DocStyle;
+
+        let src = "
+            //! Crate docs
+            /// Adds one
+            fn add_one(x: Field) -> Field {
+                // The sum
+                x /* plus */ + 1
+            }
+        ";
+        let (module, comments, errors) = parse_program_with_comments(src);
+        assert!(errors.is_empty());
+        assert_eq!(module.items.len(), 1);
+
+        let summary: Vec<_> = comments
+            .iter()
+            .map(|comment| (comment.text.as_str(), comment.kind, comment.doc_style))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (" Crate docs", CommentKind::Line, Some(DocStyle::Inner)),
+                (" Adds one", CommentKind::Line, Some(DocStyle::Outer)),
+                (" The sum", CommentKind::Line, None),
+                (" plus ", CommentKind::Block, None),
+            ]
+        );
+
+        let span = comments[2].span;
+        assert_eq!(&src[span.start() as usize..span.end() as usize], "// The sum");
+    }
 }
@@ -9,8 +9,9 @@ mod test {
 
     use fm::FileId;
 
-    use noirc_errors::Location;
+    use noirc_errors::{Location, Span};
 
+    use crate::hir::comptime::InterpreterError;
     use crate::hir::def_collector::dc_crate::CompilationError;
     use crate::hir::def_collector::errors::{DefCollectorErrorKind, DuplicateType};
     use crate::hir::def_map::ModuleData;
@@ -20,19 +21,21 @@ mod test {
     use crate::hir::Context;
     use crate::node_interner::{NodeInterner, StmtId};
 
-    use crate::graph::CrateGraph;
+    use crate::graph::{CrateGraph, OverflowMode};
     use crate::hir::def_collector::dc_crate::DefCollector;
-    use crate::hir_def::expr::HirExpression;
+    use crate::hir_def::expr::{HirExpression, HirLiteral};
     use crate::hir_def::stmt::HirStatement;
+    use crate::lints::Lint;
     use crate::monomorphization::monomorphize;
     use crate::parser::ParserErrorReason;
-    use crate::ParsedModule;
     use crate::{
         hir::def_map::{CrateDefMap, LocalModuleId},
         parse_program,
     };
+    use crate::{BinaryOpKind, ParsedModule};
     use arena::Arena;
     use fm::FileManager;
+    use noirc_errors::CustomDiagnostic;
 
     pub(crate) fn has_parser_error(errors: &[(CompilationError, FileId)]) -> bool {
         errors.iter().any(|(e, _f)| matches!(e, CompilationError::ParseError(_)))
@@ -56,14 +59,72 @@ mod test {
 
     pub(crate) fn get_program(
         src: &str,
+    ) -> (ParsedModule, Context, Vec<(CompilationError, FileId)>) {
+        get_program_with_overflow_mode(src, OverflowMode::Trap)
+    }
+
+    /// Most programs in these tests define items which they never use, so warnings about unused
+    /// imports and functions are removed unless a test asks for them with `get_program_with_all_warnings`.
+    pub(crate) fn remove_unused_item_warnings(
+        errors: Vec<(CompilationError, FileId)>,
+    ) -> Vec<(CompilationError, FileId)> {
+        errors
+            .into_iter()
+            .filter(|(error, _)| {
+                !matches!(error.lint(), Some(Lint::UnusedImports | Lint::UnusedFunctions))
+            })
+            .collect()
+    }
+
+    pub(crate) fn get_program_with_overflow_mode(
+        src: &str,
+        overflow_mode: OverflowMode,
+    ) -> (ParsedModule, Context, Vec<(CompilationError, FileId)>) {
+        let (program, context, errors) = get_program_with_all_warnings(src, overflow_mode);
+        (program, context, remove_unused_item_warnings(errors))
+    }
+
+    pub(crate) fn get_program_with_all_warnings(
+        src: &str,
+        overflow_mode: OverflowMode,
+    ) -> (ParsedModule, Context, Vec<(CompilationError, FileId)>) {
+        get_program_with_std(src, None, overflow_mode)
+    }
+
+    /// Compiles `src` with a `std` dependency whose root module is `std_src`, if given, for
+    /// programs which use code generated by the compiler that refers to the standard library.
+    pub(crate) fn get_program_with_std(
+        src: &str,
+        std_src: Option<&str>,
+        overflow_mode: OverflowMode,
     ) -> (ParsedModule, Context, Vec<(CompilationError, FileId)>) {
         let root = std::path::Path::new("/");
-        let fm = FileManager::new(root, Box::new(|path| std::fs::read_to_string(path)));
+        let sources =
+            [("/main.nr", src.to_owned()), ("/std.nr", std_src.unwrap_or_default().to_owned())];
+        let fm = FileManager::new(
+            root,
+            Box::new(move |path| {
+                match sources.iter().find(|(name, _)| path == std::path::Path::new(name)) {
+                    Some((_, source)) => Ok(source.clone()),
+                    None => std::fs::read_to_string(path),
+                }
+            }),
+        );
         //let fm = FileManager::new(root,  Box::new(get_non_stdlib_asset));
         let graph = CrateGraph::default();
         let mut context = Context::new(fm, graph);
         let root_file_id = FileId::dummy();
         let root_crate_id = context.crate_graph.add_crate_root(root_file_id);
+        if std_src.is_some() {
+            // The root file must take the dummy file id before the standard library is added
+            let main_file_id = context.file_manager.add_file(std::path::Path::new("main.nr"));
+            assert_eq!(main_file_id, Some(root_file_id));
+            let std_file_id =
+                context.file_manager.add_file(std::path::Path::new("std.nr")).unwrap();
+            let std_crate_id = context.crate_graph.add_stdlib(std_file_id);
+            let std_name = "std".parse().unwrap();
+            context.crate_graph.add_dep(root_crate_id, std_name, std_crate_id).unwrap();
+        }
         let (program, parser_errors) = parse_program(src);
         let mut errors = remove_experimental_feature_warnings(
             parser_errors.iter().cloned().map(|e| (e.into(), root_file_id)).collect(),
@@ -78,6 +139,7 @@ mod test {
                 modules,
                 krate: root_crate_id,
                 extern_prelude: BTreeMap::new(),
+                overflow_mode,
             };
             // Now we want to populate the CrateDefMap using the DefCollector
             errors.extend(DefCollector::collect(
@@ -462,7 +524,7 @@ mod test {
         for (err, _file_id) in errors {
             match &err {
                 CompilationError::ResolverError(ResolverError::PathResolutionError(
-                    PathResolutionError::Unresolved(ident),
+                    PathResolutionError::Unresolved(ident, _),
                 )) => {
                     assert_eq!(ident, "NotAType");
                 }
@@ -743,6 +805,10 @@ mod test {
                 HirStatement::Constrain(constr_stmt) => constr_stmt.0,
                 HirStatement::Semi(semi_expr) => semi_expr,
                 HirStatement::For(for_loop) => for_loop.block,
+                HirStatement::While(while_loop) => while_loop.block,
+                HirStatement::Loop(loop_statement) => loop_statement.block,
+                HirStatement::Return(expr) => expr,
+                HirStatement::Break | HirStatement::Continue => continue,
                 HirStatement::Error => panic!("Invalid HirStatement!"),
             };
             let expr = interner.expression(&expr_id);
@@ -824,13 +890,144 @@ mod test {
         // It should be regarding the unresolved var `z` (Maybe change to undeclared and special case)
         match &errors[0].0 {
             CompilationError::ResolverError(ResolverError::VariableNotDeclared {
-                name,
-                span: _,
+                name, ..
             }) => assert_eq!(name, "z"),
             _ => unimplemented!("we should only have an unresolved variable"),
         }
     }
 
+    #[test]
+    fn diagnostics_have_explained_error_codes() {
+        let src = r#"
+            fn main(x : Field) {
+                let y = x + x;
+                assert(y == z);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+
+        let diagnostic = CustomDiagnostic::from(errors[0].0.clone());
+        assert_eq!(diagnostic.code, Some(noirc_errors::error_codes::E0303));
+        assert!(noirc_errors::error_codes::explain("E0303").is_some());
+    }
+
+    #[test]
+    fn argument_type_mismatch_points_at_parameter() {
+        let src = r#"
+            fn foo(x: bool) -> bool {
+                x
+            }
+
+            fn main(y : Field) {
+                let _ = foo(y);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+
+        match &errors[0].0 {
+            CompilationError::TypeError(TypeCheckError::ArgumentTypeMismatch {
+                parameter, ..
+            }) => assert!(parameter.is_some()),
+            error => panic!("Expected an argument type mismatch, got: {:?}", error),
+        }
+
+        let diagnostic = CustomDiagnostic::from(errors[0].0.clone());
+        assert_eq!(diagnostic.secondaries.len(), 2);
+    }
+
+    #[test]
+    fn unused_imports_and_functions_are_reported() {
+        let src = r#"
+            mod foo {
+                pub fn bar() {}
+                pub fn baz() {}
+                pub fn qux() {}
+            }
+            use foo::bar;
+            use foo::{baz, qux};
+
+            fn helper() {}
+            fn _intentionally_unused() {}
+            fn used() {}
+
+            fn main() {
+                qux();
+                used();
+            }
+        "#;
+        let (_program, _context, errors) = get_program_with_all_warnings(src, OverflowMode::Trap);
+
+        let unused_imports: Vec<_> = errors
+            .iter()
+            .filter_map(|(error, _)| match error {
+                CompilationError::ResolverError(ResolverError::UnusedImport {
+                    ident,
+                    statement_span,
+                }) => Some((ident.0.contents.as_str(), statement_span.is_some())),
+                _ => None,
+            })
+            .collect();
+        // Only `bar` is imported by a `use` statement of its own, which can be removed
+        assert_eq!(unused_imports, vec![("bar", true), ("baz", false)]);
+
+        let unused_functions: Vec<_> = errors
+            .iter()
+            .filter_map(|(error, _)| match error {
+                CompilationError::ResolverError(ResolverError::UnusedFunction { ident }) => {
+                    Some(ident.0.contents.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(unused_functions, vec!["helper"]);
+    }
+
+    #[test]
+    fn unresolved_names_suggest_similar_and_importable_names() {
+        let src = r#"
+            mod foo {
+                pub fn exported() {}
+                fn hidden() {}
+            }
+            fn compute() -> Field { 1 }
+
+            fn main() {
+                let counter = compte();
+                let _ = countr + counter;
+                exported();
+                hidden();
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 4, "Expected 4 errors, got: {errors:?}");
+
+        let suggestions: Vec<_> = errors
+            .iter()
+            .map(|(error, _)| match error {
+                CompilationError::ResolverError(ResolverError::VariableNotDeclared {
+                    name,
+                    suggestions,
+                    ..
+                }) => (name.as_str(), suggestions),
+                _ => panic!("Expected an undeclared variable, got: {error:?}"),
+            })
+            .collect();
+
+        assert_eq!(suggestions[0].0, "compte");
+        assert_eq!(suggestions[0].1.similar, vec!["compute"]);
+        assert_eq!(suggestions[1].0, "countr");
+        assert_eq!(suggestions[1].1.similar, vec!["counter"]);
+
+        let imports = |index: usize| -> Vec<(&str, bool)> {
+            let imports = &suggestions[index].1.imports;
+            imports.iter().map(|import| (import.path.as_str(), import.is_private)).collect()
+        };
+        assert_eq!(imports(2), vec![("crate::foo::exported", false)]);
+        assert_eq!(imports(3), vec![("crate::foo::hidden", true)]);
+    }
+
     #[test]
     fn unresolved_path() {
         let src = "
@@ -846,6 +1043,7 @@ mod test {
                     match err {
                         ResolverError::PathResolutionError(PathResolutionError::Unresolved(
                             name,
+                            _,
                         )) => {
                             assert_eq!(name.to_string(), "some");
                         }
@@ -896,6 +1094,7 @@ mod test {
                         }
                         ResolverError::PathResolutionError(PathResolutionError::Unresolved(
                             name,
+                            _,
                         )) => {
                             assert_eq!(name.to_string(), "foo");
                         }
@@ -1091,6 +1290,1369 @@ mod test {
         }
     }
 
+    #[test]
+    fn resolve_str_literal_concatenation() {
+        let src = r#"
+            fn main() {
+                let hello: str<11> = "hello" + " " + "world";
+                let nested: str<6> = "ab" + ("cd" + "ef");
+                assert(hello == "hello world");
+                assert(nested == "abcdef");
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let src = r#"
+            fn main() {
+                let too_short: str<4> = "ab" + "cde";
+                assert(too_short == "abcd");
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn resolve_while_loops() {
+        let src = r#"
+            fn main(x: u32) {
+                let mut i = 0;
+                while i < 10 {
+                    i += 1;
+                }
+                let mut y = x;
+                #[max_iterations(32)]
+                while y != 0 {
+                    y = y / 2;
+                }
+                assert(i + y == 10);
+            }
+
+            unconstrained fn unbounded(mut x: u32) -> u32 {
+                while x != 1 {
+                    x = x / 2;
+                }
+                x
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let src = r#"
+            fn main(mut x: u32) {
+                while x != 1 {
+                    x = x / 2;
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::UnboundedWhileLoop { .. })
+        ));
+
+        let src = r#"
+            fn main(x: Field) {
+                while x {}
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(
+            errors.iter().any(|(error, _)| matches!(
+                error,
+                CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+            )),
+            "Expected a type mismatch, got: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn resolve_break_and_continue() {
+        let src = r#"
+            fn main(x: u32) {
+                let mut sum = 0;
+                for i in 0..10 {
+                    if i == x { continue; }
+                    if i > 5 { break; }
+                    sum += i;
+                }
+                #[max_iterations(4)]
+                loop {
+                    sum -= 1;
+                    if sum == 0 { break }
+                }
+                assert(sum == 0);
+            }
+
+            unconstrained fn unbounded(mut x: u32) -> u32 {
+                loop {
+                    if x == 0 { break; }
+                    x -= 1;
+                }
+                x
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let src = r#"
+            fn main(x: u32) {
+                if x == 0 { break; }
+                for _ in 0..x {
+                    let f = || { continue; };
+                    f();
+                }
+                loop { break; }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 3, "Expected 3 errors, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::JumpOutsideLoop {
+                keyword: "break",
+                ..
+            })
+        ));
+        assert!(matches!(
+            errors[1].0,
+            CompilationError::ResolverError(ResolverError::JumpOutsideLoop {
+                keyword: "continue",
+                ..
+            })
+        ));
+        assert!(matches!(
+            errors[2].0,
+            CompilationError::ResolverError(ResolverError::UnboundedLoop { .. })
+        ));
+    }
+
+    #[test]
+    fn type_check_early_returns() {
+        let src = r#"
+            fn main(x: u32) -> pub u32 {
+                for i in 0..10 {
+                    if i == x { return i; }
+                }
+                let doubled = |y: u32| {
+                    if y == 0 { return 1; }
+                    y * 2
+                };
+                if x == 20 {
+                    return doubled(x)
+                } else {
+                    x
+                }
+            }
+
+            fn no_value(x: u32) {
+                if x == 0 { return; }
+                assert(x != 0);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let src = r#"
+            fn main(x: u32) -> pub u32 {
+                if x == 0 { return true; }
+                x
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn resolve_self_static_methods() {
+        let src = r#"
+            struct Point<T> {
+                x: T,
+                y: T,
+            }
+
+            impl<T> Point<T> {
+                fn new(x: T, y: T) -> Self {
+                    Self { x, y }
+                }
+
+                fn swapped(self) -> Self {
+                    Self::new(self.y, self.x)
+                }
+            }
+
+            impl Point<Field> {
+                fn origin() -> Self {
+                    Self::new(0, 0)
+                }
+            }
+
+            fn main(x: Field) {
+                let point = Point::new(x, 1).swapped();
+                assert(point.x == 1);
+                assert(Point::origin().x == 0);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn type_check_enums() {
+        let src = r#"
+            enum Shape<T> {
+                Circle(T),
+                Rectangle(T, T),
+                Empty,
+            }
+
+            impl<T> Shape<T> {
+                fn is_empty(self) -> bool {
+                    match self {
+                        Self::Empty => true,
+                        _ => false,
+                    }
+                }
+            }
+
+            fn area(shape: Shape<u32>) -> u32 {
+                match shape {
+                    Shape::Circle(r) => 3 * r * r,
+                    Shape::Rectangle(w, h) => w * h,
+                    Shape::Empty => 0,
+                }
+            }
+
+            fn main(x: u32) -> pub u32 {
+                let shape = if x == 0 { Shape::Empty } else { Shape::Rectangle(x, 2) };
+                assert(!Shape::Circle(x).is_empty());
+                area(shape)
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn enum_errors() {
+        let src = r#"
+            enum Foo { A(Field), B }
+
+            fn main(x: Field) {
+                let _ = Foo::A(x, x);
+                match Foo::B {
+                    Foo::A(_) => (),
+                }
+                match Foo::B {
+                    Foo::D => (),
+                    _ => (),
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 3, "Expected 3 errors, got: {:?}", errors);
+
+        assert!(errors.iter().any(|(error, _)| matches!(
+            error,
+            CompilationError::ResolverError(ResolverError::NoSuchVariant { .. })
+        )));
+        assert!(errors.iter().any(|(error, _)| matches!(
+            error,
+            CompilationError::TypeError(TypeCheckError::VariantArityMismatch { .. })
+        )));
+        assert!(errors.iter().any(|(error, _)| matches!(
+            error,
+            CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch { .. })
+        )));
+    }
+
+    #[test]
+    fn enum_inputs_to_main_are_constrained() {
+        let src = r#"
+            enum Inner { A, B(Field) }
+            enum Outer { X(Inner), Y }
+
+            fn main(outer: Outer, x: Field) -> pub Field {
+                match outer {
+                    Outer::X(_) => x,
+                    Outer::Y => 0,
+                }
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        // One constraint for the tag of `outer` and one for the tag nested in its `X` variant
+        let main = context.def_interner.find_function("main").unwrap();
+        let program = monomorphize(main, &context.def_interner).to_string();
+        assert_eq!(
+            program.matches("constrain ").count(),
+            2,
+            "Unexpected constraints in:\n{program}"
+        );
+    }
+
+    #[test]
+    fn try_requires_option_or_result() {
+        let src = r#"
+            fn main(x: Field) -> pub Field {
+                x?
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::TypeError(TypeCheckError::InvalidTryOperand { .. })
+        ));
+    }
+
+    #[test]
+    fn try_must_be_in_statement_position() {
+        // The operands aren't Options or Results, so only the position of each `?` is checked here
+        let src = r#"
+            fn allowed(x: Field, c: bool) -> Field {
+                let a = x?;
+                let b = if c { x? } else { 0 };
+                for _ in 0..2 {
+                    x?;
+                }
+                let f = |y: Field| -> Field { y? };
+                f(a + b)
+            }
+
+            fn nested(x: Field) -> Field {
+                let a = 10 / x?;
+                let b = { x? } + 1;
+                a + b + x?
+            }
+
+            unconstrained fn unconstrained_nested(x: Field) -> Field {
+                10 / x?
+            }
+
+            fn main(x: Field) {
+                let _ = allowed(x, true);
+                let _ = nested(x);
+                let _ = unconstrained_nested(x);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        let nested_tries = errors.iter().filter(|(error, _)| {
+            matches!(error, CompilationError::ResolverError(ResolverError::NestedTry { .. }))
+        });
+        assert_eq!(nested_tries.count(), 3, "Expected 3 nested `?` errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn private_function_called_from_parent_module() {
+        let src = r#"
+            mod foo {
+                fn helper() -> Field { 1 }
+
+                pub fn public_helper() -> Field { helper() }
+
+                mod bar {
+                    fn nested() -> Field { crate::foo::helper() }
+                }
+            }
+
+            fn main() {
+                let _ = foo::public_helper();
+                let _ = foo::helper();
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::ResolverError(ResolverError::PrivateFunctionCalled { name, .. })
+                if name == "helper"
+        ));
+    }
+
+    #[test]
+    fn glob_imports_are_shadowed_by_other_items() {
+        let src = r#"
+            mod foo {
+                pub fn one() -> Field { 1 }
+                pub fn two() -> Field { 2 }
+            }
+
+            mod bar {
+                pub fn two() -> Field { 22 }
+            }
+
+            use foo::*;
+            use bar::two;
+
+            fn one() -> Field { 11 }
+
+            fn main() {
+                let _ = one() + two();
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn ambiguous_glob_imports_are_reported_on_use() {
+        let src = r#"
+            mod foo {
+                pub fn helper() -> Field { 1 }
+                pub fn only_foo() -> Field { 2 }
+            }
+
+            mod bar {
+                pub fn helper() -> Field { 3 }
+            }
+
+            use foo::*;
+            use bar::*;
+
+            fn main() {
+                let _ = only_foo();
+                let _ = helper();
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::ResolverError(ResolverError::PathResolutionError(
+                PathResolutionError::Ambiguous(name)
+            )) if name.0.contents == "helper"
+        ));
+    }
+
+    #[test]
+    fn lint_attributes_set_the_level_of_warnings() {
+        let src = r#"
+            fn main() {
+                let warned = 1;
+            }
+
+            #[allow(unused_variables)]
+            fn allowed() {
+                let unused = 1;
+            }
+
+            #[deny(unused_variables)]
+            mod strict {
+                fn denied() {
+                    let unused = 1;
+                }
+
+                #[warn(unused_variables)]
+                fn warned() {
+                    let unused = 1;
+                }
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.len() == 4, "Expected 4 errors, got: {:?}", errors);
+
+        let lint_levels = context.def_interner.lint_levels();
+        let diagnostics: Vec<CustomDiagnostic> = errors
+            .into_iter()
+            .filter_map(|(error, file)| {
+                let lint = error.lint().expect("Expected only lints");
+                lint_levels.apply(lint, error.into(), file)
+            })
+            .collect();
+
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics.iter().filter(|diagnostic| diagnostic.is_error()).count(), 1);
+    }
+
+    #[test]
+    fn statements_after_return_are_unreachable() {
+        let src = r#"
+            fn main() -> pub Field {
+                return 1;
+                let x = 2;
+                x
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::ResolverError(ResolverError::UnreachableCode { .. })
+        ));
+    }
+
+    #[test]
+    fn databus_visibilities_are_only_allowed_on_main() {
+        let src = r#"
+            fn main(x: call_data Field) -> return_data Field {
+                foo(x)
+            }
+
+            fn foo(x: call_data Field) -> return_data Field {
+                x
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        for (error, _) in errors {
+            assert!(matches!(
+                error,
+                CompilationError::ResolverError(ResolverError::DataBusNotAllowed { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn global_array_length_from_later_globals() {
+        let src = r#"
+            global LENGTH = 2 * LEVELS + 1;
+            global LEVELS = 3;
+
+            struct Tree {
+                leaves: [Field; LENGTH],
+            }
+
+            fn main(leaves: [Field; 7]) {
+                let tree = Tree { leaves };
+                for i in 0..LENGTH {
+                    assert(tree.leaves[i] == leaves[i]);
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn global_dependency_cycle() {
+        let src = r#"
+            global A = B + 1;
+            global B = A;
+
+            fn main() {}
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::GlobalDependencyCycle { .. })
+        ));
+    }
+
+    #[test]
+    fn comptime_fn_in_array_length() {
+        let src = r#"
+            global DEPTH = 2;
+            global SIZE = tree_size(DEPTH) + 1;
+
+            comptime fn tree_size(depth: u64) -> u64 {
+                let mut leaves = 1;
+                for _level in 0..depth {
+                    leaves *= 2;
+                }
+                2 * leaves - 1
+            }
+
+            struct Tree {
+                nodes: [Field; tree_size(DEPTH)],
+                padded: [Field; SIZE],
+            }
+
+            fn main(nodes: [Field; 7], padded: [Field; 8]) {
+                let tree = Tree { nodes, padded };
+                let _nodes: [Field; tree_size(2)] = tree.nodes;
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn comptime_fn_errors_in_array_length() {
+        let src = r#"
+            fn not_comptime(x: u64) -> u64 {
+                x
+            }
+
+            comptime fn checked_size(x: u64) -> u64 {
+                assert(x < 4, "size too large");
+                x
+            }
+
+            fn main() {
+                let _a: [Field; not_comptime(1)] = [];
+                let _b: [Field; checked_size(8)] = [];
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::ResolverError(ResolverError::NotAComptimeFunction { name, .. })
+                if name == "not_comptime"
+        ));
+        assert!(matches!(
+            &errors[1].0,
+            CompilationError::ResolverError(ResolverError::ComptimeFnFailed { reason, .. })
+                if reason == "assertion failed: size too large"
+        ));
+    }
+
+    #[test]
+    fn comptime_block_is_replaced_by_its_value() {
+        let src = r#"
+            fn square(x: Field) -> Field {
+                x * x
+            }
+
+            fn main() -> pub Field {
+                let table = comptime {
+                    let mut table = [0; 4];
+                    for i in 0..4 {
+                        table[i] = square(i as Field);
+                    }
+                    table
+                };
+                table[3]
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let main = context.def_interner.find_function("main").unwrap();
+        let program = monomorphize(main, &context.def_interner).to_string();
+        assert!(program.contains("[0, 1, 4, 9]"), "Expected a constant table in:\n{program}");
+    }
+
+    #[test]
+    fn comptime_assertion_failure() {
+        let src = r#"
+            fn main() {
+                comptime {
+                    assert(1 + 1 == 3);
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::InterpreterError(InterpreterError::FailingConstraint { .. })
+        ));
+    }
+
+    #[test]
+    fn comptime_block_cannot_use_runtime_variables() {
+        let src = r#"
+            fn main(x: Field) -> pub Field {
+                comptime { x + 1 }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::InterpreterError(InterpreterError::NonComptimeVarReferenced { .. })
+        ));
+    }
+
+    #[test]
+    fn u128_literal_overflow() {
+        let src = r#"
+            fn main() -> pub (u128, u128) {
+                let max: u128 = 340282366920938463463374607431768211455;
+                let too_large: u128 = 340282366920938463463374607431768211456;
+                (max, too_large)
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::TypeError(TypeCheckError::OverflowingAssignment { .. })
+        ));
+    }
+
+    #[test]
+    fn comptime_u128_arithmetic() {
+        let src = r#"
+            fn main() -> pub u128 {
+                comptime {
+                    let x: u128 = 1 << 127;
+                    assert(x > 1);
+                    (x - 1) * 2 + 1
+                }
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let main = context.def_interner.find_function("main").unwrap();
+        let program = monomorphize(main, &context.def_interner).to_string();
+        let max = "340282366920938463463374607431768211455";
+        assert!(program.contains(max), "Expected u128::MAX in:\n{program}");
+    }
+
+    #[test]
+    fn unconstrained_block_is_lowered_to_unconstrained_lambda() {
+        let src = r#"
+            fn main(x: u32, y: u32) {
+                let mut n = x * x;
+                let root = unconstrained {
+                    let mut i = 0;
+                    while i * i < n {
+                        i += 1;
+                    }
+                    i
+                };
+                assert(root * root == n);
+                n = y;
+                let (quotient, remainder) = unconstrained { (n / x, n % x) };
+                assert(quotient * x + remainder == n);
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let main = context.def_interner.find_function("main").unwrap();
+        let program = monomorphize(main, &context.def_interner);
+        let lambdas = program.functions.iter().filter(|function| function.name == "lambda");
+        assert_eq!(lambdas.clone().count(), 2);
+        assert!(lambdas.clone().all(|function| function.unconstrained));
+        assert!(!program.functions[0].unconstrained);
+    }
+
+    #[test]
+    fn unconstrained_block_rejects_assignment_and_return() {
+        let src = r#"
+            fn main(x: u32) -> pub u32 {
+                let mut y = x;
+                let z = unconstrained {
+                    y = 2;
+                    if x == 0 {
+                        return 1;
+                    }
+                    let mut w = x;
+                    w += 1;
+                    w
+                };
+                y + z
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::AssignmentInUnconstrainedBlock { .. })
+        ));
+        assert!(matches!(
+            errors[1].0,
+            CompilationError::ResolverError(ResolverError::ReturnInUnconstrainedBlock { .. })
+        ));
+    }
+
+    #[test]
+    fn oracle_declarations_are_checked() {
+        let src = r#"
+            #[oracle(get_balance)]
+            unconstrained fn get_balance_oracle(owner: Field, nonce: u32) -> Field {}
+
+            #[oracle(not_unconstrained)]
+            fn not_unconstrained_oracle(x: Field) -> Field {}
+
+            #[oracle(with_body)]
+            unconstrained fn with_body_oracle(x: Field) -> Field {
+                x
+            }
+
+            #[oracle(destructured)]
+            unconstrained fn destructured_oracle((a, b): (Field, Field)) -> Field {}
+
+            fn main() {}
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 3, "Expected 3 errors, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::OracleNotUnconstrained { .. })
+        ));
+        assert!(matches!(
+            errors[1].0,
+            CompilationError::ResolverError(ResolverError::OracleWithBody { .. })
+        ));
+        assert!(matches!(
+            errors[2].0,
+            CompilationError::ResolverError(ResolverError::DestructuredOracleParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn monomorphization_records_called_oracles() {
+        let src = r#"
+            #[oracle(get_balance)]
+            unconstrained fn get_balance_oracle(owner: Field) -> Field {}
+
+            #[oracle(unused)]
+            unconstrained fn unused_oracle(owner: Field) -> Field {}
+
+            unconstrained fn get_balance(owner: Field) -> Field {
+                get_balance_oracle(owner) + get_balance_oracle(owner + 1)
+            }
+
+            fn main(owner: Field) {
+                let balance = get_balance(owner);
+                assert(balance != 0);
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let main = context.def_interner.find_function("main").unwrap();
+        let oracle = context.def_interner.find_function("get_balance_oracle").unwrap();
+        let program = monomorphize(main, &context.def_interner);
+        assert_eq!(program.oracles, vec![oracle]);
+    }
+
+    #[test]
+    fn bounded_recursion_is_unrolled_to_max_depth() {
+        let src = r#"
+            #[max_recursion_depth(3)]
+            fn count_down(n: u32) -> u32 {
+                if n == 0 { 0 } else { 1 + count_down(n - 1) }
+            }
+
+            #[max_recursion_depth(3)]
+            unconstrained fn count_down_unconstrained(n: u32) -> u32 {
+                if n == 0 { 0 } else { 1 + count_down_unconstrained(n - 1) }
+            }
+
+            fn main(x: u32) {
+                assert(count_down(x) == x);
+                assert(count_down_unconstrained(x) == x);
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let main = context.def_interner.find_function("main").unwrap();
+        let program = monomorphize(main, &context.def_interner);
+        let count = |name: &str| program.functions.iter().filter(|f| f.name == name).count();
+
+        // The function itself, a copy for each of the 3 recursive calls, and one which fails
+        assert_eq!(count("count_down"), 5);
+        assert_eq!(count("count_down_unconstrained"), 1);
+    }
+
+    #[test]
+    fn struct_pattern_ignores_remaining_fields() {
+        let src = r#"
+            struct State { counter: Field, owner: Field, nonce: u32 }
+
+            fn counter(State { counter, .. }: State) -> Field {
+                counter
+            }
+
+            fn main(x: Field) {
+                let state = State { counter: x, owner: 2, nonce: 3 };
+                let State { nonce, owner: _, .. } = state;
+                assert(nonce == 3);
+                assert(counter(state) == x);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn struct_update_copies_remaining_fields() {
+        let src = r#"
+            struct State<T> { counter: T, owner: Field, nonce: u32 }
+
+            fn main(x: Field) {
+                let state = State { counter: x, owner: 2, nonce: 3 };
+                let next = State { nonce: state.nonce + 1, ..state };
+                let same = State { ..next };
+                assert(same.counter == x);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn struct_update_rejects_unknown_fields() {
+        let src = r#"
+            struct State { counter: Field, owner: Field }
+
+            fn main(x: Field) {
+                let state = State { counter: x, owner: 2 };
+                let _ = State { count: 1, ..state };
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::NoSuchField { .. })
+        ));
+    }
+
+    #[test]
+    fn generic_structs_nest_inside_arrays_and_fields() {
+        let src = r#"
+            struct Pair<T> { first: T, second: T }
+
+            struct Table<T, N> {
+                rows: [Pair<T>; N],
+                totals: Pair<[T; N]>,
+            }
+
+            impl<T, N> Table<T, N> {
+                fn first_row(self) -> Pair<T> {
+                    self.rows[0]
+                }
+            }
+
+            fn swap_all<T, N>(pairs: [Pair<T>; N]) -> [Pair<T>; N] {
+                let mut swapped = pairs;
+                for i in 0..N {
+                    let Pair { first, second } = pairs[i];
+                    swapped[i] = Pair { first: second, second: first };
+                }
+                swapped
+            }
+
+            fn main(x: Field) {
+                let pairs: [Pair<Field>; 4] = [Pair { first: x, second: 1 }; 4];
+                let totals = Pair { first: [x; 4], second: [1; 4] };
+                let table = Table { rows: swap_all(pairs), totals };
+                assert(table.first_row().first == 1);
+                assert(table.totals.first[3] == x);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn impl_where_clause_applies_to_methods() {
+        let src = r#"
+            trait Eq { fn eq(self, other: Self) -> bool; }
+            trait Hash { fn hash(self) -> Field; }
+
+            struct Set<T> { items: [T; 2] }
+
+            impl<T> Set<T> where T: Hash + Eq, {
+                fn contains(self, item: T) -> bool {
+                    (self.items[0].hash() == item.hash()) & self.items[0].eq(item)
+                }
+            }
+
+            struct Key { value: Field }
+            struct Unhashable { value: Field }
+
+            impl Eq for Key { fn eq(self, other: Self) -> bool { self.value == other.value } }
+            impl Hash for Key { fn hash(self) -> Field { self.value } }
+            impl Eq for Unhashable { fn eq(self, other: Self) -> bool { self.value == other.value } }
+
+            fn main(x: Field) {
+                let keys = Set { items: [Key { value: x }, Key { value: 1 }] };
+                assert(keys.contains(Key { value: x }));
+
+                let values = Set { items: [Unhashable { value: x }, Unhashable { value: 1 }] };
+                assert(values.contains(Unhashable { value: x }));
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::TypeError(TypeCheckError::NoMatchingImplFound { .. })
+        ));
+    }
+
+    #[test]
+    fn derive_serialize_flattens_nested_structs() {
+        let src = r#"
+            #[derive(Serialize)]
+            struct Point { x: Field, y: u8 }
+
+            #[derive(Serialize)]
+            struct Line { start: Point, end: Point, visible: bool, tags: (u32, ()) }
+
+            fn main(x: Field) {
+                let start = Point { x, y: 1 };
+                let end = Point { x: 2, y: 3 };
+                let line = Line { start, end, visible: true, tags: (4, ()) };
+                let fields: [Field; 6] = line.to_fields();
+                let decoded = Line::from_fields(fields);
+                assert(decoded.end.y == 3);
+            }
+        "#;
+        let std_src = r#"
+            mod serialization {
+                trait Serialize<N> {
+                    fn serialize(self) -> [Field; N];
+                }
+            }
+        "#;
+        let (_program, _context, errors) =
+            get_program_with_std(src, Some(std_src), OverflowMode::Trap);
+        let errors = remove_unused_item_warnings(errors);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn trait_generics_are_resolved_per_impl() {
+        let src = r#"
+            trait Serialize<N> {
+                fn serialize(self) -> [Field; N];
+            }
+
+            struct Point { x: Field, y: Field }
+
+            impl Serialize<2> for Point {
+                fn serialize(self) -> [Field; 2] {
+                    [self.x, self.y]
+                }
+            }
+
+            impl Serialize<1> for Field {
+                fn serialize(self) -> [Field; 1] {
+                    [self]
+                }
+            }
+
+            fn first<T, N>(value: T) -> Field where T: Serialize<N> {
+                let fields: [Field; N] = value.serialize();
+                fields[0]
+            }
+
+            fn main(x: Field) {
+                let fields: [Field; 2] = Point { x, y: 2 }.serialize();
+                assert(fields[1] == 2);
+                assert(first(x) == first(Point { x, y: 0 }));
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn trait_generics_must_match() {
+        let src = r#"
+            trait Serialize<N> {
+                fn serialize(self) -> [Field; N];
+            }
+
+            struct Point { x: Field, y: Field }
+
+            impl Serialize<2> for Point {
+                fn serialize(self) -> [Field; 3] {
+                    [self.x, self.y, 0]
+                }
+            }
+
+            struct Unit {}
+
+            impl Serialize for Unit {
+                fn serialize(self) -> [Field; 1] {
+                    [0]
+                }
+            }
+
+            fn main() {}
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        assert!(errors.iter().any(|(error, _)| matches!(
+            error,
+            CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+        )));
+        assert!(errors.iter().any(|(error, _)| matches!(
+            error,
+            CompilationError::DefinitionError(
+                DefCollectorErrorKind::MismatchTraitImplementationNumTraitGenerics { .. }
+            )
+        )));
+    }
+
+    #[test]
+    fn derive_rejects_unknown_traits_and_unsupported_fields() {
+        let src = r#"
+            #[derive(Hash)]
+            struct Point { x: Field }
+
+            #[derive(Serialize)]
+            struct Named { name: str<4> }
+
+            fn main() {}
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::DefinitionError(DefCollectorErrorKind::UnknownDerive { .. })
+        ));
+        assert!(matches!(
+            errors[1].0,
+            CompilationError::DefinitionError(DefCollectorErrorKind::UnsupportedDeriveField { .. })
+        ));
+    }
+
+    #[test]
+    fn entry_point_parameters_must_be_named() {
+        let src = r#"
+            fn main((x, y): (Field, Field), mut z: Field) {
+                z += x;
+                assert(z == y);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::DestructuredEntryPointParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn wrapping_operators_on_integers() {
+        let src = r#"
+            fn main(x: u8, y: i8) {
+                let mut a = x +% 255 -% 1 *% x;
+                a +%= 1;
+                let b = y *% y -% 1;
+                assert(a != 0);
+                assert(b != 0);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn wrap_overflow_mode_resolves_wrapping_operators() {
+        let src = r#"
+            fn main(x: u8, y: u8) -> pub u8 {
+                x + y
+            }
+        "#;
+        let (_program, context, errors) = get_program_with_overflow_mode(src, OverflowMode::Wrap);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let interner = &context.def_interner;
+        let main_func_id = interner.find_function("main").unwrap();
+        let statements = interner.function(&main_func_id).block(interner).statements().to_vec();
+        let HirStatement::Expression(expr_id) = interner.statement(&statements[0]) else {
+            panic!("Expected an expression statement");
+        };
+        let HirExpression::Infix(infix) = interner.expression(&expr_id) else {
+            panic!("Expected an infix expression");
+        };
+        assert_eq!(infix.operator.kind, BinaryOpKind::WrappingAdd);
+    }
+
+    #[test]
+    fn mutable_references_to_array_elements_as_arguments() {
+        let src = r#"
+            struct Point { x: Field, y: Field }
+
+            fn main() {
+                let mut points = [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+                swap(&mut points[0].x, &mut points[1].y);
+                increment_first(&mut points);
+            }
+
+            fn swap(a: &mut Field, b: &mut Field) {
+                let tmp = *a;
+                *a = *b;
+                *b = tmp;
+            }
+
+            fn increment_first(points: &mut [Point; 2]) {
+                increment(&mut points[0].x);
+            }
+
+            fn increment(x: &mut Field) {
+                *x += 1;
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        // Each element is copied into a mutable variable which is written back after the call
+        let main = context.def_interner.find_function("main").unwrap();
+        let program = monomorphize(main, &context.def_interner).to_string();
+        assert_eq!(program.matches("let element$").count(), 3);
+    }
+
+    #[test]
+    fn mutable_references_to_array_elements_are_checked() {
+        let src = r#"
+            fn main(array: [Field; 2]) {
+                let mut copy = array;
+                let reference = &mut copy[0];
+                *reference = 1;
+                increment(&mut array[1]);
+            }
+
+            fn increment(x: &mut Field) {
+                *x += 1;
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::MutableReferenceToArrayElement { .. })
+        ));
+        assert!(matches!(
+            errors[1].0,
+            CompilationError::TypeError(TypeCheckError::ResolverError(
+                ResolverError::MutableReferenceToImmutableVariable { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn compile_time_string_operations() {
+        let src = r#"
+            global PREFIX = "noir";
+
+            fn main(x: Field) {
+                let name = PREFIX + "-lang";
+                assert(name == "noir-lang");
+                assert(name[4] == 45);
+                assert(x == 1, "expected " + PREFIX);
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let interner = &context.def_interner;
+        let main_func_id = interner.find_function("main").unwrap();
+        let statements = interner.function(&main_func_id).block(interner).statements().to_vec();
+
+        let HirStatement::Let(let_statement) = interner.statement(&statements[0]) else {
+            panic!("Expected a let statement");
+        };
+        let name = interner.expression(&let_statement.expression);
+        assert!(
+            matches!(name, HirExpression::Literal(HirLiteral::Str(name)) if name == "noir-lang")
+        );
+
+        let HirStatement::Constrain(comparison) = interner.statement(&statements[1]) else {
+            panic!("Expected a constrain statement");
+        };
+        let comparison = interner.expression(&comparison.0);
+        assert!(matches!(comparison, HirExpression::Literal(HirLiteral::Bool(true))));
+
+        let HirStatement::Constrain(constrain) = interner.statement(&statements[3]) else {
+            panic!("Expected a constrain statement");
+        };
+        assert_eq!(constrain.2.as_deref(), Some("expected noir"));
+    }
+
+    #[test]
+    fn runtime_string_operations_require_unconstrained_code() {
+        let src = r#"
+            fn main(name: str<4>) {
+                let _greeting = "hi " + name;
+                assert(name[0] == 110);
+                let first = unconstrained { ("hi " + name)[3] };
+                assert(first == 110);
+                assert(greet(name) == "hi noir");
+            }
+
+            unconstrained fn greet(name: str<4>) -> str<7> {
+                "hi " + name
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+        for (error, _) in errors {
+            assert!(matches!(
+                error,
+                CompilationError::TypeError(TypeCheckError::RuntimeStringOperation { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn records_references_for_renaming() {
+        let src = r#"
+            struct Foo { bar: Field, baz: Field }
+
+            fn main(x: Field) {
+                let foo = Foo { bar: x, baz: 2 };
+                let y = foo.bar;
+                assert(y == foo.bar);
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let interner = &context.def_interner;
+        let location_of = |needle: &str| {
+            let index = src.find(needle).unwrap() as u32;
+            Location::new(Span::single_char(index), FileId::dummy())
+        };
+
+        let field = interner.find_reference_at(location_of("bar: Field")).unwrap();
+        assert_eq!(interner.reference_name(&field), "bar");
+        assert_eq!(interner.references(&field).len(), 3);
+        assert!(interner.find_rename_conflict(&field, "baz").is_some());
+        assert!(interner.find_rename_conflict(&field, "qux").is_none());
+
+        // `foo` is used while `y` is in scope, so `y` can't be renamed to it
+        let local = interner.find_reference_at(location_of("y ==")).unwrap();
+        assert_eq!(interner.reference_name(&local), "y");
+        assert_eq!(interner.references(&local).len(), 1);
+        assert!(interner.find_rename_conflict(&local, "foo").is_some());
+        assert!(interner.find_rename_conflict(&local, "z").is_none());
+    }
+
+    #[test]
+    fn records_type_parameter_locations() {
+        let src = r#"
+            fn id<T>(x: T) -> T {
+                x
+            }
+
+            fn main() {
+                let _ = id(1);
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+
+        let mut starts: Vec<_> = context
+            .def_interner
+            .type_parameter_locations()
+            .iter()
+            .map(|location| location.span.start())
+            .collect();
+        starts.sort_unstable();
+        starts.dedup();
+
+        let expected: Vec<_> = src.match_indices('T').map(|(index, _)| index as u32).collect();
+        assert_eq!(starts, expected);
+    }
+
     fn check_rewrite(src: &str, expected: &str) {
         let (_program, context, _errors) = get_program(src);
         let main_func_id = context.def_interner.find_function("main").unwrap();
@@ -26,6 +26,10 @@ pub enum PrintableType {
         name: String,
         fields: Vec<(String, PrintableType)>,
     },
+    Enum {
+        name: String,
+        variants: Vec<(String, Vec<PrintableType>)>,
+    },
     String {
         length: u64,
     },
@@ -43,6 +47,11 @@ impl PrintableType {
             Self::Struct { fields, .. } => {
                 fields.iter().fold(0, |acc, (_, field_type)| acc + field_type.field_count())
             }
+            Self::Enum { variants, .. } => {
+                // Enums are encoded as a tag followed by the payloads of every variant
+                let payloads = variants.iter().flat_map(|(_, fields)| fields);
+                payloads.fold(1, |acc, field_type| acc + field_type.field_count())
+            }
             Self::String { length } => *length as u32,
         }
     }
@@ -57,6 +66,8 @@ pub enum PrintableValue {
     String(String),
     Vec(Vec<PrintableValue>),
     Struct(BTreeMap<String, PrintableValue>),
+    /// An enum value holding the index of its active variant and that variant's payload
+    Variant(usize, Vec<PrintableValue>),
 }
 
 /// In order to display a `PrintableValue` we need a `PrintableType` to accurately
@@ -73,6 +84,12 @@ pub enum ForeignCallError {
 
     #[error("Could not parse PrintableType argument. {0}")]
     ParsingError(#[from] serde_json::Error),
+
+    #[error("No handler could be found for foreign call `{0}`")]
+    NoHandler(String),
+
+    #[error("Foreign call `{name}` could not be resolved: {message}")]
+    ResolverError { name: String, message: String },
 }
 
 impl TryFrom<&[ForeignCallParam]> for PrintableValueDisplay {
@@ -215,6 +232,23 @@ fn to_string(value: &PrintableValue, typ: &PrintableType) -> Option<String> {
             output.push_str(" }");
         }
 
+        (PrintableValue::Variant(index, values), PrintableType::Enum { name, variants }) => {
+            let (variant_name, field_types) = variants.get(*index)?;
+            output.push_str(&format!("{name}::{variant_name}"));
+
+            if !values.is_empty() {
+                output.push('(');
+                let mut fields = values.iter().zip(field_types).peekable();
+                while let Some((value, field_type)) = fields.next() {
+                    output.push_str(&format!("{}", PrintableValueDisplay::Plain(value.clone(), field_type.clone())));
+                    if fields.peek().is_some() {
+                        output.push_str(", ");
+                    }
+                }
+                output.push(')');
+            }
+        }
+
         _ => return None
     };
 
@@ -315,6 +349,21 @@ fn decode_value(
 
             PrintableValue::Struct(struct_map)
         }
+        PrintableType::Enum { variants, .. } => {
+            let tag = field_iterator.next().unwrap().to_u128() as usize;
+
+            // The payloads of every variant are always present so each must be consumed,
+            // but only the payload of the active variant is kept.
+            let mut active_fields = Vec::new();
+            for (index, (_, field_types)) in variants.iter().enumerate() {
+                let fields = vecmap(field_types, |typ| decode_value(field_iterator, typ));
+                if index == tag {
+                    active_fields = fields;
+                }
+            }
+
+            PrintableValue::Variant(tag, active_fields)
+        }
     }
 }
 
@@ -4,11 +4,11 @@ use js_sys::{JsString, Object};
 use nargo::artifacts::{
     contract::{PreprocessedContract, PreprocessedContractFunction},
     debug::DebugArtifact,
-    program::PreprocessedProgram,
+    program::{PreprocessedProgram, ABI_VERSION, BYTECODE_VERSION},
 };
 use noirc_driver::{
-    add_dep, compile_contract, compile_main, prepare_crate, prepare_dependency, CompileOptions,
-    CompiledContract, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
+    add_dep, check_crate, compile_contract, compile_main, prepare_crate, prepare_dependency,
+    CompileOptions, CompiledContract, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
 };
 use noirc_frontend::{
     graph::{CrateGraph, CrateId, CrateName},
@@ -131,35 +131,38 @@ pub enum CompileResult {
     Program { program: PreprocessedProgram, debug: DebugArtifact },
 }
 
+/// Type checks the crate at `entry_point` without generating any ACIR, reporting any errors
+/// in the same way as [`compile`].
 #[wasm_bindgen]
-pub fn compile(
+pub fn check(
     entry_point: String,
-    contracts: Option<bool>,
     dependency_graph: Option<JsDependencyGraph>,
-) -> Result<JsCompileResult, JsCompileError> {
+) -> Result<(), JsCompileError> {
     console_error_panic_hook::set_once();
 
-    let dependency_graph: DependencyGraph = if let Some(dependency_graph) = dependency_graph {
-        <JsValue as JsValueSerdeExt>::into_serde(&JsValue::from(dependency_graph))
-            .map_err(|err| err.to_string())?
-    } else {
-        DependencyGraph { root_dependencies: vec![], library_dependencies: HashMap::new() }
-    };
+    let (mut context, crate_id) = prepare_context(&entry_point, dependency_graph)?;
 
-    let root = Path::new("/");
-    let fm = FileManager::new(root, Box::new(get_non_stdlib_asset));
-    let graph = CrateGraph::default();
-    let mut context = Context::new(fm, graph);
+    check_crate(&mut context, crate_id, false).map_err(|errs| {
+        CompileError::with_file_diagnostics("Failed to check program", errs, &context.file_manager)
+    })?;
 
-    let path = Path::new(&entry_point);
-    let crate_id = prepare_crate(&mut context, path);
+    Ok(())
+}
 
-    process_dependency_graph(&mut context, dependency_graph);
+#[wasm_bindgen]
+pub fn compile(
+    entry_point: String,
+    contracts: Option<bool>,
+    dependency_graph: Option<JsDependencyGraph>,
+) -> Result<JsCompileResult, JsCompileError> {
+    console_error_panic_hook::set_once();
 
-    let compile_options = CompileOptions::default();
+    let (mut context, crate_id) = prepare_context(&entry_point, dependency_graph)?;
 
     // For now we default to plonk width = 3, though we can add it as a parameter
     let np_language = acvm::Language::PLONKCSat { width: 3 };
+    let compile_options =
+        CompileOptions { np_language: Some(np_language), ..CompileOptions::default() };
     #[allow(deprecated)]
     let is_opcode_supported = acvm::pwg::default_is_opcode_supported(np_language);
 
@@ -200,6 +203,30 @@ pub fn compile(
     }
 }
 
+fn prepare_context(
+    entry_point: &str,
+    dependency_graph: Option<JsDependencyGraph>,
+) -> Result<(Context, CrateId), JsCompileError> {
+    let dependency_graph: DependencyGraph = if let Some(dependency_graph) = dependency_graph {
+        <JsValue as JsValueSerdeExt>::into_serde(&JsValue::from(dependency_graph))
+            .map_err(|err| err.to_string())?
+    } else {
+        DependencyGraph { root_dependencies: vec![], library_dependencies: HashMap::new() }
+    };
+
+    let root = Path::new("/");
+    let fm = FileManager::new(root, Box::new(get_non_stdlib_asset));
+    let graph = CrateGraph::default();
+    let mut context = Context::new(fm, graph);
+
+    let path = Path::new(entry_point);
+    let crate_id = prepare_crate(&mut context, path);
+
+    process_dependency_graph(&mut context, dependency_graph);
+
+    Ok((context, crate_id))
+}
+
 fn process_dependency_graph(context: &mut Context, dependency_graph: DependencyGraph) {
     let mut crate_names: HashMap<&CrateName, CrateId> = HashMap::new();
 
@@ -242,8 +269,10 @@ fn preprocess_program(program: CompiledProgram) -> CompileResult {
     let preprocessed_program = PreprocessedProgram {
         hash: program.hash,
         backend: String::from(BACKEND_IDENTIFIER),
+        abi_version: ABI_VERSION,
         abi: program.abi,
         noir_version: NOIR_ARTIFACT_VERSION_STRING.to_string(),
+        bytecode_version: BYTECODE_VERSION,
         bytecode: program.circuit,
     };
 
@@ -272,6 +301,7 @@ fn preprocess_contract(contract: CompiledContract) -> CompileResult {
         noir_version: String::from(NOIR_ARTIFACT_VERSION_STRING),
         name: contract.name,
         backend: String::from(BACKEND_IDENTIFIER),
+        abi_version: ABI_VERSION,
         functions: preprocessed_functions,
         events: contract.events,
     };
@@ -4,7 +4,7 @@ use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
 use fm::FileManager;
-use noirc_errors::FileDiagnostic;
+use noirc_errors::{Applicability, FileDiagnostic};
 
 #[wasm_bindgen(typescript_custom_section)]
 const DIAGNOSTICS: &'static str = r#"
@@ -16,6 +16,13 @@ export type Diagnostic = {
         start: number;
         end: number;
     }>;
+    suggestions: ReadonlyArray<{
+        message: string;
+        start: number;
+        end: number;
+        replacement: string;
+        machine_applicable: boolean;
+    }>;
 }
 
 export interface CompileError extends Error {
@@ -79,11 +86,22 @@ struct DiagnosticLabel {
     end: u32,
 }
 
+/// A replacement of the source between `start` and `end` which fixes a diagnostic.
+#[derive(Serialize)]
+struct DiagnosticSuggestion {
+    message: String,
+    start: u32,
+    end: u32,
+    replacement: String,
+    machine_applicable: bool,
+}
+
 #[derive(Serialize)]
 pub struct Diagnostic {
     message: String,
     file: String,
     secondaries: Vec<DiagnosticLabel>,
+    suggestions: Vec<DiagnosticSuggestion>,
 }
 
 impl Diagnostic {
@@ -101,7 +119,19 @@ impl Diagnostic {
             })
             .collect();
 
-        Diagnostic { message, file, secondaries }
+        let suggestions = diagnostic
+            .suggestions()
+            .iter()
+            .map(|suggestion| DiagnosticSuggestion {
+                message: suggestion.message.clone(),
+                start: suggestion.span.start(),
+                end: suggestion.span.end(),
+                replacement: suggestion.replacement.clone(),
+                machine_applicable: suggestion.applicability == Applicability::MachineApplicable,
+            })
+            .collect();
+
+        Diagnostic { message, file, secondaries, suggestions }
     }
 }
 
@@ -17,7 +17,7 @@ mod compile;
 mod errors;
 
 pub use circuit::{acir_read_bytes, acir_write_bytes};
-pub use compile::compile;
+pub use compile::{check, compile};
 
 #[derive(Serialize, Deserialize)]
 pub struct BuildInfo {
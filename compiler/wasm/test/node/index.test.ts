@@ -9,7 +9,7 @@ import {
 } from '../shared';
 import { readFileSync } from 'node:fs';
 import { join, resolve } from 'node:path';
-import { compile } from '@noir-lang/noir_wasm';
+import { check, compile } from '@noir-lang/noir_wasm';
 import { initializeResolver } from '@noir-lang/source-resolver';
 
 // eslint-disable-next-line @typescript-eslint/no-explicit-any
@@ -35,6 +35,12 @@ describe('noir wasm compilation', () => {
     }).timeout(10e3);
   });
 
+  describe('can check simple scripts', () => {
+    it('without generating any bytecode', async () => {
+      expect(() => check(join(__dirname, simpleScriptSourcePath))).to.not.throw();
+    }).timeout(10e3);
+  });
+
   describe('can compile scripts with dependencies', () => {
     beforeEach(() => {
       // this test requires a custom resolver in order to correctly resolve dependencies
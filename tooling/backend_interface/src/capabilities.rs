@@ -0,0 +1,54 @@
+use serde::Deserialize;
+
+/// The family of proving system a backend implements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvingSystem {
+    #[default]
+    Plonk,
+    Honk,
+    Groth16,
+    #[serde(other)]
+    Unknown,
+}
+
+/// The kinds of memory block a backend may be able to constrain natively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryBlockKind {
+    /// Blocks which are only read from once initialized
+    Rom,
+    /// Blocks which are also written to
+    Ram,
+}
+
+/// The features a backend supports beyond proving and verifying circuits.
+///
+/// Backends which don't report their capabilities predate them and support everything,
+/// so each capability defaults to being supported.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct BackendCapabilities {
+    pub proving_system: ProvingSystem,
+    /// Whether proofs can be made for verification within another circuit
+    pub recursion: bool,
+    /// Whether a Solidity verifier can be generated for a circuit
+    pub solidity_verifier: bool,
+    /// The kinds of memory block the backend supports. Accesses to other blocks are compiled
+    /// to arithmetic opcodes instead.
+    pub memory_blocks: Vec<MemoryBlockKind>,
+    /// The size of the largest circuit the backend can prove, if it is limited
+    pub max_circuit_size: Option<u32>,
+}
+
+impl Default for BackendCapabilities {
+    fn default() -> Self {
+        BackendCapabilities {
+            proving_system: ProvingSystem::default(),
+            recursion: true,
+            solidity_verifier: true,
+            memory_blocks: vec![MemoryBlockKind::Rom, MemoryBlockKind::Ram],
+            max_circuit_size: None,
+        }
+    }
+}
@@ -3,7 +3,7 @@ use serde::Deserialize;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
-use crate::{BackendError, BackendOpcodeSupport};
+use crate::{BackendCapabilities, BackendError, BackendInfo, BackendOpcodeSupport};
 
 use super::string_from_stderr;
 
@@ -16,6 +16,8 @@ struct InfoResponse {
     language: LanguageResponse,
     opcodes_supported: Vec<String>,
     black_box_functions_supported: Vec<String>,
+    #[serde(default)]
+    capabilities: BackendCapabilities,
 }
 
 #[derive(Deserialize)]
@@ -25,19 +27,21 @@ struct LanguageResponse {
 }
 
 impl BackendOpcodeSupport {
-    fn new(info: InfoResponse) -> Self {
+    fn new(info: InfoResponse, capabilities: &BackendCapabilities) -> Self {
         let opcodes: HashSet<String> = info.opcodes_supported.into_iter().collect();
-        let black_box_functions: HashSet<String> =
+        let mut black_box_functions: HashSet<String> =
             info.black_box_functions_supported.into_iter().collect();
-        Self { opcodes, black_box_functions }
+        // Aggregating proofs is pointless if they can't be created recursively
+        if !capabilities.recursion {
+            black_box_functions.remove("recursive_aggregation");
+        }
+        let memory_blocks = capabilities.memory_blocks.iter().copied().collect();
+        Self { opcodes, black_box_functions, memory_blocks }
     }
 }
 
 impl InfoCommand {
-    pub(crate) fn run(
-        self,
-        binary_path: &Path,
-    ) -> Result<(Language, BackendOpcodeSupport), BackendError> {
+    pub(crate) fn run(self, binary_path: &Path) -> Result<BackendInfo, BackendError> {
         let mut command = std::process::Command::new(binary_path);
 
         command.arg("info").arg("-c").arg(self.crs_path).arg("-o").arg("-");
@@ -59,7 +63,9 @@ impl InfoCommand {
             _ => panic!("Unknown langauge"),
         };
 
-        Ok((language, BackendOpcodeSupport::new(backend_info)))
+        let capabilities = backend_info.capabilities.clone();
+        let opcode_support = BackendOpcodeSupport::new(backend_info, &capabilities);
+        Ok(BackendInfo { language, opcode_support, capabilities })
     }
 }
 
@@ -72,10 +78,15 @@ fn info_command() -> Result<(), BackendError> {
     let backend = crate::get_mock_backend()?;
     let crs_path = backend.backend_directory();
 
-    let (language, opcode_support) = InfoCommand { crs_path }.run(backend.binary_path())?;
+    let backend_info = InfoCommand { crs_path }.run(backend.binary_path())?;
 
-    assert!(matches!(language, Language::PLONKCSat { width: 3 }));
-    assert!(opcode_support.is_opcode_supported(&Opcode::Arithmetic(Expression::default())));
+    assert!(matches!(backend_info.language, Language::PLONKCSat { width: 3 }));
+    assert_eq!(backend_info.expression_width(), Some(3));
+    assert!(backend_info
+        .opcode_support
+        .is_opcode_supported(&Opcode::Arithmetic(Expression::default())));
+    // The mock backend doesn't report its capabilities so it is assumed to support everything
+    assert_eq!(backend_info.capabilities, crate::BackendCapabilities::default());
 
     Ok(())
 }
@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+use acvm::acir::circuit::opcodes::BlackBoxFuncCall;
+use acvm::acir::circuit::{Circuit, Opcode};
+use serde::Serialize;
+
+use crate::{BackendCapabilities, ProvingSystem};
+
+/// The number of rows added to a circuit by each kind of lookup table which it uses.
+const LOOKUP_TABLE_ROWS: u32 = 1 << 14;
+
+/// The size of each point of the structured reference string loaded to prove a circuit.
+const SRS_POINT_BYTES: u64 = 64;
+
+/// A rough prediction of the resources needed to prove a circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CostEstimate {
+    /// The number of rows the backend is expected to pad the circuit to.
+    pub dyadic_circuit_size: u32,
+    pub proving_time_secs: f64,
+    pub memory_bytes: u64,
+}
+
+/// Predicts the cost of proving circuits with a backend from the circuit's size and the opcodes
+/// which it contains.
+///
+/// Provers spend most of their time on FFTs and multi-scalar multiplications over the circuit padded
+/// to a power of two, so costs grow in steps as circuits cross each power of two. Backends using
+/// lookup arguments also add a table for each kind of range, bitwise or memory opcode used.
+///
+/// The coefficients are based on measurements of barretenberg on a recent laptop, so estimates
+/// should only be used to compare circuits or to budget hardware to within a factor of a few.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostModel {
+    proving_system: ProvingSystem,
+    /// The proving time for each row of the padded circuit, multiplied by the log of its size
+    secs_per_row_log: f64,
+    /// The memory used for each row of the padded circuit, excluding the reference string
+    bytes_per_row: u64,
+}
+
+impl CostModel {
+    pub fn new(capabilities: &BackendCapabilities) -> Self {
+        let (secs_per_row_log, bytes_per_row) = match capabilities.proving_system {
+            ProvingSystem::Plonk | ProvingSystem::Unknown => (3.8e-7, 1200),
+            ProvingSystem::Honk => (1.9e-7, 800),
+            ProvingSystem::Groth16 => (4.8e-7, 500),
+        };
+        CostModel { proving_system: capabilities.proving_system, secs_per_row_log, bytes_per_row }
+    }
+
+    /// Estimates the cost of proving `circuit`, which the backend reports as having `circuit_size` gates.
+    pub fn estimate(&self, circuit: &Circuit, circuit_size: u32) -> CostEstimate {
+        let size = circuit_size.saturating_add(self.lookup_table_rows(circuit)).max(2);
+        let dyadic_circuit_size = size.checked_next_power_of_two().unwrap_or(u32::MAX);
+
+        let rows = dyadic_circuit_size as f64;
+        let proving_time_secs = self.secs_per_row_log * rows * rows.log2();
+        let memory_bytes = (self.bytes_per_row + SRS_POINT_BYTES) * u64::from(dyadic_circuit_size);
+
+        CostEstimate { dyadic_circuit_size, proving_time_secs, memory_bytes }
+    }
+
+    fn lookup_table_rows(&self, circuit: &Circuit) -> u32 {
+        if self.proving_system == ProvingSystem::Groth16 {
+            return 0;
+        }
+
+        let tables: HashSet<&str> = circuit
+            .opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { .. }) => Some("range"),
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND { .. }) => Some("and"),
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::XOR { .. }) => Some("xor"),
+                Opcode::MemoryInit { .. } | Opcode::MemoryOp { .. } => Some("memory"),
+                _ => None,
+            })
+            .collect();
+        tables.len() as u32 * LOOKUP_TABLE_ROWS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::circuit::opcodes::{BlackBoxFuncCall, FunctionInput};
+    use acvm::acir::circuit::{Circuit, Opcode};
+    use acvm::acir::native_types::Witness;
+
+    use super::{CostModel, LOOKUP_TABLE_ROWS};
+    use crate::{BackendCapabilities, ProvingSystem};
+
+    fn range_circuit() -> Circuit {
+        let input = FunctionInput { witness: Witness(1), num_bits: 8 };
+        Circuit {
+            opcodes: vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input })],
+            ..Circuit::default()
+        }
+    }
+
+    #[test]
+    fn costs_grow_with_padded_circuit_size() {
+        let model = CostModel::new(&BackendCapabilities::default());
+        let circuit = Circuit::default();
+
+        let small = model.estimate(&circuit, 1000);
+        let same_padding = model.estimate(&circuit, 1024);
+        let large = model.estimate(&circuit, 1025);
+
+        assert_eq!(small, same_padding);
+        assert_eq!(large.dyadic_circuit_size, 2048);
+        assert!(large.proving_time_secs > small.proving_time_secs);
+        assert!(large.memory_bytes > small.memory_bytes);
+    }
+
+    #[test]
+    fn lookup_tables_only_add_rows_for_lookup_based_systems() {
+        let plonk = CostModel::new(&BackendCapabilities::default());
+        let groth16 = CostModel::new(&BackendCapabilities {
+            proving_system: ProvingSystem::Groth16,
+            ..BackendCapabilities::default()
+        });
+
+        assert_eq!(
+            plonk.estimate(&range_circuit(), 1).dyadic_circuit_size,
+            (LOOKUP_TABLE_ROWS + 1).next_power_of_two()
+        );
+        assert_eq!(groth16.estimate(&range_circuit(), 1).dyadic_circuit_size, 2);
+    }
+}
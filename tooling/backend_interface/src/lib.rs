@@ -3,16 +3,24 @@
 
 use std::{collections::HashSet, path::PathBuf};
 
+mod capabilities;
 mod cli;
+mod cost_model;
 mod download;
+mod mock;
 mod proof_system;
 mod smart_contract;
 
-use acvm::acir::circuit::Opcode;
+use acvm::acir::circuit::{Circuit, Opcode};
+use acvm::acir::native_types::WitnessMap;
+use acvm::{FieldElement, Language};
 use bb_abstraction_leaks::ACVM_BACKEND_BARRETENBERG;
 use bb_abstraction_leaks::BB_VERSION;
+pub use capabilities::{BackendCapabilities, MemoryBlockKind, ProvingSystem};
 use cli::VersionCommand;
+pub use cost_model::{CostEstimate, CostModel};
 pub use download::download_backend;
+pub use mock::{MockBackend, MOCK_BACKEND};
 
 const BACKENDS_DIR: &str = ".nargo/backends";
 
@@ -25,10 +33,10 @@ pub fn backends_directory() -> PathBuf {
 test_binary::build_test_binary_once!(mock_backend, "test-binaries");
 
 #[cfg(test)]
-fn get_mock_backend() -> Result<Backend, BackendError> {
+fn get_mock_backend() -> Result<BinaryBackend, BackendError> {
     std::env::set_var("NARGO_BACKEND_PATH", path_to_mock_backend());
 
-    let mock_backend = Backend::new("mock_backend".to_string());
+    let mock_backend = BinaryBackend::new("mock_backend".to_string());
     mock_backend.assert_binary_exists()?;
 
     Ok(mock_backend)
@@ -52,16 +60,117 @@ pub enum BackendError {
 
     #[error("The backend encountered an error: {0:?}")]
     CommandFailed(String),
+
+    #[error("The `{0}` backend does not support {1}")]
+    UnsupportedCapability(String, &'static str),
+
+    #[error("The circuit has a size of {size} but the backend can only prove circuits of up to {max_circuit_size}")]
+    CircuitTooLarge { size: u32, max_circuit_size: u32 },
+}
+
+/// Everything a backend reports about itself, which determines how programs are compiled for it.
+pub struct BackendInfo {
+    pub language: Language,
+    pub opcode_support: BackendOpcodeSupport,
+    pub capabilities: BackendCapabilities,
+}
+
+impl BackendInfo {
+    /// The number of witnesses each arithmetic opcode may contain, if limited
+    pub fn expression_width(&self) -> Option<usize> {
+        match self.language {
+            Language::PLONKCSat { width } => Some(width),
+            Language::R1CS => None,
+        }
+    }
 }
 
+/// A proving system which circuits are handed to for proving and verification.
+pub trait Backend {
+    fn name(&self) -> &str;
+
+    fn get_backend_info(&self) -> Result<BackendInfo, BackendError>;
+
+    fn capabilities(&self) -> Result<BackendCapabilities, BackendError> {
+        Ok(self.get_backend_info()?.capabilities)
+    }
+
+    fn get_exact_circuit_size(&self, circuit: &Circuit) -> Result<u32, BackendError>;
+
+    /// Returns the number of gates the backend generates for each opcode of the circuit, in
+    /// the order of the circuit's opcodes
+    fn get_gates_per_opcode(&self, _circuit: &Circuit) -> Result<Vec<u32>, BackendError> {
+        Err(BackendError::UnsupportedCapability(
+            self.name().to_owned(),
+            "reporting the gates of each opcode",
+        ))
+    }
+
+    fn prove(
+        &self,
+        circuit: &Circuit,
+        witness_values: WitnessMap,
+        is_recursive: bool,
+    ) -> Result<Vec<u8>, BackendError>;
+
+    fn verify(
+        &self,
+        proof: &[u8],
+        public_inputs: WitnessMap,
+        circuit: &Circuit,
+        is_recursive: bool,
+    ) -> Result<bool, BackendError>;
+
+    /// Returns the verification key of the circuit
+    fn write_vk(&self, circuit: &Circuit) -> Result<Vec<u8>, BackendError>;
+
+    /// Returns the proof as fields, the hash of the verification key and the verification key
+    /// as fields, for verifying the proof recursively
+    fn get_intermediate_proof_artifacts(
+        &self,
+        circuit: &Circuit,
+        proof: &[u8],
+        public_inputs: WitnessMap,
+    ) -> Result<(Vec<FieldElement>, FieldElement, Vec<FieldElement>), BackendError>;
+
+    /// Returns a Solidity contract which verifies proofs of the circuit
+    fn eth_contract(&self, circuit: &Circuit) -> Result<String, BackendError>;
+
+    /// If we cannot get a valid backend, returns the default backend which supports all the opcodes
+    /// and uses Plonk with width 3
+    /// The function also prints a message saying we could not find a backend
+    fn get_backend_info_or_default(&self) -> BackendInfo {
+        if let Ok(backend_info) = self.get_backend_info() {
+            backend_info
+        } else {
+            println!("No valid backend found, defaulting to Plonk with width 3 and all opcodes supported");
+            BackendInfo {
+                language: Language::PLONKCSat { width: 3 },
+                opcode_support: BackendOpcodeSupport::all(),
+                capabilities: BackendCapabilities::default(),
+            }
+        }
+    }
+}
+
+/// Returns the built-in or installed backend with the given name.
+pub fn get_backend(name: String) -> Box<dyn Backend> {
+    if name == MOCK_BACKEND {
+        return Box::new(MockBackend);
+    }
+    // All installed backends are currently binaries speaking the barretenberg CLI protocol
+    Box::new(BinaryBackend::new(name))
+}
+
+/// A backend binary which is driven through the barretenberg CLI protocol.
 #[derive(Debug)]
-pub struct Backend {
+pub struct BinaryBackend {
     name: String,
     binary_path: PathBuf,
 }
 
-impl Backend {
-    pub fn new(name: String) -> Backend {
+impl BinaryBackend {
+    pub fn new(name: String) -> BinaryBackend {
         let binary_path = if let Some(binary_path) = std::env::var_os("NARGO_BACKEND_PATH") {
             PathBuf::from(binary_path)
         } else {
@@ -69,11 +178,7 @@ impl Backend {
 
             backends_directory().join(&name).join(BINARY_NAME)
         };
-        Backend { name, binary_path }
-    }
-
-    pub fn name(&self) -> &str {
-        &self.name
+        BinaryBackend { name, binary_path }
     }
 
     fn binary_path(&self) -> &PathBuf {
@@ -138,6 +243,7 @@ impl Backend {
 pub struct BackendOpcodeSupport {
     opcodes: HashSet<String>,
     black_box_functions: HashSet<String>,
+    memory_blocks: HashSet<MemoryBlockKind>,
 }
 
 impl BackendOpcodeSupport {
@@ -147,7 +253,16 @@ impl BackendOpcodeSupport {
             Opcode::Directive(_) => self.opcodes.contains("directive"),
             Opcode::Brillig(_) => self.opcodes.contains("brillig"),
             Opcode::MemoryInit { .. } => self.opcodes.contains("memory_init"),
-            Opcode::MemoryOp { .. } => self.opcodes.contains("memory_op"),
+            Opcode::MemoryOp { op, .. } => {
+                // Reads are checked against ROM support as the compiler only keeps the memory
+                // opcodes of a block which is written to if writes are supported as well.
+                let kind = if op.operation.is_zero() {
+                    MemoryBlockKind::Rom
+                } else {
+                    MemoryBlockKind::Ram
+                };
+                self.opcodes.contains("memory_op") && self.memory_blocks.contains(&kind)
+            }
             Opcode::BlackBoxFuncCall(func) => {
                 self.black_box_functions.contains(func.get_black_box_func().name())
             }
@@ -179,17 +294,18 @@ impl BackendOpcodeSupport {
                 "recursive_aggregation".to_string(),
                 "ecdsa_secp256r1".to_string(),
             ]),
+            memory_blocks: HashSet::from([MemoryBlockKind::Rom, MemoryBlockKind::Ram]),
         }
     }
 }
 
 #[cfg(test)]
 mod backend {
-    use crate::{Backend, BackendError};
+    use crate::{BackendError, BinaryBackend};
 
     #[test]
     fn raises_error_on_missing_binary() {
-        let bad_backend = Backend::new("i_dont_exist".to_string());
+        let bad_backend = BinaryBackend::new("i_dont_exist".to_string());
 
         let binary_path = bad_backend.assert_binary_exists();
 
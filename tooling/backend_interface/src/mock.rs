@@ -0,0 +1,93 @@
+use acvm::acir::{circuit::Circuit, native_types::WitnessMap};
+use acvm::{FieldElement, Language};
+
+use crate::{Backend, BackendCapabilities, BackendError, BackendInfo, BackendOpcodeSupport};
+
+/// The name under which [`MockBackend`] can be selected.
+pub const MOCK_BACKEND: &str = "mock";
+
+/// The bytes of every proof produced by [`MockBackend`].
+const MOCK_PROOF: &[u8] = b"mock proof";
+
+/// A built-in backend which doesn't generate real proofs.
+///
+/// Programs are still executed in full to solve their witness before proving, but proofs are
+/// returned instantly and always verify. This allows test suites which don't need real proofs to
+/// skip the cost of proving.
+#[derive(Debug, Default)]
+pub struct MockBackend;
+
+impl Backend for MockBackend {
+    fn name(&self) -> &str {
+        MOCK_BACKEND
+    }
+
+    fn get_backend_info(&self) -> Result<BackendInfo, BackendError> {
+        Ok(BackendInfo {
+            language: Language::PLONKCSat { width: 3 },
+            opcode_support: BackendOpcodeSupport::all(),
+            capabilities: BackendCapabilities {
+                solidity_verifier: false,
+                ..BackendCapabilities::default()
+            },
+        })
+    }
+
+    fn get_exact_circuit_size(&self, circuit: &Circuit) -> Result<u32, BackendError> {
+        Ok(circuit.opcodes.len() as u32)
+    }
+
+    fn get_gates_per_opcode(&self, circuit: &Circuit) -> Result<Vec<u32>, BackendError> {
+        Ok(vec![1; circuit.opcodes.len()])
+    }
+
+    fn prove(
+        &self,
+        _circuit: &Circuit,
+        _witness_values: WitnessMap,
+        _is_recursive: bool,
+    ) -> Result<Vec<u8>, BackendError> {
+        Ok(MOCK_PROOF.to_vec())
+    }
+
+    fn verify(
+        &self,
+        _proof: &[u8],
+        _public_inputs: WitnessMap,
+        _circuit: &Circuit,
+        _is_recursive: bool,
+    ) -> Result<bool, BackendError> {
+        Ok(true)
+    }
+
+    fn write_vk(&self, _circuit: &Circuit) -> Result<Vec<u8>, BackendError> {
+        Ok(Vec::new())
+    }
+
+    fn get_intermediate_proof_artifacts(
+        &self,
+        _circuit: &Circuit,
+        _proof: &[u8],
+        _public_inputs: WitnessMap,
+    ) -> Result<(Vec<FieldElement>, FieldElement, Vec<FieldElement>), BackendError> {
+        Ok((Vec::new(), FieldElement::zero(), Vec::new()))
+    }
+
+    fn eth_contract(&self, _circuit: &Circuit) -> Result<String, BackendError> {
+        Err(BackendError::UnsupportedCapability(
+            MOCK_BACKEND.to_owned(),
+            "generating Solidity verifiers",
+        ))
+    }
+}
+
+#[test]
+fn mock_proofs_always_verify() -> Result<(), BackendError> {
+    let backend = MockBackend;
+    let circuit = Circuit::default();
+
+    let proof = backend.prove(&circuit, WitnessMap::new(), false)?;
+    assert!(backend.verify(&proof, WitnessMap::new(), &circuit, false)?);
+
+    Ok(())
+}
@@ -4,17 +4,20 @@ use std::path::Path;
 
 use acvm::acir::{circuit::Circuit, native_types::WitnessMap};
 use acvm::FieldElement;
-use acvm::Language;
 use tempfile::tempdir;
 
 use crate::cli::{
     GatesCommand, InfoCommand, ProofAsFieldsCommand, ProveCommand, VerifyCommand,
     VkAsFieldsCommand, WriteVkCommand,
 };
-use crate::{Backend, BackendError, BackendOpcodeSupport};
+use crate::{Backend, BackendCapabilities, BackendError, BackendInfo, BinaryBackend};
 
-impl Backend {
-    pub fn get_exact_circuit_size(&self, circuit: &Circuit) -> Result<u32, BackendError> {
+impl Backend for BinaryBackend {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_exact_circuit_size(&self, circuit: &Circuit) -> Result<u32, BackendError> {
         let binary_path = self.assert_binary_exists()?;
         self.assert_correct_version()?;
 
@@ -30,25 +33,13 @@ impl Backend {
             .run(binary_path)
     }
 
-    pub fn get_backend_info(&self) -> Result<(Language, BackendOpcodeSupport), BackendError> {
+    fn get_backend_info(&self) -> Result<BackendInfo, BackendError> {
         let binary_path = self.assert_binary_exists()?;
         self.assert_correct_version()?;
         InfoCommand { crs_path: self.crs_directory() }.run(binary_path)
     }
 
-    /// If we cannot get a valid backend, returns the default backend which supports all the opcodes
-    /// and uses Plonk with width 3
-    /// The function also prints a message saying we could not find a backend
-    pub fn get_backend_info_or_default(&self) -> (Language, BackendOpcodeSupport) {
-        if let Ok(backend_info) = self.get_backend_info() {
-            (backend_info.0, backend_info.1)
-        } else {
-            println!("No valid backend found, defaulting to Plonk with width 3 and all opcodes supported");
-            (Language::PLONKCSat { width: 3 }, BackendOpcodeSupport::all())
-        }
-    }
-
-    pub fn prove(
+    fn prove(
         &self,
         circuit: &Circuit,
         witness_values: WitnessMap,
@@ -57,6 +48,17 @@ impl Backend {
         let binary_path = self.assert_binary_exists()?;
         self.assert_correct_version()?;
 
+        let capabilities = self.capabilities()?;
+        if is_recursive && !capabilities.recursion {
+            return Err(BackendError::UnsupportedCapability(self.name.clone(), "recursive proofs"));
+        }
+        if let Some(max_circuit_size) = capabilities.max_circuit_size {
+            let size = self.get_exact_circuit_size(circuit)?;
+            if size > max_circuit_size {
+                return Err(BackendError::CircuitTooLarge { size, max_circuit_size });
+            }
+        }
+
         let temp_directory = tempdir().expect("could not create a temporary directory");
         let temp_directory = temp_directory.path().to_path_buf();
 
@@ -88,7 +90,7 @@ impl Backend {
         Ok(proof)
     }
 
-    pub fn verify(
+    fn verify(
         &self,
         proof: &[u8],
         public_inputs: WitnessMap,
@@ -97,6 +99,9 @@ impl Backend {
     ) -> Result<bool, BackendError> {
         let binary_path = self.assert_binary_exists()?;
         self.assert_correct_version()?;
+        if is_recursive {
+            self.assert_capability(|capabilities| capabilities.recursion, "recursive proofs")?;
+        }
 
         let temp_directory = tempdir().expect("could not create a temporary directory");
         let temp_directory = temp_directory.path().to_path_buf();
@@ -127,7 +132,32 @@ impl Backend {
             .run(binary_path)
     }
 
-    pub fn get_intermediate_proof_artifacts(
+    fn write_vk(&self, circuit: &Circuit) -> Result<Vec<u8>, BackendError> {
+        let binary_path = self.assert_binary_exists()?;
+        self.assert_correct_version()?;
+
+        let temp_directory = tempdir().expect("could not create a temporary directory");
+        let temp_directory = temp_directory.path().to_path_buf();
+
+        // Create a temporary file for the circuit
+        let bytecode_path = temp_directory.join("circuit").with_extension("bytecode");
+        let serialized_circuit = Circuit::serialize_circuit(circuit);
+        write_to_file(&serialized_circuit, &bytecode_path);
+
+        // Create the verification key and read it back from the specified path
+        let vk_path = temp_directory.join("vk");
+
+        WriteVkCommand {
+            crs_path: self.crs_directory(),
+            bytecode_path,
+            vk_path_output: vk_path.clone(),
+        }
+        .run(binary_path)?;
+
+        Ok(std::fs::read(vk_path)?)
+    }
+
+    fn get_intermediate_proof_artifacts(
         &self,
         circuit: &Circuit,
         proof: &[u8],
@@ -171,6 +201,28 @@ impl Backend {
 
         Ok((proof_as_fields, vk_hash, vk_as_fields))
     }
+
+    fn eth_contract(&self, circuit: &Circuit) -> Result<String, BackendError> {
+        self.assert_capability(
+            |capabilities| capabilities.solidity_verifier,
+            "generating Solidity verifiers",
+        )?;
+        self.generate_eth_contract(circuit)
+    }
+}
+
+impl BinaryBackend {
+    fn assert_capability(
+        &self,
+        has_capability: impl FnOnce(&BackendCapabilities) -> bool,
+        capability: &'static str,
+    ) -> Result<(), BackendError> {
+        if has_capability(&self.capabilities()?) {
+            Ok(())
+        } else {
+            Err(BackendError::UnsupportedCapability(self.name.clone(), capability))
+        }
+    }
 }
 
 pub(super) fn write_to_file(bytes: &[u8], path: &Path) -> String {
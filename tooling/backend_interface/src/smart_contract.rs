@@ -1,13 +1,13 @@
 use super::proof_system::write_to_file;
 use crate::{
     cli::{ContractCommand, WriteVkCommand},
-    Backend, BackendError,
+    BackendError, BinaryBackend,
 };
 use acvm::acir::circuit::Circuit;
 use tempfile::tempdir;
 
-impl Backend {
-    pub fn eth_contract(&self, circuit: &Circuit) -> Result<String, BackendError> {
+impl BinaryBackend {
+    pub(crate) fn generate_eth_contract(&self, circuit: &Circuit) -> Result<String, BackendError> {
         let binary_path = self.assert_binary_exists()?;
         self.assert_correct_version()?;
 
@@ -42,7 +42,7 @@ mod tests {
         native_types::{Expression, Witness},
     };
 
-    use crate::{get_mock_backend, BackendError};
+    use crate::{get_mock_backend, Backend, BackendError};
 
     #[test]
     fn test_smart_contract() -> Result<(), BackendError> {
@@ -0,0 +1,50 @@
+
+/**
+ * @title Ultra Plonk verifier reading its public inputs from an EIP-4844 blob
+ * @dev Each public input is checked to be the evaluation of the blob at the matching point, using the
+ * point evaluation precompile, before the proof is verified.
+ */
+contract UltraBlobVerifier is UltraVerifier {
+    uint256 internal constant KZG_PROOF_LENGTH = 48;
+
+    error BlobNotFound();
+    error InvalidBlobInputs();
+    error PointEvaluationFailed(uint256 index);
+
+    function verifyWithBlob(
+        bytes calldata _proof,
+        bytes32[] calldata _publicInputs,
+        uint256 _blobIndex,
+        bytes32[] calldata _evaluationPoints,
+        bytes calldata _commitment,
+        bytes calldata _kzgProofs
+    ) external view returns (bool) {
+        bytes32 versionedHash = blobhash(_blobIndex);
+        if (versionedHash == bytes32(0)) {
+            revert BlobNotFound();
+        }
+        if (
+            _evaluationPoints.length != _publicInputs.length
+                || _kzgProofs.length != _publicInputs.length * KZG_PROOF_LENGTH
+        ) {
+            revert InvalidBlobInputs();
+        }
+
+        for (uint256 i = 0; i < _publicInputs.length; i++) {
+            bytes memory input = abi.encodePacked(
+                versionedHash,
+                _evaluationPoints[i],
+                _publicInputs[i],
+                _commitment,
+                _kzgProofs[i * KZG_PROOF_LENGTH:(i + 1) * KZG_PROOF_LENGTH]
+            );
+            (bool success,) = address(0x0a).staticcall(input);
+            if (!success) {
+                revert PointEvaluationFailed(i);
+            }
+        }
+
+        // `verify` reads the proof from its own calldata so must be called externally.
+        return this.verify(_proof, _publicInputs);
+    }
+}
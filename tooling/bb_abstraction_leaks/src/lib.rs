@@ -10,10 +10,30 @@ pub const BB_VERSION: &str = env!("BB_VERSION");
 /// Embed the Solidity verifier file
 const ULTRA_VERIFIER_CONTRACT: &str = include_str!("contract.sol");
 
+/// Embed the Solidity verifier which reads public inputs from a blob
+const ULTRA_BLOB_VERIFIER_CONTRACT: &str = include_str!("blob_verifier.sol");
+
+/// The name of the contract which verifies proofs with public inputs passed as calldata.
+pub const ULTRA_VERIFIER_NAME: &str = "UltraVerifier";
+
+/// The name of the contract which verifies proofs with public inputs read from a blob.
+pub const ULTRA_BLOB_VERIFIER_NAME: &str = "UltraBlobVerifier";
+
+/// The addresses of the precompiles called by the verifier contract: modexp, ecAdd, ecMul and ecPairing.
+pub const VERIFIER_PRECOMPILES: [u8; 4] = [0x05, 0x06, 0x07, 0x08];
+
+/// The address of the point evaluation precompile used to read public inputs from a blob.
+pub const POINT_EVALUATION_PRECOMPILE: u8 = 0x0a;
+
 pub fn complete_barretenberg_verifier_contract(contract: String) -> String {
     format!("{contract}{ULTRA_VERIFIER_CONTRACT}")
 }
 
+/// Adds a verifier contract to `contract` which reads its public inputs from an EIP-4844 blob.
+pub fn append_barretenberg_blob_verifier_contract(contract: String) -> String {
+    format!("{contract}{ULTRA_BLOB_VERIFIER_CONTRACT}")
+}
+
 /// Removes the public inputs which are prepended to a proof by Barretenberg.
 pub fn remove_public_inputs(num_pub_inputs: usize, proof: &[u8]) -> Vec<u8> {
     // Barretenberg prepends the public inputs onto the proof so we need to remove
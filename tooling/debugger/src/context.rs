@@ -112,6 +112,7 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
             }
             Err(err) => DebugCommandResult::Error(NargoError::ExecutionError(
                 ExecutionError::SolvingError(err),
+                WitnessMap::new(),
             )),
         }
     }
@@ -152,6 +153,7 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
             }
             ACVMStatus::Failure(error) => DebugCommandResult::Error(NargoError::ExecutionError(
                 ExecutionError::SolvingError(error),
+                WitnessMap::new(),
             )),
             ACVMStatus::RequiresForeignCall(_) => {
                 unreachable!("Unexpected pending foreign call resolution");
@@ -0,0 +1,450 @@
+use crate::context::{DebugCommandResult, DebugContext};
+
+use acvm::acir::circuit::{Circuit, OpcodeLocation};
+use acvm::acir::native_types::WitnessMap;
+use acvm::BlackBoxFunctionSolver;
+
+use nargo::artifacts::debug::DebugArtifact;
+use noirc_abi::{input_parser::InputValue, Abi, MAIN_RETURN_NAME};
+use noirc_errors::Location;
+
+use fm::FileId;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+/// The only thread of a debugging session, as the circuit is executed sequentially.
+const THREAD_ID: i64 = 1;
+
+// The references the client uses to fetch the variables of each scope
+const INPUTS_SCOPE: i64 = 1;
+const WITNESSES_SCOPE: i64 = 2;
+const BRILLIG_REGISTERS_SCOPE: i64 = 3;
+
+/// A request sent by the client of a Debug Adapter Protocol session.
+#[derive(Debug, Deserialize)]
+pub struct DapRequest {
+    pub seq: i64,
+    pub command: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+/// Reads requests from and writes responses and events to a Debug Adapter Protocol client,
+/// using the protocol's `Content-Length` framing.
+pub struct DapServer<R, W> {
+    input: R,
+    output: W,
+    seq: i64,
+}
+
+impl<R: BufRead, W: Write> DapServer<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output, seq: 0 }
+    }
+
+    /// Reads the next request, returning `None` once the client has closed the connection.
+    pub fn read_request(&mut self) -> io::Result<Option<DapRequest>> {
+        let mut content_length = None;
+        loop {
+            let mut header = String::new();
+            if self.input.read_line(&mut header)? == 0 {
+                return Ok(None);
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(length) = header.strip_prefix("Content-Length:") {
+                let length = length
+                    .trim()
+                    .parse()
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                content_length = Some(length);
+            }
+        }
+
+        let Some(content_length) = content_length else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Missing Content-Length header",
+            ));
+        };
+        let mut content = vec![0; content_length];
+        self.input.read_exact(&mut content)?;
+        Ok(Some(serde_json::from_slice(&content)?))
+    }
+
+    pub fn respond(&mut self, request: &DapRequest, body: Value) -> io::Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": true,
+            "command": request.command,
+            "body": body,
+        }))
+    }
+
+    pub fn respond_error(&mut self, request: &DapRequest, message: &str) -> io::Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": false,
+            "command": request.command,
+            "message": message,
+        }))
+    }
+
+    pub fn send_event(&mut self, event: &str, body: Value) -> io::Result<()> {
+        self.send(json!({ "type": "event", "event": event, "body": body }))
+    }
+
+    fn send(&mut self, mut message: Value) -> io::Result<()> {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        let content = serde_json::to_string(&message)?;
+        write!(self.output, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+        self.output.flush()
+    }
+}
+
+struct DapSession<'a, R, W, B: BlackBoxFunctionSolver> {
+    server: &'a mut DapServer<R, W>,
+    context: DebugContext<'a, B>,
+    debug_artifact: &'a DebugArtifact,
+    abi: &'a Abi,
+    /// The opcodes breakpoints have been set at for the lines of each source file.
+    source_breakpoints: BTreeMap<FileId, Vec<OpcodeLocation>>,
+}
+
+impl<'a, R: BufRead, W: Write, B: BlackBoxFunctionSolver> DapSession<'a, R, W, B> {
+    fn run_loop(&mut self) -> io::Result<()> {
+        while let Some(request) = self.server.read_request()? {
+            match request.command.as_str() {
+                "setBreakpoints" => self.set_source_breakpoints(&request)?,
+                "setExceptionBreakpoints" => self.server.respond(&request, json!({}))?,
+                "configurationDone" => {
+                    self.server.respond(&request, json!({}))?;
+                    self.send_stopped("entry", None)?;
+                }
+                "threads" => {
+                    let threads = json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] });
+                    self.server.respond(&request, threads)?;
+                }
+                "stackTrace" => self.stack_trace(&request)?,
+                "scopes" => self.scopes(&request)?,
+                "variables" => self.variables(&request)?,
+                "next" => {
+                    let by_opcode = request.arguments["granularity"] == "instruction";
+                    self.step(&request, json!({}), |context| {
+                        if by_opcode {
+                            context.step_acir_opcode()
+                        } else {
+                            context.next()
+                        }
+                    })?;
+                }
+                "stepIn" => self.step(&request, json!({}), DebugContext::step_into_opcode)?,
+                "stepOut" => self.step(&request, json!({}), DebugContext::step_acir_opcode)?,
+                "continue" => {
+                    let body = json!({ "allThreadsContinued": true });
+                    self.step(&request, body, DebugContext::cont)?;
+                }
+                "pause" => {
+                    // Execution only progresses while handling a request, so it is already paused
+                    self.server.respond(&request, json!({}))?;
+                    self.send_stopped("pause", None)?;
+                }
+                "disconnect" | "terminate" => {
+                    self.server.respond(&request, json!({}))?;
+                    return Ok(());
+                }
+                command => {
+                    self.server
+                        .respond_error(&request, &format!("Unsupported command {command}"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Responds to a request which resumes execution, then resumes it with `step` and tells the
+    /// client where execution stopped.
+    fn step(
+        &mut self,
+        request: &DapRequest,
+        body: Value,
+        step: impl FnOnce(&mut DebugContext<'a, B>) -> DebugCommandResult,
+    ) -> io::Result<()> {
+        self.server.respond(request, body)?;
+        let result = if self.context.get_current_opcode_location().is_some() {
+            step(&mut self.context)
+        } else {
+            DebugCommandResult::Done
+        };
+
+        match result {
+            DebugCommandResult::Ok => self.send_stopped("step", None),
+            DebugCommandResult::BreakpointReached(_) => self.send_stopped("breakpoint", None),
+            DebugCommandResult::Done => self.server.send_event("terminated", json!({})),
+            DebugCommandResult::Error(error) => {
+                self.send_stopped("exception", Some(error.to_string()))
+            }
+        }
+    }
+
+    fn send_stopped(&mut self, reason: &str, text: Option<String>) -> io::Result<()> {
+        let mut body = json!({
+            "reason": reason,
+            "threadId": THREAD_ID,
+            "allThreadsStopped": true,
+        });
+        if let Some(text) = text {
+            body["text"] = json!(text);
+        }
+        self.server.send_event("stopped", body)
+    }
+
+    /// Replaces the breakpoints of a source file with ones at the first opcode generated for
+    /// each of the requested lines. Lines without any opcodes get unverified breakpoints.
+    fn set_source_breakpoints(&mut self, request: &DapRequest) -> io::Result<()> {
+        let path = request.arguments["source"]["path"].as_str().unwrap_or_default();
+        let Some(file_id) = self
+            .debug_artifact
+            .file_map
+            .iter()
+            .find_map(|(file_id, file)| (file.path == Path::new(path)).then_some(*file_id))
+        else {
+            let breakpoints: Vec<_> = requested_lines(request)
+                .map(|line| json!({ "verified": false, "line": line }))
+                .collect();
+            return self.server.respond(request, json!({ "breakpoints": breakpoints }));
+        };
+
+        for location in self.source_breakpoints.remove(&file_id).unwrap_or_default() {
+            self.context.delete_breakpoint(&location);
+        }
+
+        let mut locations = Vec::new();
+        let mut breakpoints = Vec::new();
+        for line in requested_lines(request) {
+            match self.find_opcode_at_line(file_id, line) {
+                Some(location) => {
+                    self.context.add_breakpoint(location.clone());
+                    locations.push(location);
+                    breakpoints.push(json!({ "verified": true, "line": line }));
+                }
+                None => breakpoints.push(json!({
+                    "verified": false,
+                    "line": line,
+                    "message": "No opcodes were generated for this line",
+                })),
+            }
+        }
+        self.source_breakpoints.insert(file_id, locations);
+
+        self.server.respond(request, json!({ "breakpoints": breakpoints }))
+    }
+
+    /// Finds the first opcode whose innermost source location starts on `line` of `file_id`.
+    fn find_opcode_at_line(&self, file_id: FileId, line: usize) -> Option<OpcodeLocation> {
+        let debug_artifact = self.debug_artifact;
+        debug_artifact.debug_symbols[0].locations.iter().find_map(
+            |(opcode_location, call_stack)| {
+                let location = call_stack.last()?;
+                let on_line = location.file == file_id
+                    && debug_artifact.location_line_number(*location).ok() == Some(line);
+                on_line.then(|| opcode_location.clone())
+            },
+        )
+    }
+
+    fn stack_trace(&mut self, request: &DapRequest) -> io::Result<()> {
+        let call_stack = self.context.get_current_source_location().unwrap_or_default();
+        let mut frames: Vec<_> = call_stack
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(index, location)| self.stack_frame(index, *location))
+            .collect();
+
+        // Opcodes inserted by the compiler may have no source location
+        if frames.is_empty() {
+            if let Some(opcode_location) = self.context.get_current_opcode_location() {
+                frames.push(json!({
+                    "id": 0,
+                    "name": format!("opcode {opcode_location}"),
+                    "line": 0,
+                    "column": 0,
+                }));
+            }
+        }
+
+        let total_frames = frames.len();
+        self.server.respond(request, json!({ "stackFrames": frames, "totalFrames": total_frames }))
+    }
+
+    fn stack_frame(&self, index: usize, location: Location) -> Value {
+        let debug_artifact = self.debug_artifact;
+        let name = debug_artifact.debug_symbols[0]
+            .functions
+            .iter()
+            .find(|function| function.contains(&location))
+            .map_or("<unknown>", |function| function.name.as_str());
+        let line = debug_artifact.location_line_number(location).unwrap_or_default();
+        let column = debug_artifact.location_column_number(location).unwrap_or_default();
+
+        let mut frame = json!({ "id": index, "name": name, "line": line, "column": column });
+        if let Some(file) = debug_artifact.file_map.get(&location.file) {
+            let file_name = file.path.file_name().map(|name| name.to_string_lossy());
+            frame["source"] = json!({ "name": file_name, "path": file.path });
+        }
+        frame
+    }
+
+    fn scopes(&mut self, request: &DapRequest) -> io::Result<()> {
+        let mut scopes = vec![
+            json!({ "name": "Inputs", "variablesReference": INPUTS_SCOPE, "expensive": false }),
+            json!({ "name": "Witnesses", "variablesReference": WITNESSES_SCOPE, "expensive": false }),
+        ];
+        if self.context.get_brillig_registers().is_some() {
+            scopes.push(json!({
+                "name": "Brillig Registers",
+                "variablesReference": BRILLIG_REGISTERS_SCOPE,
+                "expensive": false,
+            }));
+        }
+        self.server.respond(request, json!({ "scopes": scopes }))
+    }
+
+    fn variables(&mut self, request: &DapRequest) -> io::Result<()> {
+        let variables: Vec<_> = match request.arguments["variablesReference"].as_i64() {
+            // The inputs are decoded from the witnesses they were written to, along with the
+            // return value once it has been solved.
+            Some(INPUTS_SCOPE) => match self.abi.decode(self.context.get_witness_map()) {
+                Ok((inputs, return_value)) => inputs
+                    .iter()
+                    .map(|(name, value)| variable(name, format_input_value(value)))
+                    .chain(
+                        return_value
+                            .map(|value| variable(MAIN_RETURN_NAME, format_input_value(&value))),
+                    )
+                    .collect(),
+                Err(_) => Vec::new(),
+            },
+            Some(WITNESSES_SCOPE) => {
+                // NOTE: we need to clone() here to get the iterator
+                let witness_map = self.context.get_witness_map().clone();
+                witness_map
+                    .into_iter()
+                    .map(|(witness, value)| {
+                        variable(&format!("_{}", witness.witness_index()), value.to_string())
+                    })
+                    .collect()
+            }
+            Some(BRILLIG_REGISTERS_SCOPE) => {
+                let registers = self.context.get_brillig_registers();
+                registers
+                    .map(|registers| registers.inner.as_slice())
+                    .unwrap_or_default()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        variable(&index.to_string(), value.to_field().to_string())
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        self.server.respond(request, json!({ "variables": variables }))
+    }
+}
+
+/// Returns the lines of a `setBreakpoints` request.
+fn requested_lines(request: &DapRequest) -> impl Iterator<Item = usize> + '_ {
+    request.arguments["breakpoints"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|breakpoint| breakpoint["line"].as_u64())
+        .map(|line| line as usize)
+}
+
+fn variable(name: &str, value: String) -> Value {
+    json!({ "name": name, "value": value, "variablesReference": 0 })
+}
+
+fn format_input_value(value: &InputValue) -> String {
+    let format_values = |values: &[InputValue]| {
+        values.iter().map(format_input_value).collect::<Vec<_>>().join(", ")
+    };
+    match value {
+        InputValue::Field(field) => field.to_string(),
+        InputValue::String(string) => format!("{string:?}"),
+        InputValue::Vec(values) => format!("[{}]", format_values(values)),
+        InputValue::Struct(fields) => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(name, value)| format!("{name}: {}", format_input_value(value)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        InputValue::Variant(name, fields) if fields.is_empty() => name.clone(),
+        InputValue::Variant(name, fields) => format!("{name}({})", format_values(fields)),
+    }
+}
+
+/// Runs a debugging session with a client which has already been initialized and has asked to
+/// launch the program, stopping on entry once the client has finished configuring the session.
+///
+/// Returns the solved witness if the circuit was executed to completion.
+pub(crate) fn run_session<R: BufRead, W: Write, B: BlackBoxFunctionSolver>(
+    server: &mut DapServer<R, W>,
+    blackbox_solver: &B,
+    circuit: &Circuit,
+    debug_artifact: &DebugArtifact,
+    abi: &Abi,
+    initial_witness: WitnessMap,
+) -> io::Result<Option<WitnessMap>> {
+    let context = DebugContext::new(blackbox_solver, circuit, debug_artifact, initial_witness);
+    let mut session =
+        DapSession { server, context, debug_artifact, abi, source_breakpoints: BTreeMap::new() };
+
+    // Breakpoints can now be configured
+    session.server.send_event("initialized", json!({}))?;
+    session.run_loop()?;
+
+    if session.context.is_solved() {
+        Ok(Some(session.context.finalize()))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_reads_requests_and_frames_responses() {
+    let content = r#"{"seq":1,"type":"request","command":"threads"}"#;
+    let input = format!("Content-Length: {}\r\n\r\n{content}", content.len());
+    let mut output = Vec::new();
+
+    let mut server = DapServer::new(input.as_bytes(), &mut output);
+    let request = server.read_request().unwrap().expect("Expected a request");
+    assert_eq!(request.command, "threads");
+    assert_eq!(request.arguments, Value::Null);
+    server.respond(&request, json!({ "threads": [] })).unwrap();
+    assert!(server.read_request().unwrap().is_none());
+
+    let output = String::from_utf8(output).unwrap();
+    let (header, content) = output.split_once("\r\n\r\n").unwrap();
+    assert_eq!(header, format!("Content-Length: {}", content.len()));
+
+    let response: Value = serde_json::from_str(content).unwrap();
+    assert_eq!(response["type"], "response");
+    assert_eq!(response["request_seq"], 1);
+    assert_eq!(response["success"], true);
+    assert_eq!(response["body"], json!({ "threads": [] }));
+}
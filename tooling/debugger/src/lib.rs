@@ -1,18 +1,42 @@
 mod context;
+mod dap;
 mod repl;
 
+use std::io::{self, BufRead, Write};
+
 use acvm::BlackBoxFunctionSolver;
 use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
 
 use nargo::artifacts::debug::DebugArtifact;
 
 use nargo::NargoError;
+use noirc_abi::Abi;
+
+pub use dap::{DapRequest, DapServer};
 
+/// Debugs a circuit in an interactive REPL.
+///
+/// If `run_until_failure` is set, the circuit is executed without stopping first and the REPL is
+/// only started if execution fails, at the opcode which failed.
 pub fn debug_circuit<B: BlackBoxFunctionSolver>(
     blackbox_solver: &B,
     circuit: &Circuit,
     debug_artifact: DebugArtifact,
     initial_witness: WitnessMap,
+    run_until_failure: bool,
 ) -> Result<Option<WitnessMap>, NargoError> {
-    repl::run(blackbox_solver, circuit, &debug_artifact, initial_witness)
+    repl::run(blackbox_solver, circuit, &debug_artifact, initial_witness, run_until_failure)
+}
+
+/// Debugs a circuit for a Debug Adapter Protocol client, once the client has been initialized
+/// and has asked to launch the program.
+pub fn run_dap_loop<R: BufRead, W: Write, B: BlackBoxFunctionSolver>(
+    server: &mut DapServer<R, W>,
+    blackbox_solver: &B,
+    circuit: &Circuit,
+    debug_artifact: DebugArtifact,
+    abi: &Abi,
+    initial_witness: WitnessMap,
+) -> io::Result<Option<WitnessMap>> {
+    dap::run_session(server, blackbox_solver, circuit, &debug_artifact, abi, initial_witness)
 }
@@ -392,12 +392,22 @@ pub fn run<B: BlackBoxFunctionSolver>(
     circuit: &Circuit,
     debug_artifact: &DebugArtifact,
     initial_witness: WitnessMap,
+    run_until_failure: bool,
 ) -> Result<Option<WitnessMap>, NargoError> {
     let context =
         RefCell::new(ReplDebugger::new(blackbox_solver, circuit, debug_artifact, initial_witness));
     let ref_context = &context;
 
-    ref_context.borrow().show_current_vm_status();
+    if run_until_failure {
+        let result = ref_context.borrow_mut().context.cont();
+        if matches!(result, DebugCommandResult::Done) {
+            return Ok(Some(context.into_inner().finalize()));
+        }
+        println!("Execution failed, starting debugger");
+        ref_context.borrow_mut().handle_debug_command_result(result);
+    } else {
+        ref_context.borrow().show_current_vm_status();
+    }
 
     let mut repl = Repl::builder()
         .add(
@@ -18,17 +18,21 @@ use async_lsp::{
     ResponseError,
 };
 use codespan_reporting::files;
+use fm::FileReader;
+use noirc_errors::debug_info::OpCodesCount;
 use noirc_frontend::{
     graph::{CrateId, CrateName},
-    hir::{Context, FunctionNameMatch},
+    hir::{Context, FunctionNameMatch, ParsedFiles},
 };
 use notifications::{
     on_did_change_configuration, on_did_change_text_document, on_did_close_text_document,
     on_did_open_text_document, on_did_save_text_document, on_exit, on_initialized,
 };
 use requests::{
-    on_code_lens_request, on_formatting, on_initialize, on_profile_run_request, on_shutdown,
-    on_test_run_request, on_tests_request,
+    on_code_lens_request, on_formatting, on_goto_definition_request, on_hover_request,
+    on_initialize, on_inlay_hint_request, on_profile_run_request, on_range_formatting,
+    on_rename_request, on_semantic_tokens_full_request, on_shutdown, on_test_run_request,
+    on_tests_request, on_will_save_wait_until,
 };
 use serde_json::Value as JsonValue;
 use tower::Service;
@@ -39,7 +43,7 @@ mod solver;
 mod types;
 
 use solver::WrapperSolver;
-use types::{notification, request, NargoTest, NargoTestId, Position, Range, Url};
+use types::{notification, request, Diagnostic, NargoTest, NargoTestId, Position, Range, Url};
 
 // State for the LSP gets implemented on this struct and is internal to the implementation
 pub struct LspState {
@@ -47,6 +51,18 @@ pub struct LspState {
     client: ClientSocket,
     solver: WrapperSolver,
     input_files: HashMap<String, String>,
+    /// The opcodes generated by each function of the programs in the workspace when they were
+    /// last compiled successfully, along with the start of the function's body, keyed by the
+    /// root directory of the package they were compiled for.
+    function_opcode_counts: HashMap<PathBuf, HashMap<Url, Vec<(Position, OpCodesCount)>>>,
+    /// Whether the client asked for documents to be formatted when they are saved.
+    format_on_save: bool,
+    /// The modules parsed when the workspace was last checked, so that only changed files need
+    /// to be parsed again.
+    parsed_files: ParsedFiles,
+    /// The diagnostics found when each package was last checked, keyed by the package's root
+    /// directory and then by the document they are reported in.
+    package_diagnostics: HashMap<PathBuf, HashMap<Url, Vec<Diagnostic>>>,
 }
 
 impl LspState {
@@ -56,6 +72,10 @@ impl LspState {
             root_path: None,
             solver: WrapperSolver(Box::new(solver)),
             input_files: HashMap::new(),
+            function_opcode_counts: HashMap::new(),
+            format_on_save: false,
+            parsed_files: ParsedFiles::new(),
+            package_diagnostics: HashMap::new(),
         }
     }
 }
@@ -71,11 +91,18 @@ impl NargoLspService {
         router
             .request::<request::Initialize, _>(on_initialize)
             .request::<request::Formatting, _>(on_formatting)
+            .request::<request::RangeFormatting, _>(on_range_formatting)
+            .request::<request::WillSaveWaitUntil, _>(on_will_save_wait_until)
             .request::<request::Shutdown, _>(on_shutdown)
             .request::<request::CodeLens, _>(on_code_lens_request)
             .request::<request::NargoTests, _>(on_tests_request)
             .request::<request::NargoTestRun, _>(on_test_run_request)
             .request::<request::NargoProfileRun, _>(on_profile_run_request)
+            .request::<request::GotoDefinition, _>(on_goto_definition_request)
+            .request::<request::HoverRequest, _>(on_hover_request)
+            .request::<request::Rename, _>(on_rename_request)
+            .request::<request::InlayHintRequest, _>(on_inlay_hint_request)
+            .request::<request::SemanticTokensFullRequest, _>(on_semantic_tokens_full_request)
             .notification::<notification::Initialized>(on_initialized)
             .notification::<notification::DidChangeConfiguration>(on_did_change_configuration)
             .notification::<notification::DidOpenTextDocument>(on_did_open_text_document)
@@ -176,6 +203,56 @@ fn byte_span_to_range<'a, F: files::Files<'a> + ?Sized>(
     }
 }
 
+/// Converts a location in the compiled sources into one the client can open.
+fn to_lsp_location(
+    context: &Context,
+    location: noirc_errors::Location,
+) -> Option<lsp_types::Location> {
+    let uri = Url::from_file_path(context.file_manager.path(location.file)).ok()?;
+    let files = context.file_manager.as_file_map();
+    let range = byte_span_to_range(files, location.file, location.span.into())?;
+    Some(lsp_types::Location { uri, range })
+}
+
+/// Converts a position sent by the client, whose character offset is counted in UTF-16 code units,
+/// into a byte index within the file.
+fn position_to_byte_index<'a, F: files::Files<'a> + ?Sized>(
+    files: &'a F,
+    file_id: F::FileId,
+    position: &Position,
+) -> Option<usize> {
+    let line_range = files.line_range(file_id, position.line as usize).ok()?;
+    let source = files.source(file_id).ok()?;
+    let line = source.as_ref().get(line_range.clone())?;
+
+    let mut utf16_offset = 0;
+    for (byte_offset, char) in line.char_indices() {
+        if utf16_offset >= position.character as usize {
+            return Some(line_range.start + byte_offset);
+        }
+        utf16_offset += char.len_utf16();
+    }
+    Some(line_range.end)
+}
+
+/// Returns a reader for the workspace's sources which reads the documents open in the client from
+/// their unsaved contents, and every other file from disk.
+fn open_documents_reader(state: &LspState) -> Box<FileReader> {
+    let open_documents: HashMap<PathBuf, String> = state
+        .input_files
+        .iter()
+        .filter_map(|(uri, text)| {
+            let path = Url::parse(uri).ok()?.to_file_path().ok()?;
+            Some((path, text.clone()))
+        })
+        .collect();
+
+    Box::new(move |path: &Path| match open_documents.get(path) {
+        Some(text) => Ok(text.clone()),
+        None => get_non_stdlib_asset(path),
+    })
+}
+
 cfg_if::cfg_if! {
     if #[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))] {
         use wasm_bindgen::{prelude::*, JsValue};
@@ -1,19 +1,32 @@
-use std::ops::ControlFlow;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::ControlFlow,
+    path::Path,
+};
 
 use async_lsp::{ErrorCode, LanguageClient, ResponseError};
-use nargo::prepare_package;
+use lsp_types::Location;
+use nargo::{
+    package::{Dependency, Package},
+    prepare_package,
+};
 use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::{check_crate, NOIR_ARTIFACT_VERSION_STRING};
-use noirc_errors::{DiagnosticKind, FileDiagnostic};
+use noirc_driver::{check_crate, compile_no_check, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::{debug_info::OpCodesCount, Applicability, DiagnosticKind, FileDiagnostic};
+use noirc_frontend::{graph::CrateId, hir::Context};
+use serde_json::json;
 
 use crate::types::{
-    notification, Diagnostic, DiagnosticSeverity, DidChangeConfigurationParams,
-    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, InitializedParams, LogMessageParams, MessageType, NargoPackageTests,
-    PublishDiagnosticsParams,
+    notification, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity,
+    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializedParams, LogMessageParams,
+    MessageType, NargoPackageTests, NumberOrString, Position, PublishDiagnosticsParams, Url,
 };
 
-use crate::{byte_span_to_range, get_non_stdlib_asset, get_package_tests_in_crate, LspState};
+use crate::{
+    byte_span_to_range, get_package_tests_in_crate, open_documents_reader, to_lsp_location,
+    LspState,
+};
 
 pub(super) fn on_initialized(
     _state: &mut LspState,
@@ -43,7 +56,7 @@ pub(super) fn on_did_change_text_document(
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
     let text = params.content_changes.into_iter().next().unwrap().text;
     state.input_files.insert(params.text_document.uri.to_string(), text);
-    ControlFlow::Continue(())
+    process_noir_document(state, &params.text_document.uri, false)
 }
 
 pub(super) fn on_did_close_text_document(
@@ -58,7 +71,21 @@ pub(super) fn on_did_save_text_document(
     state: &mut LspState,
     params: DidSaveTextDocumentParams,
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
-    let file_path = match params.text_document.uri.to_file_path() {
+    process_noir_document(state, &params.text_document.uri, true)
+}
+
+/// Rechecks the packages of the workspace which contain the document at `uri`, either directly or
+/// through their dependencies, and publishes their diagnostics. Packages which haven't been
+/// checked yet are checked as well, and files whose sources haven't changed aren't parsed again.
+///
+/// When the document has been `saved`, the programs and tests of the rechecked packages are
+/// refreshed too.
+fn process_noir_document(
+    state: &mut LspState,
+    uri: &Url,
+    saved: bool,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    let file_path = match uri.to_file_path() {
         Ok(file_path) => file_path,
         Err(()) => {
             return ControlFlow::Break(Err(ResponseError::new(
@@ -108,15 +135,41 @@ pub(super) fn on_did_save_text_document(
         }
     };
 
-    let diagnostics: Vec<_> = workspace
+    let packages: Vec<_> = workspace
         .into_iter()
-        .flat_map(|package| -> Vec<Diagnostic> {
-            let (mut context, crate_id) = prepare_package(package, Box::new(get_non_stdlib_asset));
+        .filter(|package| {
+            !state.package_diagnostics.contains_key(&package.root_dir)
+                || package_contains_file(package, &file_path)
+        })
+        .collect();
+
+    let mut changed_uris = HashSet::new();
+    for package in packages {
+        let (mut context, crate_id) = prepare_package(package, open_documents_reader(state));
+        context.parsed_files = Some(std::mem::take(&mut state.parsed_files));
 
-            let file_diagnostics = match check_crate(&mut context, crate_id, false) {
-                Ok(((), warnings)) => warnings,
-                Err(errors_and_warnings) => errors_and_warnings,
-            };
+        let (checked, file_diagnostics) = match check_crate(&mut context, crate_id, false) {
+            Ok(((), warnings)) => (true, warnings),
+            Err(errors_and_warnings) => (false, errors_and_warnings),
+        };
+
+        let mut diagnostics: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
+        for file_diagnostic in file_diagnostics {
+            if let Some((uri, diagnostic)) = to_lsp_diagnostic(&context, file_diagnostic) {
+                diagnostics.entry(uri).or_default().push(diagnostic);
+            }
+        }
+        let previous_diagnostics =
+            state.package_diagnostics.insert(package.root_dir.clone(), diagnostics);
+        changed_uris.extend(previous_diagnostics.unwrap_or_default().into_keys());
+        changed_uris.extend(state.package_diagnostics[&package.root_dir].keys().cloned());
+
+        if saved {
+            let mut function_opcode_counts = HashMap::new();
+            if checked && package.is_binary() {
+                count_function_opcodes(&context, crate_id, &mut function_opcode_counts);
+            }
+            state.function_opcode_counts.insert(package.root_dir.clone(), function_opcode_counts);
 
             // We don't add test headings for a package if it contains no `#[test]` functions
             if let Some(tests) = get_package_tests_in_crate(&context, &crate_id, &package.name) {
@@ -125,52 +178,148 @@ pub(super) fn on_did_save_text_document(
                     tests,
                 });
             }
+        }
+
+        state.parsed_files = context.parsed_files.take().unwrap_or_default();
+    }
+
+    if saved {
+        // We need to refresh lenses when we compile since that's the only time they can be accurately reflected
+        std::mem::drop(state.client.code_lens_refresh(()));
+        std::mem::drop(state.client.inlay_hint_refresh(()));
+    }
+
+    // A document's diagnostics are those reported by every package which includes it, so a
+    // file shared between packages is published with the diagnostics of each of them.
+    for uri in changed_uris {
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        for diagnostic in state
+            .package_diagnostics
+            .values()
+            .filter_map(|package_diagnostics| package_diagnostics.get(&uri))
+            .flatten()
+        {
+            if !diagnostics.contains(diagnostic) {
+                diagnostics.push(diagnostic.clone());
+            }
+        }
+
+        let _ = state.client.publish_diagnostics(PublishDiagnosticsParams {
+            uri,
+            version: None,
+            diagnostics,
+        });
+    }
 
-            let fm = &context.file_manager;
-            let files = fm.as_file_map();
-
-            file_diagnostics
-                .into_iter()
-                .filter_map(|FileDiagnostic { file_id, diagnostic, call_stack: _ }| {
-                    // Ignore diagnostics for any file that wasn't the file we saved
-                    // TODO: In the future, we could create "related" diagnostics for these files
-                    if fm.path(file_id) != file_path {
-                        return None;
-                    }
-
-                    // TODO: Should this be the first item in secondaries? Should we bail when we find a range?
-                    let range = diagnostic
-                        .secondaries
-                        .into_iter()
-                        .filter_map(|sec| byte_span_to_range(files, file_id, sec.span.into()))
-                        .last()
-                        .unwrap_or_default();
-
-                    let severity = match diagnostic.kind {
-                        DiagnosticKind::Error => DiagnosticSeverity::ERROR,
-                        DiagnosticKind::Warning => DiagnosticSeverity::WARNING,
-                    };
-                    Some(Diagnostic {
-                        range,
-                        severity: Some(severity),
-                        message: diagnostic.message,
-                        ..Default::default()
-                    })
-                })
-                .collect()
+    ControlFlow::Continue(())
+}
+
+/// Whether the file at `file_path` belongs to `package` or to one of its dependencies, in which
+/// case it is compiled as part of the package.
+fn package_contains_file(package: &Package, file_path: &Path) -> bool {
+    file_path.starts_with(&package.root_dir)
+        || package.dependencies.values().any(|dependency| match dependency {
+            Dependency::Local { package } | Dependency::Remote { package } => {
+                package_contains_file(package, file_path)
+            }
+        })
+}
+
+/// Converts a diagnostic found while checking a package into one for the client, along with the
+/// document it is reported in.
+fn to_lsp_diagnostic(
+    context: &Context,
+    FileDiagnostic { file_id, diagnostic, call_stack: _ }: FileDiagnostic,
+) -> Option<(Url, Diagnostic)> {
+    let fm = &context.file_manager;
+    let files = fm.as_file_map();
+
+    // Diagnostics in the standard library can't be opened by the client
+    let uri = Url::from_file_path(fm.path(file_id)).ok()?;
+
+    // Labels pointing into other files, such as at the definition of an item, are
+    // reported as related information.
+    let (labels, other_file_labels): (Vec<_>, Vec<_>) = diagnostic
+        .secondaries
+        .iter()
+        .partition(|sec| sec.file.map_or(true, |file| file == file_id));
+
+    // TODO: Should this be the first item in secondaries? Should we bail when we find a range?
+    let range = labels
+        .into_iter()
+        .filter_map(|sec| byte_span_to_range(files, file_id, sec.span.into()))
+        .last()
+        .unwrap_or_default();
+
+    let related_information: Vec<_> = other_file_labels
+        .into_iter()
+        .filter_map(|sec| {
+            let file = sec.file?;
+            let uri = Url::from_file_path(fm.path(file)).ok()?;
+            let range = byte_span_to_range(files, file, sec.span.into())?;
+            Some(DiagnosticRelatedInformation {
+                location: Location { uri, range },
+                message: sec.message.clone(),
+            })
         })
         .collect();
 
-    // We need to refresh lenses when we compile since that's the only time they can be accurately reflected
-    std::mem::drop(state.client.code_lens_refresh(()));
+    // Suggestions are passed on to the client so that it can offer to apply them.
+    let suggestions: Vec<_> = diagnostic
+        .suggestions()
+        .iter()
+        .filter_map(|suggestion| {
+            let range = byte_span_to_range(files, file_id, suggestion.span.into())?;
+            Some(json!({
+                "message": suggestion.message,
+                "range": range,
+                "replacement": suggestion.replacement,
+                "machineApplicable":
+                    suggestion.applicability == Applicability::MachineApplicable,
+            }))
+        })
+        .collect();
 
-    let _ = state.client.publish_diagnostics(PublishDiagnosticsParams {
-        uri: params.text_document.uri,
-        version: None,
-        diagnostics,
-    });
+    let severity = match diagnostic.kind {
+        DiagnosticKind::Error => DiagnosticSeverity::ERROR,
+        DiagnosticKind::Warning => DiagnosticSeverity::WARNING,
+    };
+    let diagnostic = Diagnostic {
+        range,
+        severity: Some(severity),
+        code: diagnostic.code.map(|code| NumberOrString::String(code.to_owned())),
+        message: diagnostic.message,
+        related_information: (!related_information.is_empty()).then_some(related_information),
+        data: (!suggestions.is_empty()).then(|| json!({ "suggestions": suggestions })),
+        ..Default::default()
+    };
+    Some((uri, diagnostic))
+}
 
-    ControlFlow::Continue(())
+/// Compiles the program of a crate which has been checked without errors, recording the opcodes
+/// generated by each of its functions so they can be shown as inlay hints.
+fn count_function_opcodes(
+    context: &Context,
+    crate_id: CrateId,
+    function_opcode_counts: &mut HashMap<Url, Vec<(Position, OpCodesCount)>>,
+) {
+    let Some(main) = context.get_main_function(&crate_id) else {
+        return;
+    };
+    let Ok(program) = compile_no_check(context, &CompileOptions::default(), main, None, false)
+    else {
+        return;
+    };
+
+    for (function, opcodes_count) in program.debug.count_function_opcodes() {
+        let Some(body) = to_lsp_location(context, function.location) else {
+            continue;
+        };
+        // The hint is placed just after the opening brace of the function's body
+        let position =
+            Position { line: body.range.start.line, character: body.range.start.character + 1 };
+        function_opcode_counts.entry(body.uri).or_default().push((position, opcodes_count));
+    }
 }
 
 pub(super) fn on_exit(
@@ -16,6 +16,8 @@ use crate::{
 const ARROW: &str = "▶\u{fe0e}";
 const TEST_COMMAND: &str = "nargo.test";
 const TEST_CODELENS_TITLE: &str = "Run Test";
+const DEBUG_TEST_COMMAND: &str = "nargo.debug.test";
+const DEBUG_TEST_CODELENS_TITLE: &str = "Debug Test";
 const COMPILE_COMMAND: &str = "nargo.compile";
 const COMPILE_CODELENS_TITLE: &str = "Compile";
 const INFO_COMMAND: &str = "nargo.info";
@@ -106,21 +108,31 @@ fn on_code_lens_request_inner(
             let range =
                 byte_span_to_range(files, file_id, location.span.into()).unwrap_or_default();
 
+            let test_args = [
+                package_selection_args(&workspace, package),
+                vec!["--exact".into(), func_name.into()],
+            ]
+            .concat();
+
             let test_command = Command {
                 title: with_arrow(TEST_CODELENS_TITLE),
                 command: TEST_COMMAND.into(),
-                arguments: Some(
-                    [
-                        package_selection_args(&workspace, package),
-                        vec!["--exact".into(), func_name.into()],
-                    ]
-                    .concat(),
-                ),
+                arguments: Some(test_args.clone()),
             };
 
             let test_lens = CodeLens { range, command: Some(test_command), data: None };
 
             lenses.push(test_lens);
+
+            let debug_test_command = Command {
+                title: DEBUG_TEST_CODELENS_TITLE.to_string(),
+                command: DEBUG_TEST_COMMAND.into(),
+                arguments: Some(test_args),
+            };
+
+            let debug_test_lens = CodeLens { range, command: Some(debug_test_command), data: None };
+
+            lenses.push(debug_test_lens);
         }
 
         if package.is_binary() {
@@ -0,0 +1,88 @@
+use std::future::{self, Future};
+
+use async_lsp::{ErrorCode, ResponseError};
+use nargo_fmt::Config;
+use noirc_frontend::parser::ParserError;
+use similar::{DiffTag, TextDiff};
+
+use crate::{
+    types::{
+        DocumentFormattingParams, DocumentRangeFormattingParams, Position, Range, TextEdit, Url,
+        WillSaveTextDocumentParams,
+    },
+    LspState,
+};
+
+pub(crate) fn on_formatting(
+    state: &mut LspState,
+    params: DocumentFormattingParams,
+) -> impl Future<Output = Result<Option<Vec<TextEdit>>, ResponseError>> {
+    future::ready(format_document(state, &params.text_document.uri, None))
+}
+
+pub(crate) fn on_range_formatting(
+    state: &mut LspState,
+    params: DocumentRangeFormattingParams,
+) -> impl Future<Output = Result<Option<Vec<TextEdit>>, ResponseError>> {
+    future::ready(format_document(state, &params.text_document.uri, Some(params.range)))
+}
+
+pub(crate) fn on_will_save_wait_until(
+    state: &mut LspState,
+    params: WillSaveTextDocumentParams,
+) -> impl Future<Output = Result<Option<Vec<TextEdit>>, ResponseError>> {
+    let edits = if state.format_on_save {
+        format_document(state, &params.text_document.uri, None)
+    } else {
+        Ok(None)
+    };
+    future::ready(edits)
+}
+
+/// Formats an open document with the project's formatter config, returning edits for the lines
+/// which change. If a `range` is given, only changes to lines within it are returned.
+fn format_document(
+    state: &LspState,
+    uri: &Url,
+    range: Option<Range>,
+) -> Result<Option<Vec<TextEdit>>, ResponseError> {
+    let Some(source) = state.input_files.get(&uri.to_string()) else {
+        return Ok(None);
+    };
+
+    let (module, errors) = noirc_frontend::parse_program(source);
+    if !errors.iter().all(ParserError::is_warning) {
+        return Ok(None);
+    }
+
+    let config = match &state.root_path {
+        Some(root_path) => Config::read(root_path)
+            .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err.to_string()))?,
+        None => Config::default(),
+    };
+    let formatted = nargo_fmt::format(source, module, &config);
+
+    let diff = TextDiff::from_lines(source.as_str(), &formatted);
+    let edits = diff
+        .ops()
+        .iter()
+        .filter(|op| op.tag() != DiffTag::Equal)
+        .filter(|op| {
+            let lines = op.old_range();
+            range.map_or(true, |range| {
+                range.start.line as usize <= lines.start && lines.end <= range.end.line as usize + 1
+            })
+        })
+        .map(|op| {
+            let lines = op.old_range();
+            let start = Position { line: lines.start as u32, character: 0 };
+            let end = Position { line: lines.end as u32, character: 0 };
+            TextEdit {
+                range: Range::new(start, end),
+                new_text: diff.new_slices()[op.new_range()].concat(),
+            }
+        })
+        .collect();
+
+    Ok(Some(edits))
+}
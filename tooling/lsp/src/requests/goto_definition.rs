@@ -0,0 +1,30 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+
+use crate::{
+    to_lsp_location,
+    types::{GotoDefinitionParams, GotoDefinitionResponse},
+    LspState,
+};
+
+use super::process_request;
+
+pub(crate) fn on_goto_definition_request(
+    state: &mut LspState,
+    params: GotoDefinitionParams,
+) -> impl Future<Output = Result<Option<GotoDefinitionResponse>, ResponseError>> {
+    future::ready(on_goto_definition_request_inner(state, params))
+}
+
+fn on_goto_definition_request_inner(
+    state: &LspState,
+    params: GotoDefinitionParams,
+) -> Result<Option<GotoDefinitionResponse>, ResponseError> {
+    process_request(state, params.text_document_position_params, |location, context| {
+        let interner = &context.def_interner;
+        let expr_id = interner.find_expression_at(location)?;
+        let definition_location = interner.referenced_location(expr_id)?;
+        to_lsp_location(context, definition_location).map(GotoDefinitionResponse::Scalar)
+    })
+}
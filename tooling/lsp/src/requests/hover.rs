@@ -0,0 +1,109 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::FileId;
+use noirc_errors::Location;
+use noirc_frontend::{
+    hir::Context,
+    hir_def::expr::HirExpression,
+    node_interner::{DefinitionKind, ExprId, NodeInterner},
+    Type,
+};
+
+use crate::{
+    types::{Hover, HoverContents, HoverParams, MarkupContent, MarkupKind},
+    LspState,
+};
+
+use super::process_request;
+
+pub(crate) fn on_hover_request(
+    state: &mut LspState,
+    params: HoverParams,
+) -> impl Future<Output = Result<Option<Hover>, ResponseError>> {
+    future::ready(on_hover_request_inner(state, params))
+}
+
+fn on_hover_request_inner(
+    state: &LspState,
+    params: HoverParams,
+) -> Result<Option<Hover>, ResponseError> {
+    process_request(state, params.text_document_position_params, |location, context| {
+        let interner = &context.def_interner;
+        let expr_id = interner.find_expression_at(location)?;
+
+        let mut value = format!("```noir\n{}\n```", describe_expression(interner, expr_id));
+        if let Some(docs) = interner
+            .referenced_location(expr_id)
+            .and_then(|location| doc_comments(context, location))
+        {
+            value.push_str("\n\n");
+            value.push_str(&docs);
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value }),
+            range: None,
+        })
+    })
+}
+
+/// Describes the item an expression refers to, using the type it has been resolved to at this use.
+fn describe_expression(interner: &NodeInterner, expr_id: ExprId) -> String {
+    let typ = interner.id_type(expr_id).follow_bindings();
+    match interner.expression(&expr_id) {
+        HirExpression::Ident(ident) => {
+            let definition = interner.definition(ident.id);
+            let name = &definition.name;
+            match (&definition.kind, typ) {
+                (DefinitionKind::Function(_), Type::Function(args, ret, _)) => {
+                    let args = args.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+                    if matches!(*ret, Type::Unit) {
+                        format!("fn {name}({args})")
+                    } else {
+                        format!("fn {name}({args}) -> {ret}")
+                    }
+                }
+                (DefinitionKind::Global(_), typ) => format!("global {name}: {typ}"),
+                (DefinitionKind::Local(_), typ) if definition.mutable => {
+                    format!("let mut {name}: {typ}")
+                }
+                (DefinitionKind::Local(_), typ) => format!("let {name}: {typ}"),
+                (_, typ) => format!("{name}: {typ}"),
+            }
+        }
+        HirExpression::Constructor(_) => format!("struct {typ}"),
+        HirExpression::MemberAccess(access) => format!("{}: {typ}", access.rhs),
+        _ => typ.to_string(),
+    }
+}
+
+/// Collects the `///` comments directly above the definition at `location`.
+fn doc_comments(context: &Context, location: Location) -> Option<String> {
+    let source = source_before(context, location.file, location.span.start() as usize)?;
+
+    let mut lines: Vec<&str> = source
+        .lines()
+        .rev()
+        .skip(1)
+        .map(str::trim)
+        .skip_while(|line| line.starts_with("#["))
+        .map_while(|line| line.strip_prefix("///"))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect();
+    lines.reverse();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// Returns the source of `file` up to the end of the line containing `byte_index`.
+fn source_before(context: &Context, file: FileId, byte_index: usize) -> Option<&str> {
+    let source = context.file_manager.as_file_map().get_file(file)?.source();
+    let line_end =
+        source.get(byte_index..)?.find('\n').map_or(source.len(), |end| byte_index + end);
+    source.get(..line_end)
+}
@@ -0,0 +1,124 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::FileId;
+use noirc_errors::debug_info::OpCodesCount;
+use noirc_frontend::{
+    hir::Context,
+    hir_def::{expr::HirIdent, stmt::HirPattern},
+    Type, TypeVariableKind,
+};
+
+use crate::{
+    byte_span_to_range,
+    types::{
+        InlayHint, InlayHintKind, InlayHintLabel, InlayHintParams, InlayHintTooltip, Position,
+    },
+    LspState,
+};
+
+use super::process_file_request;
+
+pub(crate) fn on_inlay_hint_request(
+    state: &mut LspState,
+    params: InlayHintParams,
+) -> impl Future<Output = Result<Option<Vec<InlayHint>>, ResponseError>> {
+    future::ready(on_inlay_hint_request_inner(state, params))
+}
+
+fn on_inlay_hint_request_inner(
+    state: &LspState,
+    params: InlayHintParams,
+) -> Result<Option<Vec<InlayHint>>, ResponseError> {
+    let uri = &params.text_document.uri;
+    let mut hints =
+        process_file_request(state, uri, |file, context| Some(type_hints(context, file)))?
+            .unwrap_or_default();
+
+    // A file shared by several programs in the workspace shows the counts from each of them
+    let opcode_hints = state
+        .function_opcode_counts
+        .values()
+        .filter_map(|function_opcode_counts| function_opcode_counts.get(uri))
+        .flatten()
+        .map(|(position, opcodes_count)| opcodes_count_hint(*position, opcodes_count));
+    hints.extend(opcode_hints);
+
+    let range = params.range;
+    hints.retain(|hint| range.start <= hint.position && hint.position <= range.end);
+    Ok(Some(hints))
+}
+
+/// Hints the types inferred for the variables of `let` statements without a type annotation.
+fn type_hints(context: &Context, file: FileId) -> Vec<InlayHint> {
+    let interner = &context.def_interner;
+    let files = context.file_manager.as_file_map();
+
+    // Unannotated `let` statements are given a fresh type variable during name resolution
+    let inferred_lets = interner.let_statements().filter(|let_stmt| {
+        matches!(let_stmt.r#type, Type::TypeVariable(_, TypeVariableKind::Normal))
+    });
+
+    let mut hints = Vec::new();
+    for let_stmt in inferred_lets {
+        let mut idents = Vec::new();
+        collect_pattern_idents(&let_stmt.pattern, &mut idents);
+
+        for ident in idents.into_iter().filter(|ident| ident.location.file == file) {
+            let typ = interner.id_type(ident.id).follow_bindings();
+            if typ == Type::Error {
+                continue;
+            }
+            let Some(range) = byte_span_to_range(files, file, ident.location.span.into()) else {
+                continue;
+            };
+
+            hints.push(InlayHint {
+                position: range.end,
+                label: InlayHintLabel::String(format!(": {typ}")),
+                kind: Some(InlayHintKind::TYPE),
+                text_edits: None,
+                tooltip: None,
+                padding_left: None,
+                padding_right: None,
+                data: None,
+            });
+        }
+    }
+    hints
+}
+
+fn collect_pattern_idents(pattern: &HirPattern, idents: &mut Vec<HirIdent>) {
+    match pattern {
+        HirPattern::Identifier(ident) => idents.push(*ident),
+        HirPattern::Mutable(pattern, _) => collect_pattern_idents(pattern, idents),
+        HirPattern::Tuple(patterns, _) => {
+            patterns.iter().for_each(|pattern| collect_pattern_idents(pattern, idents));
+        }
+        HirPattern::Struct(_, fields, _) => {
+            fields.iter().for_each(|(_, pattern)| collect_pattern_idents(pattern, idents));
+        }
+    }
+}
+
+/// Hints the number of opcodes a function compiled to, placed just inside the start of its body.
+fn opcodes_count_hint(position: Position, opcodes_count: &OpCodesCount) -> InlayHint {
+    let mut label = format!("{} ACIR opcodes", opcodes_count.acir_size);
+    if opcodes_count.brillig_size > 0 {
+        label.push_str(&format!(", {} Brillig opcodes", opcodes_count.brillig_size));
+    }
+
+    InlayHint {
+        position,
+        label: InlayHintLabel::String(label),
+        kind: None,
+        text_edits: None,
+        tooltip: Some(InlayHintTooltip::String(
+            "Opcodes generated by this function and its callees in the last successful compile"
+                .to_string(),
+        )),
+        padding_left: Some(true),
+        padding_right: None,
+        data: None,
+    }
+}
@@ -1,11 +1,23 @@
 use std::future::Future;
 
-use crate::types::{CodeLensOptions, InitializeParams};
-use async_lsp::ResponseError;
-use lsp_types::{Position, TextDocumentSyncCapability, TextDocumentSyncKind};
-use nargo_fmt::Config;
+use crate::types::{
+    CodeLensOptions, InitializeParams, SemanticTokensFullOptions, SemanticTokensOptions,
+    SemanticTokensServerCapabilities, TextDocumentPositionParams, Url,
+};
+use async_lsp::{ErrorCode, ResponseError};
+use fm::FileId;
+use lsp_types::{
+    HoverProviderCapability, OneOf, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TextDocumentSyncSaveOptions,
+};
+use nargo::prepare_package;
+use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{check_crate, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::{Location, Span};
+use noirc_frontend::hir::Context;
 
 use crate::{
+    get_non_stdlib_asset, position_to_byte_index,
     types::{InitializeResult, NargoCapability, NargoTestsOptions, ServerCapabilities},
     LspState,
 };
@@ -21,13 +33,27 @@ use crate::{
 // and params passed in.
 
 mod code_lens_request;
+mod formatting;
+mod goto_definition;
+mod hover;
+mod inlay_hint;
 mod profile_run;
+mod rename;
+mod semantic_tokens;
 mod test_run;
 mod tests;
 
 pub(crate) use {
-    code_lens_request::on_code_lens_request, profile_run::on_profile_run_request,
-    test_run::on_test_run_request, tests::on_tests_request,
+    code_lens_request::on_code_lens_request,
+    formatting::{on_formatting, on_range_formatting, on_will_save_wait_until},
+    goto_definition::on_goto_definition_request,
+    hover::on_hover_request,
+    inlay_hint::on_inlay_hint_request,
+    profile_run::on_profile_run_request,
+    rename::on_rename_request,
+    semantic_tokens::on_semantic_tokens_full_request,
+    test_run::on_test_run_request,
+    tests::on_tests_request,
 };
 
 pub(crate) fn on_initialize(
@@ -35,12 +61,35 @@ pub(crate) fn on_initialize(
     params: InitializeParams,
 ) -> impl Future<Output = Result<InitializeResult, ResponseError>> {
     state.root_path = params.root_uri.and_then(|root_uri| root_uri.to_file_path().ok());
-
-    async {
-        let text_document_sync = TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL);
+    state.format_on_save = params
+        .initialization_options
+        .as_ref()
+        .and_then(|options| options.get("formatOnSave")?.as_bool())
+        .unwrap_or(false);
+    let format_on_save = state.format_on_save;
+
+    async move {
+        // Documents are only formatted on save if the client asks for it
+        let text_document_sync = if format_on_save {
+            TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                will_save_wait_until: Some(true),
+                save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                ..Default::default()
+            })
+        } else {
+            TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)
+        };
 
         let code_lens = CodeLensOptions { resolve_provider: Some(false) };
 
+        let semantic_tokens = SemanticTokensOptions {
+            legend: semantic_tokens::semantic_tokens_legend(),
+            full: Some(SemanticTokensFullOptions::Bool(true)),
+            ..Default::default()
+        };
+
         let nargo = NargoCapability {
             tests: Some(NargoTestsOptions {
                 fetch: Some(true),
@@ -54,6 +103,14 @@ pub(crate) fn on_initialize(
                 text_document_sync: Some(text_document_sync),
                 code_lens_provider: Some(code_lens),
                 document_formatting_provider: true,
+                document_range_formatting_provider: Some(OneOf::Left(true)),
+                definition_provider: Some(OneOf::Left(true)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                rename_provider: Some(OneOf::Left(true)),
+                inlay_hint_provider: Some(OneOf::Left(true)),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(semantic_tokens),
+                ),
                 nargo: Some(nargo),
             },
             server_info: None,
@@ -61,40 +118,68 @@ pub(crate) fn on_initialize(
     }
 }
 
-pub(crate) fn on_formatting(
-    state: &mut LspState,
-    params: lsp_types::DocumentFormattingParams,
-) -> impl Future<Output = Result<Option<Vec<lsp_types::TextEdit>>, ResponseError>> {
-    std::future::ready(on_formatting_inner(state, params))
+/// Type checks each package in the workspace which contains the document of a request, calling
+/// `callback` with the location of the request's cursor in the checked sources.
+///
+/// Returns the first `Some` produced by `callback`, or `None` if no package produced one.
+fn process_request<T>(
+    state: &LspState,
+    text_document_position_params: TextDocumentPositionParams,
+    mut callback: impl FnMut(Location, &Context) -> Option<T>,
+) -> Result<Option<T>, ResponseError> {
+    let position = text_document_position_params.position;
+    process_file_request(
+        state,
+        &text_document_position_params.text_document.uri,
+        |file, context| {
+            let files = context.file_manager.as_file_map();
+            let byte_index = position_to_byte_index(files, file, &position)?;
+            let location = Location::new(Span::single_char(byte_index as u32), file);
+            callback(location, context)
+        },
+    )
 }
 
-fn on_formatting_inner(
+/// Type checks each package in the workspace which contains the document at `uri`, calling
+/// `callback` with the id of the document in the checked sources.
+///
+/// Returns the first `Some` produced by `callback`, or `None` if no package produced one.
+fn process_file_request<T>(
     state: &LspState,
-    params: lsp_types::DocumentFormattingParams,
-) -> Result<Option<Vec<lsp_types::TextEdit>>, ResponseError> {
-    let path = params.text_document.uri.to_string();
-
-    if let Some(source) = state.input_files.get(&path) {
-        let (module, errors) = noirc_frontend::parse_program(source);
-        if !errors.is_empty() {
-            return Ok(None);
-        }
-
-        let new_text = nargo_fmt::format(source, module, &Config::default());
-
-        let start_position = Position { line: 0, character: 0 };
-        let end_position = Position {
-            line: source.lines().count() as u32,
-            character: source.chars().count() as u32,
+    uri: &Url,
+    mut callback: impl FnMut(FileId, &Context) -> Option<T>,
+) -> Result<Option<T>, ResponseError> {
+    let file_path = uri.to_file_path().map_err(|_| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "URI is not a valid file path")
+    })?;
+
+    let root_path = state.root_path.as_deref().ok_or_else(|| {
+        ResponseError::new(ErrorCode::REQUEST_FAILED, "Could not find project root")
+    })?;
+
+    let toml_path = find_package_manifest(root_path, &file_path)
+        .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err))?;
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        PackageSelection::All,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )
+    .map_err(|err| ResponseError::new(ErrorCode::REQUEST_FAILED, err))?;
+
+    for package in &workspace {
+        let (mut context, crate_id) = prepare_package(package, Box::new(get_non_stdlib_asset));
+        // Errors elsewhere in the package shouldn't stop us from resolving what we can
+        let _ = check_crate(&mut context, crate_id, false);
+
+        let Some(file) = context.file_manager.name_to_id(file_path.clone()) else {
+            continue;
         };
-
-        Ok(Some(vec![lsp_types::TextEdit {
-            range: lsp_types::Range::new(start_position, end_position),
-            new_text,
-        }]))
-    } else {
-        Ok(None)
+        if let Some(result) = callback(file, &context) {
+            return Ok(Some(result));
+        }
     }
+
+    Ok(None)
 }
 
 pub(crate) fn on_shutdown(
@@ -108,7 +193,8 @@ pub(crate) fn on_shutdown(
 mod initialization {
     use async_lsp::ClientSocket;
     use lsp_types::{
-        CodeLensOptions, InitializeParams, TextDocumentSyncCapability, TextDocumentSyncKind,
+        CodeLensOptions, HoverProviderCapability, InitializeParams, OneOf,
+        TextDocumentSyncCapability, TextDocumentSyncKind,
     };
     use tokio::test;
 
@@ -131,6 +217,11 @@ mod initialization {
                 )),
                 code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(false) }),
                 document_formatting_provider: true,
+                definition_provider: Some(OneOf::Left(true)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                rename_provider: Some(OneOf::Left(true)),
+                inlay_hint_provider: Some(OneOf::Left(true)),
+                semantic_tokens_provider: Some(_),
                 ..
             }
         ));
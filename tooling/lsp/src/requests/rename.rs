@@ -0,0 +1,95 @@
+use std::{
+    collections::HashMap,
+    future::{self, Future},
+};
+
+use async_lsp::{ErrorCode, ResponseError};
+use noirc_frontend::{
+    lexer::Lexer,
+    token::{SpannedToken, Token},
+};
+
+use crate::{
+    to_lsp_location,
+    types::{RenameParams, TextEdit, Url, WorkspaceEdit},
+    LspState,
+};
+
+use super::process_request;
+
+pub(crate) fn on_rename_request(
+    state: &mut LspState,
+    params: RenameParams,
+) -> impl Future<Output = Result<Option<WorkspaceEdit>, ResponseError>> {
+    future::ready(on_rename_request_inner(state, params))
+}
+
+fn on_rename_request_inner(
+    state: &LspState,
+    params: RenameParams,
+) -> Result<Option<WorkspaceEdit>, ResponseError> {
+    let new_name = params.new_name;
+    if !is_identifier(&new_name) {
+        return Err(ResponseError::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("`{new_name}` is not a valid identifier"),
+        ));
+    }
+
+    // Each package in the workspace is checked separately, so the same edits may be found
+    // several times if the renamed item is mentioned by more than one package.
+    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    let error = process_request(state, params.text_document_position, |location, context| {
+        let interner = &context.def_interner;
+        let id = interner.find_reference_at(location)?;
+
+        let declaration = interner.declaration_location(&id)?;
+        if to_lsp_location(context, declaration).is_none() {
+            let name = interner.reference_name(&id);
+            return Some(format!("`{name}` is not declared in this workspace"));
+        }
+
+        if let Some(conflict) = interner.find_rename_conflict(&id, &new_name) {
+            let name = interner.reference_name(&id);
+            let message = match to_lsp_location(context, conflict) {
+                Some(conflict) => format!(
+                    "Renaming `{name}` to `{new_name}` would conflict with the `{new_name}` at {}:{}",
+                    conflict.uri,
+                    conflict.range.start.line + 1
+                ),
+                None => format!("Renaming `{name}` to `{new_name}` would conflict with another item"),
+            };
+            return Some(message);
+        }
+
+        let mentions = std::iter::once(declaration).chain(interner.references(&id).iter().copied());
+        for location in mentions.filter_map(|location| to_lsp_location(context, location)) {
+            let edit = TextEdit { range: location.range, new_text: new_name.clone() };
+            let edits = changes.entry(location.uri).or_default();
+            if !edits.contains(&edit) {
+                edits.push(edit);
+            }
+        }
+        None
+    })?;
+
+    if let Some(message) = error {
+        return Err(ResponseError::new(ErrorCode::REQUEST_FAILED, message));
+    }
+
+    if changes.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(WorkspaceEdit { changes: Some(changes), ..Default::default() }))
+    }
+}
+
+/// Checks that `name` lexes as a single identifier, and so isn't a keyword.
+fn is_identifier(name: &str) -> bool {
+    let (tokens, errors) = Lexer::lex(name);
+    errors.is_empty()
+        && matches!(
+            tokens.0.iter().map(SpannedToken::token).collect::<Vec<_>>().as_slice(),
+            [Token::Ident(ident), Token::EOF] if ident == name
+        )
+}
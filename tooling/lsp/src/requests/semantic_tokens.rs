@@ -0,0 +1,164 @@
+use std::future::{self, Future};
+
+use async_lsp::ResponseError;
+use fm::FileId;
+use noirc_frontend::{
+    hir::Context,
+    node_interner::{DefinitionKind, NodeInterner, ReferenceId},
+    token::FunctionAttribute,
+};
+
+use crate::{
+    byte_span_to_range,
+    types::{
+        Position, Range, SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens,
+        SemanticTokensLegend, SemanticTokensParams, SemanticTokensResult,
+    },
+    LspState,
+};
+
+use super::process_file_request;
+
+/// The types of token the server reports, in the order they are indexed by.
+const TOKEN_TYPES: [SemanticTokenType; 5] = [
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::TYPE_PARAMETER,
+];
+
+const FUNCTION: u32 = 0;
+const VARIABLE: u32 = 1;
+const STRUCT: u32 = 2;
+const PROPERTY: u32 = 3;
+const TYPE_PARAMETER: u32 = 4;
+
+/// The modifiers the server reports, in the order of their bits in a token's modifier set.
+const TOKEN_MODIFIERS: [SemanticTokenModifier; 6] = [
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::READONLY,
+    SemanticTokenModifier::STATIC,
+    SemanticTokenModifier::new("mutable"),
+    SemanticTokenModifier::new("unconstrained"),
+    SemanticTokenModifier::new("oracle"),
+];
+
+const DECLARATION: u32 = 1 << 0;
+const READONLY: u32 = 1 << 1;
+const STATIC: u32 = 1 << 2;
+const MUTABLE: u32 = 1 << 3;
+const UNCONSTRAINED: u32 = 1 << 4;
+const ORACLE: u32 = 1 << 5;
+
+pub(crate) fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+pub(crate) fn on_semantic_tokens_full_request(
+    state: &mut LspState,
+    params: SemanticTokensParams,
+) -> impl Future<Output = Result<Option<SemanticTokensResult>, ResponseError>> {
+    future::ready(on_semantic_tokens_full_request_inner(state, params))
+}
+
+fn on_semantic_tokens_full_request_inner(
+    state: &LspState,
+    params: SemanticTokensParams,
+) -> Result<Option<SemanticTokensResult>, ResponseError> {
+    process_file_request(state, &params.text_document.uri, |file, context| {
+        let data = encode_tokens(semantic_tokens(context, file));
+        Some(SemanticTokensResult::Tokens(SemanticTokens { result_id: None, data }))
+    })
+}
+
+/// Collects the range, type and modifiers of each name in `file` which refers to an item,
+/// sorted by where they start.
+fn semantic_tokens(context: &Context, file: FileId) -> Vec<(Range, u32, u32)> {
+    let interner = &context.def_interner;
+    let files = context.file_manager.as_file_map();
+
+    let references = interner.reference_locations().filter_map(|(id, location)| {
+        let (token_type, mut modifiers) = classify(interner, &id)?;
+        if interner.declaration_location(&id) == Some(location) {
+            modifiers |= DECLARATION;
+        }
+        Some((location, token_type, modifiers))
+    });
+    let type_parameters =
+        interner.type_parameter_locations().iter().map(|location| (*location, TYPE_PARAMETER, 0));
+
+    let mut tokens: Vec<_> = references
+        .chain(type_parameters)
+        .filter(|(location, _, _)| location.file == file)
+        .filter_map(|(location, token_type, modifiers)| {
+            let range = byte_span_to_range(files, file, location.span.into())?;
+            // Tokens can't span several lines unless the client says it supports them
+            let single_line = range.start.line == range.end.line && range.start < range.end;
+            single_line.then_some((range, token_type, modifiers))
+        })
+        .collect();
+    tokens.sort_by_key(|(range, _, _)| range.start);
+    tokens
+}
+
+/// Returns the token type and modifiers for the name of `id`.
+fn classify(interner: &NodeInterner, id: &ReferenceId) -> Option<(u32, u32)> {
+    match id {
+        ReferenceId::Definition(definition_id) => {
+            let definition = interner.try_definition(*definition_id)?;
+            match definition.kind {
+                DefinitionKind::Function(func_id) => {
+                    let function_modifiers = interner.function_modifiers(&func_id);
+                    let mut modifiers = 0;
+                    if function_modifiers.is_unconstrained {
+                        modifiers |= UNCONSTRAINED;
+                    }
+                    if matches!(
+                        function_modifiers.attributes.function,
+                        Some(FunctionAttribute::Oracle(_))
+                    ) {
+                        modifiers |= ORACLE;
+                    }
+                    Some((FUNCTION, modifiers))
+                }
+                DefinitionKind::Global(_) => Some((VARIABLE, READONLY | STATIC)),
+                DefinitionKind::Local(_) if definition.mutable => Some((VARIABLE, MUTABLE)),
+                DefinitionKind::Local(_) => Some((VARIABLE, 0)),
+                DefinitionKind::GenericType(_) => Some((TYPE_PARAMETER, 0)),
+            }
+        }
+        ReferenceId::Struct(_) => Some((STRUCT, 0)),
+        ReferenceId::StructField(..) => Some((PROPERTY, 0)),
+    }
+}
+
+/// Encodes sorted tokens relative to the token before them, as the protocol expects. A token
+/// overlapping the one before it, such as a name recorded both as a declaration and as a use, is
+/// skipped.
+fn encode_tokens(tokens: Vec<(Range, u32, u32)>) -> Vec<SemanticToken> {
+    let mut data = Vec::new();
+    let mut previous = Range::default();
+    for (range, token_type, token_modifiers_bitset) in tokens {
+        if !data.is_empty() && range.start < previous.end {
+            continue;
+        }
+
+        let Position { line, character } = range.start;
+        let delta_line = line - previous.start.line;
+        let delta_start =
+            if delta_line == 0 { character - previous.start.character } else { character };
+        data.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: range.end.character - character,
+            token_type,
+            token_modifiers_bitset,
+        });
+        previous = range;
+    }
+    data
+}
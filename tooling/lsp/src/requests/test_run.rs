@@ -10,8 +10,8 @@ use noirc_driver::{check_crate, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_frontend::hir::FunctionNameMatch;
 
 use crate::{
-    get_non_stdlib_asset,
-    types::{NargoTestRunParams, NargoTestRunResult},
+    get_non_stdlib_asset, to_lsp_location,
+    types::{notification, NargoTestRunParams, NargoTestRunResult},
     LspState,
 };
 
@@ -19,7 +19,13 @@ pub(crate) fn on_test_run_request(
     state: &mut LspState,
     params: NargoTestRunParams,
 ) -> impl Future<Output = Result<NargoTestRunResult, ResponseError>> {
-    future::ready(on_test_run_request_inner(state, params))
+    let result = on_test_run_request_inner(state, params);
+    // The result is also sent as a notification so that clients which started the test from a
+    // code lens, rather than with this request, can show it.
+    if let Ok(result) = &result {
+        let _ = state.client.notify::<notification::NargoTestRunStatus>(result.clone());
+    }
+    future::ready(result)
 }
 
 fn on_test_run_request_inner(
@@ -57,6 +63,7 @@ fn on_test_run_request_inner(
                     id: params.id.clone(),
                     result: "error".to_string(),
                     message: Some("The project failed to compile".into()),
+                    location: None,
                 };
                 return Ok(result);
             };
@@ -73,23 +80,49 @@ fn on_test_run_request_inner(
                 )
             })?;
 
-            let test_result =
-                run_test(&state.solver, &context, test_function, false, &CompileOptions::default());
+            let _ = state.client.notify::<notification::NargoTestRunStatus>(NargoTestRunResult {
+                id: params.id.clone(),
+                result: "running".to_string(),
+                message: None,
+                location: None,
+            });
+
+            let test_result = run_test(
+                &state.solver,
+                &context,
+                test_function,
+                false,
+                None,
+                None,
+                &CompileOptions::default(),
+            );
+            let location = test_result
+                .failure_location()
+                .and_then(|location| to_lsp_location(&context, location));
             let result = match test_result {
                 TestStatus::Pass => NargoTestRunResult {
                     id: params.id.clone(),
                     result: "pass".to_string(),
                     message: None,
+                    location: None,
                 },
                 TestStatus::Fail { message, .. } => NargoTestRunResult {
                     id: params.id.clone(),
                     result: "fail".to_string(),
                     message: Some(message),
+                    location,
                 },
                 TestStatus::CompileError(diag) => NargoTestRunResult {
                     id: params.id.clone(),
                     result: "error".to_string(),
                     message: Some(diag.diagnostic.message),
+                    location,
+                },
+                TestStatus::Ignored { reason } => NargoTestRunResult {
+                    id: params.id.clone(),
+                    result: "skipped".to_string(),
+                    message: reason,
+                    location: None,
                 },
             };
             Ok(result)
@@ -8,11 +8,19 @@ use std::collections::{BTreeMap, HashMap};
 
 // Re-providing lsp_types that we don't need to override
 pub(crate) use lsp_types::{
-    CodeLens, CodeLensOptions, CodeLensParams, Command, Diagnostic, DiagnosticSeverity,
-    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
-    DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializedParams,
-    LogMessageParams, MessageType, Position, PublishDiagnosticsParams, Range, ServerInfo,
-    TextDocumentSyncCapability, Url,
+    CodeLens, CodeLensOptions, CodeLensParams, Command, DefinitionOptions, Diagnostic,
+    DiagnosticRelatedInformation, DiagnosticSeverity, DidChangeConfigurationParams,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, DocumentFormattingParams, DocumentRangeFormattingOptions,
+    DocumentRangeFormattingParams, GotoDefinitionParams, GotoDefinitionResponse, Hover,
+    HoverContents, HoverParams, HoverProviderCapability, InitializeParams, InitializedParams,
+    InlayHint, InlayHintKind, InlayHintLabel, InlayHintParams, InlayHintServerCapabilities,
+    InlayHintTooltip, LogMessageParams, MarkupContent, MarkupKind, MessageType, NumberOrString,
+    OneOf, Position, PublishDiagnosticsParams, Range, RenameOptions, RenameParams, SemanticToken,
+    SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensFullOptions,
+    SemanticTokensLegend, SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
+    SemanticTokensServerCapabilities, ServerInfo, TextDocumentPositionParams,
+    TextDocumentSyncCapability, TextEdit, Url, WillSaveTextDocumentParams, WorkspaceEdit,
 };
 
 pub(crate) mod request {
@@ -24,7 +32,10 @@ pub(crate) mod request {
     };
 
     // Re-providing lsp_types that we don't need to override
-    pub(crate) use lsp_types::request::{CodeLensRequest as CodeLens, Formatting, Shutdown};
+    pub(crate) use lsp_types::request::{
+        CodeLensRequest as CodeLens, Formatting, GotoDefinition, HoverRequest, InlayHintRequest,
+        RangeFormatting, Rename, SemanticTokensFullRequest, Shutdown, WillSaveWaitUntil,
+    };
 
     #[derive(Debug)]
     pub(crate) struct Initialize;
@@ -62,7 +73,7 @@ pub(crate) mod request {
 pub(crate) mod notification {
     use lsp_types::notification::Notification;
 
-    use super::NargoPackageTests;
+    use super::{NargoPackageTests, NargoTestRunResult};
 
     // Re-providing lsp_types that we don't need to override
     pub(crate) use lsp_types::notification::{
@@ -75,6 +86,12 @@ pub(crate) mod notification {
         type Params = NargoPackageTests;
         const METHOD: &'static str = "nargo/tests/update";
     }
+
+    pub(crate) struct NargoTestRunStatus;
+    impl Notification for NargoTestRunStatus {
+        type Params = NargoTestRunResult;
+        const METHOD: &'static str = "nargo/tests/status";
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Default, Deserialize, Serialize)]
@@ -115,6 +132,31 @@ pub(crate) struct ServerCapabilities {
     /// The server provides document formatting.
     pub(crate) document_formatting_provider: bool,
 
+    /// The server provides formatting of a range within a document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) document_range_formatting_provider:
+        Option<OneOf<bool, DocumentRangeFormattingOptions>>,
+
+    /// The server provides goto definition support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) definition_provider: Option<OneOf<bool, DefinitionOptions>>,
+
+    /// The server provides hover support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hover_provider: Option<HoverProviderCapability>,
+
+    /// The server provides rename support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) rename_provider: Option<OneOf<bool, RenameOptions>>,
+
+    /// The server provides inlay hints.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) inlay_hint_provider: Option<OneOf<bool, InlayHintServerCapabilities>>,
+
+    /// The server provides semantic tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) semantic_tokens_provider: Option<SemanticTokensServerCapabilities>,
+
     /// The server handles and provides custom nargo messages.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) nargo: Option<NargoCapability>,
@@ -196,11 +238,13 @@ pub(crate) struct NargoTestRunParams {
     pub(crate) id: NargoTestId,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct NargoTestRunResult {
     pub(crate) id: NargoTestId,
     pub(crate) result: String,
     pub(crate) message: Option<String>,
+    /// The assertion which failed or the code which didn't compile, if it is known
+    pub(crate) location: Option<lsp_types::Location>,
 }
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct NargoProfileRunParams {
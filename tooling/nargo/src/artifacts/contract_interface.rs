@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use noirc_abi::{AbiParameter, AbiType, ContractEvent, Sign};
+use noirc_driver::ContractFunctionType;
+use serde::Serialize;
+
+use super::contract::{PreprocessedContract, PreprocessedContractFunction};
+
+/// The external interface of a [`PreprocessedContract`], from which clients such as aztec.js can
+/// call its functions without inspecting their bytecode.
+#[derive(Debug, Serialize)]
+pub struct ContractInterface<'a> {
+    pub noir_version: &'a str,
+    pub name: &'a str,
+    pub functions: Vec<FunctionInterface<'a>>,
+    /// Every struct and enum used by the contract's functions, keyed by its fully qualified path.
+    pub types: BTreeMap<&'a str, &'a AbiType>,
+    pub events: &'a [ContractEvent],
+}
+
+#[derive(Debug, Serialize)]
+pub struct FunctionInterface<'a> {
+    pub name: &'a str,
+    /// The canonical signature of the function, e.g. `transfer(Field,(Field),u64)`.
+    pub signature: String,
+    /// The first 4 bytes of the keccak256 hash of `signature`, as a hex string.
+    pub selector: String,
+    pub function_type: ContractFunctionType,
+    pub is_internal: bool,
+    pub parameters: Vec<ParameterInterface<'a>>,
+    pub return_type: Option<&'a AbiType>,
+}
+
+/// A parameter of a contract function along with where it's placed in the function's arguments
+/// once they are encoded as field elements.
+#[derive(Debug, Serialize)]
+pub struct ParameterInterface<'a> {
+    #[serde(flatten)]
+    pub parameter: &'a AbiParameter,
+    /// The index of the first field element of the parameter.
+    pub offset: u32,
+    /// The number of field elements the parameter is encoded as.
+    pub size: u32,
+}
+
+impl<'a> ContractInterface<'a> {
+    pub fn new(contract: &'a PreprocessedContract) -> Self {
+        let mut types = BTreeMap::new();
+        for function in &contract.functions {
+            let abi_types = function.abi.parameters.iter().map(|param| &param.typ);
+            for typ in abi_types.chain(&function.abi.return_type) {
+                collect_struct_types(typ, &mut types);
+            }
+        }
+
+        ContractInterface {
+            noir_version: &contract.noir_version,
+            name: &contract.name,
+            functions: contract.functions.iter().map(FunctionInterface::new).collect(),
+            types,
+            events: &contract.events,
+        }
+    }
+}
+
+impl<'a> FunctionInterface<'a> {
+    fn new(function: &'a PreprocessedContractFunction) -> Self {
+        let signature = function_signature(&function.name, &function.abi.parameters);
+        let hash = acvm::blackbox_solver::keccak256(signature.as_bytes())
+            .expect("keccak256 should not fail on byte inputs");
+        let selector = format!("0x{}", hex::encode(&hash[..4]));
+
+        let mut offset = 0;
+        let parameters = function
+            .abi
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let size = parameter.typ.field_count();
+                let interface = ParameterInterface { parameter, offset, size };
+                offset += size;
+                interface
+            })
+            .collect();
+
+        FunctionInterface {
+            name: &function.name,
+            signature,
+            selector,
+            function_type: function.function_type,
+            is_internal: function.is_internal,
+            parameters,
+            return_type: function.abi.return_type.as_ref(),
+        }
+    }
+}
+
+fn function_signature(name: &str, parameters: &[AbiParameter]) -> String {
+    let parameter_types: Vec<_> =
+        parameters.iter().map(|parameter| type_signature(&parameter.typ)).collect();
+    format!("{name}({})", parameter_types.join(","))
+}
+
+/// Returns the name of `typ` in a function signature. Structs and tuples are written as a tuple of
+/// their fields so that the signature doesn't depend on the names of types.
+fn type_signature(typ: &AbiType) -> String {
+    let tuple_signature = |types: Vec<&AbiType>| {
+        let types: Vec<_> = types.into_iter().map(type_signature).collect();
+        format!("({})", types.join(","))
+    };
+
+    match typ {
+        AbiType::Field => "Field".to_owned(),
+        AbiType::Integer { sign: Sign::Unsigned, width } => format!("u{width}"),
+        AbiType::Integer { sign: Sign::Signed, width } => format!("i{width}"),
+        AbiType::Boolean => "bool".to_owned(),
+        AbiType::String { length } => format!("str<{length}>"),
+        AbiType::Array { length, typ } => format!("[{};{length}]", type_signature(typ)),
+        AbiType::Struct { fields, .. } => {
+            tuple_signature(fields.iter().map(|(_, typ)| typ).collect())
+        }
+        AbiType::Tuple { fields } => tuple_signature(fields.iter().collect()),
+        // Enums are encoded as the index of the active variant followed by the fields of each variant.
+        AbiType::Enum { variants, .. } => {
+            let variant_signatures =
+                variants.iter().map(|(_, fields)| tuple_signature(fields.iter().collect()));
+            let signatures: Vec<_> =
+                std::iter::once("Field".to_owned()).chain(variant_signatures).collect();
+            format!("({})", signatures.join(","))
+        }
+    }
+}
+
+fn collect_struct_types<'a>(typ: &'a AbiType, types: &mut BTreeMap<&'a str, &'a AbiType>) {
+    match typ {
+        AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean | AbiType::String { .. } => {}
+        AbiType::Array { typ, .. } => collect_struct_types(typ, types),
+        AbiType::Struct { path, fields } => {
+            types.insert(path, typ);
+            for (_, field_type) in fields {
+                collect_struct_types(field_type, types);
+            }
+        }
+        AbiType::Enum { path, variants } => {
+            types.insert(path, typ);
+            for field_type in variants.iter().flat_map(|(_, fields)| fields) {
+                collect_struct_types(field_type, types);
+            }
+        }
+        AbiType::Tuple { fields } => {
+            for field_type in fields {
+                collect_struct_types(field_type, types);
+            }
+        }
+    }
+}
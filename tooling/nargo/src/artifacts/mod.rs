@@ -4,5 +4,7 @@
 //! Should any projects require/desire a different artifact format, it's expected that they will write a transformer
 //! to generate them using these artifacts as a starting point.
 pub mod contract;
+pub mod contract_interface;
 pub mod debug;
 pub mod program;
+pub mod workspace_manifest;
@@ -1,13 +1,25 @@
 use acvm::acir::circuit::Circuit;
+use base64::Engine;
 use noirc_abi::Abi;
 use serde::{Deserialize, Serialize};
 
+use crate::errors::ArtifactError;
+
+pub use noirc_abi::ABI_VERSION;
+
+/// The version of the serialization used for the bytecode of a [`PreprocessedProgram`].
+///
+/// This must be incremented whenever the serialized layout of ACIR changes, with the bytecode of
+/// the previous version still being read by [`PreprocessedProgram::from_json`].
+pub const BYTECODE_VERSION: u32 = 1;
+
 /// `PreprocessedProgram` represents a Noir program which has been preprocessed by a particular backend proving system.
 ///
 /// This differs from a generic Noir program artifact in that:
 /// - The ACIR bytecode has had an optimization pass applied to tailor it for the backend.
 /// - Proving and verification keys have been pregenerated based on this ACIR.
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(try_from = "SerializedPreprocessedProgram")]
 pub struct PreprocessedProgram {
     pub noir_version: String,
 
@@ -18,11 +30,78 @@ pub struct PreprocessedProgram {
     pub hash: u64,
 
     pub backend: String,
+
+    /// The [`ABI_VERSION`] used to serialize `abi`.
+    pub abi_version: u32,
     pub abi: Abi,
 
-    #[serde(
-        serialize_with = "Circuit::serialize_circuit_base64",
-        deserialize_with = "Circuit::deserialize_circuit_base64"
-    )]
+    /// The [`BYTECODE_VERSION`] used to serialize `bytecode`.
+    pub bytecode_version: u32,
+
+    #[serde(serialize_with = "Circuit::serialize_circuit_base64")]
     pub bytecode: Circuit,
 }
+
+impl PreprocessedProgram {
+    /// Reads a program artifact, accepting bytecode written by previous versions of the serialization.
+    pub fn from_json(json: &[u8]) -> Result<Self, ArtifactError> {
+        let program: SerializedPreprocessedProgram = serde_json::from_slice(json)?;
+        program.try_into()
+    }
+}
+
+/// The serialized form of a [`PreprocessedProgram`], before its bytecode has been deserialized
+/// according to its version.
+#[derive(Deserialize)]
+struct SerializedPreprocessedProgram {
+    noir_version: String,
+    hash: u64,
+    backend: String,
+    /// Artifacts written before the ABI was versioned have no version, which is treated as 0.
+    #[serde(default)]
+    abi_version: u32,
+    abi: Abi,
+    /// Artifacts written before the bytecode was versioned have no version, which is treated as 0.
+    #[serde(default)]
+    bytecode_version: u32,
+    bytecode: String,
+}
+
+impl TryFrom<SerializedPreprocessedProgram> for PreprocessedProgram {
+    type Error = ArtifactError;
+
+    fn try_from(program: SerializedPreprocessedProgram) -> Result<Self, Self::Error> {
+        // Unversioned ABIs share the schema of the first version.
+        if program.abi_version > ABI_VERSION {
+            return Err(ArtifactError::UnsupportedAbiVersion {
+                found: program.abi_version,
+                expected: ABI_VERSION,
+            });
+        }
+        let bytecode = deserialize_bytecode(program.bytecode_version, &program.bytecode)?;
+
+        Ok(PreprocessedProgram {
+            noir_version: program.noir_version,
+            hash: program.hash,
+            backend: program.backend,
+            abi_version: ABI_VERSION,
+            abi: program.abi,
+            bytecode_version: BYTECODE_VERSION,
+            bytecode,
+        })
+    }
+}
+
+fn deserialize_bytecode(version: u32, bytecode_b64: &str) -> Result<Circuit, ArtifactError> {
+    match version {
+        // Unversioned artifacts share the layout of the first version.
+        0 | BYTECODE_VERSION => {
+            let circuit_bytes = base64::engine::general_purpose::STANDARD
+                .decode(bytecode_b64)
+                .map_err(|err| ArtifactError::InvalidBytecode(err.to_string()))?;
+            Circuit::deserialize_circuit(&circuit_bytes)
+                .map_err(|err| ArtifactError::InvalidBytecode(err.to_string()))
+        }
+        _ => Err(ArtifactError::UnsupportedVersion { found: version, expected: BYTECODE_VERSION }),
+    }
+}
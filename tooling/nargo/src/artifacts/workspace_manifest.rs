@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use acvm::acir::circuit::Circuit;
+use noirc_abi::Abi;
+use noirc_driver::ContractFunctionType;
+use serde::{Deserialize, Serialize};
+
+use super::contract::PreprocessedContract;
+use crate::ops::circuit_hash;
+
+/// The name of the [`WorkspaceManifest`] written to the target directory, without its extension.
+pub const WORKSPACE_MANIFEST_NAME: &str = "workspace";
+
+/// Lists every artifact written when compiling a workspace, so that tools such as deployment
+/// scripts can find them without searching the target directory.
+///
+/// Paths are relative to the directory containing the manifest.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkspaceManifest {
+    pub noir_version: String,
+    pub programs: Vec<ProgramEntry>,
+    pub contracts: Vec<ContractEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProgramEntry {
+    /// The name of the package which the program was compiled from.
+    pub package: String,
+    pub path: PathBuf,
+    pub abi: Abi,
+    /// The sha256 hash of the program's bytecode as a hex string, as used to match proofs to circuits.
+    pub bytecode_hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContractEntry {
+    /// The name of the package which the contract was compiled from.
+    pub package: String,
+    pub name: String,
+    pub path: PathBuf,
+    /// The path of the contract's [`ContractInterface`][super::contract_interface::ContractInterface].
+    pub interface_path: PathBuf,
+    pub functions: Vec<ContractFunctionEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContractFunctionEntry {
+    pub name: String,
+    pub function_type: ContractFunctionType,
+    pub is_internal: bool,
+    pub abi: Abi,
+    /// The sha256 hash of the function's bytecode as a hex string.
+    pub bytecode_hash: String,
+}
+
+impl WorkspaceManifest {
+    pub fn new(noir_version: String) -> Self {
+        WorkspaceManifest { noir_version, programs: Vec::new(), contracts: Vec::new() }
+    }
+}
+
+impl ProgramEntry {
+    pub fn new(package: String, path: PathBuf, abi: Abi, bytecode: &Circuit) -> Self {
+        ProgramEntry { package, path, abi, bytecode_hash: bytecode_hash(bytecode) }
+    }
+}
+
+impl ContractEntry {
+    pub fn new(
+        package: String,
+        path: PathBuf,
+        interface_path: PathBuf,
+        contract: &PreprocessedContract,
+    ) -> Self {
+        let functions = contract
+            .functions
+            .iter()
+            .map(|function| ContractFunctionEntry {
+                name: function.name.clone(),
+                function_type: function.function_type,
+                is_internal: function.is_internal,
+                abi: function.abi.clone(),
+                bytecode_hash: bytecode_hash(&function.bytecode),
+            })
+            .collect();
+
+        ContractEntry { package, name: contract.name.clone(), path, interface_path, functions }
+    }
+}
+
+fn bytecode_hash(circuit: &Circuit) -> String {
+    hex::encode(circuit_hash(circuit))
+}
@@ -0,0 +1,95 @@
+//! A cache of the packages which were checked without any errors or warnings, so that checking them
+//! again can be skipped until one of the files they were checked from changes.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use fm::FileManager;
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::PKG_FILE;
+use crate::package::{Dependency, Package};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckCache {
+    noir_version: String,
+    /// Hash of the options the package was checked with.
+    options_hash: u64,
+    /// Hash of the contents of each source file and manifest the package was checked from, including
+    /// those of its dependencies.
+    files: BTreeMap<PathBuf, u64>,
+}
+
+impl CheckCache {
+    /// Records the files read while checking `package`, which must have been checked into the context
+    /// owning `file_manager`.
+    pub fn new(
+        package: &Package,
+        file_manager: &FileManager,
+        compile_options: &CompileOptions,
+    ) -> Self {
+        let mut files = BTreeMap::new();
+        for (file_id, path) in file_manager.source_files() {
+            let source = file_manager.fetch_file(file_id).source();
+            files.insert(path.to_path_buf(), fxhash::hash64(source.as_bytes()));
+        }
+        // Manifests aren't read through the file manager, but they set the lint levels and overflow
+        // mode of each package.
+        for manifest_path in manifest_paths(package) {
+            if let Ok(contents) = std::fs::read(&manifest_path) {
+                files.insert(manifest_path, fxhash::hash64(&contents));
+            }
+        }
+
+        CheckCache {
+            noir_version: NOIR_ARTIFACT_VERSION_STRING.to_owned(),
+            options_hash: options_hash(compile_options),
+            files,
+        }
+    }
+
+    /// Reads the cache of a package, if it was saved by this version of Nargo.
+    pub fn read(path: &Path) -> Option<Self> {
+        let contents = std::fs::read(path).ok()?;
+        let cache: CheckCache = serde_json::from_slice(&contents).ok()?;
+        (cache.noir_version == NOIR_ARTIFACT_VERSION_STRING).then_some(cache)
+    }
+
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_vec(self)?)
+    }
+
+    /// True if the package would be checked with the same options and from the same files as when
+    /// the cache was saved.
+    ///
+    /// A new file can only be checked if one of the files already checked declares it as a module,
+    /// so only the files recorded need to be compared.
+    pub fn is_fresh(&self, compile_options: &CompileOptions) -> bool {
+        self.options_hash == options_hash(compile_options)
+            && self.files.iter().all(|(path, hash)| {
+                std::fs::read(path).map_or(false, |contents| fxhash::hash64(&contents) == *hash)
+            })
+    }
+}
+
+fn options_hash(compile_options: &CompileOptions) -> u64 {
+    let options = serde_json::to_vec(compile_options).expect("compile options are serializable");
+    fxhash::hash64(&options)
+}
+
+/// Returns the paths to the manifests of `package` and of all of its dependencies.
+fn manifest_paths(package: &Package) -> Vec<PathBuf> {
+    let mut paths = vec![package.root_dir.join(PKG_FILE)];
+    for dependency in package.dependencies.values() {
+        match dependency {
+            Dependency::Local { package } | Dependency::Remote { package } => {
+                paths.extend(manifest_paths(package));
+            }
+        }
+    }
+    paths
+}
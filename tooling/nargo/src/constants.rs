@@ -1,6 +1,10 @@
 // Directories
 /// The directory for the `nargo contract` command output
 pub const CONTRACT_DIR: &str = "contract";
+/// The directory within the target directory for the results of previous checks of each package
+pub const CACHE_DIR: &str = "cache";
+/// The directory within the target directory for the `nargo export` command output
+pub const EXPORT_DIR: &str = "export";
 /// The directory to store serialized circuit proofs.
 pub const PROOFS_DIR: &str = "proofs";
 /// The directory to store Noir source files
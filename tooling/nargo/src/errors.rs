@@ -1,13 +1,16 @@
+use std::path::PathBuf;
+
 use acvm::{
-    acir::circuit::OpcodeLocation,
+    acir::{circuit::OpcodeLocation, native_types::WitnessMap},
     pwg::{ErrorLocation, OpcodeResolutionError},
 };
 use noirc_errors::{
-    debug_info::DebugInfo, reporter::ReportedErrors, CustomDiagnostic, FileDiagnostic,
+    debug_info::DebugInfo, reporter::ReportedErrors, CustomDiagnostic, FileDiagnostic, StackFrame,
 };
 
 pub use noirc_errors::Location;
 
+use noirc_abi::{witness::WitnessFormatError, Abi};
 use noirc_frontend::graph::CrateName;
 use noirc_printable_type::ForeignCallError;
 use thiserror::Error;
@@ -32,25 +35,79 @@ impl From<ReportedErrors> for CompileError {
     }
 }
 
+/// Errors encountered while reading a [`PreprocessedProgram`][crate::artifacts::program::PreprocessedProgram].
+#[derive(Debug, Error)]
+pub enum ArtifactError {
+    #[error("Could not parse artifact: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    #[error("Unsupported artifact version {found}, expected version {expected} or earlier. Recompile the program to regenerate it")]
+    UnsupportedVersion { found: u32, expected: u32 },
+
+    #[error("Unsupported ABI version {found}, expected version {expected} or earlier. Recompile the program to regenerate it")]
+    UnsupportedAbiVersion { found: u32, expected: u32 },
+
+    #[error("Could not deserialize artifact bytecode: {0}")]
+    InvalidBytecode(String),
+}
+
+/// Errors encountered while decoding a [`Proof`][crate::ops::Proof].
+#[derive(Debug, Error)]
+pub enum ProofError {
+    #[error("Proof is not valid hex: {0}")]
+    InvalidHex(#[from] hex::FromHexError),
+
+    #[error("Proof ends before all of its public inputs could be read")]
+    Truncated,
+
+    #[error("Proof was generated for a different circuit, run `nargo prove` to regenerate it")]
+    CircuitMismatch,
+}
+
+/// Errors encountered while passing a proof to an outer circuit as [`RecursionArtifacts`][crate::ops::RecursionArtifacts].
+#[derive(Debug, Error)]
+pub enum RecursionError {
+    #[error("The outer circuit has no parameter `{0}`")]
+    MissingParameter(String),
+
+    #[error("Parameter `{0}` of the outer circuit must be a `Field` or an array of `Field`s")]
+    TypeMismatch(String),
+
+    #[error("Parameter `{name}` of the outer circuit has length {expected} but the inner proof requires {found}")]
+    LengthMismatch { name: String, expected: usize, found: usize },
+}
+
+/// Errors encountered while running an [`ExternalWitnessSolver`][crate::ops::ExternalWitnessSolver].
+#[derive(Debug, Error)]
+pub enum WitnessSolverError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    WitnessFormat(#[from] WitnessFormatError),
+
+    #[error("{0}")]
+    Failed(String),
+}
+
 #[derive(Debug, Error)]
 pub enum NargoError {
     /// Error while compiling Noir into ACIR.
-    #[error("Failed to compile circuit")]
-    CompilationError,
+    #[error("Failed to compile circuit: {0}")]
+    CompilationError(#[from] acvm::compiler::CompileError),
 
-    /// ACIR circuit execution error
-    #[error(transparent)]
-    ExecutionError(#[from] ExecutionError),
+    /// ACIR circuit execution error, along with the values of the witnesses used by the opcode
+    /// which failed
+    #[error("{0}")]
+    ExecutionError(ExecutionError, WitnessMap),
 
     /// Oracle handling error
     #[error(transparent)]
     ForeignCallError(#[from] ForeignCallError),
-}
 
-impl From<acvm::compiler::CompileError> for NargoError {
-    fn from(_: acvm::compiler::CompileError) -> Self {
-        NargoError::CompilationError
-    }
+    /// Error from an external witness solver
+    #[error("Witness solver `{}` failed: {error}", .binary_path.display())]
+    WitnessSolverError { binary_path: PathBuf, error: WitnessSolverError },
 }
 
 impl NargoError {
@@ -61,7 +118,7 @@ impl NargoError {
     /// in tests to expected failure messages
     pub fn user_defined_failure_message(&self) -> Option<&str> {
         let execution_error = match self {
-            NargoError::ExecutionError(error) => error,
+            NargoError::ExecutionError(error, _) => error,
             _ => return None,
         };
 
@@ -129,10 +186,13 @@ fn extract_locations_from_error(
 /// Tries to generate a runtime diagnostic from a nargo error. It will successfully do so if it's a runtime error with a call stack.
 pub fn try_to_diagnose_runtime_error(
     nargo_err: &NargoError,
+    abi: &Abi,
     debug: &DebugInfo,
 ) -> Option<FileDiagnostic> {
-    let execution_error = match nargo_err {
-        NargoError::ExecutionError(execution_error) => execution_error,
+    let (execution_error, witness_values) = match nargo_err {
+        NargoError::ExecutionError(execution_error, witness_values) => {
+            (execution_error, witness_values)
+        }
         _ => return None,
     };
 
@@ -143,23 +203,56 @@ pub fn try_to_diagnose_runtime_error(
     let location = source_locations.last()?;
 
     let message = match nargo_err {
-        NargoError::ExecutionError(ExecutionError::AssertionFailed(message, _)) => {
+        NargoError::ExecutionError(ExecutionError::AssertionFailed(message, _), _) => {
             format!("Assertion failed: '{message}'")
         }
-        NargoError::ExecutionError(ExecutionError::SolvingError(
-            OpcodeResolutionError::IndexOutOfBounds { index, array_size, .. },
-        )) => {
+        NargoError::ExecutionError(
+            ExecutionError::SolvingError(OpcodeResolutionError::IndexOutOfBounds {
+                index,
+                array_size,
+                ..
+            }),
+            _,
+        ) => {
             format!("Index out of bounds, array has size {array_size:?}, but index was {index:?}")
         }
-        NargoError::ExecutionError(ExecutionError::SolvingError(
-            OpcodeResolutionError::UnsatisfiedConstrain { .. },
-        )) => "Failed constraint".into(),
+        NargoError::ExecutionError(
+            ExecutionError::SolvingError(OpcodeResolutionError::UnsatisfiedConstrain { .. }),
+            _,
+        ) => "Failed constraint".into(),
         _ => nargo_err.to_string(),
     };
 
-    Some(
-        CustomDiagnostic::simple_error(message, String::new(), location.span)
-            .in_file(location.file)
-            .with_call_stack(source_locations),
-    )
+    // Each location of the call stack is named after the function it is in, which is the
+    // function called by the location before it.
+    let call_stack = source_locations
+        .iter()
+        .map(|location| StackFrame {
+            location: *location,
+            function: debug.function_name(location).map(ToOwned::to_owned),
+        })
+        .collect();
+
+    let mut diagnostic = CustomDiagnostic::simple_error(message, String::new(), location.span);
+    if let Some(values) = describe_witness_values(witness_values, abi) {
+        diagnostic.add_note(values);
+    }
+
+    Some(diagnostic.in_file(location.file).with_stack_frames(call_stack))
+}
+
+/// Describes the values of the witnesses used by a failing opcode, naming those which hold the
+/// program's inputs or return value after them, e.g. `x = 42, y = 41`.
+fn describe_witness_values(witness_values: &WitnessMap, abi: &Abi) -> Option<String> {
+    let witness_names = abi.witness_names();
+    let values: Vec<_> = witness_values
+        .clone()
+        .into_iter()
+        .map(|(witness, value)| match witness_names.get(&witness) {
+            Some(name) => format!("{name} = {value}"),
+            None => format!("_{} = {value}", witness.witness_index()),
+        })
+        .collect();
+
+    (!values.is_empty()).then(|| format!("with {}", values.join(", ")))
 }
@@ -8,6 +8,7 @@
 //! Noir Package Manager abbreviated is npm, which is already taken.
 
 pub mod artifacts;
+pub mod cache;
 pub mod constants;
 pub mod errors;
 pub mod ops;
@@ -17,10 +18,11 @@ pub mod workspace;
 use std::collections::BTreeMap;
 
 use fm::{FileManager, FileReader};
-use noirc_driver::{add_dep, prepare_crate, prepare_dependency};
+use noirc_driver::{add_dep, prepare_crate, prepare_dependency, CompileOptions};
 use noirc_frontend::{
     graph::{CrateGraph, CrateId, CrateName},
     hir::Context,
+    lints::LintLevel,
 };
 use package::{Dependency, Package};
 
@@ -35,6 +37,8 @@ pub fn prepare_dependencies(
         match dep {
             Dependency::Remote { package } | Dependency::Local { package } => {
                 let crate_id = prepare_dependency(context, &package.entry_path);
+                context.crate_graph.set_overflow_mode(crate_id, package.overflow_mode);
+                context.crate_graph.set_lint_levels(crate_id, package.lint_levels.clone());
                 add_dep(context, parent_crate, crate_id, dep_name.clone());
                 prepare_dependencies(context, crate_id, &package.dependencies);
             }
@@ -49,8 +53,27 @@ pub fn prepare_package(package: &Package, file_reader: Box<FileReader>) -> (Cont
     let mut context = Context::new(fm, graph);
 
     let crate_id = prepare_crate(&mut context, &package.entry_path);
+    context.crate_graph.set_overflow_mode(crate_id, package.overflow_mode);
+    context.crate_graph.set_lint_levels(crate_id, package.lint_levels.clone());
 
     prepare_dependencies(&mut context, crate_id, &package.dependencies);
 
     (context, crate_id)
 }
+
+/// Sets the level of each lint given with `--allow` or `--deny` in `crate_id`, overriding the
+/// `[lints]` table of its Nargo.toml. A lint given with both is denied.
+pub fn override_lint_levels(
+    context: &mut Context,
+    crate_id: CrateId,
+    compile_options: &CompileOptions,
+) {
+    let mut lint_levels = context.crate_graph[crate_id].lint_levels.clone();
+    for lint in &compile_options.allow_lints {
+        lint_levels.insert(*lint, LintLevel::Allow);
+    }
+    for lint in &compile_options.deny_lints {
+        lint_levels.insert(*lint, LintLevel::Deny);
+    }
+    context.crate_graph.set_lint_levels(crate_id, lint_levels);
+}
@@ -3,7 +3,8 @@ use fm::FileManager;
 use noirc_driver::{CompilationResult, CompileOptions, CompiledContract, CompiledProgram};
 
 use crate::errors::CompileError;
-use crate::prepare_package;
+use crate::ops::optimization_error;
+use crate::{override_lint_levels, prepare_package};
 use crate::{package::Package, workspace::Workspace};
 
 use rayon::prelude::*;
@@ -39,22 +40,24 @@ pub fn compile_workspace(
     // Report any warnings/errors which were encountered during compilation.
     let compiled_programs: Vec<CompiledProgram> = program_results
         .into_iter()
-        .map(|(file_manager, compilation_result)| {
+        .zip(binary_packages)
+        .map(|((file_manager, compilation_result), package)| {
             report_errors(
                 compilation_result,
                 &file_manager,
-                compile_options.deny_warnings,
+                package.compile_options(compile_options).deny_warnings,
                 compile_options.silence_warnings,
             )
         })
         .collect::<Result<_, _>>()?;
     let compiled_contracts: Vec<CompiledContract> = contract_results
         .into_iter()
-        .map(|(file_manager, compilation_result)| {
+        .zip(contract_packages)
+        .map(|((file_manager, compilation_result), package)| {
             report_errors(
                 compilation_result,
                 &file_manager,
-                compile_options.deny_warnings,
+                package.compile_options(compile_options).deny_warnings,
                 compile_options.silence_warnings,
             )
         })
@@ -70,8 +73,13 @@ pub fn compile_program(
     np_language: Language,
     is_opcode_supported: &impl Fn(&Opcode) -> bool,
 ) -> (FileManager, CompilationResult<CompiledProgram>) {
+    let compile_options = &CompileOptions {
+        np_language: Some(np_language),
+        ..package.compile_options(compile_options)
+    };
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
+    override_lint_levels(&mut context, crate_id, compile_options);
 
     let program_artifact_path = workspace.package_build_path(package);
     let mut debug_artifact_path = program_artifact_path.clone();
@@ -98,9 +106,14 @@ pub fn compile_program(
     };
 
     // Apply backend specific optimizations.
-    let optimized_program =
-        crate::ops::optimize_program(program, np_language, &is_opcode_supported_pedersen_hash)
-            .expect("Backend does not support an opcode that is in the IR");
+    let optimized_program = match crate::ops::optimize_program(
+        program,
+        np_language,
+        &is_opcode_supported_pedersen_hash,
+    ) {
+        Ok(program) => program,
+        Err(error) => return (context.file_manager, Err(vec![optimization_error(error)])),
+    };
 
     (context.file_manager, Ok((optimized_program, warnings)))
 }
@@ -111,8 +124,13 @@ fn compile_contract(
     np_language: Language,
     is_opcode_supported: &impl Fn(&Opcode) -> bool,
 ) -> (FileManager, CompilationResult<CompiledContract>) {
+    let compile_options = &CompileOptions {
+        np_language: Some(np_language),
+        ..package.compile_options(compile_options)
+    };
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
+    override_lint_levels(&mut context, crate_id, compile_options);
     let (contract, warnings) =
         match noirc_driver::compile_contract(&mut context, crate_id, compile_options) {
             Ok(contracts_and_warnings) => contracts_and_warnings,
@@ -122,8 +140,10 @@ fn compile_contract(
         };
 
     let optimized_contract =
-        crate::ops::optimize_contract(contract, np_language, &is_opcode_supported)
-            .expect("Backend does not support an opcode that is in the IR");
+        match crate::ops::optimize_contract(contract, np_language, &is_opcode_supported) {
+            Ok(contract) => contract,
+            Err(error) => return (context.file_manager, Err(vec![optimization_error(error)])),
+        };
 
     (context.file_manager, Ok((optimized_contract, warnings)))
 }
@@ -0,0 +1,194 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use acvm::acir::brillig::ForeignCallResult;
+use acvm::acir::circuit::{Circuit, OpcodeLocation};
+use acvm::acir::native_types::WitnessMap;
+use acvm::pwg::{ACVMStatus, BrilligSolverStatus, ForeignCallWaitInfo, StepResult, ACVM};
+use acvm::BlackBoxFunctionSolver;
+use codespan_reporting::files::Files;
+use fm::FileManager;
+use noirc_errors::debug_info::DebugInfo;
+use noirc_printable_type::PrintableValueDisplay;
+
+use crate::NargoError;
+
+use super::execute::{execution_failure, WitnessSolver};
+use super::foreign_calls::{ForeignCall, ForeignCallExecutor};
+
+/// Solves witnesses with the ACVM one opcode at a time, recording the location of each ACIR and
+/// Brillig opcode which is executed.
+///
+/// ACIR opcodes are always executed in full, so it is the Brillig opcodes of unconstrained functions
+/// which show the branches taken.
+pub struct TracingAcvmSolver<'b, B: BlackBoxFunctionSolver> {
+    blackbox_solver: &'b B,
+    executed_opcodes: RefCell<BTreeSet<OpcodeLocation>>,
+}
+
+impl<'b, B: BlackBoxFunctionSolver> TracingAcvmSolver<'b, B> {
+    pub fn new(blackbox_solver: &'b B) -> Self {
+        TracingAcvmSolver { blackbox_solver, executed_opcodes: RefCell::default() }
+    }
+
+    /// Returns the locations of the opcodes executed by every circuit solved so far.
+    pub fn executed_opcodes(&self) -> BTreeSet<OpcodeLocation> {
+        self.executed_opcodes.borrow().clone()
+    }
+}
+
+impl<B: BlackBoxFunctionSolver> WitnessSolver for TracingAcvmSolver<'_, B> {
+    fn solve(
+        &self,
+        circuit: &Circuit,
+        initial_witness: WitnessMap,
+        foreign_call_executor: &mut dyn ForeignCallExecutor,
+    ) -> Result<WitnessMap, NargoError> {
+        let mut executed_opcodes = self.executed_opcodes.borrow_mut();
+        let mut acvm = ACVM::new(self.blackbox_solver, &circuit.opcodes, initial_witness);
+        let mut dynamic_assert_message: Option<String> = None;
+
+        let mut status = ACVMStatus::InProgress;
+        while status == ACVMStatus::InProgress {
+            let acir_index = acvm.instruction_pointer();
+            if acir_index >= circuit.opcodes.len() {
+                status = acvm.solve();
+                continue;
+            }
+            executed_opcodes.insert(OpcodeLocation::Acir(acir_index));
+
+            status = match acvm.step_into_brillig_opcode() {
+                StepResult::Status(status) => status,
+                StepResult::IntoBrillig(mut brillig_solver) => loop {
+                    executed_opcodes.insert(OpcodeLocation::Brillig {
+                        acir_index,
+                        brillig_index: brillig_solver.program_counter(),
+                    });
+                    match brillig_solver.step() {
+                        Ok(BrilligSolverStatus::InProgress) => (),
+                        Ok(BrilligSolverStatus::Finished) => {
+                            break acvm.finish_brillig_with_solver(brillig_solver);
+                        }
+                        Ok(BrilligSolverStatus::ForeignCallWait(foreign_call)) => {
+                            let result = execute_foreign_call(
+                                &foreign_call,
+                                foreign_call_executor,
+                                &mut dynamic_assert_message,
+                            )?;
+                            brillig_solver.resolve_pending_foreign_call(result);
+                        }
+                        Err(error) => break ACVMStatus::Failure(error),
+                    }
+                },
+            };
+
+            if let ACVMStatus::RequiresForeignCall(foreign_call) = &status {
+                let result = execute_foreign_call(
+                    foreign_call,
+                    foreign_call_executor,
+                    &mut dynamic_assert_message,
+                )?;
+                acvm.resolve_pending_foreign_call(result);
+                status = ACVMStatus::InProgress;
+            }
+        }
+
+        match status {
+            ACVMStatus::Failure(error) => {
+                Err(execution_failure(circuit, error, dynamic_assert_message, acvm.witness_map()))
+            }
+            _ => Ok(acvm.finalize()),
+        }
+    }
+}
+
+/// Executes a foreign call made while solving a circuit, keeping the message of a failing assertion
+/// aside instead of passing it to the executor.
+///
+/// Any other call means execution moved on from the assertion, so its message is discarded.
+fn execute_foreign_call(
+    foreign_call: &ForeignCallWaitInfo,
+    foreign_call_executor: &mut dyn ForeignCallExecutor,
+    dynamic_assert_message: &mut Option<String>,
+) -> Result<ForeignCallResult, NargoError> {
+    if foreign_call.function == ForeignCall::AssertMessage.name() {
+        let message: PrintableValueDisplay = foreign_call.inputs.as_slice().try_into()?;
+        *dynamic_assert_message = Some(message.to_string());
+        Ok(ForeignCallResult { values: vec![] })
+    } else {
+        *dynamic_assert_message = None;
+        Ok(foreign_call_executor.execute(foreign_call)?)
+    }
+}
+
+/// Line coverage of source code, counting for each line the opcodes generated from it which were
+/// executed, summed over all of the programs recorded.
+///
+/// Only files within the root directory given when the report is created are covered, so that
+/// the standard library and dependencies don't crowd out the package's own code.
+pub struct CoverageReport {
+    root_dir: PathBuf,
+    files: BTreeMap<PathBuf, BTreeMap<usize, u64>>,
+}
+
+impl CoverageReport {
+    pub fn new(root_dir: PathBuf) -> Self {
+        CoverageReport { root_dir, files: BTreeMap::new() }
+    }
+
+    /// Records the lines of a program which were executed, given the locations of the opcodes it
+    /// executed. Each line with an opcode is counted, so that lines which are never executed show up
+    /// in the report.
+    pub fn record(
+        &mut self,
+        debug_info: &DebugInfo,
+        executed_opcodes: &BTreeSet<OpcodeLocation>,
+        file_manager: &FileManager,
+    ) {
+        let file_map = file_manager.as_file_map();
+        for (opcode_location, call_stack) in &debug_info.locations {
+            // The innermost location is the statement which the opcode was generated from.
+            let Some(location) = call_stack.last() else {
+                continue;
+            };
+            let path = file_manager.path(location.file);
+            if !path.starts_with(&self.root_dir) {
+                continue;
+            }
+            let Ok(line_index) = file_map.line_index(location.file, location.span.start() as usize)
+            else {
+                continue;
+            };
+
+            let lines = self.files.entry(path.to_path_buf()).or_default();
+            let hits = lines.entry(line_index + 1).or_default();
+            if executed_opcodes.contains(opcode_location) {
+                *hits += 1;
+            }
+        }
+    }
+
+    /// Returns the number of lines executed and the number of lines with any code in each file.
+    pub fn file_summaries(&self) -> impl Iterator<Item = (&Path, usize, usize)> {
+        self.files.iter().map(|(path, lines)| {
+            let hit = lines.values().filter(|hits| **hits > 0).count();
+            (path.as_path(), hit, lines.len())
+        })
+    }
+
+    /// Formats the report as an lcov tracefile, as read by tools such as `genhtml` and editor extensions.
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for (path, lines) in &self.files {
+            writeln!(lcov, "TN:\nSF:{}", path.display()).unwrap();
+            for (line, hits) in lines {
+                writeln!(lcov, "DA:{line},{hits}").unwrap();
+            }
+            let hit = lines.values().filter(|hits| **hits > 0).count();
+            writeln!(lcov, "LF:{}\nLH:{hit}\nend_of_record", lines.len()).unwrap();
+        }
+        lcov
+    }
+}
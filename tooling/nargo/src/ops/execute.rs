@@ -1,22 +1,74 @@
+use acvm::acir::brillig::ForeignCallResult;
 use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeResolutionError, ACVM};
 use acvm::BlackBoxFunctionSolver;
-use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
+use acvm::{acir::circuit::Circuit, acir::circuit::OpcodeLocation, acir::native_types::WitnessMap};
+use noirc_printable_type::PrintableValueDisplay;
 
 use crate::errors::ExecutionError;
 use crate::NargoError;
 
-use super::foreign_calls::ForeignCallExecutor;
+use super::foreign_calls::{ForeignCall, ForeignCallExecutor};
+use super::parallel_solver::opcode_witnesses;
 
-pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
+/// An engine which solves the witness of a circuit given the values of its inputs.
+///
+/// The ACVM is used by default through [`AcvmSolver`], but other engines such as
+/// [`ExternalWitnessSolver`][super::ExternalWitnessSolver] can be used to execute large circuits faster.
+pub trait WitnessSolver {
+    fn solve(
+        &self,
+        circuit: &Circuit,
+        initial_witness: WitnessMap,
+        foreign_call_executor: &mut dyn ForeignCallExecutor,
+    ) -> Result<WitnessMap, NargoError>;
+}
+
+pub fn execute_circuit<F: ForeignCallExecutor>(
     circuit: &Circuit,
     initial_witness: WitnessMap,
-    blackbox_solver: &B,
+    solver: &dyn WitnessSolver,
     foreign_call_executor: &mut F,
+) -> Result<WitnessMap, NargoError> {
+    solver.solve(circuit, initial_witness, foreign_call_executor)
+}
+
+/// Solves witnesses by interpreting circuits with the ACVM.
+pub struct AcvmSolver<'b, B: BlackBoxFunctionSolver> {
+    blackbox_solver: &'b B,
+}
+
+impl<'b, B: BlackBoxFunctionSolver> AcvmSolver<'b, B> {
+    pub fn new(blackbox_solver: &'b B) -> Self {
+        AcvmSolver { blackbox_solver }
+    }
+}
+
+impl<B: BlackBoxFunctionSolver> WitnessSolver for AcvmSolver<'_, B> {
+    fn solve(
+        &self,
+        circuit: &Circuit,
+        initial_witness: WitnessMap,
+        foreign_call_executor: &mut dyn ForeignCallExecutor,
+    ) -> Result<WitnessMap, NargoError> {
+        solve_with_acvm(circuit, initial_witness, self.blackbox_solver, foreign_call_executor)
+    }
+}
+
+pub(super) fn solve_with_acvm<B: BlackBoxFunctionSolver>(
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut dyn ForeignCallExecutor,
 ) -> Result<WitnessMap, NargoError> {
     let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness);
 
+    // Assertions with a format string message resolve that message at runtime, just before failing.
+    // The message only applies if solving fails before the solver stops for anything else.
+    let mut dynamic_assert_message: Option<String> = None;
+
     loop {
         let solver_status = acvm.solve();
+        let assert_message = dynamic_assert_message.take();
 
         match solver_status {
             ACVMStatus::Solved => break,
@@ -24,28 +76,14 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
                 unreachable!("Execution should not stop while in `InProgress` state.")
             }
             ACVMStatus::Failure(error) => {
-                let call_stack = match &error {
-                    OpcodeResolutionError::UnsatisfiedConstrain {
-                        opcode_location: ErrorLocation::Resolved(opcode_location),
-                    } => Some(vec![*opcode_location]),
-                    OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
-                        Some(call_stack.clone())
-                    }
-                    _ => None,
-                };
-
-                return Err(NargoError::ExecutionError(match call_stack {
-                    Some(call_stack) => {
-                        if let Some(assert_message) = circuit.get_assert_message(
-                            *call_stack.last().expect("Call stacks should not be empty"),
-                        ) {
-                            ExecutionError::AssertionFailed(assert_message.to_owned(), call_stack)
-                        } else {
-                            ExecutionError::SolvingError(error)
-                        }
-                    }
-                    None => ExecutionError::SolvingError(error),
-                }));
+                return Err(execution_failure(circuit, error, assert_message, acvm.witness_map()));
+            }
+            ACVMStatus::RequiresForeignCall(foreign_call)
+                if foreign_call.function == ForeignCall::AssertMessage.name() =>
+            {
+                let message: PrintableValueDisplay = foreign_call.inputs.as_slice().try_into()?;
+                dynamic_assert_message = Some(message.to_string());
+                acvm.resolve_pending_foreign_call(ForeignCallResult { values: vec![] });
             }
             ACVMStatus::RequiresForeignCall(foreign_call) => {
                 let foreign_call_result = foreign_call_executor.execute(&foreign_call)?;
@@ -57,3 +95,75 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver, F: ForeignCallExecutor>(
     let solved_witness = acvm.finalize();
     Ok(solved_witness)
 }
+
+/// Builds the error returned when solving `circuit` fails with `error`, using the assertion message
+/// resolved at runtime if there is one, or otherwise the one stored in the circuit.
+pub(super) fn execution_failure(
+    circuit: &Circuit,
+    error: OpcodeResolutionError,
+    dynamic_assert_message: Option<String>,
+    witness_map: &WitnessMap,
+) -> NargoError {
+    let call_stack = match &error {
+        OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(opcode_location),
+        } => Some(vec![*opcode_location]),
+        OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => Some(call_stack.clone()),
+        _ => None,
+    };
+
+    let witness_values = failing_opcode_witnesses(circuit, &error, witness_map);
+    let execution_error = match call_stack {
+        Some(call_stack) => {
+            let assert_message = dynamic_assert_message.or_else(|| {
+                circuit
+                    .get_assert_message(
+                        *call_stack.last().expect("Call stacks should not be empty"),
+                    )
+                    .map(ToOwned::to_owned)
+            });
+            if let Some(assert_message) = assert_message {
+                ExecutionError::AssertionFailed(assert_message, call_stack)
+            } else {
+                ExecutionError::SolvingError(error)
+            }
+        }
+        None => ExecutionError::SolvingError(error),
+    };
+    NargoError::ExecutionError(execution_error, witness_values)
+}
+
+/// Returns the values of the witnesses used by the opcode at which `error` occurred, so that they can
+/// be reported alongside it.
+fn failing_opcode_witnesses(
+    circuit: &Circuit,
+    error: &OpcodeResolutionError,
+    witness_map: &WitnessMap,
+) -> WitnessMap {
+    let opcode_location = match error {
+        OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(opcode_location),
+        }
+        | OpcodeResolutionError::IndexOutOfBounds {
+            opcode_location: ErrorLocation::Resolved(opcode_location),
+            ..
+        } => Some(*opcode_location),
+        OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
+            call_stack.first().copied()
+        }
+        _ => None,
+    };
+    let Some(OpcodeLocation::Acir(acir_index) | OpcodeLocation::Brillig { acir_index, .. }) =
+        opcode_location
+    else {
+        return WitnessMap::new();
+    };
+
+    let mut values = WitnessMap::new();
+    for witness in circuit.opcodes.get(acir_index).map(opcode_witnesses).unwrap_or_default() {
+        if let Some(value) = witness_map.get(&witness) {
+            values.insert(witness, *value);
+        }
+    }
+    values
+}
@@ -0,0 +1,78 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use acvm::acir::{circuit::Circuit, native_types::WitnessMap};
+use noirc_abi::witness::{deserialize_witness, serialize_witness};
+
+use crate::errors::WitnessSolverError;
+use crate::NargoError;
+
+use super::{execute::WitnessSolver, foreign_calls::ForeignCallExecutor};
+
+/// Solves witnesses by running an external binary, such as a solver compiled natively for the circuit.
+///
+/// The binary is invoked as `<binary> --bytecode <PATH> --witness <PATH> --output <PATH>`. The
+/// bytecode file holds the serialized circuit, as passed to backends, and the witness file holds
+/// the values of the program's inputs in nargo's binary witness format. The binary must write the
+/// solved witness in the same format to the output path, or exit unsuccessfully with an error
+/// message on stderr.
+///
+/// Foreign calls are not forwarded to nargo, so the binary must resolve any which the circuit makes.
+#[derive(Debug, Clone)]
+pub struct ExternalWitnessSolver {
+    binary_path: PathBuf,
+}
+
+impl ExternalWitnessSolver {
+    pub fn new(binary_path: PathBuf) -> Self {
+        ExternalWitnessSolver { binary_path }
+    }
+
+    pub fn binary_path(&self) -> &Path {
+        &self.binary_path
+    }
+
+    fn run(
+        &self,
+        circuit: &Circuit,
+        initial_witness: WitnessMap,
+    ) -> Result<WitnessMap, WitnessSolverError> {
+        let temp_directory = tempfile::tempdir()?;
+        let bytecode_path = temp_directory.path().join("circuit.bytecode");
+        let witness_path = temp_directory.path().join("initial_witness.gz");
+        let output_path = temp_directory.path().join("solved_witness.gz");
+
+        std::fs::write(&bytecode_path, Circuit::serialize_circuit(circuit))?;
+        std::fs::write(&witness_path, serialize_witness(&initial_witness)?)?;
+
+        let output = Command::new(&self.binary_path)
+            .arg("--bytecode")
+            .arg(&bytecode_path)
+            .arg("--witness")
+            .arg(&witness_path)
+            .arg("--output")
+            .arg(&output_path)
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+            return Err(WitnessSolverError::Failed(stderr));
+        }
+
+        let solved_witness = deserialize_witness(&std::fs::read(&output_path)?)?;
+        Ok(solved_witness)
+    }
+}
+
+impl WitnessSolver for ExternalWitnessSolver {
+    fn solve(
+        &self,
+        circuit: &Circuit,
+        initial_witness: WitnessMap,
+        _foreign_call_executor: &mut dyn ForeignCallExecutor,
+    ) -> Result<WitnessMap, NargoError> {
+        self.run(circuit, initial_witness).map_err(|error| NargoError::WitnessSolverError {
+            binary_path: self.binary_path.clone(),
+            error,
+        })
+    }
+}
@@ -5,6 +5,8 @@ use acvm::{
 use iter_extended::vecmap;
 use noirc_printable_type::{decode_string_value, ForeignCallError, PrintableValueDisplay};
 
+use super::json_rpc::JsonRpcForeignCallResolver;
+
 pub trait ForeignCallExecutor {
     fn execute(
         &mut self,
@@ -16,6 +18,7 @@ pub trait ForeignCallExecutor {
 /// After resolution of a foreign call, nargo will restart execution of the ACVM
 pub(crate) enum ForeignCall {
     Println,
+    AssertMessage,
     Sequence,
     ReverseSequence,
     CreateMock,
@@ -35,6 +38,7 @@ impl ForeignCall {
     pub(crate) fn name(&self) -> &'static str {
         match self {
             ForeignCall::Println => "println",
+            ForeignCall::AssertMessage => "assert_message",
             ForeignCall::Sequence => "get_number_sequence",
             ForeignCall::ReverseSequence => "get_reverse_number_sequence",
             ForeignCall::CreateMock => "create_mock",
@@ -48,6 +52,7 @@ impl ForeignCall {
     pub(crate) fn lookup(op_name: &str) -> Option<ForeignCall> {
         match op_name {
             "println" => Some(ForeignCall::Println),
+            "assert_message" => Some(ForeignCall::AssertMessage),
             "get_number_sequence" => Some(ForeignCall::Sequence),
             "get_reverse_number_sequence" => Some(ForeignCall::ReverseSequence),
             "create_mock" => Some(ForeignCall::CreateMock),
@@ -101,12 +106,20 @@ pub struct DefaultForeignCallExecutor {
     mocked_responses: Vec<MockedCall>,
     /// Whether to print [`ForeignCall::Println`] output.
     show_output: bool,
+    /// The resolver for foreign calls which aren't handled natively or by a mock.
+    external_resolver: Option<JsonRpcForeignCallResolver>,
 }
 
 impl DefaultForeignCallExecutor {
     pub fn new(show_output: bool) -> Self {
         DefaultForeignCallExecutor { show_output, ..DefaultForeignCallExecutor::default() }
     }
+
+    /// Forwards any foreign calls which can't be handled by nargo to `resolver`.
+    pub fn with_external_resolver(mut self, resolver: JsonRpcForeignCallResolver) -> Self {
+        self.external_resolver = Some(resolver);
+        self
+    }
 }
 
 impl DefaultForeignCallExecutor {
@@ -147,6 +160,8 @@ impl ForeignCallExecutor for DefaultForeignCallExecutor {
                 }
                 Ok(ForeignCallResult { values: vec![] })
             }
+            // Assertion messages are captured by `execute_circuit`, there is nothing to do here.
+            Some(ForeignCall::AssertMessage) => Ok(ForeignCallResult { values: vec![] }),
             Some(ForeignCall::Sequence) => {
                 let sequence_length: u128 =
                     foreign_call.inputs[0].unwrap_value().to_field().to_u128();
@@ -219,8 +234,13 @@ impl ForeignCallExecutor for DefaultForeignCallExecutor {
                 let response_position = self
                     .mocked_responses
                     .iter()
-                    .position(|response| response.matches(foreign_call_name, &foreign_call.inputs))
-                    .unwrap_or_else(|| panic!("Unknown foreign call {}", foreign_call_name));
+                    .position(|response| response.matches(foreign_call_name, &foreign_call.inputs));
+                let Some(response_position) = response_position else {
+                    return match &mut self.external_resolver {
+                        Some(resolver) => resolver.resolve(foreign_call),
+                        None => Err(ForeignCallError::NoHandler(foreign_call_name.to_owned())),
+                    };
+                };
 
                 let mock = self
                     .mocked_responses
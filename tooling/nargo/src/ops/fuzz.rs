@@ -0,0 +1,346 @@
+use acvm::{BlackBoxFunctionSolver, FieldElement};
+use noirc_abi::input_parser::InputValue;
+use noirc_abi::{Abi, AbiType, InputMap, Sign};
+use noirc_driver::CompiledProgram;
+
+use crate::NargoError;
+
+use super::execute::{execute_circuit, AcvmSolver};
+use super::foreign_calls::DefaultForeignCallExecutor;
+
+/// The most executions spent shrinking a failing case, so that programs which are slow to execute
+/// still report a counterexample in reasonable time.
+const MAX_SHRINK_ATTEMPTS: usize = 2000;
+
+pub enum FuzzOutcome {
+    /// Every set of inputs sampled executed successfully.
+    Passed,
+    /// The program failed to execute, with `inputs` being the smallest inputs found which fail.
+    Failed { inputs: InputMap, error: NargoError },
+}
+
+/// Executes a program with `runs` sets of inputs sampled from its ABI, shrinking the first set which
+/// fails to execute.
+///
+/// Any failure to execute the circuit, such as a failing assertion, counts as a counterexample.
+/// Other errors, such as an oracle which can't be resolved, are returned as they stop every run.
+pub fn fuzz_program<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    program: &CompiledProgram,
+    runs: usize,
+    seed: u64,
+) -> Result<FuzzOutcome, NargoError> {
+    let mut generator = InputGenerator::new(seed);
+    for _ in 0..runs {
+        let inputs = generator.generate(&program.abi);
+        if let Some(error) = execute(blackbox_solver, program, &inputs)? {
+            let (inputs, error) = shrink(blackbox_solver, program, inputs, error)?;
+            return Ok(FuzzOutcome::Failed { inputs, error });
+        }
+    }
+    Ok(FuzzOutcome::Passed)
+}
+
+/// Executes a program, returning the error it failed with if the circuit couldn't be solved.
+fn execute<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    program: &CompiledProgram,
+    inputs: &InputMap,
+) -> Result<Option<NargoError>, NargoError> {
+    let initial_witness =
+        program.abi.encode(inputs, None).expect("sampled inputs should match the ABI");
+    let mut foreign_call_executor = DefaultForeignCallExecutor::new(false);
+    let result = execute_circuit(
+        &program.circuit,
+        initial_witness,
+        &AcvmSolver::new(blackbox_solver),
+        &mut foreign_call_executor,
+    );
+    match result {
+        Ok(_) => Ok(None),
+        Err(error @ NargoError::ExecutionError(..)) => Ok(Some(error)),
+        Err(error) => Err(error),
+    }
+}
+
+/// Repeatedly replaces the value of a parameter by a simpler one for which the program still fails,
+/// until none of the simpler values fail.
+fn shrink<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    program: &CompiledProgram,
+    mut inputs: InputMap,
+    mut error: NargoError,
+) -> Result<(InputMap, NargoError), NargoError> {
+    let mut attempts = 0;
+    'shrinking: loop {
+        for param in &program.abi.parameters {
+            for candidate in shrink_candidates(&inputs[&param.name], &param.typ) {
+                if attempts == MAX_SHRINK_ATTEMPTS {
+                    break 'shrinking;
+                }
+                attempts += 1;
+
+                let mut candidate_inputs = inputs.clone();
+                candidate_inputs.insert(param.name.clone(), candidate);
+                if let Some(candidate_error) = execute(blackbox_solver, program, &candidate_inputs)?
+                {
+                    inputs = candidate_inputs;
+                    error = candidate_error;
+                    continue 'shrinking;
+                }
+            }
+        }
+        break;
+    }
+    Ok((inputs, error))
+}
+
+/// Returns values of type `typ` which are simpler than `value`, the simplest first.
+///
+/// Each candidate is smaller than `value` in some well-founded order, so that shrinking terminates.
+fn shrink_candidates(value: &InputValue, typ: &AbiType) -> Vec<InputValue> {
+    let simplest = simplest_value(typ);
+    if *value == simplest {
+        return Vec::new();
+    }
+
+    let mut candidates = vec![simplest];
+    match (value, typ) {
+        (InputValue::Field(field), _) => {
+            if *field != FieldElement::one() {
+                candidates.push(InputValue::Field(FieldElement::one()));
+            }
+            match field.try_into_u128() {
+                Some(integer) if integer > 2 => {
+                    candidates.push(InputValue::Field((integer / 2).into()));
+                    candidates.push(InputValue::Field((integer - 1).into()));
+                }
+                Some(_) => (),
+                // Integers which don't fit into 128 bits are only ever fields.
+                None => candidates.push(InputValue::Field(field.to_u128().into())),
+            }
+        }
+        (InputValue::Vec(elements), AbiType::Array { typ, .. }) => {
+            for (index, element) in elements.iter().enumerate() {
+                for candidate in shrink_candidates(element, typ) {
+                    let mut elements = elements.clone();
+                    elements[index] = candidate;
+                    candidates.push(InputValue::Vec(elements));
+                }
+            }
+        }
+        (InputValue::Vec(elements), AbiType::Tuple { fields }) => {
+            for (index, (element, typ)) in elements.iter().zip(fields).enumerate() {
+                for candidate in shrink_candidates(element, typ) {
+                    let mut elements = elements.clone();
+                    elements[index] = candidate;
+                    candidates.push(InputValue::Vec(elements));
+                }
+            }
+        }
+        (InputValue::Struct(members), AbiType::Struct { fields, .. }) => {
+            for (name, typ) in fields {
+                for candidate in shrink_candidates(&members[name], typ) {
+                    let mut members = members.clone();
+                    members.insert(name.clone(), candidate);
+                    candidates.push(InputValue::Struct(members));
+                }
+            }
+        }
+        (InputValue::Variant(variant, values), AbiType::Enum { variants, .. }) => {
+            let field_types = variants
+                .iter()
+                .find_map(|(name, field_types)| (name == variant).then_some(field_types))
+                .expect("the variant should be one of the enum's");
+            for (index, (value, typ)) in values.iter().zip(field_types).enumerate() {
+                for candidate in shrink_candidates(value, typ) {
+                    let mut values = values.clone();
+                    values[index] = candidate;
+                    candidates.push(InputValue::Variant(variant.clone(), values));
+                }
+            }
+        }
+        // Strings are only shrunk to their simplest value, as the characters making a program fail
+        // are rarely the ones closest to `a`.
+        _ => (),
+    }
+    candidates
+}
+
+/// The value which shrinking tends towards: zero, false, a string of `a`s or the first variant of
+/// an enum, all the way down.
+fn simplest_value(typ: &AbiType) -> InputValue {
+    match typ {
+        AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => {
+            InputValue::Field(FieldElement::zero())
+        }
+        AbiType::String { length } => InputValue::String("a".repeat(*length as usize)),
+        AbiType::Array { length, typ } => {
+            InputValue::Vec(vec![simplest_value(typ); *length as usize])
+        }
+        AbiType::Tuple { fields } => InputValue::Vec(fields.iter().map(simplest_value).collect()),
+        AbiType::Struct { fields, .. } => InputValue::Struct(
+            fields.iter().map(|(name, typ)| (name.clone(), simplest_value(typ))).collect(),
+        ),
+        AbiType::Enum { variants, .. } => {
+            let (name, field_types) = &variants[0];
+            InputValue::Variant(name.clone(), field_types.iter().map(simplest_value).collect())
+        }
+    }
+}
+
+/// Samples inputs which match an ABI from a seeded pseudorandom generator, so that a run can be
+/// reproduced from its seed.
+///
+/// Edge cases such as zero and the bounds of integer types are sampled more often than they would
+/// be uniformly, as they're the values most likely to make a program fail.
+pub struct InputGenerator {
+    state: u64,
+}
+
+impl InputGenerator {
+    pub fn new(seed: u64) -> Self {
+        InputGenerator { state: seed }
+    }
+
+    pub fn generate(&mut self, abi: &Abi) -> InputMap {
+        abi.parameters.iter().map(|param| (param.name.clone(), self.value(&param.typ))).collect()
+    }
+
+    fn value(&mut self, typ: &AbiType) -> InputValue {
+        match typ {
+            AbiType::Field => InputValue::Field(self.field()),
+            AbiType::Integer { sign, width } => InputValue::Field(self.integer(*sign, *width)),
+            AbiType::Boolean => InputValue::Field((self.below(2) as u128).into()),
+            AbiType::String { length } => {
+                const CHARACTERS: &[u8] =
+                    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+                let string = (0..*length)
+                    .map(|_| CHARACTERS[self.below(CHARACTERS.len() as u64) as usize] as char)
+                    .collect();
+                InputValue::String(string)
+            }
+            AbiType::Array { length, typ } => {
+                InputValue::Vec((0..*length).map(|_| self.value(typ)).collect())
+            }
+            AbiType::Tuple { fields } => {
+                InputValue::Vec(fields.iter().map(|typ| self.value(typ)).collect())
+            }
+            AbiType::Struct { fields, .. } => InputValue::Struct(
+                fields.iter().map(|(name, typ)| (name.clone(), self.value(typ))).collect(),
+            ),
+            AbiType::Enum { variants, .. } => {
+                let (name, field_types) = &variants[self.below(variants.len() as u64) as usize];
+                let values = field_types.iter().map(|typ| self.value(typ)).collect();
+                InputValue::Variant(name.clone(), values)
+            }
+        }
+    }
+
+    fn field(&mut self) -> FieldElement {
+        match self.below(4) {
+            0 => {
+                let edge_cases = [FieldElement::zero(), FieldElement::one(), -FieldElement::one()];
+                edge_cases[self.below(edge_cases.len() as u64) as usize]
+            }
+            1 => (self.below(256) as u128).into(),
+            _ => {
+                let bytes: Vec<u8> = (0..4).flat_map(|_| self.next_u64().to_be_bytes()).collect();
+                FieldElement::from_be_bytes_reduce(&bytes)
+            }
+        }
+    }
+
+    /// Samples an integer, with signed integers being encoded in two's complement as in the ABI.
+    fn integer(&mut self, sign: Sign, width: u32) -> FieldElement {
+        // Integers wider than 128 bits are only sampled from their lowest 128 bits.
+        let max = if width >= 128 { u128::MAX } else { (1 << width) - 1 };
+        let value = match self.below(4) {
+            0 => {
+                let edge_cases = match sign {
+                    Sign::Unsigned => vec![0, 1, max],
+                    // Zero, one, -1, the largest value and the smallest value.
+                    Sign::Signed => vec![0, 1, max, max >> 1, (max >> 1) + 1],
+                };
+                edge_cases[self.below(edge_cases.len() as u64) as usize]
+            }
+            1 => self.below(256) as u128 & max,
+            _ => (((self.next_u64() as u128) << 64) | self.next_u64() as u128) & max,
+        };
+        value.into()
+    }
+
+    /// Returns a number sampled uniformly enough from `0..bound`.
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// The SplitMix64 generator, which is fast and good enough to sample inputs from.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noirc_abi::input_parser::InputValue;
+    use noirc_abi::{AbiType, Sign};
+
+    use super::{shrink_candidates, InputGenerator};
+
+    #[test]
+    fn sampled_values_match_their_types() {
+        let types = [
+            AbiType::Field,
+            AbiType::Boolean,
+            AbiType::Integer { sign: Sign::Unsigned, width: 8 },
+            AbiType::Integer { sign: Sign::Signed, width: 64 },
+            AbiType::String { length: 5 },
+            AbiType::Array {
+                length: 3,
+                typ: Box::new(AbiType::Integer { sign: Sign::Unsigned, width: 1 }),
+            },
+            AbiType::Struct {
+                path: "Point".to_owned(),
+                fields: vec![
+                    ("x".to_owned(), AbiType::Field),
+                    ("y".to_owned(), AbiType::Integer { sign: Sign::Unsigned, width: 32 }),
+                ],
+            },
+            AbiType::Enum {
+                path: "Shape".to_owned(),
+                variants: vec![
+                    ("Empty".to_owned(), Vec::new()),
+                    ("Square".to_owned(), vec![AbiType::Boolean]),
+                ],
+            },
+        ];
+
+        let mut generator = InputGenerator::new(42);
+        for typ in &types {
+            for _ in 0..100 {
+                let value = generator.value(typ);
+                assert!(value.matches_abi(typ), "{value:?} doesn't match {typ:?}");
+                for candidate in shrink_candidates(&value, typ) {
+                    assert!(candidate.matches_abi(typ), "{candidate:?} doesn't match {typ:?}");
+                    assert_ne!(candidate, value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn simplest_values_have_no_shrink_candidates() {
+        let typ = AbiType::Integer { sign: Sign::Unsigned, width: 32 };
+        let value = InputValue::Field(7u128.into());
+
+        let candidates = shrink_candidates(&value, &typ);
+
+        assert_eq!(candidates[0], InputValue::Field(0u128.into()));
+        assert!(shrink_candidates(&candidates[0], &typ).is_empty());
+    }
+}
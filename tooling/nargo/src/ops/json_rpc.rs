@@ -0,0 +1,211 @@
+use acvm::{
+    acir::brillig::{ForeignCallParam, ForeignCallResult, Value},
+    pwg::ForeignCallWaitInfo,
+    FieldElement,
+};
+use iter_extended::{try_vecmap, vecmap};
+use noirc_abi::{
+    input_parser::{json::JsonTypes, InputValue},
+    AbiOracle, AbiType,
+};
+use noirc_printable_type::ForeignCallError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// Resolves foreign calls which nargo doesn't handle itself by forwarding them to an external
+/// JSON-RPC 2.0 server.
+///
+/// The method of each request is the name of the oracle. If the program's ABI includes the oracle's
+/// signature then the params hold one value per oracle parameter, encoded in the same way as a
+/// JSON input file, and the result is expected to be the oracle's return value encoded in the same
+/// way. Otherwise each param and each element of the result is either a single field as a hex string
+/// or an array of them, mirroring the [`ForeignCallParam`]s seen by the ACVM.
+///
+/// Errors returned by the server are reported as a failure of the program's execution.
+#[derive(Debug)]
+pub struct JsonRpcForeignCallResolver {
+    url: String,
+    oracles: Vec<AbiOracle>,
+    next_id: u64,
+}
+
+#[derive(Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: Vec<JsonValue>,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse {
+    #[serde(default)]
+    result: JsonValue,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// The shape of a single output of a foreign call.
+enum OutputShape {
+    Single,
+    Array(usize),
+}
+
+impl JsonRpcForeignCallResolver {
+    /// Creates a resolver which sends foreign calls to the server at `url`, using `oracles` to
+    /// encode the calls with their types.
+    pub fn new(url: String, oracles: Vec<AbiOracle>) -> Self {
+        JsonRpcForeignCallResolver { url, oracles, next_id: 0 }
+    }
+
+    pub(crate) fn resolve(
+        &mut self,
+        foreign_call: &ForeignCallWaitInfo,
+    ) -> Result<ForeignCallResult, ForeignCallError> {
+        let name = foreign_call.function.as_str();
+        let error =
+            |message: String| ForeignCallError::ResolverError { name: name.to_owned(), message };
+        let oracle = self.oracles.iter().find(|oracle| oracle.name == name);
+
+        let params = match oracle {
+            Some(oracle) => encode_typed_params(oracle, &foreign_call.inputs).map_err(error)?,
+            None => vecmap(&foreign_call.inputs, encode_raw_param),
+        };
+
+        let request = JsonRpcRequest { jsonrpc: "2.0", id: self.next_id, method: name, params };
+        self.next_id += 1;
+        let response = self.send(&request).map_err(error)?;
+
+        if let Some(JsonRpcError { code, message }) = response.error {
+            return Err(error(format!("{message} (error code {code})")));
+        }
+
+        let values = match oracle {
+            Some(oracle) => decode_typed_result(oracle, response.result),
+            None => decode_raw_result(response.result),
+        }
+        .map_err(error)?;
+
+        Ok(ForeignCallResult { values })
+    }
+
+    fn send(&self, request: &JsonRpcRequest) -> Result<JsonRpcResponse, String> {
+        let body = serde_json::to_string(request).map_err(|err| err.to_string())?;
+        let response = ureq::post(&self.url)
+            .set("Content-Type", "application/json")
+            .send_string(&body)
+            .map_err(|err| err.to_string())?
+            .into_string()
+            .map_err(|err| err.to_string())?;
+        serde_json::from_str(&response).map_err(|err| format!("invalid response: {err}"))
+    }
+}
+
+fn encode_typed_params(
+    oracle: &AbiOracle,
+    inputs: &[ForeignCallParam],
+) -> Result<Vec<JsonValue>, String> {
+    let fields: Vec<FieldElement> =
+        inputs.iter().flat_map(|param| vecmap(param.values(), |value| value.to_field())).collect();
+    let values = oracle.decode_inputs(&fields).map_err(|err| err.to_string())?;
+
+    try_vecmap(values.iter().zip(&oracle.parameters), |(value, param)| {
+        let json =
+            JsonTypes::try_from_input_value(value, &param.typ).map_err(|err| err.to_string())?;
+        serde_json::to_value(json).map_err(|err| err.to_string())
+    })
+}
+
+fn decode_typed_result(
+    oracle: &AbiOracle,
+    result: JsonValue,
+) -> Result<Vec<ForeignCallParam>, String> {
+    let Some(return_type) = &oracle.return_type else {
+        return Ok(Vec::new());
+    };
+
+    let json: JsonTypes = serde_json::from_value(result).map_err(|err| err.to_string())?;
+    let value = InputValue::try_from_json(json, return_type, &oracle.name)
+        .map_err(|err| err.to_string())?;
+    let mut fields = oracle.encode_return_value(value).map_err(|err| err.to_string())?.into_iter();
+
+    let mut shapes = Vec::new();
+    output_shapes(return_type, &mut shapes);
+    Ok(vecmap(shapes, |shape| match shape {
+        OutputShape::Single => ForeignCallParam::Single(Value::from(fields.next().unwrap())),
+        OutputShape::Array(length) => {
+            ForeignCallParam::Array(vecmap(fields.by_ref().take(length), Value::from))
+        }
+    }))
+}
+
+/// Collects the outputs which a value of type `typ` is returned from a foreign call as.
+///
+/// Arrays and strings are returned as a single flattened array while the fields of structs and
+/// tuples are returned as separate outputs.
+fn output_shapes(typ: &AbiType, shapes: &mut Vec<OutputShape>) {
+    match typ {
+        AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => {
+            shapes.push(OutputShape::Single);
+        }
+        AbiType::Array { .. } | AbiType::String { .. } => {
+            shapes.push(OutputShape::Array(typ.field_count() as usize));
+        }
+        AbiType::Struct { fields, .. } => {
+            for (_, field_type) in fields {
+                output_shapes(field_type, shapes);
+            }
+        }
+        AbiType::Tuple { fields } => {
+            for field_type in fields {
+                output_shapes(field_type, shapes);
+            }
+        }
+        AbiType::Enum { variants, .. } => {
+            shapes.push(OutputShape::Single);
+            for (_, field_types) in variants {
+                for field_type in field_types {
+                    output_shapes(field_type, shapes);
+                }
+            }
+        }
+    }
+}
+
+fn encode_raw_param(param: &ForeignCallParam) -> JsonValue {
+    let encode_value =
+        |value: &Value| JsonValue::String(format!("0x{}", value.to_field().to_hex()));
+    match param {
+        ForeignCallParam::Single(value) => encode_value(value),
+        ForeignCallParam::Array(values) => JsonValue::Array(vecmap(values, encode_value)),
+    }
+}
+
+fn decode_raw_result(result: JsonValue) -> Result<Vec<ForeignCallParam>, String> {
+    let decode_value = |value: &JsonValue| -> Result<Value, String> {
+        let field = match value {
+            JsonValue::String(hex) => FieldElement::from_hex(hex),
+            JsonValue::Number(number) => {
+                number.as_u64().map(|number| FieldElement::from(number as u128))
+            }
+            _ => None,
+        };
+        field.map(Value::from).ok_or_else(|| format!("expected a field element, found {value}"))
+    };
+
+    match result {
+        JsonValue::Null => Ok(Vec::new()),
+        JsonValue::Array(outputs) => try_vecmap(outputs, |output| match output {
+            JsonValue::Array(values) => {
+                Ok(ForeignCallParam::Array(try_vecmap(&values, decode_value)?))
+            }
+            value => Ok(ForeignCallParam::Single(decode_value(&value)?)),
+        }),
+        result => Err(format!("expected an array of outputs, found {result}")),
+    }
+}
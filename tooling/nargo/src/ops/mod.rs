@@ -1,11 +1,25 @@
 pub use self::compile::{compile_program, compile_workspace};
-pub use self::execute::execute_circuit;
+pub use self::coverage::{CoverageReport, TracingAcvmSolver};
+pub use self::execute::{execute_circuit, AcvmSolver, WitnessSolver};
+pub use self::external_solver::ExternalWitnessSolver;
 pub use self::foreign_calls::{DefaultForeignCallExecutor, ForeignCallExecutor};
-pub use self::optimize::{optimize_contract, optimize_program};
+pub use self::fuzz::{fuzz_program, FuzzOutcome, InputGenerator};
+pub use self::json_rpc::JsonRpcForeignCallResolver;
+pub use self::optimize::{optimization_error, optimize_contract, optimize_program};
+pub use self::parallel_solver::ParallelAcvmSolver;
+pub use self::proof::{circuit_hash, Proof};
+pub use self::recursion::RecursionArtifacts;
 pub use self::test::{run_test, TestStatus};
 
 mod compile;
+mod coverage;
 mod execute;
+mod external_solver;
 mod foreign_calls;
+mod fuzz;
+mod json_rpc;
 mod optimize;
+mod parallel_solver;
+mod proof;
+mod recursion;
 mod test;
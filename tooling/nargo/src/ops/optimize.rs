@@ -1,6 +1,8 @@
 use acvm::{acir::circuit::Opcode, Language};
+use fm::FileId;
 use iter_extended::try_vecmap;
 use noirc_driver::{CompiledContract, CompiledProgram};
+use noirc_errors::{error_codes, CustomDiagnostic, FileDiagnostic};
 
 use crate::NargoError;
 
@@ -32,3 +34,12 @@ pub fn optimize_contract(
 
     Ok(CompiledContract { functions, ..contract })
 }
+
+/// Converts an error encountered while optimizing a program, such as an opcode which neither
+/// the backend nor acvm's fallbacks support, into a diagnostic which can be reported alongside
+/// any other compilation errors.
+pub fn optimization_error(error: NargoError) -> FileDiagnostic {
+    CustomDiagnostic::from_message(&error.to_string())
+        .with_code(error_codes::E0703)
+        .in_file(FileId::default())
+}
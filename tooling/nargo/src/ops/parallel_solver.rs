@@ -0,0 +1,513 @@
+use std::collections::HashMap;
+
+use acvm::acir::brillig::Opcode as BrilligOpcode;
+use acvm::acir::circuit::brillig::{BrilligInputs, BrilligOutputs};
+use acvm::acir::circuit::directives::Directive;
+use acvm::acir::circuit::opcodes::{BlackBoxFuncCall, BlockId};
+use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
+use acvm::acir::native_types::{Expression, Witness, WitnessMap};
+use acvm::pwg::{ErrorLocation, OpcodeResolutionError};
+use acvm::BlackBoxFunctionSolver;
+use rayon::prelude::*;
+
+use crate::errors::ExecutionError;
+use crate::NargoError;
+
+use super::execute::{solve_with_acvm, WitnessSolver};
+use super::foreign_calls::{DefaultForeignCallExecutor, ForeignCallExecutor};
+
+/// Solves witnesses with the ACVM, solving groups of opcodes which don't depend on each other on
+/// multiple threads.
+///
+/// Opcodes are split into stages, each of which holds groups of opcodes that only depend on each other
+/// and on the witnesses solved by previous stages. A new stage starts at each opcode which joins
+/// several groups, such as one combining the results of independent hash chains. Groups which make
+/// foreign calls are solved in order on the calling thread, as the foreign call executor can't be
+/// shared between threads.
+///
+/// Circuits which don't split into independent groups, or whose memory blocks span several stages,
+/// are solved by a single ACVM as usual.
+pub struct ParallelAcvmSolver<F> {
+    new_blackbox_solver: F,
+}
+
+impl<B: BlackBoxFunctionSolver, F: Fn() -> B + Sync> ParallelAcvmSolver<F> {
+    /// Creates a solver which calls `new_blackbox_solver` to create a blackbox function solver for
+    /// each thread.
+    pub fn new(new_blackbox_solver: F) -> Self {
+        ParallelAcvmSolver { new_blackbox_solver }
+    }
+}
+
+impl<B: BlackBoxFunctionSolver, F: Fn() -> B + Sync> WitnessSolver for ParallelAcvmSolver<F> {
+    fn solve(
+        &self,
+        circuit: &Circuit,
+        initial_witness: WitnessMap,
+        foreign_call_executor: &mut dyn ForeignCallExecutor,
+    ) -> Result<WitnessMap, NargoError> {
+        let Some(stages) = plan_stages(circuit, &initial_witness) else {
+            let blackbox_solver = (self.new_blackbox_solver)();
+            return solve_with_acvm(
+                circuit,
+                initial_witness,
+                &blackbox_solver,
+                foreign_call_executor,
+            );
+        };
+
+        let blackbox_solver = (self.new_blackbox_solver)();
+        let mut witness_map = initial_witness;
+        for stage in stages {
+            let (sequential_groups, parallel_groups): (Vec<_>, Vec<_>) =
+                stage.iter().partition(|group| makes_foreign_calls(circuit, group));
+
+            let mut results: Vec<(usize, Result<WitnessMap, NargoError>)> = parallel_groups
+                .par_iter()
+                .map_init(&self.new_blackbox_solver, |blackbox_solver, group| {
+                    let mut foreign_call_executor = DefaultForeignCallExecutor::new(false);
+                    let result = solve_group(
+                        circuit,
+                        group,
+                        &witness_map,
+                        blackbox_solver,
+                        &mut foreign_call_executor,
+                    );
+                    (group[0], result)
+                })
+                .collect();
+            for group in sequential_groups {
+                let result = solve_group(
+                    circuit,
+                    group,
+                    &witness_map,
+                    &blackbox_solver,
+                    foreign_call_executor,
+                );
+                results.push((group[0], result));
+            }
+
+            // Report the error of the earliest failing group, as a single ACVM would have.
+            results.sort_by_key(|(first_opcode, _)| *first_opcode);
+            for (_, result) in results {
+                for (witness, value) in result? {
+                    witness_map.insert(witness, value);
+                }
+            }
+        }
+
+        Ok(witness_map)
+    }
+}
+
+/// Solves the opcodes of `circuit` at the indices in `group`, given the witnesses solved so far.
+fn solve_group<B: BlackBoxFunctionSolver>(
+    circuit: &Circuit,
+    group: &[usize],
+    witness_map: &WitnessMap,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut dyn ForeignCallExecutor,
+) -> Result<WitnessMap, NargoError> {
+    let opcodes: Vec<Opcode> = group.iter().map(|index| circuit.opcodes[*index].clone()).collect();
+
+    let mut initial_witness = WitnessMap::new();
+    for witness in opcodes.iter().flat_map(opcode_witnesses) {
+        if let Some(value) = witness_map.get(&witness) {
+            initial_witness.insert(witness, *value);
+        }
+    }
+
+    let assert_messages = circuit
+        .assert_messages
+        .iter()
+        .filter_map(|(location, message)| {
+            let acir_index = group.binary_search(&acir_index(*location)).ok()?;
+            Some((with_acir_index(*location, acir_index), message.clone()))
+        })
+        .collect();
+    let group_circuit = Circuit { opcodes, assert_messages, ..Circuit::default() };
+
+    solve_with_acvm(&group_circuit, initial_witness, blackbox_solver, foreign_call_executor)
+        .map_err(|error| remap_error_locations(error, group))
+}
+
+/// Splits the opcodes of `circuit` into stages of independent groups, returning `None` if the circuit
+/// can't be solved any faster this way.
+fn plan_stages(circuit: &Circuit, initial_witness: &WitnessMap) -> Option<Vec<Vec<Vec<usize>>>> {
+    let num_opcodes = circuit.opcodes.len();
+    // Each witness is solved by the first opcode which uses it, as the ACVM solves opcodes in order.
+    let mut solved_by: HashMap<Witness, usize> = HashMap::new();
+    let mut last_block_use: HashMap<BlockId, usize> = HashMap::new();
+    let mut block_stages: HashMap<BlockId, usize> = HashMap::new();
+    let mut opcode_stages: Vec<usize> = Vec::with_capacity(num_opcodes);
+    let mut parents: Vec<usize> = Vec::with_capacity(num_opcodes);
+    let mut stage = 0;
+
+    for (index, opcode) in circuit.opcodes.iter().enumerate() {
+        let witnesses = opcode_witnesses(opcode);
+        let block_id = match opcode {
+            Opcode::MemoryOp { block_id, .. } | Opcode::MemoryInit { block_id, .. } => {
+                Some(*block_id)
+            }
+            _ => None,
+        };
+
+        let dependencies = witnesses
+            .iter()
+            .filter_map(|witness| solved_by.get(witness))
+            .chain(block_id.and_then(|block_id| last_block_use.get(&block_id)))
+            .copied();
+        let mut roots: Vec<usize> = dependencies
+            .filter(|dependency| opcode_stages[*dependency] == stage)
+            .map(|dependency| find_root(&mut parents, dependency))
+            .collect();
+        roots.sort_unstable();
+        roots.dedup();
+
+        if roots.len() > 1 {
+            stage += 1;
+            roots.clear();
+        }
+        if let Some(block_id) = block_id {
+            // The contents of memory blocks are held by a single ACVM so can't be shared between stages.
+            if *block_stages.entry(block_id).or_insert(stage) != stage {
+                return None;
+            }
+            last_block_use.insert(block_id, index);
+        }
+
+        parents.push(index);
+        for root in roots {
+            parents[root] = index;
+        }
+        opcode_stages.push(stage);
+        for witness in witnesses {
+            if !initial_witness.contains_key(&witness) {
+                solved_by.entry(witness).or_insert(index);
+            }
+        }
+    }
+
+    let mut stages: Vec<Vec<Vec<usize>>> = vec![Vec::new(); stage + 1];
+    let mut group_indices: HashMap<usize, usize> = HashMap::new();
+    for index in 0..num_opcodes {
+        let root = find_root(&mut parents, index);
+        let groups = &mut stages[opcode_stages[index]];
+        let group_index = *group_indices.entry(root).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[group_index].push(index);
+    }
+
+    stages.iter().any(|groups| groups.len() > 1).then_some(stages)
+}
+
+fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+fn makes_foreign_calls(circuit: &Circuit, group: &[usize]) -> bool {
+    group.iter().any(|index| match &circuit.opcodes[*index] {
+        Opcode::Brillig(brillig) => brillig
+            .bytecode
+            .iter()
+            .any(|opcode| matches!(opcode, BrilligOpcode::ForeignCall { .. })),
+        _ => false,
+    })
+}
+
+/// Returns every witness which `opcode` reads or writes.
+pub(super) fn opcode_witnesses(opcode: &Opcode) -> Vec<Witness> {
+    let mut witnesses = Vec::new();
+    match opcode {
+        Opcode::Arithmetic(expression) => add_expression_witnesses(expression, &mut witnesses),
+        Opcode::BlackBoxFuncCall(call) => {
+            witnesses.extend(call.get_inputs_vec().iter().map(|input| input.witness));
+            if let BlackBoxFuncCall::RecursiveAggregation {
+                input_aggregation_object: Some(inputs),
+                ..
+            } = call
+            {
+                witnesses.extend(inputs.iter().map(|input| input.witness));
+            }
+            witnesses.extend(call.get_outputs_vec());
+        }
+        Opcode::Directive(Directive::Quotient(quotient)) => {
+            add_expression_witnesses(&quotient.a, &mut witnesses);
+            add_expression_witnesses(&quotient.b, &mut witnesses);
+            witnesses.extend([quotient.q, quotient.r]);
+            if let Some(predicate) = &quotient.predicate {
+                add_expression_witnesses(predicate, &mut witnesses);
+            }
+        }
+        Opcode::Directive(Directive::ToLeRadix { a, b, .. }) => {
+            add_expression_witnesses(a, &mut witnesses);
+            witnesses.extend(b);
+        }
+        Opcode::Directive(Directive::PermutationSort { inputs, bits, .. }) => {
+            for expression in inputs.iter().flatten() {
+                add_expression_witnesses(expression, &mut witnesses);
+            }
+            witnesses.extend(bits);
+        }
+        Opcode::Brillig(brillig) => {
+            for input in &brillig.inputs {
+                match input {
+                    BrilligInputs::Single(expression) => {
+                        add_expression_witnesses(expression, &mut witnesses);
+                    }
+                    BrilligInputs::Array(expressions) => {
+                        for expression in expressions {
+                            add_expression_witnesses(expression, &mut witnesses);
+                        }
+                    }
+                }
+            }
+            for output in &brillig.outputs {
+                match output {
+                    BrilligOutputs::Simple(witness) => witnesses.push(*witness),
+                    BrilligOutputs::Array(outputs) => witnesses.extend(outputs),
+                }
+            }
+            if let Some(predicate) = &brillig.predicate {
+                add_expression_witnesses(predicate, &mut witnesses);
+            }
+        }
+        Opcode::MemoryOp { op, predicate, .. } => {
+            add_expression_witnesses(&op.operation, &mut witnesses);
+            add_expression_witnesses(&op.index, &mut witnesses);
+            add_expression_witnesses(&op.value, &mut witnesses);
+            if let Some(predicate) = predicate {
+                add_expression_witnesses(predicate, &mut witnesses);
+            }
+        }
+        Opcode::MemoryInit { init, .. } => witnesses.extend(init),
+    }
+    witnesses
+}
+
+fn add_expression_witnesses(expression: &Expression, witnesses: &mut Vec<Witness>) {
+    for (_, lhs, rhs) in &expression.mul_terms {
+        witnesses.extend([*lhs, *rhs]);
+    }
+    witnesses.extend(expression.linear_combinations.iter().map(|(_, witness)| *witness));
+}
+
+fn acir_index(location: OpcodeLocation) -> usize {
+    match location {
+        OpcodeLocation::Acir(index) | OpcodeLocation::Brillig { acir_index: index, .. } => index,
+    }
+}
+
+fn with_acir_index(location: OpcodeLocation, acir_index: usize) -> OpcodeLocation {
+    match location {
+        OpcodeLocation::Acir(_) => OpcodeLocation::Acir(acir_index),
+        OpcodeLocation::Brillig { brillig_index, .. } => {
+            OpcodeLocation::Brillig { acir_index, brillig_index }
+        }
+    }
+}
+
+/// Maps the opcode locations in an error from solving a group back to the locations of the opcodes
+/// in the full circuit.
+fn remap_error_locations(error: NargoError, group: &[usize]) -> NargoError {
+    let remap = |location: OpcodeLocation| with_acir_index(location, group[acir_index(location)]);
+    let remap_call_stack =
+        |call_stack: Vec<OpcodeLocation>| call_stack.into_iter().map(remap).collect();
+
+    let (execution_error, witness_values) = match error {
+        NargoError::ExecutionError(execution_error, witness_values) => {
+            (execution_error, witness_values)
+        }
+        error => return error,
+    };
+    let execution_error = match execution_error {
+        ExecutionError::AssertionFailed(message, call_stack) => {
+            ExecutionError::AssertionFailed(message, remap_call_stack(call_stack))
+        }
+        ExecutionError::SolvingError(error) => ExecutionError::SolvingError(match error {
+            OpcodeResolutionError::UnsatisfiedConstrain {
+                opcode_location: ErrorLocation::Resolved(location),
+            } => OpcodeResolutionError::UnsatisfiedConstrain {
+                opcode_location: ErrorLocation::Resolved(remap(location)),
+            },
+            OpcodeResolutionError::IndexOutOfBounds {
+                opcode_location: ErrorLocation::Resolved(location),
+                index,
+                array_size,
+            } => OpcodeResolutionError::IndexOutOfBounds {
+                opcode_location: ErrorLocation::Resolved(remap(location)),
+                index,
+                array_size,
+            },
+            OpcodeResolutionError::BrilligFunctionFailed { message, call_stack } => {
+                OpcodeResolutionError::BrilligFunctionFailed {
+                    message,
+                    call_stack: remap_call_stack(call_stack),
+                }
+            }
+            error => error,
+        }),
+    };
+    NargoError::ExecutionError(execution_error, witness_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::acir::circuit::opcodes::{BlockId, MemOp};
+    use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
+    use acvm::acir::native_types::{Expression, Witness, WitnessMap};
+    use acvm::{BlackBoxFunctionSolver, BlackBoxResolutionError, FieldElement};
+
+    use crate::errors::ExecutionError;
+    use crate::ops::{AcvmSolver, DefaultForeignCallExecutor, WitnessSolver};
+    use crate::NargoError;
+
+    use super::{plan_stages, ParallelAcvmSolver};
+
+    struct StubbedSolver;
+
+    impl BlackBoxFunctionSolver for StubbedSolver {
+        fn schnorr_verify(
+            &self,
+            _public_key_x: &FieldElement,
+            _public_key_y: &FieldElement,
+            _signature: &[u8],
+            _message: &[u8],
+        ) -> Result<bool, BlackBoxResolutionError> {
+            unimplemented!();
+        }
+
+        fn pedersen_commitment(
+            &self,
+            _inputs: &[FieldElement],
+            _domain_separator: u32,
+        ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+            unimplemented!();
+        }
+
+        fn pedersen_hash(
+            &self,
+            _inputs: &[FieldElement],
+            _domain_separator: u32,
+        ) -> Result<FieldElement, BlackBoxResolutionError> {
+            unimplemented!();
+        }
+
+        fn fixed_base_scalar_mul(
+            &self,
+            _low: &FieldElement,
+            _high: &FieldElement,
+        ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+            unimplemented!();
+        }
+    }
+
+    /// Asserts that the sum of each witness times its coefficient, plus `constant`, is zero.
+    fn linear(terms: &[(i128, u32)], constant: i128) -> Opcode {
+        Opcode::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: terms
+                .iter()
+                .map(|(coefficient, witness)| (FieldElement::from(*coefficient), Witness(*witness)))
+                .collect(),
+            q_c: FieldElement::from(constant),
+        })
+    }
+
+    /// Two independent chains, `w3 = 2 * (w1 + 1)` and `w6 = 3 * (w4 + 3)`, joined by `w7 = w3 + w6`.
+    fn joined_chains() -> Circuit {
+        let opcodes = vec![
+            linear(&[(1, 1), (-1, 2)], 1),
+            linear(&[(2, 2), (-1, 3)], 0),
+            linear(&[(1, 4), (-1, 5)], 3),
+            linear(&[(3, 5), (-1, 6)], 0),
+            linear(&[(1, 3), (1, 6), (-1, 7)], 0),
+        ];
+        Circuit { current_witness_index: 7, opcodes, ..Circuit::default() }
+    }
+
+    fn inputs() -> WitnessMap {
+        BTreeMap::from([
+            (Witness(1), FieldElement::from(1_i128)),
+            (Witness(4), FieldElement::from(2_i128)),
+        ])
+        .into()
+    }
+
+    fn solve(solver: &dyn WitnessSolver, circuit: &Circuit) -> Result<WitnessMap, NargoError> {
+        solver.solve(circuit, inputs(), &mut DefaultForeignCallExecutor::new(false))
+    }
+
+    #[test]
+    fn independent_chains_are_split_at_the_join() {
+        let stages = plan_stages(&joined_chains(), &inputs());
+
+        assert_eq!(stages, Some(vec![vec![vec![0, 1], vec![2, 3]], vec![vec![4]]]));
+    }
+
+    #[test]
+    fn solves_the_same_witnesses_as_a_single_acvm() {
+        let circuit = joined_chains();
+
+        let parallel = solve(&ParallelAcvmSolver::new(|| StubbedSolver), &circuit).unwrap();
+        let sequential = solve(&AcvmSolver::new(&StubbedSolver), &circuit).unwrap();
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel[&Witness(7)], FieldElement::from(19_i128));
+    }
+
+    #[test]
+    fn memory_blocks_used_across_a_join_are_not_split() {
+        let opcodes = vec![
+            linear(&[(1, 1), (-1, 2)], 1),
+            Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(2)] },
+            linear(&[(1, 4), (-1, 5)], 3),
+            linear(&[(1, 2), (1, 5), (-1, 6)], 0),
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp::read_at_mem_index(Expression::zero(), Witness(7)),
+                predicate: None,
+            },
+        ];
+        let circuit = Circuit { current_witness_index: 7, opcodes, ..Circuit::default() };
+
+        assert_eq!(plan_stages(&circuit, &inputs()), None);
+
+        let witnesses = solve(&ParallelAcvmSolver::new(|| StubbedSolver), &circuit).unwrap();
+        assert_eq!(witnesses[&Witness(7)], FieldElement::from(2_i128));
+    }
+
+    #[test]
+    fn failures_are_reported_at_their_location_in_the_circuit() {
+        // The second chain also asserts that `w5 == 10`, which fails as `w5 == 5`
+        let mut circuit = joined_chains();
+        circuit.opcodes.insert(3, linear(&[(1, 5)], -10));
+        circuit.assert_messages = vec![(OpcodeLocation::Acir(3), "w5 must be 10".to_owned())];
+        let stages = plan_stages(&circuit, &inputs());
+        assert_eq!(stages, Some(vec![vec![vec![0, 1], vec![2, 3, 4]], vec![vec![5]]]));
+
+        let parallel = solve(&ParallelAcvmSolver::new(|| StubbedSolver), &circuit).unwrap_err();
+        let sequential = solve(&AcvmSolver::new(&StubbedSolver), &circuit).unwrap_err();
+
+        for error in [parallel, sequential] {
+            match error {
+                NargoError::ExecutionError(
+                    ExecutionError::AssertionFailed(message, call_stack),
+                    _,
+                ) => {
+                    assert_eq!(message, "w5 must be 10");
+                    assert_eq!(call_stack, vec![OpcodeLocation::Acir(3)]);
+                }
+                other => panic!("Expected a failed assertion, found {other:?}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use acvm::{
+    acir::{
+        circuit::Circuit,
+        native_types::{Witness, WitnessMap},
+    },
+    FieldElement,
+};
+
+use crate::errors::ProofError;
+
+const CIRCUIT_HASH_LENGTH: usize = 32;
+const WITNESS_INDEX_LENGTH: usize = 4;
+
+/// Hashes the serialized form of `circuit` so that a [`Proof`] can be matched against the circuit it proves.
+pub fn circuit_hash(circuit: &Circuit) -> [u8; 32] {
+    acvm::blackbox_solver::sha256(&Circuit::serialize_circuit(circuit))
+        .expect("sha256 should not fail on byte inputs")
+}
+
+/// A proof generated by a backend along with the public inputs it was generated for and the
+/// hash of the circuit being proven.
+///
+/// When serialized, a proof is laid out as the 32 byte circuit hash, the number of public inputs
+/// as a little-endian `u32`, each public input as a little-endian `u32` witness index followed by
+/// its value as a big-endian field element, and then the raw bytes returned by the backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    circuit_hash: [u8; 32],
+    public_inputs: WitnessMap,
+    bytes: Vec<u8>,
+}
+
+impl Proof {
+    pub fn new(circuit: &Circuit, public_inputs: WitnessMap, bytes: Vec<u8>) -> Self {
+        Proof { circuit_hash: circuit_hash(circuit), public_inputs, bytes }
+    }
+
+    /// The raw proof bytes as returned by the backend, without any public inputs.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The public inputs of the proven circuit, ordered by witness index.
+    pub fn public_inputs(&self) -> &WitnessMap {
+        &self.public_inputs
+    }
+
+    pub fn circuit_hash(&self) -> [u8; 32] {
+        self.circuit_hash
+    }
+
+    /// Returns an error if this proof was not generated for `circuit`.
+    pub fn check_circuit(&self, circuit: &Circuit) -> Result<(), ProofError> {
+        if self.circuit_hash == circuit_hash(circuit) {
+            Ok(())
+        } else {
+            Err(ProofError::CircuitMismatch)
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let public_inputs: Vec<_> = self.public_inputs.clone().into_iter().collect();
+
+        let mut buf = Vec::with_capacity(
+            CIRCUIT_HASH_LENGTH
+                + WITNESS_INDEX_LENGTH
+                + public_inputs.len()
+                    * (WITNESS_INDEX_LENGTH + FieldElement::max_num_bytes() as usize)
+                + self.bytes.len(),
+        );
+        buf.extend_from_slice(&self.circuit_hash);
+        buf.extend_from_slice(&(public_inputs.len() as u32).to_le_bytes());
+        for (witness, value) in public_inputs {
+            buf.extend_from_slice(&witness.witness_index().to_le_bytes());
+            buf.extend(value.to_be_bytes());
+        }
+        buf.extend_from_slice(&self.bytes);
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofError> {
+        let field_length = FieldElement::max_num_bytes() as usize;
+
+        let (circuit_hash, rest) = split_bytes(bytes, CIRCUIT_HASH_LENGTH)?;
+        let (num_public_inputs, mut rest) = split_bytes(rest, WITNESS_INDEX_LENGTH)?;
+        let num_public_inputs = u32::from_le_bytes(num_public_inputs.try_into().unwrap());
+
+        let mut public_inputs = WitnessMap::new();
+        for _ in 0..num_public_inputs {
+            let (witness_index, remaining) = split_bytes(rest, WITNESS_INDEX_LENGTH)?;
+            let (value, remaining) = split_bytes(remaining, field_length)?;
+            rest = remaining;
+
+            let witness = Witness(u32::from_le_bytes(witness_index.try_into().unwrap()));
+            public_inputs.insert(witness, FieldElement::from_be_bytes_reduce(value));
+        }
+
+        Ok(Proof {
+            circuit_hash: circuit_hash.try_into().unwrap(),
+            public_inputs,
+            bytes: rest.to_vec(),
+        })
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    pub fn from_hex(hex_str: &str) -> Result<Self, ProofError> {
+        let bytes = hex::decode(hex_str.trim())?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+fn split_bytes(bytes: &[u8], length: usize) -> Result<(&[u8], &[u8]), ProofError> {
+    if bytes.len() < length {
+        return Err(ProofError::Truncated);
+    }
+    Ok(bytes.split_at(length))
+}
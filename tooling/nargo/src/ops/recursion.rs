@@ -0,0 +1,86 @@
+use acvm::FieldElement;
+use noirc_abi::{input_parser::InputValue, Abi, AbiType, InputMap};
+
+use crate::errors::RecursionError;
+
+use super::Proof;
+
+/// The inputs to `std::verify_proof` which verify a [`Proof`] within another circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecursionArtifacts {
+    pub verification_key: Vec<FieldElement>,
+    pub proof: Vec<FieldElement>,
+    pub public_inputs: Vec<FieldElement>,
+    pub key_hash: FieldElement,
+}
+
+impl RecursionArtifacts {
+    /// Combines `proof` with its fields and those of its verification key, as returned by the backend.
+    ///
+    /// Backends return the public inputs of the proof at the start of its fields, which are separated
+    /// out as `std::verify_proof` takes them as a separate argument.
+    pub fn new(
+        proof: &Proof,
+        proof_as_fields: Vec<FieldElement>,
+        key_hash: FieldElement,
+        vk_as_fields: Vec<FieldElement>,
+    ) -> Self {
+        let public_inputs: Vec<FieldElement> =
+            proof.public_inputs().clone().into_iter().map(|(_, value)| value).collect();
+        let proof_fields = proof_as_fields.into_iter().skip(public_inputs.len()).collect();
+
+        RecursionArtifacts {
+            verification_key: vk_as_fields,
+            proof: proof_fields,
+            public_inputs,
+            key_hash,
+        }
+    }
+
+    /// Returns the artifacts as inputs to the parameters of an outer circuit with the given `abi`.
+    ///
+    /// The parameters are expected to be called `verification_key`, `proof`, `public_inputs` and
+    /// `key_hash`, each preceded by `prefix`, and the lengths of their arrays must match the artifacts.
+    pub fn to_input_map(&self, abi: &Abi, prefix: &str) -> Result<InputMap, RecursionError> {
+        let arrays = [
+            ("verification_key", &self.verification_key),
+            ("proof", &self.proof),
+            ("public_inputs", &self.public_inputs),
+        ];
+
+        let mut input_map = InputMap::new();
+        for (name, fields) in arrays {
+            let name = format!("{prefix}{name}");
+            match parameter_type(abi, &name)? {
+                AbiType::Array { length, typ } if **typ == AbiType::Field => {
+                    if *length as usize != fields.len() {
+                        return Err(RecursionError::LengthMismatch {
+                            name,
+                            expected: *length as usize,
+                            found: fields.len(),
+                        });
+                    }
+                }
+                _ => return Err(RecursionError::TypeMismatch(name)),
+            }
+            let value = InputValue::Vec(fields.iter().copied().map(InputValue::Field).collect());
+            input_map.insert(name, value);
+        }
+
+        let name = format!("{prefix}key_hash");
+        if parameter_type(abi, &name)? != &AbiType::Field {
+            return Err(RecursionError::TypeMismatch(name));
+        }
+        input_map.insert(name, InputValue::Field(self.key_hash));
+
+        Ok(input_map)
+    }
+}
+
+fn parameter_type<'a>(abi: &'a Abi, name: &str) -> Result<&'a AbiType, RecursionError> {
+    abi.parameters
+        .iter()
+        .find(|param| param.name == name)
+        .map(|param| &param.typ)
+        .ok_or_else(|| RecursionError::MissingParameter(name.to_owned()))
+}
@@ -1,38 +1,101 @@
 use acvm::{acir::native_types::WitnessMap, BlackBoxFunctionSolver};
+use noirc_abi::Abi;
 use noirc_driver::{compile_no_check, CompileOptions};
-use noirc_errors::{debug_info::DebugInfo, FileDiagnostic};
+use noirc_errors::{debug_info::DebugInfo, FileDiagnostic, Location};
 use noirc_evaluator::errors::RuntimeError;
 use noirc_frontend::hir::{def_map::TestFunction, Context};
+use noirc_frontend::token::TestMode;
 
 use crate::{errors::try_to_diagnose_runtime_error, NargoError};
 
-use super::{execute_circuit, DefaultForeignCallExecutor};
+use super::{
+    execute_circuit, AcvmSolver, CoverageReport, DefaultForeignCallExecutor,
+    JsonRpcForeignCallResolver, TracingAcvmSolver,
+};
 
 pub enum TestStatus {
     Pass,
     Fail { message: String, error_diagnostic: Option<FileDiagnostic> },
     CompileError(FileDiagnostic),
+    Ignored { reason: Option<String> },
 }
 
+impl TestStatus {
+    /// Returns the location of the assertion which failed, or of the code which failed to
+    /// compile, taken from the first label of the test's diagnostic.
+    pub fn failure_location(&self) -> Option<Location> {
+        let diagnostic = match self {
+            TestStatus::Fail { error_diagnostic, .. } => error_diagnostic.as_ref()?,
+            TestStatus::CompileError(diagnostic) => diagnostic,
+            TestStatus::Pass | TestStatus::Ignored { .. } => return None,
+        };
+        let label = diagnostic.diagnostic.secondaries.first()?;
+        Some(Location::new(label.span, label.file.unwrap_or(diagnostic.file_id)))
+    }
+}
+
+/// Runs a test function. Oracle calls which aren't mocked by the test are sent to the JSON-RPC
+/// server at `oracle_resolver`, if one is given, and the lines executed by the test are added to
+/// `coverage`, if given.
 pub fn run_test<B: BlackBoxFunctionSolver>(
     blackbox_solver: &B,
     context: &Context,
     test_function: TestFunction,
     show_output: bool,
+    oracle_resolver: Option<&str>,
+    coverage: Option<&mut CoverageReport>,
     config: &CompileOptions,
 ) -> TestStatus {
-    let program = compile_no_check(context, config, test_function.get_id(), None, false);
+    // Tests which restrict the pipeline they run in override whether Brillig is forced
+    let force_brillig = match test_function.mode() {
+        TestMode::Any => config.force_brillig,
+        TestMode::OnlyAcir => false,
+        TestMode::OnlyBrillig => true,
+        TestMode::Ignore { reason } => return TestStatus::Ignored { reason: reason.clone() },
+    };
+    let config = CompileOptions { force_brillig, ..config.clone() };
+
+    let program = compile_no_check(context, &config, test_function.get_id(), None, false);
     match program {
         Ok(program) => {
+            let mut foreign_call_executor = DefaultForeignCallExecutor::new(show_output);
+            if let Some(url) = oracle_resolver {
+                let resolver =
+                    JsonRpcForeignCallResolver::new(url.to_owned(), program.abi.oracles.clone());
+                foreign_call_executor = foreign_call_executor.with_external_resolver(resolver);
+            }
+
             // Run the backend to ensure the PWG evaluates functions like std::hash::pedersen,
             // otherwise constraints involving these expressions will not error.
-            let circuit_execution = execute_circuit(
-                &program.circuit,
-                WitnessMap::new(),
-                blackbox_solver,
-                &mut DefaultForeignCallExecutor::new(show_output),
-            );
-            test_status_program_compile_pass(test_function, program.debug, circuit_execution)
+            let circuit_execution = match coverage {
+                Some(coverage) => {
+                    let solver = TracingAcvmSolver::new(blackbox_solver);
+                    let circuit_execution = execute_circuit(
+                        &program.circuit,
+                        WitnessMap::new(),
+                        &solver,
+                        &mut foreign_call_executor,
+                    );
+                    coverage.record(
+                        &program.debug,
+                        &solver.executed_opcodes(),
+                        &context.file_manager,
+                    );
+                    circuit_execution
+                }
+                None => execute_circuit(
+                    &program.circuit,
+                    WitnessMap::new(),
+                    &AcvmSolver::new(blackbox_solver),
+                    &mut foreign_call_executor,
+                ),
+            };
+            test_status_program_compile_pass(
+                test_function,
+                &program.abi,
+                program.debug,
+                circuit_execution,
+            )
         }
         Err(err) => test_status_program_compile_fail(err, test_function),
     }
@@ -56,8 +119,9 @@ fn test_status_program_compile_fail(err: RuntimeError, test_function: TestFuncti
     } else {
         None
     };
+    let error = err.to_string();
 
-    check_expected_failure_message(test_function, assert_message, Some(err.into()))
+    check_expected_failure_message(test_function, assert_message, &error, Some(err.into()))
 }
 
 /// The test function compiled successfully.
@@ -66,6 +130,7 @@ fn test_status_program_compile_fail(err: RuntimeError, test_function: TestFuncti
 /// passed/failed to determine the test status.
 fn test_status_program_compile_pass(
     test_function: TestFunction,
+    abi: &Abi,
     debug: DebugInfo,
     circuit_execution: Result<WitnessMap, NargoError>,
 ) -> TestStatus {
@@ -87,7 +152,7 @@ fn test_status_program_compile_pass(
     // If we reach here, then the circuit execution failed.
     //
     // Check if the function should have passed
-    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, &debug);
+    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, abi, &debug);
     let test_should_have_passed = !test_function.should_fail();
     if test_should_have_passed {
         return TestStatus::Fail {
@@ -99,13 +164,18 @@ fn test_status_program_compile_pass(
     check_expected_failure_message(
         test_function,
         circuit_execution_err.user_defined_failure_message().map(|s| s.to_string()),
+        &circuit_execution_err.to_string(),
         diagnostic,
     )
 }
 
+/// Checks that a test which was expected to fail failed with the expected message.
+/// `error` describes the failure when it was not a failed assertion with a message, such as an
+/// index out of bounds, so that tests which fail for the wrong reason can be told apart.
 fn check_expected_failure_message(
     test_function: TestFunction,
     failed_assertion: Option<String>,
+    error: &str,
     error_diagnostic: Option<FileDiagnostic>,
 ) -> TestStatus {
     // Extract the expected failure message, if there was one
@@ -125,12 +195,16 @@ fn check_expected_failure_message(
     }
 
     // The expected failure message does not match the actual failure message
-    TestStatus::Fail {
-        message: format!(
+    let message = match failed_assertion {
+        Some(failed_assertion) => format!(
             "\nerror: Test failed with the wrong message. \nExpected: {} \nGot: {}",
-            test_function.failure_reason().unwrap_or_default(),
-            failed_assertion.unwrap_or_default().trim_matches('\'')
+            expected_failure_message,
+            failed_assertion.trim_matches('\'')
         ),
-        error_diagnostic,
-    }
+        None => format!(
+            "\nerror: Test failed without an assertion message. \nExpected: {} \nGot error: {}",
+            expected_failure_message, error
+        ),
+    };
+    TestStatus::Fail { message, error_diagnostic }
 }
@@ -1,6 +1,8 @@
 use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
 
-use noirc_frontend::graph::CrateName;
+use noirc_driver::{CompileOptions, SsaPipeline};
+use noirc_frontend::graph::{CrateName, OverflowMode};
+use noirc_frontend::lints::{Lint, LintLevel};
 
 use crate::constants::{PROVER_INPUT_FILE, VERIFIER_INPUT_FILE};
 
@@ -50,6 +52,16 @@ pub struct Package {
     pub entry_path: PathBuf,
     pub name: CrateName,
     pub dependencies: BTreeMap<CrateName, Dependency>,
+    // Whether integer arithmetic in this package traps or wraps on overflow
+    pub overflow_mode: OverflowMode,
+    // The levels lints are reported at in this package, from its `[lints]` table
+    pub lint_levels: BTreeMap<Lint, LintLevel>,
+    // Whether every warning in this package is an error, from `warnings = "deny"` in its `[lints]` table
+    pub deny_warnings: bool,
+    // The SSA passes run when compiling this package, from its `[optimizer]` table
+    pub passes: Option<SsaPipeline>,
+    // An external binary used to solve the witness of this package instead of the ACVM
+    pub witness_solver: Option<PathBuf>,
 }
 
 impl Package {
@@ -75,4 +87,12 @@ impl Package {
     pub fn is_library(&self) -> bool {
         self.package_type == PackageType::Library
     }
+
+    /// Applies the settings from this package's Nargo.toml to `compile_options`, with those given
+    /// on the command line taking priority.
+    pub fn compile_options(&self, compile_options: &CompileOptions) -> CompileOptions {
+        let passes = compile_options.passes.clone().or_else(|| self.passes.clone());
+        let deny_warnings = compile_options.deny_warnings || self.deny_warnings;
+        CompileOptions { passes, deny_warnings, ..compile_options.clone() }
+    }
 }
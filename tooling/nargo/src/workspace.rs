@@ -10,7 +10,7 @@ use std::{
 };
 
 use crate::{
-    constants::{CONTRACT_DIR, PROOFS_DIR, TARGET_DIR},
+    constants::{CACHE_DIR, CONTRACT_DIR, EXPORT_DIR, PROOFS_DIR, TARGET_DIR},
     package::Package,
 };
 
@@ -40,6 +40,16 @@ impl Workspace {
     pub fn target_directory_path(&self) -> PathBuf {
         self.root_dir.join(TARGET_DIR)
     }
+
+    pub fn check_cache_path(&self, package: &Package) -> PathBuf {
+        let name: String = package.name.clone().into();
+        self.target_directory_path().join(CACHE_DIR).join(name).with_extension("json")
+    }
+
+    pub fn export_directory_path(&self, package: &Package) -> PathBuf {
+        let name: String = package.name.clone().into();
+        self.target_directory_path().join(EXPORT_DIR).join(name)
+    }
 }
 
 pub enum IntoIter<'a, T> {
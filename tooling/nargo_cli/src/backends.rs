@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use backend_interface::backends_directory;
-pub(crate) use backend_interface::Backend;
+pub(crate) use backend_interface::{get_backend, Backend, MOCK_BACKEND};
 
 fn active_backend_file_path() -> PathBuf {
     backends_directory().join(".selected_backend")
@@ -37,3 +37,21 @@ pub(crate) fn get_active_backend() -> String {
 
     std::fs::read_to_string(active_backend_file).expect("Could not read active backend file")
 }
+
+pub(crate) fn get_available_backends() -> Vec<String> {
+    let backend_directory_contents = std::fs::read_dir(backends_directory())
+        .expect("Could not read backends directory contents");
+
+    // TODO: Highlight the currently active backend.
+    backend_directory_contents
+        .into_iter()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.is_dir() {
+                path.file_name().map(|name| name.to_string_lossy().to_string())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
@@ -4,13 +4,12 @@ use backend_interface::backends_directory;
 
 use crate::{
     backends::{
-        clear_active_backend, get_active_backend, set_active_backend, ACVM_BACKEND_BARRETENBERG,
+        clear_active_backend, get_active_backend, get_available_backends, set_active_backend,
+        ACVM_BACKEND_BARRETENBERG,
     },
     errors::{BackendError, CliError},
 };
 
-use super::ls_cmd::get_available_backends;
-
 /// Uninstalls a backend
 #[derive(Debug, Clone, Args)]
 pub(crate) struct UninstallCommand {
@@ -1,12 +1,10 @@
 use clap::Args;
 
 use crate::{
-    backends::set_active_backend,
+    backends::{get_available_backends, set_active_backend, MOCK_BACKEND},
     errors::{BackendError, CliError},
 };
 
-use super::ls_cmd::get_available_backends;
-
 /// Select the backend to use
 #[derive(Debug, Clone, Args)]
 pub(crate) struct UseCommand {
@@ -16,7 +14,7 @@ pub(crate) struct UseCommand {
 pub(crate) fn run(args: UseCommand) -> Result<(), CliError> {
     let backends = get_available_backends();
 
-    if !backends.contains(&args.backend) {
+    if args.backend != MOCK_BACKEND && !backends.contains(&args.backend) {
         return Err(BackendError::UnknownBackend(args.backend).into());
     }
 
@@ -0,0 +1,284 @@
+use std::time::{Duration, Instant};
+
+use backend_interface::BackendInfo;
+use clap::Args;
+use nargo::constants::PROVER_INPUT_FILE;
+use nargo::package::Package;
+use nargo::workspace::Workspace;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_abi::input_parser::Format;
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::graph::CrateName;
+use prettytable::{row, table};
+use serde::Serialize;
+
+use super::compile_cmd::compile_bin_package;
+use super::execute_cmd::execute_program;
+use super::fs::inputs::read_inputs_from_file;
+use super::NargoConfig;
+use crate::{backends::Backend, errors::CliError};
+
+/// Measures how long it takes to compile and execute the program, and optionally to prove and verify it
+#[derive(Debug, Clone, Args)]
+pub(crate) struct BenchCommand {
+    /// Also measure proving and verifying with the active backend
+    #[arg(long)]
+    prove: bool,
+
+    /// The number of times to run each step
+    #[arg(long, short = 'n', default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+
+    /// The name of the toml file which contains the inputs for the prover
+    #[clap(long, short, default_value = PROVER_INPUT_FILE)]
+    prover_name: String,
+
+    /// The name of the package to benchmark
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Benchmark all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// Output a JSON formatted report, e.g. to track performance over releases
+    #[clap(long)]
+    json: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(
+    backend: &dyn Backend,
+    args: BenchCommand,
+    config: NargoConfig,
+) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.clone().map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    let mut packages = Vec::new();
+    for package in &workspace {
+        packages.push(bench_package(backend, &workspace, package, &args)?);
+    }
+
+    let report = BenchReport {
+        nargo_version: env!("CARGO_PKG_VERSION"),
+        backend: backend.name().to_owned(),
+        runs: args.runs,
+        packages,
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string(&report).unwrap());
+    } else {
+        let mut bench_table = table!([
+            Fm->"Package",
+            Fm->"Step",
+            Fm->"Mean (ms)",
+            Fm->"Median (ms)",
+            Fm->"Std. Dev. (ms)"
+        ]);
+        for package in &report.packages {
+            for (step, timings) in package.steps() {
+                bench_table.add_row(row![
+                    Fm->format!("{}", package.name),
+                    format!("{step}"),
+                    Fc->format!("{:.2}", timings.mean_ms),
+                    Fc->format!("{:.2}", timings.median_ms),
+                    Fc->format!("{:.2}", timings.std_dev_ms),
+                ]);
+            }
+        }
+        bench_table.printstd();
+
+        if let Some(memory) = peak_memory() {
+            println!("Peak memory of nargo: {}", format_bytes(memory.nargo));
+            if args.prove {
+                println!("Peak memory of the backend: {}", format_bytes(memory.backend));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn bench_package(
+    backend: &dyn Backend,
+    workspace: &Workspace,
+    package: &Package,
+    args: &BenchCommand,
+) -> Result<PackageBench, CliError> {
+    let BackendInfo { language: np_language, opcode_support, .. } = if args.prove {
+        backend.get_backend_info()?
+    } else {
+        backend.get_backend_info_or_default()
+    };
+
+    let mut compile = Vec::new();
+    let mut execute = Vec::new();
+    let mut prove = Vec::new();
+    let mut verify = Vec::new();
+    for _ in 0..args.runs {
+        let start = Instant::now();
+        let program = compile_bin_package(
+            workspace,
+            package,
+            &args.compile_options,
+            np_language,
+            &opcode_support,
+        )?;
+        compile.push(start.elapsed());
+
+        let (inputs_map, _) = read_inputs_from_file(
+            &package.root_dir,
+            &args.prover_name,
+            Format::Toml,
+            &program.abi,
+        )?;
+        let start = Instant::now();
+        let solved_witness =
+            execute_program(&program, &inputs_map, None, package.witness_solver.as_deref())?;
+        execute.push(start.elapsed());
+
+        if !args.prove {
+            continue;
+        }
+
+        let public_abi = program.abi.public_abi();
+        let (public_inputs, return_value) = public_abi.decode(&solved_witness)?;
+        let public_inputs = public_abi.encode(&public_inputs, return_value)?;
+
+        let start = Instant::now();
+        let proof = backend.prove(&program.circuit, solved_witness, false)?;
+        prove.push(start.elapsed());
+
+        let start = Instant::now();
+        let valid_proof = backend.verify(&proof, public_inputs, &program.circuit, false)?;
+        verify.push(start.elapsed());
+        if !valid_proof {
+            return Err(CliError::InvalidProof("".into()));
+        }
+    }
+
+    Ok(PackageBench {
+        name: package.name.to_string(),
+        compile: Timings::new(&compile),
+        execute: Timings::new(&execute),
+        prove: args.prove.then(|| Timings::new(&prove)),
+        verify: args.prove.then(|| Timings::new(&verify)),
+        peak_memory: peak_memory(),
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct BenchReport {
+    nargo_version: &'static str,
+    backend: String,
+    runs: u32,
+    packages: Vec<PackageBench>,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageBench {
+    name: String,
+    compile: Timings,
+    execute: Timings,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prove: Option<Timings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verify: Option<Timings>,
+    /// The peak memory after benchmarking this package, which includes any previous packages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peak_memory: Option<PeakMemory>,
+}
+
+impl PackageBench {
+    fn steps(&self) -> Vec<(&'static str, &Timings)> {
+        let mut steps = vec![("compile", &self.compile), ("execute", &self.execute)];
+        steps.extend(self.prove.as_ref().map(|timings| ("prove", timings)));
+        steps.extend(self.verify.as_ref().map(|timings| ("verify", timings)));
+        steps
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Timings {
+    mean_ms: f64,
+    median_ms: f64,
+    std_dev_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+}
+
+impl Timings {
+    fn new(samples: &[Duration]) -> Self {
+        let mut samples: Vec<f64> =
+            samples.iter().map(|sample| sample.as_secs_f64() * 1000.0).collect();
+        samples.sort_by(|a, b| a.total_cmp(b));
+
+        let count = samples.len() as f64;
+        let mean_ms = samples.iter().sum::<f64>() / count;
+        let middle = samples.len() / 2;
+        let median_ms = if samples.len() % 2 == 0 {
+            (samples[middle - 1] + samples[middle]) / 2.0
+        } else {
+            samples[middle]
+        };
+        let variance = if samples.len() > 1 {
+            samples.iter().map(|sample| (sample - mean_ms).powi(2)).sum::<f64>() / (count - 1.0)
+        } else {
+            0.0
+        };
+
+        Timings {
+            mean_ms,
+            median_ms,
+            std_dev_ms: variance.sqrt(),
+            min_ms: samples[0],
+            max_ms: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// The most memory held at once by nargo and by the backend processes it ran, in bytes.
+#[derive(Debug, Clone, Copy, Serialize)]
+struct PeakMemory {
+    nargo: u64,
+    backend: u64,
+}
+
+#[cfg(unix)]
+fn peak_memory() -> Option<PeakMemory> {
+    fn max_rss(who: libc::c_int) -> Option<u64> {
+        // SAFETY: `getrusage` only writes to the `rusage` struct passed to it.
+        let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+        if unsafe { libc::getrusage(who, &mut usage) } != 0 {
+            return None;
+        }
+        // macOS reports the maximum resident set size in bytes while other platforms use kilobytes.
+        let max_rss = usage.ru_maxrss as u64;
+        Some(if cfg!(target_os = "macos") { max_rss } else { max_rss * 1024 })
+    }
+
+    Some(PeakMemory {
+        nargo: max_rss(libc::RUSAGE_SELF)?,
+        backend: max_rss(libc::RUSAGE_CHILDREN)?,
+    })
+}
+
+#[cfg(not(unix))]
+fn peak_memory() -> Option<PeakMemory> {
+    None
+}
+
+fn format_bytes(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
@@ -3,7 +3,10 @@ use crate::errors::CliError;
 
 use clap::Args;
 use iter_extended::btree_map;
-use nargo::{errors::CompileError, package::Package, prepare_package};
+use nargo::{
+    cache::CheckCache, errors::CompileError, override_lint_levels, package::Package,
+    prepare_package, workspace::Workspace,
+};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::{AbiParameter, AbiType, MAIN_RETURN_NAME};
 use noirc_driver::{
@@ -33,7 +36,7 @@ pub(crate) struct CheckCommand {
 }
 
 pub(crate) fn run(
-    _backend: &Backend,
+    _backend: &dyn Backend,
     args: CheckCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -48,21 +51,47 @@ pub(crate) fn run(
     )?;
 
     for package in &workspace {
-        check_package(package, &args.compile_options)?;
+        check_package(&workspace, package, &args.compile_options)?;
         println!("[{}] Constraint system successfully built!", package.name);
     }
     Ok(())
 }
 
-fn check_package(package: &Package, compile_options: &CompileOptions) -> Result<(), CompileError> {
+fn check_package(
+    workspace: &Workspace,
+    package: &Package,
+    compile_options: &CompileOptions,
+) -> Result<(), CompileError> {
+    let compile_options = &package.compile_options(compile_options);
+
+    // Packages which were checked without any warnings don't need to be checked again until their
+    // files change, unless their input files were deleted and need to be regenerated.
+    let cache_path = workspace.check_cache_path(package);
+    let has_input_files = package.is_library()
+        || package.is_contract()
+        || (package.prover_input_path().exists() && package.verifier_input_path().exists());
+    if has_input_files
+        && CheckCache::read(&cache_path).map_or(false, |cache| cache.is_fresh(compile_options))
+    {
+        return Ok(());
+    }
+
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
-    check_crate_and_report_errors(
-        &mut context,
-        crate_id,
+    override_lint_levels(&mut context, crate_id, compile_options);
+    let result = check_crate(&mut context, crate_id, compile_options.deny_warnings);
+    let has_warnings = !matches!(&result, Ok((_, warnings)) if warnings.is_empty());
+    super::compile_cmd::report_errors(
+        result,
+        &context.file_manager,
         compile_options.deny_warnings,
         compile_options.silence_warnings,
     )?;
+    let missing_main = package.is_binary() && context.get_main_function(&crate_id).is_none();
+    if !has_warnings && !missing_main {
+        // Failing to save the cache only means that the package is checked again next time.
+        let _ = CheckCache::new(package, &context.file_manager, compile_options).write(&cache_path);
+    }
 
     if package.is_library() || package.is_contract() {
         // Libraries do not have ABIs while contracts have many, so we cannot generate a `Prover.toml` file.
@@ -114,6 +143,13 @@ fn create_input_toml_template(
                 );
                 toml::Value::Table(default_value_map)
             }
+            AbiType::Enum { .. } => {
+                let default_value_map = toml::map::Map::from_iter([
+                    ("variant".to_owned(), toml::Value::String("".to_owned())),
+                    ("fields".to_owned(), toml::Value::Array(Vec::new())),
+                ]);
+                toml::Value::Table(default_value_map)
+            }
             _ => toml::Value::String("".to_owned()),
         }
     }
@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+use nargo::artifacts::program::PreprocessedProgram;
+use nargo::package::Package;
+use nargo::workspace::Workspace;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_abi::{Abi, AbiType, Sign};
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use noirc_frontend::graph::CrateName;
+
+use crate::backends::Backend;
+use crate::errors::CliError;
+
+use super::fs::{create_named_dir, program::read_program_from_file, write_to_file};
+use super::NargoConfig;
+
+/// Generates bindings for calling compiled programs and exported functions from other languages
+#[derive(Debug, Clone, Args)]
+pub(crate) struct CodegenCommand {
+    /// The language to generate bindings for
+    #[clap(long, value_enum)]
+    language: CodegenLanguage,
+
+    /// The directory to write the bindings to [default: ./target/codegen]
+    #[clap(long)]
+    out_dir: Option<PathBuf>,
+
+    /// The name of the package to generate bindings for
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Generate bindings for all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CodegenLanguage {
+    /// An `index.js` module with a function per program, typed by an `index.d.ts` declaration file
+    Typescript,
+}
+
+pub(crate) fn run(
+    _backend: &dyn Backend,
+    args: CodegenCommand,
+    config: NargoConfig,
+) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+    let out_dir = args.out_dir.unwrap_or_else(|| workspace.target_directory_path().join("codegen"));
+
+    for package in &workspace {
+        let programs = read_package_programs(&workspace, package)?;
+        if programs.is_empty() {
+            return Err(CliError::Generic(format!(
+                "[{}] No compiled programs found, run `nargo compile` or `nargo export` first",
+                package.name
+            )));
+        }
+
+        let package_dir = create_named_dir(&out_dir.join(package.name.to_string()), "codegen");
+        match args.language {
+            CodegenLanguage::Typescript => {
+                let abis: Vec<_> =
+                    programs.iter().map(|(name, program)| (name.as_str(), &program.abi)).collect();
+                write_to_file(
+                    typescript_declarations(&abis).as_bytes(),
+                    &package_dir.join("index.d.ts"),
+                );
+                write_to_file(
+                    javascript_module(&programs).as_bytes(),
+                    &package_dir.join("index.js"),
+                );
+            }
+        }
+        println!("[{}] Bindings written to {}", package.name, package_dir.display());
+    }
+
+    Ok(())
+}
+
+/// Reads the artifact of a binary package or the artifacts of the functions exported from a library,
+/// keyed by the name of the function generated for them.
+fn read_package_programs(
+    workspace: &Workspace,
+    package: &Package,
+) -> Result<BTreeMap<String, PreprocessedProgram>, CliError> {
+    let mut programs = BTreeMap::new();
+    if package.is_binary() {
+        let artifact_path = workspace.package_build_path(package);
+        if artifact_path.exists() {
+            programs.insert(package.name.to_string(), read_program_from_file(artifact_path)?);
+        }
+    } else if package.is_library() {
+        let export_dir = workspace.export_directory_path(package);
+        for artifact_path in json_files_in(&export_dir) {
+            let Some(artifact_name) = artifact_path.file_stem().and_then(|stem| stem.to_str())
+            else {
+                continue;
+            };
+            // Exported functions in submodules are saved as `module-function.json`.
+            let function_name = artifact_name.replace('-', "_");
+            programs.insert(function_name, read_program_from_file(&artifact_path)?);
+        }
+    }
+    Ok(programs)
+}
+
+fn json_files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |extension| extension == "json"))
+        .collect()
+}
+
+const GENERATED_HEADER: &str = "// Generated by `nargo codegen`. Do not edit.\n";
+
+/// Generates the TypeScript declarations for the inputs and outputs of each program.
+fn typescript_declarations(programs: &[(&str, &Abi)]) -> String {
+    let mut named_types = BTreeMap::new();
+    let mut functions = String::new();
+    for (name, abi) in programs {
+        let parameters: Vec<_> = abi
+            .parameters
+            .iter()
+            .map(|param| {
+                format!("{}: {}", param.name, typescript_type(&param.typ, &mut named_types))
+            })
+            .collect();
+        let return_type = abi
+            .return_type
+            .as_ref()
+            .map_or_else(|| "void".to_owned(), |typ| typescript_type(typ, &mut named_types));
+
+        writeln!(functions, "\nexport type {name}_Input = {{ {} }};", parameters.join("; "))
+            .unwrap();
+        writeln!(functions, "export type {name}_Output = {return_type};").unwrap();
+        writeln!(
+            functions,
+            "export declare function {name}(args: {name}_Input): Promise<{name}_Output>;"
+        )
+        .unwrap();
+    }
+
+    let mut declarations = GENERATED_HEADER.to_owned();
+    declarations.push_str("\nexport type Field = string;\n");
+    for (name, definition) in named_types {
+        writeln!(declarations, "export type {name} = {definition};").unwrap();
+    }
+    declarations.push_str(&functions);
+    declarations
+}
+
+/// Returns the TypeScript type of values of an [`AbiType`], adding the definitions of any integer
+/// aliases, structs or enums it refers to to `named_types`.
+fn typescript_type(typ: &AbiType, named_types: &mut BTreeMap<String, String>) -> String {
+    match typ {
+        AbiType::Field => "Field".to_owned(),
+        AbiType::Integer { sign, width } => {
+            let name = match sign {
+                Sign::Unsigned => format!("u{width}"),
+                Sign::Signed => format!("i{width}"),
+            };
+            named_types.insert(name.clone(), "string".to_owned());
+            name
+        }
+        AbiType::Boolean => "boolean".to_owned(),
+        AbiType::String { .. } => "string".to_owned(),
+        AbiType::Array { typ, .. } => format!("{}[]", typescript_type(typ, named_types)),
+        AbiType::Tuple { fields } => {
+            let fields: Vec<_> =
+                fields.iter().map(|field| typescript_type(field, named_types)).collect();
+            format!("[{}]", fields.join(", "))
+        }
+        AbiType::Struct { path, fields } => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(name, field)| format!("{name}: {}", typescript_type(field, named_types)))
+                .collect();
+            let name = path.replace("::", "_");
+            named_types.insert(name.clone(), format!("{{ {} }}", fields.join("; ")));
+            name
+        }
+        AbiType::Enum { path, variants } => {
+            // Enums are given as the name of their variant and its fields, as in JSON inputs.
+            let variants: Vec<_> = variants
+                .iter()
+                .map(|(variant, fields)| {
+                    let fields: Vec<_> =
+                        fields.iter().map(|field| typescript_type(field, named_types)).collect();
+                    format!("{{ variant: \"{variant}\"; fields: [{}] }}", fields.join(", "))
+                })
+                .collect();
+            let name = path.replace("::", "_");
+            named_types.insert(name.clone(), variants.join(" | "));
+            name
+        }
+    }
+}
+
+/// Generates a JavaScript module with a function executing each program through `noir_js`.
+fn javascript_module(programs: &BTreeMap<String, PreprocessedProgram>) -> String {
+    let mut module = GENERATED_HEADER.to_owned();
+    module.push_str("import { Noir } from \"@noir-lang/noir_js\";\n");
+    for (name, program) in programs {
+        // `noir_js` only needs the ABI and bytecode, the rest of the artifact is left out.
+        let artifact = serde_json::to_value(program).expect("program artifacts are serializable");
+        let compiled_circuit = serde_json::json!({
+            "abi_version": artifact["abi_version"],
+            "abi": artifact["abi"],
+            "bytecode": artifact["bytecode"],
+        });
+        write!(
+            module,
+            r#"
+const {name}_program = {compiled_circuit};
+
+export async function {name}(args) {{
+  const program = new Noir({name}_program);
+  const {{ returnValue }} = await program.execute(args);
+  return returnValue;
+}}
+"#
+        )
+        .unwrap();
+    }
+    module
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use noirc_abi::{Abi, AbiParameter, AbiType, AbiVisibility, Sign};
+
+    use super::typescript_declarations;
+
+    #[test]
+    fn declares_named_types_for_structs_and_integers() {
+        let point = AbiType::Struct {
+            path: "geometry::Point".to_owned(),
+            fields: vec![
+                ("x".to_owned(), AbiType::Field),
+                ("y".to_owned(), AbiType::Integer { sign: Sign::Unsigned, width: 32 }),
+            ],
+        };
+        let abi = Abi {
+            parameters: vec![AbiParameter {
+                name: "points".to_owned(),
+                typ: AbiType::Array { length: 2, typ: Box::new(point) },
+                visibility: AbiVisibility::Private,
+            }],
+            param_witnesses: BTreeMap::new(),
+            return_type: Some(AbiType::Boolean),
+            return_visibility: AbiVisibility::Public,
+            return_witnesses: Vec::new(),
+            oracles: Vec::new(),
+        };
+
+        let declarations = typescript_declarations(&[("in_bounds", &abi)]);
+
+        assert!(declarations.contains("export type u32 = string;"));
+        assert!(declarations.contains("export type geometry_Point = { x: Field; y: u32 };"));
+        assert!(
+            declarations.contains("export type in_bounds_Input = { points: geometry_Point[] };")
+        );
+        assert!(declarations.contains("export type in_bounds_Output = boolean;"));
+        assert!(declarations.contains(
+            "export declare function in_bounds(args: in_bounds_Input): Promise<in_bounds_Output>;"
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ChainProfileError;
+
+/// The name of the chain profile used when none is given.
+pub(super) const DEFAULT_CHAIN_PROFILE: &str = "ethereum";
+
+/// Where a verifier contract reads the public inputs of a proof from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum PublicInputsSource {
+    /// Public inputs are passed as calldata alongside the proof.
+    Calldata,
+    /// Public inputs are read from an EIP-4844 blob using the point evaluation precompile.
+    Blob,
+}
+
+/// The features of an EVM chain which determine how a verifier contract is generated for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct ChainProfile {
+    pub(super) name: String,
+    pub(super) chain_id: Option<u64>,
+    /// The version requirement written in the contract's `pragma solidity` statement.
+    pub(super) solidity_version: String,
+    /// The addresses of the precompiles available on the chain.
+    pub(super) precompiles: Vec<u8>,
+    pub(super) public_inputs: PublicInputsSource,
+}
+
+impl ChainProfile {
+    /// Returns the built-in profile called `name`, or otherwise reads the profile from the TOML
+    /// file at the path `name`.
+    pub(super) fn resolve(name: &str) -> Result<Self, ChainProfileError> {
+        match name {
+            "ethereum" => Ok(Self::ethereum()),
+            "ethereum-blobs" => Ok(ChainProfile {
+                name: name.to_owned(),
+                // `blobhash` was introduced in Solidity 0.8.24
+                solidity_version: ">=0.8.24".to_owned(),
+                public_inputs: PublicInputsSource::Blob,
+                ..Self::ethereum()
+            }),
+            _ if Path::new(name).is_file() => Self::from_file(Path::new(name)),
+            _ => Err(ChainProfileError::UnknownProfile(name.to_owned())),
+        }
+    }
+
+    fn ethereum() -> Self {
+        ChainProfile {
+            name: "ethereum".to_owned(),
+            chain_id: Some(1),
+            solidity_version: ">=0.8.4".to_owned(),
+            precompiles: (0x01..=0x0a).collect(),
+            public_inputs: PublicInputsSource::Calldata,
+        }
+    }
+
+    fn from_file(path: &Path) -> Result<Self, ChainProfileError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| ChainProfileError::UnreadableProfile(path.to_owned(), error))?;
+        toml::from_str(&contents)
+            .map_err(|error| ChainProfileError::InvalidProfile(path.to_owned(), error))
+    }
+
+    /// Returns an error if any of `precompiles` are not available on the chain.
+    pub(super) fn check_precompiles(&self, precompiles: &[u8]) -> Result<(), ChainProfileError> {
+        let missing: Vec<u8> = precompiles
+            .iter()
+            .filter(|address| !self.precompiles.contains(address))
+            .copied()
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(ChainProfileError::MissingPrecompiles { chain: self.name.clone(), missing })
+        }
+    }
+
+    /// Replaces the `pragma solidity` statements of `contract` with the profile's Solidity version.
+    pub(super) fn apply_solidity_version(&self, contract: &str) -> String {
+        let pragma = format!("pragma solidity {};", self.solidity_version);
+        let mut has_pragma = false;
+        let lines: Vec<&str> = contract
+            .lines()
+            .map(|line| {
+                if line.trim_start().starts_with("pragma solidity") {
+                    has_pragma = true;
+                    pragma.as_str()
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        if has_pragma {
+            lines.join("\n") + "\n"
+        } else {
+            format!("{pragma}\n\n{contract}")
+        }
+    }
+}
+
+/// Describes a generated verifier contract for the tools used to deploy and call it.
+#[derive(Debug, Serialize)]
+pub(super) struct DeploymentInfo<'a> {
+    pub(super) chain: &'a ChainProfile,
+    /// The path of the Solidity file, relative to this file.
+    pub(super) contract_path: &'a str,
+    /// The contract to deploy, if known for the backend.
+    pub(super) contract_name: Option<&'a str>,
+    /// The signature of the function which verifies proofs.
+    pub(super) verify_function: Option<&'a str>,
+    pub(super) num_public_inputs: usize,
+    pub(super) required_precompiles: Vec<u8>,
+}
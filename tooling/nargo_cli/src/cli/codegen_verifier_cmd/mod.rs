@@ -0,0 +1,157 @@
+use super::NargoConfig;
+use super::{
+    compile_cmd::compile_bin_package,
+    fs::{create_named_dir, write_to_file},
+};
+use crate::backends::Backend;
+use crate::errors::{ChainProfileError, CliError};
+use chain_profile::{ChainProfile, DeploymentInfo, PublicInputsSource, DEFAULT_CHAIN_PROFILE};
+
+use acvm::Language;
+use backend_interface::{BackendInfo, BackendOpcodeSupport};
+use bb_abstraction_leaks::{
+    ACVM_BACKEND_BARRETENBERG, POINT_EVALUATION_PRECOMPILE, ULTRA_BLOB_VERIFIER_NAME,
+    ULTRA_VERIFIER_NAME, VERIFIER_PRECOMPILES,
+};
+use clap::Args;
+use nargo::package::Package;
+use nargo::workspace::Workspace;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::graph::CrateName;
+
+mod chain_profile;
+
+/// Generates a Solidity verifier smart contract for the program
+#[derive(Debug, Clone, Args)]
+pub(crate) struct CodegenVerifierCommand {
+    /// The name of the package to codegen
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Codegen all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// The chain to generate the verifier for, either `ethereum`, `ethereum-blobs` or the path to
+    /// a chain profile's TOML file
+    #[clap(long, default_value = DEFAULT_CHAIN_PROFILE)]
+    chain: String,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(
+    backend: &dyn Backend,
+    args: CodegenVerifierCommand,
+    config: NargoConfig,
+) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    let chain_profile = ChainProfile::resolve(&args.chain)?;
+    let is_barretenberg = backend.name() == ACVM_BACKEND_BARRETENBERG;
+    let required_precompiles = required_precompiles(&chain_profile, backend.name())?;
+    chain_profile.check_precompiles(&required_precompiles)?;
+
+    let BackendInfo { language: np_language, opcode_support, .. } = backend.get_backend_info()?;
+    for package in &workspace {
+        let (smart_contract_string, num_public_inputs) = smart_contract_for_package(
+            &workspace,
+            backend,
+            package,
+            &args.compile_options,
+            np_language,
+            &opcode_support,
+            &chain_profile,
+        )?;
+
+        let contract_dir = workspace.contracts_directory_path(package);
+        create_named_dir(&contract_dir, "contract");
+        let contract_path = contract_dir.join("plonk_vk").with_extension("sol");
+
+        let path = write_to_file(smart_contract_string.as_bytes(), &contract_path);
+        println!("[{}] Contract successfully created and located at {path}", package.name);
+
+        let (contract_name, verify_function) = match (is_barretenberg, chain_profile.public_inputs)
+        {
+            (true, PublicInputsSource::Calldata) => {
+                (Some(ULTRA_VERIFIER_NAME), Some("verify(bytes,bytes32[])"))
+            }
+            (true, PublicInputsSource::Blob) => (
+                Some(ULTRA_BLOB_VERIFIER_NAME),
+                Some("verifyWithBlob(bytes,bytes32[],uint256,bytes32[],bytes,bytes)"),
+            ),
+            (false, _) => (None, None),
+        };
+        let deployment_info = DeploymentInfo {
+            chain: &chain_profile,
+            contract_path: "plonk_vk.sol",
+            contract_name,
+            verify_function,
+            num_public_inputs,
+            required_precompiles: required_precompiles.clone(),
+        };
+        let deployment_path = contract_dir.join("plonk_vk").with_extension("json");
+        let deployment_json =
+            serde_json::to_vec_pretty(&deployment_info).expect("deployment info should serialize");
+        write_to_file(&deployment_json, &deployment_path);
+    }
+
+    Ok(())
+}
+
+/// Returns the precompiles which the verifier contract generated for `chain_profile` will call.
+fn required_precompiles(
+    chain_profile: &ChainProfile,
+    backend_name: &str,
+) -> Result<Vec<u8>, ChainProfileError> {
+    // Only the precompiles used by barretenberg's verifier are known.
+    let is_barretenberg = backend_name == ACVM_BACKEND_BARRETENBERG;
+    let mut precompiles = if is_barretenberg { VERIFIER_PRECOMPILES.to_vec() } else { Vec::new() };
+    if chain_profile.public_inputs == PublicInputsSource::Blob {
+        if !is_barretenberg {
+            return Err(ChainProfileError::UnsupportedPublicInputs(backend_name.to_owned()));
+        }
+        precompiles.push(POINT_EVALUATION_PRECOMPILE);
+    }
+    Ok(precompiles)
+}
+
+fn smart_contract_for_package(
+    workspace: &Workspace,
+    backend: &dyn Backend,
+    package: &Package,
+    compile_options: &CompileOptions,
+    np_language: Language,
+    opcode_support: &BackendOpcodeSupport,
+    chain_profile: &ChainProfile,
+) -> Result<(String, usize), CliError> {
+    let program =
+        compile_bin_package(workspace, package, compile_options, np_language, opcode_support)?;
+
+    let mut smart_contract_string = backend.eth_contract(&program.circuit)?;
+
+    if backend.name() == ACVM_BACKEND_BARRETENBERG {
+        smart_contract_string =
+            bb_abstraction_leaks::complete_barretenberg_verifier_contract(smart_contract_string);
+        if chain_profile.public_inputs == PublicInputsSource::Blob {
+            smart_contract_string =
+                bb_abstraction_leaks::append_barretenberg_blob_verifier_contract(
+                    smart_contract_string,
+                );
+        }
+    }
+    let smart_contract_string = chain_profile.apply_solidity_version(&smart_contract_string);
+
+    let num_public_inputs = program.circuit.public_inputs().0.len();
+    Ok((smart_contract_string, num_public_inputs))
+}
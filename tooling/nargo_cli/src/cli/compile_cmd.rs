@@ -1,19 +1,22 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use acvm::acir::circuit::opcodes::BlackBoxFuncCall;
 use acvm::acir::circuit::Opcode;
 use acvm::Language;
-use backend_interface::BackendOpcodeSupport;
+use backend_interface::{BackendInfo, BackendOpcodeSupport};
 use fm::FileManager;
 use iter_extended::vecmap;
 use nargo::artifacts::contract::PreprocessedContract;
 use nargo::artifacts::contract::PreprocessedContractFunction;
+use nargo::artifacts::contract_interface::ContractInterface;
 use nargo::artifacts::debug::DebugArtifact;
-use nargo::artifacts::program::PreprocessedProgram;
+use nargo::artifacts::program::{PreprocessedProgram, ABI_VERSION, BYTECODE_VERSION};
+use nargo::artifacts::workspace_manifest::{ContractEntry, ProgramEntry, WorkspaceManifest};
 use nargo::errors::CompileError;
+use nargo::ops::optimization_error;
 use nargo::package::Package;
-use nargo::prepare_package;
 use nargo::workspace::Workspace;
+use nargo::{override_lint_levels, prepare_package};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
 use noirc_driver::{CompilationResult, CompileOptions, CompiledContract, CompiledProgram};
@@ -25,8 +28,9 @@ use crate::backends::Backend;
 use crate::errors::CliError;
 
 use super::fs::program::{
-    read_debug_artifact_from_file, read_program_from_file, save_contract_to_file,
-    save_debug_artifact_to_file, save_program_to_file,
+    read_debug_artifact_from_file, read_program_from_file, save_contract_interface_to_file,
+    save_contract_to_file, save_debug_artifact_to_file, save_program_to_file,
+    save_workspace_manifest_to_file,
 };
 use super::NargoConfig;
 use rayon::prelude::*;
@@ -54,7 +58,7 @@ pub(crate) struct CompileCommand {
 }
 
 pub(crate) fn run(
-    backend: &Backend,
+    backend: &dyn Backend,
     args: CompileCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -76,8 +80,9 @@ pub(crate) fn run(
         .cloned()
         .partition(|package| package.is_binary());
 
-    let (np_language, opcode_support) = backend.get_backend_info_or_default();
-    let (_, compiled_contracts) = compile_workspace(
+    let BackendInfo { language: np_language, opcode_support, .. } =
+        backend.get_backend_info_or_default();
+    let (compiled_programs, compiled_contracts) = compile_workspace(
         &workspace,
         &binary_packages,
         &contract_packages,
@@ -86,10 +91,25 @@ pub(crate) fn run(
         &args.compile_options,
     )?;
 
+    // Programs are saved as they're compiled, so only their entries in the manifest are needed here.
+    let mut workspace_manifest = WorkspaceManifest::new(NOIR_ARTIFACT_VERSION_STRING.to_owned());
+    for (package, program) in binary_packages.iter().zip(compiled_programs) {
+        let package_name: String = (&package.name).into();
+        let path = PathBuf::from(&package_name).with_extension("json");
+        workspace_manifest.programs.push(ProgramEntry::new(
+            package_name,
+            path,
+            program.abi,
+            &program.circuit,
+        ));
+    }
+
     // Save build artifacts to disk.
     for (package, contract) in contract_packages.into_iter().zip(compiled_contracts) {
-        save_contract(contract, &package, &circuit_dir);
+        let contract_entry = save_contract(contract, &package, &circuit_dir);
+        workspace_manifest.contracts.push(contract_entry);
     }
+    save_workspace_manifest_to_file(&workspace_manifest, &circuit_dir);
 
     Ok(())
 }
@@ -107,7 +127,8 @@ pub(super) fn compile_workspace(
         .par_iter()
         .map(|package| {
             let is_opcode_supported = |opcode: &_| opcode_support.is_opcode_supported(opcode);
-            compile_program(workspace, package, compile_options, np_language, &is_opcode_supported)
+            let compile_options = package_compile_options(workspace, package, compile_options);
+            compile_program(workspace, package, &compile_options, np_language, &is_opcode_supported)
         })
         .collect();
     let contract_results: Vec<(FileManager, CompilationResult<CompiledContract>)> =
@@ -115,29 +136,32 @@ pub(super) fn compile_workspace(
             .par_iter()
             .map(|package| {
                 let is_opcode_supported = |opcode: &_| opcode_support.is_opcode_supported(opcode);
-                compile_contract(package, compile_options, np_language, &is_opcode_supported)
+                let compile_options = package_compile_options(workspace, package, compile_options);
+                compile_contract(package, &compile_options, np_language, &is_opcode_supported)
             })
             .collect();
 
     // Report any warnings/errors which were encountered during compilation.
     let compiled_programs: Vec<CompiledProgram> = program_results
         .into_iter()
-        .map(|(file_manager, compilation_result)| {
+        .zip(binary_packages)
+        .map(|((file_manager, compilation_result), package)| {
             report_errors(
                 compilation_result,
                 &file_manager,
-                compile_options.deny_warnings,
+                package.compile_options(compile_options).deny_warnings,
                 compile_options.silence_warnings,
             )
         })
         .collect::<Result<_, _>>()?;
     let compiled_contracts: Vec<CompiledContract> = contract_results
         .into_iter()
-        .map(|(file_manager, compilation_result)| {
+        .zip(contract_packages)
+        .map(|((file_manager, compilation_result), package)| {
             report_errors(
                 compilation_result,
                 &file_manager,
-                compile_options.deny_warnings,
+                package.compile_options(compile_options).deny_warnings,
                 compile_options.silence_warnings,
             )
         })
@@ -157,6 +181,7 @@ pub(crate) fn compile_bin_package(
         return Err(CompileError::LibraryCrate(package.name.clone()).into());
     }
 
+    let compile_options = &package_compile_options(workspace, package, compile_options);
     let (file_manager, compilation_result) =
         compile_program(workspace, package, compile_options, np_language, &|opcode| {
             opcode_support.is_opcode_supported(opcode)
@@ -172,6 +197,18 @@ pub(crate) fn compile_bin_package(
     Ok(program)
 }
 
+/// Points any SSA snapshots requested with `--show-ssa-passes` to `target/debug_ssa/<package>`,
+/// and applies the settings from the package's Nargo.toml.
+fn package_compile_options(
+    workspace: &Workspace,
+    package: &Package,
+    compile_options: &CompileOptions,
+) -> CompileOptions {
+    let ssa_dump_dir =
+        workspace.target_directory_path().join("debug_ssa").join(package.name.to_string());
+    CompileOptions { ssa_dump_dir: Some(ssa_dump_dir), ..package.compile_options(compile_options) }
+}
+
 fn compile_program(
     workspace: &Workspace,
     package: &Package,
@@ -179,8 +216,11 @@ fn compile_program(
     np_language: Language,
     is_opcode_supported: &impl Fn(&Opcode) -> bool,
 ) -> (FileManager, CompilationResult<CompiledProgram>) {
+    let compile_options =
+        &CompileOptions { np_language: Some(np_language), ..compile_options.clone() };
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
+    override_lint_levels(&mut context, crate_id, compile_options);
 
     let program_artifact_path = workspace.package_build_path(package);
     let mut debug_artifact_path = program_artifact_path.clone();
@@ -227,9 +267,14 @@ fn compile_program(
     };
 
     // Apply backend specific optimizations.
-    let optimized_program =
-        nargo::ops::optimize_program(program, np_language, &is_opcode_supported_pedersen_hash)
-            .expect("Backend does not support an opcode that is in the IR");
+    let optimized_program = match nargo::ops::optimize_program(
+        program,
+        np_language,
+        &is_opcode_supported_pedersen_hash,
+    ) {
+        Ok(program) => program,
+        Err(error) => return (context.file_manager, Err(vec![optimization_error(error)])),
+    };
 
     save_program(optimized_program.clone(), package, &workspace.target_directory_path());
 
@@ -242,8 +287,11 @@ fn compile_contract(
     np_language: Language,
     is_opcode_supported: &impl Fn(&Opcode) -> bool,
 ) -> (FileManager, CompilationResult<CompiledContract>) {
+    let compile_options =
+        &CompileOptions { np_language: Some(np_language), ..compile_options.clone() };
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
+    override_lint_levels(&mut context, crate_id, compile_options);
     let (contract, warnings) =
         match noirc_driver::compile_contract(&mut context, crate_id, compile_options) {
             Ok(contracts_and_warnings) => contracts_and_warnings,
@@ -253,8 +301,10 @@ fn compile_contract(
         };
 
     let optimized_contract =
-        nargo::ops::optimize_contract(contract, np_language, &is_opcode_supported)
-            .expect("Backend does not support an opcode that is in the IR");
+        match nargo::ops::optimize_contract(contract, np_language, &is_opcode_supported) {
+            Ok(contract) => contract,
+            Err(error) => return (context.file_manager, Err(vec![optimization_error(error)])),
+        };
 
     (context.file_manager, Ok((optimized_contract, warnings)))
 }
@@ -263,8 +313,10 @@ fn save_program(program: CompiledProgram, package: &Package, circuit_dir: &Path)
     let preprocessed_program = PreprocessedProgram {
         hash: program.hash,
         backend: String::from(BACKEND_IDENTIFIER),
+        abi_version: ABI_VERSION,
         abi: program.abi,
         noir_version: program.noir_version,
+        bytecode_version: BYTECODE_VERSION,
         bytecode: program.circuit,
     };
 
@@ -279,7 +331,11 @@ fn save_program(program: CompiledProgram, package: &Package, circuit_dir: &Path)
     save_debug_artifact_to_file(&debug_artifact, &circuit_name, circuit_dir);
 }
 
-fn save_contract(contract: CompiledContract, package: &Package, circuit_dir: &Path) {
+fn save_contract(
+    contract: CompiledContract,
+    package: &Package,
+    circuit_dir: &Path,
+) -> ContractEntry {
     // TODO(#1389): I wonder if it is incorrect for nargo-core to know anything about contracts.
     // As can be seen here, It seems like a leaky abstraction where ContractFunctions (essentially CompiledPrograms)
     // are compiled via nargo-core and then the PreprocessedContract is constructed here.
@@ -302,21 +358,28 @@ fn save_contract(contract: CompiledContract, package: &Package, circuit_dir: &Pa
         noir_version: contract.noir_version,
         name: contract.name,
         backend: String::from(BACKEND_IDENTIFIER),
+        abi_version: ABI_VERSION,
         functions: preprocessed_functions,
         events: contract.events,
     };
 
-    save_contract_to_file(
-        &preprocessed_contract,
-        &format!("{}-{}", package.name, preprocessed_contract.name),
+    let circuit_name = format!("{}-{}", package.name, preprocessed_contract.name);
+    let contract_path = save_contract_to_file(&preprocessed_contract, &circuit_name, circuit_dir);
+    let interface_path = save_contract_interface_to_file(
+        &ContractInterface::new(&preprocessed_contract),
+        &circuit_name,
         circuit_dir,
     );
+    save_debug_artifact_to_file(&debug_artifact, &circuit_name, circuit_dir);
 
-    save_debug_artifact_to_file(
-        &debug_artifact,
-        &format!("{}-{}", package.name, preprocessed_contract.name),
-        circuit_dir,
-    );
+    let relative_path =
+        |path: PathBuf| path.strip_prefix(circuit_dir).map(Path::to_path_buf).unwrap_or(path);
+    ContractEntry::new(
+        package.name.to_string(),
+        relative_path(contract_path),
+        relative_path(interface_path),
+        &preprocessed_contract,
+    )
 }
 
 /// Helper function for reporting any errors in a `CompilationResult<T>`
@@ -328,12 +391,22 @@ pub(crate) fn report_errors<T>(
     silence_warnings: bool,
 ) -> Result<T, CompileError> {
     let (t, warnings) = result.map_err(|errors| {
-        noirc_errors::reporter::report_all(
+        let reported_errors = noirc_errors::reporter::report_all(
             file_manager.as_file_map(),
             &errors,
             deny_warnings,
             silence_warnings,
-        )
+        );
+
+        let first_code = errors
+            .iter()
+            .filter(|error| error.diagnostic.is_error())
+            .find_map(|error| error.diagnostic.code);
+        if let Some(code) = first_code {
+            eprintln!("For more information about an error, try `nargo explain {code}`.");
+        }
+
+        reported_errors
     })?;
 
     noirc_errors::reporter::report_all(
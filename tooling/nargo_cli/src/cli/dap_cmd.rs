@@ -0,0 +1,146 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use acvm::acir::native_types::WitnessMap;
+use backend_interface::BackendInfo;
+use clap::Args;
+
+use nargo::artifacts::debug::DebugArtifact;
+use nargo::constants::PROVER_INPUT_FILE;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noir_debugger::{DapRequest, DapServer};
+use noirc_abi::input_parser::Format;
+use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
+use serde_json::json;
+
+use super::compile_cmd::compile_bin_package;
+use super::fs::inputs::read_inputs_from_file;
+use super::NargoConfig;
+use crate::backends::Backend;
+use crate::errors::CliError;
+
+/// Starts a Debug Adapter Protocol server
+///
+/// Starts a DAP server on stdin and stdout which allows editors such as VS Code to debug the
+/// execution of Noir programs.
+///
+/// The client launches a program by passing the `projectFolder` of its package, along with the
+/// `package` to debug in a workspace and the `proverName` of the file to read inputs from.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct DapCommand;
+
+pub(crate) fn run(
+    backend: &dyn Backend,
+    _args: DapCommand,
+    _config: NargoConfig,
+) -> Result<(), CliError> {
+    let mut server = DapServer::new(BufReader::new(io::stdin()), io::stdout());
+    loop_uninitialized(backend, &mut server).map_err(CliError::DapError)
+}
+
+/// Handles the requests of a client until it asks to launch a program, which is then debugged
+/// until the client disconnects.
+fn loop_uninitialized<R: BufRead, W: Write>(
+    backend: &dyn Backend,
+    server: &mut DapServer<R, W>,
+) -> io::Result<()> {
+    while let Some(request) = server.read_request()? {
+        match request.command.as_str() {
+            "initialize" => {
+                let capabilities = json!({
+                    "supportsConfigurationDoneRequest": true,
+                    "supportsSteppingGranularity": true,
+                    "supportsTerminateRequest": true,
+                });
+                server.respond(&request, capabilities)?;
+            }
+            // There is no running program to attach to, so attaching launches the program too
+            "launch" | "attach" => match load_program(backend, &request) {
+                Ok((program, initial_witness)) => {
+                    server.respond(&request, json!({}))?;
+                    return debug_program(server, program, initial_witness);
+                }
+                Err(error) => server.respond_error(&request, &error.to_string())?,
+            },
+            "disconnect" => {
+                server.respond(&request, json!({}))?;
+                return Ok(());
+            }
+            command => server.respond_error(
+                &request,
+                &format!("Unsupported command {command} before a program is launched"),
+            )?,
+        }
+    }
+    Ok(())
+}
+
+/// Compiles the package a client asked to debug and encodes its inputs.
+fn load_program(
+    backend: &dyn Backend,
+    request: &DapRequest,
+) -> Result<(CompiledProgram, WitnessMap), CliError> {
+    let arguments = &request.arguments;
+    let Some(project_folder) = arguments["projectFolder"].as_str() else {
+        return Err(CliError::Generic("Missing `projectFolder` launch argument".to_string()));
+    };
+    let selection = match arguments["package"].as_str() {
+        Some(package) => PackageSelection::Selected(package.parse().map_err(CliError::Generic)?),
+        None => PackageSelection::DefaultOrAll,
+    };
+    let prover_name = arguments["proverName"].as_str().unwrap_or(PROVER_INPUT_FILE);
+
+    let toml_path = get_package_manifest(Path::new(project_folder))?;
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+    let BackendInfo { language: np_language, opcode_support, .. } = backend.get_backend_info()?;
+
+    let Some(package) = workspace.into_iter().find(|p| p.is_binary()) else {
+        return Err(CliError::Generic(
+            "No matching binary packages found in workspace. Only binary packages can be debugged."
+                .to_string(),
+        ));
+    };
+
+    let program = compile_bin_package(
+        &workspace,
+        package,
+        &CompileOptions::default(),
+        np_language,
+        &opcode_support,
+    )?;
+
+    let (inputs_map, _) =
+        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
+    let initial_witness = program.abi.encode(&inputs_map, None)?;
+
+    Ok((program, initial_witness))
+}
+
+fn debug_program<R: BufRead, W: Write>(
+    server: &mut DapServer<R, W>,
+    program: CompiledProgram,
+    initial_witness: WitnessMap,
+) -> io::Result<()> {
+    #[allow(deprecated)]
+    let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
+
+    let debug_artifact = DebugArtifact {
+        debug_symbols: vec![program.debug],
+        file_map: program.file_map,
+        warnings: program.warnings,
+    };
+
+    noir_debugger::run_dap_loop(
+        server,
+        &blackbox_solver,
+        &program.circuit,
+        debug_artifact,
+        &program.abi,
+        initial_witness,
+    )?;
+    Ok(())
+}
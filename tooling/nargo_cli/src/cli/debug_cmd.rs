@@ -1,4 +1,5 @@
 use acvm::acir::native_types::WitnessMap;
+use backend_interface::BackendInfo;
 use clap::Args;
 
 use nargo::artifacts::debug::DebugArtifact;
@@ -30,12 +31,16 @@ pub(crate) struct DebugCommand {
     #[clap(long)]
     package: Option<CrateName>,
 
+    /// Run the program without stopping and only start debugging if it fails
+    #[clap(long)]
+    run_until_failure: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
 
 pub(crate) fn run(
-    backend: &Backend,
+    backend: &dyn Backend,
     args: DebugCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -47,7 +52,7 @@ pub(crate) fn run(
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
     )?;
     let target_dir = &workspace.target_directory_path();
-    let (np_language, opcode_support) = backend.get_backend_info()?;
+    let BackendInfo { language: np_language, opcode_support, .. } = backend.get_backend_info()?;
 
     let Some(package) = workspace.into_iter().find(|p| p.is_binary()) else {
         println!(
@@ -65,8 +70,12 @@ pub(crate) fn run(
     )?;
 
     println!("[{}] Starting debugger", package.name);
-    let (return_value, solved_witness) =
-        debug_program_and_decode(compiled_program, package, &args.prover_name)?;
+    let (return_value, solved_witness) = debug_program_and_decode(
+        compiled_program,
+        package,
+        &args.prover_name,
+        args.run_until_failure,
+    )?;
 
     if let Some(solved_witness) = solved_witness {
         println!("[{}] Circuit witness successfully solved", package.name);
@@ -91,11 +100,12 @@ fn debug_program_and_decode(
     program: CompiledProgram,
     package: &Package,
     prover_name: &str,
+    run_until_failure: bool,
 ) -> Result<(Option<InputValue>, Option<WitnessMap>), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
-    let solved_witness = debug_program(&program, &inputs_map)?;
+    let solved_witness = debug_program(&program, &inputs_map, run_until_failure)?;
     let public_abi = program.abi.public_abi();
 
     match solved_witness {
@@ -110,6 +120,7 @@ fn debug_program_and_decode(
 pub(crate) fn debug_program(
     compiled_program: &CompiledProgram,
     inputs_map: &InputMap,
+    run_until_failure: bool,
 ) -> Result<Option<WitnessMap>, CliError> {
     #[allow(deprecated)]
     let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
@@ -127,6 +138,7 @@ pub(crate) fn debug_program(
         &compiled_program.circuit,
         debug_artifact,
         initial_witness,
+        run_until_failure,
     )
     .map_err(CliError::from)
 }
@@ -1,14 +1,20 @@
+use std::path::Path;
+
 use acvm::acir::native_types::WitnessMap;
+use backend_interface::BackendInfo;
 use clap::Args;
 
 use nargo::artifacts::debug::DebugArtifact;
 use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::try_to_diagnose_runtime_error;
-use nargo::ops::DefaultForeignCallExecutor;
+use nargo::ops::{
+    DefaultForeignCallExecutor, ExternalWitnessSolver, JsonRpcForeignCallResolver,
+    ParallelAcvmSolver, WitnessSolver,
+};
 use nargo::package::Package;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::input_parser::{Format, InputValue};
-use noirc_abi::InputMap;
+use noirc_abi::{InputMap, MAIN_RETURN_NAME};
 use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_frontend::graph::CrateName;
 
@@ -16,7 +22,7 @@ use super::compile_cmd::compile_bin_package;
 use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
 use super::NargoConfig;
 use crate::backends::Backend;
-use crate::errors::CliError;
+use crate::errors::{CliError, FilesystemError};
 
 /// Executes a circuit to calculate its return value
 #[derive(Debug, Clone, Args)]
@@ -24,10 +30,18 @@ pub(crate) struct ExecuteCommand {
     /// Write the execution witness to named file
     witness_name: Option<String>,
 
-    /// The name of the toml file which contains the inputs for the prover
+    /// The name of the file which contains the inputs for the prover
     #[clap(long, short, default_value = PROVER_INPUT_FILE)]
     prover_name: String,
 
+    /// The format of the prover inputs file, either `toml` or `json`
+    #[clap(long, default_value = "toml")]
+    input_format: Format,
+
+    /// Print the circuit output as `toml` or `json` instead of its debug representation
+    #[clap(long)]
+    output_format: Option<Format>,
+
     /// The name of the package to execute
     #[clap(long, conflicts_with = "workspace")]
     package: Option<CrateName>,
@@ -36,12 +50,16 @@ pub(crate) struct ExecuteCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// JSON-RPC server to resolve oracle calls which aren't mocked
+    #[clap(long)]
+    oracle_resolver: Option<String>,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
 
 pub(crate) fn run(
-    backend: &Backend,
+    backend: &dyn Backend,
     args: ExecuteCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -56,7 +74,8 @@ pub(crate) fn run(
     )?;
     let target_dir = &workspace.target_directory_path();
 
-    let (np_language, opcode_support) = backend.get_backend_info_or_default();
+    let BackendInfo { language: np_language, opcode_support, .. } =
+        backend.get_backend_info_or_default();
     for package in &workspace {
         let compiled_program = compile_bin_package(
             &workspace,
@@ -66,12 +85,28 @@ pub(crate) fn run(
             &opcode_support,
         )?;
 
-        let (return_value, solved_witness) =
-            execute_program_and_decode(compiled_program, package, &args.prover_name)?;
+        let public_abi = compiled_program.abi.public_abi();
+        let (return_value, solved_witness) = execute_program_and_decode(
+            compiled_program,
+            package,
+            &args.prover_name,
+            args.input_format,
+            args.oracle_resolver.as_deref(),
+        )?;
 
         println!("[{}] Circuit witness successfully solved", package.name);
-        if let Some(return_value) = return_value {
-            println!("[{}] Circuit output: {return_value:?}", package.name);
+        match (return_value, args.output_format) {
+            (Some(return_value), Some(format)) => {
+                let output = InputMap::from([(MAIN_RETURN_NAME.to_owned(), return_value)]);
+                println!("[{}] Circuit output:", package.name);
+                let output =
+                    format.serialize(&output, &public_abi).map_err(FilesystemError::from)?;
+                println!("{output}");
+            }
+            (Some(return_value), None) => {
+                println!("[{}] Circuit output: {return_value:?}", package.name);
+            }
+            (None, _) => (),
         }
         if let Some(witness_name) = &args.witness_name {
             let witness_path = save_witness_to_dir(solved_witness, witness_name, target_dir)?;
@@ -86,11 +121,14 @@ fn execute_program_and_decode(
     program: CompiledProgram,
     package: &Package,
     prover_name: &str,
+    input_format: Format,
+    oracle_resolver: Option<&str>,
 ) -> Result<(Option<InputValue>, WitnessMap), CliError> {
-    // Parse the initial witness values from Prover.toml
+    // Parse the initial witness values from the prover inputs file
     let (inputs_map, _) =
-        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
-    let solved_witness = execute_program(&program, &inputs_map)?;
+        read_inputs_from_file(&package.root_dir, prover_name, input_format, &program.abi)?;
+    let solved_witness =
+        execute_program(&program, &inputs_map, oracle_resolver, package.witness_solver.as_deref())?;
     let public_abi = program.abi.public_abi();
     let (_, return_value) = public_abi.decode(&solved_witness)?;
 
@@ -100,17 +138,33 @@ fn execute_program_and_decode(
 pub(crate) fn execute_program(
     compiled_program: &CompiledProgram,
     inputs_map: &InputMap,
+    oracle_resolver: Option<&str>,
+    witness_solver: Option<&Path>,
 ) -> Result<WitnessMap, CliError> {
+    // Each thread solving a group of independent opcodes needs its own blackbox solver.
     #[allow(deprecated)]
-    let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
+    let acvm_solver =
+        ParallelAcvmSolver::new(barretenberg_blackbox_solver::BarretenbergSolver::new);
+    let external_solver = witness_solver.map(|path| ExternalWitnessSolver::new(path.to_owned()));
+    let solver: &dyn WitnessSolver = match &external_solver {
+        Some(external_solver) => external_solver,
+        None => &acvm_solver,
+    };
 
     let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
 
+    let mut foreign_call_executor = DefaultForeignCallExecutor::new(true);
+    if let Some(url) = oracle_resolver {
+        let resolver =
+            JsonRpcForeignCallResolver::new(url.to_owned(), compiled_program.abi.oracles.clone());
+        foreign_call_executor = foreign_call_executor.with_external_resolver(resolver);
+    }
+
     let solved_witness_err = nargo::ops::execute_circuit(
         &compiled_program.circuit,
         initial_witness,
-        &blackbox_solver,
-        &mut DefaultForeignCallExecutor::new(true),
+        solver,
+        &mut foreign_call_executor,
     );
     match solved_witness_err {
         Ok(solved_witness) => Ok(solved_witness),
@@ -121,7 +175,9 @@ pub(crate) fn execute_program(
                 warnings: compiled_program.warnings.clone(),
             };
 
-            if let Some(diagnostic) = try_to_diagnose_runtime_error(&err, &compiled_program.debug) {
+            if let Some(diagnostic) =
+                try_to_diagnose_runtime_error(&err, &compiled_program.abi, &compiled_program.debug)
+            {
                 diagnostic.report(&debug_artifact, false);
             }
 
@@ -0,0 +1,21 @@
+use clap::Args;
+use noirc_errors::error_codes;
+
+use crate::errors::CliError;
+
+/// Prints an extended explanation of a compiler error code
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExplainCommand {
+    /// The error code to explain, such as E0303
+    code: String,
+}
+
+pub(crate) fn run(args: ExplainCommand) -> Result<(), CliError> {
+    let code = args.code.to_uppercase();
+    let explanation = error_codes::explain(&code)
+        .ok_or_else(|| CliError::Generic(format!("{} is not a known error code", args.code)))?;
+
+    println!("{explanation}");
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use std::path::Path;
+
+use clap::Args;
+use nargo::artifacts::program::{PreprocessedProgram, ABI_VERSION, BYTECODE_VERSION};
+use nargo::errors::CompileError;
+use nargo::package::Package;
+use nargo::workspace::Workspace;
+use nargo::{override_lint_levels, prepare_package};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{
+    compile_no_check, CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
+};
+use noirc_errors::FileDiagnostic;
+use noirc_frontend::graph::CrateName;
+
+use crate::backends::Backend;
+use crate::errors::CliError;
+
+use super::check_cmd::check_crate_and_report_errors;
+use super::compile_cmd::report_errors;
+use super::fs::program::save_exported_program_to_file;
+use super::NargoConfig;
+
+// TODO(#1388): pull this from backend.
+const BACKEND_IDENTIFIER: &str = "acvm-backend-barretenberg";
+
+/// Exports functions marked with #[export] as standalone programs
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExportCommand {
+    /// The name of the package to export
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Export all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// Only export the exported function with this fully qualified name
+    #[arg(long)]
+    function: Option<String>,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(
+    _backend: &dyn Backend,
+    args: ExportCommand,
+    config: NargoConfig,
+) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+    )?;
+
+    let library_packages: Vec<_> =
+        workspace.into_iter().filter(|package| package.is_library()).collect();
+
+    let mut exported_count = 0;
+    for package in library_packages {
+        exported_count +=
+            export_package(&workspace, package, args.function.as_deref(), &args.compile_options)?;
+    }
+
+    match args.function {
+        Some(function) if exported_count == 0 => {
+            Err(CliError::Generic(format!("No exported function named `{function}` was found")))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Compiles the #[export] functions of a library package, optionally restricted to the one named
+/// `function`, and writes them to `target/export/<package>`. Returns the number of functions exported.
+fn export_package(
+    workspace: &Workspace,
+    package: &Package,
+    function: Option<&str>,
+    compile_options: &CompileOptions,
+) -> Result<usize, CompileError> {
+    let compile_options = &package.compile_options(compile_options);
+    let (mut context, crate_id) =
+        prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
+    override_lint_levels(&mut context, crate_id, compile_options);
+    check_crate_and_report_errors(
+        &mut context,
+        crate_id,
+        compile_options.deny_warnings,
+        compile_options.silence_warnings,
+    )?;
+
+    let exported_functions: Vec<_> = context
+        .get_all_exported_functions_in_crate(&crate_id)
+        .into_iter()
+        .filter(|(function_name, _)| function.map_or(true, |function| function_name == function))
+        .collect();
+
+    let export_dir = workspace.export_directory_path(package);
+    for (function_name, function_id) in &exported_functions {
+        let program = compile_no_check(&context, compile_options, *function_id, None, false)
+            .map(|program| (program, Vec::new()))
+            .map_err(|error| vec![FileDiagnostic::from(error)]);
+        let program = report_errors(
+            program,
+            &context.file_manager,
+            compile_options.deny_warnings,
+            compile_options.silence_warnings,
+        )?;
+
+        save_program(program, function_name, &export_dir);
+        println!("[{}] Exported `{function_name}`", package.name);
+    }
+
+    Ok(exported_functions.len())
+}
+
+fn save_program(program: CompiledProgram, function_name: &str, export_dir: &Path) {
+    let preprocessed_program = PreprocessedProgram {
+        hash: program.hash,
+        backend: String::from(BACKEND_IDENTIFIER),
+        abi_version: ABI_VERSION,
+        abi: program.abi,
+        noir_version: program.noir_version,
+        bytecode_version: BYTECODE_VERSION,
+        bytecode: program.circuit,
+    };
+
+    // Function names cannot contain `-`, so this can't make two exported functions collide.
+    let artifact_name = function_name.replace("::", "-");
+    save_exported_program_to_file(&preprocessed_program, &artifact_name, export_dir);
+}
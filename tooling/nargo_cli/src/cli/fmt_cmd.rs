@@ -5,7 +5,7 @@ use fm::FileManager;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
 use noirc_errors::CustomDiagnostic;
-use noirc_frontend::{hir::def_map::parse_file, parser::ParserError};
+use noirc_frontend::{graph::CrateName, hir::def_map::parse_file, parser::ParserError};
 
 use crate::errors::CliError;
 
@@ -17,15 +17,26 @@ pub(crate) struct FormatCommand {
     /// Run noirfmt in check mode
     #[arg(long)]
     check: bool,
+
+    /// The name of the package to format
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Format all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
 }
 
 pub(crate) fn run(args: FormatCommand, config: NargoConfig) -> Result<(), CliError> {
     let check_mode = args.check;
 
     let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
     let workspace = resolve_workspace_from_toml(
         &toml_path,
-        PackageSelection::All,
+        selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
     )?;
 
@@ -78,6 +89,7 @@ pub(crate) fn run(args: FormatCommand, config: NargoConfig) -> Result<(), CliErr
                         check_exit_code_one = true;
                     }
 
+                    println!("Diff in {}:", entry.path().display());
                     println!("{diff}");
                 }
 
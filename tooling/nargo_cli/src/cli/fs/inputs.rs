@@ -1,8 +1,12 @@
 use noirc_abi::{
+    errors::InputParserError,
     input_parser::{Format, InputValue},
     Abi, InputMap, MAIN_RETURN_NAME,
 };
-use std::{collections::BTreeMap, path::Path};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 use crate::errors::FilesystemError;
 
@@ -27,7 +31,8 @@ pub(crate) fn read_inputs_from_file<P: AsRef<Path>>(
 
     let file_path = path.as_ref().join(file_name).with_extension(format.ext());
     if !file_path.exists() {
-        return Err(FilesystemError::MissingTomlFile(file_name.to_owned(), file_path));
+        let file_name = format!("{file_name}.{}", format.ext());
+        return Err(FilesystemError::MissingInputFile(file_name, file_path));
     }
 
     let input_string = std::fs::read_to_string(file_path).unwrap();
@@ -65,6 +70,46 @@ pub(crate) fn write_inputs_to_file<P: AsRef<Path>>(
     Ok(())
 }
 
+/// Writes the values of `input_map` into a toml inputs file, keeping the values which the file
+/// already holds for any other parameters.
+pub(crate) fn update_inputs_file<P: AsRef<Path>>(
+    input_map: &InputMap,
+    abi: &Abi,
+    path: P,
+    file_name: &str,
+) -> Result<PathBuf, FilesystemError> {
+    let file_path = path.as_ref().join(file_name).with_extension(Format::Toml.ext());
+
+    // Only the parameters being written are serialized so that the other inputs needn't be known.
+    let parameters =
+        abi.parameters.iter().filter(|param| input_map.contains_key(&param.name)).cloned();
+    let partial_abi = Abi {
+        parameters: parameters.collect(),
+        param_witnesses: BTreeMap::new(),
+        return_type: None,
+        return_visibility: abi.return_visibility,
+        return_witnesses: Vec::new(),
+        oracles: Vec::new(),
+    };
+    let serialized_inputs = Format::Toml.serialize(input_map, &partial_abi)?;
+    let new_inputs: toml::Table =
+        toml::from_str(&serialized_inputs).map_err(InputParserError::from)?;
+
+    let mut inputs: toml::Table = if file_path.exists() {
+        let input_string = std::fs::read_to_string(&file_path)
+            .map_err(|_| FilesystemError::PathNotValid(file_path.clone()))?;
+        toml::from_str(&input_string).map_err(InputParserError::from)?
+    } else {
+        toml::Table::new()
+    };
+    inputs.extend(new_inputs);
+
+    let serialized_output = toml::to_string(&inputs).map_err(InputParserError::from)?;
+    write_to_file(serialized_output.as_bytes(), &file_path);
+
+    Ok(file_path)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::BTreeMap, vec};
@@ -99,11 +144,13 @@ mod tests {
                 },
             ],
             return_type: Some(AbiType::Field),
+            return_visibility: AbiVisibility::Public,
 
             // Input serialization is only dependent on types, not position in witness map.
             // Neither of these should be relevant so we leave them empty.
             param_witnesses: BTreeMap::new(),
             return_witnesses: Vec::new(),
+            oracles: Vec::new(),
         };
         let input_map = BTreeMap::from([
             ("foo".to_owned(), InputValue::Field(42u128.into())),
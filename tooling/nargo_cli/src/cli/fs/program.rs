@@ -1,7 +1,9 @@
 use std::path::{Path, PathBuf};
 
 use nargo::artifacts::{
-    contract::PreprocessedContract, debug::DebugArtifact, program::PreprocessedProgram,
+    contract::PreprocessedContract, contract_interface::ContractInterface, debug::DebugArtifact,
+    program::PreprocessedProgram, workspace_manifest::WorkspaceManifest,
+    workspace_manifest::WORKSPACE_MANIFEST_NAME,
 };
 use noirc_frontend::graph::CrateName;
 
@@ -18,6 +20,14 @@ pub(crate) fn save_program_to_file<P: AsRef<Path>>(
     save_build_artifact_to_file(compiled_program, &circuit_name, circuit_dir)
 }
 
+pub(crate) fn save_exported_program_to_file<P: AsRef<Path>>(
+    compiled_program: &PreprocessedProgram,
+    function_name: &str,
+    export_dir: P,
+) -> PathBuf {
+    save_build_artifact_to_file(compiled_program, function_name, export_dir)
+}
+
 pub(crate) fn save_contract_to_file<P: AsRef<Path>>(
     compiled_contract: &PreprocessedContract,
     circuit_name: &str,
@@ -26,6 +36,22 @@ pub(crate) fn save_contract_to_file<P: AsRef<Path>>(
     save_build_artifact_to_file(compiled_contract, circuit_name, circuit_dir)
 }
 
+pub(crate) fn save_contract_interface_to_file<P: AsRef<Path>>(
+    contract_interface: &ContractInterface,
+    circuit_name: &str,
+    circuit_dir: P,
+) -> PathBuf {
+    let artifact_name = format!("interface_{circuit_name}");
+    save_build_artifact_to_file(contract_interface, &artifact_name, circuit_dir)
+}
+
+pub(crate) fn save_workspace_manifest_to_file<P: AsRef<Path>>(
+    workspace_manifest: &WorkspaceManifest,
+    circuit_dir: P,
+) -> PathBuf {
+    save_build_artifact_to_file(workspace_manifest, WORKSPACE_MANIFEST_NAME, circuit_dir)
+}
+
 pub(crate) fn save_debug_artifact_to_file<P: AsRef<Path>>(
     debug_artifact: &DebugArtifact,
     circuit_name: &str,
@@ -55,8 +81,7 @@ pub(crate) fn read_program_from_file<P: AsRef<Path>>(
 
     let input_string =
         std::fs::read(&file_path).map_err(|_| FilesystemError::PathNotValid(file_path))?;
-    let program = serde_json::from_slice(&input_string)
-        .map_err(|err| FilesystemError::ProgramSerializationError(err.to_string()))?;
+    let program = PreprocessedProgram::from_json(&input_string)?;
 
     Ok(program)
 }
@@ -1,11 +1,19 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
 use acvm::acir::native_types::WitnessMap;
 use nargo::constants::WITNESS_EXT;
+use noirc_abi::witness::{
+    deserialize_witness, write_witness_chunks, WitnessReader, DEFAULT_WITNESS_CHUNK_SIZE,
+    WITNESS_MAGIC,
+};
 
-use super::{create_named_dir, write_to_file};
+use super::create_named_dir;
 use crate::errors::FilesystemError;
 
+/// Writes `witnesses` to the witness directory in chunks, so that the file is never held in memory
+/// alongside the witness map.
 pub(crate) fn save_witness_to_dir<P: AsRef<Path>>(
     witnesses: WitnessMap,
     witness_name: &str,
@@ -14,9 +22,34 @@ pub(crate) fn save_witness_to_dir<P: AsRef<Path>>(
     create_named_dir(witness_dir.as_ref(), "witness");
     let witness_path = witness_dir.as_ref().join(witness_name).with_extension(WITNESS_EXT);
 
-    let buf: Vec<u8> = witnesses.try_into()?;
-
-    write_to_file(buf.as_slice(), &witness_path);
+    let file = File::create(&witness_path)
+        .unwrap_or_else(|why| panic!("couldn't create {}: {why}", witness_path.display()));
+    write_witness_chunks(witnesses, BufWriter::new(file), DEFAULT_WITNESS_CHUNK_SIZE)?;
 
     Ok(witness_path)
 }
+
+/// Reads a witness file in either witness format, reading chunked witnesses one chunk at a time.
+///
+/// The chunks are collected into a single witness map, as that is what the backend proves from.
+pub(crate) fn read_witness_from_file<P: AsRef<Path>>(
+    witness_path: P,
+) -> Result<WitnessMap, FilesystemError> {
+    let witness_path = witness_path.as_ref();
+    let mut file = File::open(witness_path)
+        .map_err(|_| FilesystemError::PathNotValid(witness_path.to_path_buf()))?;
+
+    let mut magic = [0; WITNESS_MAGIC.len()];
+    let is_chunked = file.read_exact(&mut magic).is_ok() && magic == WITNESS_MAGIC;
+    file.seek(SeekFrom::Start(0))
+        .map_err(|_| FilesystemError::PathNotValid(witness_path.to_path_buf()))?;
+
+    if is_chunked {
+        Ok(WitnessReader::new(BufReader::new(file))?.read_all()?)
+    } else {
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)
+            .map_err(|_| FilesystemError::PathNotValid(witness_path.to_path_buf()))?;
+        Ok(deserialize_witness(&buf)?)
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use backend_interface::BackendInfo;
+use clap::Args;
+
+use nargo::artifacts::debug::DebugArtifact;
+use nargo::errors::try_to_diagnose_runtime_error;
+use nargo::ops::{fuzz_program, FuzzOutcome};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_abi::input_parser::Format;
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::graph::CrateName;
+
+use super::compile_cmd::compile_bin_package;
+use super::fs::inputs::write_inputs_to_file;
+use super::NargoConfig;
+use crate::backends::Backend;
+use crate::errors::CliError;
+
+/// Executes a circuit with random inputs sampled from its ABI, saving the smallest failing inputs
+#[derive(Debug, Clone, Args)]
+pub(crate) struct FuzzCommand {
+    /// The number of sets of inputs to execute the circuit with
+    #[clap(long, default_value_t = 256)]
+    runs: usize,
+
+    /// Seed the inputs are sampled from, to reproduce an earlier run [default: random]
+    #[clap(long)]
+    seed: Option<u64>,
+
+    /// The name of the toml file to write the failing inputs to
+    #[clap(long, short, default_value = "Fuzz")]
+    prover_name: String,
+
+    /// The name of the package to fuzz
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Fuzz all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(
+    backend: &dyn Backend,
+    args: FuzzCommand,
+    config: NargoConfig,
+) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    let seed = args.seed.unwrap_or_else(|| {
+        SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_nanos() as u64)
+    });
+    println!("Fuzzing with seed {seed}");
+
+    #[allow(deprecated)]
+    let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
+    let BackendInfo { language: np_language, opcode_support, .. } =
+        backend.get_backend_info_or_default();
+    for package in &workspace {
+        let compiled_program = compile_bin_package(
+            &workspace,
+            package,
+            &args.compile_options,
+            np_language,
+            &opcode_support,
+        )?;
+
+        match fuzz_program(&blackbox_solver, &compiled_program, args.runs, seed)? {
+            FuzzOutcome::Passed => {
+                println!(
+                    "[{}] Executed {} sets of inputs without failing",
+                    package.name, args.runs
+                );
+            }
+            FuzzOutcome::Failed { inputs, error } => {
+                let debug_artifact = DebugArtifact {
+                    debug_symbols: vec![compiled_program.debug.clone()],
+                    file_map: compiled_program.file_map.clone(),
+                    warnings: compiled_program.warnings.clone(),
+                };
+                if let Some(diagnostic) = try_to_diagnose_runtime_error(
+                    &error,
+                    &compiled_program.abi,
+                    &compiled_program.debug,
+                ) {
+                    diagnostic.report(&debug_artifact, false);
+                }
+
+                write_inputs_to_file(
+                    &inputs,
+                    &None,
+                    &compiled_program.abi,
+                    &package.root_dir,
+                    &args.prover_name,
+                    Format::Toml,
+                )?;
+                println!(
+                    "[{}] Failing inputs written to {}.toml, run `nargo execute --prover-name {}` to reproduce",
+                    package.name, args.prover_name, args.prover_name
+                );
+
+                return Err(CliError::NargoError(error));
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
+use acvm::acir::circuit::{Circuit, Opcode};
 use acvm::Language;
-use backend_interface::BackendError;
+use backend_interface::{BackendError, BackendInfo, CostEstimate, CostModel};
 use clap::Args;
 use iter_extended::vecmap;
 use nargo::{artifacts::debug::DebugArtifact, package::Package};
@@ -11,7 +12,7 @@ use noirc_driver::{
 };
 use noirc_errors::{debug_info::OpCodesCount, Location};
 use noirc_frontend::graph::CrateName;
-use prettytable::{row, table, Row};
+use prettytable::{cell, row, table, Row, Table};
 use rayon::prelude::*;
 use serde::Serialize;
 
@@ -24,7 +25,8 @@ use super::{compile_cmd::compile_workspace, NargoConfig};
 ///
 /// Current information provided:
 /// 1. The number of ACIR opcodes
-/// 2. Counts the final number gates in the circuit used by a backend
+/// 2. The number of Brillig opcodes run by the circuit's unconstrained calls
+/// 3. Counts the final number gates in the circuit used by a backend
 #[derive(Debug, Clone, Args)]
 pub(crate) struct InfoCommand {
     /// The name of the package to detail
@@ -36,18 +38,22 @@ pub(crate) struct InfoCommand {
     workspace: bool,
 
     /// Output a JSON formatted report. Changes to this format are not currently considered breaking.
-    #[clap(long, hide = true)]
+    #[clap(long)]
     json: bool,
 
     #[clap(long, hide = true)]
     profile_info: bool,
 
+    /// Estimate the time and memory needed to prove each circuit with the backend
+    #[clap(long)]
+    estimate: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
 
 pub(crate) fn run(
-    backend: &Backend,
+    backend: &dyn Backend,
     args: InfoCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -67,7 +73,9 @@ pub(crate) fn run(
         .cloned()
         .partition(|package| package.is_binary());
 
-    let (np_language, opcode_support) = backend.get_backend_info_or_default();
+    let BackendInfo { language: np_language, opcode_support, capabilities } =
+        backend.get_backend_info_or_default();
+    let cost_model = args.estimate.then(|| CostModel::new(&capabilities));
     let (compiled_programs, compiled_contracts) = compile_workspace(
         &workspace,
         &binary_packages,
@@ -98,13 +106,21 @@ pub(crate) fn run(
         .into_par_iter()
         .zip(compiled_programs)
         .map(|(package, program)| {
-            count_opcodes_and_gates_in_program(backend, program, &package, np_language)
+            count_opcodes_and_gates_in_program(
+                backend,
+                program,
+                &package,
+                np_language,
+                cost_model.as_ref(),
+            )
         })
         .collect::<Result<_, _>>()?;
 
     let contract_info = compiled_contracts
         .into_par_iter()
-        .map(|contract| count_opcodes_and_gates_in_contract(backend, contract, np_language))
+        .map(|contract| {
+            count_opcodes_and_gates_in_contract(backend, contract, np_language, cost_model.as_ref())
+        })
         .collect::<Result<_, _>>()?;
 
     let info_report = InfoReport { programs: program_info, contracts: contract_info };
@@ -115,7 +131,10 @@ pub(crate) fn run(
     } else {
         // Otherwise print human-readable table.
         if !info_report.programs.is_empty() {
-            let mut program_table = table!([Fm->"Package", Fm->"Language", Fm->"ACIR Opcodes", Fm->"Backend Circuit Size"]);
+            let mut program_table = table!([Fm->"Package", Fm->"Language", Fm->"ACIR Opcodes", Fm->"Brillig Opcodes", Fm->"Backend Circuit Size"]);
+            if args.estimate {
+                add_estimate_titles(&mut program_table);
+            }
 
             for program in info_report.programs {
                 program_table.add_row(program.into());
@@ -128,8 +147,12 @@ pub(crate) fn run(
                 Fm->"Function",
                 Fm->"Language",
                 Fm->"ACIR Opcodes",
+                Fm->"Brillig Opcodes",
                 Fm->"Backend Circuit Size"
             ]);
+            if args.estimate {
+                add_estimate_titles(&mut contract_table);
+            }
             for contract_info in info_report.contracts {
                 let contract_rows: Vec<Row> = contract_info.into();
                 for row in contract_rows {
@@ -205,17 +228,23 @@ struct ProgramInfo {
     #[serde(skip)]
     language: Language,
     acir_opcodes: usize,
+    brillig_opcodes: usize,
     circuit_size: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimate: Option<CostEstimate>,
 }
 
 impl From<ProgramInfo> for Row {
     fn from(program_info: ProgramInfo) -> Self {
-        row![
+        let mut row = row![
             Fm->format!("{}", program_info.name),
             format!("{:?}", program_info.language),
             Fc->format!("{}", program_info.acir_opcodes),
+            Fc->format!("{}", program_info.brillig_opcodes),
             Fc->format!("{}", program_info.circuit_size),
-        ]
+        ];
+        add_estimate_cells(&mut row, program_info.estimate);
+        row
     }
 }
 
@@ -231,53 +260,97 @@ struct ContractInfo {
 struct FunctionInfo {
     name: String,
     acir_opcodes: usize,
+    brillig_opcodes: usize,
     circuit_size: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimate: Option<CostEstimate>,
 }
 
 impl From<ContractInfo> for Vec<Row> {
     fn from(contract_info: ContractInfo) -> Self {
         vecmap(contract_info.functions, |function| {
-            row![
+            let mut row = row![
                 Fm->format!("{}", contract_info.name),
                 Fc->format!("{}", function.name),
                 format!("{:?}", contract_info.language),
                 Fc->format!("{}", function.acir_opcodes),
+                Fc->format!("{}", function.brillig_opcodes),
                 Fc->format!("{}", function.circuit_size),
-            ]
+            ];
+            add_estimate_cells(&mut row, function.estimate);
+            row
         })
     }
 }
 
+fn add_estimate_titles(table: &mut Table) {
+    let titles = table.get_row_mut(0).expect("tables should have a title row");
+    titles.add_cell(cell!(Fm->"Est. Proving Time"));
+    titles.add_cell(cell!(Fm->"Est. Proving Memory"));
+}
+
+fn add_estimate_cells(row: &mut Row, estimate: Option<CostEstimate>) {
+    if let Some(estimate) = estimate {
+        row.add_cell(cell!(Fc->format!("{:.1} s", estimate.proving_time_secs)));
+        row.add_cell(cell!(
+            Fc->format!("{:.1} MiB", estimate.memory_bytes as f64 / (1024.0 * 1024.0))
+        ));
+    }
+}
+
 fn count_opcodes_and_gates_in_program(
-    backend: &Backend,
+    backend: &dyn Backend,
     compiled_program: CompiledProgram,
     package: &Package,
     language: Language,
+    cost_model: Option<&CostModel>,
 ) -> Result<ProgramInfo, CliError> {
+    let circuit_size = backend.get_exact_circuit_size(&compiled_program.circuit)?;
     Ok(ProgramInfo {
         name: package.name.to_string(),
         language,
         acir_opcodes: compiled_program.circuit.opcodes.len(),
-        circuit_size: backend.get_exact_circuit_size(&compiled_program.circuit)?,
+        brillig_opcodes: count_brillig_opcodes(&compiled_program.circuit),
+        circuit_size,
+        estimate: cost_model
+            .map(|cost_model| cost_model.estimate(&compiled_program.circuit, circuit_size)),
     })
 }
 
 fn count_opcodes_and_gates_in_contract(
-    backend: &Backend,
+    backend: &dyn Backend,
     contract: CompiledContract,
     language: Language,
+    cost_model: Option<&CostModel>,
 ) -> Result<ContractInfo, CliError> {
     let functions = contract
         .functions
         .into_par_iter()
         .map(|function| -> Result<_, BackendError> {
+            let circuit_size = backend.get_exact_circuit_size(&function.bytecode)?;
             Ok(FunctionInfo {
                 name: function.name,
                 acir_opcodes: function.bytecode.opcodes.len(),
-                circuit_size: backend.get_exact_circuit_size(&function.bytecode)?,
+                brillig_opcodes: count_brillig_opcodes(&function.bytecode),
+                circuit_size,
+                estimate: cost_model
+                    .map(|cost_model| cost_model.estimate(&function.bytecode, circuit_size)),
             })
         })
         .collect::<Result<_, _>>()?;
 
     Ok(ContractInfo { name: contract.name, language, functions })
 }
+
+/// Counts the opcodes of the Brillig bytecode run by each ACIR Brillig opcode of a circuit.
+/// An unconstrained function called from several places is counted once for each call.
+fn count_brillig_opcodes(circuit: &Circuit) -> usize {
+    circuit
+        .opcodes
+        .iter()
+        .map(|opcode| match opcode {
+            Opcode::Brillig(brillig) => brillig.bytecode.len(),
+            _ => 0,
+        })
+        .sum()
+}
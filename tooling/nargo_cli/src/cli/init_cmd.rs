@@ -36,14 +36,17 @@ const LIB_EXAMPLE: &str = include_str!("./noir_template_files/library.nr");
 
 pub(crate) fn run(
     // Backend is currently unused, but we might want to use it to inform the "new" template in the future
-    _backend: &Backend,
+    _backend: &dyn Backend,
     args: InitCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
     let package_name = match args.name {
         Some(name) => name,
         None => {
-            let name = config.program_dir.file_name().unwrap().to_str().unwrap();
+            let name =
+                config.program_dir.file_name().and_then(|name| name.to_str()).ok_or_else(|| {
+                    CliError::InvalidPackageName(config.program_dir.display().to_string())
+                })?;
             name.parse().map_err(|_| CliError::InvalidPackageName(name.into()))?
         }
     };
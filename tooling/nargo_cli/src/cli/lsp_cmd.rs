@@ -20,7 +20,7 @@ pub(crate) struct LspCommand;
 
 pub(crate) fn run(
     // Backend is currently unused, but we might want to use it to inform the lsp in the future
-    _backend: &Backend,
+    _backend: &dyn Backend,
     _args: LspCommand,
     _config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -11,17 +11,25 @@ use crate::backends::get_active_backend;
 mod fs;
 
 mod backend_cmd;
+mod bench_cmd;
 mod check_cmd;
+mod codegen_cmd;
 mod codegen_verifier_cmd;
 mod compile_cmd;
+mod dap_cmd;
 mod debug_cmd;
 mod execute_cmd;
+mod explain_cmd;
+mod export_cmd;
 mod fmt_cmd;
+mod fuzz_cmd;
 mod info_cmd;
 mod init_cmd;
 mod lsp_cmd;
 mod new_cmd;
+mod profile_cmd;
 mod prove_cmd;
+mod recursion_inputs_cmd;
 mod test_cmd;
 mod verify_cmd;
 
@@ -45,6 +53,10 @@ struct NargoCli {
 
     #[clap(flatten)]
     config: NargoConfig,
+
+    /// The backend to use instead of the active one, e.g. `mock` to skip generating real proofs
+    #[arg(long, global = true)]
+    backend: Option<String>,
 }
 
 #[non_exhaustive]
@@ -61,23 +73,31 @@ enum NargoCommand {
     Backend(backend_cmd::BackendCommand),
     Check(check_cmd::CheckCommand),
     Fmt(fmt_cmd::FormatCommand),
+    Codegen(codegen_cmd::CodegenCommand),
     CodegenVerifier(codegen_verifier_cmd::CodegenVerifierCommand),
     #[command(alias = "build")]
     Compile(compile_cmd::CompileCommand),
     New(new_cmd::NewCommand),
     Init(init_cmd::InitCommand),
     Execute(execute_cmd::ExecuteCommand),
-    #[command(hide = true)] // Hidden while the feature is being built out
+    Export(export_cmd::ExportCommand),
     Debug(debug_cmd::DebugCommand),
     Prove(prove_cmd::ProveCommand),
     Verify(verify_cmd::VerifyCommand),
+    Bench(bench_cmd::BenchCommand),
+    RecursionInputs(recursion_inputs_cmd::RecursionInputsCommand),
     Test(test_cmd::TestCommand),
+    Fuzz(fuzz_cmd::FuzzCommand),
     Info(info_cmd::InfoCommand),
+    Profile(profile_cmd::ProfileCommand),
     Lsp(lsp_cmd::LspCommand),
+    #[command(hide = true)] // Hidden while the feature is being built out
+    Dap(dap_cmd::DapCommand),
+    Explain(explain_cmd::ExplainCommand),
 }
 
 pub(crate) fn start_cli() -> eyre::Result<()> {
-    let NargoCli { command, mut config } = NargoCli::parse();
+    let NargoCli { command, mut config, backend } = NargoCli::parse();
 
     // If the provided `program_dir` is relative, make it absolute by joining it to the current directory.
     if !config.program_dir.is_absolute() {
@@ -90,13 +110,15 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::New(_)
             | NargoCommand::Init(_)
             | NargoCommand::Lsp(_)
+            | NargoCommand::Dap(_)
             | NargoCommand::Backend(_)
+            | NargoCommand::Explain(_)
     ) {
         config.program_dir = find_package_root(&config.program_dir)?;
     }
 
-    let active_backend = get_active_backend();
-    let backend = crate::backends::Backend::new(active_backend);
+    let active_backend = backend.unwrap_or_else(get_active_backend);
+    let backend = crate::backends::get_backend(active_backend);
 
     match command {
         NargoCommand::New(args) => new_cmd::run(&backend, args, config),
@@ -105,14 +127,22 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::Compile(args) => compile_cmd::run(&backend, args, config),
         NargoCommand::Debug(args) => debug_cmd::run(&backend, args, config),
         NargoCommand::Execute(args) => execute_cmd::run(&backend, args, config),
+        NargoCommand::Export(args) => export_cmd::run(&backend, args, config),
         NargoCommand::Prove(args) => prove_cmd::run(&backend, args, config),
         NargoCommand::Verify(args) => verify_cmd::run(&backend, args, config),
+        NargoCommand::Bench(args) => bench_cmd::run(&backend, args, config),
+        NargoCommand::RecursionInputs(args) => recursion_inputs_cmd::run(&backend, args, config),
         NargoCommand::Test(args) => test_cmd::run(&backend, args, config),
+        NargoCommand::Fuzz(args) => fuzz_cmd::run(&backend, args, config),
         NargoCommand::Info(args) => info_cmd::run(&backend, args, config),
+        NargoCommand::Profile(args) => profile_cmd::run(&backend, args, config),
+        NargoCommand::Codegen(args) => codegen_cmd::run(&backend, args, config),
         NargoCommand::CodegenVerifier(args) => codegen_verifier_cmd::run(&backend, args, config),
         NargoCommand::Backend(args) => backend_cmd::run(args),
         NargoCommand::Lsp(args) => lsp_cmd::run(&backend, args, config),
+        NargoCommand::Dap(args) => dap_cmd::run(&backend, args, config),
         NargoCommand::Fmt(args) => fmt_cmd::run(args, config),
+        NargoCommand::Explain(args) => explain_cmd::run(args),
     }?;
 
     Ok(())
@@ -32,7 +32,7 @@ pub(crate) struct NewCommand {
 
 pub(crate) fn run(
     // Backend is currently unused, but we might want to use it to inform the "new" template in the future
-    _backend: &Backend,
+    _backend: &dyn Backend,
     args: NewCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -45,7 +45,11 @@ pub(crate) fn run(
     let package_name = match args.name {
         Some(name) => name,
         None => {
-            let name = args.path.file_name().unwrap().to_str().unwrap();
+            let name = args
+                .path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| CliError::InvalidPackageName(args.path.display().to_string()))?;
             name.parse().map_err(|_| CliError::InvalidPackageName(name.into()))?
         }
     };
@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+
+use acvm::acir::circuit::{Circuit, OpcodeLocation};
+use backend_interface::BackendInfo;
+use clap::Args;
+use inferno::flamegraph;
+use nargo::artifacts::debug::DebugArtifact;
+use nargo::workspace::Workspace;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::{debug_info::DebugInfo, Location};
+use noirc_frontend::graph::CrateName;
+
+use crate::backends::Backend;
+use crate::errors::CliError;
+
+use super::fs::{create_named_dir, write_to_file};
+use super::{compile_cmd::compile_workspace, NargoConfig};
+
+/// Profiles which source code the cost of each circuit comes from
+///
+/// Prints the cost of each call stack in the circuit's source code in the folded stacks format,
+/// which tools such as inferno and flamegraph.pl render as flamegraphs.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ProfileCommand {
+    /// The name of the package to profile
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Profile all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// Write a flamegraph SVG of each circuit to the target directory instead of printing the
+    /// folded stacks
+    #[clap(long)]
+    flamegraph: bool,
+
+    /// Measure the cost of each opcode in the gates the backend generates for it, rather than
+    /// counting each opcode once
+    #[clap(long)]
+    gates: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(
+    backend: &dyn Backend,
+    args: ProfileCommand,
+    config: NargoConfig,
+) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    let (binary_packages, contract_packages): (Vec<_>, Vec<_>) = workspace
+        .into_iter()
+        .filter(|package| !package.is_library())
+        .cloned()
+        .partition(|package| package.is_binary());
+
+    let BackendInfo { language: np_language, opcode_support, .. } =
+        backend.get_backend_info_or_default();
+    let (compiled_programs, compiled_contracts) = compile_workspace(
+        &workspace,
+        &binary_packages,
+        &contract_packages,
+        np_language,
+        &opcode_support,
+        &args.compile_options,
+    )?;
+
+    for (package, program) in binary_packages.iter().zip(compiled_programs) {
+        let debug_artifact: DebugArtifact = program.clone().into();
+        let name = package.name.to_string();
+        profile_circuit(
+            backend,
+            &args,
+            &workspace,
+            &name,
+            &program.circuit,
+            &program.debug,
+            &debug_artifact,
+        )?;
+    }
+    for contract in compiled_contracts {
+        let debug_artifact: DebugArtifact = (&contract).into();
+        for function in &contract.functions {
+            let name = format!("{}-{}", contract.name, function.name);
+            profile_circuit(
+                backend,
+                &args,
+                &workspace,
+                &name,
+                &function.bytecode,
+                &function.debug,
+                &debug_artifact,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the folded stacks of a circuit, or writes them as a flamegraph to the target directory.
+fn profile_circuit(
+    backend: &dyn Backend,
+    args: &ProfileCommand,
+    workspace: &Workspace,
+    name: &str,
+    circuit: &Circuit,
+    debug: &DebugInfo,
+    debug_artifact: &DebugArtifact,
+) -> Result<(), CliError> {
+    let costs = if args.gates {
+        backend.get_gates_per_opcode(circuit)?
+    } else {
+        vec![1; circuit.opcodes.len()]
+    };
+    let stacks = folded_stacks(name, debug, debug_artifact, &costs);
+
+    if !args.flamegraph {
+        stacks.iter().for_each(|stack| println!("{stack}"));
+        return Ok(());
+    }
+    if stacks.is_empty() {
+        println!("Skipping flamegraph of {name} as its circuit is empty");
+        return Ok(());
+    }
+
+    let count_name = if args.gates { "gates" } else { "opcodes" };
+    let mut options = flamegraph::Options::default();
+    options.title = format!("{name} ({count_name})");
+    options.count_name = count_name.to_string();
+    let mut svg = Vec::new();
+    flamegraph::from_lines(&mut options, stacks.iter().map(String::as_str), &mut svg)
+        .map_err(|err| CliError::Generic(format!("Could not render flamegraph: {err}")))?;
+
+    let target_dir = create_named_dir(&workspace.target_directory_path(), "target");
+    let svg_path = target_dir.join(name).with_extension("svg");
+    println!("Flamegraph written to {}", write_to_file(&svg, &svg_path));
+    Ok(())
+}
+
+/// Sums the `costs` of each opcode of a circuit by the call stack it was generated from,
+/// returning a line of `root;caller;...;callee cost` for each call stack.
+fn folded_stacks(
+    root: &str,
+    debug: &DebugInfo,
+    debug_artifact: &DebugArtifact,
+    costs: &[u32],
+) -> Vec<String> {
+    let mut stack_costs: BTreeMap<String, u64> = BTreeMap::new();
+    for (index, cost) in costs.iter().enumerate() {
+        let call_stack = debug.opcode_location(&OpcodeLocation::Acir(index)).unwrap_or_default();
+
+        let mut frames = vec![root.replace(';', ":")];
+        frames.extend(call_stack.iter().map(|location| frame(debug, debug_artifact, *location)));
+        *stack_costs.entry(frames.join(";")).or_default() += u64::from(*cost);
+    }
+
+    stack_costs.into_iter().map(|(stack, cost)| format!("{stack} {cost}")).collect()
+}
+
+/// Names the frame of a call stack at `location`, such as `main src/main.nr:4`. Each call site in
+/// a function is a separate frame so that the cost of each call can be told apart.
+fn frame(debug: &DebugInfo, debug_artifact: &DebugArtifact, location: Location) -> String {
+    let function = debug.function_name(&location).unwrap_or("<unknown>");
+    let frame = match (
+        debug_artifact.file_map.get(&location.file),
+        debug_artifact.location_line_number(location),
+    ) {
+        (Some(file), Ok(line)) => format!("{function} {}:{line}", file.path.display()),
+        _ => function.to_string(),
+    };
+    // Semicolons separate the frames of folded stacks
+    frame.replace(';', ":")
+}
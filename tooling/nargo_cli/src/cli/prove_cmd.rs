@@ -1,5 +1,9 @@
+use std::path::{Path, PathBuf};
+
+use backend_interface::BackendInfo;
 use clap::Args;
 use nargo::constants::{PROVER_INPUT_FILE, VERIFIER_INPUT_FILE};
+use nargo::ops::Proof;
 use nargo::package::Package;
 use nargo::workspace::Workspace;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
@@ -11,6 +15,7 @@ use super::compile_cmd::compile_bin_package;
 use super::fs::{
     inputs::{read_inputs_from_file, write_inputs_to_file},
     proof::save_proof_to_dir,
+    witness::read_witness_from_file,
 };
 use super::NargoConfig;
 use crate::{backends::Backend, cli::execute_cmd::execute_program, errors::CliError};
@@ -18,18 +23,38 @@ use crate::{backends::Backend, cli::execute_cmd::execute_program, errors::CliErr
 /// Create proof for this program. The proof is returned as a hex encoded string.
 #[derive(Debug, Clone, Args)]
 pub(crate) struct ProveCommand {
-    /// The name of the toml file which contains the inputs for the prover
+    /// The name of the file which contains the inputs for the prover
     #[clap(long, short, default_value = PROVER_INPUT_FILE)]
     prover_name: String,
 
-    /// The name of the toml file which contains the inputs for the verifier
+    /// The name of the file which the inputs for the verifier are written to
     #[clap(long, short, default_value = VERIFIER_INPUT_FILE)]
     verifier_name: String,
 
+    /// The format of the prover inputs file, either `toml` or `json`
+    #[clap(long, default_value = "toml")]
+    input_format: Format,
+
+    /// The format to write the verifier inputs file in, either `toml` or `json`
+    #[clap(long, default_value = "toml")]
+    output_format: Format,
+
     /// Verify proof after proving
     #[arg(long)]
     verify: bool,
 
+    /// Generate a proof which can be verified within another circuit
+    #[arg(long)]
+    recursive: bool,
+
+    /// Prove using the witness in this file rather than executing the program with the prover inputs
+    #[arg(long, conflicts_with = "workspace")]
+    witness: Option<PathBuf>,
+
+    /// JSON-RPC server to resolve oracle calls which aren't mocked
+    #[clap(long)]
+    oracle_resolver: Option<String>,
+
     /// The name of the package to prove
     #[clap(long, conflicts_with = "workspace")]
     package: Option<CrateName>,
@@ -43,7 +68,7 @@ pub(crate) struct ProveCommand {
 }
 
 pub(crate) fn run(
-    backend: &Backend,
+    backend: &dyn Backend,
     args: ProveCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -57,7 +82,7 @@ pub(crate) fn run(
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
     )?;
 
-    let (np_language, opcode_support) = backend.get_backend_info()?;
+    let BackendInfo { language: np_language, opcode_support, .. } = backend.get_backend_info()?;
     for package in &workspace {
         let program = compile_bin_package(
             &workspace,
@@ -74,6 +99,11 @@ pub(crate) fn run(
             program,
             &args.prover_name,
             &args.verifier_name,
+            args.input_format,
+            args.output_format,
+            args.witness.as_deref(),
+            args.oracle_resolver.as_deref(),
+            args.recursive,
             args.verify,
         )?;
     }
@@ -81,22 +111,42 @@ pub(crate) fn run(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn prove_package(
-    backend: &Backend,
+    backend: &dyn Backend,
     workspace: &Workspace,
     package: &Package,
     compiled_program: CompiledProgram,
     prover_name: &str,
     verifier_name: &str,
+    input_format: Format,
+    output_format: Format,
+    witness_path: Option<&Path>,
+    oracle_resolver: Option<&str>,
+    is_recursive: bool,
     check_proof: bool,
 ) -> Result<(), CliError> {
-    // Parse the initial witness values from Prover.toml
-    let (inputs_map, _) =
-        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &compiled_program.abi)?;
-
-    let solved_witness = execute_program(&compiled_program, &inputs_map)?;
+    let solved_witness = match witness_path {
+        Some(witness_path) => read_witness_from_file(witness_path)?,
+        None => {
+            // Parse the initial witness values from the prover inputs file
+            let (inputs_map, _) = read_inputs_from_file(
+                &package.root_dir,
+                prover_name,
+                input_format,
+                &compiled_program.abi,
+            )?;
+
+            execute_program(
+                &compiled_program,
+                &inputs_map,
+                oracle_resolver,
+                package.witness_solver.as_deref(),
+            )?
+        }
+    };
 
-    // Write public inputs into Verifier.toml
+    // Write public inputs into the verifier inputs file
     let public_abi = compiled_program.abi.public_abi();
     let (public_inputs, return_value) = public_abi.decode(&solved_witness)?;
 
@@ -106,15 +156,20 @@ pub(crate) fn prove_package(
         &public_abi,
         &package.root_dir,
         verifier_name,
-        Format::Toml,
+        output_format,
     )?;
 
-    let proof = backend.prove(&compiled_program.circuit, solved_witness, false)?;
+    let public_inputs = public_abi.encode(&public_inputs, return_value)?;
+    let proof_bytes = backend.prove(&compiled_program.circuit, solved_witness, is_recursive)?;
+    let proof = Proof::new(&compiled_program.circuit, public_inputs, proof_bytes);
 
     if check_proof {
-        let public_inputs = public_abi.encode(&public_inputs, return_value)?;
-        let valid_proof =
-            backend.verify(&proof, public_inputs, &compiled_program.circuit, false)?;
+        let valid_proof = backend.verify(
+            proof.bytes(),
+            proof.public_inputs().clone(),
+            &compiled_program.circuit,
+            is_recursive,
+        )?;
 
         if !valid_proof {
             return Err(CliError::InvalidProof("".into()));
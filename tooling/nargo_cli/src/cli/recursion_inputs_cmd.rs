@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+
+use backend_interface::BackendInfo;
+use clap::Args;
+use nargo::constants::{PROOF_EXT, PROVER_INPUT_FILE};
+use nargo::ops::RecursionArtifacts;
+use nargo::package::Package;
+use nargo::workspace::Workspace;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::graph::CrateName;
+
+use super::compile_cmd::compile_bin_package;
+use super::fs::{inputs::update_inputs_file, proof::load_proof_from_file};
+use super::NargoConfig;
+use crate::{backends::Backend, errors::CliError};
+
+/// Writes the inputs to `std::verify_proof` which verify this program's proof into the prover
+/// inputs of an outer circuit
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RecursionInputsCommand {
+    /// The directory of the outer circuit's package
+    #[clap(long)]
+    outer: PathBuf,
+
+    /// The name of the outer circuit's toml file which the inputs are written to
+    #[clap(long, short, default_value = PROVER_INPUT_FILE)]
+    prover_name: String,
+
+    /// The prefix of the outer circuit's parameters, e.g. `inner_` for `inner_proof`
+    #[clap(long, default_value = "")]
+    prefix: String,
+
+    /// The name of the package whose proof is verified by the outer circuit
+    #[clap(long)]
+    package: Option<CrateName>,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(
+    backend: &dyn Backend,
+    args: RecursionInputsCommand,
+    config: NargoConfig,
+) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let selection = args.package.map_or(PackageSelection::DefaultOrAll, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+    let package = single_package(&workspace)?;
+
+    let outer_toml_path = get_package_manifest(&args.outer)?;
+    let outer_workspace = resolve_workspace_from_toml(
+        &outer_toml_path,
+        PackageSelection::DefaultOrAll,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+    let outer_package = single_package(&outer_workspace)?;
+
+    let BackendInfo { language: np_language, opcode_support, .. } = backend.get_backend_info()?;
+    let program = compile_bin_package(
+        &workspace,
+        package,
+        &args.compile_options,
+        np_language,
+        &opcode_support,
+    )?;
+    let outer_program = compile_bin_package(
+        &outer_workspace,
+        outer_package,
+        &args.compile_options,
+        np_language,
+        &opcode_support,
+    )?;
+
+    let proof_path =
+        workspace.proofs_directory_path().join(package.name.to_string()).with_extension(PROOF_EXT);
+    let proof = load_proof_from_file(&proof_path)?;
+    proof.check_circuit(&program.circuit)?;
+
+    let (proof_as_fields, key_hash, vk_as_fields) = backend.get_intermediate_proof_artifacts(
+        &program.circuit,
+        proof.bytes(),
+        proof.public_inputs().clone(),
+    )?;
+    let artifacts = RecursionArtifacts::new(&proof, proof_as_fields, key_hash, vk_as_fields);
+
+    let input_map = artifacts.to_input_map(&outer_program.abi, &args.prefix)?;
+    let inputs_path = update_inputs_file(
+        &input_map,
+        &outer_program.abi,
+        &outer_package.root_dir,
+        &args.prover_name,
+    )?;
+    println!("[{}] Inputs to verify the proof written to {}", package.name, inputs_path.display());
+
+    Ok(())
+}
+
+fn single_package(workspace: &Workspace) -> Result<&Package, CliError> {
+    let mut packages = workspace.into_iter();
+    match (packages.next(), packages.next()) {
+        (Some(package), None) => Ok(package),
+        _ => Err(CliError::Generic(format!(
+            "Workspace at {} must be narrowed down to a single package with `--package`",
+            workspace.root_dir.display()
+        ))),
+    }
+}
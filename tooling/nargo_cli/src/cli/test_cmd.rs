@@ -1,19 +1,24 @@
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use acvm::BlackBoxFunctionSolver;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use nargo::{
-    ops::{run_test, TestStatus},
+    ops::{run_test, CoverageReport, TestStatus},
+    override_lint_levels,
     package::Package,
     prepare_package,
 };
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_frontend::{graph::CrateName, hir::FunctionNameMatch};
+use serde::Serialize;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use crate::{backends::Backend, cli::check_cmd::check_crate_and_report_errors, errors::CliError};
 
+use super::fs::{create_named_dir, write_to_file};
 use super::NargoConfig;
 
 /// Run the tests for this program
@@ -38,12 +43,58 @@ pub(crate) struct TestCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// The format of the test results printed to stdout
+    #[clap(long, value_enum, default_value_t = TestFormat::Pretty)]
+    format: TestFormat,
+
+    /// Write the test results to this file instead of stdout, in the `--format` given
+    #[clap(long)]
+    report_path: Option<PathBuf>,
+
+    /// JSON-RPC server to resolve oracle calls which aren't mocked
+    #[clap(long)]
+    oracle_resolver: Option<String>,
+
+    /// Record the lines executed by the tests and write them to `target/lcov.info`
+    #[clap(long)]
+    coverage: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TestFormat {
+    /// Colored progress of each test for people to read
+    Pretty,
+    /// A JSON array with the result of each test
+    Json,
+    /// A JUnit XML report with a test suite for each package
+    Junit,
+}
+
+/// The result of a single test, as written to machine-readable reports
+#[derive(Debug, Serialize)]
+struct TestReport {
+    package: String,
+    name: String,
+    status: TestOutcome,
+    /// The time taken to run the test, in seconds
+    duration: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
+}
+
 pub(crate) fn run(
-    _backend: &Backend,
+    _backend: &dyn Backend,
     args: TestCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -68,26 +119,92 @@ pub(crate) fn run(
         None => FunctionNameMatch::Anything,
     };
 
+    if args.format == TestFormat::Pretty && args.report_path.is_some() {
+        return Err(CliError::Generic(
+            "`--report-path` requires `--format json` or `--format junit`".to_string(),
+        ));
+    }
+
     #[allow(deprecated)]
     let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
+    let mut reports = Vec::new();
+    let mut coverage = args.coverage.then(|| CoverageReport::new(workspace.root_dir.clone()));
+    let mut result = Ok(());
     for package in &workspace {
-        // By unwrapping here with `?`, we stop the test runner upon a package failing
-        // TODO: We should run the whole suite even if there are failures in a package
-        run_tests(&blackbox_solver, package, pattern, args.show_output, &args.compile_options)?;
+        // The whole suite is run so that the report covers every package, but the first package
+        // which fails is reported as the error of the command.
+        let package_result =
+            run_tests(&blackbox_solver, package, pattern, &args, &mut reports, &mut coverage);
+        if result.is_ok() {
+            result = package_result;
+        }
     }
 
-    Ok(())
+    if let Some(coverage) = coverage {
+        let lcov_path = workspace.target_directory_path().join("lcov.info");
+        report_coverage(&coverage, &workspace.root_dir, &lcov_path);
+    }
+
+    let report = match args.format {
+        TestFormat::Pretty => return result,
+        TestFormat::Json => serde_json::to_string_pretty(&reports)
+            .map_err(|err| CliError::Generic(format!("Could not serialize test report: {err}")))?,
+        TestFormat::Junit => junit_report(&reports),
+    };
+    match &args.report_path {
+        Some(report_path) => {
+            let path = write_to_file(report.as_bytes(), report_path);
+            eprintln!("Test report written to {path}");
+        }
+        None => println!("{report}"),
+    }
+
+    result
 }
 
+/// Prints the line coverage of each file to stderr and writes the lcov report to `lcov_path`.
+fn report_coverage(coverage: &CoverageReport, root_dir: &Path, lcov_path: &Path) {
+    let percentage = |hit: usize, found: usize| {
+        if found == 0 {
+            100.0
+        } else {
+            hit as f64 * 100.0 / found as f64
+        }
+    };
+
+    let (mut total_hit, mut total_found) = (0, 0);
+    eprintln!("Coverage:");
+    for (path, hit, found) in coverage.file_summaries() {
+        let path = path.strip_prefix(root_dir).unwrap_or(path);
+        eprintln!("  {}: {hit}/{found} lines ({:.1}%)", path.display(), percentage(hit, found));
+        total_hit += hit;
+        total_found += found;
+    }
+    eprintln!(
+        "Total: {total_hit}/{total_found} lines ({:.1}%)",
+        percentage(total_hit, total_found)
+    );
+
+    create_named_dir(lcov_path.parent().expect("lcov path should be in a directory"), "target");
+    let path = write_to_file(coverage.to_lcov().as_bytes(), lcov_path);
+    eprintln!("Coverage report written to {path}");
+}
+
+/// Runs the tests of a package, printing their progress to stderr and adding their results to
+/// `reports`, and the lines they execute to `coverage` if it is being recorded. Returns an error
+/// if any of the tests fail.
 fn run_tests<S: BlackBoxFunctionSolver>(
     blackbox_solver: &S,
     package: &Package,
     test_name: FunctionNameMatch,
-    show_output: bool,
-    compile_options: &CompileOptions,
+    args: &TestCommand,
+    reports: &mut Vec<TestReport>,
+    coverage: &mut Option<CoverageReport>,
 ) -> Result<(), CliError> {
+    let compile_options = &package.compile_options(&args.compile_options);
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
+    override_lint_levels(&mut context, crate_id, compile_options);
     check_crate_and_report_errors(
         &mut context,
         crate_id,
@@ -97,8 +214,12 @@ fn run_tests<S: BlackBoxFunctionSolver>(
 
     let test_functions = context.get_all_test_functions_in_crate_matching(&crate_id, test_name);
 
-    println!("[{}] Running {} test functions", package.name, test_functions.len());
+    // Machine-readable reports are the only thing written to stdout
+    if args.format == TestFormat::Pretty {
+        println!("[{}] Running {} test functions", package.name, test_functions.len());
+    }
     let mut failing = 0;
+    let mut ignored = 0;
 
     let writer = StandardStream::stderr(ColorChoice::Always);
     let mut writer = writer.lock();
@@ -108,7 +229,19 @@ fn run_tests<S: BlackBoxFunctionSolver>(
             .expect("Failed to write to stdout");
         writer.flush().expect("Failed to flush writer");
 
-        match run_test(blackbox_solver, &context, test_function, show_output, compile_options) {
+        let start = Instant::now();
+        let status = run_test(
+            blackbox_solver,
+            &context,
+            test_function,
+            args.show_output,
+            args.oracle_resolver.as_deref(),
+            coverage.as_mut(),
+            compile_options,
+        );
+        reports.push(TestReport::new(package, &test_name, &status, start.elapsed()));
+
+        match status {
             TestStatus::Pass { .. } => {
                 writer
                     .set_color(ColorSpec::new().set_fg(Some(Color::Green)))
@@ -142,6 +275,17 @@ fn run_tests<S: BlackBoxFunctionSolver>(
                 );
                 failing += 1;
             }
+            TestStatus::Ignored { reason } => {
+                writer
+                    .set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))
+                    .expect("Failed to set color");
+                match reason {
+                    Some(reason) => writeln!(writer, "ignored, {reason}"),
+                    None => writeln!(writer, "ignored"),
+                }
+                .expect("Failed to write to stdout");
+                ignored += 1;
+            }
         }
         writer.reset().expect("Failed to reset writer");
     }
@@ -149,7 +293,11 @@ fn run_tests<S: BlackBoxFunctionSolver>(
     if failing == 0 {
         write!(writer, "[{}] ", package.name).expect("Failed to write to stdout");
         writer.set_color(ColorSpec::new().set_fg(Some(Color::Green))).expect("Failed to set color");
-        writeln!(writer, "All tests passed").expect("Failed to write to stdout");
+        match ignored {
+            0 => writeln!(writer, "All tests passed"),
+            _ => writeln!(writer, "All tests passed, {ignored} ignored"),
+        }
+        .expect("Failed to write to stdout");
     } else {
         let plural = if failing == 1 { "" } else { "s" };
         return Err(CliError::Generic(format!("[{}] {failing} test{plural} failed", package.name)));
@@ -158,3 +306,131 @@ fn run_tests<S: BlackBoxFunctionSolver>(
     writer.reset().expect("Failed to reset writer");
     Ok(())
 }
+
+impl TestReport {
+    fn new(package: &Package, name: &str, status: &TestStatus, duration: Duration) -> Self {
+        let (status, message) = match status {
+            TestStatus::Pass => (TestOutcome::Passed, None),
+            TestStatus::Fail { message, .. } => (TestOutcome::Failed, Some(message.clone())),
+            TestStatus::CompileError(diagnostic) => {
+                (TestOutcome::Failed, Some(diagnostic.diagnostic.message.clone()))
+            }
+            TestStatus::Ignored { reason } => (TestOutcome::Ignored, reason.clone()),
+        };
+        TestReport {
+            package: package.name.to_string(),
+            name: name.to_string(),
+            status,
+            duration: duration.as_secs_f64(),
+            message,
+        }
+    }
+}
+
+/// Renders the test results as a JUnit XML report, with a test suite for each package.
+fn junit_report(reports: &[TestReport]) -> String {
+    let mut packages: Vec<&str> = Vec::new();
+    for report in reports {
+        if !packages.contains(&report.package.as_str()) {
+            packages.push(&report.package);
+        }
+    }
+
+    let count = |tests: &[&TestReport], outcome: TestOutcome| {
+        tests.iter().filter(|report| report.status == outcome).count()
+    };
+    let total_duration =
+        |tests: &[&TestReport]| -> f64 { tests.iter().map(|report| report.duration).sum() };
+    let all_tests: Vec<_> = reports.iter().collect();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"nargo test\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+        all_tests.len(),
+        count(&all_tests, TestOutcome::Failed),
+        count(&all_tests, TestOutcome::Ignored),
+        total_duration(&all_tests),
+    ));
+    for package in packages {
+        let tests: Vec<_> = reports.iter().filter(|report| report.package == package).collect();
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+            xml_escape(package),
+            tests.len(),
+            count(&tests, TestOutcome::Failed),
+            count(&tests, TestOutcome::Ignored),
+            total_duration(&tests),
+        ));
+        for test in tests {
+            xml.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.6}\"",
+                xml_escape(&test.name),
+                xml_escape(&test.package),
+                test.duration,
+            ));
+            let message = test.message.as_deref().map(xml_escape).unwrap_or_default();
+            match test.status {
+                TestOutcome::Passed => xml.push_str(" />\n"),
+                TestOutcome::Failed => xml.push_str(&format!(
+                    ">\n      <failure message=\"{message}\">{message}</failure>\n    </testcase>\n"
+                )),
+                TestOutcome::Ignored => xml.push_str(&format!(
+                    ">\n      <skipped message=\"{message}\" />\n    </testcase>\n"
+                )),
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>");
+    xml
+}
+
+/// Escapes the characters of `text` which cannot appear in XML attributes or text.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{junit_report, TestOutcome, TestReport};
+
+    #[test]
+    fn junit_report_groups_tests_by_package() {
+        let report = |package: &str, name: &str, status, message: Option<&str>| TestReport {
+            package: package.to_string(),
+            name: name.to_string(),
+            status,
+            duration: 0.5,
+            message: message.map(str::to_string),
+        };
+        let reports = vec![
+            report("foo", "test_add", TestOutcome::Passed, None),
+            report("foo", "test_lt", TestOutcome::Failed, Some("Failed assertion: x < y")),
+            report("bar", "test_slow", TestOutcome::Ignored, None),
+        ];
+
+        let xml = junit_report(&reports);
+        assert!(xml.contains(
+            r#"<testsuites name="nargo test" tests="3" failures="1" skipped="1" time="1.500000">"#
+        ));
+        assert!(xml.contains(
+            r#"<testsuite name="foo" tests="2" failures="1" skipped="0" time="1.000000">"#
+        ));
+        assert!(xml.contains(
+            r#"<testsuite name="bar" tests="1" failures="0" skipped="1" time="0.500000">"#
+        ));
+        assert!(xml.contains(r#"<testcase name="test_add" classname="foo" time="0.500000" />"#));
+        assert!(xml.contains(r#"<failure message="Failed assertion: x &lt; y">"#));
+    }
+}
@@ -1,10 +1,14 @@
 use super::NargoConfig;
 use super::{
     compile_cmd::compile_bin_package,
-    fs::{inputs::read_inputs_from_file, load_hex_data},
+    fs::{inputs::read_inputs_from_file, proof::load_proof_from_file},
+};
+use crate::{
+    backends::{get_available_backends, get_backend, Backend, MOCK_BACKEND},
+    errors::{BackendError, CliError},
 };
-use crate::{backends::Backend, errors::CliError};
 
+use backend_interface::BackendInfo;
 use clap::Args;
 use nargo::constants::{PROOF_EXT, VERIFIER_INPUT_FILE};
 use nargo::package::Package;
@@ -17,10 +21,14 @@ use noirc_frontend::graph::CrateName;
 /// Given a proof and a program, verify whether the proof is valid
 #[derive(Debug, Clone, Args)]
 pub(crate) struct VerifyCommand {
-    /// The name of the toml file which contains the inputs for the verifier
+    /// The name of the file which contains the inputs for the verifier
     #[clap(long, short, default_value = VERIFIER_INPUT_FILE)]
     verifier_name: String,
 
+    /// The format of the verifier inputs file, either `toml` or `json`
+    #[clap(long, default_value = "toml")]
+    input_format: Format,
+
     /// The name of the package verify
     #[clap(long, conflicts_with = "workspace")]
     package: Option<CrateName>,
@@ -29,12 +37,24 @@ pub(crate) struct VerifyCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// Also verify the proof with these installed backends, to check that they agree
+    #[clap(long, value_delimiter = ',', conflicts_with = "cross_verify_all")]
+    cross_verify: Vec<String>,
+
+    /// Verify a proof which was generated with `nargo prove --recursive`
+    #[clap(long)]
+    recursive: bool,
+
+    /// Also verify the proof with every installed backend
+    #[clap(long)]
+    cross_verify_all: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
 
 pub(crate) fn run(
-    backend: &Backend,
+    backend: &dyn Backend,
     args: VerifyCommand,
     config: NargoConfig,
 ) -> Result<(), CliError> {
@@ -48,7 +68,21 @@ pub(crate) fn run(
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
     )?;
 
-    let (np_language, opcode_support) = backend.get_backend_info()?;
+    let installed_backends = get_available_backends();
+    let cross_backend_names =
+        if args.cross_verify_all { installed_backends.clone() } else { args.cross_verify };
+    let mut cross_backends = Vec::new();
+    for name in cross_backend_names {
+        if name == backend.name() {
+            continue;
+        }
+        if name != MOCK_BACKEND && !installed_backends.contains(&name) {
+            return Err(BackendError::UnknownBackend(name).into());
+        }
+        cross_backends.push(get_backend(name));
+    }
+
+    let BackendInfo { language: np_language, opcode_support, .. } = backend.get_backend_info()?;
     for package in &workspace {
         let program = compile_bin_package(
             &workspace,
@@ -58,36 +92,74 @@ pub(crate) fn run(
             &opcode_support,
         )?;
 
-        verify_package(backend, &workspace, package, program, &args.verifier_name)?;
+        verify_package(
+            backend,
+            &cross_backends,
+            &workspace,
+            package,
+            program,
+            &args.verifier_name,
+            args.input_format,
+            args.recursive,
+        )?;
     }
 
     Ok(())
 }
 
 fn verify_package(
-    backend: &Backend,
+    backend: &dyn Backend,
+    cross_backends: &[Box<dyn Backend>],
     workspace: &Workspace,
     package: &Package,
     compiled_program: CompiledProgram,
     verifier_name: &str,
+    input_format: Format,
+    is_recursive: bool,
 ) -> Result<(), CliError> {
     // Load public inputs (if any) from `verifier_name`.
     let public_abi = compiled_program.abi.public_abi();
     let (public_inputs_map, return_value) =
-        read_inputs_from_file(&package.root_dir, verifier_name, Format::Toml, &public_abi)?;
+        read_inputs_from_file(&package.root_dir, verifier_name, input_format, &public_abi)?;
 
     let public_inputs = public_abi.encode(&public_inputs_map, return_value)?;
 
     let proof_path =
         workspace.proofs_directory_path().join(package.name.to_string()).with_extension(PROOF_EXT);
 
-    let proof = load_hex_data(&proof_path)?;
+    let proof = load_proof_from_file(&proof_path)?;
+    proof.check_circuit(&compiled_program.circuit)?;
 
-    let valid_proof = backend.verify(&proof, public_inputs, &compiled_program.circuit, false)?;
+    let valid_proof = backend.verify(
+        proof.bytes(),
+        public_inputs.clone(),
+        &compiled_program.circuit,
+        is_recursive,
+    )?;
+    if !valid_proof {
+        return Err(CliError::InvalidProof(proof_path));
+    }
+
+    // The proof is checked against the circuit compiled for the primary backend, as this is the
+    // circuit which it was generated for.
+    let mut failures = Vec::new();
+    for cross_backend in cross_backends {
+        let name = cross_backend.name();
+        match cross_backend.verify(
+            proof.bytes(),
+            public_inputs.clone(),
+            &compiled_program.circuit,
+            is_recursive,
+        ) {
+            Ok(true) => println!("[{}] Proof verified with `{name}`", package.name),
+            Ok(false) => failures.push(format!("`{name}` rejected the proof")),
+            Err(error) => failures.push(format!("`{name}` failed to verify the proof: {error}")),
+        }
+    }
 
-    if valid_proof {
+    if failures.is_empty() {
         Ok(())
     } else {
-        Err(CliError::InvalidProof(proof_path))
+        Err(CliError::BackendsDisagree { proof_path, backend: backend.name().to_owned(), failures })
     }
 }
@@ -1,8 +1,11 @@
-use acvm::acir::native_types::WitnessMapError;
 use hex::FromHexError;
-use nargo::{errors::CompileError, NargoError};
+use nargo::{
+    errors::{ArtifactError, CompileError, ProofError, RecursionError},
+    NargoError,
+};
 use nargo_toml::ManifestError;
 use noirc_abi::errors::{AbiError, InputParserError};
+use noirc_abi::witness::WitnessFormatError;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -13,17 +16,25 @@ pub(crate) enum FilesystemError {
     #[error("Error: could not parse hex build artifact (proof, proving and/or verification keys, ACIR checksum) ({0})")]
     HexArtifactNotValid(FromHexError),
     #[error(
-        " Error: cannot find {0}.toml file.\n Expected location: {1:?} \n Please generate this file at the expected location."
+        " Error: cannot find {0} file.\n Expected location: {1:?} \n Please generate this file at the expected location."
     )]
-    MissingTomlFile(String, PathBuf),
+    MissingInputFile(String, PathBuf),
 
     /// Input parsing error
     #[error(transparent)]
     InputParserError(#[from] InputParserError),
 
-    /// WitnessMap serialization error
+    /// Witness serialization error
     #[error(transparent)]
-    WitnessMapSerialization(#[from] WitnessMapError),
+    WitnessSerialization(#[from] WitnessFormatError),
+
+    /// Program artifact deserialization error
+    #[error(transparent)]
+    ArtifactError(#[from] ArtifactError),
+
+    /// Proof deserialization error
+    #[error(transparent)]
+    ProofSerialization(#[from] ProofError),
 
     #[error("Error: could not deserialize build program: {0}")]
     ProgramSerializationError(String),
@@ -39,6 +50,9 @@ pub(crate) enum CliError {
     #[error("Failed to verify proof {}", .0.display())]
     InvalidProof(PathBuf),
 
+    #[error("Proof {} was verified with `{backend}` but not with other backends:\n{}", .proof_path.display(), .failures.join("\n"))]
+    BackendsDisagree { proof_path: PathBuf, backend: String, failures: Vec<String> },
+
     #[error("Invalid package name {0}. Did you mean to use `--name`?")]
     InvalidPackageName(String),
 
@@ -50,9 +64,16 @@ pub(crate) enum CliError {
     #[error(transparent)]
     FilesystemError(#[from] FilesystemError),
 
+    /// Error from a proof which doesn't match the program being verified
+    #[error(transparent)]
+    ProofError(#[from] ProofError),
+
     #[error(transparent)]
     LspError(#[from] async_lsp::Error),
 
+    #[error("Debug Adapter Protocol connection failed: {0}")]
+    DapError(std::io::Error),
+
     /// Error from Nargo
     #[error(transparent)]
     NargoError(#[from] NargoError),
@@ -61,6 +82,14 @@ pub(crate) enum CliError {
     #[error(transparent)]
     ManifestError(#[from] ManifestError),
 
+    /// Error from an outer circuit which can't take the inputs to verify a proof
+    #[error(transparent)]
+    RecursionError(#[from] RecursionError),
+
+    /// Error from the chain profile of a verifier contract
+    #[error(transparent)]
+    ChainProfileError(#[from] ChainProfileError),
+
     /// Error from the compilation pipeline
     #[error(transparent)]
     CompileError(#[from] CompileError),
@@ -74,6 +103,32 @@ pub(crate) enum CliError {
     BackendCommunicationError(#[from] backend_interface::BackendError),
 }
 
+/// Errors encountered while resolving the chain a verifier contract is generated for.
+#[derive(Debug, Error)]
+pub(crate) enum ChainProfileError {
+    #[error("Unknown chain profile `{0}`. Pass one of `ethereum` or `ethereum-blobs`, or the path to a profile's TOML file")]
+    UnknownProfile(String),
+
+    #[error("Could not read chain profile {}: {1}", .0.display())]
+    UnreadableProfile(PathBuf, std::io::Error),
+
+    #[error("Invalid chain profile {}: {1}", .0.display())]
+    InvalidProfile(PathBuf, toml::de::Error),
+
+    #[error("The verifier requires precompiles {} which are not available on `{chain}`", format_precompiles(.missing))]
+    MissingPrecompiles { chain: String, missing: Vec<u8> },
+
+    #[error(
+        "The `{0}` backend can only generate verifiers which read public inputs from calldata"
+    )]
+    UnsupportedPublicInputs(String),
+}
+
+fn format_precompiles(precompiles: &[u8]) -> String {
+    let addresses: Vec<_> = precompiles.iter().map(|address| format!("{address:#04x}")).collect();
+    addresses.join(", ")
+}
+
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum BackendError {
     #[error("No backend is installed with the name {0}")]
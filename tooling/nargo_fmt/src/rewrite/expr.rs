@@ -44,6 +44,7 @@ pub(crate) fn rewrite(
         ExpressionKind::Cast(cast) => {
             format!("{} as {}", rewrite_sub_expr(visitor, shape, cast.lhs), cast.r#type)
         }
+        ExpressionKind::Try(expr) => format!("{}?", rewrite_sub_expr(visitor, shape, *expr)),
         kind @ ExpressionKind::Infix(_) => {
             super::infix(visitor.fork(), Expression { kind, span }, shape)
         }
@@ -118,6 +119,10 @@ pub(crate) fn rewrite(
         ExpressionKind::Parenthesized(sub_expr) => {
             super::parenthesized(visitor, shape, span, *sub_expr)
         }
+        // Struct update syntax is left as written
+        ExpressionKind::Constructor(constructor) if constructor.base.is_some() => {
+            visitor.slice(span).to_string()
+        }
         ExpressionKind::Constructor(constructor) => {
             let type_name = visitor.slice(span.start()..constructor.type_name.span().end());
             let fields_span = visitor
@@ -138,7 +143,11 @@ pub(crate) fn rewrite(
 
             visitor.format_if(*if_expr)
         }
-        ExpressionKind::Lambda(_) | ExpressionKind::Variable(_) => visitor.slice(span).to_string(),
+        ExpressionKind::Lambda(_)
+        | ExpressionKind::Match(_)
+        | ExpressionKind::Comptime(_)
+        | ExpressionKind::Unconstrained(_)
+        | ExpressionKind::Variable(_) => visitor.slice(span).to_string(),
         ExpressionKind::Error => unreachable!(),
     }
 }
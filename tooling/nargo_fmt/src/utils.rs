@@ -254,6 +254,8 @@ impl Item for Param {
         let visibility = match self.visibility {
             Visibility::Public => "pub ",
             Visibility::Private => "",
+            Visibility::CallData => "call_data ",
+            Visibility::ReturnData => "return_data ",
         };
         let pattern = visitor.slice(self.pattern.span());
         let ty = visitor.slice(self.typ.span.unwrap());
@@ -110,8 +110,10 @@ impl super::FmtVisitor<'_> {
                 result.push_str("distinct ");
             }
 
-            if let Visibility::Public = func.def.return_visibility {
-                result.push_str("pub ");
+            match func.def.return_visibility {
+                Visibility::Public => result.push_str("pub "),
+                Visibility::ReturnData => result.push_str("return_data "),
+                Visibility::Private | Visibility::CallData => (),
             }
 
             result.push_str(self.slice(span));
@@ -165,6 +167,11 @@ impl super::FmtVisitor<'_> {
                     let after_brace = self.span_after(span, Token::LeftBrace).start();
                     self.last_position = after_brace;
 
+                    for attribute in module.attributes {
+                        self.push_str(&attribute.to_string());
+                        self.push_str(&self.indent.to_string_with_newline());
+                    }
+
                     let keyword = if module.is_contract { "contract" } else { "mod" };
 
                     self.push_str(&format!("{keyword} {name} "));
@@ -183,6 +190,7 @@ impl super::FmtVisitor<'_> {
                 }
                 ItemKind::Import(_)
                 | ItemKind::Struct(_)
+                | ItemKind::Enum(_)
                 | ItemKind::Trait(_)
                 | ItemKind::TraitImpl(_)
                 | ItemKind::Impl(_)
@@ -33,8 +33,9 @@ impl super::FmtVisitor<'_> {
                     self.push_rewrite(format!("{let_str} {expr_str};"), span);
                 }
                 StatementKind::Constrain(ConstrainStatement(expr, message, kind)) => {
-                    let message =
-                        message.map_or(String::new(), |message| format!(", \"{message}\""));
+                    let message = message.map_or(String::new(), |message| {
+                        format!(", {}", rewrite::sub_expr(self, self.shape(), message))
+                    });
                     let constrain = match kind {
                         ConstrainKind::Assert => {
                             let assertion = rewrite::sub_expr(self, self.shape(), expr);
@@ -70,8 +71,36 @@ impl super::FmtVisitor<'_> {
                         ForRange::Array(array) => rewrite::sub_expr(self, self.shape(), array),
                     };
                     let block = rewrite::sub_expr(self, self.shape(), for_stmt.block);
+                    let attribute = self.max_iterations(for_stmt.max_iterations);
 
-                    let result = format!("for {identifier} in {range} {block}");
+                    let result = format!("{attribute}for {identifier} in {range} {block}");
+                    self.push_rewrite(result, span);
+                }
+                StatementKind::While(while_stmt) => {
+                    let condition = rewrite::sub_expr(self, self.shape(), while_stmt.condition);
+                    let block = rewrite::sub_expr(self, self.shape(), while_stmt.block);
+
+                    let attribute = self.max_iterations(while_stmt.max_iterations);
+
+                    let result = format!("{attribute}while {condition} {block}");
+                    self.push_rewrite(result, span);
+                }
+                StatementKind::Loop(loop_stmt) => {
+                    let block = rewrite::sub_expr(self, self.shape(), loop_stmt.block);
+                    let attribute = self.max_iterations(loop_stmt.max_iterations);
+
+                    let result = format!("{attribute}loop {block}");
+                    self.push_rewrite(result, span);
+                }
+                StatementKind::Break(_) => self.push_rewrite("break;".to_string(), span),
+                StatementKind::Continue(_) => self.push_rewrite("continue;".to_string(), span),
+                StatementKind::Return(return_stmt) => {
+                    let result = match return_stmt.expression {
+                        Some(expr) => {
+                            format!("return {};", rewrite::sub_expr(self, self.shape(), expr))
+                        }
+                        None => "return;".to_string(),
+                    };
                     self.push_rewrite(result, span);
                 }
                 StatementKind::Assign(_) => {
@@ -83,4 +112,15 @@ impl super::FmtVisitor<'_> {
             self.last_position = span.end();
         }
     }
+
+    /// Renders the `#[max_iterations(N)]` attribute of a loop on its own line, if there is one.
+    fn max_iterations(&self, max_iterations: Option<u64>) -> String {
+        match max_iterations {
+            Some(count) => format!(
+                "#[max_iterations({count})]{}",
+                self.shape().indent.to_string_with_newline()
+            ),
+            None => String::new(),
+        }
+    }
 }
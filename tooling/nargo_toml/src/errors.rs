@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use nargo::package::PackageType;
+use noirc_driver::SsaPipelineError;
 use noirc_frontend::graph::CrateName;
 use thiserror::Error;
 
@@ -28,6 +29,20 @@ pub enum ManifestError {
     #[error("Nargo.toml is badly formed, could not parse.\n\n {0}")]
     MalformedFile(#[from] toml::de::Error),
 
+    #[error("Cannot use `{1}` for `overflow` field in {0}, expected `trap` or `wrap`")]
+    InvalidOverflowMode(PathBuf, String),
+
+    #[error("Unknown lint `{1}` in the `lints` table of {0}")]
+    UnknownLint(PathBuf, String),
+
+    #[error(
+        "Cannot use `{2}` as the level of lint `{1}` in {0}, expected `allow`, `warn` or `deny`"
+    )]
+    InvalidLintLevel(PathBuf, String, String),
+
+    #[error("Invalid `passes` field in the `optimizer` table of {0}: {1}")]
+    InvalidPasses(PathBuf, SsaPipelineError),
+
     #[error("Unexpected workspace definition found in {0}")]
     UnexpectedWorkspace(PathBuf),
 
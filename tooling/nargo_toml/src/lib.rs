@@ -13,7 +13,9 @@ use nargo::{
     package::{Dependency, Package, PackageType},
     workspace::Workspace,
 };
-use noirc_frontend::graph::CrateName;
+use noirc_driver::SsaPipeline;
+use noirc_frontend::graph::{CrateName, OverflowMode};
+use noirc_frontend::lints::{Lint, LintLevel};
 use serde::Deserialize;
 
 mod errors;
@@ -23,6 +25,9 @@ mod semver;
 pub use errors::ManifestError;
 use git::clone_git_repo;
 
+/// The entry of the `[lints]` table which sets the level of every warning in a package.
+const WARNINGS_LINT_GROUP: &str = "warnings";
+
 /// Returns the [PathBuf] of the directory containing the `Nargo.toml` by searching from `current_path` to the root of its [Path].
 ///
 /// Returns a [ManifestError] if no parent directories of `current_path` contain a manifest file.
@@ -95,6 +100,12 @@ struct PackageConfig {
     package: PackageMetadata,
     #[serde(default)]
     dependencies: BTreeMap<String, DependencyConfig>,
+    // Maps lint names to the level they are reported at, e.g. `unused_variables = "allow"`.
+    // The `warnings` entry applies to every warning in the package.
+    #[serde(default)]
+    lints: BTreeMap<String, String>,
+    #[serde(default)]
+    optimizer: OptimizerConfig,
 }
 
 impl PackageConfig {
@@ -132,6 +143,28 @@ impl PackageConfig {
             None => return Err(ManifestError::MissingPackageType(root_dir.join("Nargo.toml"))),
         };
 
+        let overflow_mode = match self.package.overflow.as_deref() {
+            Some("trap") | None => OverflowMode::Trap,
+            Some("wrap") => OverflowMode::Wrap,
+            Some(invalid) => {
+                return Err(ManifestError::InvalidOverflowMode(
+                    root_dir.join("Nargo.toml"),
+                    invalid.to_string(),
+                ))
+            }
+        };
+
+        let (lint_levels, deny_warnings) =
+            resolve_lint_levels(&self.lints, &root_dir.join("Nargo.toml"))?;
+
+        let passes = self
+            .optimizer
+            .passes
+            .as_ref()
+            .map(|names| SsaPipeline::from_names(names))
+            .transpose()
+            .map_err(|error| ManifestError::InvalidPasses(root_dir.join("Nargo.toml"), error))?;
+
         let entry_path = if let Some(entry_path) = &self.package.entry {
             let custom_entry_path = root_dir.join(entry_path);
             if custom_entry_path.exists() {
@@ -170,10 +203,52 @@ impl PackageConfig {
             package_type,
             name,
             dependencies,
+            overflow_mode,
+            lint_levels,
+            deny_warnings,
+            passes,
+            witness_solver: self
+                .package
+                .witness_solver
+                .as_ref()
+                .map(|witness_solver| root_dir.join(witness_solver)),
         })
     }
 }
 
+/// Resolves the `[lints]` table of the manifest at `toml` into the level of each lint, and whether
+/// every warning in the package is denied.
+fn resolve_lint_levels(
+    lints: &BTreeMap<String, String>,
+    toml: &Path,
+) -> Result<(BTreeMap<Lint, LintLevel>, bool), ManifestError> {
+    let lookup_level = |name: &str, level: &str| {
+        LintLevel::lookup(level).ok_or_else(|| {
+            ManifestError::InvalidLintLevel(toml.to_path_buf(), name.to_string(), level.to_string())
+        })
+    };
+
+    // `warnings` is the level of every lint which isn't given its own level
+    let warnings_level = match lints.get(WARNINGS_LINT_GROUP) {
+        Some(level) => lookup_level(WARNINGS_LINT_GROUP, level)?,
+        None => LintLevel::default(),
+    };
+
+    let mut lint_levels = BTreeMap::new();
+    for (name, level) in lints.iter().filter(|(name, _)| *name != WARNINGS_LINT_GROUP) {
+        let lint = Lint::lookup(name)
+            .ok_or_else(|| ManifestError::UnknownLint(toml.to_path_buf(), name.to_string()))?;
+        lint_levels.insert(lint, lookup_level(name, level)?);
+    }
+    if warnings_level != LintLevel::default() {
+        for lint in Lint::ALL {
+            lint_levels.entry(lint).or_insert(warnings_level);
+        }
+    }
+
+    Ok((lint_levels, warnings_level == LintLevel::Deny))
+}
+
 /// Contains all the information about a package, as loaded from a `Nargo.toml`.
 #[derive(Debug, Deserialize, Clone)]
 #[serde(untagged)]
@@ -238,6 +313,18 @@ struct PackageMetadata {
     compiler_version: Option<String>,
     backend: Option<String>,
     license: Option<String>,
+    // Either "trap" (the default) or "wrap"
+    overflow: Option<String>,
+    // Path to a binary which solves witnesses instead of the ACVM, relative to the package root
+    witness_solver: Option<PathBuf>,
+}
+
+/// The `[optimizer]` table of a `Nargo.toml`.
+#[derive(Default, Debug, Deserialize, Clone)]
+struct OptimizerConfig {
+    /// The SSA passes to run after inlining, or the passes of the default pipeline to disable
+    /// if each is prefixed with `-`.
+    passes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -422,6 +509,40 @@ fn parse_standard_toml() {
     assert!(Config::try_from(src).is_ok());
 }
 
+#[test]
+fn parse_package_toml_with_lints() {
+    let src = r#"
+        [package]
+        name = "test"
+        type = "bin"
+
+        [lints]
+        unused_variables = "allow"
+        deprecated = "deny"
+    "#;
+
+    let Config::Package { package_config } = Config::try_from(src).unwrap() else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.lints["unused_variables"], "allow");
+    assert_eq!(package_config.lints["deprecated"], "deny");
+}
+
+#[test]
+fn warnings_lint_group_sets_default_level() {
+    let lints = BTreeMap::from([
+        ("warnings".to_string(), "deny".to_string()),
+        ("unused_variables".to_string(), "allow".to_string()),
+    ]);
+
+    let (lint_levels, deny_warnings) =
+        resolve_lint_levels(&lints, Path::new("Nargo.toml")).unwrap();
+
+    assert!(deny_warnings);
+    assert_eq!(lint_levels[&Lint::UnusedVariables], LintLevel::Allow);
+    assert_eq!(lint_levels[&Lint::Deprecated], LintLevel::Deny);
+}
+
 #[test]
 fn parse_package_toml_no_deps() {
     let src = r#"
@@ -68,7 +68,7 @@ mod tests {
     use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
 
     use nargo::package::PackageType;
-    use noirc_frontend::graph::CrateName;
+    use noirc_frontend::graph::{CrateName, OverflowMode};
 
     use super::*;
 
@@ -83,6 +83,11 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            overflow_mode: OverflowMode::default(),
+            lint_levels: BTreeMap::new(),
+            deny_warnings: false,
+            passes: None,
+            witness_solver: None,
         };
         if let Err(err) = semver_check_package(&package, &compiler_version) {
             panic!("semver check should have passed. compiler version is 0.1.0 and required version from the package is 0.1.0\n error: {err:?}")
@@ -113,6 +118,11 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            overflow_mode: OverflowMode::default(),
+            lint_levels: BTreeMap::new(),
+            deny_warnings: false,
+            passes: None,
+            witness_solver: None,
         };
 
         let valid_dependency = Package {
@@ -122,6 +132,11 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("good_dependency").unwrap(),
             dependencies: BTreeMap::new(),
+            overflow_mode: OverflowMode::default(),
+            lint_levels: BTreeMap::new(),
+            deny_warnings: false,
+            passes: None,
+            witness_solver: None,
         };
         let invalid_dependency = Package {
             compiler_required_version: Some("0.2.0".to_string()),
@@ -130,6 +145,11 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("bad_dependency").unwrap(),
             dependencies: BTreeMap::new(),
+            overflow_mode: OverflowMode::default(),
+            lint_levels: BTreeMap::new(),
+            deny_warnings: false,
+            passes: None,
+            witness_solver: None,
         };
 
         package.dependencies.insert(
@@ -169,6 +189,11 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            overflow_mode: OverflowMode::default(),
+            lint_levels: BTreeMap::new(),
+            deny_warnings: false,
+            passes: None,
+            witness_solver: None,
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
@@ -187,6 +212,11 @@ mod tests {
             entry_path: PathBuf::new(),
             name: CrateName::from_str("test").unwrap(),
             dependencies: BTreeMap::new(),
+            overflow_mode: OverflowMode::default(),
+            lint_levels: BTreeMap::new(),
+            deny_warnings: false,
+            passes: None,
+            witness_solver: None,
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
@@ -1,5 +1,5 @@
 use crate::{input_parser::InputValue, AbiParameter, AbiType};
-use acvm::acir::native_types::Witness;
+use acvm::{acir::native_types::Witness, FieldElement};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -14,6 +14,8 @@ pub enum InputParserError {
     AbiTypeMismatch(AbiType),
     #[error("Expected argument `{0}`, but none was found")]
     MissingArgument(String),
+    #[error("Enum {path} has no variant named `{variant}`")]
+    UnknownEnumVariant { path: String, variant: String },
 }
 
 impl From<toml::ser::Error> for InputParserError {
@@ -36,6 +38,8 @@ impl From<serde_json::Error> for InputParserError {
 
 #[derive(Debug, Error)]
 pub enum AbiError {
+    #[error(transparent)]
+    InputParserError(#[from] InputParserError),
     #[error("Received parameters not expected by ABI: {0:?}")]
     UnexpectedParams(Vec<String>),
     #[error("The parameter {} is expected to be a {:?} but found incompatible value {value:?}", .param.name, .param.typ)]
@@ -52,4 +56,8 @@ pub enum AbiError {
     ReturnTypeMismatch { return_type: AbiType, value: InputValue },
     #[error("No return value is expected but received {0:?}")]
     UnexpectedReturnValue(InputValue),
+    #[error("Oracle `{name}` expects {expected} input fields but received {found}")]
+    OracleInputLengthMismatch { name: String, expected: usize, found: usize },
+    #[error("Enum {path} has no variant with the tag {tag}")]
+    InvalidEnumTag { path: String, tag: FieldElement },
 }
@@ -1,4 +1,6 @@
-use super::{parse_str_to_field, InputValue};
+use super::{
+    format_negative_integer, parse_str_to_field, parse_str_to_signed, signed_to_field, InputValue,
+};
 use crate::{errors::InputParserError, Abi, AbiType, MAIN_RETURN_NAME};
 use acvm::FieldElement;
 use iter_extended::{try_btree_map, try_vecmap};
@@ -69,6 +71,8 @@ pub enum JsonTypes {
     // however we restrict the allowable size. Values which do not fit in a u64 should be passed
     // as a string.
     Integer(u64),
+    // A negative integer, which is only valid for signed integer types
+    NegativeInteger(i64),
     // Simple boolean flag
     Bool(bool),
     // Array of JsonTypes
@@ -84,7 +88,9 @@ impl JsonTypes {
     ) -> Result<JsonTypes, InputParserError> {
         let json_value = match (value, abi_type) {
             (InputValue::Field(f), AbiType::Field | AbiType::Integer { .. }) => {
-                JsonTypes::String(Self::format_field_string(*f))
+                let f_str = format_negative_integer(*f, abi_type)
+                    .unwrap_or_else(|| Self::format_field_string(*f));
+                JsonTypes::String(f_str)
             }
             (InputValue::Field(f), AbiType::Boolean) => JsonTypes::Bool(f.is_one()),
 
@@ -104,6 +110,22 @@ impl JsonTypes {
                 JsonTypes::Table(map_with_json_types)
             }
 
+            (InputValue::Variant(name, values), AbiType::Enum { variants, .. }) => {
+                let (_, field_types) = variants
+                    .iter()
+                    .find(|(variant, _)| variant == name)
+                    .ok_or_else(|| InputParserError::AbiTypeMismatch(abi_type.clone()))?;
+
+                let fields = try_vecmap(values.iter().zip(field_types), |(value, typ)| {
+                    JsonTypes::try_from_input_value(value, typ)
+                })?;
+
+                JsonTypes::Table(BTreeMap::from([
+                    ("variant".to_owned(), JsonTypes::String(name.to_owned())),
+                    ("fields".to_owned(), JsonTypes::Array(fields)),
+                ]))
+            }
+
             (InputValue::Vec(vector), AbiType::Tuple { fields }) => {
                 let fields = try_vecmap(vector.iter().zip(fields), |(value, typ)| {
                     JsonTypes::try_from_input_value(value, typ)
@@ -142,8 +164,13 @@ impl InputValue {
             (JsonTypes::String(string), AbiType::String { .. }) => InputValue::String(string),
             (
                 JsonTypes::String(string),
-                AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean,
+                AbiType::Field
+                | AbiType::Integer { sign: crate::Sign::Unsigned, .. }
+                | AbiType::Boolean,
             ) => InputValue::Field(parse_str_to_field(&string)?),
+            (JsonTypes::String(string), AbiType::Integer { sign: crate::Sign::Signed, width }) => {
+                InputValue::Field(parse_str_to_signed(&string, *width)?)
+            }
 
             (
                 JsonTypes::Integer(integer),
@@ -154,6 +181,11 @@ impl InputValue {
                 InputValue::Field(new_value)
             }
 
+            (
+                JsonTypes::NegativeInteger(integer),
+                AbiType::Integer { sign: crate::Sign::Signed, width },
+            ) => InputValue::Field(signed_to_field(integer.into(), *width)?),
+
             (JsonTypes::Bool(boolean), AbiType::Boolean) => InputValue::Field(boolean.into()),
 
             (JsonTypes::Array(array), AbiType::Array { typ, .. }) => {
@@ -176,6 +208,38 @@ impl InputValue {
                 InputValue::Struct(native_table)
             }
 
+            // Enums are given as a table of the variant's name and its fields,
+            // e.g. `{ variant = "Some", fields = [1] }`. Fields may be omitted for fieldless variants.
+            (JsonTypes::Table(table), AbiType::Enum { variants, .. }) => {
+                let variant_id = format!("{arg_name}.variant");
+                let name = match table.get("variant") {
+                    Some(JsonTypes::String(name)) => name,
+                    Some(_) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
+                    None => return Err(InputParserError::MissingArgument(variant_id)),
+                };
+
+                let (_, field_types) = variants
+                    .iter()
+                    .find(|(variant, _)| variant == name)
+                    .ok_or_else(|| InputParserError::AbiTypeMismatch(param_type.clone()))?;
+
+                let fields = match table.get("fields") {
+                    Some(JsonTypes::Array(fields)) => fields.clone(),
+                    None => Vec::new(),
+                    Some(_) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
+                };
+
+                if fields.len() != field_types.len() {
+                    return Err(InputParserError::AbiTypeMismatch(param_type.clone()));
+                }
+
+                let field_id = format!("{arg_name}.fields");
+                let fields = try_vecmap(fields.into_iter().zip(field_types), |(value, typ)| {
+                    InputValue::try_from_json(value, typ, &field_id)
+                })?;
+                InputValue::Variant(name.to_owned(), fields)
+            }
+
             (JsonTypes::Array(array), AbiType::Tuple { fields }) => {
                 let tuple_fields = try_vecmap(array.into_iter().zip(fields), |(value, typ)| {
                     InputValue::try_from_json(value, typ, arg_name)
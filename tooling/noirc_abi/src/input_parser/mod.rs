@@ -6,7 +6,7 @@ use acvm::FieldElement;
 use serde::Serialize;
 
 use crate::errors::InputParserError;
-use crate::{Abi, AbiType};
+use crate::{Abi, AbiType, Sign};
 
 pub mod json;
 mod toml;
@@ -20,6 +20,8 @@ pub enum InputValue {
     String(String),
     Vec(Vec<InputValue>),
     Struct(BTreeMap<String, InputValue>),
+    /// A value of an enum, holding the name of its variant and the variant's fields
+    Variant(String, Vec<InputValue>),
 }
 
 impl InputValue {
@@ -64,6 +66,16 @@ impl InputValue {
                 })
             }
 
+            (InputValue::Variant(name, values), AbiType::Enum { variants, .. }) => {
+                let Some((_, field_types)) = variants.iter().find(|(variant, _)| variant == name)
+                else {
+                    return false;
+                };
+
+                values.len() == field_types.len()
+                    && values.iter().zip(field_types).all(|(value, typ)| value.matches_abi(typ))
+            }
+
             (InputValue::Vec(vec_elements), AbiType::Tuple { fields }) => {
                 if vec_elements.len() != fields.len() {
                     return false;
@@ -83,6 +95,7 @@ impl InputValue {
 
 /// The different formats that are supported when parsing
 /// the initial witness values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(test, derive(strum_macros::EnumIter))]
 pub enum Format {
     Json,
@@ -98,6 +111,18 @@ impl Format {
     }
 }
 
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "json" => Ok(Format::Json),
+            "toml" => Ok(Format::Toml),
+            _ => Err(format!("Unknown format `{format}`. Expected `json` or `toml`")),
+        }
+    }
+}
+
 impl Format {
     pub fn parse(
         &self,
@@ -158,11 +183,45 @@ mod serialization_tests {
                     },
                     visibility: AbiVisibility::Private,
                 },
+                AbiParameter {
+                    name: "baz".into(),
+                    typ: AbiType::Enum {
+                        path: "MyEnum".into(),
+                        variants: vec![
+                            ("Empty".into(), vec![]),
+                            ("Pair".into(), vec![AbiType::Field, AbiType::Boolean]),
+                        ],
+                    },
+                    visibility: AbiVisibility::Private,
+                },
+                AbiParameter {
+                    name: "qux".into(),
+                    typ: AbiType::Integer { sign: Sign::Signed, width: 8 },
+                    visibility: AbiVisibility::Private,
+                },
+                AbiParameter {
+                    name: "quux".into(),
+                    typ: AbiType::Tuple {
+                        fields: vec![
+                            AbiType::Field,
+                            AbiType::Struct {
+                                path: "Point".into(),
+                                fields: vec![
+                                    ("x".into(), AbiType::Field),
+                                    ("y".into(), AbiType::Field),
+                                ],
+                            },
+                        ],
+                    },
+                    visibility: AbiVisibility::Private,
+                },
             ],
             return_type: Some(AbiType::String { length: 5 }),
+            return_visibility: AbiVisibility::Public,
             // These two fields are unused when serializing/deserializing to file.
             param_witnesses: BTreeMap::new(),
             return_witnesses: Vec::new(),
+            oracles: Vec::new(),
         };
 
         let input_map: BTreeMap<String, InputValue> = BTreeMap::from([
@@ -180,6 +239,25 @@ mod serialization_tests {
                     ),
                 ])),
             ),
+            (
+                "baz".into(),
+                InputValue::Variant(
+                    "Pair".into(),
+                    vec![InputValue::Field(7u128.into()), InputValue::Field(true.into())],
+                ),
+            ),
+            // -5 in two's complement
+            ("qux".into(), InputValue::Field(251u128.into())),
+            (
+                "quux".into(),
+                InputValue::Vec(vec![
+                    InputValue::Field(3u128.into()),
+                    InputValue::Struct(BTreeMap::from([
+                        ("x".into(), InputValue::Field(4u128.into())),
+                        ("y".into(), InputValue::Field(5u128.into())),
+                    ])),
+                ]),
+            ),
             (MAIN_RETURN_NAME.into(), InputValue::String("hello".to_owned())),
         ]);
 
@@ -211,29 +289,53 @@ fn parse_str_to_field(value: &str) -> Result<FieldElement, InputParserError> {
     })
 }
 
-fn parse_str_to_signed(value: &str, witdh: u32) -> Result<FieldElement, InputParserError> {
+fn parse_str_to_signed(value: &str, width: u32) -> Result<FieldElement, InputParserError> {
     let big_num = if let Some(hex) = value.strip_prefix("0x") {
         BigInt::from_str_radix(hex, 16)
     } else {
         BigInt::from_str_radix(value, 10)
     };
 
-    big_num.map_err(|err_msg| InputParserError::ParseStr(err_msg.to_string())).and_then(|bigint| {
-        let modulus: BigInt = FieldElement::modulus().into();
-        let bigint = if bigint.sign() == num_bigint::Sign::Minus {
-            BigInt::from(2).pow(witdh) + bigint
-        } else {
-            bigint
-        };
-        if bigint.is_zero() || (bigint.sign() == num_bigint::Sign::Plus && bigint < modulus) {
-            Ok(field_from_big_int(bigint))
-        } else {
-            Err(InputParserError::ParseStr(format!(
-                "Input exceeds field modulus. Values must fall within [0, {})",
-                FieldElement::modulus(),
-            )))
+    big_num
+        .map_err(|err_msg| InputParserError::ParseStr(err_msg.to_string()))
+        .and_then(|bigint| signed_to_field(bigint, width))
+}
+
+/// Encodes a signed integer as a field element, representing negative values
+/// by their two's complement
+fn signed_to_field(value: BigInt, width: u32) -> Result<FieldElement, InputParserError> {
+    let modulus: BigInt = FieldElement::modulus().into();
+    let value = if value.sign() == num_bigint::Sign::Minus {
+        let min = -BigInt::from(2).pow(width - 1);
+        if value < min {
+            return Err(InputParserError::ParseStr(format!(
+                "Input is below the minimum value of a {width}-bit signed integer, {min}"
+            )));
         }
-    })
+        BigInt::from(2).pow(width) + value
+    } else {
+        value
+    };
+    if value.is_zero() || (value.sign() == num_bigint::Sign::Plus && value < modulus) {
+        Ok(field_from_big_int(value))
+    } else {
+        Err(InputParserError::ParseStr(format!(
+            "Input exceeds field modulus. Values must fall within [0, {})",
+            FieldElement::modulus(),
+        )))
+    }
+}
+
+/// Formats a signed integer whose two's complement encoding is negative as a negative decimal,
+/// so that it can be read back by [`parse_str_to_signed`]. Returns `None` for any other value.
+fn format_negative_integer(field: FieldElement, abi_type: &AbiType) -> Option<String> {
+    let AbiType::Integer { sign: Sign::Signed, width } = abi_type else {
+        return None;
+    };
+    let value = BigUint::from_bytes_be(&field.to_be_bytes());
+    let modulus = BigUint::from(2u32).pow(*width);
+    let is_negative = value >= BigUint::from(2u32).pow(width - 1) && value < modulus;
+    is_negative.then(|| format!("-{}", modulus - value))
 }
 
 fn field_from_big_uint(bigint: BigUint) -> FieldElement {
@@ -257,7 +359,7 @@ mod test {
     use acvm::FieldElement;
     use num_bigint::BigUint;
 
-    use super::parse_str_to_field;
+    use super::{parse_str_to_field, parse_str_to_signed};
 
     fn big_uint_from_field(field: FieldElement) -> BigUint {
         BigUint::from_bytes_be(&field.to_be_bytes())
@@ -290,6 +392,15 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_signed_integers_from_strings() {
+        assert_eq!(parse_str_to_signed("127", 8).unwrap(), FieldElement::from(127u128));
+        assert_eq!(parse_str_to_signed("-1", 8).unwrap(), FieldElement::from(255u128));
+        assert_eq!(parse_str_to_signed("-128", 8).unwrap(), FieldElement::from(128u128));
+        assert_eq!(parse_str_to_signed("0xff", 8).unwrap(), FieldElement::from(255u128));
+        assert!(parse_str_to_signed("-129", 8).is_err());
+    }
+
     #[test]
     fn rejects_noncanonical_fields() {
         let noncanonical_field = FieldElement::modulus().to_string();
@@ -1,4 +1,6 @@
-use super::{parse_str_to_field, parse_str_to_signed, InputValue};
+use super::{
+    format_negative_integer, parse_str_to_field, parse_str_to_signed, signed_to_field, InputValue,
+};
 use crate::{errors::InputParserError, Abi, AbiType, MAIN_RETURN_NAME};
 use acvm::FieldElement;
 use iter_extended::{try_btree_map, try_vecmap};
@@ -65,7 +67,7 @@ enum TomlTypes {
     String(String),
     // Just a regular integer, that can fit in 64 bits
     // Note that the toml spec specifies that all numbers are represented as `i64`s.
-    Integer(u64),
+    Integer(i64),
     // Simple boolean flag
     Bool(bool),
     // Array of TomlTypes
@@ -81,7 +83,8 @@ impl TomlTypes {
     ) -> Result<TomlTypes, InputParserError> {
         let toml_value = match (value, abi_type) {
             (InputValue::Field(f), AbiType::Field | AbiType::Integer { .. }) => {
-                let f_str = format!("0x{}", f.to_hex());
+                let f_str = format_negative_integer(*f, abi_type)
+                    .unwrap_or_else(|| format!("0x{}", f.to_hex()));
                 TomlTypes::String(f_str)
             }
             (InputValue::Field(f), AbiType::Boolean) => TomlTypes::Bool(f.is_one()),
@@ -102,6 +105,22 @@ impl TomlTypes {
                 TomlTypes::Table(map_with_toml_types)
             }
 
+            (InputValue::Variant(name, values), AbiType::Enum { variants, .. }) => {
+                let (_, field_types) = variants
+                    .iter()
+                    .find(|(variant, _)| variant == name)
+                    .ok_or_else(|| InputParserError::AbiTypeMismatch(abi_type.clone()))?;
+
+                let fields = try_vecmap(values.iter().zip(field_types), |(value, typ)| {
+                    TomlTypes::try_from_input_value(value, typ)
+                })?;
+
+                TomlTypes::Table(BTreeMap::from([
+                    ("variant".to_owned(), TomlTypes::String(name.to_owned())),
+                    ("fields".to_owned(), TomlTypes::Array(fields)),
+                ]))
+            }
+
             (InputValue::Vec(vector), AbiType::Tuple { fields }) => {
                 let fields = try_vecmap(vector.iter().zip(fields), |(value, typ)| {
                     TomlTypes::try_from_input_value(value, typ)
@@ -132,6 +151,10 @@ impl InputValue {
             (TomlTypes::String(string), AbiType::Integer { sign: crate::Sign::Signed, width }) => {
                 InputValue::Field(parse_str_to_signed(&string, *width)?)
             }
+            (
+                TomlTypes::Integer(integer),
+                AbiType::Integer { sign: crate::Sign::Signed, width },
+            ) => InputValue::Field(signed_to_field(integer.into(), *width)?),
             (
                 TomlTypes::Integer(integer),
                 AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean,
@@ -163,6 +186,38 @@ impl InputValue {
                 InputValue::Struct(native_table)
             }
 
+            // Enums are given as a table of the variant's name and its fields,
+            // e.g. `{ variant = "Some", fields = [1] }`. Fields may be omitted for fieldless variants.
+            (TomlTypes::Table(table), AbiType::Enum { variants, .. }) => {
+                let variant_id = format!("{arg_name}.variant");
+                let name = match table.get("variant") {
+                    Some(TomlTypes::String(name)) => name,
+                    Some(_) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
+                    None => return Err(InputParserError::MissingArgument(variant_id)),
+                };
+
+                let (_, field_types) = variants
+                    .iter()
+                    .find(|(variant, _)| variant == name)
+                    .ok_or_else(|| InputParserError::AbiTypeMismatch(param_type.clone()))?;
+
+                let fields = match table.get("fields") {
+                    Some(TomlTypes::Array(fields)) => fields.clone(),
+                    None => Vec::new(),
+                    Some(_) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
+                };
+
+                if fields.len() != field_types.len() {
+                    return Err(InputParserError::AbiTypeMismatch(param_type.clone()));
+                }
+
+                let field_id = format!("{arg_name}.fields");
+                let fields = try_vecmap(fields.into_iter().zip(field_types), |(value, typ)| {
+                    InputValue::try_from_toml(value, typ, &field_id)
+                })?;
+                InputValue::Variant(name.to_owned(), fields)
+            }
+
             (TomlTypes::Array(array), AbiType::Tuple { fields }) => {
                 let tuple_fields = try_vecmap(array.into_iter().zip(fields), |(value, typ)| {
                     InputValue::try_from_toml(value, typ, arg_name)
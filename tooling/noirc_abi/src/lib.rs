@@ -7,7 +7,7 @@ use acvm::{
     acir::native_types::{Witness, WitnessMap},
     FieldElement,
 };
-use errors::AbiError;
+use errors::{AbiError, InputParserError};
 use input_parser::InputValue;
 use iter_extended::{try_btree_map, try_vecmap, vecmap};
 use noirc_frontend::{
@@ -24,12 +24,19 @@ use std::{collections::BTreeMap, str};
 pub mod errors;
 pub mod input_parser;
 mod serialization;
+pub mod witness;
 
 /// A map from the fields in an TOML/JSON file which correspond to some ABI to their values
 pub type InputMap = BTreeMap<String, InputValue>;
 
 pub const MAIN_RETURN_NAME: &str = "return";
 
+/// The version of the JSON schema an [`Abi`] is serialized with.
+///
+/// This must be incremented whenever the serialized shape of [`Abi`] or [`AbiType`] changes so that
+/// consumers of program artifacts, such as generated TypeScript bindings, can detect it.
+pub const ABI_VERSION: u32 = 1;
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "lowercase")]
 /// Types that are allowed in the (main function in binary)
@@ -62,6 +69,16 @@ pub enum AbiType {
         )]
         fields: Vec<(String, AbiType)>,
     },
+    /// Enums are encoded as the index of the active variant followed by the fields of
+    /// every variant. The fields of inactive variants are zeroed.
+    Enum {
+        path: String,
+        #[serde(
+            serialize_with = "serialization::serialize_enum_variants",
+            deserialize_with = "serialization::deserialize_enum_variants"
+        )]
+        variants: Vec<(String, Vec<AbiType>)>,
+    },
     Tuple {
         fields: Vec<AbiType>,
     },
@@ -78,6 +95,19 @@ pub enum AbiVisibility {
     // Constants are not allowed in the ABI for main at the moment.
     // Constant,
     Private,
+    /// A public input read from the call data of the databus.
+    #[serde(rename = "call_data")]
+    CallData,
+    /// A public output written to the return data of the databus.
+    #[serde(rename = "return_data")]
+    ReturnData,
+}
+
+impl AbiVisibility {
+    /// The visibility of a return value which isn't placed on the databus.
+    fn public() -> AbiVisibility {
+        AbiVisibility::Public
+    }
 }
 
 impl From<Visibility> for AbiVisibility {
@@ -85,6 +115,8 @@ impl From<Visibility> for AbiVisibility {
         match value {
             Visibility::Public => AbiVisibility::Public,
             Visibility::Private => AbiVisibility::Private,
+            Visibility::CallData => AbiVisibility::CallData,
+            Visibility::ReturnData => AbiVisibility::ReturnData,
         }
     }
 }
@@ -94,6 +126,8 @@ impl From<&Visibility> for AbiVisibility {
         match value {
             Visibility::Public => AbiVisibility::Public,
             Visibility::Private => AbiVisibility::Private,
+            Visibility::CallData => AbiVisibility::CallData,
+            Visibility::ReturnData => AbiVisibility::ReturnData,
         }
     }
 }
@@ -159,6 +193,16 @@ impl AbiType {
             Type::Unit => unreachable!(),
             Type::Constant(_) => unreachable!(),
             Type::TraitAsType(_) => unreachable!(),
+            Type::Struct(def, ref args) if def.borrow().is_enum() => {
+                let enum_type = def.borrow();
+                let variants = vecmap(enum_type.get_variants(args), |(name, fields)| {
+                    (name, vecmap(fields, |typ| Self::from_type(context, &typ)))
+                });
+                // For the ABI, we always want to resolve the enum paths from the root crate
+                let path =
+                    context.fully_qualified_struct_path(context.root_crate_id(), enum_type.id);
+                Self::Enum { variants, path }
+            }
             Type::Struct(def, ref args) => {
                 let struct_type = def.borrow();
                 let fields = struct_type.get_fields(args);
@@ -189,6 +233,10 @@ impl AbiType {
             AbiType::Struct { fields, .. } => {
                 fields.iter().fold(0, |acc, (_, field_type)| acc + field_type.field_count())
             }
+            AbiType::Enum { variants, .. } => {
+                let payloads = variants.iter().flat_map(|(_, fields)| fields);
+                payloads.fold(1, |acc, field_typ| acc + field_typ.field_count())
+            }
             AbiType::Tuple { fields } => {
                 fields.iter().fold(0, |acc, field_typ| acc + field_typ.field_count())
             }
@@ -208,7 +256,61 @@ pub struct AbiParameter {
 
 impl AbiParameter {
     pub fn is_public(&self) -> bool {
-        self.visibility == AbiVisibility::Public
+        self.visibility != AbiVisibility::Private
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// An argument to an `#[oracle]` function called by the program.
+pub struct AbiOracleParameter {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: AbiType,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The signature of an `#[oracle]` function called by the program.
+///
+/// Foreign call resolvers can use this to validate the payload of a foreign call with this name.
+pub struct AbiOracle {
+    /// The name of the foreign call, as given in the `#[oracle(name)]` attribute.
+    pub name: String,
+    pub parameters: Vec<AbiOracleParameter>,
+    pub return_type: Option<AbiType>,
+}
+
+impl AbiOracle {
+    /// Returns the number of field elements required to represent the oracle's inputs once encoded.
+    pub fn field_count(&self) -> u32 {
+        self.parameters.iter().map(|param| param.typ.field_count()).sum()
+    }
+
+    /// Decodes the field elements passed to the oracle into a value for each of its parameters.
+    pub fn decode_inputs(&self, inputs: &[FieldElement]) -> Result<Vec<InputValue>, AbiError> {
+        let expected = self.field_count() as usize;
+        if inputs.len() != expected {
+            return Err(AbiError::OracleInputLengthMismatch {
+                name: self.name.clone(),
+                expected,
+                found: inputs.len(),
+            });
+        }
+
+        let mut field_iterator = inputs.iter().copied();
+        try_vecmap(&self.parameters, |param| decode_value(&mut field_iterator, &param.typ))
+    }
+
+    /// Encodes a value returned by the oracle into field elements.
+    pub fn encode_return_value(&self, value: InputValue) -> Result<Vec<FieldElement>, AbiError> {
+        match &self.return_type {
+            Some(return_type) if value.matches_abi(return_type) => {
+                Abi::encode_value(value, return_type)
+            }
+            Some(return_type) => {
+                Err(AbiError::ReturnTypeMismatch { return_type: return_type.clone(), value })
+            }
+            None => Err(AbiError::UnexpectedReturnValue(value)),
+        }
     }
 }
 
@@ -220,7 +322,13 @@ pub struct Abi {
     /// This defines how to convert between the [`InputMap`] and [`WitnessMap`].
     pub param_witnesses: BTreeMap<String, Vec<Range<Witness>>>,
     pub return_type: Option<AbiType>,
+    /// Whether the return value is a regular public output or is written to the return data of the databus.
+    #[serde(default = "AbiVisibility::public")]
+    pub return_visibility: AbiVisibility,
     pub return_witnesses: Vec<Witness>,
+    /// The signatures of the oracles the program may call while being executed.
+    #[serde(default)]
+    pub oracles: Vec<AbiOracle>,
 }
 
 impl Abi {
@@ -247,6 +355,11 @@ impl Abi {
         self.return_type.is_none() && self.parameters.is_empty()
     }
 
+    /// Returns the signature of the oracle with the given foreign call name, if the program calls it.
+    pub fn oracle(&self, name: &str) -> Option<&AbiOracle> {
+        self.oracles.iter().find(|oracle| oracle.name == name)
+    }
+
     pub fn to_btree_map(&self) -> BTreeMap<String, AbiType> {
         let mut map = BTreeMap::new();
         for param in self.parameters.iter() {
@@ -269,8 +382,35 @@ impl Abi {
             parameters,
             param_witnesses,
             return_type: self.return_type,
+            return_visibility: self.return_visibility,
             return_witnesses: self.return_witnesses,
+            oracles: self.oracles,
+        }
+    }
+
+    /// Returns the name of the input or return value held by each witness of the ABI, such as `x`,
+    /// `arr[2]`, `point.x` or `pair.0`.
+    ///
+    /// The return value is named `return`, unless its witnesses are also inputs.
+    pub fn witness_names(&self) -> BTreeMap<Witness, String> {
+        let mut witness_names = BTreeMap::new();
+        for param in &self.parameters {
+            let Some(ranges) = self.param_witnesses.get(&param.name) else {
+                continue;
+            };
+            let mut names = Vec::new();
+            value_names(&param.typ, param.name.clone(), &mut names);
+            witness_names.extend(range_to_vec(ranges).into_iter().zip(names));
         }
+
+        if let Some(return_type) = &self.return_type {
+            let mut names = Vec::new();
+            value_names(return_type, "return".to_owned(), &mut names);
+            for (witness, name) in self.return_witnesses.iter().zip(names) {
+                witness_names.entry(*witness).or_insert(name);
+            }
+        }
+        witness_names
     }
 
     /// Encode a set of inputs as described in the ABI into a `WitnessMap`.
@@ -381,6 +521,27 @@ impl Abi {
                     encoded_value.extend(Self::encode_value(object[field].clone(), typ)?);
                 }
             }
+            (InputValue::Variant(name, mut values), AbiType::Enum { path, variants }) => {
+                let tag =
+                    variants.iter().position(|(variant, _)| *variant == name).ok_or_else(|| {
+                        InputParserError::UnknownEnumVariant { path: path.clone(), variant: name }
+                    })?;
+                encoded_value.push((tag as u128).into());
+
+                // Every variant's fields are encoded so that the encoding has a fixed size,
+                // with only those of the active variant holding a value.
+                for (index, (_, field_types)) in variants.iter().enumerate() {
+                    if index == tag {
+                        for (value, typ) in std::mem::take(&mut values).into_iter().zip(field_types)
+                        {
+                            encoded_value.extend(Self::encode_value(value, typ)?);
+                        }
+                    } else {
+                        let field_count: u32 = field_types.iter().map(AbiType::field_count).sum();
+                        encoded_value.extend(vec![FieldElement::zero(); field_count as usize]);
+                    }
+                }
+            }
             (InputValue::Vec(vec_elements), AbiType::Tuple { fields }) => {
                 for (value, typ) in vec_elements.into_iter().zip(fields) {
                     encoded_value.extend(Self::encode_value(value, typ)?);
@@ -478,6 +639,25 @@ fn decode_value(
 
             InputValue::Struct(struct_map)
         }
+        AbiType::Enum { path, variants } => {
+            let tag = field_iterator.next().unwrap();
+            let index = tag.try_to_u64().map(|index| index as usize);
+            let index = index.filter(|index| *index < variants.len());
+            let index =
+                index.ok_or_else(|| AbiError::InvalidEnumTag { path: path.clone(), tag })?;
+
+            // The fields of every variant are always encoded so each must be consumed,
+            // but only those of the active variant are kept.
+            let mut active_fields = Vec::new();
+            for (variant, (_, field_types)) in variants.iter().enumerate() {
+                let fields = try_vecmap(field_types, |typ| decode_value(field_iterator, typ))?;
+                if variant == index {
+                    active_fields = fields;
+                }
+            }
+
+            InputValue::Variant(variants[index].0.clone(), active_fields)
+        }
         AbiType::Tuple { fields } => {
             let mut tuple_elements = Vec::with_capacity(fields.len());
             for field_typ in fields {
@@ -530,6 +710,40 @@ impl ContractEvent {
     }
 }
 
+/// Pushes the name of each field element of a value of type `typ` named `name`, in the order they
+/// are encoded.
+fn value_names(typ: &AbiType, name: String, names: &mut Vec<String>) {
+    match typ {
+        AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => names.push(name),
+        AbiType::Array { length, typ } => {
+            for index in 0..*length {
+                value_names(typ, format!("{name}[{index}]"), names);
+            }
+        }
+        AbiType::String { length } => {
+            names.extend((0..*length).map(|index| format!("{name}[{index}]")));
+        }
+        AbiType::Struct { fields, .. } => {
+            for (field_name, field_type) in fields {
+                value_names(field_type, format!("{name}.{field_name}"), names);
+            }
+        }
+        AbiType::Tuple { fields } => {
+            for (index, field_type) in fields.iter().enumerate() {
+                value_names(field_type, format!("{name}.{index}"), names);
+            }
+        }
+        AbiType::Enum { variants, .. } => {
+            names.push(format!("{name}.tag"));
+            for (variant, field_types) in variants {
+                for (index, field_type) in field_types.iter().enumerate() {
+                    value_names(field_type, format!("{name}.{variant}.{index}"), names);
+                }
+            }
+        }
+    }
+}
+
 fn range_to_vec(ranges: &[Range<Witness>]) -> Vec<Witness> {
     let mut result = Vec::new();
     for range in ranges {
@@ -569,7 +783,9 @@ mod test {
                 ("thing2".to_string(), vec![(Witness(3)..Witness(4))]),
             ]),
             return_type: Some(AbiType::Field),
+            return_visibility: AbiVisibility::Public,
             return_witnesses: vec![Witness(3)],
+            oracles: vec![],
         };
 
         // Note we omit return value from inputs
@@ -594,4 +810,83 @@ mod test {
         // We also decode the return value (we can do this immediately as we know it shares a witness with an input).
         assert_eq!(return_value.unwrap(), reconstructed_inputs["thing2"]);
     }
+
+    #[test]
+    fn enum_encoding_pads_inactive_variants() {
+        let abi = Abi {
+            parameters: vec![AbiParameter {
+                name: "thing".to_string(),
+                typ: AbiType::Enum {
+                    path: "MyEnum".to_string(),
+                    variants: vec![
+                        ("A".to_string(), vec![AbiType::Field, AbiType::Field]),
+                        ("B".to_string(), vec![AbiType::Field]),
+                    ],
+                },
+                visibility: AbiVisibility::Private,
+            }],
+            param_witnesses: BTreeMap::from([(
+                "thing".to_string(),
+                vec![(Witness(1)..Witness(5))],
+            )]),
+            return_type: None,
+            return_visibility: AbiVisibility::Public,
+            return_witnesses: vec![],
+            oracles: vec![],
+        };
+
+        let value = InputValue::Variant("B".to_string(), vec![InputValue::Field(5u128.into())]);
+        let inputs: InputMap = BTreeMap::from([("thing".to_string(), value.clone())]);
+
+        let witness_map = abi.encode(&inputs, None).unwrap();
+        let encoded: Vec<FieldElement> =
+            (1..5).map(|index| *witness_map.get(&Witness(index)).unwrap()).collect();
+        let expected: Vec<FieldElement> =
+            vec![1u128.into(), 0u128.into(), 0u128.into(), 5u128.into()];
+        assert_eq!(encoded, expected);
+
+        let (reconstructed_inputs, _) = abi.decode(&witness_map).unwrap();
+        assert_eq!(reconstructed_inputs["thing"], value);
+    }
+
+    #[test]
+    fn witness_names_follow_encoding_order() {
+        let abi = Abi {
+            parameters: vec![
+                AbiParameter {
+                    name: "point".to_string(),
+                    typ: AbiType::Struct {
+                        path: "Point".to_string(),
+                        fields: vec![
+                            ("x".to_string(), AbiType::Field),
+                            ("y".to_string(), AbiType::Field),
+                        ],
+                    },
+                    visibility: AbiVisibility::Private,
+                },
+                AbiParameter {
+                    name: "arr".to_string(),
+                    typ: AbiType::Array { length: 2, typ: Box::new(AbiType::Boolean) },
+                    visibility: AbiVisibility::Public,
+                },
+            ],
+            param_witnesses: BTreeMap::from([
+                ("point".to_string(), vec![(Witness(1)..Witness(3))]),
+                ("arr".to_string(), vec![(Witness(3)..Witness(5))]),
+            ]),
+            return_type: Some(AbiType::Tuple { fields: vec![AbiType::Field, AbiType::Field] }),
+            return_visibility: AbiVisibility::Public,
+            return_witnesses: vec![Witness(1), Witness(5)],
+            oracles: vec![],
+        };
+
+        let expected = BTreeMap::from([
+            (Witness(1), "point.x".to_string()),
+            (Witness(2), "point.y".to_string()),
+            (Witness(3), "arr[0]".to_string()),
+            (Witness(4), "arr[1]".to_string()),
+            (Witness(5), "return.1".to_string()),
+        ]);
+        assert_eq!(abi.witness_names(), expected);
+    }
 }
@@ -11,6 +11,12 @@ use crate::AbiType;
 // the ordering of elements in a map, this creates potential for improper ABI encoding of structs if the fields are
 // deserialized into a different order. To prevent this, we store the fields in an array to create an unambiguous ordering.
 
+#[derive(Serialize, Deserialize)]
+struct EnumVariant {
+    name: String,
+    fields: Vec<AbiType>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct StructField {
     name: String,
@@ -41,6 +47,33 @@ where
     Ok(vecmap(fields_vector, |StructField { name, typ }| (name, typ)))
 }
 
+// Enum variants are similarly serialized as a `Vec<EnumVariant>` as their order determines their tag.
+
+pub(crate) fn serialize_enum_variants<S>(
+    variants: &[(String, Vec<AbiType>)],
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let variants_vector = vecmap(variants, |(name, fields)| EnumVariant {
+        name: name.to_owned(),
+        fields: fields.to_owned(),
+    });
+
+    variants_vector.serialize(s)
+}
+
+pub(crate) fn deserialize_enum_variants<'de, D>(
+    deserializer: D,
+) -> Result<Vec<(String, Vec<AbiType>)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let variants_vector = Vec::<EnumVariant>::deserialize(deserializer)?;
+    Ok(vecmap(variants_vector, |EnumVariant { name, fields }| (name, fields)))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{AbiParameter, AbiType, AbiVisibility, Sign};
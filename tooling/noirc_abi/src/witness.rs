@@ -0,0 +1,577 @@
+//! The binary formats used to store witnesses outside of the compiler.
+//!
+//! A witness file is gzip-compressed and, once decompressed, is laid out as:
+//!
+//! | Bytes   | Contents                                                                   |
+//! | ------- | -------------------------------------------------------------------------- |
+//! | 4       | The magic bytes `NWIT`                                                     |
+//! | 1       | The format version, currently `1`                                          |
+//! | 1       | The number of bytes `n` used to encode each field element                  |
+//! | 4       | The number of entries, as a little-endian `u32`                            |
+//! | 4 + `n` | Each entry: its witness index as a little-endian `u32`, then its value as  |
+//! |         | an `n` byte big-endian field element                                       |
+//!
+//! Entries are sorted by witness index and each index appears at most once.
+//! This allows tools other than nargo to produce witnesses which nargo can read.
+//!
+//! Large witnesses are instead written in chunks by a [`WitnessWriter`] so that neither writing nor
+//! reading them requires a second copy of the whole witness in memory. A chunked witness file is
+//! not compressed as a whole and is laid out as:
+//!
+//! | Bytes | Contents                                                                     |
+//! | ----- | ---------------------------------------------------------------------------- |
+//! | 4     | The magic bytes `NWIT`                                                       |
+//! | 1     | The format version, currently `2`                                            |
+//! | 1     | The number of bytes `n` used to encode each field element                    |
+//! |       | Each chunk: a gzip stream of entries, encoded as in the format above         |
+//! | 28    | For each chunk: its first and last witness indices and its number of entries |
+//! |       | as little-endian `u32`s, then its offset in the file and its compressed      |
+//! |       | length as little-endian `u64`s                                               |
+//! | 4     | The number of chunks, as a little-endian `u32`                               |
+//! | 8     | The offset of the chunk index in the file, as a little-endian `u64`          |
+//!
+//! Entries are sorted by witness index across all chunks. The index at the end of the file allows
+//! a [`WitnessReader`] to read the chunks one at a time, or to look up a single witness.
+//!
+//! Chunking only bounds the memory used to encode and decode the file. The ACVM solves the whole
+//! circuit into a single [`WitnessMap`] and backends prove from one, so the complete witness is
+//! still held in memory while executing and proving.
+
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+
+use acvm::{
+    acir::native_types::{Witness, WitnessMap},
+    FieldElement,
+};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use thiserror::Error;
+
+/// The bytes which every decompressed witness file starts with.
+pub const WITNESS_MAGIC: [u8; 4] = *b"NWIT";
+
+/// The version of the witness format written by [`serialize_witness`].
+pub const WITNESS_FORMAT_VERSION: u8 = 1;
+
+/// The version of the chunked witness format written by [`WitnessWriter`].
+pub const CHUNKED_WITNESS_FORMAT_VERSION: u8 = 2;
+
+/// The number of entries which nargo writes to each chunk of a chunked witness file.
+pub const DEFAULT_WITNESS_CHUNK_SIZE: usize = 1 << 16;
+
+const HEADER_LENGTH: usize = WITNESS_MAGIC.len() + 2 + 4;
+const CHUNKED_HEADER_LENGTH: usize = WITNESS_MAGIC.len() + 2;
+const CHUNK_INDEX_ENTRY_LENGTH: usize = 3 * 4 + 2 * 8;
+const CHUNKED_FOOTER_LENGTH: usize = 4 + 8;
+
+#[derive(Debug, Error)]
+pub enum WitnessFormatError {
+    #[error(transparent)]
+    Compression(#[from] std::io::Error),
+    #[error("Witness data is not in the expected format")]
+    InvalidMagic,
+    #[error("Unsupported witness format version {0}, expected version {WITNESS_FORMAT_VERSION}")]
+    UnsupportedVersion(u8),
+    #[error("Witness values are encoded with {found} bytes but the field requires {expected}")]
+    FieldSizeMismatch { expected: u8, found: u8 },
+    #[error("Witness data ends before all {0} entries could be read")]
+    Truncated(u32),
+    #[error("Witness data contains {0} unexpected trailing bytes")]
+    TrailingBytes(usize),
+    #[error("Witness indices must be strictly increasing but {0:?} follows {1:?}")]
+    UnorderedWitness(Witness, Witness),
+    #[error("Value of witness {0:?} is not a canonical field element")]
+    NonCanonicalValue(Witness),
+    #[error("The chunk index of the witness file is corrupted")]
+    InvalidIndex,
+}
+
+/// Encodes `witness_map` in the witness format described in the [module documentation][self].
+pub fn serialize_witness(witness_map: &WitnessMap) -> Result<Vec<u8>, WitnessFormatError> {
+    let field_size = FieldElement::max_num_bytes() as u8;
+    let entries: Vec<_> = witness_map.clone().into_iter().collect();
+
+    let mut buf = Vec::with_capacity(HEADER_LENGTH + entries.len() * (4 + field_size as usize));
+    buf.extend_from_slice(&WITNESS_MAGIC);
+    buf.push(WITNESS_FORMAT_VERSION);
+    buf.push(field_size);
+    buf.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+    for (witness, value) in entries {
+        buf.extend_from_slice(&witness.witness_index().to_le_bytes());
+        buf.extend(value.to_be_bytes());
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&buf)?;
+    Ok(encoder.finish()?)
+}
+
+/// Decodes a witness written in either of the witness formats described in the
+/// [module documentation][self].
+pub fn deserialize_witness(bytes: &[u8]) -> Result<WitnessMap, WitnessFormatError> {
+    // Chunked witness files start with the uncompressed magic bytes rather than a gzip header.
+    if bytes.starts_with(&WITNESS_MAGIC) {
+        return WitnessReader::new(Cursor::new(bytes))?.read_all();
+    }
+
+    let mut buf = Vec::new();
+    GzDecoder::new(bytes).read_to_end(&mut buf)?;
+
+    if buf.len() < HEADER_LENGTH || buf[..WITNESS_MAGIC.len()] != WITNESS_MAGIC {
+        return Err(WitnessFormatError::InvalidMagic);
+    }
+    let (header, mut body) = buf.split_at(HEADER_LENGTH);
+
+    let version = header[4];
+    if version != WITNESS_FORMAT_VERSION {
+        return Err(WitnessFormatError::UnsupportedVersion(version));
+    }
+    let expected_field_size = FieldElement::max_num_bytes() as u8;
+    let field_size = header[5];
+    if field_size != expected_field_size {
+        return Err(WitnessFormatError::FieldSizeMismatch {
+            expected: expected_field_size,
+            found: field_size,
+        });
+    }
+    let num_entries = u32::from_le_bytes(header[6..10].try_into().unwrap());
+
+    let entry_length = 4 + field_size as usize;
+    let mut witness_map = WitnessMap::new();
+    let mut previous_witness: Option<Witness> = None;
+    for _ in 0..num_entries {
+        if body.len() < entry_length {
+            return Err(WitnessFormatError::Truncated(num_entries));
+        }
+        let (entry, rest) = body.split_at(entry_length);
+        body = rest;
+
+        let witness = Witness(u32::from_le_bytes(entry[..4].try_into().unwrap()));
+        if let Some(previous_witness) = previous_witness {
+            if witness <= previous_witness {
+                return Err(WitnessFormatError::UnorderedWitness(witness, previous_witness));
+            }
+        }
+        previous_witness = Some(witness);
+
+        let value_bytes = &entry[4..];
+        let value = FieldElement::from_be_bytes_reduce(value_bytes);
+        if value.to_be_bytes() != value_bytes {
+            return Err(WitnessFormatError::NonCanonicalValue(witness));
+        }
+        witness_map.insert(witness, value);
+    }
+
+    if !body.is_empty() {
+        return Err(WitnessFormatError::TrailingBytes(body.len()));
+    }
+
+    Ok(witness_map)
+}
+
+/// Writes `witness_map` to `writer` in the chunked witness format, releasing the witness map's memory
+/// as its entries are written.
+pub fn write_witness_chunks<W: Write>(
+    witness_map: WitnessMap,
+    writer: W,
+    chunk_size: usize,
+) -> Result<W, WitnessFormatError> {
+    let mut witness_writer = WitnessWriter::new(writer, chunk_size)?;
+    for (witness, value) in witness_map {
+        witness_writer.write(witness, value)?;
+    }
+    witness_writer.finish()
+}
+
+/// The location of a chunk of entries within a chunked witness file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WitnessChunk {
+    pub first_witness: Witness,
+    pub last_witness: Witness,
+    pub num_entries: u32,
+    offset: u64,
+    length: u64,
+}
+
+/// Writes witness entries in the chunked witness format described in the
+/// [module documentation][self], holding at most one chunk of entries in memory.
+#[derive(Debug)]
+pub struct WitnessWriter<W: Write> {
+    writer: W,
+    chunk_size: usize,
+    chunk: Vec<u8>,
+    chunk_entries: u32,
+    chunk_first_witness: Option<Witness>,
+    previous_witness: Option<Witness>,
+    offset: u64,
+    index: Vec<WitnessChunk>,
+}
+
+impl<W: Write> WitnessWriter<W> {
+    /// Writes the header of a chunked witness file to `writer`, which each chunk of `chunk_size`
+    /// entries is then written to as it fills up.
+    pub fn new(mut writer: W, chunk_size: usize) -> Result<Self, WitnessFormatError> {
+        assert!(chunk_size > 0, "witness chunks must hold at least one entry");
+
+        writer.write_all(&WITNESS_MAGIC)?;
+        writer.write_all(&[CHUNKED_WITNESS_FORMAT_VERSION, FieldElement::max_num_bytes() as u8])?;
+
+        Ok(WitnessWriter {
+            writer,
+            chunk_size,
+            chunk: Vec::new(),
+            chunk_entries: 0,
+            chunk_first_witness: None,
+            previous_witness: None,
+            offset: CHUNKED_HEADER_LENGTH as u64,
+            index: Vec::new(),
+        })
+    }
+
+    /// Adds an entry to the witness. Entries must be written in increasing order of witness index.
+    pub fn write(
+        &mut self,
+        witness: Witness,
+        value: FieldElement,
+    ) -> Result<(), WitnessFormatError> {
+        if let Some(previous_witness) = self.previous_witness {
+            if witness <= previous_witness {
+                return Err(WitnessFormatError::UnorderedWitness(witness, previous_witness));
+            }
+        }
+        self.previous_witness = Some(witness);
+        self.chunk_first_witness.get_or_insert(witness);
+
+        self.chunk.extend_from_slice(&witness.witness_index().to_le_bytes());
+        self.chunk.extend(value.to_be_bytes());
+        self.chunk_entries += 1;
+
+        if self.chunk_entries as usize == self.chunk_size {
+            self.write_chunk()?;
+        }
+        Ok(())
+    }
+
+    /// Writes any remaining entries along with the chunk index, returning the underlying writer.
+    pub fn finish(mut self) -> Result<W, WitnessFormatError> {
+        self.write_chunk()?;
+
+        let index_offset = self.offset;
+        for chunk in &self.index {
+            self.writer.write_all(&chunk.first_witness.witness_index().to_le_bytes())?;
+            self.writer.write_all(&chunk.last_witness.witness_index().to_le_bytes())?;
+            self.writer.write_all(&chunk.num_entries.to_le_bytes())?;
+            self.writer.write_all(&chunk.offset.to_le_bytes())?;
+            self.writer.write_all(&chunk.length.to_le_bytes())?;
+        }
+        self.writer.write_all(&(self.index.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&index_offset.to_le_bytes())?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    fn write_chunk(&mut self) -> Result<(), WitnessFormatError> {
+        let (Some(first_witness), Some(last_witness)) =
+            (self.chunk_first_witness.take(), self.previous_witness)
+        else {
+            return Ok(());
+        };
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&self.chunk)?;
+        let compressed = encoder.finish()?;
+        self.writer.write_all(&compressed)?;
+
+        self.index.push(WitnessChunk {
+            first_witness,
+            last_witness,
+            num_entries: self.chunk_entries,
+            offset: self.offset,
+            length: compressed.len() as u64,
+        });
+        self.offset += compressed.len() as u64;
+        self.chunk.clear();
+        self.chunk_entries = 0;
+        Ok(())
+    }
+}
+
+/// Reads a witness written in the chunked witness format described in the
+/// [module documentation][self], one chunk at a time.
+#[derive(Debug)]
+pub struct WitnessReader<R: Read + Seek> {
+    reader: R,
+    index: Vec<WitnessChunk>,
+}
+
+impl<R: Read + Seek> WitnessReader<R> {
+    /// Reads the header and chunk index of a chunked witness file.
+    pub fn new(mut reader: R) -> Result<Self, WitnessFormatError> {
+        let mut header = [0; CHUNKED_HEADER_LENGTH];
+        reader.read_exact(&mut header).map_err(|_| WitnessFormatError::InvalidMagic)?;
+        if header[..WITNESS_MAGIC.len()] != WITNESS_MAGIC {
+            return Err(WitnessFormatError::InvalidMagic);
+        }
+        let version = header[4];
+        if version != CHUNKED_WITNESS_FORMAT_VERSION {
+            return Err(WitnessFormatError::UnsupportedVersion(version));
+        }
+        let expected_field_size = FieldElement::max_num_bytes() as u8;
+        let field_size = header[5];
+        if field_size != expected_field_size {
+            return Err(WitnessFormatError::FieldSizeMismatch {
+                expected: expected_field_size,
+                found: field_size,
+            });
+        }
+
+        let file_length = reader.seek(SeekFrom::End(0))?;
+        if file_length < (CHUNKED_HEADER_LENGTH + CHUNKED_FOOTER_LENGTH) as u64 {
+            return Err(WitnessFormatError::InvalidIndex);
+        }
+        let mut footer = [0; CHUNKED_FOOTER_LENGTH];
+        reader.seek(SeekFrom::End(-(CHUNKED_FOOTER_LENGTH as i64)))?;
+        reader.read_exact(&mut footer)?;
+        let num_chunks = u32::from_le_bytes(footer[..4].try_into().unwrap());
+        let index_offset = u64::from_le_bytes(footer[4..].try_into().unwrap());
+
+        let index_length = num_chunks as u64 * CHUNK_INDEX_ENTRY_LENGTH as u64;
+        if index_offset.checked_add(index_length + CHUNKED_FOOTER_LENGTH as u64)
+            != Some(file_length)
+        {
+            return Err(WitnessFormatError::InvalidIndex);
+        }
+
+        let mut index_bytes = vec![0; index_length as usize];
+        reader.seek(SeekFrom::Start(index_offset))?;
+        reader.read_exact(&mut index_bytes)?;
+
+        let mut index: Vec<WitnessChunk> = Vec::with_capacity(num_chunks as usize);
+        for entry in index_bytes.chunks_exact(CHUNK_INDEX_ENTRY_LENGTH) {
+            let chunk = WitnessChunk {
+                first_witness: Witness(u32::from_le_bytes(entry[0..4].try_into().unwrap())),
+                last_witness: Witness(u32::from_le_bytes(entry[4..8].try_into().unwrap())),
+                num_entries: u32::from_le_bytes(entry[8..12].try_into().unwrap()),
+                offset: u64::from_le_bytes(entry[12..20].try_into().unwrap()),
+                length: u64::from_le_bytes(entry[20..28].try_into().unwrap()),
+            };
+            let follows_previous =
+                index.last().map_or(chunk.offset == CHUNKED_HEADER_LENGTH as u64, |previous| {
+                    previous.last_witness < chunk.first_witness
+                        && previous.offset + previous.length == chunk.offset
+                });
+            if chunk.num_entries == 0
+                || chunk.first_witness > chunk.last_witness
+                || !follows_previous
+            {
+                return Err(WitnessFormatError::InvalidIndex);
+            }
+            index.push(chunk);
+        }
+        let chunks_end =
+            index.last().map_or(CHUNKED_HEADER_LENGTH as u64, |chunk| chunk.offset + chunk.length);
+        if chunks_end != index_offset {
+            return Err(WitnessFormatError::InvalidIndex);
+        }
+
+        Ok(WitnessReader { reader, index })
+    }
+
+    /// The chunks of the witness, sorted by witness index.
+    pub fn chunks(&self) -> &[WitnessChunk] {
+        &self.index
+    }
+
+    /// Reads the entries of the chunk at position `chunk_index` in [`WitnessReader::chunks`].
+    pub fn read_chunk(
+        &mut self,
+        chunk_index: usize,
+    ) -> Result<Vec<(Witness, FieldElement)>, WitnessFormatError> {
+        let chunk = self.index[chunk_index];
+        self.reader.seek(SeekFrom::Start(chunk.offset))?;
+
+        let mut buf = Vec::new();
+        GzDecoder::new((&mut self.reader).take(chunk.length)).read_to_end(&mut buf)?;
+
+        let entry_length = 4 + FieldElement::max_num_bytes() as usize;
+        if buf.len() < chunk.num_entries as usize * entry_length {
+            return Err(WitnessFormatError::Truncated(chunk.num_entries));
+        }
+        if buf.len() > chunk.num_entries as usize * entry_length {
+            return Err(WitnessFormatError::TrailingBytes(
+                buf.len() - chunk.num_entries as usize * entry_length,
+            ));
+        }
+
+        let mut entries = Vec::with_capacity(chunk.num_entries as usize);
+        let mut previous_witness: Option<Witness> = None;
+        for entry in buf.chunks_exact(entry_length) {
+            let witness = Witness(u32::from_le_bytes(entry[..4].try_into().unwrap()));
+            if let Some(previous_witness) = previous_witness {
+                if witness <= previous_witness {
+                    return Err(WitnessFormatError::UnorderedWitness(witness, previous_witness));
+                }
+            }
+            previous_witness = Some(witness);
+
+            let value_bytes = &entry[4..];
+            let value = FieldElement::from_be_bytes_reduce(value_bytes);
+            if value.to_be_bytes() != value_bytes {
+                return Err(WitnessFormatError::NonCanonicalValue(witness));
+            }
+            entries.push((witness, value));
+        }
+
+        let first_witness = entries.first().map(|(witness, _)| *witness);
+        if first_witness != Some(chunk.first_witness)
+            || previous_witness != Some(chunk.last_witness)
+        {
+            return Err(WitnessFormatError::InvalidIndex);
+        }
+
+        Ok(entries)
+    }
+
+    /// Returns the value of `witness`, only reading the chunk which contains it.
+    pub fn get(&mut self, witness: Witness) -> Result<Option<FieldElement>, WitnessFormatError> {
+        let chunk_index = self.index.partition_point(|chunk| chunk.last_witness < witness);
+        match self.index.get(chunk_index) {
+            Some(chunk) if chunk.first_witness <= witness => {
+                let entries = self.read_chunk(chunk_index)?;
+                Ok(entries
+                    .binary_search_by_key(&witness, |(witness, _)| *witness)
+                    .ok()
+                    .map(|position| entries[position].1))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Reads every chunk of the witness into a single [`WitnessMap`].
+    pub fn read_all(&mut self) -> Result<WitnessMap, WitnessFormatError> {
+        let mut witness_map = WitnessMap::new();
+        for chunk_index in 0..self.index.len() {
+            for (witness, value) in self.read_chunk(chunk_index)? {
+                witness_map.insert(witness, value);
+            }
+        }
+        Ok(witness_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::native_types::{Witness, WitnessMap},
+        FieldElement,
+    };
+    use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+    use std::collections::BTreeMap;
+    use std::io::{Cursor, Read, Write};
+
+    use super::{
+        deserialize_witness, serialize_witness, write_witness_chunks, WitnessFormatError,
+        WitnessReader, WitnessWriter, WITNESS_MAGIC,
+    };
+
+    fn compress(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn witness_round_trips() {
+        let witness_map = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::from(10_u128)),
+            (Witness(3), -FieldElement::one()),
+            (Witness(100), FieldElement::zero()),
+        ]));
+
+        let bytes = serialize_witness(&witness_map).unwrap();
+        assert_eq!(deserialize_witness(&bytes).unwrap(), witness_map);
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let mut data = WITNESS_MAGIC.to_vec();
+        data.extend([2, 32, 0, 0, 0, 0]);
+
+        assert!(matches!(
+            deserialize_witness(&compress(&data)),
+            Err(WitnessFormatError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let witness_map = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+        let mut data = Vec::new();
+        GzDecoder::new(serialize_witness(&witness_map).unwrap().as_slice())
+            .read_to_end(&mut data)
+            .unwrap();
+        data.pop();
+
+        assert!(matches!(
+            deserialize_witness(&compress(&data)),
+            Err(WitnessFormatError::Truncated(1))
+        ));
+    }
+
+    fn witness_of_size(size: u32) -> WitnessMap {
+        WitnessMap::from(BTreeMap::from_iter(
+            (0..size).map(|index| (Witness(2 * index + 1), FieldElement::from(index as u128))),
+        ))
+    }
+
+    #[test]
+    fn chunked_witness_round_trips() {
+        let witness_map = witness_of_size(10);
+        let bytes = write_witness_chunks(witness_map.clone(), Vec::new(), 3).unwrap();
+
+        let mut reader = WitnessReader::new(Cursor::new(bytes.as_slice())).unwrap();
+        assert_eq!(reader.chunks().len(), 4);
+        assert_eq!(reader.read_all().unwrap(), witness_map);
+        assert_eq!(deserialize_witness(&bytes).unwrap(), witness_map);
+    }
+
+    #[test]
+    fn chunked_witness_looks_up_single_witnesses() {
+        let bytes = write_witness_chunks(witness_of_size(10), Vec::new(), 4).unwrap();
+        let mut reader = WitnessReader::new(Cursor::new(bytes)).unwrap();
+
+        assert_eq!(reader.get(Witness(13)).unwrap(), Some(FieldElement::from(6_u128)));
+        assert_eq!(reader.get(Witness(14)).unwrap(), None);
+        assert_eq!(reader.get(Witness(100)).unwrap(), None);
+    }
+
+    #[test]
+    fn empty_chunked_witness_round_trips() {
+        let bytes = write_witness_chunks(WitnessMap::new(), Vec::new(), 3).unwrap();
+        assert_eq!(deserialize_witness(&bytes).unwrap(), WitnessMap::new());
+    }
+
+    #[test]
+    fn chunked_writer_rejects_unordered_witnesses() {
+        let mut writer = WitnessWriter::new(Vec::new(), 3).unwrap();
+        writer.write(Witness(2), FieldElement::one()).unwrap();
+
+        assert!(matches!(
+            writer.write(Witness(1), FieldElement::one()),
+            Err(WitnessFormatError::UnorderedWitness(Witness(1), Witness(2)))
+        ));
+    }
+
+    #[test]
+    fn rejects_corrupted_chunk_index() {
+        let mut bytes = write_witness_chunks(witness_of_size(10), Vec::new(), 3).unwrap();
+        let length = bytes.len();
+        // Point the index at the start of the chunks.
+        bytes[length - 8..].copy_from_slice(&6_u64.to_le_bytes());
+
+        assert!(matches!(
+            WitnessReader::new(Cursor::new(bytes)),
+            Err(WitnessFormatError::InvalidIndex)
+        ));
+    }
+}
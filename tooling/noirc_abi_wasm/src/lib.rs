@@ -47,7 +47,7 @@ extern "C" {
 
 #[wasm_bindgen(typescript_custom_section)]
 const ABI: &'static str = r#"
-export type Visibility = "public" | "private";
+export type Visibility = "public" | "private" | "call_data" | "return_data";
 export type Sign = "unsigned" | "signed";
 export type AbiType = 
     { kind: "field" } |
@@ -56,7 +56,8 @@ export type AbiType =
     { kind: "integer", sign: Sign, width: number } |
     { kind: "array", length: number, type: AbiType } |
     { kind: "tuple", fields: AbiType[] } |
-    { kind: "struct", path: string, fields: [string, AbiType][] };
+    { kind: "struct", path: string, fields: [string, AbiType][] } |
+    { kind: "enum", path: string, variants: { name: string, fields: AbiType[] }[] };
 
 export type AbiParameter = {
     name: string,
@@ -64,11 +65,19 @@ export type AbiParameter = {
     visibility: Visibility,
 };
     
+export type AbiOracle = {
+    name: string,
+    parameters: { name: string, type: AbiType }[],
+    return_type: AbiType | null,
+};
+
 export type Abi = {
     parameters: AbiParameter[],
     param_witnesses: Record<string, {start: number, end: number}[]>,
     return_type: AbiType | null,
+    return_visibility?: Visibility,
     return_witnesses: number[],
+    oracles?: AbiOracle[],
 }
 "#;
 
@@ -0,0 +1,51 @@
+#ifndef NOIRC_FFI_H
+#define NOIRC_FFI_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum NoirStatus {
+    NOIR_STATUS_OK = 0,
+    NOIR_STATUS_INVALID_ARGUMENT = 1,
+    NOIR_STATUS_INVALID_MANIFEST = 2,
+    NOIR_STATUS_COMPILATION_FAILED = 3,
+    NOIR_STATUS_INVALID_ARTIFACT = 4,
+    NOIR_STATUS_INVALID_INPUTS = 5,
+    NOIR_STATUS_EXECUTION_FAILED = 6,
+    NOIR_STATUS_INTERNAL_ERROR = 7,
+} NoirStatus;
+
+typedef struct NoirBuffer {
+    uint8_t *data;
+    size_t len;
+} NoirBuffer;
+
+/* `output` is only set if `status` is `NOIR_STATUS_OK`, otherwise `error` describes the failure.
+ * Every result must be released with `noir_result_free`. */
+typedef struct NoirResult {
+    NoirStatus status;
+    NoirBuffer output;
+    char *error;
+} NoirResult;
+
+/* Returns the version of the compiler. The string is owned by the library. */
+const char *noir_version(void);
+
+/* Compiles the binary package in `program_dir`, returning its JSON program artifact. */
+NoirResult noir_compile(const char *program_dir);
+
+/* Solves the witness of a program given its JSON artifact and its inputs in the format of a
+ * `Prover.toml` file, returning the witness in the format written by `nargo execute`. */
+NoirResult noir_execute(const uint8_t *artifact, size_t artifact_len, const char *inputs);
+
+void noir_result_free(NoirResult result);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,276 @@
+#![warn(unused_crate_dependencies, unused_extern_crates)]
+#![warn(unreachable_pub)]
+#![warn(clippy::semicolon_if_nothing_returned)]
+
+//! C bindings which allow hosts written in other languages to compile and execute Noir programs.
+//!
+//! Every function returns a [`NoirResult`] which owns its output and error message. These must be
+//! released by passing the result to [`noir_result_free`]. The matching C declarations can be found in
+//! `include/noirc_ffi.h`.
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, UnwindSafe};
+use std::path::Path;
+
+use acvm::Language;
+use fm::FileManager;
+use nargo::artifacts::program::{PreprocessedProgram, ABI_VERSION, BYTECODE_VERSION};
+use nargo::ops::{compile_program, execute_circuit, AcvmSolver, DefaultForeignCallExecutor};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_abi::input_parser::Format;
+use noirc_abi::witness::serialize_witness;
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::FileDiagnostic;
+
+const BACKEND_IDENTIFIER: &str = "acvm-backend-barretenberg";
+
+/// The outcome of a call into the library.
+///
+/// The values of existing variants will not change so that hosts can match on them.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoirStatus {
+    Ok = 0,
+    /// A pointer was null or a string was not valid UTF-8.
+    InvalidArgument = 1,
+    /// The `Nargo.toml` of the program could not be read.
+    InvalidManifest = 2,
+    /// The program failed to compile. The error message contains the diagnostics.
+    CompilationFailed = 3,
+    /// The program artifact could not be read.
+    InvalidArtifact = 4,
+    /// The inputs do not match the program's ABI.
+    InvalidInputs = 5,
+    /// The program failed while solving its witness.
+    ExecutionFailed = 6,
+    /// The library encountered an internal error.
+    InternalError = 7,
+}
+
+/// A byte buffer allocated by the library.
+#[repr(C)]
+#[derive(Debug)]
+pub struct NoirBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl NoirBuffer {
+    fn empty() -> Self {
+        NoirBuffer { data: std::ptr::null_mut(), len: 0 }
+    }
+
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let bytes = Box::leak(bytes.into_boxed_slice());
+        NoirBuffer { data: bytes.as_mut_ptr(), len: bytes.len() }
+    }
+}
+
+/// The result of a call into the library.
+///
+/// `output` is only set if `status` is [`NoirStatus::Ok`], otherwise `error` is a nul-terminated
+/// message describing the failure.
+#[repr(C)]
+#[derive(Debug)]
+pub struct NoirResult {
+    pub status: NoirStatus,
+    pub output: NoirBuffer,
+    pub error: *mut c_char,
+}
+
+struct FfiError {
+    status: NoirStatus,
+    message: String,
+}
+
+impl FfiError {
+    fn new(status: NoirStatus, message: impl ToString) -> Self {
+        FfiError { status, message: message.to_string() }
+    }
+}
+
+impl From<Result<Vec<u8>, FfiError>> for NoirResult {
+    fn from(result: Result<Vec<u8>, FfiError>) -> Self {
+        match result {
+            Ok(output) => NoirResult {
+                status: NoirStatus::Ok,
+                output: NoirBuffer::from_vec(output),
+                error: std::ptr::null_mut(),
+            },
+            Err(FfiError { status, message }) => {
+                // Error messages may contain nul bytes from user source code, which C strings can't.
+                let message = CString::new(message.replace('\0', ""))
+                    .expect("nul bytes should have been removed");
+                NoirResult { status, output: NoirBuffer::empty(), error: message.into_raw() }
+            }
+        }
+    }
+}
+
+/// Runs `f`, converting any panic into an error so that it doesn't unwind into the host.
+fn run(f: impl FnOnce() -> Result<Vec<u8>, FfiError> + UnwindSafe) -> NoirResult {
+    let result = catch_unwind(f).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(FfiError::new(NoirStatus::InternalError, message))
+    });
+    result.into()
+}
+
+/// # Safety
+///
+/// `ptr` must be null or point to a nul-terminated string.
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::new(NoirStatus::InvalidArgument, format!("`{name}` is null")));
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|_| {
+        FfiError::new(NoirStatus::InvalidArgument, format!("`{name}` is not valid UTF-8"))
+    })
+}
+
+/// Returns the version of the compiler as a nul-terminated string owned by the library.
+#[no_mangle]
+pub extern "C" fn noir_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Compiles the binary package in `program_dir`, which must contain a `Nargo.toml`.
+///
+/// On success the output is the JSON program artifact, as written to `target` by `nargo compile`.
+///
+/// # Safety
+///
+/// `program_dir` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn noir_compile(program_dir: *const c_char) -> NoirResult {
+    let program_dir = match read_str(program_dir, "program_dir") {
+        Ok(program_dir) => program_dir,
+        Err(error) => return Err(error).into(),
+    };
+    run(|| compile(Path::new(program_dir)))
+}
+
+/// Solves the witness of a program given its JSON artifact and its inputs in the format of a
+/// `Prover.toml` file.
+///
+/// On success the output is the solved witness in the format written by `nargo execute`.
+///
+/// # Safety
+///
+/// `artifact` must point to `artifact_len` readable bytes and `inputs` must be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn noir_execute(
+    artifact: *const u8,
+    artifact_len: usize,
+    inputs: *const c_char,
+) -> NoirResult {
+    if artifact.is_null() {
+        return Err(FfiError::new(NoirStatus::InvalidArgument, "`artifact` is null")).into();
+    }
+    let artifact = std::slice::from_raw_parts(artifact, artifact_len);
+    let inputs = match read_str(inputs, "inputs") {
+        Ok(inputs) => inputs,
+        Err(error) => return Err(error).into(),
+    };
+    run(|| execute(artifact, inputs))
+}
+
+/// Releases the output and error message of `result`.
+///
+/// # Safety
+///
+/// `result` must have been returned by this library and must not have been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn noir_result_free(result: NoirResult) {
+    if !result.output.data.is_null() {
+        let output = std::slice::from_raw_parts_mut(result.output.data, result.output.len);
+        drop(Box::from_raw(output as *mut [u8]));
+    }
+    if !result.error.is_null() {
+        drop(CString::from_raw(result.error));
+    }
+}
+
+fn compile(program_dir: &Path) -> Result<Vec<u8>, FfiError> {
+    let toml_path = get_package_manifest(program_dir)
+        .map_err(|err| FfiError::new(NoirStatus::InvalidManifest, err))?;
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        PackageSelection::DefaultOrAll,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )
+    .map_err(|err| FfiError::new(NoirStatus::InvalidManifest, err))?;
+
+    let Some(package) = workspace.into_iter().find(|package| package.is_binary()) else {
+        return Err(FfiError::new(
+            NoirStatus::InvalidManifest,
+            "the workspace does not contain a binary package",
+        ));
+    };
+
+    let np_language = Language::PLONKCSat { width: 3 };
+    #[allow(deprecated)]
+    let is_opcode_supported = acvm::pwg::default_is_opcode_supported(np_language);
+    let (file_manager, result) = compile_program(
+        &workspace,
+        package,
+        &CompileOptions::default(),
+        np_language,
+        &is_opcode_supported,
+    );
+    let (program, _) = result.map_err(|errors| {
+        FfiError::new(NoirStatus::CompilationFailed, format_diagnostics(&errors, &file_manager))
+    })?;
+
+    let preprocessed_program = PreprocessedProgram {
+        hash: program.hash,
+        backend: String::from(BACKEND_IDENTIFIER),
+        abi_version: ABI_VERSION,
+        abi: program.abi,
+        noir_version: program.noir_version,
+        bytecode_version: BYTECODE_VERSION,
+        bytecode: program.circuit,
+    };
+    serde_json::to_vec(&preprocessed_program)
+        .map_err(|err| FfiError::new(NoirStatus::InternalError, err))
+}
+
+fn execute(artifact: &[u8], inputs: &str) -> Result<Vec<u8>, FfiError> {
+    let program = PreprocessedProgram::from_json(artifact)
+        .map_err(|err| FfiError::new(NoirStatus::InvalidArtifact, err))?;
+
+    let inputs_map = Format::Toml
+        .parse(inputs, &program.abi)
+        .map_err(|err| FfiError::new(NoirStatus::InvalidInputs, err))?;
+    let initial_witness = program
+        .abi
+        .encode(&inputs_map, None)
+        .map_err(|err| FfiError::new(NoirStatus::InvalidInputs, err))?;
+
+    #[allow(deprecated)]
+    let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
+    let solved_witness = execute_circuit(
+        &program.bytecode,
+        initial_witness,
+        &AcvmSolver::new(&blackbox_solver),
+        &mut DefaultForeignCallExecutor::new(false),
+    )
+    .map_err(|err| FfiError::new(NoirStatus::ExecutionFailed, err))?;
+
+    serialize_witness(&solved_witness).map_err(|err| FfiError::new(NoirStatus::InternalError, err))
+}
+
+fn format_diagnostics(diagnostics: &[FileDiagnostic], file_manager: &FileManager) -> String {
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let path = file_manager.path(diagnostic.file_id);
+            format!("{}: {}", path.display(), diagnostic.diagnostic.message)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}